@@ -6,11 +6,15 @@ use mongodb::{
     options::{FindOptions, UpdateOptions},
     Collection,
 };
+use rusqlite::{params, Connection, Row};
+use tokio_postgres::{GenericClient, Row as PostgresRow};
 
 use crate::{
     convert_tools::ConvertTools,
     database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
-    datatypes::{FinanceAccount, FinanceAccountType},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::{AccountCategory, FinanceAccount, FinanceAccountType},
 };
 
 #[async_trait(?Send)]
@@ -68,7 +72,8 @@ impl DBFinanceConfigFunctions for DbHandlerMongoDB {
         debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
         let projection = doc! {"finance_account_type_id":<i32>::from(1),
         "title":<i32>::from(1),
-        "description":<i32>::from(1),};
+        "description":<i32>::from(1),
+        "account_category":<i32>::from(1),};
         let options = FindOptions::builder().projection(projection).build();
 
         let query_execute_result = accounting_type_collection.find(filter, options).await;
@@ -101,11 +106,27 @@ impl DBFinanceConfigFunctions for DbHandlerMongoDB {
             if stored_description.is_err() {
                 return Err(stored_description.unwrap_err().to_string());
             }
+            // account types stored before categories were introduced have no category
+            let mut stored_category = AccountCategory::DEFAULT_OF_STORED_TYPES;
+            if inner_doc.contains_key("account_category") && !inner_doc.is_null("account_category")
+            {
+                let stored_category_value = inner_doc.get_i32("account_category");
+                if stored_category_value.is_err() {
+                    return Err(stored_category_value.unwrap_err().to_string());
+                }
+                let stored_category_parse =
+                    AccountCategory::get_from_int(stored_category_value.unwrap());
+                if stored_category_parse.is_err() {
+                    return Err(stored_category_parse.unwrap_err());
+                }
+                stored_category = stored_category_parse.unwrap();
+            }
 
             let accounting_type = FinanceAccountType {
                 id: some_uuid_parse_result.unwrap(),
                 title: stored_title.unwrap().into(),
                 description: stored_description.unwrap().into(),
+                account_category: stored_category,
             };
 
             result_list.push(accounting_type);
@@ -136,12 +157,14 @@ impl DBFinanceConfigFunctions for DbHandlerMongoDB {
         let accounting_type_collection: Collection<Document> =
             db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_ACCOUNTING_TYPES);
 
-        let filter = doc! {"finance_account_type_id":&finance_account_type.id};
+        // the user is part of the filter, an id of another user must not take over its account type
+        let filter = doc! {"finance_account_type_id":&finance_account_type.id, "user_id": &user_id};
         let inner_doc = doc! {
             "finance_account_type_id":&finance_account_type.id,
             "user_id": &user_id,
             "title": &finance_account_type.title,
             "description": &finance_account_type.description,
+            "account_category": finance_account_type.account_category.to_int(),
         };
         let upsert_doc = doc! {"$set": inner_doc  };
         let opts = UpdateOptions::builder().upsert(true).build();
@@ -200,7 +223,8 @@ impl DBFinanceConfigFunctions for DbHandlerMongoDB {
         let projection = doc! {"finance_account_id":<i32>::from(1),
         "finance_account_type_id":<i32>::from(1),
         "title":<i32>::from(1),
-        "description":<i32>::from(1),};
+        "description":<i32>::from(1),
+        "account_number":<i32>::from(1),};
         let options = FindOptions::builder().projection(projection).build();
 
         let query_execute_result = account_collection.find(filter, options).await;
@@ -238,12 +262,27 @@ impl DBFinanceConfigFunctions for DbHandlerMongoDB {
             if stored_description.is_err() {
                 return Err(stored_description.unwrap_err().to_string());
             }
+            // accounts stored before account numbers were introduced have no number
+            let mut stored_account_number = None;
+            if inner_doc.contains_key("account_number") && !inner_doc.is_null("account_number") {
+                let stored_account_number_value = inner_doc.get_i64("account_number");
+                if stored_account_number_value.is_err() {
+                    return Err(stored_account_number_value.unwrap_err().to_string());
+                }
+                let stored_account_number_convert =
+                    u32::try_from(stored_account_number_value.unwrap());
+                if stored_account_number_convert.is_err() {
+                    return Err(stored_account_number_convert.unwrap_err().to_string());
+                }
+                stored_account_number = Some(stored_account_number_convert.unwrap());
+            }
 
             let accounting_type = FinanceAccount {
                 id: stored_account_id.unwrap(),
                 finance_account_type_id: stored_account_type_id.unwrap(),
                 title: stored_title.unwrap().into(),
                 description: stored_description.unwrap().into(),
+                account_number: stored_account_number,
             };
 
             result_list.push(accounting_type);
@@ -274,13 +313,15 @@ impl DBFinanceConfigFunctions for DbHandlerMongoDB {
         let account_collection: Collection<Document> =
             db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_ACCOUNTS);
 
-        let filter = doc! {"finance_account_id":&finance_account.id};
+        // the user is part of the filter, an id of another user must not take over its account
+        let filter = doc! {"finance_account_id":&finance_account.id, "user_id": &user_id};
         let inner_doc = doc! {
             "finance_account_id":&finance_account.id,
             "finance_account_type_id":&finance_account.finance_account_type_id,
             "user_id": &user_id,
             "title": &finance_account.title,
             "description": &finance_account.description,
+            "account_number": finance_account.account_number.map(|elem| elem as i64),
         };
         let upsert_doc = doc! {"$set": inner_doc  };
         let opts = UpdateOptions::builder().upsert(true).build();
@@ -307,3 +348,472 @@ impl DBFinanceConfigFunctions for DbHandlerMongoDB {
         Ok(())
     }
 }
+
+impl DbHandlerSqlite {
+    /// inserts or updates the account type of the user, also used inside a transaction
+    pub(crate) fn upsert_finance_account_type_row(
+        connection: &Connection,
+        user_id: &Uuid,
+        finance_account_type: &FinanceAccountType,
+    ) -> Result<usize, rusqlite::Error> {
+        return connection.execute(
+            "INSERT INTO FinanceAccountTypes (finance_account_type_id, user_id, title, description, account_category)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (user_id, finance_account_type_id) DO UPDATE SET title = excluded.title,
+            description = excluded.description, account_category = excluded.account_category",
+            params![
+                finance_account_type.id.to_string(),
+                user_id.to_string(),
+                &finance_account_type.title,
+                &finance_account_type.description,
+                finance_account_type.account_category.to_int(),
+            ],
+        );
+    }
+
+    /// inserts or updates the account of the user, also used inside a transaction
+    pub(crate) fn upsert_finance_account_row(
+        connection: &Connection,
+        user_id: &Uuid,
+        finance_account: &FinanceAccount,
+    ) -> Result<usize, rusqlite::Error> {
+        return connection.execute(
+            "INSERT INTO FinanceAccounts (finance_account_id, user_id, finance_account_type_id, title, description, account_number)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6)
+            ON CONFLICT (user_id, finance_account_id) DO UPDATE SET finance_account_type_id = excluded.finance_account_type_id,
+            title = excluded.title, description = excluded.description, account_number = excluded.account_number",
+            params![
+                finance_account.id.to_string(),
+                user_id.to_string(),
+                finance_account.finance_account_type_id.to_string(),
+                &finance_account.title,
+                &finance_account.description,
+                finance_account.account_number,
+            ],
+        );
+    }
+
+    fn read_finance_account_type_row(row: &Row) -> Result<FinanceAccountType, String> {
+        let some_uuid_parse_result =
+            DbHandlerSqlite::get_uuid_from_row(row, "finance_account_type_id");
+        if some_uuid_parse_result.is_err() {
+            return Err(some_uuid_parse_result.unwrap_err());
+        }
+        let stored_title = row.get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+        let stored_description = row.get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(stored_description.unwrap_err().to_string());
+        }
+        let stored_category_value = row.get::<_, i32>("account_category");
+        if stored_category_value.is_err() {
+            return Err(stored_category_value.unwrap_err().to_string());
+        }
+        let stored_category = AccountCategory::get_from_int(stored_category_value.unwrap());
+        if stored_category.is_err() {
+            return Err(stored_category.unwrap_err());
+        }
+
+        return Ok(FinanceAccountType {
+            id: some_uuid_parse_result.unwrap(),
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            account_category: stored_category.unwrap(),
+        });
+    }
+
+    fn read_finance_account_row(row: &Row) -> Result<FinanceAccount, String> {
+        let stored_account_id = DbHandlerSqlite::get_uuid_from_row(row, "finance_account_id");
+        if stored_account_id.is_err() {
+            return Err(stored_account_id.unwrap_err());
+        }
+        let stored_account_type_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "finance_account_type_id");
+        if stored_account_type_id.is_err() {
+            return Err(stored_account_type_id.unwrap_err());
+        }
+        let stored_title = row.get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+        let stored_description = row.get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(stored_description.unwrap_err().to_string());
+        }
+        let stored_account_number = row.get::<_, Option<u32>>("account_number");
+        if stored_account_number.is_err() {
+            return Err(stored_account_number.unwrap_err().to_string());
+        }
+
+        return Ok(FinanceAccount {
+            id: stored_account_id.unwrap(),
+            finance_account_type_id: stored_account_type_id.unwrap(),
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            account_number: stored_account_number.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceConfigFunctions for DbHandlerSqlite {
+    async fn finance_account_type_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceAccountType>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceAccountTypes WHERE user_id = ?1 ORDER BY rowid",
+            [user_id.to_string()],
+            DbHandlerSqlite::read_finance_account_type_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} finance account types",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_account_type_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type: &FinanceAccountType,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result = DbHandlerSqlite::upsert_finance_account_type_row(
+            &connection,
+            user_id,
+            finance_account_type,
+        );
+        if upsert_result.is_err() {
+            let upsert_err = upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","upserted finance accpunt type for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_account_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        limit_account_ids: Option<&Vec<Uuid>>,
+    ) -> Result<Vec<FinanceAccount>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceAccounts WHERE user_id = ?1 ORDER BY rowid",
+            [user_id.to_string()],
+            DbHandlerSqlite::read_finance_account_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let mut result_list = query_result.unwrap();
+        if limit_account_ids.is_some() {
+            let limit_ids = limit_account_ids.unwrap();
+            result_list.retain(|elem| limit_ids.contains(&elem.id));
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} finance accounts",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_account_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account: &FinanceAccount,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result =
+            DbHandlerSqlite::upsert_finance_account_row(&connection, user_id, finance_account);
+        if upsert_result.is_err() {
+            let upsert_err = upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","upserted finance account for user id {}",&user_id);
+
+        Ok(())
+    }
+}
+
+impl DbHandlerPostgres {
+    /// inserts or updates the account type of the user, also used inside a transaction
+    pub(crate) async fn upsert_finance_account_type_row(
+        client: &impl GenericClient,
+        user_id: &Uuid,
+        finance_account_type: &FinanceAccountType,
+    ) -> Result<u64, tokio_postgres::Error> {
+        return client
+            .execute(
+                "INSERT INTO FinanceAccountTypes (finance_account_type_id, user_id, title, description, account_category)
+                VALUES ($1, $2, $3, $4, $5)
+                ON CONFLICT (user_id, finance_account_type_id) DO UPDATE SET title = excluded.title,
+                description = excluded.description, account_category = excluded.account_category",
+                &[
+                    &finance_account_type.id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &finance_account_type.title,
+                    &finance_account_type.description,
+                    &finance_account_type.account_category.to_int(),
+                ],
+            )
+            .await;
+    }
+
+    /// inserts or updates the account of the user, also used inside a transaction
+    pub(crate) async fn upsert_finance_account_row(
+        client: &impl GenericClient,
+        user_id: &Uuid,
+        finance_account: &FinanceAccount,
+    ) -> Result<u64, tokio_postgres::Error> {
+        return client
+            .execute(
+                "INSERT INTO FinanceAccounts (finance_account_id, user_id, finance_account_type_id, title, description, account_number)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                ON CONFLICT (user_id, finance_account_id) DO UPDATE SET finance_account_type_id = excluded.finance_account_type_id,
+                title = excluded.title, description = excluded.description, account_number = excluded.account_number",
+                &[
+                    &finance_account.id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &finance_account.finance_account_type_id.to_uuid_1(),
+                    &finance_account.title,
+                    &finance_account.description,
+                    &finance_account.account_number.map(|elem| elem as i64),
+                ],
+            )
+            .await;
+    }
+
+    fn read_finance_account_type_row(row: &PostgresRow) -> Result<FinanceAccountType, String> {
+        let some_uuid_parse_result =
+            DbHandlerPostgres::get_uuid_from_row(row, "finance_account_type_id");
+        if some_uuid_parse_result.is_err() {
+            return Err(some_uuid_parse_result.unwrap_err());
+        }
+        let stored_title = row.try_get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+        let stored_description = row.try_get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_description.unwrap_err(),
+            ));
+        }
+        let stored_category_value = row.try_get::<_, i32>("account_category");
+        if stored_category_value.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_category_value.unwrap_err(),
+            ));
+        }
+        let stored_category = AccountCategory::get_from_int(stored_category_value.unwrap());
+        if stored_category.is_err() {
+            return Err(stored_category.unwrap_err());
+        }
+
+        return Ok(FinanceAccountType {
+            id: some_uuid_parse_result.unwrap(),
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            account_category: stored_category.unwrap(),
+        });
+    }
+
+    fn read_finance_account_row(row: &PostgresRow) -> Result<FinanceAccount, String> {
+        let stored_account_id = DbHandlerPostgres::get_uuid_from_row(row, "finance_account_id");
+        if stored_account_id.is_err() {
+            return Err(stored_account_id.unwrap_err());
+        }
+        let stored_account_type_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "finance_account_type_id");
+        if stored_account_type_id.is_err() {
+            return Err(stored_account_type_id.unwrap_err());
+        }
+        let stored_title = row.try_get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+        let stored_description = row.try_get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_description.unwrap_err(),
+            ));
+        }
+        let stored_account_number = row.try_get::<_, Option<i64>>("account_number");
+        if stored_account_number.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_account_number.unwrap_err(),
+            ));
+        }
+
+        return Ok(FinanceAccount {
+            id: stored_account_id.unwrap(),
+            finance_account_type_id: stored_account_type_id.unwrap(),
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            account_number: stored_account_number.unwrap().map(|elem| elem as u32),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceConfigFunctions for DbHandlerPostgres {
+    async fn finance_account_type_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceAccountType>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceAccountTypes WHERE user_id = $1 ORDER BY insert_order",
+            &[&user_id.to_uuid_1()],
+            DbHandlerPostgres::read_finance_account_type_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} finance account types",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_account_type_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type: &FinanceAccountType,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result = DbHandlerPostgres::upsert_finance_account_type_row(
+            &client,
+            user_id,
+            finance_account_type,
+        )
+        .await;
+        if upsert_result.is_err() {
+            let upsert_err = DbHandlerPostgres::format_error(&upsert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","upserted finance accpunt type for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_account_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        limit_account_ids: Option<&Vec<Uuid>>,
+    ) -> Result<Vec<FinanceAccount>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceAccounts WHERE user_id = $1 ORDER BY insert_order",
+            &[&user_id.to_uuid_1()],
+            DbHandlerPostgres::read_finance_account_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let mut result_list = query_result.unwrap();
+        if limit_account_ids.is_some() {
+            let limit_ids = limit_account_ids.unwrap();
+            result_list.retain(|elem| limit_ids.contains(&elem.id));
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} finance accounts",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_account_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account: &FinanceAccount,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result =
+            DbHandlerPostgres::upsert_finance_account_row(&client, user_id, finance_account).await;
+        if upsert_result.is_err() {
+            let upsert_err = DbHandlerPostgres::format_error(&upsert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","upserted finance account for user id {}",&user_id);
+
+        Ok(())
+    }
+}
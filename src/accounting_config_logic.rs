@@ -27,10 +27,16 @@ impl<'a> FinanceAccountingConfigHandle<'a> {
     }
 
     pub fn finance_account_type_list(&self) -> Result<Vec<FinanceAccountType>, String> {
-        let temp_var_1 = executor::block_on(
-            self.db_connector
-                .finance_account_type_list(&self.db_connection_settings, &self.user_id),
-        );
+        let temp_var_1 = executor::block_on(self.finance_account_type_list_async());
+
+        return temp_var_1;
+    }
+
+    pub async fn finance_account_type_list_async(&self) -> Result<Vec<FinanceAccountType>, String> {
+        let temp_var_1 = self
+            .db_connector
+            .finance_account_type_list(&self.db_connection_settings, &self.user_id)
+            .await;
 
         return temp_var_1;
     }
@@ -96,6 +102,37 @@ impl<'a> FinanceAccountingConfigHandle<'a> {
             );
         }
 
+        if finance_account.account_number.is_some() {
+            let account_number = finance_account.account_number.unwrap();
+            // DATEV accepts account numbers with up to nine digits
+            if account_number == 0 || account_number > 999_999_999 {
+                return Err(format!(
+                    "could not upsert finance account because account number {} is not between 1 and 999999999",
+                    account_number
+                ));
+            }
+            let temp_var_3 = executor::block_on(self.db_connector.finance_account_list(
+                &self.db_connection_settings,
+                &self.user_id,
+                None,
+            ));
+            if temp_var_3.is_err() {
+                return Err(format!(
+                    "Err upserting finance account, could not get list of available accounts: {}",
+                    temp_var_3.unwrap_err()
+                ));
+            }
+            let position_same_number = temp_var_3.unwrap().iter().position(|elem| {
+                elem.id.ne(&finance_account.id) && elem.account_number.eq(&Some(account_number))
+            });
+            if position_same_number.is_some() {
+                return Err(format!(
+                    "could not upsert finance account because account number {} is already used",
+                    account_number
+                ));
+            }
+        }
+
         let temp_var_1 = self.db_connector.finance_account_upsert(
             &self.db_connection_settings,
             &self.user_id,
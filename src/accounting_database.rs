@@ -7,17 +7,29 @@ use log::{debug, warn};
 use mongodb::{
     bson::{doc, Document, Uuid},
     error::{TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT},
-    options::{Acknowledgment, FindOptions, ReadConcern, TransactionOptions, WriteConcern},
-    ClientSession, Collection,
+    options::{
+        Acknowledgment, FindOptions, ReadConcern, TransactionOptions, UpdateOptions, WriteConcern,
+    },
+    ClientSession, Collection, Database,
+};
+use rusqlite::{
+    params, params_from_iter, Connection, OptionalExtension, Row, Transaction, TransactionBehavior,
+};
+use tokio_postgres::{
+    Client as PostgresClient, GenericClient, Row as PostgresRow, Transaction as PostgresTransaction,
 };
 
 use crate::{
+    accounting_config_database::DBFinanceConfigFunctions,
     accounting_config_logic::FinanceAccountingConfigHandle,
     convert_tools::ConvertTools,
     database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::{DbHandlerPostgres, PostgresTransactionError},
+    database_handler_sqlite::DbHandlerSqlite,
     datatypes::{
-        BookingEntryType, FinanceAccountBookingEntry, FinanceBookingRequest, FinanceBookingResult,
-        FinanceJournalEntry,
+        BookingEntryType, FinanceAccountBookingEntry, FinanceBackupArchive, FinanceBookingLeg,
+        FinanceBookingRequest, FinanceBookingResult, FinanceJournalEntry,
+        FinanceSplitBookingRequest, FinanceSplitBookingResult,
     },
     mdb_convert_tools::MdbConvertTools,
 };
@@ -42,14 +54,16 @@ impl FinanceAccountBookingEntryListSearchOption {
     }
 }
 
+/// accounting functions need the account configuration (e.g. account categories for balances)
 #[async_trait(?Send)]
-pub trait DBFinanceAccountingFunctions {
+pub trait DBFinanceAccountingFunctions: DBFinanceConfigFunctions {
     async fn finance_journal_entry_list(
         &self,
         conncetion_settings: &DbConnectionSetting,
         user_id: &Uuid,
         booking_time_from: Option<DateTime<Utc>>,
         booking_time_till: Option<DateTime<Utc>>,
+        tag: Option<String>,
     ) -> Result<Vec<FinanceJournalEntry>, String>;
 
     async fn finance_account_booking_entry_list(
@@ -66,12 +80,54 @@ pub trait DBFinanceAccountingFunctions {
         action_to_insert: FinanceBookingRequest,
     ) -> Result<FinanceBookingResult, String>;
 
+    async fn finance_insert_split_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String>;
+
+    /// inserts several (split) booking entries at once, either all entries are written or none
+    async fn finance_insert_split_booking_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String>;
+
+    /// posts a mirrored entry (debit and credit swapped) for the given journal entry and marks the original entry as reversed
+    async fn finance_reverse_journal_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String>;
+
     async fn finance_get_last_saldo_account_entries(
         &self,
         conncetion_settings: &DbConnectionSetting,
         user_id: &Uuid,
         list_account_ids: Option<Vec<Uuid>>,
     ) -> Result<HashMap<Uuid, FinanceAccountBookingEntry>, String>;
+
+    /// last running number handed out for the journal of the user (counter record)
+    async fn finance_get_journal_max_number(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<u64, String>;
+
+    /// writes account types, accounts, journal and booking entries of the archive with their stored ids
+    /// and running numbers and sets the counter record, only allowed for an empty journal,
+    /// ids used by another user are rejected, either all data is written or none
+    async fn finance_restore_archive(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), String>;
 }
 
 #[async_trait(?Send)]
@@ -82,6 +138,7 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         user_id: &Uuid,
         booking_time_from: Option<DateTime<Utc>>,
         booking_time_till: Option<DateTime<Utc>>,
+        tag: Option<String>,
     ) -> Result<Vec<FinanceJournalEntry>, String> {
         // Get a handle to the deployment.
         let client_create_result = self.get_internal_db_client();
@@ -109,6 +166,11 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
             let sub_doc2 = doc! {"booking_time": doc! {"$lte": booking_time_till.unwrap()}};
             sub_filters.push(sub_doc2);
         }
+        if tag.is_some() {
+            // matches when the tags array contains the given tag
+            let sub_doc3 = doc! {"tags": tag.unwrap()};
+            sub_filters.push(sub_doc3);
+        }
         let filter = if sub_filters.len().eq(&1) {
             sub_filters[0].clone()
         } else {
@@ -120,14 +182,23 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         "finance_journal_diary_id":<i32>::from(1),
         "is_simple_entry":<i32>::from(1),
         "is_saldo":<i32>::from(1),
+        "is_closing":<i32>::from(1),
         "debit_finance_account_id":<i32>::from(1),
         "credit_finance_account_id":<i32>::from(1),
         "running_number":<i32>::from(1),
         "booking_time":<i32>::from(1),
         "amount":<i32>::from(1),
         "title":<i32>::from(1),
-        "description":<i32>::from(1),};
-        let options = FindOptions::builder().projection(projection).build();
+        "description":<i32>::from(1),
+        "reverse_of_journal_entry_id":<i32>::from(1),
+        "reversed_by_journal_entry_id":<i32>::from(1),
+        "tags":<i32>::from(1),
+        "import_reference":<i32>::from(1),};
+        let sort = doc! {"booking_time":<i32>::from(1), "running_number":<i32>::from(1)};
+        let options = FindOptions::builder()
+            .projection(projection)
+            .sort(sort)
+            .build();
 
         let query_execute_result = journal_diary_entries_collection.find(filter, options).await;
 
@@ -190,6 +261,31 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
             if stored_is_saldo.is_err() {
                 return Err(stored_is_simple_entry.unwrap_err().to_string());
             }
+            let reverse_of_id_parse_result = ConvertTools::get_optional_uuid_from_document(
+                &inner_doc,
+                "reverse_of_journal_entry_id",
+            );
+            if reverse_of_id_parse_result.is_err() {
+                return Err(reverse_of_id_parse_result.unwrap_err());
+            }
+            let reversed_by_id_parse_result = ConvertTools::get_optional_uuid_from_document(
+                &inner_doc,
+                "reversed_by_journal_entry_id",
+            );
+            if reversed_by_id_parse_result.is_err() {
+                return Err(reversed_by_id_parse_result.unwrap_err());
+            }
+            // entries stored before period closing was introduced have no flag
+            let stored_is_closing = inner_doc.get_bool("is_closing").unwrap_or(false);
+            let stored_tags = ConvertTools::get_string_list_from_document(&inner_doc, "tags");
+            if stored_tags.is_err() {
+                return Err(stored_tags.unwrap_err());
+            }
+            // only entries of bank statement imports have a reference
+            let stored_import_reference = inner_doc
+                .get_str("import_reference")
+                .ok()
+                .map(|elem| elem.to_string());
 
             let entry = FinanceJournalEntry {
                 id: some_journal_entry_id_parse_result.unwrap(),
@@ -199,9 +295,14 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
                 description: stored_description.unwrap().into(),
                 is_simple_entry: stored_is_simple_entry.unwrap(),
                 is_saldo: stored_is_saldo.unwrap(),
+                is_closing: stored_is_closing,
                 debit_finance_account_id: some_debit_account_id_parse_result.unwrap(),
                 credit_finance_account_id: some_credit_account_id_parse_result.unwrap(),
                 running_number: stored_running_number.unwrap() as u64,
+                reverse_of_journal_entry_id: reverse_of_id_parse_result.unwrap(),
+                reversed_by_journal_entry_id: reversed_by_id_parse_result.unwrap(),
+                tags: stored_tags.unwrap(),
+                import_reference: stored_import_reference,
             };
 
             journal_entries_list.push(entry);
@@ -263,17 +364,15 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         for search_option in &search_options {
             //get a binary of UUID or it will not work in production
             let mut sub_filter = doc! { "finance_account_id": MdbConvertTools::get_binary_from_bson_uuid(&search_option.finance_account_id)};
+            let mut booking_time_filter = Document::new();
             if search_option.booking_time_from.is_some() {
-                sub_filter.insert(
-                    "booking_time",
-                    doc! {"$gte": search_option.booking_time_from.unwrap()},
-                );
+                booking_time_filter.insert("$gte", search_option.booking_time_from.unwrap());
             }
             if search_option.booking_time_till.is_some() {
-                sub_filter.insert(
-                    "booking_time",
-                    doc! {"$lte": search_option.booking_time_from.unwrap()},
-                );
+                booking_time_filter.insert("$lte", search_option.booking_time_till.unwrap());
+            }
+            if !booking_time_filter.is_empty() {
+                sub_filter.insert("booking_time", booking_time_filter);
             }
             sub_filter_docs.push(sub_filter);
         }
@@ -288,7 +387,9 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         "booking_time":<i32>::from(1),
         "amount":<i32>::from(1),
         "title":<i32>::from(1),
-        "description":<i32>::from(1),};
+        "description":<i32>::from(1),
+        "tags":<i32>::from(1),
+        "reconciliation_id":<i32>::from(1),};
         let options = FindOptions::builder().projection(projection).build();
 
         let query_execute_result = booking_entries_collection.find(filter, options).await;
@@ -353,6 +454,16 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
             if stored_description.is_err() {
                 return Err(stored_description.unwrap_err().to_string());
             }
+            let stored_tags = ConvertTools::get_string_list_from_document(&inner_doc, "tags");
+            if stored_tags.is_err() {
+                return Err(stored_tags.unwrap_err());
+            }
+            // entries stored before reconciliation was introduced have no field
+            let stored_reconciliation_id =
+                ConvertTools::get_optional_uuid_from_document(&inner_doc, "reconciliation_id");
+            if stored_reconciliation_id.is_err() {
+                return Err(stored_reconciliation_id.unwrap_err());
+            }
 
             let entry = FinanceAccountBookingEntry {
                 id: some_booking_entry_id_parse_result.unwrap(),
@@ -363,11 +474,23 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
                 amount: stored_amount.unwrap() as u64,
                 title: stored_title.unwrap().into(),
                 description: stored_description.unwrap().into(),
+                tags: stored_tags.unwrap(),
+                reconciliation_id: stored_reconciliation_id.unwrap(),
             };
 
             booking_entries_list.push(entry);
         }
 
+        let sort_result = DbHandlerMongoDB::sort_booking_entries_by_journal(
+            &db_instance,
+            user_id,
+            &mut booking_entries_list,
+        )
+        .await;
+        if sort_result.is_err() {
+            return Err(sort_result.unwrap_err());
+        }
+
         Ok(booking_entries_list)
     }
 
@@ -377,6 +500,34 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         user_id: &Uuid,
         action_to_insert: FinanceBookingRequest,
     ) -> Result<FinanceBookingResult, String> {
+        let split_request = FinanceSplitBookingRequest::from_booking_request(&action_to_insert);
+        let split_result = self
+            .finance_insert_split_booking_entry(conncetion_settings, user_id, split_request)
+            .await;
+        if split_result.is_err() {
+            return Err(split_result.unwrap_err());
+        }
+        let mut split_object = split_result.unwrap();
+        if split_object.debit_account_entries.len() != 1
+            || split_object.credit_account_entries.len() != 1
+        {
+            return Err("unexpected number of booking entries for simple entry".into());
+        }
+
+        let return_object = FinanceBookingResult {
+            journal_entry: split_object.journal_entry,
+            debit_account_entry: split_object.debit_account_entries.remove(0),
+            credit_account_entry: split_object.credit_account_entries.remove(0),
+        };
+        Ok(return_object)
+    }
+
+    async fn finance_insert_split_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String> {
         // Get a handle to the deployment.
         let client_create_result = self.get_internal_db_client();
         if client_create_result.is_err() {
@@ -386,13 +537,21 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         }
         let client = client_create_result.unwrap();
 
+        if action_to_insert.debit_legs.is_empty() || action_to_insert.credit_legs.is_empty() {
+            return Err("at least one debit and one credit leg is required".into());
+        }
+
+        let account_ids_to_check: Vec<Uuid> = action_to_insert
+            .debit_legs
+            .iter()
+            .chain(action_to_insert.credit_legs.iter())
+            .map(|elem| elem.finance_account_id)
+            .collect();
+
         let accounting_handle =
             FinanceAccountingConfigHandle::new(&conncetion_settings, &user_id, self);
         let account_list_result = accounting_handle
-            .finance_account_list_async(Some(&vec![
-                action_to_insert.credit_finance_account_id,
-                action_to_insert.debit_finance_account_id,
-            ]))
+            .finance_account_list_async(Some(&account_ids_to_check))
             .await;
         if account_list_result.is_err() {
             return Err(format!(
@@ -402,17 +561,21 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         }
 
         let account_list = account_list_result.unwrap();
-        let check_credit_account_check_option = account_list
-            .iter()
-            .position(|elem| elem.id.eq(&action_to_insert.credit_finance_account_id));
-        if check_credit_account_check_option.is_none() {
-            return Err("credit account is not available".into());
+        for debit_leg in &action_to_insert.debit_legs {
+            let check_debit_account_check_option = account_list
+                .iter()
+                .position(|elem| elem.id.eq(&debit_leg.finance_account_id));
+            if check_debit_account_check_option.is_none() {
+                return Err("debit account is not available".into());
+            }
         }
-        let check_debit_account_check_option = account_list
-            .iter()
-            .position(|elem| elem.id.eq(&action_to_insert.debit_finance_account_id));
-        if check_debit_account_check_option.is_none() {
-            return Err("debit account is not available".into());
+        for credit_leg in &action_to_insert.credit_legs {
+            let check_credit_account_check_option = account_list
+                .iter()
+                .position(|elem| elem.id.eq(&credit_leg.finance_account_id));
+            if check_credit_account_check_option.is_none() {
+                return Err("credit account is not available".into());
+            }
         }
 
         let session_result = client.start_session(None).await;
@@ -466,6 +629,183 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         }
     }
 
+    async fn finance_insert_split_booking_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        if actions_to_insert.is_empty() {
+            return Err("no booking entries given".into());
+        }
+
+        let mut account_ids_to_check: Vec<Uuid> = Vec::new();
+        for action_to_insert in &actions_to_insert {
+            if action_to_insert.debit_legs.is_empty() || action_to_insert.credit_legs.is_empty() {
+                return Err("at least one debit and one credit leg is required".into());
+            }
+            for booking_leg in action_to_insert
+                .debit_legs
+                .iter()
+                .chain(action_to_insert.credit_legs.iter())
+            {
+                if !account_ids_to_check.contains(&booking_leg.finance_account_id) {
+                    account_ids_to_check.push(booking_leg.finance_account_id);
+                }
+            }
+        }
+
+        let accounting_handle =
+            FinanceAccountingConfigHandle::new(&conncetion_settings, &user_id, self);
+        let account_list_result = accounting_handle
+            .finance_account_list_async(Some(&account_ids_to_check))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error retriving account list: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+
+        let account_list = account_list_result.unwrap();
+        for account_id in &account_ids_to_check {
+            let check_account_check_option =
+                account_list.iter().position(|elem| elem.id.eq(account_id));
+            if check_account_check_option.is_none() {
+                return Err(format!("account {} is not available", account_id));
+            }
+        }
+
+        let session_result = client.start_session(None).await;
+        if session_result.is_err() {
+            return Err(format!(
+                "problem getting session: {}",
+                session_result.unwrap_err()
+            ));
+        }
+
+        let options = TransactionOptions::builder()
+            .read_concern(ReadConcern::majority())
+            .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+            .build();
+
+        let mut session = session_result.unwrap();
+        let transaction_start_result = session.start_transaction(options).await;
+        if transaction_start_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_start_result.unwrap_err()
+            ));
+        }
+
+        loop {
+            let execute_result =
+                DbHandlerMongoDB::execute_finance_insert_booking_entries_with_transaction(
+                    &mut session,
+                    &conncetion_settings.instance,
+                    &user_id,
+                    actions_to_insert.clone(),
+                )
+                .await;
+            if execute_result.is_ok() {
+                let result_object = execute_result.unwrap();
+                return Ok(result_object);
+            } else {
+                let error_var = execute_result.unwrap_err();
+
+                if !error_var.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+                    let error_message;
+                    let custom_info = error_var.get_custom::<String>();
+                    if custom_info.is_some() {
+                        error_message = custom_info.unwrap().to_string();
+                    } else {
+                        error_message = error_var.to_string();
+                    }
+                    return Err(format!("Problem closing transaction: {}", error_message));
+                }
+            }
+        }
+    }
+
+    async fn finance_reverse_journal_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let session_result = client.start_session(None).await;
+        if session_result.is_err() {
+            return Err(format!(
+                "problem getting session: {}",
+                session_result.unwrap_err()
+            ));
+        }
+
+        let options = TransactionOptions::builder()
+            .read_concern(ReadConcern::majority())
+            .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+            .build();
+
+        let mut session = session_result.unwrap();
+        let transaction_start_result = session.start_transaction(options).await;
+        if transaction_start_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_start_result.unwrap_err()
+            ));
+        }
+
+        loop {
+            let execute_result =
+                DbHandlerMongoDB::execute_finance_reverse_journal_entry_with_transaction(
+                    &mut session,
+                    &conncetion_settings.instance,
+                    &user_id,
+                    &journal_entry_id,
+                    booking_time,
+                    &reason,
+                )
+                .await;
+            if execute_result.is_ok() {
+                let result_object = execute_result.unwrap();
+                return Ok(result_object);
+            } else {
+                let error_var = execute_result.unwrap_err();
+
+                if !error_var.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+                    let error_message;
+                    let custom_info = error_var.get_custom::<String>();
+                    if custom_info.is_some() {
+                        error_message = custom_info.unwrap().to_string();
+                    } else {
+                        error_message = error_var.to_string();
+                    }
+                    return Err(format!("Problem closing transaction: {}", error_message));
+                }
+            }
+        }
+    }
+
     async fn finance_get_last_saldo_account_entries(
         &self,
         conncetion_settings: &DbConnectionSetting,
@@ -499,7 +839,9 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
         "booking_time":<i32>::from(1),
         "amount":<i32>::from(1),
         "title":<i32>::from(1),
-        "description":<i32>::from(1),};
+        "description":<i32>::from(1),
+        "tags":<i32>::from(1),
+        "reconciliation_id":<i32>::from(1),};
         let options = FindOptions::builder().projection(projection).build();
 
         let query_execute_result = booking_entries_collection.find(filter, options).await;
@@ -564,6 +906,16 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
             if stored_description.is_err() {
                 return Err(stored_description.unwrap_err().to_string());
             }
+            let stored_tags = ConvertTools::get_string_list_from_document(&inner_doc, "tags");
+            if stored_tags.is_err() {
+                return Err(stored_tags.unwrap_err());
+            }
+            // entries stored before reconciliation was introduced have no field
+            let stored_reconciliation_id =
+                ConvertTools::get_optional_uuid_from_document(&inner_doc, "reconciliation_id");
+            if stored_reconciliation_id.is_err() {
+                return Err(stored_reconciliation_id.unwrap_err());
+            }
 
             let entry = FinanceAccountBookingEntry {
                 id: some_booking_entry_id_parse_result.unwrap(),
@@ -574,11 +926,23 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
                 amount: stored_amount.unwrap() as u64,
                 title: stored_title.unwrap().into(),
                 description: stored_description.unwrap().into(),
+                tags: stored_tags.unwrap(),
+                reconciliation_id: stored_reconciliation_id.unwrap(),
             };
 
             booking_entries_list.push(entry);
         }
 
+        let sort_result = DbHandlerMongoDB::sort_booking_entries_by_journal(
+            &db_instance,
+            user_id,
+            &mut booking_entries_list,
+        )
+        .await;
+        if sort_result.is_err() {
+            return Err(sort_result.unwrap_err());
+        }
+
         let mut return_object = HashMap::new();
 
         let account_ids_to_check = match list_account_ids {
@@ -599,9 +963,8 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
                 })
                 .collect();
 
-            let oldest_saldo_entry_option = saldo_entries_per_account
-                .iter()
-                .max_by_key(|elem| elem.booking_time);
+            // ordered by booking time and running number, so the last saldo entry is the latest
+            let oldest_saldo_entry_option = saldo_entries_per_account.last();
             if oldest_saldo_entry_option.is_some() {
                 return_object.insert(
                     account_id_to_check,
@@ -614,78 +977,2996 @@ impl DBFinanceAccountingFunctions for DbHandlerMongoDB {
 
         Ok(return_object)
     }
-}
 
-impl DbHandlerMongoDB {
-    /// Helper function for DBFinanceAccountingFunctions::finance_insert_booking_entry()
-    /// see https://github.com/mongodb/mongo-rust-driver/blob/main/tests/transactions_example.rs
-    /// see https://docs.rs/mongodb/2.8.2/mongodb/struct.ClientSession.html
-    /// see https://www.mongodb.com/docs/v2.2/reference/operator/update/inc/
-    async fn execute_finance_insert_booking_entry_with_transaction(
-        session: &mut ClientSession,
-        db_instance_name: &String,
+    async fn finance_get_journal_max_number(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
         user_id: &Uuid,
-        action_to_insert: FinanceBookingRequest,
-    ) -> Result<FinanceBookingResult, mongodb::error::Error> {
-        let client = session.client();
-        let db_instance = client.database(&db_instance_name);
-
-        let booking_entries_collection: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES);
+    ) -> Result<u64, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
 
-        let journal_diary_entries_collection: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_DIARY);
+        let db_instance = client.database(&conncetion_settings.instance);
 
         let counter_entries_collection: Collection<Document> =
             db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_COUNTERS);
 
         let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
-
-        let increasing_journal_max_result = counter_entries_collection
-            .update_one_with_session(
-                doc! {"user_id": user_id_value.clone()},
-                doc! {"$inc": doc! {"booking_journal_max_number":1}},
-                None,
-                session,
-            )
-            .await?;
-
-        if increasing_journal_max_result.modified_count.ne(&1) {
-            return Err(mongodb::error::Error::custom(format!(
-                "could not update max number record, upated record: {}",
-                increasing_journal_max_result.modified_count
-            )));
+        let find_result = counter_entries_collection
+            .find_one(doc! {"user_id":user_id_value}, None)
+            .await;
+        if find_result.is_err() {
+            return Err(format!(
+                "Error getting counter record: {}",
+                find_result.unwrap_err()
+            ));
         }
-
+        let counter_document_option = find_result.unwrap();
+        if counter_document_option.is_none() {
+            // the counter record is created with the first booking
+            return Ok(0);
+        }
+        let stored_max_number = counter_document_option
+            .unwrap()
+            .get_i64("booking_journal_max_number");
+        if stored_max_number.is_err() {
+            return Err(format!(
+                "could not read max number: {}",
+                stored_max_number.unwrap_err()
+            ));
+        }
+        return Ok(stored_max_number.unwrap() as u64);
+    }
+
+    async fn finance_restore_archive(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let session_result = client.start_session(None).await;
+        if session_result.is_err() {
+            return Err(format!(
+                "problem getting session: {}",
+                session_result.unwrap_err()
+            ));
+        }
+
+        let options = TransactionOptions::builder()
+            .read_concern(ReadConcern::majority())
+            .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+            .build();
+
+        let mut session = session_result.unwrap();
+        let transaction_start_result = session.start_transaction(options).await;
+        if transaction_start_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_start_result.unwrap_err()
+            ));
+        }
+
+        loop {
+            let execute_result =
+                DbHandlerMongoDB::execute_finance_restore_archive_with_transaction(
+                    &mut session,
+                    &conncetion_settings.instance,
+                    &user_id,
+                    archive,
+                )
+                .await;
+            if execute_result.is_ok() {
+                return Ok(());
+            } else {
+                let error_var = execute_result.unwrap_err();
+
+                if !error_var.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+                    let error_message;
+                    let custom_info = error_var.get_custom::<String>();
+                    if custom_info.is_some() {
+                        error_message = custom_info.unwrap().to_string();
+                    } else {
+                        error_message = error_var.to_string();
+                    }
+                    return Err(format!("Problem closing transaction: {}", error_message));
+                }
+            }
+        }
+    }
+}
+
+impl DbHandlerMongoDB {
+    /// Helper function to order booking entries by booking time and running number
+    /// booking entries carry no running number, so it is read from their journal entries
+    async fn sort_booking_entries_by_journal(
+        db_instance: &Database,
+        user_id: &Uuid,
+        booking_entries: &mut Vec<FinanceAccountBookingEntry>,
+    ) -> Result<(), String> {
+        if booking_entries.is_empty() {
+            return Ok(());
+        }
+
+        let journal_diary_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_DIARY);
+
+        let journal_entry_ids: Vec<mongodb::bson::Binary> = booking_entries
+            .iter()
+            .map(|elem| MdbConvertTools::get_binary_from_bson_uuid(&elem.finance_journal_diary_id))
+            .collect();
+        let filter = doc! {"user_id":MdbConvertTools::get_binary_from_bson_uuid(user_id),
+        "finance_journal_diary_id": doc! {"$in": journal_entry_ids}};
+        let projection = doc! {"finance_journal_diary_id":<i32>::from(1),
+        "running_number":<i32>::from(1),};
+        let options = FindOptions::builder().projection(projection).build();
+
+        let query_execute_result = journal_diary_entries_collection.find(filter, options).await;
+        if query_execute_result.is_err() {
+            return Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut running_number_per_journal_entry = HashMap::new();
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let some_journal_entry_id_parse_result =
+                ConvertTools::get_uuid_from_document(&inner_doc, "finance_journal_diary_id");
+            if some_journal_entry_id_parse_result.is_err() {
+                return Err(some_journal_entry_id_parse_result.unwrap_err().to_string());
+            }
+            let stored_running_number = inner_doc.get_i64("running_number");
+            if stored_running_number.is_err() {
+                return Err(stored_running_number.unwrap_err().to_string());
+            }
+
+            running_number_per_journal_entry.insert(
+                some_journal_entry_id_parse_result.unwrap(),
+                stored_running_number.unwrap() as u64,
+            );
+        }
+
+        booking_entries.sort_by_key(|elem| {
+            (
+                elem.booking_time,
+                running_number_per_journal_entry
+                    .get(&elem.finance_journal_diary_id)
+                    .cloned()
+                    .unwrap_or_default(),
+            )
+        });
+
+        Ok(())
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_insert_split_booking_entry()
+    /// writes one journal entry and one booking entry per leg
+    /// see https://github.com/mongodb/mongo-rust-driver/blob/main/tests/transactions_example.rs
+    /// see https://docs.rs/mongodb/2.8.2/mongodb/struct.ClientSession.html
+    /// see https://www.mongodb.com/docs/v2.2/reference/operator/update/inc/
+    async fn execute_finance_insert_booking_entry_with_transaction(
+        session: &mut ClientSession,
+        db_instance_name: &String,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, mongodb::error::Error> {
+        let return_object = DbHandlerMongoDB::write_finance_split_booking_with_session(
+            session,
+            db_instance_name,
+            user_id,
+            action_to_insert,
+            None,
+        )
+        .await?;
+
+        loop {
+            let result = session.commit_transaction().await;
+            if let Err(ref error) = result {
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue;
+                }
+            }
+
+            return Ok(return_object);
+        }
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_insert_split_booking_entries()
+    /// writes all entries inside the same transaction
+    async fn execute_finance_insert_booking_entries_with_transaction(
+        session: &mut ClientSession,
+        db_instance_name: &String,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, mongodb::error::Error> {
+        let mut return_object = Vec::new();
+        for action_to_insert in actions_to_insert {
+            let insert_result = DbHandlerMongoDB::write_finance_split_booking_with_session(
+                session,
+                db_instance_name,
+                user_id,
+                action_to_insert,
+                None,
+            )
+            .await?;
+            return_object.push(insert_result);
+        }
+
+        loop {
+            let result = session.commit_transaction().await;
+            if let Err(ref error) = result {
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue;
+                }
+            }
+
+            return Ok(return_object);
+        }
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_reverse_journal_entry()
+    /// checks and mirrors the original entry, inserts the reversal and marks the original inside one transaction
+    async fn execute_finance_reverse_journal_entry_with_transaction(
+        session: &mut ClientSession,
+        db_instance_name: &String,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, mongodb::error::Error> {
+        let client = session.client();
+        let db_instance = client.database(&db_instance_name);
+
+        let booking_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES);
+
+        let journal_diary_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_DIARY);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let journal_entry_id_value = mongodb::bson::Binary::from_uuid(journal_entry_id.clone());
+
+        let original_journal_document_option = journal_diary_entries_collection
+            .find_one_with_session(
+                doc! {"user_id":user_id_value.clone(),"finance_journal_diary_id":journal_entry_id_value.clone()},
+                None,
+                session,
+            )
+            .await?;
+        if original_journal_document_option.is_none() {
+            return Err(mongodb::error::Error::custom(format!(
+                "journal entry {} not found",
+                journal_entry_id
+            )));
+        }
+        let original_journal_document = original_journal_document_option.unwrap();
+
+        let reversed_by_result = ConvertTools::get_optional_uuid_from_document(
+            &original_journal_document,
+            "reversed_by_journal_entry_id",
+        );
+        if reversed_by_result.is_err() {
+            return Err(mongodb::error::Error::custom(
+                reversed_by_result.unwrap_err(),
+            ));
+        }
+        if reversed_by_result.unwrap().is_some() {
+            return Err(mongodb::error::Error::custom(
+                "journal entry is already reversed".to_string(),
+            ));
+        }
+        let reverse_of_result = ConvertTools::get_optional_uuid_from_document(
+            &original_journal_document,
+            "reverse_of_journal_entry_id",
+        );
+        if reverse_of_result.is_err() {
+            return Err(mongodb::error::Error::custom(
+                reverse_of_result.unwrap_err(),
+            ));
+        }
+        if reverse_of_result.unwrap().is_some() {
+            return Err(mongodb::error::Error::custom(
+                "a reversal entry can not be reversed".to_string(),
+            ));
+        }
+        let stored_is_saldo = original_journal_document.get_bool("is_saldo");
+        if stored_is_saldo.is_err() || stored_is_saldo.unwrap() {
+            return Err(mongodb::error::Error::custom(
+                "saldo entries can not be reversed".to_string(),
+            ));
+        }
+        if original_journal_document
+            .get_bool("is_closing")
+            .unwrap_or(false)
+        {
+            return Err(mongodb::error::Error::custom(
+                "closing entries can not be reversed".to_string(),
+            ));
+        }
+        let stored_running_number = original_journal_document.get_i64("running_number");
+        let stored_title = original_journal_document.get_str("title");
+        let stored_tags =
+            ConvertTools::get_string_list_from_document(&original_journal_document, "tags");
+        if stored_running_number.is_err() || stored_title.is_err() || stored_tags.is_err() {
+            return Err(mongodb::error::Error::custom(
+                "could not read original journal entry".to_string(),
+            ));
+        }
+
+        let mut booking_cursor = booking_entries_collection
+            .find_with_session(
+                doc! {"user_id":user_id_value.clone(),"finance_journal_diary_id":journal_entry_id_value.clone()},
+                None,
+                session,
+            )
+            .await?;
+
+        let mut debit_legs = Vec::new();
+        let mut credit_legs = Vec::new();
+        let mut involved_account_values = Vec::new();
+        while let Some(data_doc) = booking_cursor.next(session).await {
+            let inner_doc = data_doc?;
+            let finance_account_id_result =
+                ConvertTools::get_uuid_from_document(&inner_doc, "finance_account_id");
+            if finance_account_id_result.is_err() {
+                return Err(mongodb::error::Error::custom(
+                    finance_account_id_result.unwrap_err(),
+                ));
+            }
+            let stored_booking_type_int = inner_doc.get_i32("booking_type");
+            let stored_amount = inner_doc.get_i64("amount");
+            if stored_booking_type_int.is_err() || stored_amount.is_err() {
+                return Err(mongodb::error::Error::custom(
+                    "could not read booking entry of original journal entry".to_string(),
+                ));
+            }
+            let reconciliation_id_result =
+                ConvertTools::get_optional_uuid_from_document(&inner_doc, "reconciliation_id");
+            if reconciliation_id_result.is_err() {
+                return Err(mongodb::error::Error::custom(
+                    reconciliation_id_result.unwrap_err(),
+                ));
+            }
+            if reconciliation_id_result.unwrap().is_some() {
+                return Err(mongodb::error::Error::custom(
+                    "journal entry contains reconciled booking entries, unreconcile them first"
+                        .to_string(),
+                ));
+            }
+            let finance_account_id = finance_account_id_result.unwrap();
+            involved_account_values.push(mongodb::bson::Binary::from_uuid(finance_account_id));
+            // debit and credit are swapped for the reversal
+            let reversed_leg = FinanceBookingLeg {
+                finance_account_id,
+                amount: stored_amount.unwrap() as u64,
+            };
+            if BookingEntryType::get_from_int(stored_booking_type_int.unwrap())
+                .eq(&Ok(BookingEntryType::Debit))
+            {
+                credit_legs.push(reversed_leg);
+            } else {
+                debit_legs.push(reversed_leg);
+            }
+        }
+        if debit_legs.is_empty() || credit_legs.is_empty() {
+            return Err(mongodb::error::Error::custom(
+                "booking entries of original journal entry are incomplete".to_string(),
+            ));
+        }
+
+        let later_saldo_count = booking_entries_collection
+            .count_documents_with_session(
+                doc! {"user_id":user_id_value.clone(),
+                "finance_account_id":doc! {"$in":involved_account_values},
+                "booking_type":doc! {"$in":[BookingEntryType::SaldoCredit.to_int(),BookingEntryType::SaldoDebit.to_int()]},
+                "booking_time":doc! {"$gte":booking_time}},
+                None,
+                session,
+            )
+            .await?;
+        if later_saldo_count > 0 {
+            return Err(mongodb::error::Error::custom(
+                "Can not insert reversal before saldo of an involved account".to_string(),
+            ));
+        }
+
+        let reversal_request = FinanceSplitBookingRequest {
+            is_saldo: false,
+            is_closing: false,
+            debit_legs,
+            credit_legs,
+            booking_time,
+            title: format!(
+                "Reversal of #{}: {}",
+                stored_running_number.unwrap(),
+                stored_title.unwrap()
+            ),
+            description: reason.clone(),
+            // the reversal keeps the tags so that tag summaries net out
+            tags: stored_tags.unwrap(),
+            import_reference: None,
+        };
+
+        let return_object = DbHandlerMongoDB::write_finance_split_booking_with_session(
+            session,
+            db_instance_name,
+            user_id,
+            reversal_request,
+            Some(journal_entry_id.clone()),
+        )
+        .await?;
+
+        let update_original_result = journal_diary_entries_collection
+            .update_one_with_session(
+                doc! {"user_id":user_id_value.clone(),"finance_journal_diary_id":journal_entry_id_value.clone()},
+                doc! {"$set": doc! {"reversed_by_journal_entry_id":mongodb::bson::Binary::from_uuid(return_object.journal_entry.id)}},
+                None,
+                session,
+            )
+            .await?;
+        if update_original_result.modified_count.ne(&1) {
+            return Err(mongodb::error::Error::custom(format!(
+                "could not mark original journal entry as reversed, upated record: {}",
+                update_original_result.modified_count
+            )));
+        }
+
+        loop {
+            let result = session.commit_transaction().await;
+            if let Err(ref error) = result {
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue;
+                }
+            }
+
+            return Ok(return_object);
+        }
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_restore_archive()
+    /// returns an error for the first id of the archive which is already used by another user
+    async fn check_archive_ids_of_other_users_with_session(
+        session: &mut ClientSession,
+        db_instance: &Database,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), mongodb::error::Error> {
+        let id_lists = [
+            (
+                DbHandlerMongoDB::COLLECTION_NAME_ACCOUNTING_TYPES,
+                "finance_account_type_id",
+                "account type",
+                archive
+                    .account_types
+                    .iter()
+                    .map(|elem| elem.id)
+                    .collect::<Vec<Uuid>>(),
+            ),
+            (
+                DbHandlerMongoDB::COLLECTION_NAME_ACCOUNTS,
+                "finance_account_id",
+                "account",
+                archive.accounts.iter().map(|elem| elem.id).collect(),
+            ),
+            (
+                DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_DIARY,
+                "finance_journal_diary_id",
+                "journal entry",
+                archive.journal_entries.iter().map(|elem| elem.id).collect(),
+            ),
+            (
+                DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES,
+                "booking_entry_id",
+                "booking entry",
+                archive.booking_entries.iter().map(|elem| elem.id).collect(),
+            ),
+        ];
+        for (collection_name, id_field_name, object_name, ids) in id_lists {
+            let collection: Collection<Document> = db_instance.collection(collection_name);
+            let id_values: Vec<mongodb::bson::Binary> = ids
+                .iter()
+                .map(|elem| mongodb::bson::Binary::from_uuid(elem.clone()))
+                .collect();
+            let filter = doc! {id_field_name: doc! {"$in": id_values},
+            "user_id": doc! {"$ne": mongodb::bson::Binary::from_uuid(user_id.clone())}};
+            let used_document_option = collection
+                .find_one_with_session(filter, None, session)
+                .await?;
+            if used_document_option.is_some() {
+                let used_id_result = ConvertTools::get_uuid_from_document(
+                    &used_document_option.unwrap(),
+                    id_field_name,
+                );
+                if used_id_result.is_err() {
+                    return Err(mongodb::error::Error::custom(
+                        used_id_result.unwrap_err().to_string(),
+                    ));
+                }
+                return Err(mongodb::error::Error::custom(format!(
+                    "{} {} of the archive is used by another user",
+                    object_name,
+                    used_id_result.unwrap()
+                )));
+            }
+        }
+        return Ok(());
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_restore_archive()
+    /// writes the configuration and the entries as they are and sets the counter record inside one transaction
+    async fn execute_finance_restore_archive_with_transaction(
+        session: &mut ClientSession,
+        db_instance_name: &String,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), mongodb::error::Error> {
+        let client = session.client();
+        let db_instance = client.database(&db_instance_name);
+
+        let accounting_type_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_ACCOUNTING_TYPES);
+
+        let account_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_ACCOUNTS);
+
+        let booking_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES);
+
+        let journal_diary_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_DIARY);
+
+        let counter_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_COUNTERS);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+
+        let existing_journal_count = journal_diary_entries_collection
+            .count_documents_with_session(doc! {"user_id":user_id_value.clone()}, None, session)
+            .await?;
+        if existing_journal_count > 0 {
+            return Err(mongodb::error::Error::custom(
+                "the journal is not empty, entries can only be restored into an empty journal"
+                    .to_string(),
+            ));
+        }
+
+        DbHandlerMongoDB::check_archive_ids_of_other_users_with_session(
+            session,
+            &db_instance,
+            user_id,
+            archive,
+        )
+        .await?;
+
+        for account_type in &archive.account_types {
+            let account_type_id_value = mongodb::bson::Binary::from_uuid(account_type.id);
+            accounting_type_collection
+                .update_one_with_session(
+                    doc! {"finance_account_type_id":account_type_id_value.clone(), "user_id": user_id_value.clone()},
+                    doc! {"$set": doc! {
+                        "finance_account_type_id":account_type_id_value,
+                        "user_id": user_id_value.clone(),
+                        "title": account_type.title.clone(),
+                        "description": account_type.description.clone(),
+                        "account_category": account_type.account_category.to_int(),
+                    }},
+                    UpdateOptions::builder().upsert(true).build(),
+                    session,
+                )
+                .await?;
+        }
+        for account in &archive.accounts {
+            let account_id_value = mongodb::bson::Binary::from_uuid(account.id);
+            account_collection
+                .update_one_with_session(
+                    doc! {"finance_account_id":account_id_value.clone(), "user_id": user_id_value.clone()},
+                    doc! {"$set": doc! {
+                        "finance_account_id":account_id_value,
+                        "finance_account_type_id":mongodb::bson::Binary::from_uuid(account.finance_account_type_id),
+                        "user_id": user_id_value.clone(),
+                        "title": account.title.clone(),
+                        "description": account.description.clone(),
+                        "account_number": account.account_number.map(|elem| elem as i64),
+                    }},
+                    UpdateOptions::builder().upsert(true).build(),
+                    session,
+                )
+                .await?;
+        }
+
+        if !archive.journal_entries.is_empty() {
+            let journal_documents: Vec<Document> = archive
+                .journal_entries
+                .iter()
+                .map(|journal_entry| {
+                    doc! {
+                        "finance_journal_diary_id":mongodb::bson::Binary::from_uuid(journal_entry.id),
+                        "user_id": user_id_value.clone(),
+                        "is_simple_entry": journal_entry.is_simple_entry,
+                        "is_saldo":journal_entry.is_saldo,
+                        "is_closing":journal_entry.is_closing,
+                        "debit_finance_account_id":mongodb::bson::Binary::from_uuid(journal_entry.debit_finance_account_id),
+                        "credit_finance_account_id":mongodb::bson::Binary::from_uuid(journal_entry.credit_finance_account_id),
+                        "running_number":journal_entry.running_number as i64,
+                        "booking_time":journal_entry.booking_time,
+                        "amount":journal_entry.amount as i64,
+                        "title":journal_entry.title.clone(),
+                        "description":journal_entry.description.clone(),
+                        "reverse_of_journal_entry_id":journal_entry.reverse_of_journal_entry_id.map(mongodb::bson::Binary::from_uuid),
+                        "reversed_by_journal_entry_id":journal_entry.reversed_by_journal_entry_id.map(mongodb::bson::Binary::from_uuid),
+                        "tags":journal_entry.tags.clone(),
+                        "import_reference":journal_entry.import_reference.clone()
+                    }
+                })
+                .collect();
+            journal_diary_entries_collection
+                .insert_many_with_session(journal_documents, None, session)
+                .await?;
+        }
+
+        if !archive.booking_entries.is_empty() {
+            let booking_documents: Vec<Document> = archive
+                .booking_entries
+                .iter()
+                .map(|booking_entry| {
+                    doc! {
+                        "booking_entry_id":mongodb::bson::Binary::from_uuid(booking_entry.id),
+                        "user_id": user_id_value.clone(),
+                        "finance_account_id":mongodb::bson::Binary::from_uuid(booking_entry.finance_account_id),
+                        "finance_journal_diary_id":mongodb::bson::Binary::from_uuid(booking_entry.finance_journal_diary_id),
+                        "booking_type":booking_entry.booking_type.to_int(),
+                        "booking_time":booking_entry.booking_time,
+                        "amount":booking_entry.amount as i64,
+                        "title":booking_entry.title.clone(),
+                        "description":booking_entry.description.clone(),
+                        "tags":booking_entry.tags.clone(),
+                        "reconciliation_id":booking_entry.reconciliation_id.map(mongodb::bson::Binary::from_uuid)
+                    }
+                })
+                .collect();
+            booking_entries_collection
+                .insert_many_with_session(booking_documents, None, session)
+                .await?;
+        }
+
+        counter_entries_collection
+            .update_one_with_session(
+                doc! {"user_id": user_id_value.clone()},
+                doc! {"$set": doc! {"booking_journal_max_number":archive.booking_journal_max_number as i64},
+                "$setOnInsert": doc! {"counter_entry_id":mongodb::bson::Binary::from_uuid(Uuid::new())}},
+                UpdateOptions::builder().upsert(true).build(),
+                session,
+            )
+            .await?;
+
+        loop {
+            let result = session.commit_transaction().await;
+            if let Err(ref error) = result {
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue;
+                }
+            }
+
+            return Ok(());
+        }
+    }
+
+    /// Helper function writing journal entry, booking entries and running number inside a running transaction,
+    /// the transaction is not committed
+    async fn write_finance_split_booking_with_session(
+        session: &mut ClientSession,
+        db_instance_name: &String,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+        reverse_of_journal_entry_id: Option<Uuid>,
+    ) -> Result<FinanceSplitBookingResult, mongodb::error::Error> {
+        let client = session.client();
+        let db_instance = client.database(&db_instance_name);
+
+        let booking_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES);
+
+        let journal_diary_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_DIARY);
+
+        let counter_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_COUNTERS);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+
+        let increasing_journal_max_result = counter_entries_collection
+            .update_one_with_session(
+                doc! {"user_id": user_id_value.clone()},
+                doc! {"$inc": doc! {"booking_journal_max_number":1}},
+                None,
+                session,
+            )
+            .await?;
+
+        if increasing_journal_max_result.modified_count.ne(&1) {
+            return Err(mongodb::error::Error::custom(format!(
+                "could not update max number record, upated record: {}",
+                increasing_journal_max_result.modified_count
+            )));
+        }
+
         let filter = doc! {"user_id":user_id_value.clone()};
 
-        let max_number_execute_result = counter_entries_collection
-            .find_one_with_session(filter, None, session)
+        let max_number_execute_result = counter_entries_collection
+            .find_one_with_session(filter, None, session)
+            .await?;
+        let counter_document = max_number_execute_result.unwrap();
+        let new_running_number_result = counter_document.get_i64("booking_journal_max_number");
+        if new_running_number_result.is_err() {
+            return Err(mongodb::error::Error::custom(format!(
+                "could not get new max number: {}",
+                new_running_number_result.unwrap_err()
+            )));
+        }
+        let new_running_number = new_running_number_result.unwrap() as u64;
+
+        // same rule as the unique index of the SQL backends, the update of the counter record
+        // lets concurrent transactions of the user conflict, so the check can not be passed twice
+        if action_to_insert.import_reference.is_some() {
+            let same_reference_option = journal_diary_entries_collection
+                .find_one_with_session(
+                    doc! {"user_id":user_id_value.clone(),
+                    "debit_finance_account_id":mongodb::bson::Binary::from_uuid(action_to_insert.debit_legs[0].finance_account_id),
+                    "credit_finance_account_id":mongodb::bson::Binary::from_uuid(action_to_insert.credit_legs[0].finance_account_id),
+                    "import_reference":action_to_insert.import_reference.clone(),
+                    "reversed_by_journal_entry_id":mongodb::bson::Bson::Null},
+                    None,
+                    session,
+                )
+                .await?;
+            if same_reference_option.is_some() {
+                return Err(mongodb::error::Error::custom(format!(
+                    "import reference {} is already booked",
+                    action_to_insert.import_reference.unwrap()
+                )));
+            }
+        }
+
+        let journal_diary_entry_id = Uuid::new();
+        let journal_diary_entry_id_value =
+            mongodb::bson::Binary::from_uuid(journal_diary_entry_id.clone());
+        let journal_amount_result = action_to_insert.sum_debit_amount();
+        if journal_amount_result.is_err() {
+            return Err(mongodb::error::Error::custom(
+                journal_amount_result.unwrap_err(),
+            ));
+        }
+        // for entries with more than two accounts the first leg of each side is stored as main account
+        let new_journal_entry = FinanceJournalEntry {
+            id: journal_diary_entry_id.clone(),
+            is_simple_entry: action_to_insert.is_simple_entry(),
+            is_saldo: action_to_insert.is_saldo,
+            is_closing: action_to_insert.is_closing,
+            debit_finance_account_id: action_to_insert.debit_legs[0].finance_account_id,
+            credit_finance_account_id: action_to_insert.credit_legs[0].finance_account_id,
+            running_number: new_running_number as u64,
+            booking_time: action_to_insert.booking_time,
+            amount: journal_amount_result.unwrap(),
+            title: action_to_insert.title.clone(),
+            description: action_to_insert.description.clone(),
+            reverse_of_journal_entry_id,
+            reversed_by_journal_entry_id: None,
+            tags: action_to_insert.tags.clone(),
+            import_reference: action_to_insert.import_reference.clone(),
+        };
+
+        let credit_booking_type = if action_to_insert.is_saldo {
+            BookingEntryType::SaldoCredit
+        } else {
+            BookingEntryType::Credit
+        };
+        let debit_booking_type = if action_to_insert.is_saldo {
+            BookingEntryType::SaldoDebit
+        } else {
+            BookingEntryType::Debit
+        };
+        let mut new_debit_account_entries = Vec::new();
+        for debit_leg in &action_to_insert.debit_legs {
+            new_debit_account_entries.push(FinanceAccountBookingEntry {
+                id: Uuid::new(),
+                finance_account_id: debit_leg.finance_account_id,
+                finance_journal_diary_id: new_journal_entry.id.clone(),
+                booking_type: debit_booking_type.clone(),
+                booking_time: action_to_insert.booking_time,
+                amount: debit_leg.amount,
+                title: action_to_insert.title.clone(),
+                description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
+            });
+        }
+        let mut new_credit_account_entries = Vec::new();
+        for credit_leg in &action_to_insert.credit_legs {
+            new_credit_account_entries.push(FinanceAccountBookingEntry {
+                id: Uuid::new(),
+                finance_account_id: credit_leg.finance_account_id,
+                finance_journal_diary_id: new_journal_entry.id.clone(),
+                booking_type: credit_booking_type.clone(),
+                booking_time: action_to_insert.booking_time,
+                amount: credit_leg.amount,
+                title: action_to_insert.title.clone(),
+                description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
+            });
+        }
+
+        let journal_insert_result = journal_diary_entries_collection
+            .insert_one_with_session(
+                doc! {
+                    "finance_journal_diary_id":journal_diary_entry_id_value.clone(),
+                    "user_id": user_id_value.clone(),
+                    "is_simple_entry": new_journal_entry.is_simple_entry,
+                    "is_saldo":new_journal_entry.is_saldo,
+                    "is_closing":new_journal_entry.is_closing,
+                    "debit_finance_account_id":mongodb::bson::Binary::from_uuid(new_journal_entry.debit_finance_account_id),
+                    "credit_finance_account_id":mongodb::bson::Binary::from_uuid(new_journal_entry.credit_finance_account_id),
+                    "running_number":new_running_number as i64,
+                    "booking_time":new_journal_entry.booking_time,
+                    "amount":new_journal_entry.amount as i64,
+                    "title":new_journal_entry.title.clone(),
+                    "description":new_journal_entry.description.clone(),
+                    "reverse_of_journal_entry_id":reverse_of_journal_entry_id.map(mongodb::bson::Binary::from_uuid),
+                    "reversed_by_journal_entry_id":mongodb::bson::Bson::Null,
+                    "tags":new_journal_entry.tags.clone(),
+                    "import_reference":new_journal_entry.import_reference.clone()
+                },
+                None,
+                session,
+            )
+            .await;
+
+        if journal_insert_result.is_err() {
+            return Err(mongodb::error::Error::custom(format!(
+                "could not update journal: {}",
+                journal_insert_result.unwrap_err()
+            )));
+        }
+
+        let mut booking_documents = Vec::new();
+        for booking_entry in new_debit_account_entries
+            .iter()
+            .chain(new_credit_account_entries.iter())
+        {
+            booking_documents.push(doc! {
+                "booking_entry_id":mongodb::bson::Binary::from_uuid(booking_entry.id),
+                "user_id": user_id_value.clone(),
+                "finance_account_id":mongodb::bson::Binary::from_uuid(booking_entry.finance_account_id),
+                "finance_journal_diary_id":journal_diary_entry_id_value.clone(),
+                "booking_type":booking_entry.booking_type.to_int(),
+                "booking_time":booking_entry.booking_time,
+                "amount":booking_entry.amount as i64,
+                "title":booking_entry.title.clone(),
+                "description":booking_entry.description.clone(),
+                "tags":booking_entry.tags.clone()
+            });
+        }
+
+        let booking_insert_result = booking_entries_collection
+            .insert_many_with_session(booking_documents, None, session)
+            .await;
+
+        if booking_insert_result.is_err() {
+            return Err(mongodb::error::Error::custom(format!(
+                "could not update bookings: {}",
+                booking_insert_result.unwrap_err()
+            )));
+        }
+
+        let return_object = FinanceSplitBookingResult {
+            journal_entry: new_journal_entry,
+            debit_account_entries: new_debit_account_entries,
+            credit_account_entries: new_credit_account_entries,
+        };
+        Ok(return_object)
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceAccountingFunctions for DbHandlerSqlite {
+    async fn finance_journal_entry_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        tag: Option<String>,
+    ) -> Result<Vec<FinanceJournalEntry>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let mut sql_statement = "SELECT * FROM FinanceJournalDiary WHERE user_id = ?".to_string();
+        let mut query_params = vec![user_id.to_string()];
+        if booking_time_from.is_some() {
+            sql_statement.push_str(" AND booking_time >= ?");
+            query_params.push(DbHandlerSqlite::format_datetime(
+                &booking_time_from.unwrap(),
+            ));
+        }
+        if booking_time_till.is_some() {
+            sql_statement.push_str(" AND booking_time <= ?");
+            query_params.push(DbHandlerSqlite::format_datetime(
+                &booking_time_till.unwrap(),
+            ));
+        }
+        if tag.is_some() {
+            // matches when the tags array contains the given tag
+            sql_statement.push_str(" AND EXISTS (SELECT 1 FROM json_each(tags) WHERE value = ?)");
+            query_params.push(tag.unwrap());
+        }
+        sql_statement.push_str(" ORDER BY booking_time, running_number");
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            &sql_statement,
+            params_from_iter(query_params),
+            DbHandlerSqlite::read_journal_entry_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+
+        Ok(query_result.unwrap())
+    }
+
+    async fn finance_account_booking_entry_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        search_options: Vec<FinanceAccountBookingEntryListSearchOption>,
+    ) -> Result<Vec<FinanceAccountBookingEntry>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        for search_option in &search_options {
+            let account_check_result = DbHandlerSqlite::check_account_exists(
+                &connection,
+                user_id,
+                &search_option.finance_account_id,
+            );
+            if account_check_result.is_err() {
+                return Err(account_check_result.unwrap_err());
+            }
+            if !account_check_result.unwrap() {
+                return Err(format!(
+                    "account {} not avaiable",
+                    search_option.finance_account_id
+                ));
+            }
+        }
+
+        let mut booking_entries_list = Vec::new();
+        for search_option in &search_options {
+            let mut sql_statement =
+                "SELECT * FROM BookingEntries WHERE user_id = ? AND finance_account_id = ?"
+                    .to_string();
+            let mut query_params = vec![
+                user_id.to_string(),
+                search_option.finance_account_id.to_string(),
+            ];
+            if search_option.booking_time_from.is_some() {
+                sql_statement.push_str(" AND booking_time >= ?");
+                query_params.push(DbHandlerSqlite::format_datetime(
+                    &search_option.booking_time_from.unwrap(),
+                ));
+            }
+            if search_option.booking_time_till.is_some() {
+                sql_statement.push_str(" AND booking_time <= ?");
+                query_params.push(DbHandlerSqlite::format_datetime(
+                    &search_option.booking_time_till.unwrap(),
+                ));
+            }
+            // entries with the same booking time follow the running number of their journal entry
+            sql_statement.push_str(
+                " ORDER BY booking_time, (SELECT running_number FROM FinanceJournalDiary
+                WHERE FinanceJournalDiary.user_id = BookingEntries.user_id
+                AND FinanceJournalDiary.finance_journal_diary_id = BookingEntries.finance_journal_diary_id),
+                rowid",
+            );
+
+            let query_result = DbHandlerSqlite::query_list(
+                &connection,
+                &sql_statement,
+                params_from_iter(query_params),
+                DbHandlerSqlite::read_booking_entry_row,
+            );
+            if query_result.is_err() {
+                return Err(query_result.unwrap_err());
+            }
+            booking_entries_list.append(&mut query_result.unwrap());
+        }
+
+        Ok(booking_entries_list)
+    }
+
+    async fn finance_insert_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceBookingRequest,
+    ) -> Result<FinanceBookingResult, String> {
+        let split_request = FinanceSplitBookingRequest::from_booking_request(&action_to_insert);
+        let split_result = self
+            .finance_insert_split_booking_entry(conncetion_settings, user_id, split_request)
+            .await;
+        if split_result.is_err() {
+            return Err(split_result.unwrap_err());
+        }
+        let mut split_object = split_result.unwrap();
+        if split_object.debit_account_entries.len() != 1
+            || split_object.credit_account_entries.len() != 1
+        {
+            return Err("unexpected number of booking entries for simple entry".into());
+        }
+
+        let return_object = FinanceBookingResult {
+            journal_entry: split_object.journal_entry,
+            debit_account_entry: split_object.debit_account_entries.remove(0),
+            credit_account_entry: split_object.credit_account_entries.remove(0),
+        };
+        Ok(return_object)
+    }
+
+    async fn finance_insert_split_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let insert_result = self
+            .finance_insert_split_booking_entries(
+                conncetion_settings,
+                user_id,
+                Vec::from([action_to_insert]),
+            )
+            .await;
+        if insert_result.is_err() {
+            return Err(insert_result.unwrap_err());
+        }
+
+        Ok(insert_result.unwrap().remove(0))
+    }
+
+    async fn finance_insert_split_booking_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String> {
+        if actions_to_insert.is_empty() {
+            return Err("no booking entries given".into());
+        }
+
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let mut connection = connection_result.unwrap();
+
+        // a batch reports the first unknown account, a single entry reports the side of the leg
+        let is_batch = actions_to_insert.len() > 1;
+        for action_to_insert in &actions_to_insert {
+            if action_to_insert.debit_legs.is_empty() || action_to_insert.credit_legs.is_empty() {
+                return Err("at least one debit and one credit leg is required".into());
+            }
+            for (leg_index, booking_leg) in action_to_insert
+                .debit_legs
+                .iter()
+                .chain(action_to_insert.credit_legs.iter())
+                .enumerate()
+            {
+                let account_check_result = DbHandlerSqlite::check_account_exists(
+                    &connection,
+                    user_id,
+                    &booking_leg.finance_account_id,
+                );
+                if account_check_result.is_err() {
+                    return Err(account_check_result.unwrap_err());
+                }
+                if account_check_result.unwrap() {
+                    continue;
+                }
+                if is_batch {
+                    return Err(format!(
+                        "account {} is not available",
+                        booking_leg.finance_account_id
+                    ));
+                }
+                if leg_index < action_to_insert.debit_legs.len() {
+                    return Err("debit account is not available".into());
+                }
+                return Err("credit account is not available".into());
+            }
+        }
+
+        let transaction_result =
+            connection.transaction_with_behavior(TransactionBehavior::Immediate);
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_result.unwrap_err()
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let mut return_object = Vec::new();
+        for action_to_insert in actions_to_insert {
+            let write_result = DbHandlerSqlite::write_finance_split_booking_with_transaction(
+                &transaction,
+                user_id,
+                action_to_insert,
+                None,
+            );
+            if write_result.is_err() {
+                return Err(write_result.unwrap_err());
+            }
+            return_object.push(write_result.unwrap());
+        }
+
+        let commit_result = transaction.commit();
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                commit_result.unwrap_err()
+            ));
+        }
+
+        Ok(return_object)
+    }
+
+    async fn finance_reverse_journal_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let mut connection = connection_result.unwrap();
+
+        // checks and writes in one transaction, so the original entry can not be reversed twice
+        let transaction_result =
+            connection.transaction_with_behavior(TransactionBehavior::Immediate);
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_result.unwrap_err()
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let original_entry_result = DbHandlerSqlite::query_list(
+            &transaction,
+            "SELECT * FROM FinanceJournalDiary WHERE user_id = ?1 AND finance_journal_diary_id = ?2",
+            [user_id.to_string(), journal_entry_id.to_string()],
+            DbHandlerSqlite::read_journal_entry_row,
+        );
+        if original_entry_result.is_err() {
+            return Err(original_entry_result.unwrap_err());
+        }
+        let mut original_entry_list = original_entry_result.unwrap();
+        if original_entry_list.is_empty() {
+            return Err(format!("journal entry {} not found", journal_entry_id));
+        }
+        let original_entry = original_entry_list.remove(0);
+        if original_entry.reversed_by_journal_entry_id.is_some() {
+            return Err("journal entry is already reversed".into());
+        }
+        if original_entry.reverse_of_journal_entry_id.is_some() {
+            return Err("a reversal entry can not be reversed".into());
+        }
+        if original_entry.is_saldo {
+            return Err("saldo entries can not be reversed".into());
+        }
+        if original_entry.is_closing {
+            return Err("closing entries can not be reversed".into());
+        }
+
+        let booking_entries_result = DbHandlerSqlite::query_list(
+            &transaction,
+            "SELECT * FROM BookingEntries WHERE user_id = ?1 AND finance_journal_diary_id = ?2 ORDER BY rowid",
+            [user_id.to_string(), journal_entry_id.to_string()],
+            DbHandlerSqlite::read_booking_entry_row,
+        );
+        if booking_entries_result.is_err() {
+            return Err(booking_entries_result.unwrap_err());
+        }
+
+        let mut debit_legs = Vec::new();
+        let mut credit_legs = Vec::new();
+        for booking_entry in booking_entries_result.unwrap() {
+            if booking_entry.reconciliation_id.is_some() {
+                return Err(
+                    "journal entry contains reconciled booking entries, unreconcile them first"
+                        .into(),
+                );
+            }
+            let later_saldo_result = transaction.query_row(
+                "SELECT COUNT(*) FROM BookingEntries WHERE user_id = ?1 AND finance_account_id = ?2
+                AND booking_type IN (?3, ?4) AND booking_time >= ?5",
+                params![
+                    user_id.to_string(),
+                    booking_entry.finance_account_id.to_string(),
+                    BookingEntryType::SaldoCredit.to_int(),
+                    BookingEntryType::SaldoDebit.to_int(),
+                    DbHandlerSqlite::format_datetime(&booking_time),
+                ],
+                |row| row.get::<_, i64>(0),
+            );
+            if later_saldo_result.is_err() {
+                return Err(later_saldo_result.unwrap_err().to_string());
+            }
+            if later_saldo_result.unwrap() > 0 {
+                return Err("Can not insert reversal before saldo of an involved account".into());
+            }
+            // debit and credit are swapped for the reversal
+            let reversed_leg = FinanceBookingLeg {
+                finance_account_id: booking_entry.finance_account_id,
+                amount: booking_entry.amount,
+            };
+            if booking_entry.booking_type.eq(&BookingEntryType::Debit) {
+                credit_legs.push(reversed_leg);
+            } else {
+                debit_legs.push(reversed_leg);
+            }
+        }
+
+        let reversal_request = FinanceSplitBookingRequest {
+            is_saldo: false,
+            is_closing: false,
+            debit_legs,
+            credit_legs,
+            booking_time,
+            title: format!(
+                "Reversal of #{}: {}",
+                original_entry.running_number, original_entry.title
+            ),
+            description: reason.clone(),
+            tags: original_entry.tags.clone(),
+            import_reference: None,
+        };
+        let write_result = DbHandlerSqlite::write_finance_split_booking_with_transaction(
+            &transaction,
+            user_id,
+            reversal_request,
+            Some(journal_entry_id.clone()),
+        );
+        if write_result.is_err() {
+            return Err(write_result.unwrap_err());
+        }
+        let return_object = write_result.unwrap();
+
+        let update_result = transaction.execute(
+            "UPDATE FinanceJournalDiary SET reversed_by_journal_entry_id = ?1
+            WHERE user_id = ?2 AND finance_journal_diary_id = ?3",
+            params![
+                return_object.journal_entry.id.to_string(),
+                user_id.to_string(),
+                journal_entry_id.to_string()
+            ],
+        );
+        if update_result.is_err() {
+            return Err(format!(
+                "could not mark original journal entry as reversed: {}",
+                update_result.unwrap_err()
+            ));
+        }
+
+        let commit_result = transaction.commit();
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                commit_result.unwrap_err()
+            ));
+        }
+
+        Ok(return_object)
+    }
+
+    async fn finance_get_last_saldo_account_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        list_account_ids: Option<Vec<Uuid>>,
+    ) -> Result<HashMap<Uuid, FinanceAccountBookingEntry>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM BookingEntries WHERE user_id = ?1 AND booking_type IN (?2, ?3)
+            ORDER BY booking_time, (SELECT running_number FROM FinanceJournalDiary
+            WHERE FinanceJournalDiary.user_id = BookingEntries.user_id
+            AND FinanceJournalDiary.finance_journal_diary_id = BookingEntries.finance_journal_diary_id),
+            rowid",
+            params![
+                user_id.to_string(),
+                BookingEntryType::SaldoCredit.to_int(),
+                BookingEntryType::SaldoDebit.to_int(),
+            ],
+            DbHandlerSqlite::read_booking_entry_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+
+        // ordered by booking time and running number, so the last saldo entry of each account remains
+        let mut return_object = HashMap::new();
+        for saldo_entry in query_result.unwrap() {
+            if list_account_ids.is_some()
+                && !list_account_ids
+                    .as_ref()
+                    .unwrap()
+                    .contains(&saldo_entry.finance_account_id)
+            {
+                continue;
+            }
+            return_object.insert(saldo_entry.finance_account_id, saldo_entry);
+        }
+
+        Ok(return_object)
+    }
+
+    async fn finance_get_journal_max_number(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<u64, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection
+            .query_row(
+                "SELECT booking_journal_max_number FROM CounterCollection WHERE user_id = ?1",
+                [user_id.to_string()],
+                |row| row.get::<_, i64>(0),
+            )
+            .optional();
+        if query_result.is_err() {
+            return Err(format!(
+                "Error getting counter record: {}",
+                query_result.unwrap_err()
+            ));
+        }
+        // the counter record is created with the first booking
+        return Ok(query_result.unwrap().unwrap_or(0) as u64);
+    }
+
+    async fn finance_restore_archive(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let mut connection = connection_result.unwrap();
+
+        let transaction_result =
+            connection.transaction_with_behavior(TransactionBehavior::Immediate);
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_result.unwrap_err()
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let existing_journal_count = transaction.query_row(
+            "SELECT COUNT(*) FROM FinanceJournalDiary WHERE user_id = ?1",
+            [user_id.to_string()],
+            |row| row.get::<_, i64>(0),
+        );
+        if existing_journal_count.is_err() {
+            return Err(existing_journal_count.unwrap_err().to_string());
+        }
+        if existing_journal_count.unwrap() > 0 {
+            return Err(
+                "the journal is not empty, entries can only be restored into an empty journal"
+                    .into(),
+            );
+        }
+
+        let ids_check_result =
+            DbHandlerSqlite::check_archive_ids_of_other_users(&transaction, user_id, archive);
+        if ids_check_result.is_err() {
+            return Err(ids_check_result.unwrap_err());
+        }
+
+        for account_type in &archive.account_types {
+            let upsert_result = DbHandlerSqlite::upsert_finance_account_type_row(
+                &transaction,
+                user_id,
+                account_type,
+            );
+            if upsert_result.is_err() {
+                return Err(format!(
+                    "could not write account type {}: {}",
+                    account_type.title,
+                    upsert_result.unwrap_err()
+                ));
+            }
+        }
+        for account in &archive.accounts {
+            let upsert_result =
+                DbHandlerSqlite::upsert_finance_account_row(&transaction, user_id, account);
+            if upsert_result.is_err() {
+                return Err(format!(
+                    "could not write account {}: {}",
+                    account.title,
+                    upsert_result.unwrap_err()
+                ));
+            }
+        }
+        for journal_entry in &archive.journal_entries {
+            let insert_result =
+                DbHandlerSqlite::insert_journal_entry_row(&transaction, user_id, journal_entry);
+            if insert_result.is_err() {
+                return Err(insert_result.unwrap_err());
+            }
+        }
+        for booking_entry in &archive.booking_entries {
+            let insert_result =
+                DbHandlerSqlite::insert_booking_entry_row(&transaction, user_id, booking_entry);
+            if insert_result.is_err() {
+                return Err(insert_result.unwrap_err());
+            }
+        }
+
+        let counter_result = transaction.execute(
+            "INSERT INTO CounterCollection (user_id, counter_entry_id, booking_journal_max_number)
+            VALUES (?1, ?2, ?3)
+            ON CONFLICT (user_id) DO UPDATE SET booking_journal_max_number = excluded.booking_journal_max_number",
+            params![
+                user_id.to_string(),
+                Uuid::new().to_string(),
+                archive.booking_journal_max_number as i64
+            ],
+        );
+        if counter_result.is_err() {
+            return Err(format!(
+                "could not set counter record: {}",
+                counter_result.unwrap_err()
+            ));
+        }
+
+        let commit_result = transaction.commit();
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                commit_result.unwrap_err()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl DbHandlerSqlite {
+    /// returns an error for the first id of the archive which is already used by another user
+    fn check_archive_ids_of_other_users(
+        connection: &Connection,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), String> {
+        let id_lists = [
+            (
+                "FinanceAccountTypes",
+                "finance_account_type_id",
+                "account type",
+                archive
+                    .account_types
+                    .iter()
+                    .map(|elem| elem.id)
+                    .collect::<Vec<Uuid>>(),
+            ),
+            (
+                "FinanceAccounts",
+                "finance_account_id",
+                "account",
+                archive.accounts.iter().map(|elem| elem.id).collect(),
+            ),
+            (
+                "FinanceJournalDiary",
+                "finance_journal_diary_id",
+                "journal entry",
+                archive.journal_entries.iter().map(|elem| elem.id).collect(),
+            ),
+            (
+                "BookingEntries",
+                "booking_entry_id",
+                "booking entry",
+                archive.booking_entries.iter().map(|elem| elem.id).collect(),
+            ),
+        ];
+        for (table_name, id_column_name, object_name, ids) in id_lists {
+            let id_texts: Vec<String> = ids.iter().map(|elem| elem.to_string()).collect();
+            let query_result = connection
+                .query_row(
+                    &format!(
+                        "SELECT {1} FROM {0} WHERE user_id <> ?1
+                        AND {1} IN (SELECT value FROM json_each(?2)) LIMIT 1",
+                        table_name, id_column_name
+                    ),
+                    params![
+                        user_id.to_string(),
+                        DbHandlerSqlite::format_string_list(&id_texts)
+                    ],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional();
+            if query_result.is_err() {
+                return Err(query_result.unwrap_err().to_string());
+            }
+            if let Some(used_id) = query_result.unwrap() {
+                return Err(format!(
+                    "{} {} of the archive is used by another user",
+                    object_name, used_id
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    fn check_account_exists(
+        connection: &Connection,
+        user_id: &Uuid,
+        finance_account_id: &Uuid,
+    ) -> Result<bool, String> {
+        let query_result = connection.query_row(
+            "SELECT COUNT(*) FROM FinanceAccounts WHERE user_id = ?1 AND finance_account_id = ?2",
+            [user_id.to_string(), finance_account_id.to_string()],
+            |row| row.get::<_, i64>(0),
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        return Ok(query_result.unwrap() > 0);
+    }
+
+    pub(crate) fn read_journal_entry_row(row: &Row) -> Result<FinanceJournalEntry, String> {
+        let some_journal_entry_id_parse_result =
+            DbHandlerSqlite::get_uuid_from_row(row, "finance_journal_diary_id");
+        if some_journal_entry_id_parse_result.is_err() {
+            return Err(some_journal_entry_id_parse_result.unwrap_err());
+        }
+        let some_debit_account_id_parse_result =
+            DbHandlerSqlite::get_uuid_from_row(row, "debit_finance_account_id");
+        if some_debit_account_id_parse_result.is_err() {
+            return Err(some_debit_account_id_parse_result.unwrap_err());
+        }
+        let some_credit_account_id_parse_result =
+            DbHandlerSqlite::get_uuid_from_row(row, "credit_finance_account_id");
+        if some_credit_account_id_parse_result.is_err() {
+            return Err(some_credit_account_id_parse_result.unwrap_err());
+        }
+        let stored_booking_time = DbHandlerSqlite::get_datetime_from_row(row, "booking_time");
+        if stored_booking_time.is_err() {
+            return Err(stored_booking_time.unwrap_err());
+        }
+        let stored_amount = row.get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(stored_amount.unwrap_err().to_string());
+        }
+        let stored_running_number = row.get::<_, i64>("running_number");
+        if stored_running_number.is_err() {
+            return Err(stored_running_number.unwrap_err().to_string());
+        }
+        let stored_title = row.get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+        let stored_description = row.get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(stored_description.unwrap_err().to_string());
+        }
+        let stored_is_simple_entry = row.get::<_, bool>("is_simple_entry");
+        if stored_is_simple_entry.is_err() {
+            return Err(stored_is_simple_entry.unwrap_err().to_string());
+        }
+        let stored_is_saldo = row.get::<_, bool>("is_saldo");
+        if stored_is_saldo.is_err() {
+            return Err(stored_is_saldo.unwrap_err().to_string());
+        }
+        let stored_is_closing = row.get::<_, bool>("is_closing");
+        if stored_is_closing.is_err() {
+            return Err(stored_is_closing.unwrap_err().to_string());
+        }
+        let reverse_of_id_parse_result =
+            DbHandlerSqlite::get_optional_uuid_from_row(row, "reverse_of_journal_entry_id");
+        if reverse_of_id_parse_result.is_err() {
+            return Err(reverse_of_id_parse_result.unwrap_err());
+        }
+        let reversed_by_id_parse_result =
+            DbHandlerSqlite::get_optional_uuid_from_row(row, "reversed_by_journal_entry_id");
+        if reversed_by_id_parse_result.is_err() {
+            return Err(reversed_by_id_parse_result.unwrap_err());
+        }
+        let stored_tags = DbHandlerSqlite::get_string_list_from_row(row, "tags");
+        if stored_tags.is_err() {
+            return Err(stored_tags.unwrap_err());
+        }
+        let stored_import_reference = row.get::<_, Option<String>>("import_reference");
+        if stored_import_reference.is_err() {
+            return Err(stored_import_reference.unwrap_err().to_string());
+        }
+
+        return Ok(FinanceJournalEntry {
+            id: some_journal_entry_id_parse_result.unwrap(),
+            booking_time: stored_booking_time.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            is_simple_entry: stored_is_simple_entry.unwrap(),
+            is_saldo: stored_is_saldo.unwrap(),
+            is_closing: stored_is_closing.unwrap(),
+            debit_finance_account_id: some_debit_account_id_parse_result.unwrap(),
+            credit_finance_account_id: some_credit_account_id_parse_result.unwrap(),
+            running_number: stored_running_number.unwrap() as u64,
+            reverse_of_journal_entry_id: reverse_of_id_parse_result.unwrap(),
+            reversed_by_journal_entry_id: reversed_by_id_parse_result.unwrap(),
+            tags: stored_tags.unwrap(),
+            import_reference: stored_import_reference.unwrap(),
+        });
+    }
+
+    pub(crate) fn read_booking_entry_row(row: &Row) -> Result<FinanceAccountBookingEntry, String> {
+        let some_booking_entry_id_parse_result =
+            DbHandlerSqlite::get_uuid_from_row(row, "booking_entry_id");
+        if some_booking_entry_id_parse_result.is_err() {
+            return Err(some_booking_entry_id_parse_result.unwrap_err());
+        }
+        let some_account_id_parse_result =
+            DbHandlerSqlite::get_uuid_from_row(row, "finance_account_id");
+        if some_account_id_parse_result.is_err() {
+            return Err(some_account_id_parse_result.unwrap_err());
+        }
+        let some_journal_entry_id_parse_result =
+            DbHandlerSqlite::get_uuid_from_row(row, "finance_journal_diary_id");
+        if some_journal_entry_id_parse_result.is_err() {
+            return Err(some_journal_entry_id_parse_result.unwrap_err());
+        }
+        let stored_booking_type = row.get::<_, i32>("booking_type");
+        if stored_booking_type.is_err() {
+            return Err(stored_booking_type.unwrap_err().to_string());
+        }
+        let booking_type_parse_result =
+            BookingEntryType::get_from_int(stored_booking_type.unwrap());
+        if booking_type_parse_result.is_err() {
+            return Err(booking_type_parse_result.unwrap_err());
+        }
+        let stored_booking_time = DbHandlerSqlite::get_datetime_from_row(row, "booking_time");
+        if stored_booking_time.is_err() {
+            return Err(stored_booking_time.unwrap_err());
+        }
+        let stored_amount = row.get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(stored_amount.unwrap_err().to_string());
+        }
+        let stored_title = row.get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+        let stored_description = row.get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(stored_description.unwrap_err().to_string());
+        }
+        let stored_tags = DbHandlerSqlite::get_string_list_from_row(row, "tags");
+        if stored_tags.is_err() {
+            return Err(stored_tags.unwrap_err());
+        }
+        let reconciliation_id_parse_result =
+            DbHandlerSqlite::get_optional_uuid_from_row(row, "reconciliation_id");
+        if reconciliation_id_parse_result.is_err() {
+            return Err(reconciliation_id_parse_result.unwrap_err());
+        }
+
+        return Ok(FinanceAccountBookingEntry {
+            id: some_booking_entry_id_parse_result.unwrap(),
+            finance_account_id: some_account_id_parse_result.unwrap(),
+            finance_journal_diary_id: some_journal_entry_id_parse_result.unwrap(),
+            booking_type: booking_type_parse_result.unwrap(),
+            booking_time: stored_booking_time.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            tags: stored_tags.unwrap(),
+            reconciliation_id: reconciliation_id_parse_result.unwrap(),
+        });
+    }
+
+    fn insert_journal_entry_row(
+        connection: &Connection,
+        user_id: &Uuid,
+        journal_entry: &FinanceJournalEntry,
+    ) -> Result<(), String> {
+        let insert_result = connection.execute(
+            "INSERT INTO FinanceJournalDiary (finance_journal_diary_id, user_id, is_simple_entry, is_saldo,
+            is_closing, debit_finance_account_id, credit_finance_account_id, running_number, booking_time,
+            amount, title, description, reverse_of_journal_entry_id, reversed_by_journal_entry_id, tags,
+            import_reference) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+            params![
+                journal_entry.id.to_string(),
+                user_id.to_string(),
+                journal_entry.is_simple_entry,
+                journal_entry.is_saldo,
+                journal_entry.is_closing,
+                journal_entry.debit_finance_account_id.to_string(),
+                journal_entry.credit_finance_account_id.to_string(),
+                journal_entry.running_number as i64,
+                DbHandlerSqlite::format_datetime(&journal_entry.booking_time),
+                journal_entry.amount as i64,
+                &journal_entry.title,
+                &journal_entry.description,
+                journal_entry
+                    .reverse_of_journal_entry_id
+                    .map(|elem| elem.to_string()),
+                journal_entry
+                    .reversed_by_journal_entry_id
+                    .map(|elem| elem.to_string()),
+                DbHandlerSqlite::format_string_list(&journal_entry.tags),
+                &journal_entry.import_reference,
+            ],
+        );
+        if insert_result.is_err() {
+            return Err(format!(
+                "could not update journal: {}",
+                insert_result.unwrap_err()
+            ));
+        }
+        return Ok(());
+    }
+
+    fn insert_booking_entry_row(
+        connection: &Connection,
+        user_id: &Uuid,
+        booking_entry: &FinanceAccountBookingEntry,
+    ) -> Result<(), String> {
+        let insert_result = connection.execute(
+            "INSERT INTO BookingEntries (booking_entry_id, user_id, finance_account_id, finance_journal_diary_id,
+            booking_type, booking_time, amount, title, description, tags, reconciliation_id)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)",
+            params![
+                booking_entry.id.to_string(),
+                user_id.to_string(),
+                booking_entry.finance_account_id.to_string(),
+                booking_entry.finance_journal_diary_id.to_string(),
+                booking_entry.booking_type.to_int(),
+                DbHandlerSqlite::format_datetime(&booking_entry.booking_time),
+                booking_entry.amount as i64,
+                &booking_entry.title,
+                &booking_entry.description,
+                DbHandlerSqlite::format_string_list(&booking_entry.tags),
+                booking_entry.reconciliation_id.map(|elem| elem.to_string()),
+            ],
+        );
+        if insert_result.is_err() {
+            return Err(format!(
+                "could not update bookings: {}",
+                insert_result.unwrap_err()
+            ));
+        }
+        return Ok(());
+    }
+
+    /// the accounts have to be checked before, the running number is taken from the counter record
+    /// which is created on the first booking
+    fn write_finance_split_booking_with_transaction(
+        transaction: &Transaction,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+        reverse_of_journal_entry_id: Option<Uuid>,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let counter_result = transaction.query_row(
+            "INSERT INTO CounterCollection (user_id, counter_entry_id, booking_journal_max_number)
+            VALUES (?1, ?2, (SELECT IFNULL(MAX(running_number), 0) FROM FinanceJournalDiary WHERE user_id = ?1) + 1)
+            ON CONFLICT (user_id) DO UPDATE SET booking_journal_max_number = booking_journal_max_number + 1
+            RETURNING booking_journal_max_number",
+            params![user_id.to_string(), Uuid::new().to_string()],
+            |row| row.get::<_, i64>(0),
+        );
+        if counter_result.is_err() {
+            return Err(format!(
+                "could not update max number record: {}",
+                counter_result.unwrap_err()
+            ));
+        }
+        let new_running_number = counter_result.unwrap() as u64;
+
+        let journal_amount_result = action_to_insert.sum_debit_amount();
+        if journal_amount_result.is_err() {
+            return Err(journal_amount_result.unwrap_err());
+        }
+        // for entries with more than two accounts the first leg of each side is stored as main account
+        let new_journal_entry = FinanceJournalEntry {
+            id: Uuid::new(),
+            is_simple_entry: action_to_insert.is_simple_entry(),
+            is_saldo: action_to_insert.is_saldo,
+            is_closing: action_to_insert.is_closing,
+            debit_finance_account_id: action_to_insert.debit_legs[0].finance_account_id,
+            credit_finance_account_id: action_to_insert.credit_legs[0].finance_account_id,
+            running_number: new_running_number,
+            booking_time: action_to_insert.booking_time,
+            amount: journal_amount_result.unwrap(),
+            title: action_to_insert.title.clone(),
+            description: action_to_insert.description.clone(),
+            reverse_of_journal_entry_id,
+            reversed_by_journal_entry_id: None,
+            tags: action_to_insert.tags.clone(),
+            import_reference: action_to_insert.import_reference.clone(),
+        };
+
+        let credit_booking_type = if action_to_insert.is_saldo {
+            BookingEntryType::SaldoCredit
+        } else {
+            BookingEntryType::Credit
+        };
+        let debit_booking_type = if action_to_insert.is_saldo {
+            BookingEntryType::SaldoDebit
+        } else {
+            BookingEntryType::Debit
+        };
+        let mut new_debit_account_entries = Vec::new();
+        for debit_leg in &action_to_insert.debit_legs {
+            new_debit_account_entries.push(FinanceAccountBookingEntry {
+                id: Uuid::new(),
+                finance_account_id: debit_leg.finance_account_id,
+                finance_journal_diary_id: new_journal_entry.id.clone(),
+                booking_type: debit_booking_type.clone(),
+                booking_time: action_to_insert.booking_time,
+                amount: debit_leg.amount,
+                title: action_to_insert.title.clone(),
+                description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
+            });
+        }
+        let mut new_credit_account_entries = Vec::new();
+        for credit_leg in &action_to_insert.credit_legs {
+            new_credit_account_entries.push(FinanceAccountBookingEntry {
+                id: Uuid::new(),
+                finance_account_id: credit_leg.finance_account_id,
+                finance_journal_diary_id: new_journal_entry.id.clone(),
+                booking_type: credit_booking_type.clone(),
+                booking_time: action_to_insert.booking_time,
+                amount: credit_leg.amount,
+                title: action_to_insert.title.clone(),
+                description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
+            });
+        }
+
+        let journal_insert_result =
+            DbHandlerSqlite::insert_journal_entry_row(transaction, user_id, &new_journal_entry);
+        if journal_insert_result.is_err() {
+            return Err(journal_insert_result.unwrap_err());
+        }
+        for booking_entry in new_debit_account_entries
+            .iter()
+            .chain(new_credit_account_entries.iter())
+        {
+            let booking_insert_result =
+                DbHandlerSqlite::insert_booking_entry_row(transaction, user_id, booking_entry);
+            if booking_insert_result.is_err() {
+                return Err(booking_insert_result.unwrap_err());
+            }
+        }
+
+        let return_object = FinanceSplitBookingResult {
+            journal_entry: new_journal_entry,
+            debit_account_entries: new_debit_account_entries,
+            credit_account_entries: new_credit_account_entries,
+        };
+        Ok(return_object)
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceAccountingFunctions for DbHandlerPostgres {
+    async fn finance_journal_entry_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        tag: Option<String>,
+    ) -> Result<Vec<FinanceJournalEntry>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceJournalDiary WHERE user_id = $1
+            AND ($2::TIMESTAMPTZ IS NULL OR booking_time >= $2)
+            AND ($3::TIMESTAMPTZ IS NULL OR booking_time <= $3)
+            AND ($4::TEXT IS NULL OR $4 = ANY (tags)) ORDER BY booking_time, running_number",
+            &[
+                &user_id.to_uuid_1(),
+                &booking_time_from,
+                &booking_time_till,
+                &tag,
+            ],
+            DbHandlerPostgres::read_journal_entry_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+
+        Ok(query_result.unwrap())
+    }
+
+    async fn finance_account_booking_entry_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        search_options: Vec<FinanceAccountBookingEntryListSearchOption>,
+    ) -> Result<Vec<FinanceAccountBookingEntry>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        for search_option in &search_options {
+            let account_check_result = DbHandlerPostgres::check_account_exists(
+                &client,
+                user_id,
+                &search_option.finance_account_id,
+            )
+            .await;
+            if account_check_result.is_err() {
+                return Err(DbHandlerPostgres::format_error(
+                    &account_check_result.unwrap_err(),
+                ));
+            }
+            if !account_check_result.unwrap() {
+                return Err(format!(
+                    "account {} not avaiable",
+                    search_option.finance_account_id
+                ));
+            }
+        }
+
+        let mut booking_entries_list = Vec::new();
+        for search_option in &search_options {
+            let query_result = DbHandlerPostgres::query_list(
+                &client,
+                "SELECT * FROM BookingEntries WHERE user_id = $1 AND finance_account_id = $2
+                AND ($3::TIMESTAMPTZ IS NULL OR booking_time >= $3)
+                AND ($4::TIMESTAMPTZ IS NULL OR booking_time <= $4)
+                ORDER BY booking_time, (SELECT running_number FROM FinanceJournalDiary
+                WHERE FinanceJournalDiary.user_id = BookingEntries.user_id
+                AND FinanceJournalDiary.finance_journal_diary_id = BookingEntries.finance_journal_diary_id),
+                insert_order",
+                &[
+                    &user_id.to_uuid_1(),
+                    &search_option.finance_account_id.to_uuid_1(),
+                    &search_option.booking_time_from,
+                    &search_option.booking_time_till,
+                ],
+                DbHandlerPostgres::read_booking_entry_row,
+            )
+            .await;
+            if query_result.is_err() {
+                return Err(query_result.unwrap_err());
+            }
+            booking_entries_list.append(&mut query_result.unwrap());
+        }
+
+        Ok(booking_entries_list)
+    }
+
+    async fn finance_insert_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceBookingRequest,
+    ) -> Result<FinanceBookingResult, String> {
+        let split_request = FinanceSplitBookingRequest::from_booking_request(&action_to_insert);
+        let split_result = self
+            .finance_insert_split_booking_entry(conncetion_settings, user_id, split_request)
+            .await;
+        if split_result.is_err() {
+            return Err(split_result.unwrap_err());
+        }
+        let mut split_object = split_result.unwrap();
+        if split_object.debit_account_entries.len() != 1
+            || split_object.credit_account_entries.len() != 1
+        {
+            return Err("unexpected number of booking entries for simple entry".into());
+        }
+
+        let return_object = FinanceBookingResult {
+            journal_entry: split_object.journal_entry,
+            debit_account_entry: split_object.debit_account_entries.remove(0),
+            credit_account_entry: split_object.credit_account_entries.remove(0),
+        };
+        Ok(return_object)
+    }
+
+    async fn finance_insert_split_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let insert_result = self
+            .finance_insert_split_booking_entries(
+                conncetion_settings,
+                user_id,
+                Vec::from([action_to_insert]),
+            )
+            .await;
+        if insert_result.is_err() {
+            return Err(insert_result.unwrap_err());
+        }
+
+        Ok(insert_result.unwrap().remove(0))
+    }
+
+    async fn finance_insert_split_booking_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String> {
+        if actions_to_insert.is_empty() {
+            return Err("no booking entries given".into());
+        }
+        for action_to_insert in &actions_to_insert {
+            if action_to_insert.debit_legs.is_empty() || action_to_insert.credit_legs.is_empty() {
+                return Err("at least one debit and one credit leg is required".into());
+            }
+        }
+
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let mut client = client_result.unwrap();
+
+        let mut attempt_count = 0;
+        loop {
+            attempt_count += 1;
+            let execute_result =
+                DbHandlerPostgres::execute_finance_insert_booking_entries_with_transaction(
+                    &mut client,
+                    user_id,
+                    actions_to_insert.clone(),
+                )
+                .await;
+            if execute_result.is_ok() {
+                return Ok(execute_result.unwrap());
+            }
+            let error_var = execute_result.unwrap_err();
+            if !error_var.retry_possible
+                || attempt_count >= DbHandlerPostgres::TRANSACTION_ATTEMPT_LIMIT
+            {
+                return Err(error_var.message);
+            }
+            debug!(target:"app::FinanceOverView","booking attempt {} failed, trying again: {}",attempt_count,error_var.message);
+        }
+    }
+
+    async fn finance_reverse_journal_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let mut client = client_result.unwrap();
+
+        let mut attempt_count = 0;
+        loop {
+            attempt_count += 1;
+            let execute_result =
+                DbHandlerPostgres::execute_finance_reverse_journal_entry_with_transaction(
+                    &mut client,
+                    user_id,
+                    journal_entry_id,
+                    booking_time,
+                    reason,
+                )
+                .await;
+            if execute_result.is_ok() {
+                return Ok(execute_result.unwrap());
+            }
+            let error_var = execute_result.unwrap_err();
+            if !error_var.retry_possible
+                || attempt_count >= DbHandlerPostgres::TRANSACTION_ATTEMPT_LIMIT
+            {
+                return Err(error_var.message);
+            }
+            debug!(target:"app::FinanceOverView","reversal attempt {} failed, trying again: {}",attempt_count,error_var.message);
+        }
+    }
+
+    async fn finance_get_last_saldo_account_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        list_account_ids: Option<Vec<Uuid>>,
+    ) -> Result<HashMap<Uuid, FinanceAccountBookingEntry>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM BookingEntries WHERE user_id = $1 AND booking_type IN ($2, $3)
+            ORDER BY booking_time, (SELECT running_number FROM FinanceJournalDiary
+            WHERE FinanceJournalDiary.user_id = BookingEntries.user_id
+            AND FinanceJournalDiary.finance_journal_diary_id = BookingEntries.finance_journal_diary_id),
+            insert_order",
+            &[
+                &user_id.to_uuid_1(),
+                &BookingEntryType::SaldoCredit.to_int(),
+                &BookingEntryType::SaldoDebit.to_int(),
+            ],
+            DbHandlerPostgres::read_booking_entry_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+
+        // ordered by booking time and running number, so the last saldo entry of each account remains
+        let mut return_object = HashMap::new();
+        for saldo_entry in query_result.unwrap() {
+            if list_account_ids.is_some()
+                && !list_account_ids
+                    .as_ref()
+                    .unwrap()
+                    .contains(&saldo_entry.finance_account_id)
+            {
+                continue;
+            }
+            return_object.insert(saldo_entry.finance_account_id, saldo_entry);
+        }
+
+        Ok(return_object)
+    }
+
+    async fn finance_get_journal_max_number(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<u64, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_opt(
+                "SELECT booking_journal_max_number FROM CounterCollection WHERE user_id = $1",
+                &[&user_id.to_uuid_1()],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(format!(
+                "Error getting counter record: {}",
+                DbHandlerPostgres::format_error(&query_result.unwrap_err())
+            ));
+        }
+        // the counter record is created with the first booking
+        let counter_option = query_result.unwrap();
+        if counter_option.is_none() {
+            return Ok(0);
+        }
+        return Ok(counter_option.unwrap().get::<_, i64>(0) as u64);
+    }
+
+    async fn finance_restore_archive(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let mut client = client_result.unwrap();
+
+        let mut attempt_count = 0;
+        loop {
+            attempt_count += 1;
+            let execute_result =
+                DbHandlerPostgres::execute_finance_restore_archive_with_transaction(
+                    &mut client,
+                    user_id,
+                    archive,
+                )
+                .await;
+            if execute_result.is_ok() {
+                return Ok(());
+            }
+            let error_var = execute_result.unwrap_err();
+            if !error_var.retry_possible
+                || attempt_count >= DbHandlerPostgres::TRANSACTION_ATTEMPT_LIMIT
+            {
+                return Err(error_var.message);
+            }
+            debug!(target:"app::FinanceOverView","restore attempt {} failed, trying again: {}",attempt_count,error_var.message);
+        }
+    }
+}
+
+impl DbHandlerPostgres {
+    async fn check_account_exists(
+        client: &impl GenericClient,
+        user_id: &Uuid,
+        finance_account_id: &Uuid,
+    ) -> Result<bool, tokio_postgres::Error> {
+        let query_result = client
+            .query_one(
+                "SELECT COUNT(*) FROM FinanceAccounts WHERE user_id = $1 AND finance_account_id = $2",
+                &[&user_id.to_uuid_1(), &finance_account_id.to_uuid_1()],
+            )
             .await?;
-        let counter_document = max_number_execute_result.unwrap();
-        let new_running_number_result = counter_document.get_i64("booking_journal_max_number");
-        if new_running_number_result.is_err() {
-            return Err(mongodb::error::Error::custom(format!(
-                "could not get new max number: {}",
-                new_running_number_result.unwrap_err()
-            )));
+        return Ok(query_result.get::<_, i64>(0) > 0);
+    }
+
+    pub(crate) fn read_journal_entry_row(row: &PostgresRow) -> Result<FinanceJournalEntry, String> {
+        let some_journal_entry_id_parse_result =
+            DbHandlerPostgres::get_uuid_from_row(row, "finance_journal_diary_id");
+        if some_journal_entry_id_parse_result.is_err() {
+            return Err(some_journal_entry_id_parse_result.unwrap_err());
+        }
+        let some_debit_account_id_parse_result =
+            DbHandlerPostgres::get_uuid_from_row(row, "debit_finance_account_id");
+        if some_debit_account_id_parse_result.is_err() {
+            return Err(some_debit_account_id_parse_result.unwrap_err());
+        }
+        let some_credit_account_id_parse_result =
+            DbHandlerPostgres::get_uuid_from_row(row, "credit_finance_account_id");
+        if some_credit_account_id_parse_result.is_err() {
+            return Err(some_credit_account_id_parse_result.unwrap_err());
+        }
+        let stored_booking_time = row.try_get::<_, DateTime<Utc>>("booking_time");
+        if stored_booking_time.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_booking_time.unwrap_err(),
+            ));
+        }
+        let stored_amount = row.try_get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_amount.unwrap_err()));
+        }
+        let stored_running_number = row.try_get::<_, i64>("running_number");
+        if stored_running_number.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_running_number.unwrap_err(),
+            ));
+        }
+        let stored_title = row.try_get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+        let stored_description = row.try_get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_description.unwrap_err(),
+            ));
+        }
+        let stored_is_simple_entry = row.try_get::<_, bool>("is_simple_entry");
+        if stored_is_simple_entry.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_is_simple_entry.unwrap_err(),
+            ));
+        }
+        let stored_is_saldo = row.try_get::<_, bool>("is_saldo");
+        if stored_is_saldo.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_is_saldo.unwrap_err(),
+            ));
+        }
+        let stored_is_closing = row.try_get::<_, bool>("is_closing");
+        if stored_is_closing.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_is_closing.unwrap_err(),
+            ));
+        }
+        let reverse_of_id_parse_result =
+            DbHandlerPostgres::get_optional_uuid_from_row(row, "reverse_of_journal_entry_id");
+        if reverse_of_id_parse_result.is_err() {
+            return Err(reverse_of_id_parse_result.unwrap_err());
+        }
+        let reversed_by_id_parse_result =
+            DbHandlerPostgres::get_optional_uuid_from_row(row, "reversed_by_journal_entry_id");
+        if reversed_by_id_parse_result.is_err() {
+            return Err(reversed_by_id_parse_result.unwrap_err());
+        }
+        let stored_tags = row.try_get::<_, Vec<String>>("tags");
+        if stored_tags.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_tags.unwrap_err()));
+        }
+        let stored_import_reference = row.try_get::<_, Option<String>>("import_reference");
+        if stored_import_reference.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_import_reference.unwrap_err(),
+            ));
         }
-        let new_running_number = new_running_number_result.unwrap() as u64;
 
-        let journal_diary_entry_id = Uuid::new();
-        let journal_diary_entry_id_value =
-            mongodb::bson::Binary::from_uuid(journal_diary_entry_id.clone());
+        return Ok(FinanceJournalEntry {
+            id: some_journal_entry_id_parse_result.unwrap(),
+            booking_time: stored_booking_time.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            is_simple_entry: stored_is_simple_entry.unwrap(),
+            is_saldo: stored_is_saldo.unwrap(),
+            is_closing: stored_is_closing.unwrap(),
+            debit_finance_account_id: some_debit_account_id_parse_result.unwrap(),
+            credit_finance_account_id: some_credit_account_id_parse_result.unwrap(),
+            running_number: stored_running_number.unwrap() as u64,
+            reverse_of_journal_entry_id: reverse_of_id_parse_result.unwrap(),
+            reversed_by_journal_entry_id: reversed_by_id_parse_result.unwrap(),
+            tags: stored_tags.unwrap(),
+            import_reference: stored_import_reference.unwrap(),
+        });
+    }
+
+    pub(crate) fn read_booking_entry_row(
+        row: &PostgresRow,
+    ) -> Result<FinanceAccountBookingEntry, String> {
+        let some_booking_entry_id_parse_result =
+            DbHandlerPostgres::get_uuid_from_row(row, "booking_entry_id");
+        if some_booking_entry_id_parse_result.is_err() {
+            return Err(some_booking_entry_id_parse_result.unwrap_err());
+        }
+        let some_account_id_parse_result =
+            DbHandlerPostgres::get_uuid_from_row(row, "finance_account_id");
+        if some_account_id_parse_result.is_err() {
+            return Err(some_account_id_parse_result.unwrap_err());
+        }
+        let some_journal_entry_id_parse_result =
+            DbHandlerPostgres::get_uuid_from_row(row, "finance_journal_diary_id");
+        if some_journal_entry_id_parse_result.is_err() {
+            return Err(some_journal_entry_id_parse_result.unwrap_err());
+        }
+        let stored_booking_type = row.try_get::<_, i32>("booking_type");
+        if stored_booking_type.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_booking_type.unwrap_err(),
+            ));
+        }
+        let booking_type_parse_result =
+            BookingEntryType::get_from_int(stored_booking_type.unwrap());
+        if booking_type_parse_result.is_err() {
+            return Err(booking_type_parse_result.unwrap_err());
+        }
+        let stored_booking_time = row.try_get::<_, DateTime<Utc>>("booking_time");
+        if stored_booking_time.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_booking_time.unwrap_err(),
+            ));
+        }
+        let stored_amount = row.try_get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_amount.unwrap_err()));
+        }
+        let stored_title = row.try_get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+        let stored_description = row.try_get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_description.unwrap_err(),
+            ));
+        }
+        let stored_tags = row.try_get::<_, Vec<String>>("tags");
+        if stored_tags.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_tags.unwrap_err()));
+        }
+        let reconciliation_id_parse_result =
+            DbHandlerPostgres::get_optional_uuid_from_row(row, "reconciliation_id");
+        if reconciliation_id_parse_result.is_err() {
+            return Err(reconciliation_id_parse_result.unwrap_err());
+        }
+
+        return Ok(FinanceAccountBookingEntry {
+            id: some_booking_entry_id_parse_result.unwrap(),
+            finance_account_id: some_account_id_parse_result.unwrap(),
+            finance_journal_diary_id: some_journal_entry_id_parse_result.unwrap(),
+            booking_type: booking_type_parse_result.unwrap(),
+            booking_time: stored_booking_time.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+            tags: stored_tags.unwrap(),
+            reconciliation_id: reconciliation_id_parse_result.unwrap(),
+        });
+    }
+
+    async fn insert_journal_entry_row(
+        transaction: &PostgresTransaction<'_>,
+        user_id: &Uuid,
+        journal_entry: &FinanceJournalEntry,
+    ) -> Result<(), PostgresTransactionError> {
+        let insert_result = transaction
+            .execute(
+                "INSERT INTO FinanceJournalDiary (finance_journal_diary_id, user_id, is_simple_entry, is_saldo,
+                is_closing, debit_finance_account_id, credit_finance_account_id, running_number, booking_time,
+                amount, title, description, reverse_of_journal_entry_id, reversed_by_journal_entry_id, tags,
+                import_reference) VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12, $13, $14, $15, $16)",
+                &[
+                    &journal_entry.id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &journal_entry.is_simple_entry,
+                    &journal_entry.is_saldo,
+                    &journal_entry.is_closing,
+                    &journal_entry.debit_finance_account_id.to_uuid_1(),
+                    &journal_entry.credit_finance_account_id.to_uuid_1(),
+                    &(journal_entry.running_number as i64),
+                    &journal_entry.booking_time,
+                    &(journal_entry.amount as i64),
+                    &journal_entry.title,
+                    &journal_entry.description,
+                    &journal_entry
+                        .reverse_of_journal_entry_id
+                        .map(|elem| elem.to_uuid_1()),
+                    &journal_entry
+                        .reversed_by_journal_entry_id
+                        .map(|elem| elem.to_uuid_1()),
+                    &journal_entry.tags,
+                    &journal_entry.import_reference,
+                ],
+            )
+            .await;
+        if insert_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not update journal",
+                insert_result.unwrap_err(),
+            ));
+        }
+        return Ok(());
+    }
+
+    async fn insert_booking_entry_row(
+        transaction: &PostgresTransaction<'_>,
+        user_id: &Uuid,
+        booking_entry: &FinanceAccountBookingEntry,
+    ) -> Result<(), PostgresTransactionError> {
+        let insert_result = transaction
+            .execute(
+                "INSERT INTO BookingEntries (booking_entry_id, user_id, finance_account_id, finance_journal_diary_id,
+                booking_type, booking_time, amount, title, description, tags, reconciliation_id)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)",
+                &[
+                    &booking_entry.id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &booking_entry.finance_account_id.to_uuid_1(),
+                    &booking_entry.finance_journal_diary_id.to_uuid_1(),
+                    &booking_entry.booking_type.to_int(),
+                    &booking_entry.booking_time,
+                    &(booking_entry.amount as i64),
+                    &booking_entry.title,
+                    &booking_entry.description,
+                    &booking_entry.tags,
+                    &booking_entry.reconciliation_id.map(|elem| elem.to_uuid_1()),
+                ],
+            )
+            .await;
+        if insert_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not update bookings",
+                insert_result.unwrap_err(),
+            ));
+        }
+        return Ok(());
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_insert_split_booking_entries()
+    /// checks the accounts and writes all entries inside the same serializable transaction
+    async fn execute_finance_insert_booking_entries_with_transaction(
+        client: &mut PostgresClient,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, PostgresTransactionError> {
+        let transaction = DbHandlerPostgres::start_serializable_transaction(client).await?;
+
+        // a batch reports the first unknown account, a single entry reports the side of the leg
+        let is_batch = actions_to_insert.len() > 1;
+        for action_to_insert in &actions_to_insert {
+            for (leg_index, booking_leg) in action_to_insert
+                .debit_legs
+                .iter()
+                .chain(action_to_insert.credit_legs.iter())
+                .enumerate()
+            {
+                let account_check_result = DbHandlerPostgres::check_account_exists(
+                    &transaction,
+                    user_id,
+                    &booking_leg.finance_account_id,
+                )
+                .await;
+                if account_check_result.is_err() {
+                    return Err(PostgresTransactionError::from_db_error(
+                        "could not check account",
+                        account_check_result.unwrap_err(),
+                    ));
+                }
+                if account_check_result.unwrap() {
+                    continue;
+                }
+                if is_batch {
+                    return Err(format!(
+                        "account {} is not available",
+                        booking_leg.finance_account_id
+                    )
+                    .into());
+                }
+                if leg_index < action_to_insert.debit_legs.len() {
+                    return Err("debit account is not available".into());
+                }
+                return Err("credit account is not available".into());
+            }
+        }
+
+        let mut return_object = Vec::new();
+        for action_to_insert in actions_to_insert {
+            let write_result = DbHandlerPostgres::write_finance_split_booking_with_transaction(
+                &transaction,
+                user_id,
+                action_to_insert,
+                None,
+            )
+            .await?;
+            return_object.push(write_result);
+        }
+
+        let commit_result = transaction.commit().await;
+        if commit_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "Problem closing transaction",
+                commit_result.unwrap_err(),
+            ));
+        }
+
+        Ok(return_object)
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_reverse_journal_entry()
+    /// checks and mirrors the original entry, inserts the reversal and marks the original inside one transaction
+    async fn execute_finance_reverse_journal_entry_with_transaction(
+        client: &mut PostgresClient,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, PostgresTransactionError> {
+        let transaction = DbHandlerPostgres::start_serializable_transaction(client).await?;
+
+        let original_entry_result = transaction
+            .query(
+                "SELECT * FROM FinanceJournalDiary WHERE user_id = $1 AND finance_journal_diary_id = $2",
+                &[&user_id.to_uuid_1(), &journal_entry_id.to_uuid_1()],
+            )
+            .await;
+        if original_entry_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not read journal entry",
+                original_entry_result.unwrap_err(),
+            ));
+        }
+        let mut original_entry_list = DbHandlerPostgres::read_rows(
+            original_entry_result.unwrap(),
+            DbHandlerPostgres::read_journal_entry_row,
+        )?;
+        if original_entry_list.is_empty() {
+            return Err(format!("journal entry {} not found", journal_entry_id).into());
+        }
+        let original_entry = original_entry_list.remove(0);
+        if original_entry.reversed_by_journal_entry_id.is_some() {
+            return Err("journal entry is already reversed".into());
+        }
+        if original_entry.reverse_of_journal_entry_id.is_some() {
+            return Err("a reversal entry can not be reversed".into());
+        }
+        if original_entry.is_saldo {
+            return Err("saldo entries can not be reversed".into());
+        }
+        if original_entry.is_closing {
+            return Err("closing entries can not be reversed".into());
+        }
+
+        let booking_entries_result = transaction
+            .query(
+                "SELECT * FROM BookingEntries WHERE user_id = $1 AND finance_journal_diary_id = $2
+                ORDER BY insert_order",
+                &[&user_id.to_uuid_1(), &journal_entry_id.to_uuid_1()],
+            )
+            .await;
+        if booking_entries_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not read booking entries",
+                booking_entries_result.unwrap_err(),
+            ));
+        }
+        let booking_entries = DbHandlerPostgres::read_rows(
+            booking_entries_result.unwrap(),
+            DbHandlerPostgres::read_booking_entry_row,
+        )?;
+
+        let mut debit_legs = Vec::new();
+        let mut credit_legs = Vec::new();
+        for booking_entry in booking_entries {
+            if booking_entry.reconciliation_id.is_some() {
+                return Err(
+                    "journal entry contains reconciled booking entries, unreconcile them first"
+                        .into(),
+                );
+            }
+            let later_saldo_result = transaction
+                .query_one(
+                    "SELECT COUNT(*) FROM BookingEntries WHERE user_id = $1 AND finance_account_id = $2
+                    AND booking_type IN ($3, $4) AND booking_time >= $5",
+                    &[
+                        &user_id.to_uuid_1(),
+                        &booking_entry.finance_account_id.to_uuid_1(),
+                        &BookingEntryType::SaldoCredit.to_int(),
+                        &BookingEntryType::SaldoDebit.to_int(),
+                        &booking_time,
+                    ],
+                )
+                .await;
+            if later_saldo_result.is_err() {
+                return Err(PostgresTransactionError::from_db_error(
+                    "could not check saldo entries",
+                    later_saldo_result.unwrap_err(),
+                ));
+            }
+            if later_saldo_result.unwrap().get::<_, i64>(0) > 0 {
+                return Err("Can not insert reversal before saldo of an involved account".into());
+            }
+            // debit and credit are swapped for the reversal
+            let reversed_leg = FinanceBookingLeg {
+                finance_account_id: booking_entry.finance_account_id,
+                amount: booking_entry.amount,
+            };
+            if booking_entry.booking_type.eq(&BookingEntryType::Debit) {
+                credit_legs.push(reversed_leg);
+            } else {
+                debit_legs.push(reversed_leg);
+            }
+        }
+
+        let reversal_request = FinanceSplitBookingRequest {
+            is_saldo: false,
+            is_closing: false,
+            debit_legs,
+            credit_legs,
+            booking_time,
+            title: format!(
+                "Reversal of #{}: {}",
+                original_entry.running_number, original_entry.title
+            ),
+            description: reason.clone(),
+            tags: original_entry.tags.clone(),
+            import_reference: None,
+        };
+        let return_object = DbHandlerPostgres::write_finance_split_booking_with_transaction(
+            &transaction,
+            user_id,
+            reversal_request,
+            Some(journal_entry_id.clone()),
+        )
+        .await?;
+
+        let update_result = transaction
+            .execute(
+                "UPDATE FinanceJournalDiary SET reversed_by_journal_entry_id = $1
+                WHERE user_id = $2 AND finance_journal_diary_id = $3",
+                &[
+                    &return_object.journal_entry.id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &journal_entry_id.to_uuid_1(),
+                ],
+            )
+            .await;
+        if update_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not mark original journal entry as reversed",
+                update_result.unwrap_err(),
+            ));
+        }
+
+        let commit_result = transaction.commit().await;
+        if commit_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "Problem closing transaction",
+                commit_result.unwrap_err(),
+            ));
+        }
+
+        Ok(return_object)
+    }
+
+    /// returns an error for the first id of the archive which is already used by another user
+    async fn check_archive_ids_of_other_users(
+        client: &impl GenericClient,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), PostgresTransactionError> {
+        let id_lists = [
+            (
+                "FinanceAccountTypes",
+                "finance_account_type_id",
+                "account type",
+                archive
+                    .account_types
+                    .iter()
+                    .map(|elem| elem.id)
+                    .collect::<Vec<Uuid>>(),
+            ),
+            (
+                "FinanceAccounts",
+                "finance_account_id",
+                "account",
+                archive.accounts.iter().map(|elem| elem.id).collect(),
+            ),
+            (
+                "FinanceJournalDiary",
+                "finance_journal_diary_id",
+                "journal entry",
+                archive.journal_entries.iter().map(|elem| elem.id).collect(),
+            ),
+            (
+                "BookingEntries",
+                "booking_entry_id",
+                "booking entry",
+                archive.booking_entries.iter().map(|elem| elem.id).collect(),
+            ),
+        ];
+        for (table_name, id_column_name, object_name, ids) in id_lists {
+            let id_values: Vec<_> = ids.iter().map(|elem| elem.to_uuid_1()).collect();
+            let query_result = client
+                .query_opt(
+                    &format!(
+                        "SELECT {1} FROM {0} WHERE user_id <> $1 AND {1} = ANY ($2) LIMIT 1",
+                        table_name, id_column_name
+                    ),
+                    &[&user_id.to_uuid_1(), &id_values],
+                )
+                .await;
+            if query_result.is_err() {
+                return Err(PostgresTransactionError::from_db_error(
+                    "could not check the ids of the archive",
+                    query_result.unwrap_err(),
+                ));
+            }
+            if let Some(used_id_row) = query_result.unwrap() {
+                let used_id_result =
+                    DbHandlerPostgres::get_uuid_from_row(&used_id_row, id_column_name);
+                if used_id_result.is_err() {
+                    return Err(PostgresTransactionError::from(used_id_result.unwrap_err()));
+                }
+                return Err(PostgresTransactionError::from(format!(
+                    "{} {} of the archive is used by another user",
+                    object_name,
+                    used_id_result.unwrap()
+                )));
+            }
+        }
+        return Ok(());
+    }
+
+    /// Helper function for DBFinanceAccountingFunctions::finance_restore_archive()
+    async fn execute_finance_restore_archive_with_transaction(
+        client: &mut PostgresClient,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), PostgresTransactionError> {
+        let transaction = DbHandlerPostgres::start_serializable_transaction(client).await?;
+
+        let existing_journal_count = transaction
+            .query_one(
+                "SELECT COUNT(*) FROM FinanceJournalDiary WHERE user_id = $1",
+                &[&user_id.to_uuid_1()],
+            )
+            .await;
+        if existing_journal_count.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not read journal",
+                existing_journal_count.unwrap_err(),
+            ));
+        }
+        if existing_journal_count.unwrap().get::<_, i64>(0) > 0 {
+            return Err(
+                "the journal is not empty, entries can only be restored into an empty journal"
+                    .into(),
+            );
+        }
+
+        DbHandlerPostgres::check_archive_ids_of_other_users(&transaction, user_id, archive).await?;
+
+        for account_type in &archive.account_types {
+            let upsert_result = DbHandlerPostgres::upsert_finance_account_type_row(
+                &transaction,
+                user_id,
+                account_type,
+            )
+            .await;
+            if upsert_result.is_err() {
+                return Err(PostgresTransactionError::from_db_error(
+                    "could not write account type",
+                    upsert_result.unwrap_err(),
+                ));
+            }
+        }
+        for account in &archive.accounts {
+            let upsert_result =
+                DbHandlerPostgres::upsert_finance_account_row(&transaction, user_id, account).await;
+            if upsert_result.is_err() {
+                return Err(PostgresTransactionError::from_db_error(
+                    "could not write account",
+                    upsert_result.unwrap_err(),
+                ));
+            }
+        }
+        for journal_entry in &archive.journal_entries {
+            DbHandlerPostgres::insert_journal_entry_row(&transaction, user_id, journal_entry)
+                .await?;
+        }
+        for booking_entry in &archive.booking_entries {
+            DbHandlerPostgres::insert_booking_entry_row(&transaction, user_id, booking_entry)
+                .await?;
+        }
+
+        let counter_result = transaction
+            .execute(
+                "INSERT INTO CounterCollection (user_id, counter_entry_id, booking_journal_max_number)
+                VALUES ($1, $2, $3)
+                ON CONFLICT (user_id) DO UPDATE SET booking_journal_max_number = excluded.booking_journal_max_number",
+                &[
+                    &user_id.to_uuid_1(),
+                    &Uuid::new().to_uuid_1(),
+                    &(archive.booking_journal_max_number as i64),
+                ],
+            )
+            .await;
+        if counter_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not set counter record",
+                counter_result.unwrap_err(),
+            ));
+        }
+
+        let commit_result = transaction.commit().await;
+        if commit_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "Problem closing transaction",
+                commit_result.unwrap_err(),
+            ));
+        }
+
+        Ok(())
+    }
+
+    /// the accounts have to be checked before, the running number is taken from the counter record
+    /// which is created on the first booking, concurrent bookings of the user fail with a serialization
+    /// failure and are repeated by the caller
+    async fn write_finance_split_booking_with_transaction(
+        transaction: &PostgresTransaction<'_>,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+        reverse_of_journal_entry_id: Option<Uuid>,
+    ) -> Result<FinanceSplitBookingResult, PostgresTransactionError> {
+        let counter_result = transaction
+            .query_one(
+                "INSERT INTO CounterCollection (user_id, counter_entry_id, booking_journal_max_number)
+                VALUES ($1, $2, (SELECT COALESCE(MAX(running_number), 0) FROM FinanceJournalDiary WHERE user_id = $1) + 1)
+                ON CONFLICT (user_id) DO UPDATE
+                SET booking_journal_max_number = CounterCollection.booking_journal_max_number + 1
+                RETURNING booking_journal_max_number",
+                &[&user_id.to_uuid_1(), &Uuid::new().to_uuid_1()],
+            )
+            .await;
+        if counter_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "could not update max number record",
+                counter_result.unwrap_err(),
+            ));
+        }
+        let new_running_number = counter_result.unwrap().get::<_, i64>(0) as u64;
+
+        let journal_amount_result = action_to_insert.sum_debit_amount();
+        if journal_amount_result.is_err() {
+            return Err(PostgresTransactionError::from(
+                journal_amount_result.unwrap_err(),
+            ));
+        }
+        // for entries with more than two accounts the first leg of each side is stored as main account
         let new_journal_entry = FinanceJournalEntry {
             id: Uuid::new(),
-            is_simple_entry: action_to_insert.is_simple_entry,
+            is_simple_entry: action_to_insert.is_simple_entry(),
             is_saldo: action_to_insert.is_saldo,
-            debit_finance_account_id: action_to_insert.debit_finance_account_id,
-            credit_finance_account_id: action_to_insert.credit_finance_account_id,
-            running_number: new_running_number as u64,
+            is_closing: action_to_insert.is_closing,
+            debit_finance_account_id: action_to_insert.debit_legs[0].finance_account_id,
+            credit_finance_account_id: action_to_insert.credit_legs[0].finance_account_id,
+            running_number: new_running_number,
             booking_time: action_to_insert.booking_time,
-            amount: action_to_insert.amount,
+            amount: journal_amount_result.unwrap(),
             title: action_to_insert.title.clone(),
             description: action_to_insert.description.clone(),
+            reverse_of_journal_entry_id,
+            reversed_by_journal_entry_id: None,
+            tags: action_to_insert.tags.clone(),
+            import_reference: action_to_insert.import_reference.clone(),
         };
 
         let credit_booking_type = if action_to_insert.is_saldo {
@@ -693,121 +3974,57 @@ impl DbHandlerMongoDB {
         } else {
             BookingEntryType::Credit
         };
-        let new_credit_account_entry = FinanceAccountBookingEntry {
-            id: Uuid::new(),
-            finance_account_id: action_to_insert.credit_finance_account_id,
-            finance_journal_diary_id: new_journal_entry.id.clone(),
-            booking_type: credit_booking_type.clone(),
-            booking_time: action_to_insert.booking_time,
-            amount: action_to_insert.amount,
-            title: action_to_insert.title.clone(),
-            description: action_to_insert.description.clone(),
-        };
         let debit_booking_type = if action_to_insert.is_saldo {
             BookingEntryType::SaldoDebit
         } else {
             BookingEntryType::Debit
         };
-        let new_debit_account_entry = FinanceAccountBookingEntry {
-            id: Uuid::new(),
-            finance_account_id: action_to_insert.debit_finance_account_id,
-            finance_journal_diary_id: new_journal_entry.id.clone(),
-            booking_type: debit_booking_type.clone(),
-            booking_time: action_to_insert.booking_time,
-            amount: action_to_insert.amount,
-            title: action_to_insert.title.clone(),
-            description: action_to_insert.description.clone(),
-        };
-        let debit_finance_account_id_value =
-            mongodb::bson::Binary::from_uuid(action_to_insert.debit_finance_account_id);
-        let credit_finance_account_id_value =
-            mongodb::bson::Binary::from_uuid(action_to_insert.credit_finance_account_id);
-
-        let journal_insert_result = journal_diary_entries_collection
-            .insert_one_with_session(
-                doc! {
-                    "finance_journal_diary_id":journal_diary_entry_id_value.clone(),
-                    "user_id": user_id_value.clone(),
-                    "is_simple_entry": action_to_insert.is_simple_entry,
-                    "is_saldo":action_to_insert.is_saldo,
-                    "debit_finance_account_id":debit_finance_account_id_value.clone(),
-                    "credit_finance_account_id":credit_finance_account_id_value.clone(),
-                    "running_number":new_running_number as i64,
-                    "booking_time":action_to_insert.booking_time,
-                    "amount":action_to_insert.amount as i64,
-                    "title":action_to_insert.title.clone(),
-                    "description":action_to_insert.description.clone()
-                },
-                None,
-                session,
-            )
-            .await;
-
-        if journal_insert_result.is_err() {
-            return Err(mongodb::error::Error::custom(format!(
-                "could not update journal: {}",
-                journal_insert_result.unwrap_err()
-            )));
+        let mut new_debit_account_entries = Vec::new();
+        for debit_leg in &action_to_insert.debit_legs {
+            new_debit_account_entries.push(FinanceAccountBookingEntry {
+                id: Uuid::new(),
+                finance_account_id: debit_leg.finance_account_id,
+                finance_journal_diary_id: new_journal_entry.id.clone(),
+                booking_type: debit_booking_type.clone(),
+                booking_time: action_to_insert.booking_time,
+                amount: debit_leg.amount,
+                title: action_to_insert.title.clone(),
+                description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
+            });
         }
-
-        let booking_insert_1_result = booking_entries_collection
-            .insert_one_with_session(
-                doc! {
-                    "booking_entry_id":mongodb::bson::Binary::from_uuid(new_debit_account_entry.id),
-                    "user_id": user_id_value.clone(),
-                    "finance_account_id":debit_finance_account_id_value.clone(),
-                    "finance_journal_diary_id":journal_diary_entry_id_value.clone(),
-                    "booking_type":debit_booking_type.to_int(),
-                    "booking_time":action_to_insert.booking_time,
-                    "amount":action_to_insert.amount  as i64,
-                    "title":action_to_insert.title.clone(),
-                    "description":action_to_insert.description.clone()
-                },
-                None,
-                session,
-            )
-            .await;
-
-        if booking_insert_1_result.is_err() {
-            return Err(mongodb::error::Error::custom(format!(
-                "could not update first booking: {}",
-                booking_insert_1_result.unwrap_err()
-            )));
+        let mut new_credit_account_entries = Vec::new();
+        for credit_leg in &action_to_insert.credit_legs {
+            new_credit_account_entries.push(FinanceAccountBookingEntry {
+                id: Uuid::new(),
+                finance_account_id: credit_leg.finance_account_id,
+                finance_journal_diary_id: new_journal_entry.id.clone(),
+                booking_type: credit_booking_type.clone(),
+                booking_time: action_to_insert.booking_time,
+                amount: credit_leg.amount,
+                title: action_to_insert.title.clone(),
+                description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
+            });
         }
 
-        let booking_insert_2_result=booking_entries_collection.insert_one_with_session(doc! {
-    "booking_entry_id":mongodb::bson::Binary::from_uuid(new_credit_account_entry.id),
-    "user_id": user_id_value.clone(),
-    "finance_account_id":credit_finance_account_id_value.clone(),
-    "finance_journal_diary_id":journal_diary_entry_id_value.clone(),
-    "booking_type":credit_booking_type.to_int(),
-    "booking_time":action_to_insert.booking_time,
-    "amount":action_to_insert.amount  as i64,
-    "title":action_to_insert.title.clone(),
-    "description":action_to_insert.description.clone()
-}, None, session).await;
-
-        if booking_insert_2_result.is_err() {
-            return Err(mongodb::error::Error::custom(format!(
-                "could not update second booking: {}",
-                booking_insert_2_result.unwrap_err()
-            )));
+        DbHandlerPostgres::insert_journal_entry_row(transaction, user_id, &new_journal_entry)
+            .await?;
+        for booking_entry in new_debit_account_entries
+            .iter()
+            .chain(new_credit_account_entries.iter())
+        {
+            DbHandlerPostgres::insert_booking_entry_row(transaction, user_id, booking_entry)
+                .await?;
         }
 
-        loop {
-            let result = session.commit_transaction().await;
-            if let Err(ref error) = result {
-                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
-                    continue;
-                }
-            }
-
-            let return_object = FinanceBookingResult {
-                journal_entry: new_journal_entry,
-                debit_account_entry: new_debit_account_entry,
-                credit_account_entry: new_credit_account_entry,
-            };
-            return Ok(return_object);
-        }
+        let return_object = FinanceSplitBookingResult {
+            journal_entry: new_journal_entry,
+            debit_account_entries: new_debit_account_entries,
+            credit_account_entries: new_credit_account_entries,
+        };
+        Ok(return_object)
     }
 }
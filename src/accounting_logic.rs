@@ -1,20 +1,34 @@
 use std::collections::HashMap;
 
-use async_session::chrono::{DateTime, Utc};
+use async_session::chrono::{DateTime, Duration, Utc};
 use futures::executor;
 use mongodb::bson::Uuid;
 
 use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
     accounting_database::{
         DBFinanceAccountingFunctions, FinanceAccountBookingEntryListSearchOption,
     },
     database_handler_mongodb::DbConnectionSetting,
     datatypes::{
         AccountBalanceInfo, AccountBalanceType, BookingEntryType, FinanceAccountBookingEntry,
-        FinanceBookingRequest, FinanceBookingResult, FinanceJournalEntry,
+        FinanceBookingLeg, FinanceBookingRequest, FinanceBookingResult, FinanceJournalEntry,
+        FinanceSplitBookingRequest, FinanceSplitBookingResult,
     },
 };
 
+/// trims the given tags and drops empty and duplicate ones, the order of the first occurrence is kept
+pub fn normalize_tags(tags: &Vec<String>) -> Vec<String> {
+    let mut return_object: Vec<String> = Vec::new();
+    for tag in tags {
+        let trimmed_tag = tag.trim();
+        if !trimmed_tag.is_empty() && !return_object.iter().any(|elem| elem.eq(trimmed_tag)) {
+            return_object.push(trimmed_tag.to_string());
+        }
+    }
+    return return_object;
+}
+
 pub struct FinanceBookingHandle<'a> {
     db_connection_settings: &'a DbConnectionSetting,
     user_id: &'a Uuid,
@@ -39,6 +53,25 @@ impl<'a> FinanceBookingHandle<'a> {
         booking_time_from: Option<DateTime<Utc>>,
         booking_time_till: Option<DateTime<Utc>>,
     ) -> Result<Vec<FinanceJournalEntry>, String> {
+        let temp_var_1 = self
+            .list_journal_entries_with_tag(booking_time_from, booking_time_till, None)
+            .await;
+
+        return temp_var_1;
+    }
+
+    /// lists the journal entries of the given time range, when a tag is given only entries
+    /// carrying that tag are returned
+    pub async fn list_journal_entries_with_tag(
+        &self,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        tag: Option<String>,
+    ) -> Result<Vec<FinanceJournalEntry>, String> {
+        let tag_filter = match tag {
+            Some(tag_value) if !tag_value.trim().is_empty() => Some(tag_value.trim().to_string()),
+            _ => None,
+        };
         if booking_time_from.is_some() && booking_time_till.is_some() {
             if booking_time_from.unwrap().gt(&booking_time_till.unwrap()) {
                 return Err(
@@ -53,6 +86,7 @@ impl<'a> FinanceBookingHandle<'a> {
                 &self.user_id,
                 booking_time_from,
                 booking_time_till,
+                tag_filter,
             )
             .await;
 
@@ -94,42 +128,13 @@ impl<'a> FinanceBookingHandle<'a> {
         return temp_var_1;
     }
 
+    /// inserts a simple booking entry, several entries of an account may share the same booking time
+    /// (they are ordered by their running number), but no entry may be dated at or before the last saldo
+    /// of an involved account because the balances of the closed period are already carried forward
     pub async fn finance_insert_booking_entry(
         &self,
         action_to_insert: &FinanceBookingRequest,
     ) -> Result<FinanceBookingResult, String> {
-        let check_journal_entries_result = self
-            .list_journal_entries(
-                Some(action_to_insert.booking_time),
-                Some(action_to_insert.booking_time),
-            )
-            .await;
-        if check_journal_entries_result.is_err() {
-            return Err(format!(
-                "Error checking already existing entries: {}",
-                check_journal_entries_result.unwrap_err()
-            ));
-        }
-        let check_journal_entries = check_journal_entries_result.unwrap();
-        if check_journal_entries.len() > 0 {
-            let position_credit_result = check_journal_entries.iter().position(|elem| {
-                elem.credit_finance_account_id
-                    .eq(&action_to_insert.credit_finance_account_id)
-                    && elem.booking_time.eq(&action_to_insert.booking_time)
-            });
-            if position_credit_result.is_some() {
-                return Err("Could not perform request: there is already an entry for credit account at specified booking time".into());
-            }
-            let position_debit_result = check_journal_entries.iter().position(|elem| {
-                elem.debit_finance_account_id
-                    .eq(&action_to_insert.debit_finance_account_id)
-                    && elem.booking_time.eq(&action_to_insert.booking_time)
-            });
-            if position_debit_result.is_some() {
-                return Err("Could not perform request: there is already an entry for debit account at specified booking time".into());
-            }
-        }
-
         if action_to_insert
             .credit_finance_account_id
             .eq(&action_to_insert.debit_finance_account_id)
@@ -158,7 +163,10 @@ impl<'a> FinanceBookingHandle<'a> {
                     .booking_time
                     .le(&credit_account_saldo_entry.unwrap().booking_time)
                 {
-                    return Err("Can not insert before saldo of credit account".into());
+                    return Err(format!(
+                        "Can not insert before saldo of credit account, the account is closed up to {}",
+                        credit_account_saldo_entry.unwrap().booking_time
+                    ));
                 }
             }
         }
@@ -170,20 +178,159 @@ impl<'a> FinanceBookingHandle<'a> {
                     .booking_time
                     .le(&debit_account_saldo_entry.unwrap().booking_time)
                 {
-                    return Err("Can not insert before saldo of debit account".into());
+                    return Err(format!(
+                        "Can not insert before saldo of debit account, the account is closed up to {}",
+                        debit_account_saldo_entry.unwrap().booking_time
+                    ));
                 }
             }
         }
 
+        let mut request_to_insert = action_to_insert.clone();
+        request_to_insert.tags = normalize_tags(&action_to_insert.tags);
+
         let temp_var_0 = self.db_connector.finance_insert_booking_entry(
             &self.db_connection_settings,
             &self.user_id,
-            action_to_insert.clone(),
+            request_to_insert,
         );
         let temp_var_1 = temp_var_0.await;
         return temp_var_1;
     }
 
+    pub async fn finance_insert_split_booking_entry(
+        &self,
+        action_to_insert: &FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        if action_to_insert.debit_legs.is_empty() {
+            return Err("Could not perform request: no debit account given".into());
+        }
+        if action_to_insert.credit_legs.is_empty() {
+            return Err("Could not perform request: no credit account given".into());
+        }
+        let position_zero_amount = action_to_insert
+            .debit_legs
+            .iter()
+            .chain(action_to_insert.credit_legs.iter())
+            .position(|elem| elem.amount == 0);
+        if position_zero_amount.is_some() {
+            return Err("Could not perform request: amount of an account must not be zero".into());
+        }
+        let sum_debit_result = action_to_insert.sum_debit_amount();
+        if sum_debit_result.is_err() {
+            return Err(format!(
+                "Could not perform request: debit amounts: {}",
+                sum_debit_result.unwrap_err()
+            ));
+        }
+        let sum_credit_result = action_to_insert.sum_credit_amount();
+        if sum_credit_result.is_err() {
+            return Err(format!(
+                "Could not perform request: credit amounts: {}",
+                sum_credit_result.unwrap_err()
+            ));
+        }
+        if sum_debit_result.as_ref().unwrap() != sum_credit_result.as_ref().unwrap() {
+            return Err(format!(
+                "Could not perform request: sum of debit amounts ({}) is not equal to sum of credit amounts ({})",
+                sum_debit_result.unwrap(),
+                sum_credit_result.unwrap()
+            ));
+        }
+
+        let mut account_ids_to_check: Vec<Uuid> = Vec::new();
+        for booking_leg in action_to_insert
+            .debit_legs
+            .iter()
+            .chain(action_to_insert.credit_legs.iter())
+        {
+            if account_ids_to_check.contains(&booking_leg.finance_account_id) {
+                return Err(format!(
+                    "Could not perform request: account {} is used more than once",
+                    booking_leg.finance_account_id
+                ));
+            }
+            account_ids_to_check.push(booking_leg.finance_account_id);
+        }
+
+        let saldo_information_result = self
+            .finance_get_last_saldo_account_entries(Some(account_ids_to_check.clone()))
+            .await;
+        if saldo_information_result.is_err() {
+            return Err(format!(
+                "Error checking already existing entries: {}",
+                saldo_information_result.unwrap_err()
+            ));
+        }
+        let saldo_information = saldo_information_result.unwrap();
+        for account_id in &account_ids_to_check {
+            let account_saldo_entry = saldo_information.get(account_id);
+            if account_saldo_entry.is_some() {
+                if action_to_insert
+                    .booking_time
+                    .le(&account_saldo_entry.unwrap().booking_time)
+                {
+                    return Err(format!(
+                        "Can not insert before saldo of account {}, the account is closed up to {}",
+                        account_id,
+                        account_saldo_entry.unwrap().booking_time
+                    ));
+                }
+            }
+        }
+
+        let mut request_to_insert = action_to_insert.clone();
+        request_to_insert.tags = normalize_tags(&action_to_insert.tags);
+
+        let temp_var_0 = self.db_connector.finance_insert_split_booking_entry(
+            &self.db_connection_settings,
+            &self.user_id,
+            request_to_insert,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn finance_insert_split_booking_entry_sync(
+        &self,
+        action_to_insert: &FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let temp_var_0 =
+            executor::block_on(self.finance_insert_split_booking_entry(action_to_insert));
+        return temp_var_0;
+    }
+
+    /// reverses (storno) a journal entry by posting a mirrored entry at current time,
+    /// the original entry stays and is marked as reversed
+    pub async fn reverse_journal_entry(
+        &self,
+        journal_entry_id: &Uuid,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        if reason.trim().is_empty() {
+            return Err("Could not perform request: a reason for the reversal is required".into());
+        }
+
+        let temp_var_0 = self.db_connector.finance_reverse_journal_entry(
+            &self.db_connection_settings,
+            &self.user_id,
+            journal_entry_id,
+            Utc::now(),
+            reason,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn reverse_journal_entry_sync(
+        &self,
+        journal_entry_id: &Uuid,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let temp_var_0 = executor::block_on(self.reverse_journal_entry(journal_entry_id, reason));
+        return temp_var_0;
+    }
+
     pub fn finance_insert_booking_entry_sync(
         &self,
         action_to_insert: &FinanceBookingRequest,
@@ -195,6 +342,20 @@ impl<'a> FinanceBookingHandle<'a> {
     pub async fn calculate_balance_info(
         &self,
         accounts_to_calculate: &Vec<Uuid>,
+    ) -> Result<Vec<AccountBalanceInfo>, String> {
+        let temp_var_0 = self
+            .calculate_balance_info_till(accounts_to_calculate, None)
+            .await;
+        return temp_var_0;
+    }
+
+    /// calculates the balance of the accounts as of booking_time_till (inclusive), based on the latest saldo
+    /// of an account at or before that time, without such a saldo all entries up to that time are summed;
+    /// entries closing a period are left out so that a balance at the closing time shows the closed balances
+    pub async fn calculate_balance_info_till(
+        &self,
+        accounts_to_calculate: &Vec<Uuid>,
+        booking_time_till: Option<DateTime<Utc>>,
     ) -> Result<Vec<AccountBalanceInfo>, String> {
         let mut return_object: Vec<AccountBalanceInfo> = Vec::new();
         // at first get last saldo information per account
@@ -217,17 +378,34 @@ impl<'a> FinanceBookingHandle<'a> {
             let time_start_option;
             if saldo_information_list.contains_key(account_id) {
                 let saldo_datetime = saldo_information_list.get(account_id).unwrap().booking_time;
-                time_start_option = Some(saldo_datetime);
+                if booking_time_till.is_some() && saldo_datetime.gt(&booking_time_till.unwrap()) {
+                    // an earlier saldo is searched within the entries
+                    time_start_option = None;
+                } else {
+                    time_start_option = Some(saldo_datetime);
+                }
             } else {
                 time_start_option = None;
             }
             let search_option = FinanceAccountBookingEntryListSearchOption::new(
                 account_id,
                 time_start_option,
-                None,
+                booking_time_till,
             );
             search_options.push(search_option);
         }
+        // journal entries are needed from the earliest period start on
+        let journal_time_from_option = if search_options
+            .iter()
+            .any(|elem| elem.booking_time_from.is_none())
+        {
+            None
+        } else {
+            search_options
+                .iter()
+                .filter_map(|elem| elem.booking_time_from)
+                .min()
+        };
         let booking_entries_multi_result = self.list_account_booking_entries(search_options).await;
         if booking_entries_multi_result.is_err() {
             return Err(format!(
@@ -237,6 +415,51 @@ impl<'a> FinanceBookingHandle<'a> {
         }
         let booking_entries_multi = booking_entries_multi_result.unwrap();
 
+        // before the opening saldo of the next period the closing entries are already booked
+        let mut closing_journal_entry_ids: Vec<Uuid> = Vec::new();
+        if booking_time_till.is_some() && !accounts_to_calculate.is_empty() {
+            let journal_entries_result = self
+                .list_journal_entries(journal_time_from_option, booking_time_till)
+                .await;
+            if journal_entries_result.is_err() {
+                return Err(format!(
+                    "Error getting journal entries: {}",
+                    journal_entries_result.unwrap_err()
+                ));
+            }
+            closing_journal_entry_ids = journal_entries_result
+                .unwrap()
+                .iter()
+                .filter(|elem| elem.is_closing)
+                .map(|elem| elem.id)
+                .collect();
+        }
+
+        // account categories are needed for the natural sign of the balance
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle
+            .finance_account_list_async(Some(accounts_to_calculate))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        let account_list = account_list_result.unwrap();
+        let account_type_list_result = config_handle.finance_account_type_list_async().await;
+        if account_type_list_result.is_err() {
+            return Err(format!(
+                "Error getting account type information: {}",
+                account_type_list_result.unwrap_err()
+            ));
+        }
+        let account_type_list = account_type_list_result.unwrap();
+
         // per account get entries since last saldo
         for account_id in accounts_to_calculate {
             let mut sum_credit_amount = 0;
@@ -244,9 +467,21 @@ impl<'a> FinanceBookingHandle<'a> {
             let booking_entries: std::iter::Filter<
                 std::slice::Iter<FinanceAccountBookingEntry>,
                 _,
-            > = booking_entries_multi
-                .iter()
-                .filter(|elem| elem.finance_account_id.eq(account_id));
+            > = booking_entries_multi.iter().filter(|elem| {
+                elem.finance_account_id.eq(account_id)
+                    && !closing_journal_entry_ids.contains(&elem.finance_journal_diary_id)
+            });
+            let period_start_option = booking_entries
+                .clone()
+                .filter(|elem| {
+                    elem.booking_type.eq(&BookingEntryType::SaldoCredit)
+                        || elem.booking_type.eq(&BookingEntryType::SaldoDebit)
+                })
+                .map(|elem| elem.booking_time)
+                .max();
+            let booking_entries = booking_entries.filter(|elem| {
+                period_start_option.is_none() || elem.booking_time.ge(&period_start_option.unwrap())
+            });
             for booking_entry in booking_entries {
                 if booking_entry.booking_type.eq(&BookingEntryType::Credit)
                     || booking_entry
@@ -265,10 +500,25 @@ impl<'a> FinanceBookingHandle<'a> {
             } else {
                 AccountBalanceType::Debit
             };
+            let account_type_option = account_list
+                .iter()
+                .find(|elem| elem.id.eq(account_id))
+                .and_then(|account| {
+                    account_type_list
+                        .iter()
+                        .find(|elem| elem.id.eq(&account.finance_account_type_id))
+                });
+            if account_type_option.is_none() {
+                return Err(format!(
+                    "Error getting account category: account type of account {} not found",
+                    account_id
+                ));
+            }
             let balance_info = AccountBalanceInfo {
                 account_id: account_id.clone(),
                 amount: balance_amount,
                 balance_type,
+                account_category: account_type_option.unwrap().account_category.clone(),
             };
 
             return_object.push(balance_info);
@@ -278,6 +528,363 @@ impl<'a> FinanceBookingHandle<'a> {
         return temp_var0;
     }
 
+    /// lists the movements of the accounts between time_from and time_till (both inclusive),
+    /// saldo entries and entries closing a period are left out, they only carry balances between periods
+    pub async fn list_movement_booking_entries(
+        &self,
+        account_ids: &Vec<Uuid>,
+        time_from: DateTime<Utc>,
+        time_till: DateTime<Utc>,
+    ) -> Result<Vec<FinanceAccountBookingEntry>, String> {
+        if account_ids.is_empty() {
+            return Ok(Vec::new());
+        }
+        let journal_entries_result = self
+            .list_journal_entries(Some(time_from), Some(time_till))
+            .await;
+        if journal_entries_result.is_err() {
+            return Err(format!(
+                "Error getting journal entries: {}",
+                journal_entries_result.unwrap_err()
+            ));
+        }
+        let closing_journal_entry_ids: Vec<Uuid> = journal_entries_result
+            .unwrap()
+            .iter()
+            .filter(|elem| elem.is_closing)
+            .map(|elem| elem.id)
+            .collect();
+
+        let search_options = account_ids
+            .iter()
+            .map(|elem| {
+                FinanceAccountBookingEntryListSearchOption::new(
+                    elem,
+                    Some(time_from),
+                    Some(time_till),
+                )
+            })
+            .collect();
+        let booking_entries_result = self.list_account_booking_entries(search_options).await;
+        if booking_entries_result.is_err() {
+            return Err(format!(
+                "Error getting booking entries: {}",
+                booking_entries_result.unwrap_err()
+            ));
+        }
+        let return_object = booking_entries_result
+            .unwrap()
+            .into_iter()
+            .filter(|elem| {
+                (elem.booking_type.eq(&BookingEntryType::Credit)
+                    || elem.booking_type.eq(&BookingEntryType::Debit))
+                    && !closing_journal_entry_ids.contains(&elem.finance_journal_diary_id)
+            })
+            .collect();
+        return Ok(return_object);
+    }
+
+    /// sums the movements of the accounts between time_from and time_till (both inclusive),
+    /// see list_movement_booking_entries, the category of the account decides the sign of the amount
+    pub async fn calculate_movement_info(
+        &self,
+        account_ids: &Vec<Uuid>,
+        time_from: DateTime<Utc>,
+        time_till: DateTime<Utc>,
+    ) -> Result<Vec<AccountBalanceInfo>, String> {
+        let booking_entries_result = self
+            .list_movement_booking_entries(account_ids, time_from, time_till)
+            .await;
+        if booking_entries_result.is_err() {
+            return Err(booking_entries_result.unwrap_err());
+        }
+        let booking_entries = booking_entries_result.unwrap();
+
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle
+            .finance_account_list_async(Some(account_ids))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        let account_list = account_list_result.unwrap();
+        let account_type_list_result = config_handle.finance_account_type_list_async().await;
+        if account_type_list_result.is_err() {
+            return Err(format!(
+                "Error getting account type information: {}",
+                account_type_list_result.unwrap_err()
+            ));
+        }
+        let account_type_list = account_type_list_result.unwrap();
+
+        let mut return_object: Vec<AccountBalanceInfo> = Vec::new();
+        for account_id in account_ids {
+            let mut sum_credit_amount: u64 = 0;
+            let mut sum_debit_amount: u64 = 0;
+            for booking_entry in booking_entries
+                .iter()
+                .filter(|elem| elem.finance_account_id.eq(account_id))
+            {
+                if booking_entry.booking_type.eq(&BookingEntryType::Credit) {
+                    sum_credit_amount += booking_entry.amount;
+                } else {
+                    sum_debit_amount += booking_entry.amount;
+                }
+            }
+            let account_type_option = account_list
+                .iter()
+                .find(|elem| elem.id.eq(account_id))
+                .and_then(|account| {
+                    account_type_list
+                        .iter()
+                        .find(|elem| elem.id.eq(&account.finance_account_type_id))
+                });
+            if account_type_option.is_none() {
+                return Err(format!(
+                    "Error getting account category: account type of account {} not found",
+                    account_id
+                ));
+            }
+            return_object.push(AccountBalanceInfo {
+                account_id: *account_id,
+                amount: sum_credit_amount.abs_diff(sum_debit_amount),
+                balance_type: if sum_credit_amount.gt(&sum_debit_amount) {
+                    AccountBalanceType::Credit
+                } else {
+                    AccountBalanceType::Debit
+                },
+                account_category: account_type_option.unwrap().account_category.clone(),
+            });
+        }
+        return Ok(return_object);
+    }
+
+    /// closes the accounts at closing_time against the closing account and opens the next period:
+    /// the balance of every account is booked against the closing account at closing_time,
+    /// one second later the balances are carried forward as saldo entries,
+    /// accounts with a balance of zero are skipped, all entries are written at once
+    pub async fn close_accounts(
+        &self,
+        accounts_to_close: &Vec<Uuid>,
+        closing_account_id: &Uuid,
+        closing_time: DateTime<Utc>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String> {
+        if accounts_to_close.is_empty() {
+            return Err("Could not perform request: no accounts to close given".into());
+        }
+        if accounts_to_close.contains(closing_account_id) {
+            return Err(
+                "Could not perform request: closing account can not be closed against itself"
+                    .into(),
+            );
+        }
+        let opening_time = closing_time + Duration::seconds(1);
+
+        let mut involved_account_ids: Vec<Uuid> = Vec::new();
+        for account_id in accounts_to_close {
+            if !involved_account_ids.contains(account_id) {
+                involved_account_ids.push(account_id.clone());
+            }
+        }
+        involved_account_ids.push(closing_account_id.clone());
+
+        let saldo_information_result = self
+            .finance_get_last_saldo_account_entries(Some(involved_account_ids.clone()))
+            .await;
+        if saldo_information_result.is_err() {
+            return Err(format!(
+                "Error checking already existing entries: {}",
+                saldo_information_result.unwrap_err()
+            ));
+        }
+        let saldo_information = saldo_information_result.unwrap();
+        for account_id in &involved_account_ids {
+            let account_saldo_entry = saldo_information.get(account_id);
+            if account_saldo_entry.is_some() {
+                if closing_time.le(&account_saldo_entry.unwrap().booking_time) {
+                    return Err(format!(
+                        "Can not close accounts before saldo of account {}",
+                        account_id
+                    ));
+                }
+            }
+        }
+
+        // entries between closing and opening would belong to no period
+        let search_options = involved_account_ids
+            .iter()
+            .map(|elem| {
+                FinanceAccountBookingEntryListSearchOption::new(
+                    elem,
+                    Some(closing_time),
+                    Some(opening_time),
+                )
+            })
+            .collect();
+        let check_booking_entries_result = self.list_account_booking_entries(search_options).await;
+        if check_booking_entries_result.is_err() {
+            return Err(format!(
+                "Error checking already existing entries: {}",
+                check_booking_entries_result.unwrap_err()
+            ));
+        }
+        let check_booking_entries = check_booking_entries_result.unwrap();
+        let position_existing_result = check_booking_entries.iter().position(|elem| {
+            elem.booking_time.gt(&closing_time) && elem.booking_time.lt(&opening_time)
+        });
+        if position_existing_result.is_some() {
+            return Err(format!("Could not perform request: account {} has an entry between closing and opening of the period",check_booking_entries[position_existing_result.unwrap()].finance_account_id));
+        }
+
+        let balance_info_result = self
+            .calculate_balance_info_till(&involved_account_ids, Some(closing_time))
+            .await;
+        if balance_info_result.is_err() {
+            return Err(format!(
+                "Error calculating balances: {}",
+                balance_info_result.unwrap_err()
+            ));
+        }
+        let balance_info_list = balance_info_result.unwrap();
+
+        let mut debit_balance_legs: Vec<FinanceBookingLeg> = Vec::new();
+        let mut credit_balance_legs: Vec<FinanceBookingLeg> = Vec::new();
+        let mut closing_account_balance: i128 = 0;
+        for balance_info in &balance_info_list {
+            let signed_amount: i128 = if balance_info.balance_type.eq(&AccountBalanceType::Debit) {
+                balance_info.amount as i128
+            } else {
+                -(balance_info.amount as i128)
+            };
+            if balance_info.account_id.eq(closing_account_id) {
+                closing_account_balance += signed_amount;
+                continue;
+            }
+            if balance_info.amount == 0 {
+                continue;
+            }
+            let booking_leg = FinanceBookingLeg {
+                finance_account_id: balance_info.account_id,
+                amount: balance_info.amount,
+            };
+            if balance_info.balance_type.eq(&AccountBalanceType::Debit) {
+                debit_balance_legs.push(booking_leg);
+            } else {
+                credit_balance_legs.push(booking_leg);
+            }
+        }
+
+        if debit_balance_legs.is_empty() && credit_balance_legs.is_empty() {
+            return Err("Could not perform request: all accounts have a balance of zero".into());
+        }
+
+        let sum_debit_balances: u64 = debit_balance_legs.iter().map(|elem| elem.amount).sum();
+        let sum_credit_balances: u64 = credit_balance_legs.iter().map(|elem| elem.amount).sum();
+        // the balance of the closing account itself is not carried forward,
+        // so it has to be zero after closing
+        closing_account_balance += sum_debit_balances as i128;
+        closing_account_balance -= sum_credit_balances as i128;
+        if closing_account_balance != 0 {
+            return Err(format!("Could not perform request: closing account {} would not be balanced after closing, all accounts have to be closed together",closing_account_id));
+        }
+
+        let closing_date_text = closing_time.format("%Y-%m-%d %H:%M:%S").to_string();
+        let mut actions_to_insert: Vec<FinanceSplitBookingRequest> = Vec::new();
+        if !debit_balance_legs.is_empty() {
+            actions_to_insert.push(FinanceSplitBookingRequest {
+                is_saldo: false,
+                is_closing: true,
+                debit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: closing_account_id.clone(),
+                    amount: sum_debit_balances,
+                }],
+                credit_legs: debit_balance_legs.clone(),
+                booking_time: closing_time,
+                title: format!("Closing at {}", closing_date_text),
+                description: "closing of debit balances".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            });
+        }
+        if !credit_balance_legs.is_empty() {
+            actions_to_insert.push(FinanceSplitBookingRequest {
+                is_saldo: false,
+                is_closing: true,
+                debit_legs: credit_balance_legs.clone(),
+                credit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: closing_account_id.clone(),
+                    amount: sum_credit_balances,
+                }],
+                booking_time: closing_time,
+                title: format!("Closing at {}", closing_date_text),
+                description: "closing of credit balances".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            });
+        }
+        if !debit_balance_legs.is_empty() {
+            actions_to_insert.push(FinanceSplitBookingRequest {
+                is_saldo: true,
+                is_closing: false,
+                debit_legs: debit_balance_legs,
+                credit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: closing_account_id.clone(),
+                    amount: sum_debit_balances,
+                }],
+                booking_time: opening_time,
+                title: format!("Carry forward after closing at {}", closing_date_text),
+                description: "opening of debit balances".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            });
+        }
+        if !credit_balance_legs.is_empty() {
+            actions_to_insert.push(FinanceSplitBookingRequest {
+                is_saldo: true,
+                is_closing: false,
+                debit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: closing_account_id.clone(),
+                    amount: sum_credit_balances,
+                }],
+                credit_legs: credit_balance_legs,
+                booking_time: opening_time,
+                title: format!("Carry forward after closing at {}", closing_date_text),
+                description: "opening of credit balances".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            });
+        }
+
+        let temp_var_0 = self.db_connector.finance_insert_split_booking_entries(
+            &self.db_connection_settings,
+            &self.user_id,
+            actions_to_insert,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn close_accounts_sync(
+        &self,
+        accounts_to_close: &Vec<Uuid>,
+        closing_account_id: &Uuid,
+        closing_time: DateTime<Utc>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String> {
+        let temp_var_0 = executor::block_on(self.close_accounts(
+            accounts_to_close,
+            closing_account_id,
+            closing_time,
+        ));
+        return temp_var_0;
+    }
+
     pub async fn finance_get_last_saldo_account_entries(
         &self,
         list_account_ids: Option<Vec<Uuid>>,
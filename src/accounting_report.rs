@@ -0,0 +1,802 @@
+use std::collections::HashMap;
+
+use async_session::chrono::{DateTime, Utc};
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
+    accounting_database::FinanceAccountBookingEntryListSearchOption,
+    accounting_logic::FinanceBookingHandle,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{
+        AccountBalanceType, AccountCategory, BookingEntryType, FinanceAccount, FinanceAccountType,
+        FinanceBalanceSheet, FinanceIncomeStatement, FinanceReportAccountLine,
+        FinanceReportSection, FinanceReportSectionMapping, FinanceReportTypeGroup,
+        FinanceTagSpendingLine, FinanceTagSpendingSummary, FinanceTrialBalance,
+        FinanceTrialBalanceLine,
+    },
+    report_section_database::DBFinanceReportSectionFunctions,
+};
+
+/// section of a report with the account types shown in it
+#[derive(Debug)]
+pub struct FinanceReportSectionDefinition {
+    pub title: String,
+    /// decides the total the section is counted in and the sign of the amounts
+    pub account_category: AccountCategory,
+    pub finance_account_type_ids: Vec<Uuid>,
+}
+
+pub struct FinanceReportHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceReportSectionFunctions,
+}
+
+impl<'a> FinanceReportHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceReportSectionFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    pub async fn report_section_mapping_list(
+        &self,
+    ) -> Result<Vec<FinanceReportSectionMapping>, String> {
+        let temp_var_0 = self
+            .db_connector
+            .finance_report_section_mapping_list(&self.db_connection_settings, &self.user_id);
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    /// the section has to be part of the report the account type is shown in,
+    /// e.g. a liability account type can be moved to an asset section, but not to the income statement
+    pub async fn report_section_mapping_upsert(
+        &self,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String> {
+        let section_title = report_section_mapping.section_title.trim();
+        if section_title.is_empty() {
+            return Err("could not upsert report section mapping because title is empty".into());
+        }
+
+        let account_structure_result = self.load_account_structure().await;
+        if account_structure_result.is_err() {
+            return Err(account_structure_result.unwrap_err());
+        }
+        let (_, account_type_list) = account_structure_result.unwrap();
+        let account_type_option = account_type_list
+            .iter()
+            .find(|elem| elem.id.eq(&report_section_mapping.finance_account_type_id));
+        if account_type_option.is_none() {
+            return Err(
+                "could not upsert report section mapping because the account type is unknown"
+                    .into(),
+            );
+        }
+        if account_type_option
+            .unwrap()
+            .account_category
+            .is_balance_sheet_category()
+            != report_section_mapping
+                .section_category
+                .is_balance_sheet_category()
+        {
+            return Err(format!(
+                "could not upsert report section mapping because a {} section is not part of the report of a {} account type",
+                report_section_mapping.section_category,
+                account_type_option.unwrap().account_category
+            ));
+        }
+
+        let trimmed_report_section_mapping = FinanceReportSectionMapping {
+            section_title: section_title.into(),
+            ..report_section_mapping.clone()
+        };
+        let temp_var_0 = self.db_connector.finance_report_section_mapping_upsert(
+            &self.db_connection_settings,
+            &self.user_id,
+            &trimmed_report_section_mapping,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn report_section_mapping_upsert_sync(
+        &self,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String> {
+        let temp_var_0 =
+            executor::block_on(self.report_section_mapping_upsert(report_section_mapping));
+        return temp_var_0;
+    }
+
+    /// the account type is shown in the default section of its category again
+    pub async fn report_section_mapping_delete(
+        &self,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String> {
+        let temp_var_0 = self.db_connector.finance_report_section_mapping_delete(
+            &self.db_connection_settings,
+            &self.user_id,
+            finance_account_type_id,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn report_section_mapping_delete_sync(
+        &self,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String> {
+        let temp_var_0 =
+            executor::block_on(self.report_section_mapping_delete(finance_account_type_id));
+        return temp_var_0;
+    }
+
+    /// default sections of the balance sheet, one per category
+    pub fn get_balance_sheet_default_sections() -> Vec<(AccountCategory, &'static str)> {
+        return vec![
+            (AccountCategory::Asset, "assets"),
+            (AccountCategory::Liability, "liabilities"),
+            (AccountCategory::Equity, "equity"),
+        ];
+    }
+
+    /// default sections of the income statement, one per category
+    pub fn get_income_statement_default_sections() -> Vec<(AccountCategory, &'static str)> {
+        return vec![
+            (AccountCategory::Income, "income"),
+            (AccountCategory::Expense, "expenses"),
+        ];
+    }
+
+    /// the default sections are kept in their order (also when empty), an account type without a mapping
+    /// is shown in the default section of its category, a mapped account type in the section of its mapping,
+    /// sections only known from mappings follow the last section of their category
+    pub fn get_report_sections(
+        default_sections: &Vec<(AccountCategory, &'static str)>,
+        account_type_list: &Vec<FinanceAccountType>,
+        report_section_mappings: &Vec<FinanceReportSectionMapping>,
+    ) -> Vec<FinanceReportSectionDefinition> {
+        let mut return_object: Vec<FinanceReportSectionDefinition> = default_sections
+            .iter()
+            .map(|(account_category, title)| FinanceReportSectionDefinition {
+                title: title.to_string(),
+                account_category: account_category.clone(),
+                finance_account_type_ids: Vec::new(),
+            })
+            .collect();
+        for account_type in account_type_list {
+            let default_section_option = default_sections
+                .iter()
+                .find(|elem| elem.0.eq(&account_type.account_category));
+            if default_section_option.is_none() {
+                continue;
+            }
+            let default_section = default_section_option.unwrap();
+            let (section_category, section_title) =
+                match report_section_mappings.iter().find(|elem| {
+                    elem.finance_account_type_id.eq(&account_type.id)
+                        && default_sections
+                            .iter()
+                            .any(|section| section.0.eq(&elem.section_category))
+                }) {
+                    Some(mapping) => (
+                        mapping.section_category.clone(),
+                        mapping.section_title.clone(),
+                    ),
+                    None => (default_section.0.clone(), default_section.1.to_string()),
+                };
+
+            let position_option = return_object.iter().position(|elem| {
+                elem.account_category.eq(&section_category) && elem.title.eq(&section_title)
+            });
+            if let Some(position) = position_option {
+                return_object[position]
+                    .finance_account_type_ids
+                    .push(account_type.id);
+            } else {
+                // every category of the report has a default section
+                let insert_position = return_object
+                    .iter()
+                    .rposition(|elem| elem.account_category.eq(&section_category))
+                    .unwrap()
+                    + 1;
+                return_object.insert(
+                    insert_position,
+                    FinanceReportSectionDefinition {
+                        title: section_title,
+                        account_category: section_category,
+                        finance_account_type_ids: vec![account_type.id],
+                    },
+                );
+            }
+        }
+        return return_object;
+    }
+
+    /// balances of asset, liability and equity accounts at report_time,
+    /// grouped into the sections of the stored report section mappings
+    pub async fn balance_sheet(
+        &self,
+        report_time: DateTime<Utc>,
+    ) -> Result<FinanceBalanceSheet, String> {
+        let account_structure_result = self.load_account_structure().await;
+        if account_structure_result.is_err() {
+            return Err(account_structure_result.unwrap_err());
+        }
+        let (account_list, account_type_list) = account_structure_result.unwrap();
+
+        let section_definitions_result = self
+            .load_report_sections(
+                &FinanceReportHandle::get_balance_sheet_default_sections(),
+                &account_type_list,
+            )
+            .await;
+        if section_definitions_result.is_err() {
+            return Err(section_definitions_result.unwrap_err());
+        }
+        let section_definitions = section_definitions_result.unwrap();
+        let account_ids =
+            FinanceReportHandle::get_account_ids_of_sections(&section_definitions, &account_list);
+
+        let mut amount_per_account: HashMap<Uuid, i128> = HashMap::new();
+        if !account_ids.is_empty() {
+            let booking_handle = FinanceBookingHandle::new(
+                &self.db_connection_settings,
+                &self.user_id,
+                self.db_connector,
+            );
+            let balance_info_result = booking_handle
+                .calculate_balance_info_till(&account_ids, Some(report_time))
+                .await;
+            if balance_info_result.is_err() {
+                return Err(format!(
+                    "Error calculating balances: {}",
+                    balance_info_result.unwrap_err()
+                ));
+            }
+            for mut balance_info in balance_info_result.unwrap() {
+                // the sign follows the section the account is shown in
+                balance_info.account_category = FinanceReportHandle::get_section_category(
+                    &section_definitions,
+                    &account_list,
+                    &balance_info.account_id,
+                )
+                .unwrap();
+                amount_per_account
+                    .insert(balance_info.account_id, balance_info.get_signed_amount());
+            }
+        }
+
+        let sections = FinanceReportHandle::build_sections(
+            &section_definitions,
+            &account_list,
+            &account_type_list,
+            &amount_per_account,
+        );
+        let total_assets =
+            FinanceReportHandle::get_sections_total(&sections, &[AccountCategory::Asset]);
+        let total_liabilities_and_equity = FinanceReportHandle::get_sections_total(
+            &sections,
+            &[AccountCategory::Liability, AccountCategory::Equity],
+        );
+
+        let return_object = FinanceBalanceSheet {
+            report_time,
+            sections,
+            total_assets,
+            total_liabilities_and_equity,
+            difference: total_assets - total_liabilities_and_equity,
+        };
+        return Ok(return_object);
+    }
+
+    pub fn balance_sheet_sync(
+        &self,
+        report_time: DateTime<Utc>,
+    ) -> Result<FinanceBalanceSheet, String> {
+        let temp_var_0 = executor::block_on(self.balance_sheet(report_time));
+        return temp_var_0;
+    }
+
+    /// movements of income and expense accounts between time_from and time_till (both inclusive),
+    /// grouped into the sections of the stored report section mappings,
+    /// saldo entries and entries closing a period are ignored
+    pub async fn income_statement(
+        &self,
+        time_from: DateTime<Utc>,
+        time_till: DateTime<Utc>,
+    ) -> Result<FinanceIncomeStatement, String> {
+        if time_from.gt(&time_till) {
+            return Err("could not create report because time_from is after time_till".into());
+        }
+
+        let account_structure_result = self.load_account_structure().await;
+        if account_structure_result.is_err() {
+            return Err(account_structure_result.unwrap_err());
+        }
+        let (account_list, account_type_list) = account_structure_result.unwrap();
+
+        let section_definitions_result = self
+            .load_report_sections(
+                &FinanceReportHandle::get_income_statement_default_sections(),
+                &account_type_list,
+            )
+            .await;
+        if section_definitions_result.is_err() {
+            return Err(section_definitions_result.unwrap_err());
+        }
+        let section_definitions = section_definitions_result.unwrap();
+        let account_ids =
+            FinanceReportHandle::get_account_ids_of_sections(&section_definitions, &account_list);
+
+        let mut amount_per_account: HashMap<Uuid, i128> = HashMap::new();
+        if !account_ids.is_empty() {
+            let booking_handle = FinanceBookingHandle::new(
+                &self.db_connection_settings,
+                &self.user_id,
+                self.db_connector,
+            );
+            let movement_info_result = booking_handle
+                .calculate_movement_info(&account_ids, time_from, time_till)
+                .await;
+            if movement_info_result.is_err() {
+                return Err(movement_info_result.unwrap_err());
+            }
+            for mut movement_info in movement_info_result.unwrap() {
+                // the sign follows the section the account is shown in
+                movement_info.account_category = FinanceReportHandle::get_section_category(
+                    &section_definitions,
+                    &account_list,
+                    &movement_info.account_id,
+                )
+                .unwrap();
+                amount_per_account
+                    .insert(movement_info.account_id, movement_info.get_signed_amount());
+            }
+        }
+
+        let sections = FinanceReportHandle::build_sections(
+            &section_definitions,
+            &account_list,
+            &account_type_list,
+            &amount_per_account,
+        );
+        let total_income =
+            FinanceReportHandle::get_sections_total(&sections, &[AccountCategory::Income]);
+        let total_expense =
+            FinanceReportHandle::get_sections_total(&sections, &[AccountCategory::Expense]);
+
+        let return_object = FinanceIncomeStatement {
+            time_from,
+            time_till,
+            sections,
+            total_income,
+            total_expense,
+            net_income: total_income - total_expense,
+        };
+        return Ok(return_object);
+    }
+
+    pub fn income_statement_sync(
+        &self,
+        time_from: DateTime<Utc>,
+        time_till: DateTime<Utc>,
+    ) -> Result<FinanceIncomeStatement, String> {
+        let temp_var_0 = executor::block_on(self.income_statement(time_from, time_till));
+        return temp_var_0;
+    }
+
+    /// debit and credit totals of all accounts between time_from and time_till (both inclusive),
+    /// without time_from every account starts with its own last saldo at or before time_till,
+    /// accounts without such a saldo contain all their movements since the first booking
+    pub async fn trial_balance(
+        &self,
+        time_from: Option<DateTime<Utc>>,
+        time_till: Option<DateTime<Utc>>,
+    ) -> Result<FinanceTrialBalance, String> {
+        if time_from.is_some() && time_till.is_some() && time_from.gt(&time_till) {
+            return Err("could not create report because time_from is after time_till".into());
+        }
+
+        let account_structure_result = self.load_account_structure().await;
+        if account_structure_result.is_err() {
+            return Err(account_structure_result.unwrap_err());
+        }
+        let (account_list, account_type_list) = account_structure_result.unwrap();
+        let account_ids: Vec<Uuid> = account_list.iter().map(|elem| elem.id).collect();
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+
+        let mut lines = Vec::new();
+        if !account_ids.is_empty() {
+            let mut saldo_information_list = HashMap::new();
+            if time_from.is_none() {
+                let saldo_information_list_result = booking_handle
+                    .finance_get_last_saldo_account_entries(Some(account_ids.clone()))
+                    .await;
+                if saldo_information_list_result.is_err() {
+                    return Err(format!(
+                        "Error getting saldo information: {}",
+                        saldo_information_list_result.unwrap_err()
+                    ));
+                }
+                saldo_information_list = saldo_information_list_result.unwrap();
+            }
+            let search_options = account_ids
+                .iter()
+                .map(|elem| {
+                    // with a last saldo after time_till an earlier saldo is searched within the entries
+                    let account_time_from = time_from.or(saldo_information_list
+                        .get(elem)
+                        .map(|saldo_information| saldo_information.booking_time)
+                        .filter(|saldo_time| {
+                            time_till.is_none() || saldo_time.le(time_till.as_ref().unwrap())
+                        }));
+                    FinanceAccountBookingEntryListSearchOption::new(
+                        elem,
+                        account_time_from,
+                        time_till,
+                    )
+                })
+                .collect();
+            let booking_entries_result = booking_handle
+                .list_account_booking_entries(search_options)
+                .await;
+            if booking_entries_result.is_err() {
+                return Err(format!(
+                    "Error getting booking entries: {}",
+                    booking_entries_result.unwrap_err()
+                ));
+            }
+            let booking_entries = booking_entries_result.unwrap();
+
+            for account in &account_list {
+                let account_booking_entries = booking_entries
+                    .iter()
+                    .filter(|elem| elem.finance_account_id.eq(&account.id));
+                let account_time_from = if time_from.is_some() {
+                    time_from
+                } else {
+                    account_booking_entries
+                        .clone()
+                        .filter(|elem| {
+                            elem.booking_type.eq(&BookingEntryType::SaldoCredit)
+                                || elem.booking_type.eq(&BookingEntryType::SaldoDebit)
+                        })
+                        .map(|elem| elem.booking_time)
+                        .max()
+                };
+                let mut sum_credit_amount: u64 = 0;
+                let mut sum_debit_amount: u64 = 0;
+                for booking_entry in account_booking_entries.filter(|elem| {
+                    account_time_from.is_none()
+                        || elem.booking_time.ge(account_time_from.as_ref().unwrap())
+                }) {
+                    match booking_entry.booking_type {
+                        BookingEntryType::Credit | BookingEntryType::SaldoCredit => {
+                            sum_credit_amount += booking_entry.amount
+                        }
+                        BookingEntryType::Debit | BookingEntryType::SaldoDebit => {
+                            sum_debit_amount += booking_entry.amount
+                        }
+                    }
+                }
+                let account_type_title = account_type_list
+                    .iter()
+                    .find(|elem| elem.id.eq(&account.finance_account_type_id))
+                    .map(|elem| elem.title.clone())
+                    .unwrap_or_default();
+                lines.push(FinanceTrialBalanceLine {
+                    account_id: account.id.to_string(),
+                    account_title: account.title.clone(),
+                    account_type_title,
+                    time_from: account_time_from,
+                    debit_total: sum_debit_amount,
+                    credit_total: sum_credit_amount,
+                    balance_type: if sum_credit_amount.gt(&sum_debit_amount) {
+                        AccountBalanceType::Credit
+                    } else {
+                        AccountBalanceType::Debit
+                    },
+                    balance: sum_credit_amount.abs_diff(sum_debit_amount),
+                });
+            }
+        }
+        lines.sort_by(|a, b| {
+            a.account_type_title
+                .cmp(&b.account_type_title)
+                .then(a.account_title.cmp(&b.account_title))
+        });
+
+        let total_debit: u64 = lines.iter().map(|elem| elem.debit_total).sum();
+        let total_credit: u64 = lines.iter().map(|elem| elem.credit_total).sum();
+        let total_debit_balance: u64 = lines
+            .iter()
+            .filter(|elem| elem.balance_type.eq(&AccountBalanceType::Debit))
+            .map(|elem| elem.balance)
+            .sum();
+        let total_credit_balance: u64 = lines
+            .iter()
+            .filter(|elem| elem.balance_type.eq(&AccountBalanceType::Credit))
+            .map(|elem| elem.balance)
+            .sum();
+
+        let return_object = FinanceTrialBalance {
+            time_from,
+            time_till,
+            lines,
+            total_debit,
+            total_credit,
+            total_debit_balance,
+            total_credit_balance,
+            is_balanced: total_debit.eq(&total_credit)
+                && total_debit_balance.eq(&total_credit_balance),
+        };
+        return Ok(return_object);
+    }
+
+    pub fn trial_balance_sync(
+        &self,
+        time_from: Option<DateTime<Utc>>,
+        time_till: Option<DateTime<Utc>>,
+    ) -> Result<FinanceTrialBalance, String> {
+        let temp_var_0 = executor::block_on(self.trial_balance(time_from, time_till));
+        return temp_var_0;
+    }
+
+    /// spending per tag and calendar month between time_from and time_till (both inclusive),
+    /// only movements on expense accounts are counted, an entry with several tags counts for each tag,
+    /// saldo entries and entries closing a period are ignored like in the income statement
+    pub async fn tag_spending_summary(
+        &self,
+        time_from: DateTime<Utc>,
+        time_till: DateTime<Utc>,
+    ) -> Result<FinanceTagSpendingSummary, String> {
+        if time_from.gt(&time_till) {
+            return Err("could not create report because time_from is after time_till".into());
+        }
+
+        let account_structure_result = self.load_account_structure().await;
+        if account_structure_result.is_err() {
+            return Err(account_structure_result.unwrap_err());
+        }
+        let (account_list, account_type_list) = account_structure_result.unwrap();
+
+        // the spending only depends on the category, the report section mappings are not used
+        let section_definitions = FinanceReportHandle::get_report_sections(
+            &vec![(AccountCategory::Expense, "expenses")],
+            &account_type_list,
+            &Vec::new(),
+        );
+        let account_ids =
+            FinanceReportHandle::get_account_ids_of_sections(&section_definitions, &account_list);
+
+        // key is (tag, month), value is the amount and the ids of the journal entries
+        let mut spending_per_tag_and_month: HashMap<(String, String), (i128, Vec<Uuid>)> =
+            HashMap::new();
+        if !account_ids.is_empty() {
+            let booking_handle = FinanceBookingHandle::new(
+                &self.db_connection_settings,
+                &self.user_id,
+                self.db_connector,
+            );
+            let booking_entries_result = booking_handle
+                .list_movement_booking_entries(&account_ids, time_from, time_till)
+                .await;
+            if booking_entries_result.is_err() {
+                return Err(booking_entries_result.unwrap_err());
+            }
+
+            for booking_entry in booking_entries_result.unwrap() {
+                let signed_amount = if booking_entry.booking_type.eq(&BookingEntryType::Debit) {
+                    booking_entry.amount as i128
+                } else {
+                    -(booking_entry.amount as i128)
+                };
+                let month = booking_entry.booking_time.format("%Y-%m").to_string();
+                for tag in &booking_entry.tags {
+                    let summary_entry = spending_per_tag_and_month
+                        .entry((tag.clone(), month.clone()))
+                        .or_insert((0, Vec::new()));
+                    summary_entry.0 += signed_amount;
+                    if !summary_entry
+                        .1
+                        .contains(&booking_entry.finance_journal_diary_id)
+                    {
+                        summary_entry.1.push(booking_entry.finance_journal_diary_id);
+                    }
+                }
+            }
+        }
+
+        let mut lines: Vec<FinanceTagSpendingLine> = spending_per_tag_and_month
+            .into_iter()
+            .map(
+                |((tag, month), (amount, journal_entry_ids))| FinanceTagSpendingLine {
+                    tag,
+                    month,
+                    amount,
+                    entry_count: journal_entry_ids.len() as u64,
+                },
+            )
+            .collect();
+        lines.sort_by(|a, b| a.tag.cmp(&b.tag).then(a.month.cmp(&b.month)));
+
+        let return_object = FinanceTagSpendingSummary {
+            time_from,
+            time_till,
+            lines,
+        };
+        return Ok(return_object);
+    }
+
+    pub fn tag_spending_summary_sync(
+        &self,
+        time_from: DateTime<Utc>,
+        time_till: DateTime<Utc>,
+    ) -> Result<FinanceTagSpendingSummary, String> {
+        let temp_var_0 = executor::block_on(self.tag_spending_summary(time_from, time_till));
+        return temp_var_0;
+    }
+
+    async fn load_account_structure(
+        &self,
+    ) -> Result<(Vec<FinanceAccount>, Vec<FinanceAccountType>), String> {
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle.finance_account_list_async(None).await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        let account_type_list_result = config_handle.finance_account_type_list_async().await;
+        if account_type_list_result.is_err() {
+            return Err(format!(
+                "Error getting account type information: {}",
+                account_type_list_result.unwrap_err()
+            ));
+        }
+        return Ok((
+            account_list_result.unwrap(),
+            account_type_list_result.unwrap(),
+        ));
+    }
+
+    async fn load_report_sections(
+        &self,
+        default_sections: &Vec<(AccountCategory, &'static str)>,
+        account_type_list: &Vec<FinanceAccountType>,
+    ) -> Result<Vec<FinanceReportSectionDefinition>, String> {
+        let report_section_mapping_list_result = self.report_section_mapping_list().await;
+        if report_section_mapping_list_result.is_err() {
+            return Err(format!(
+                "Error getting report section mappings: {}",
+                report_section_mapping_list_result.unwrap_err()
+            ));
+        }
+        return Ok(FinanceReportHandle::get_report_sections(
+            default_sections,
+            account_type_list,
+            &report_section_mapping_list_result.unwrap(),
+        ));
+    }
+
+    /// category of the section the account is shown in
+    fn get_section_category(
+        section_definitions: &Vec<FinanceReportSectionDefinition>,
+        account_list: &Vec<FinanceAccount>,
+        account_id: &Uuid,
+    ) -> Option<AccountCategory> {
+        return account_list
+            .iter()
+            .find(|elem| elem.id.eq(account_id))
+            .and_then(|account| {
+                section_definitions.iter().find(|elem| {
+                    elem.finance_account_type_ids
+                        .contains(&account.finance_account_type_id)
+                })
+            })
+            .map(|elem| elem.account_category.clone());
+    }
+
+    fn get_sections_total(
+        sections: &Vec<FinanceReportSection>,
+        account_categories: &[AccountCategory],
+    ) -> i128 {
+        return sections
+            .iter()
+            .filter(|elem| {
+                elem.account_categories
+                    .iter()
+                    .any(|account_category| account_categories.contains(account_category))
+            })
+            .map(|elem| elem.total)
+            .sum();
+    }
+
+    fn get_account_ids_of_sections(
+        section_definitions: &Vec<FinanceReportSectionDefinition>,
+        account_list: &Vec<FinanceAccount>,
+    ) -> Vec<Uuid> {
+        return account_list
+            .iter()
+            .filter(|account| {
+                section_definitions.iter().any(|section_definition| {
+                    section_definition
+                        .finance_account_type_ids
+                        .contains(&account.finance_account_type_id)
+                })
+            })
+            .map(|elem| elem.id)
+            .collect();
+    }
+
+    /// groups the accounts per account type (ordered by title) into the sections
+    fn build_sections(
+        section_definitions: &Vec<FinanceReportSectionDefinition>,
+        account_list: &Vec<FinanceAccount>,
+        account_type_list: &Vec<FinanceAccountType>,
+        amount_per_account: &HashMap<Uuid, i128>,
+    ) -> Vec<FinanceReportSection> {
+        let mut sorted_account_types: Vec<&FinanceAccountType> = account_type_list.iter().collect();
+        sorted_account_types.sort_by(|a, b| a.title.cmp(&b.title));
+
+        let mut return_object = Vec::new();
+        for section_definition in section_definitions {
+            let mut groups = Vec::new();
+            for account_type in &sorted_account_types {
+                if !section_definition
+                    .finance_account_type_ids
+                    .contains(&account_type.id)
+                {
+                    continue;
+                }
+                let mut accounts: Vec<FinanceReportAccountLine> = account_list
+                    .iter()
+                    .filter(|elem| elem.finance_account_type_id.eq(&account_type.id))
+                    .map(|elem| FinanceReportAccountLine {
+                        account_id: elem.id.to_string(),
+                        title: elem.title.clone(),
+                        amount: *amount_per_account.get(&elem.id).unwrap_or(&0),
+                    })
+                    .collect();
+                accounts.sort_by(|a, b| a.title.cmp(&b.title));
+                let group_total = accounts.iter().map(|elem| elem.amount).sum();
+                groups.push(FinanceReportTypeGroup {
+                    account_type_id: account_type.id.to_string(),
+                    title: account_type.title.clone(),
+                    accounts,
+                    total: group_total,
+                });
+            }
+            let section_total = groups.iter().map(|elem| elem.total).sum();
+            return_object.push(FinanceReportSection {
+                title: section_definition.title.clone(),
+                account_categories: vec![section_definition.account_category.clone()],
+                groups,
+                total: section_total,
+            });
+        }
+        return return_object;
+    }
+}
@@ -1,5 +1,6 @@
 use std::{
     borrow::Borrow,
+    collections::HashMap,
     env,
     ffi::OsString,
     fs::{self, read_dir},
@@ -9,13 +10,13 @@ use std::{
 
 use askama::Template;
 use async_session::{
-    chrono::{DateTime, Utc},
+    chrono::{DateTime, NaiveDate, Utc},
     serde_json::json,
     SessionStore,
 };
 use axum::{
     body::Body,
-    extract::Form,
+    extract::{Form, Multipart},
     http::{header, HeaderMap, HeaderValue, StatusCode, Uri},
     response::{IntoResponse, Response},
     Json,
@@ -28,17 +29,41 @@ use serde::{Deserialize, Serialize};
 use crate::{
     accounting_config_logic::FinanceAccountingConfigHandle,
     accounting_logic::FinanceBookingHandle,
-    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    accounting_report::FinanceReportHandle,
+    attachment_logic::{AttachmentStorageSetting, FinanceAttachmentHandle},
+    backup::FinanceBackupHandle,
+    bank_import::FinanceBankImportHandle,
+    booking_template_logic::FinanceBookingTemplateHandle,
+    budget_logic::FinanceBudgetHandle,
+    categorization_rule_logic::FinanceCategorizationRuleHandle,
+    convert_tools::ConvertTools,
+    csv_export::FinanceCsvExportHandle,
+    database_handler::DbHandler,
+    database_handler_mongodb::DbConnectionSetting,
     datatypes::{
-        FinanceAccount, FinanceAccountType, FinanceBookingRequest, PasswordResetRequest,
-        PasswordResetTokenRequest,
+        AccountCategory, AmountSignConvention, BankImportPreviewRow, BankStatement,
+        BankStatementBalanceCheck, BookingEntryType, BudgetPeriod, CsvExportSettings,
+        CsvImportProfile, DatevExportSettings, FinanceAccount, FinanceAccountType,
+        FinanceBalanceSheet, FinanceBookingLeg, FinanceBookingRequest, FinanceBookingTemplate,
+        FinanceBudget, FinanceBudgetReport, FinanceCategorizationRule, FinanceIncomeStatement,
+        FinanceRecurringBooking, FinanceReportSectionMapping, FinanceSplitBookingRequest,
+        FinanceTagSpendingSummary, FinanceTrialBalance, PasswordResetRequest,
+        PasswordResetTokenRequest, PlainTextAccountingFormat, RecurringBookingSchedule,
+    },
+    datev_export::FinanceDatevExportHandle,
+    frontend_functions::{
+        generate_account_tables_sync, get_booking_tags, get_booking_time, get_optional_report_time,
+        get_report_time, get_report_time_range, send_password_reset_email,
     },
-    frontend_functions::{generate_account_tables_sync, send_password_reset_email},
     html_render::{
         AccountTableTemplate, AccountTemplate, AccountTypeTemplate,
-        AccountingAccountSingleTableTemplate, HtmlTemplate,
+        AccountingAccountSingleTableTemplate, BalanceSheetRequest, BudgetReportRequest,
+        HtmlTemplate, IncomeStatementRequest, TrialBalanceRequest,
     },
     password_handle::{self, validate_credentials, UserCredentials},
+    plain_text_accounting::FinancePlainTextAccountingHandle,
+    reconciliation_logic::FinanceReconciliationHandle,
+    recurring_booking_logic::FinanceRecurringBookingHandle,
     session_data_handle::{SessionData, SessionDataResult},
     setting_struct::SettingStruct,
 };
@@ -191,7 +216,9 @@ pub async fn do_change_passwort(
                 instance: String::from(local_settings.backend_database_instance),
             };
 
-            match validate_credentials(&db_connection, &credentials).await {
+            let db_handler = DbHandler::new(&db_connection);
+
+            match validate_credentials(&db_connection, &db_handler, &credentials).await {
                 Ok(user_id) => {
                     debug!(target: "app::FinanceOverView","trying to change password for user {}", user_id);
 
@@ -200,9 +227,12 @@ pub async fn do_change_passwort(
                         password: password_new_1.clone(),
                     };
 
-                    let update_result =
-                        password_handle::update_user_password(&db_connection, &credentials_new)
-                            .await;
+                    let update_result = password_handle::update_user_password(
+                        &db_connection,
+                        &db_handler,
+                        &credentials_new,
+                    )
+                    .await;
 
                     if update_result.is_err() {
                         change_result = format!(
@@ -306,8 +336,10 @@ pub async fn do_change_reset_secret(
 
         debug!(target: "app::FinanceOverView","trying to change reset secret for user {}", user_id);
 
+        let db_handler = DbHandler::new(&db_connection);
         let update_result = password_handle::update_user_reset_secret(
             &db_connection,
+            &db_handler,
             &user_id,
             &input.new_reset_secret,
         )
@@ -409,8 +441,10 @@ pub async fn do_register_user_via_email(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
+        let db_handler = DbHandler::new(&db_connection);
         let register_result_2 = crate::frontend_functions::register_user_with_email_verfication(
             &db_connection,
+            &db_handler,
             _new_user_name,
             _new_password,
             _new_email,
@@ -497,9 +531,13 @@ pub async fn do_request_password_reset(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let password_reset_request_result =
-            crate::password_handle::request_password_reset_token(&db_connection, input.borrow())
-                .await;
+        let db_handler = DbHandler::new(&db_connection);
+        let password_reset_request_result = crate::password_handle::request_password_reset_token(
+            &db_connection,
+            &db_handler,
+            input.borrow(),
+        )
+        .await;
 
         if password_reset_request_result.is_err() {
             request_result = password_reset_request_result.unwrap_err().to_string();
@@ -592,8 +630,13 @@ pub async fn do_change_password(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let password_change_result =
-            crate::password_handle::reset_password_with_token(&db_connection, input.borrow()).await;
+        let db_handler = DbHandler::new(&db_connection);
+        let password_change_result = crate::password_handle::reset_password_with_token(
+            &db_connection,
+            &db_handler,
+            input.borrow(),
+        )
+        .await;
 
         if password_change_result.is_err() {
             request_result = password_change_result.unwrap_err().to_string();
@@ -623,6 +666,7 @@ pub async fn do_change_password(
 pub struct CreateNewFinanceAccountTypeFormInput {
     pub title: String,
     pub description: String,
+    pub account_category: String,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -681,11 +725,23 @@ pub async fn do_create_new_finance_account_type(
         let create_result: String;
         let new_title = &input.title;
         let new_description = &input.description;
+        let new_category_result = parse_account_category(&input.account_category);
+        if new_category_result.is_err() {
+            debug!(target: "app::FinanceOverView","error in function do_create_new_finance_account_type: {}",new_category_result.unwrap_err());
+            let return_value = CreateNewFinanceAccountTypeResponse {
+                result: "Error reading data".to_string(),
+                new_id: "".into(),
+                subpage: "".into(),
+            };
+
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
         let new_uuid = Uuid::new();
         let mut new_account_type = FinanceAccountType {
             id: new_uuid,
             title: new_title.into(),
             description: new_description.into(),
+            account_category: new_category_result.unwrap(),
         };
 
         session.expire_in(std::time::Duration::from_secs(60 * 10));
@@ -697,7 +753,7 @@ pub async fn do_create_new_finance_account_type(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let db_handler = DbHandlerMongoDB::new(&db_connection);
+        let db_handler = DbHandler::new(&db_connection);
         let user_id: Uuid = session.get("user_account_id").unwrap();
         let mut return_status_code = StatusCode::OK;
         {
@@ -716,11 +772,7 @@ pub async fn do_create_new_finance_account_type(
             }
         }
 
-        let new_account_type_template = AccountTypeTemplate {
-            id: new_account_type.id.to_string(),
-            name: new_account_type.title,
-            description: new_account_type.description,
-        };
+        let new_account_type_template = AccountTypeTemplate::from_account_type(&new_account_type);
         let response_html_result = HtmlTemplate(AccountTypeCreateResponseTemplate {
             account_type: new_account_type_template,
         })
@@ -745,6 +797,27 @@ pub struct UpdateFinanceAccountTypeFormInput {
     pub account_type_id: String,
     pub title: String,
     pub description: String,
+    pub account_category: String,
+}
+
+/// every account type needs a category, empty input means no category was selected
+fn parse_account_category(input_value: &String) -> Result<AccountCategory, String> {
+    if input_value.trim().is_empty() {
+        return Err("account category is missing".into());
+    }
+    let category_value_result = input_value.trim().parse::<i32>();
+    if category_value_result.is_err() {
+        return Err(format!(
+            "could not parse account category {}: {}",
+            input_value,
+            category_value_result.unwrap_err()
+        ));
+    }
+    let category_result = AccountCategory::get_from_int(category_value_result.unwrap());
+    if category_result.is_err() {
+        return Err(category_result.unwrap_err());
+    }
+    return Ok(category_result.unwrap());
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -801,10 +874,21 @@ pub async fn do_update_finance_account_type(
             return (StatusCode::BAD_REQUEST, headers, return_value);
         }
 
+        let new_category_result = parse_account_category(&input.account_category);
+        if new_category_result.is_err() {
+            debug!(target: "app::FinanceOverView","error in function do_update_finance_account_type: {}",new_category_result.unwrap_err());
+            let return_value = UpdateFinanceAccountTypeResponse {
+                result: "Error reading data".to_string(),
+            };
+
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
         let mut old_account_type = FinanceAccountType {
             id: old_uuid.unwrap(),
             title: new_title.into(),
             description: new_description.into(),
+            account_category: new_category_result.unwrap(),
         };
 
         session.expire_in(std::time::Duration::from_secs(60 * 10));
@@ -816,7 +900,7 @@ pub async fn do_update_finance_account_type(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let db_handler = DbHandlerMongoDB::new(&db_connection);
+        let db_handler = DbHandler::new(&db_connection);
         let user_id: Uuid = session.get("user_account_id").unwrap();
         let mut return_status_code = StatusCode::OK;
         {
@@ -850,6 +934,22 @@ pub struct CreateNewFinanceAccountFormInput {
     pub title: String,
     pub description: String,
     pub account_type_id: String,
+    /// empty for accounts without number
+    pub account_number: Option<String>,
+}
+
+fn get_optional_account_number(input_value: &Option<String>) -> Result<Option<u32>, String> {
+    if input_value.is_none() || input_value.as_ref().unwrap().trim().is_empty() {
+        return Ok(None);
+    }
+    let parse_result = input_value.as_ref().unwrap().trim().parse::<u32>();
+    if parse_result.is_err() {
+        return Err(format!(
+            "could not parse account number: {}",
+            parse_result.unwrap_err()
+        ));
+    }
+    return Ok(Some(parse_result.unwrap()));
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -922,11 +1022,21 @@ pub async fn do_create_new_finance_account(
             );
             return (StatusCode::BAD_REQUEST, headers, return_value);
         }
+        let new_account_number_result = get_optional_account_number(&input.account_number);
+        if new_account_number_result.is_err() {
+            let return_value = CreateNewFinanceAccountResponse {
+                result: new_account_number_result.unwrap_err(),
+                new_id: "".into(),
+                subpage: "".into(),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
         let mut new_account = FinanceAccount {
             id: new_uuid,
             title: new_title.into(),
             description: new_description.into(),
             finance_account_type_id: new_finance_account_type_id_result.unwrap(),
+            account_number: new_account_number_result.unwrap(),
         };
         let mut available_types = Vec::new();
 
@@ -939,7 +1049,7 @@ pub async fn do_create_new_finance_account(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let db_handler = DbHandlerMongoDB::new(&db_connection);
+        let db_handler = DbHandler::new(&db_connection);
         let user_id: Uuid = session.get("user_account_id").unwrap();
         let mut return_status_code = StatusCode::OK;
         {
@@ -979,6 +1089,7 @@ pub async fn do_create_new_finance_account(
             name: new_account.title,
             description: new_account.description,
             type_title: type_title.into(),
+            account_number: AccountTemplate::format_account_number(&new_account.account_number),
         };
         let response_html_result = HtmlTemplate(AccountCreateResponseTemplate {
             account: new_account_template,
@@ -1004,6 +1115,8 @@ pub struct UpdateFinanceAccountFormInput {
     pub account_id: String,
     pub title: String,
     pub description: String,
+    /// the stored number is kept when the field is missing, an empty value removes it
+    pub account_number: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1059,6 +1172,14 @@ pub async fn do_update_finance_account(
 
             return (StatusCode::BAD_REQUEST, headers, return_value);
         }
+        let new_account_number_result = get_optional_account_number(&input.account_number);
+        if new_account_number_result.is_err() {
+            let return_value = UpdateFinanceAccountResponse {
+                result: new_account_number_result.unwrap_err(),
+            };
+
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
 
         session.expire_in(std::time::Duration::from_secs(60 * 10));
 
@@ -1069,7 +1190,7 @@ pub async fn do_update_finance_account(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let db_handler = DbHandlerMongoDB::new(&db_connection);
+        let db_handler = DbHandler::new(&db_connection);
         let user_id: Uuid = session.get("user_account_id").unwrap();
         let mut return_status_code = StatusCode::OK;
         {
@@ -1105,6 +1226,11 @@ pub async fn do_update_finance_account(
                     .finance_account_type_id,
                 title: new_title.into(),
                 description: new_description.into(),
+                account_number: if input.account_number.is_some() {
+                    new_account_number_result.unwrap()
+                } else {
+                    available_accounts[position_result.unwrap()].account_number
+                },
             };
 
             let upsert_result_2 =
@@ -1136,6 +1262,9 @@ pub struct CreateBookingEntryFormInput {
     pub amount: u64,
     pub title: String,
     pub description: String,
+    pub booking_date: Option<String>,
+    pub booking_time: Option<String>,
+    pub tags: Option<String>,
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -1149,9 +1278,52 @@ impl IntoResponse for CreateBookingEntryResponse {
     }
 }
 
-pub async fn do_create_booking_entry(
+#[derive(Deserialize, Debug)]
+pub struct CreateSplitBookingEntryLegInput {
+    pub account_id: String,
+    pub amount: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpsertBookingTemplateFormInput {
+    /// empty for a new template
+    pub booking_template_id: Option<String>,
+    pub name: String,
+    pub credit_account_id: String,
+    pub debit_account_id: String,
+    pub amount: u64,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeleteBookingTemplateFormInput {
+    pub booking_template_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateBookingEntryFromTemplateFormInput {
+    pub booking_template_id: String,
+    /// replaces the amount of the template when given
+    pub amount: Option<u64>,
+    pub booking_date: Option<String>,
+    pub booking_time: Option<String>,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct BookingTemplateResponse {
+    pub result: String,
+}
+
+impl IntoResponse for BookingTemplateResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_upsert_booking_template(
     session_data: SessionDataResult,
-    Form(input): Form<CreateBookingEntryFormInput>,
+    Form(input): Form<UpsertBookingTemplateFormInput>,
 ) -> impl IntoResponse {
     let session_data = SessionData::from_session_data_result(session_data);
 
@@ -1162,7 +1334,7 @@ pub async fn do_create_booking_entry(
     let mut headers = HeaderMap::new();
 
     if !is_logged_in {
-        let return_value = CreateBookingEntryResponse {
+        let return_value = BookingTemplateResponse {
             result: "not logged in".to_string(),
         };
         headers.insert(
@@ -1173,15 +1345,13 @@ pub async fn do_create_booking_entry(
     }
 
     if session.is_expired() {
-        let return_value = CreateBookingEntryResponse {
+        let return_value = BookingTemplateResponse {
             result: "session expired".to_string(),
         };
 
         (StatusCode::BAD_REQUEST, headers, return_value)
     } else {
-        let create_result: String;
-
-        session.expire_in(std::time::Duration::from_secs(60 * 10));
+        let upsert_result: String;
 
         let local_settings: SettingStruct = SettingStruct::global().clone();
         let db_connection = DbConnectionSetting {
@@ -1190,14 +1360,37 @@ pub async fn do_create_booking_entry(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let db_handler = DbHandlerMongoDB::new(&db_connection);
+        let db_handler = DbHandler::new(&db_connection);
         let user_id: Uuid = session.get("user_account_id").unwrap();
 
         let mut return_status_code = StatusCode::OK;
         {
+            let booking_template_id = if input.booking_template_id.is_none()
+                || input
+                    .booking_template_id
+                    .as_ref()
+                    .unwrap()
+                    .trim()
+                    .is_empty()
+            {
+                Uuid::new()
+            } else {
+                let booking_template_id_parse =
+                    Uuid::parse_str(input.booking_template_id.as_ref().unwrap());
+                if booking_template_id_parse.is_err() {
+                    let return_value = BookingTemplateResponse {
+                        result: format!(
+                            "error parsing booking_template_id: {}",
+                            booking_template_id_parse.unwrap_err()
+                        ),
+                    };
+                    return (StatusCode::BAD_REQUEST, headers, return_value);
+                }
+                booking_template_id_parse.unwrap()
+            };
             let debit_account_id_parse = Uuid::parse_str(&input.debit_account_id);
             if debit_account_id_parse.is_err() {
-                let return_value = CreateBookingEntryResponse {
+                let return_value = BookingTemplateResponse {
                     result: format!(
                         "error parsing debit_account_id: {}",
                         debit_account_id_parse.unwrap_err()
@@ -1205,10 +1398,9 @@ pub async fn do_create_booking_entry(
                 };
                 return (StatusCode::BAD_REQUEST, headers, return_value);
             }
-
             let credit_account_id_parse = Uuid::parse_str(&input.credit_account_id);
             if credit_account_id_parse.is_err() {
-                let return_value = CreateBookingEntryResponse {
+                let return_value = BookingTemplateResponse {
                     result: format!(
                         "error parsing credit_account_id: {}",
                         credit_account_id_parse.unwrap_err()
@@ -1216,39 +1408,34 @@ pub async fn do_create_booking_entry(
                 };
                 return (StatusCode::BAD_REQUEST, headers, return_value);
             }
-            let current_time = Utc::now();
 
-            let booking_config_handle =
-                FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
-
-            let action_to_insert = FinanceBookingRequest {
-                is_simple_entry: true,
-                is_saldo: false,
+            // same account mapping as do_create_booking_entry
+            let booking_template = FinanceBookingTemplate {
+                id: booking_template_id,
+                name: input.name,
                 debit_finance_account_id: credit_account_id_parse.unwrap(),
                 credit_finance_account_id: debit_account_id_parse.unwrap(),
-                booking_time: current_time,
                 amount: input.amount,
                 title: input.title,
                 description: input.description,
             };
 
-            //let create_result_response_async =  booking_config_handle.finance_insert_booking_entry(&action_to_insert).await;
-            let create_result_response =
-                booking_config_handle.finance_insert_booking_entry_sync(&action_to_insert);
-            {
-                if create_result_response.is_err() {
-                    return_status_code = StatusCode::BAD_REQUEST;
-                    create_result = create_result_response.unwrap_err().to_string()
-                } else {
-                    create_result = "OK, booking request inserted".to_string();
-                };
+            let booking_template_handle =
+                FinanceBookingTemplateHandle::new(&db_connection, &user_id, &db_handler);
+            let upsert_response =
+                booking_template_handle.booking_template_upsert_sync(&booking_template);
+            if upsert_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                upsert_result = upsert_response.unwrap_err();
+            } else {
+                upsert_result = format!("OK, booking template {} saved", booking_template.name);
             }
         }
 
-        session.expire_in(std::time::Duration::from_secs(60 * 1));
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
 
-        let return_value = CreateBookingEntryResponse {
-            result: create_result,
+        let return_value = BookingTemplateResponse {
+            result: upsert_result,
         };
 
         let _new_cookie = session_data.session_store.store_session(session).await;
@@ -1257,36 +1444,99 @@ pub async fn do_create_booking_entry(
     }
 }
 
-#[derive(Deserialize, Debug)]
-pub struct GetAccountTableRequest {
-    pub account_id: String,
-}
+pub async fn do_delete_booking_template(
+    session_data: SessionDataResult,
+    Form(input): Form<DeleteBookingTemplateFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
 
-#[derive(Serialize, Deserialize, Debug, Clone)]
-pub(crate) struct GetAccountTableResponse {
-    pub result: String,
-}
+    let mut session = session_data.session_option.unwrap().clone();
 
-impl IntoResponse for GetAccountTableResponse {
-    fn into_response(self) -> Response {
-        return Json(json!(self)).into_response();
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BookingTemplateResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BookingTemplateResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let delete_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let booking_template_id_parse = Uuid::parse_str(&input.booking_template_id);
+            if booking_template_id_parse.is_err() {
+                let return_value = BookingTemplateResponse {
+                    result: format!(
+                        "error parsing booking_template_id: {}",
+                        booking_template_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let booking_template_handle =
+                FinanceBookingTemplateHandle::new(&db_connection, &user_id, &db_handler);
+            let delete_response = booking_template_handle
+                .booking_template_delete_sync(&booking_template_id_parse.unwrap());
+            if delete_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                delete_result = delete_response.unwrap_err();
+            } else {
+                delete_result = "OK, booking template deleted".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = BookingTemplateResponse {
+            result: delete_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
     }
 }
 
-pub async fn do_get_account_table_request(
+pub async fn do_create_booking_entry_from_template(
     session_data: SessionDataResult,
-    Form(input): Form<GetAccountTableRequest>,
+    Form(input): Form<CreateBookingEntryFromTemplateFormInput>,
 ) -> impl IntoResponse {
     let session_data = SessionData::from_session_data_result(session_data);
 
-    let session = session_data.session_option.unwrap().clone();
+    let mut session = session_data.session_option.unwrap().clone();
 
     let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
 
     let mut headers = HeaderMap::new();
 
     if !is_logged_in {
-        let return_value = GetAccountTableResponse {
+        let return_value = CreateBookingEntryResponse {
             result: "not logged in".to_string(),
         };
         headers.insert(
@@ -1297,12 +1547,14 @@ pub async fn do_get_account_table_request(
     }
 
     if session.is_expired() {
-        let return_value = GetAccountTableResponse {
+        let return_value = CreateBookingEntryResponse {
             result: "session expired".to_string(),
         };
 
-        return (StatusCode::BAD_REQUEST, headers, return_value);
+        (StatusCode::BAD_REQUEST, headers, return_value)
     } else {
+        let create_result: String;
+
         let local_settings: SettingStruct = SettingStruct::global().clone();
         let db_connection = DbConnectionSetting {
             url: String::from(local_settings.backend_database_url),
@@ -1310,61 +1562,4317 @@ pub async fn do_get_account_table_request(
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
-        let db_handler = DbHandlerMongoDB::new(&db_connection);
+        let db_handler = DbHandler::new(&db_connection);
         let user_id: Uuid = session.get("user_account_id").unwrap();
-        let username: String = session.get("user_name").unwrap();
 
-        let return_status_code = StatusCode::OK;
+        let mut return_status_code = StatusCode::OK;
         {
-            let account_id_parse = Uuid::parse_str(&input.account_id);
-            if account_id_parse.is_err() {
-                let return_value = GetAccountTableResponse {
+            let booking_template_id_parse = Uuid::parse_str(&input.booking_template_id);
+            if booking_template_id_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
                     result: format!(
-                        "error parsing account_id: {}",
-                        account_id_parse.unwrap_err()
+                        "error parsing booking_template_id: {}",
+                        booking_template_id_parse.unwrap_err()
                     ),
                 };
                 return (StatusCode::BAD_REQUEST, headers, return_value);
             }
-
-            let account_config_handle =
-                FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
-
-            let accounting_booking_handle =
-                FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
-
-            let table_generate_result = generate_account_tables_sync(
-                &accounting_booking_handle,
-                &account_config_handle,
-                Some(&vec![account_id_parse.unwrap()]),
-            );
-            if table_generate_result.is_err() {
-                warn!(target: "app::FinanceOverView","error in do_get_account_table_request for user {}: {}",username,table_generate_result.unwrap_err());
-                let return_value = GetAccountTableResponse {
-                    result: "problems while getting account tables".to_string(),
+            let booking_time_parse = get_booking_time(&input.booking_date, &input.booking_time);
+            if booking_time_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
+                    result: format!(
+                        "error parsing booking date: {}",
+                        booking_time_parse.unwrap_err()
+                    ),
                 };
                 return (StatusCode::BAD_REQUEST, headers, return_value);
             }
-            let table_generate_value = table_generate_result.unwrap();
-            let first_value = &table_generate_value[0];
 
-            let single_table = AccountTableTemplate {
-                account_name: first_value.account_name.clone(),
-                booking_rows: first_value.booking_rows.clone(),
-            };
+            let booking_template_handle =
+                FinanceBookingTemplateHandle::new(&db_connection, &user_id, &db_handler);
+            let create_result_response = booking_template_handle
+                .booking_template_insert_booking_entry_sync(
+                    &booking_template_id_parse.unwrap(),
+                    booking_time_parse.unwrap(),
+                    input.amount,
+                );
+            if create_result_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                create_result = create_result_response.unwrap_err();
+            } else {
+                create_result = "OK, booking request inserted".to_string();
+            }
+        }
 
-            let response_html_result = HtmlTemplate(AccountingAccountSingleTableTemplate {
-                account_table: single_table,
-            })
-            .0
-            .render();
-            let return_html = response_html_result.unwrap();
+        session.expire_in(std::time::Duration::from_secs(60 * 1));
 
-            let return_value = GetAccountTableResponse {
-                result: return_html,
-            };
+        let return_value = CreateBookingEntryResponse {
+            result: create_result,
+        };
 
-            return (return_status_code, headers, return_value);
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateSplitBookingEntryInput {
+    pub debit_legs: Vec<CreateSplitBookingEntryLegInput>,
+    pub credit_legs: Vec<CreateSplitBookingEntryLegInput>,
+    pub title: String,
+    pub description: String,
+    pub booking_date: Option<String>,
+    pub booking_time: Option<String>,
+    pub tags: Option<String>,
+}
+
+fn parse_split_booking_legs(
+    legs_input: &Vec<CreateSplitBookingEntryLegInput>,
+) -> Result<Vec<FinanceBookingLeg>, String> {
+    let mut return_object = Vec::new();
+    for leg_input in legs_input {
+        let account_id_parse = Uuid::parse_str(&leg_input.account_id);
+        if account_id_parse.is_err() {
+            return Err(format!(
+                "error parsing account_id {}: {}",
+                leg_input.account_id,
+                account_id_parse.unwrap_err()
+            ));
         }
+        return_object.push(FinanceBookingLeg {
+            finance_account_id: account_id_parse.unwrap(),
+            amount: leg_input.amount,
+        });
+    }
+    return Ok(return_object);
+}
+
+pub async fn do_create_split_booking_entry(
+    session_data: SessionDataResult,
+    Json(input): Json<CreateSplitBookingEntryInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = CreateBookingEntryResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = CreateBookingEntryResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let create_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let debit_legs_parse = parse_split_booking_legs(&input.debit_legs);
+            if debit_legs_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
+                    result: format!("debit legs: {}", debit_legs_parse.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let credit_legs_parse = parse_split_booking_legs(&input.credit_legs);
+            if credit_legs_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
+                    result: format!("credit legs: {}", credit_legs_parse.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let booking_time_parse = get_booking_time(&input.booking_date, &input.booking_time);
+            if booking_time_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
+                    result: format!(
+                        "error parsing booking date: {}",
+                        booking_time_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let booking_handle = FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
+
+            let action_to_insert = FinanceSplitBookingRequest {
+                is_saldo: false,
+                is_closing: false,
+                debit_legs: debit_legs_parse.unwrap(),
+                credit_legs: credit_legs_parse.unwrap(),
+                booking_time: booking_time_parse.unwrap(),
+                title: input.title,
+                description: input.description,
+                tags: get_booking_tags(&input.tags),
+                import_reference: None,
+            };
+
+            let create_result_response =
+                booking_handle.finance_insert_split_booking_entry_sync(&action_to_insert);
+            if create_result_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                create_result = create_result_response.unwrap_err().to_string()
+            } else {
+                create_result = "OK, split booking request inserted".to_string();
+            };
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = CreateBookingEntryResponse {
+            result: create_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+pub async fn do_create_booking_entry(
+    session_data: SessionDataResult,
+    Form(input): Form<CreateBookingEntryFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = CreateBookingEntryResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = CreateBookingEntryResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let create_result: String;
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let debit_account_id_parse = Uuid::parse_str(&input.debit_account_id);
+            if debit_account_id_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
+                    result: format!(
+                        "error parsing debit_account_id: {}",
+                        debit_account_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let credit_account_id_parse = Uuid::parse_str(&input.credit_account_id);
+            if credit_account_id_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
+                    result: format!(
+                        "error parsing credit_account_id: {}",
+                        credit_account_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let booking_time_parse = get_booking_time(&input.booking_date, &input.booking_time);
+            if booking_time_parse.is_err() {
+                let return_value = CreateBookingEntryResponse {
+                    result: format!(
+                        "error parsing booking date: {}",
+                        booking_time_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let booking_config_handle =
+                FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
+
+            let action_to_insert = FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: credit_account_id_parse.unwrap(),
+                credit_finance_account_id: debit_account_id_parse.unwrap(),
+                booking_time: booking_time_parse.unwrap(),
+                amount: input.amount,
+                title: input.title,
+                description: input.description,
+                tags: get_booking_tags(&input.tags),
+                import_reference: None,
+            };
+
+            //let create_result_response_async =  booking_config_handle.finance_insert_booking_entry(&action_to_insert).await;
+            let create_result_response =
+                booking_config_handle.finance_insert_booking_entry_sync(&action_to_insert);
+            {
+                if create_result_response.is_err() {
+                    return_status_code = StatusCode::BAD_REQUEST;
+                    create_result = create_result_response.unwrap_err().to_string()
+                } else {
+                    create_result = "OK, booking request inserted".to_string();
+                };
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 1));
+
+        let return_value = CreateBookingEntryResponse {
+            result: create_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct ReverseJournalEntryFormInput {
+    pub journal_entry_id: String,
+    pub reason: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ReverseJournalEntryResponse {
+    pub result: String,
+}
+
+impl IntoResponse for ReverseJournalEntryResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_reverse_journal_entry(
+    session_data: SessionDataResult,
+    Form(input): Form<ReverseJournalEntryFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = ReverseJournalEntryResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = ReverseJournalEntryResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let reverse_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let journal_entry_id_parse = Uuid::parse_str(&input.journal_entry_id);
+            if journal_entry_id_parse.is_err() {
+                let return_value = ReverseJournalEntryResponse {
+                    result: format!(
+                        "error parsing journal_entry_id: {}",
+                        journal_entry_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let booking_handle = FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
+
+            let reverse_result_response = booking_handle
+                .reverse_journal_entry_sync(&journal_entry_id_parse.unwrap(), &input.reason);
+            if reverse_result_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                reverse_result = reverse_result_response.unwrap_err().to_string()
+            } else {
+                reverse_result = format!(
+                    "OK, journal entry reversed by entry #{}",
+                    reverse_result_response
+                        .unwrap()
+                        .journal_entry
+                        .running_number
+                );
+            };
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = ReverseJournalEntryResponse {
+            result: reverse_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CloseAccountsFormInput {
+    pub closing_date: String,
+    pub closing_account_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct CloseAccountsResponse {
+    pub result: String,
+}
+
+impl IntoResponse for CloseAccountsResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+/// closes all accounts of the user against the selected closing account at the end of the given day
+pub async fn do_close_accounts(
+    session_data: SessionDataResult,
+    Form(input): Form<CloseAccountsFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = CloseAccountsResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = CloseAccountsResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let close_result: String;
+
+        let closing_time_parse =
+            ConvertTools::get_utc_time_from_date_input(&input.closing_date, true);
+        if closing_time_parse.is_err() {
+            let return_value = CloseAccountsResponse {
+                result: format!(
+                    "error parsing closing_date: {}",
+                    closing_time_parse.unwrap_err()
+                ),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let closing_time = closing_time_parse.unwrap();
+
+        let closing_account_id_parse = Uuid::parse_str(&input.closing_account_id);
+        if closing_account_id_parse.is_err() {
+            let return_value = CloseAccountsResponse {
+                result: format!(
+                    "error parsing closing_account_id: {}",
+                    closing_account_id_parse.unwrap_err()
+                ),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let closing_account_id = closing_account_id_parse.unwrap();
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let accounting_config_handle =
+                FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+            let available_accounts_result = accounting_config_handle.finance_account_list(None);
+            if available_accounts_result.is_err() {
+                debug!(target: "app::FinanceOverView","error in function do_close_accounts, could not load available accounts for user {}",&user_id);
+                let return_value = CloseAccountsResponse {
+                    result: "Error reading database".to_string(),
+                };
+
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let accounts_to_close: Vec<Uuid> = available_accounts_result
+                .unwrap()
+                .iter()
+                .filter(|elem| !elem.id.eq(&closing_account_id))
+                .map(|elem| elem.id)
+                .collect();
+
+            let booking_handle = FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
+
+            let close_result_response = booking_handle.close_accounts_sync(
+                &accounts_to_close,
+                &closing_account_id,
+                closing_time,
+            );
+            if close_result_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                close_result = close_result_response.unwrap_err().to_string()
+            } else {
+                close_result = format!(
+                    "OK, accounts closed with {} journal entries",
+                    close_result_response.unwrap().len()
+                );
+            };
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = CloseAccountsResponse {
+            result: close_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct GetAccountTableRequest {
+    pub account_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct GetAccountTableResponse {
+    pub result: String,
+}
+
+impl IntoResponse for GetAccountTableResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_get_account_table_request(
+    session_data: SessionDataResult,
+    Form(input): Form<GetAccountTableRequest>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = GetAccountTableResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = GetAccountTableResponse {
+            result: "session expired".to_string(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    } else {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let username: String = session.get("user_name").unwrap();
+
+        let return_status_code = StatusCode::OK;
+        {
+            let account_id_parse = Uuid::parse_str(&input.account_id);
+            if account_id_parse.is_err() {
+                let return_value = GetAccountTableResponse {
+                    result: format!(
+                        "error parsing account_id: {}",
+                        account_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let account_config_handle =
+                FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+
+            let accounting_booking_handle =
+                FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
+
+            let table_generate_result = generate_account_tables_sync(
+                &accounting_booking_handle,
+                &account_config_handle,
+                Some(&vec![account_id_parse.unwrap()]),
+            );
+            if table_generate_result.is_err() {
+                warn!(target: "app::FinanceOverView","error in do_get_account_table_request for user {}: {}",username,table_generate_result.unwrap_err());
+                let return_value = GetAccountTableResponse {
+                    result: "problems while getting account tables".to_string(),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let table_generate_value = table_generate_result.unwrap();
+            let first_value = &table_generate_value[0];
+
+            let single_table = AccountTableTemplate {
+                account_name: first_value.account_name.clone(),
+                booking_rows: first_value.booking_rows.clone(),
+            };
+
+            let response_html_result = HtmlTemplate(AccountingAccountSingleTableTemplate {
+                account_table: single_table,
+            })
+            .0
+            .render();
+            let return_html = response_html_result.unwrap();
+
+            let return_value = GetAccountTableResponse {
+                result: return_html,
+            };
+
+            return (return_status_code, headers, return_value);
+        }
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct GetBalanceSheetResponse {
+    pub result: String,
+    pub report: Option<FinanceBalanceSheet>,
+}
+
+impl IntoResponse for GetBalanceSheetResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_get_balance_sheet(
+    session_data: SessionDataResult,
+    Form(input): Form<BalanceSheetRequest>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = GetBalanceSheetResponse {
+            result: "not logged in".to_string(),
+            report: None,
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = GetBalanceSheetResponse {
+            result: "session expired".to_string(),
+            report: None,
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    } else {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let username: String = session.get("user_name").unwrap();
+
+        let report_time_result = get_report_time(&input.report_date);
+        if report_time_result.is_err() {
+            let return_value = GetBalanceSheetResponse {
+                result: format!(
+                    "error parsing report_date: {}",
+                    report_time_result.unwrap_err()
+                ),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        let balance_sheet_result = report_handle.balance_sheet_sync(report_time_result.unwrap());
+        if balance_sheet_result.is_err() {
+            warn!(target: "app::FinanceOverView","error in do_get_balance_sheet for user {}: {}",username,balance_sheet_result.unwrap_err());
+            let return_value = GetBalanceSheetResponse {
+                result: "problems while creating balance sheet".to_string(),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let return_value = GetBalanceSheetResponse {
+            result: "balance sheet created".to_string(),
+            report: Some(balance_sheet_result.unwrap()),
+        };
+
+        return (StatusCode::OK, headers, return_value);
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpsertReportSectionMappingFormInput {
+    pub finance_account_type_id: String,
+    pub section_category: i32,
+    pub section_title: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeleteReportSectionMappingFormInput {
+    pub finance_account_type_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ReportSectionMappingResponse {
+    pub result: String,
+}
+
+impl IntoResponse for ReportSectionMappingResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_upsert_report_section_mapping(
+    session_data: SessionDataResult,
+    Form(input): Form<UpsertReportSectionMappingFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = ReportSectionMappingResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = ReportSectionMappingResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let upsert_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let account_type_id_parse = Uuid::parse_str(&input.finance_account_type_id);
+            if account_type_id_parse.is_err() {
+                let return_value = ReportSectionMappingResponse {
+                    result: format!(
+                        "error parsing finance_account_type_id: {}",
+                        account_type_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let section_category_parse = AccountCategory::get_from_int(input.section_category);
+            if section_category_parse.is_err() {
+                let return_value = ReportSectionMappingResponse {
+                    result: format!(
+                        "error parsing section_category: {}",
+                        section_category_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let report_section_mapping = FinanceReportSectionMapping {
+                finance_account_type_id: account_type_id_parse.unwrap(),
+                section_category: section_category_parse.unwrap(),
+                section_title: input.section_title,
+            };
+            let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+            let upsert_response =
+                report_handle.report_section_mapping_upsert_sync(&report_section_mapping);
+            if upsert_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                upsert_result = upsert_response.unwrap_err();
+            } else {
+                upsert_result = "OK, report section saved".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = ReportSectionMappingResponse {
+            result: upsert_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+pub async fn do_delete_report_section_mapping(
+    session_data: SessionDataResult,
+    Form(input): Form<DeleteReportSectionMappingFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = ReportSectionMappingResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = ReportSectionMappingResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let delete_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let account_type_id_parse = Uuid::parse_str(&input.finance_account_type_id);
+            if account_type_id_parse.is_err() {
+                let return_value = ReportSectionMappingResponse {
+                    result: format!(
+                        "error parsing finance_account_type_id: {}",
+                        account_type_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+            let delete_response =
+                report_handle.report_section_mapping_delete_sync(&account_type_id_parse.unwrap());
+            if delete_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                delete_result = delete_response.unwrap_err();
+            } else {
+                delete_result = "OK, account type moved back to its default section".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = ReportSectionMappingResponse {
+            result: delete_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct GetIncomeStatementResponse {
+    pub result: String,
+    pub report: Option<FinanceIncomeStatement>,
+}
+
+impl IntoResponse for GetIncomeStatementResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_get_income_statement(
+    session_data: SessionDataResult,
+    Form(input): Form<IncomeStatementRequest>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = GetIncomeStatementResponse {
+            result: "not logged in".to_string(),
+            report: None,
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = GetIncomeStatementResponse {
+            result: "session expired".to_string(),
+            report: None,
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    } else {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let username: String = session.get("user_name").unwrap();
+
+        let report_time_range_result = get_report_time_range(&input.date_from, &input.date_till);
+        if report_time_range_result.is_err() {
+            let return_value = GetIncomeStatementResponse {
+                result: format!(
+                    "error parsing report dates: {}",
+                    report_time_range_result.unwrap_err()
+                ),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let (time_from, time_till) = report_time_range_result.unwrap();
+
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        let income_statement_result = report_handle.income_statement_sync(time_from, time_till);
+        if income_statement_result.is_err() {
+            warn!(target: "app::FinanceOverView","error in do_get_income_statement for user {}: {}",username,income_statement_result.unwrap_err());
+            let return_value = GetIncomeStatementResponse {
+                result: "problems while creating income statement".to_string(),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let return_value = GetIncomeStatementResponse {
+            result: "income statement created".to_string(),
+            report: Some(income_statement_result.unwrap()),
+        };
+
+        return (StatusCode::OK, headers, return_value);
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct GetTagSummaryResponse {
+    pub result: String,
+    pub report: Option<FinanceTagSpendingSummary>,
+}
+
+impl IntoResponse for GetTagSummaryResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_get_tag_summary(
+    session_data: SessionDataResult,
+    Form(input): Form<IncomeStatementRequest>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = GetTagSummaryResponse {
+            result: "not logged in".to_string(),
+            report: None,
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = GetTagSummaryResponse {
+            result: "session expired".to_string(),
+            report: None,
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    } else {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let username: String = session.get("user_name").unwrap();
+
+        let report_time_range_result = get_report_time_range(&input.date_from, &input.date_till);
+        if report_time_range_result.is_err() {
+            let return_value = GetTagSummaryResponse {
+                result: format!(
+                    "error parsing report dates: {}",
+                    report_time_range_result.unwrap_err()
+                ),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let (time_from, time_till) = report_time_range_result.unwrap();
+
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        let tag_summary_result = report_handle.tag_spending_summary_sync(time_from, time_till);
+        if tag_summary_result.is_err() {
+            warn!(target: "app::FinanceOverView","error in do_get_tag_summary for user {}: {}",username,tag_summary_result.unwrap_err());
+            let return_value = GetTagSummaryResponse {
+                result: "problems while creating tag summary".to_string(),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let return_value = GetTagSummaryResponse {
+            result: "tag summary created".to_string(),
+            report: Some(tag_summary_result.unwrap()),
+        };
+
+        return (StatusCode::OK, headers, return_value);
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct GetTrialBalanceResponse {
+    pub result: String,
+    pub report: Option<FinanceTrialBalance>,
+}
+
+impl IntoResponse for GetTrialBalanceResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_get_trial_balance(
+    session_data: SessionDataResult,
+    Form(input): Form<TrialBalanceRequest>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = GetTrialBalanceResponse {
+            result: "not logged in".to_string(),
+            report: None,
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = GetTrialBalanceResponse {
+            result: "session expired".to_string(),
+            report: None,
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    } else {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let username: String = session.get("user_name").unwrap();
+
+        let time_from_result = get_optional_report_time(&input.date_from, false);
+        if time_from_result.is_err() {
+            let return_value = GetTrialBalanceResponse {
+                result: format!("error parsing date_from: {}", time_from_result.unwrap_err()),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let time_till_result = get_optional_report_time(&input.date_till, true);
+        if time_till_result.is_err() {
+            let return_value = GetTrialBalanceResponse {
+                result: format!("error parsing date_till: {}", time_till_result.unwrap_err()),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        let trial_balance_result =
+            report_handle.trial_balance_sync(time_from_result.unwrap(), time_till_result.unwrap());
+        if trial_balance_result.is_err() {
+            warn!(target: "app::FinanceOverView","error in do_get_trial_balance for user {}: {}",username,trial_balance_result.unwrap_err());
+            let return_value = GetTrialBalanceResponse {
+                result: "problems while creating trial balance".to_string(),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let return_value = GetTrialBalanceResponse {
+            result: "trial balance created".to_string(),
+            report: Some(trial_balance_result.unwrap()),
+        };
+
+        return (StatusCode::OK, headers, return_value);
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct CreateRecurringBookingFormInput {
+    pub credit_account_id: String,
+    pub debit_account_id: String,
+    pub amount: u64,
+    pub title: String,
+    pub description: String,
+    pub schedule: i32,
+    pub start_date: String,
+    pub start_time: Option<String>,
+    pub end_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeleteRecurringBookingFormInput {
+    pub recurring_booking_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct RecurringBookingResponse {
+    pub result: String,
+}
+
+impl IntoResponse for RecurringBookingResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_create_recurring_booking(
+    session_data: SessionDataResult,
+    Form(input): Form<CreateRecurringBookingFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = RecurringBookingResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = RecurringBookingResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let create_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let debit_account_id_parse = Uuid::parse_str(&input.debit_account_id);
+            if debit_account_id_parse.is_err() {
+                let return_value = RecurringBookingResponse {
+                    result: format!(
+                        "error parsing debit_account_id: {}",
+                        debit_account_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let credit_account_id_parse = Uuid::parse_str(&input.credit_account_id);
+            if credit_account_id_parse.is_err() {
+                let return_value = RecurringBookingResponse {
+                    result: format!(
+                        "error parsing credit_account_id: {}",
+                        credit_account_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let schedule_parse = RecurringBookingSchedule::get_from_int(input.schedule);
+            if schedule_parse.is_err() {
+                let return_value = RecurringBookingResponse {
+                    result: format!("error parsing schedule: {}", schedule_parse.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            if input.start_date.trim().is_empty() {
+                let return_value = RecurringBookingResponse {
+                    result: "a start date is required".to_string(),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let start_time_parse =
+                get_booking_time(&Some(input.start_date.clone()), &input.start_time);
+            if start_time_parse.is_err() {
+                let return_value = RecurringBookingResponse {
+                    result: format!(
+                        "error parsing start date: {}",
+                        start_time_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let end_time_parse = get_optional_report_time(&input.end_date, true);
+            if end_time_parse.is_err() {
+                let return_value = RecurringBookingResponse {
+                    result: format!("error parsing end date: {}", end_time_parse.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let start_time = start_time_parse.unwrap();
+
+            // same account mapping as a single booking entry created on the main accounting page
+            let recurring_booking = FinanceRecurringBooking {
+                id: Uuid::new(),
+                booking_template: FinanceBookingRequest {
+                    is_simple_entry: true,
+                    is_saldo: false,
+                    debit_finance_account_id: credit_account_id_parse.unwrap(),
+                    credit_finance_account_id: debit_account_id_parse.unwrap(),
+                    booking_time: start_time,
+                    amount: input.amount,
+                    title: input.title,
+                    description: input.description,
+                    tags: Vec::new(),
+                    import_reference: None,
+                },
+                schedule: schedule_parse.unwrap(),
+                start_time: start_time,
+                end_time: end_time_parse.unwrap(),
+                last_booking_time: None,
+            };
+
+            let recurring_booking_handle =
+                FinanceRecurringBookingHandle::new(&db_connection, &user_id, &db_handler);
+            let upsert_result =
+                recurring_booking_handle.recurring_booking_upsert_sync(&recurring_booking);
+            if upsert_result.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                create_result = upsert_result.unwrap_err();
+            } else {
+                let booking_result = recurring_booking_handle.book_due_occurrences_sync(Utc::now());
+                if booking_result.is_err() {
+                    create_result = format!(
+                        "OK, recurring booking created, but due bookings could not be created: {}",
+                        booking_result.unwrap_err()
+                    );
+                } else {
+                    let run_result = booking_result.unwrap();
+                    if run_result.errors.is_empty() {
+                        create_result = format!(
+                            "OK, recurring booking created, {} due bookings created",
+                            run_result.booking_results.len()
+                        );
+                    } else {
+                        create_result = format!(
+                            "OK, recurring booking created, {} due bookings created, problems: {}",
+                            run_result.booking_results.len(),
+                            run_result.errors.join("; ")
+                        );
+                    }
+                }
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = RecurringBookingResponse {
+            result: create_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+pub async fn do_delete_recurring_booking(
+    session_data: SessionDataResult,
+    Form(input): Form<DeleteRecurringBookingFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = RecurringBookingResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = RecurringBookingResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let delete_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let recurring_booking_id_parse = Uuid::parse_str(&input.recurring_booking_id);
+            if recurring_booking_id_parse.is_err() {
+                let return_value = RecurringBookingResponse {
+                    result: format!(
+                        "error parsing recurring_booking_id: {}",
+                        recurring_booking_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let recurring_booking_handle =
+                FinanceRecurringBookingHandle::new(&db_connection, &user_id, &db_handler);
+            let delete_response = recurring_booking_handle
+                .recurring_booking_delete_sync(&recurring_booking_id_parse.unwrap());
+            if delete_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                delete_result = delete_response.unwrap_err();
+            } else {
+                delete_result = "OK, recurring booking deleted".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = RecurringBookingResponse {
+            result: delete_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UpsertBudgetFormInput {
+    pub account_id: String,
+    pub period: i32,
+    pub amount: u64,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeleteBudgetFormInput {
+    pub budget_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct BudgetResponse {
+    pub result: String,
+}
+
+impl IntoResponse for BudgetResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_upsert_budget(
+    session_data: SessionDataResult,
+    Form(input): Form<UpsertBudgetFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BudgetResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BudgetResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let upsert_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let account_id_parse = Uuid::parse_str(&input.account_id);
+            if account_id_parse.is_err() {
+                let return_value = BudgetResponse {
+                    result: format!(
+                        "error parsing account_id: {}",
+                        account_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let period_parse = BudgetPeriod::get_from_int(input.period);
+            if period_parse.is_err() {
+                let return_value = BudgetResponse {
+                    result: format!("error parsing period: {}", period_parse.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            let finance_account_id = account_id_parse.unwrap();
+            let period = period_parse.unwrap();
+
+            let budget_handle = FinanceBudgetHandle::new(&db_connection, &user_id, &db_handler);
+            let budget_list_result = budget_handle.budget_list_sync();
+            if budget_list_result.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                upsert_result = budget_list_result.unwrap_err();
+            } else {
+                // setting a budget replaces the existing budget of the account and period
+                let budget_id = budget_list_result
+                    .unwrap()
+                    .iter()
+                    .find(|elem| {
+                        elem.finance_account_id.eq(&finance_account_id) && elem.period.eq(&period)
+                    })
+                    .map(|elem| elem.id)
+                    .unwrap_or(Uuid::new());
+                let budget = FinanceBudget {
+                    id: budget_id,
+                    finance_account_id,
+                    period,
+                    amount: input.amount,
+                };
+                let upsert_response = budget_handle.budget_upsert_sync(&budget);
+                if upsert_response.is_err() {
+                    return_status_code = StatusCode::BAD_REQUEST;
+                    upsert_result = upsert_response.unwrap_err();
+                } else {
+                    upsert_result = "OK, budget saved".to_string();
+                }
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = BudgetResponse {
+            result: upsert_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+pub async fn do_delete_budget(
+    session_data: SessionDataResult,
+    Form(input): Form<DeleteBudgetFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BudgetResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BudgetResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let delete_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let budget_id_parse = Uuid::parse_str(&input.budget_id);
+            if budget_id_parse.is_err() {
+                let return_value = BudgetResponse {
+                    result: format!("error parsing budget_id: {}", budget_id_parse.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let budget_handle = FinanceBudgetHandle::new(&db_connection, &user_id, &db_handler);
+            let delete_response = budget_handle.budget_delete_sync(&budget_id_parse.unwrap());
+            if delete_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                delete_result = delete_response.unwrap_err();
+            } else {
+                delete_result = "OK, budget deleted".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = BudgetResponse {
+            result: delete_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct GetBudgetReportResponse {
+    pub result: String,
+    pub report: Option<FinanceBudgetReport>,
+}
+
+impl IntoResponse for GetBudgetReportResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+pub async fn do_get_budget_report(
+    session_data: SessionDataResult,
+    Form(input): Form<BudgetReportRequest>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = GetBudgetReportResponse {
+            result: "not logged in".to_string(),
+            report: None,
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = GetBudgetReportResponse {
+            result: "session expired".to_string(),
+            report: None,
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    } else {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let username: String = session.get("user_name").unwrap();
+
+        let report_time_result = get_report_time(&input.report_date);
+        if report_time_result.is_err() {
+            let return_value = GetBudgetReportResponse {
+                result: format!(
+                    "error parsing report_date: {}",
+                    report_time_result.unwrap_err()
+                ),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let budget_handle = FinanceBudgetHandle::new(&db_connection, &user_id, &db_handler);
+        let budget_report_result = budget_handle.budget_report_sync(report_time_result.unwrap());
+        if budget_report_result.is_err() {
+            warn!(target: "app::FinanceOverView","error in do_get_budget_report for user {}: {}",username,budget_report_result.unwrap_err());
+            let return_value = GetBudgetReportResponse {
+                result: "problems while creating budget report".to_string(),
+                report: None,
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let return_value = GetBudgetReportResponse {
+            result: "budget report created".to_string(),
+            report: Some(budget_report_result.unwrap()),
+        };
+
+        return (StatusCode::OK, headers, return_value);
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct JournalAttachmentResponse {
+    pub result: String,
+}
+
+impl IntoResponse for JournalAttachmentResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+/// multipart upload with the fields journal_entry_id and file
+pub async fn do_upload_journal_attachment(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = JournalAttachmentResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = JournalAttachmentResponse {
+            result: "session expired".to_string(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let mut journal_entry_id_input: Option<String> = None;
+    let mut file_input: Option<(String, String, Vec<u8>)> = None;
+    loop {
+        let field_result = multipart.next_field().await;
+        if field_result.is_err() {
+            let return_value = JournalAttachmentResponse {
+                result: format!("error reading upload: {}", field_result.unwrap_err()),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let field_option = field_result.unwrap();
+        if field_option.is_none() {
+            break;
+        }
+        let field = field_option.unwrap();
+        let field_name = field.name().unwrap_or_default().to_string();
+        if field_name.eq("journal_entry_id") {
+            journal_entry_id_input = Some(field.text().await.unwrap_or_default());
+        } else if field_name.eq("file") {
+            let file_name = field.file_name().unwrap_or_default().to_string();
+            let content_type = field.content_type().unwrap_or_default().to_string();
+            let content_result = field.bytes().await;
+            if content_result.is_err() {
+                let return_value = JournalAttachmentResponse {
+                    result: format!("error reading file: {}", content_result.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+            file_input = Some((file_name, content_type, content_result.unwrap().to_vec()));
+        }
+    }
+    if journal_entry_id_input.is_none() || file_input.is_none() {
+        let return_value = JournalAttachmentResponse {
+            result: "journal_entry_id and file are required".to_string(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let journal_entry_id_parse = Uuid::parse_str(journal_entry_id_input.unwrap());
+    if journal_entry_id_parse.is_err() {
+        let return_value = JournalAttachmentResponse {
+            result: format!(
+                "error parsing journal_entry_id: {}",
+                journal_entry_id_parse.unwrap_err()
+            ),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (file_name, content_type, content) = file_input.unwrap();
+
+    let upload_result: String;
+    let mut return_status_code = StatusCode::OK;
+    {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let attachment_storage_setting = AttachmentStorageSetting::from_setting(&local_settings);
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let attachment_handle = FinanceAttachmentHandle::new(
+            &db_connection,
+            &user_id,
+            &db_handler,
+            &attachment_storage_setting,
+        );
+        let upload_response = attachment_handle.attachment_upload_sync(
+            &journal_entry_id_parse.unwrap(),
+            &file_name,
+            &content_type,
+            &content,
+        );
+        if upload_response.is_err() {
+            return_status_code = StatusCode::BAD_REQUEST;
+            upload_result = upload_response.unwrap_err();
+        } else {
+            upload_result = format!("OK, {} attached", upload_response.unwrap().file_name);
+        }
+    }
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+    let return_value = JournalAttachmentResponse {
+        result: upload_result,
+    };
+
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    (return_status_code, headers, return_value)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DownloadJournalAttachmentInput {
+    pub attachment_id: String,
+}
+
+/// returns the file of an attachment, only attachments of journal entries of the session user are found
+pub async fn do_download_journal_attachment(
+    session_data: SessionDataResult,
+    Form(input): Form<DownloadJournalAttachmentInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in || session.is_expired() {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("not logged in or session expired"))
+            .unwrap();
+    }
+
+    let attachment_id_parse = Uuid::parse_str(&input.attachment_id);
+    if attachment_id_parse.is_err() {
+        return Response::builder()
+            .status(StatusCode::BAD_REQUEST)
+            .body(Body::from(format!(
+                "error parsing attachment_id: {}",
+                attachment_id_parse.unwrap_err()
+            )))
+            .unwrap();
+    }
+
+    let download_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let attachment_storage_setting = AttachmentStorageSetting::from_setting(&local_settings);
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let attachment_handle = FinanceAttachmentHandle::new(
+            &db_connection,
+            &user_id,
+            &db_handler,
+            &attachment_storage_setting,
+        );
+        attachment_handle.attachment_download_sync(&attachment_id_parse.unwrap())
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if download_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_download_journal_attachment: {}",download_result.unwrap_err());
+        return Response::builder()
+            .status(StatusCode::NOT_FOUND)
+            .body(Body::from("attachment not found"))
+            .unwrap();
+    }
+    let (attachment, content) = download_result.unwrap();
+
+    return Response::builder()
+        .status(StatusCode::OK)
+        .header(
+            header::CONTENT_TYPE,
+            HeaderValue::from_str(&attachment.mime_type).unwrap(),
+        )
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!(
+                "attachment; filename=\"{}\"",
+                attachment.file_name
+            ))
+            .unwrap_or(HeaderValue::from_static("attachment")),
+        )
+        .body(Body::from(content))
+        .unwrap();
+}
+
+/// booking entry proposed for a row of a bank statement, times and ids are sent as strings
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct BankImportRowData {
+    pub row_number: usize,
+    pub booking_time: String,
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    pub amount: u64,
+    pub title: String,
+    pub description: String,
+    pub error: String,
+    /// empty when no journal entry of the same day and amount was found
+    pub duplicate_journal_entry_id: String,
+    /// empty when the file format has no transaction id
+    pub import_reference: String,
+    /// tags added by a categorization rule
+    pub tags: Vec<String>,
+}
+
+impl BankImportRowData {
+    fn from_preview_row(preview_row: &BankImportPreviewRow) -> Self {
+        match &preview_row.booking_request {
+            Some(booking_request) => Self {
+                row_number: preview_row.row_number,
+                booking_time: booking_request.booking_time.to_rfc3339(),
+                debit_account_id: booking_request.debit_finance_account_id.to_string(),
+                credit_account_id: booking_request.credit_finance_account_id.to_string(),
+                amount: booking_request.amount,
+                title: booking_request.title.clone(),
+                description: booking_request.description.clone(),
+                error: preview_row.error.clone(),
+                duplicate_journal_entry_id: preview_row
+                    .duplicate_journal_entry_id
+                    .map(|x| x.to_string())
+                    .unwrap_or_default(),
+                import_reference: booking_request.import_reference.clone().unwrap_or_default(),
+                tags: booking_request.tags.clone(),
+            },
+            None => Self {
+                row_number: preview_row.row_number,
+                booking_time: "".into(),
+                debit_account_id: "".into(),
+                credit_account_id: "".into(),
+                amount: 0,
+                title: "".into(),
+                description: "".into(),
+                error: preview_row.error.clone(),
+                duplicate_journal_entry_id: preview_row
+                    .duplicate_journal_entry_id
+                    .map(|x| x.to_string())
+                    .unwrap_or_default(),
+                import_reference: "".into(),
+                tags: Vec::new(),
+            },
+        }
+    }
+
+    fn to_preview_row(&self) -> BankImportPreviewRow {
+        let booking_time_parse = DateTime::parse_from_rfc3339(&self.booking_time);
+        if booking_time_parse.is_err() {
+            return BankImportPreviewRow {
+                row_number: self.row_number,
+                booking_request: None,
+                error: format!(
+                    "error parsing booking_time: {}",
+                    booking_time_parse.unwrap_err()
+                ),
+                duplicate_journal_entry_id: None,
+            };
+        }
+        let debit_account_id_parse = Uuid::parse_str(&self.debit_account_id);
+        if debit_account_id_parse.is_err() {
+            return BankImportPreviewRow {
+                row_number: self.row_number,
+                booking_request: None,
+                error: format!(
+                    "error parsing debit_account_id: {}",
+                    debit_account_id_parse.unwrap_err()
+                ),
+                duplicate_journal_entry_id: None,
+            };
+        }
+        let credit_account_id_parse = Uuid::parse_str(&self.credit_account_id);
+        if credit_account_id_parse.is_err() {
+            return BankImportPreviewRow {
+                row_number: self.row_number,
+                booking_request: None,
+                error: format!(
+                    "error parsing credit_account_id: {}",
+                    credit_account_id_parse.unwrap_err()
+                ),
+                duplicate_journal_entry_id: None,
+            };
+        }
+        return BankImportPreviewRow {
+            row_number: self.row_number,
+            booking_request: Some(FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: debit_account_id_parse.unwrap(),
+                credit_finance_account_id: credit_account_id_parse.unwrap(),
+                booking_time: booking_time_parse.unwrap().with_timezone(&Utc),
+                amount: self.amount,
+                title: self.title.clone(),
+                description: self.description.clone(),
+                tags: self.tags.clone(),
+                import_reference: if self.import_reference.is_empty() {
+                    None
+                } else {
+                    Some(self.import_reference.clone())
+                },
+            }),
+            error: "".into(),
+            duplicate_journal_entry_id: None,
+        };
+    }
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct BankImportPreviewResponse {
+    pub result: String,
+    pub rows: Vec<BankImportRowData>,
+}
+
+impl IntoResponse for BankImportPreviewResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+fn get_import_form_value<'a>(
+    form_fields: &'a HashMap<String, String>,
+    field_name: &str,
+) -> Result<&'a String, String> {
+    let value_option = form_fields.get(field_name);
+    if value_option.is_none() {
+        return Err(format!("{} is missing", field_name));
+    }
+    return Ok(value_option.unwrap());
+}
+
+fn get_import_form_char(
+    form_fields: &HashMap<String, String>,
+    field_name: &str,
+) -> Result<char, String> {
+    let value = get_import_form_value(form_fields, field_name)?;
+    // tab can not be entered into a text field
+    if value.eq("\\t") {
+        return Ok('\t');
+    }
+    let mut value_chars = value.chars();
+    let first_char = value_chars.next();
+    if first_char.is_none() || value_chars.next().is_some() {
+        return Err(format!("{} must be a single character", field_name));
+    }
+    return Ok(first_char.unwrap());
+}
+
+fn get_import_form_number(
+    form_fields: &HashMap<String, String>,
+    field_name: &str,
+) -> Result<usize, String> {
+    let value = get_import_form_value(form_fields, field_name)?;
+    if value.trim().is_empty() {
+        return Ok(0);
+    }
+    let value_parse = value.trim().parse::<usize>();
+    if value_parse.is_err() {
+        return Err(format!(
+            "error parsing {}: {}",
+            field_name,
+            value_parse.unwrap_err()
+        ));
+    }
+    return Ok(value_parse.unwrap());
+}
+
+fn get_import_form_account_id(
+    form_fields: &HashMap<String, String>,
+    field_name: &str,
+) -> Result<Uuid, String> {
+    let value = get_import_form_value(form_fields, field_name)?;
+    let value_parse = Uuid::parse_str(value);
+    if value_parse.is_err() {
+        return Err(format!(
+            "error parsing {}: {}",
+            field_name,
+            value_parse.unwrap_err()
+        ));
+    }
+    return Ok(value_parse.unwrap());
+}
+
+fn get_csv_import_profile(
+    form_fields: &HashMap<String, String>,
+) -> Result<CsvImportProfile, String> {
+    let sign_convention_number = get_import_form_number(form_fields, "sign_convention")?;
+    let sign_convention_result = AmountSignConvention::get_from_int(sign_convention_number as i32);
+    if sign_convention_result.is_err() {
+        return Err(sign_convention_result.unwrap_err());
+    }
+    return Ok(CsvImportProfile {
+        delimiter: get_import_form_char(form_fields, "delimiter")?,
+        skip_rows: get_import_form_number(form_fields, "skip_rows")?,
+        date_column: get_import_form_number(form_fields, "date_column")?,
+        date_format: get_import_form_value(form_fields, "date_format")?.clone(),
+        amount_column: get_import_form_number(form_fields, "amount_column")?,
+        sign_convention: sign_convention_result.unwrap(),
+        decimal_separator: get_import_form_char(form_fields, "decimal_separator")?,
+        encoding: get_import_form_value(form_fields, "encoding")?.clone(),
+        title_column: get_import_form_number(form_fields, "title_column")?,
+        description_column: get_import_form_number(form_fields, "description_column")?,
+        bank_account_id: get_import_form_account_id(form_fields, "bank_account_id")?,
+        default_incoming_account_id: get_import_form_account_id(
+            form_fields,
+            "incoming_account_id",
+        )?,
+        default_outgoing_account_id: get_import_form_account_id(
+            form_fields,
+            "outgoing_account_id",
+        )?,
+    });
+}
+
+/// reads the form fields and the statement (field file) of a bank import upload
+async fn read_bank_import_upload(
+    multipart: &mut Multipart,
+) -> Result<(HashMap<String, String>, Vec<u8>), String> {
+    let mut form_fields: HashMap<String, String> = HashMap::new();
+    let mut file_content: Option<Vec<u8>> = None;
+    loop {
+        let field_result = multipart.next_field().await;
+        if field_result.is_err() {
+            return Err(format!(
+                "error reading upload: {}",
+                field_result.unwrap_err()
+            ));
+        }
+        let field_option = field_result.unwrap();
+        if field_option.is_none() {
+            break;
+        }
+        let field = field_option.unwrap();
+        let field_name = field.name().unwrap_or_default().to_string();
+        if field_name.eq("file") {
+            let content_result = field.bytes().await;
+            if content_result.is_err() {
+                return Err(format!(
+                    "error reading file: {}",
+                    content_result.unwrap_err()
+                ));
+            }
+            file_content = Some(content_result.unwrap().to_vec());
+        } else {
+            form_fields.insert(field_name, field.text().await.unwrap_or_default());
+        }
+    }
+    if file_content.is_none() {
+        return Err("file is required".into());
+    }
+    return Ok((form_fields, file_content.unwrap()));
+}
+
+/// multipart upload with the column mapping profile fields and the statement as field file,
+/// nothing is booked yet
+pub async fn do_preview_bank_import(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BankImportPreviewResponse {
+            result: "not logged in".to_string(),
+            rows: Vec::new(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BankImportPreviewResponse {
+            result: "session expired".to_string(),
+            rows: Vec::new(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let upload_result = read_bank_import_upload(&mut multipart).await;
+    if upload_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: upload_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (form_fields, file_content) = upload_result.unwrap();
+    let profile_result = get_csv_import_profile(&form_fields);
+    if profile_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: profile_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let profile = profile_result.unwrap();
+
+    let preview_result = FinanceBankImportHandle::preview_csv_import(&file_content, &profile);
+    if preview_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: preview_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let mut preview_rows = preview_result.unwrap();
+
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+    let summary =
+        check_bank_import_preview(&user_id, &profile.bank_account_id, None, &mut preview_rows);
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    let return_value = BankImportPreviewResponse {
+        result: summary,
+        rows: preview_rows
+            .iter()
+            .map(BankImportRowData::from_preview_row)
+            .collect(),
+    };
+
+    (StatusCode::OK, headers, return_value)
+}
+
+/// bank account and counter accounts of a statement file with a fixed format
+fn get_statement_import_accounts(
+    form_fields: &HashMap<String, String>,
+) -> Result<(Uuid, Uuid, Uuid), String> {
+    return Ok((
+        get_import_form_account_id(form_fields, "bank_account_id")?,
+        get_import_form_account_id(form_fields, "incoming_account_id")?,
+        get_import_form_account_id(form_fields, "outgoing_account_id")?,
+    ));
+}
+
+fn get_balance_check_message(balance_check: &BankStatementBalanceCheck) -> String {
+    if balance_check.is_opening_balance_matching() {
+        return format!(
+            "opening balance {:.2} matches the bank account, closing balance after booking all rows: {:.2}",
+            (balance_check.statement_opening_balance as f64) / (100 as f64),
+            (balance_check.statement_closing_balance as f64) / (100 as f64)
+        );
+    }
+    if balance_check.is_closing_balance_matching() {
+        return format!(
+            "the bank account already has the closing balance {:.2}, the statement was probably booked before",
+            (balance_check.statement_closing_balance as f64) / (100 as f64)
+        );
+    }
+    return format!(
+        "opening balance {:.2} does not match the balance of the bank account {:.2}",
+        (balance_check.statement_opening_balance as f64) / (100 as f64),
+        (balance_check.book_balance as f64) / (100 as f64)
+    );
+}
+
+/// multipart upload with the fields encoding, bank_account_id, incoming_account_id,
+/// outgoing_account_id and the MT940 file as field file, nothing is booked yet
+pub async fn do_preview_mt940_import(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BankImportPreviewResponse {
+            result: "not logged in".to_string(),
+            rows: Vec::new(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BankImportPreviewResponse {
+            result: "session expired".to_string(),
+            rows: Vec::new(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let upload_result = read_bank_import_upload(&mut multipart).await;
+    if upload_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: upload_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (form_fields, file_content) = upload_result.unwrap();
+    let encoding_result = get_import_form_value(&form_fields, "encoding");
+    if encoding_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: encoding_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let encoding = encoding_result.unwrap().clone();
+    let accounts_result = get_statement_import_accounts(&form_fields);
+    if accounts_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: accounts_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (bank_account_id, incoming_account_id, outgoing_account_id) = accounts_result.unwrap();
+
+    let preview_result = FinanceBankImportHandle::preview_mt940_import(
+        &file_content,
+        &encoding,
+        &bank_account_id,
+        &incoming_account_id,
+        &outgoing_account_id,
+    );
+    if preview_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: preview_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (statements, mut preview_rows) = preview_result.unwrap();
+
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+    let summary = check_bank_import_preview(
+        &user_id,
+        &bank_account_id,
+        Some(&statements),
+        &mut preview_rows,
+    );
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    let return_value = BankImportPreviewResponse {
+        result: summary,
+        rows: preview_rows
+            .iter()
+            .map(BankImportRowData::from_preview_row)
+            .collect(),
+    };
+
+    (StatusCode::OK, headers, return_value)
+}
+
+/// applies the categorization rules, marks rows that are probably booked already and compares
+/// the balances of the statements (if the format has them) with the bank account,
+/// returns the summary for the preview
+fn check_bank_import_preview(
+    user_id: &Uuid,
+    bank_account_id: &Uuid,
+    statements: Option<&Vec<BankStatement>>,
+    preview_rows: &mut Vec<BankImportPreviewRow>,
+) -> String {
+    let local_settings: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(local_settings.backend_database_url),
+        user: String::from(local_settings.backend_database_user),
+        password: String::from(local_settings.backend_database_password),
+        instance: String::from(local_settings.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+    let bank_import_handle = FinanceBankImportHandle::new(&db_connection, user_id, &db_handler);
+    let rule_handle = FinanceCategorizationRuleHandle::new(&db_connection, user_id, &db_handler);
+
+    let categorize_result = rule_handle.categorize_preview_rows_sync(bank_account_id, preview_rows);
+    let duplicate_result =
+        bank_import_handle.mark_duplicate_rows_sync(bank_account_id, preview_rows);
+    let error_count = preview_rows
+        .iter()
+        .filter(|elem| elem.booking_request.is_none())
+        .count();
+    let duplicate_count = preview_rows
+        .iter()
+        .filter(|elem| elem.duplicate_journal_entry_id.is_some())
+        .count();
+    let mut summary = match duplicate_result {
+        Ok(_) => format!(
+            "OK, {} rows read, {} with errors, {} probably booked before",
+            preview_rows.len(),
+            error_count,
+            duplicate_count
+        ),
+        Err(error_message) => format!(
+            "OK, {} rows read, {} with errors, duplicates could not be checked: {}",
+            preview_rows.len(),
+            error_count,
+            error_message
+        ),
+    };
+    summary = match categorize_result {
+        Ok(categorized_count) => format!("{}, {} categorized by rules", summary, categorized_count),
+        Err(error_message) => format!("{}, rules could not be applied: {}", summary, error_message),
+    };
+
+    if statements.is_some() {
+        let balance_check_result =
+            bank_import_handle.check_statement_balances_sync(bank_account_id, statements.unwrap());
+        let balance_check_message = match balance_check_result {
+            Ok(balance_check) => get_balance_check_message(&balance_check),
+            Err(error_message) => format!("balance could not be checked: {}", error_message),
+        };
+        summary = format!(
+            "{}; {} statements, {}",
+            summary,
+            statements.unwrap().len(),
+            balance_check_message
+        );
+    }
+    return summary;
+}
+
+/// multipart upload with the fields bank_account_id, incoming_account_id, outgoing_account_id
+/// and the CAMT.053 file as field file, nothing is booked yet
+pub async fn do_preview_camt053_import(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BankImportPreviewResponse {
+            result: "not logged in".to_string(),
+            rows: Vec::new(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BankImportPreviewResponse {
+            result: "session expired".to_string(),
+            rows: Vec::new(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let upload_result = read_bank_import_upload(&mut multipart).await;
+    if upload_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: upload_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (form_fields, file_content) = upload_result.unwrap();
+    let accounts_result = get_statement_import_accounts(&form_fields);
+    if accounts_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: accounts_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (bank_account_id, incoming_account_id, outgoing_account_id) = accounts_result.unwrap();
+
+    let preview_result = FinanceBankImportHandle::preview_camt053_import(
+        &file_content,
+        &bank_account_id,
+        &incoming_account_id,
+        &outgoing_account_id,
+    );
+    if preview_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: preview_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (statements, mut preview_rows) = preview_result.unwrap();
+
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+    let summary = check_bank_import_preview(
+        &user_id,
+        &bank_account_id,
+        Some(&statements),
+        &mut preview_rows,
+    );
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    let return_value = BankImportPreviewResponse {
+        result: summary,
+        rows: preview_rows
+            .iter()
+            .map(BankImportRowData::from_preview_row)
+            .collect(),
+    };
+
+    (StatusCode::OK, headers, return_value)
+}
+
+/// multipart upload with the fields bank_account_id, incoming_account_id, outgoing_account_id
+/// and the OFX or QFX file as field file, nothing is booked yet
+pub async fn do_preview_ofx_import(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BankImportPreviewResponse {
+            result: "not logged in".to_string(),
+            rows: Vec::new(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BankImportPreviewResponse {
+            result: "session expired".to_string(),
+            rows: Vec::new(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let upload_result = read_bank_import_upload(&mut multipart).await;
+    if upload_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: upload_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (form_fields, file_content) = upload_result.unwrap();
+    let accounts_result = get_statement_import_accounts(&form_fields);
+    if accounts_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: accounts_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (bank_account_id, incoming_account_id, outgoing_account_id) = accounts_result.unwrap();
+
+    let preview_result = FinanceBankImportHandle::preview_ofx_import(
+        &file_content,
+        &bank_account_id,
+        &incoming_account_id,
+        &outgoing_account_id,
+    );
+    if preview_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: preview_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let mut preview_rows = preview_result.unwrap();
+
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+    let summary = check_bank_import_preview(&user_id, &bank_account_id, None, &mut preview_rows);
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    let return_value = BankImportPreviewResponse {
+        result: summary,
+        rows: preview_rows
+            .iter()
+            .map(BankImportRowData::from_preview_row)
+            .collect(),
+    };
+
+    (StatusCode::OK, headers, return_value)
+}
+
+/// encoding, date format and decimal separator of a QIF upload
+fn get_qif_import_settings(
+    form_fields: &HashMap<String, String>,
+) -> Result<(String, String, char), String> {
+    return Ok((
+        get_import_form_value(form_fields, "encoding")?.clone(),
+        get_import_form_value(form_fields, "date_format")?.clone(),
+        get_import_form_char(form_fields, "decimal_separator")?,
+    ));
+}
+
+/// multipart upload with the fields encoding, date_format, decimal_separator, bank_account_id,
+/// incoming_account_id, outgoing_account_id and the QIF file as field file, nothing is booked yet
+pub async fn do_preview_qif_import(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BankImportPreviewResponse {
+            result: "not logged in".to_string(),
+            rows: Vec::new(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BankImportPreviewResponse {
+            result: "session expired".to_string(),
+            rows: Vec::new(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let upload_result = read_bank_import_upload(&mut multipart).await;
+    if upload_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: upload_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (form_fields, file_content) = upload_result.unwrap();
+    let settings_result = get_qif_import_settings(&form_fields);
+    if settings_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: settings_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (encoding, date_format, decimal_separator) = settings_result.unwrap();
+    let accounts_result = get_statement_import_accounts(&form_fields);
+    if accounts_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: accounts_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (bank_account_id, incoming_account_id, outgoing_account_id) = accounts_result.unwrap();
+
+    let preview_result = FinanceBankImportHandle::preview_qif_import(
+        &file_content,
+        &encoding,
+        &date_format,
+        decimal_separator,
+        &bank_account_id,
+        &incoming_account_id,
+        &outgoing_account_id,
+    );
+    if preview_result.is_err() {
+        let return_value = BankImportPreviewResponse {
+            result: preview_result.unwrap_err(),
+            rows: Vec::new(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let mut preview_rows = preview_result.unwrap();
+
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+    let summary = check_bank_import_preview(&user_id, &bank_account_id, None, &mut preview_rows);
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    let return_value = BankImportPreviewResponse {
+        result: summary,
+        rows: preview_rows
+            .iter()
+            .map(BankImportRowData::from_preview_row)
+            .collect(),
+    };
+
+    (StatusCode::OK, headers, return_value)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct PostBankImportInput {
+    /// account of the statement, the rows are checked again for entries booked before
+    pub bank_account_id: String,
+    pub rows: Vec<BankImportRowData>,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct BankImportRowResultData {
+    pub row_number: usize,
+    pub journal_entry_id: String,
+    pub error: String,
+}
+
+#[derive(Serialize, Debug)]
+pub(crate) struct PostBankImportResponse {
+    pub result: String,
+    pub rows: Vec<BankImportRowResultData>,
+}
+
+impl IntoResponse for PostBankImportResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+/// books the confirmed rows of a preview, every row is booked on its own
+pub async fn do_post_bank_import(
+    session_data: SessionDataResult,
+    Json(input): Json<PostBankImportInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = PostBankImportResponse {
+            result: "not logged in".to_string(),
+            rows: Vec::new(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = PostBankImportResponse {
+            result: "session expired".to_string(),
+            rows: Vec::new(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let bank_account_id_result = Uuid::parse_str(&input.bank_account_id);
+    if bank_account_id_result.is_err() {
+        let return_value = PostBankImportResponse {
+            result: format!(
+                "error parsing bank_account_id: {}",
+                bank_account_id_result.unwrap_err()
+            ),
+            rows: Vec::new(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let bank_account_id = bank_account_id_result.unwrap();
+
+    let rows_to_post: Vec<BankImportPreviewRow> = input
+        .rows
+        .iter()
+        .map(BankImportRowData::to_preview_row)
+        .collect();
+
+    let row_results = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let bank_import_handle =
+            FinanceBankImportHandle::new(&db_connection, &user_id, &db_handler);
+        bank_import_handle.post_import_rows_sync(&bank_account_id, &rows_to_post)
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    let error_count = row_results
+        .iter()
+        .filter(|elem| elem.journal_entry_id.is_none())
+        .count();
+    let return_value = PostBankImportResponse {
+        result: format!(
+            "{} rows booked, {} rows failed",
+            row_results.len() - error_count,
+            error_count
+        ),
+        rows: row_results
+            .iter()
+            .map(|elem| BankImportRowResultData {
+                row_number: elem.row_number,
+                journal_entry_id: elem
+                    .journal_entry_id
+                    .map(|x| x.to_string())
+                    .unwrap_or_default(),
+                error: elem.error.clone(),
+            })
+            .collect(),
+    };
+
+    (StatusCode::OK, headers, return_value)
+}
+
+/// a rule as entered on the categorization rules page, amounts in cents and optional uuids as
+/// strings (empty when not set), the time range is only used to test the rule
+#[derive(Deserialize, Debug)]
+pub struct CategorizationRuleFormInput {
+    pub rule_id: Option<String>,
+    pub title: String,
+    pub priority: i32,
+    pub payee_pattern: String,
+    pub text_pattern: String,
+    pub amount_min: String,
+    pub amount_max: String,
+    pub source_account_id: String,
+    pub counter_account_id: String,
+    pub title_rewrite: String,
+    pub tags: Option<String>,
+    pub date_from: Option<String>,
+    pub date_till: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct DeleteCategorizationRuleFormInput {
+    pub rule_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct CategorizationRuleResponse {
+    pub result: String,
+}
+
+impl IntoResponse for CategorizationRuleResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+fn get_optional_rule_amount(amount_text: &str, field_name: &str) -> Result<Option<i64>, String> {
+    if amount_text.trim().is_empty() {
+        return Ok(None);
+    }
+    let amount_parse = amount_text.trim().parse::<i64>();
+    if amount_parse.is_err() {
+        return Err(format!(
+            "error parsing {}: {}",
+            field_name,
+            amount_parse.unwrap_err()
+        ));
+    }
+    return Ok(Some(amount_parse.unwrap()));
+}
+
+fn get_categorization_rule(
+    input: &CategorizationRuleFormInput,
+) -> Result<FinanceCategorizationRule, String> {
+    let mut rule_id = Uuid::new();
+    if input.rule_id.is_some() && !input.rule_id.as_ref().unwrap().trim().is_empty() {
+        let rule_id_parse = Uuid::parse_str(input.rule_id.as_ref().unwrap().trim());
+        if rule_id_parse.is_err() {
+            return Err(format!(
+                "error parsing rule_id: {}",
+                rule_id_parse.unwrap_err()
+            ));
+        }
+        rule_id = rule_id_parse.unwrap();
+    }
+    let mut source_account_id = None;
+    if !input.source_account_id.trim().is_empty() {
+        let source_account_id_parse = Uuid::parse_str(input.source_account_id.trim());
+        if source_account_id_parse.is_err() {
+            return Err(format!(
+                "error parsing source_account_id: {}",
+                source_account_id_parse.unwrap_err()
+            ));
+        }
+        source_account_id = Some(source_account_id_parse.unwrap());
+    }
+    let counter_account_id_parse = Uuid::parse_str(&input.counter_account_id);
+    if counter_account_id_parse.is_err() {
+        return Err(format!(
+            "error parsing counter_account_id: {}",
+            counter_account_id_parse.unwrap_err()
+        ));
+    }
+    return Ok(FinanceCategorizationRule {
+        id: rule_id,
+        title: input.title.clone(),
+        priority: input.priority,
+        payee_pattern: input.payee_pattern.clone(),
+        text_pattern: input.text_pattern.clone(),
+        amount_min: get_optional_rule_amount(&input.amount_min, "amount_min")?,
+        amount_max: get_optional_rule_amount(&input.amount_max, "amount_max")?,
+        source_account_id,
+        counter_account_id: counter_account_id_parse.unwrap(),
+        title_rewrite: input.title_rewrite.clone(),
+        tags: get_booking_tags(&input.tags),
+    });
+}
+
+pub async fn do_upsert_categorization_rule(
+    session_data: SessionDataResult,
+    Form(input): Form<CategorizationRuleFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = CategorizationRuleResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = CategorizationRuleResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let upsert_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let rule_result = get_categorization_rule(&input);
+            if rule_result.is_err() {
+                let return_value = CategorizationRuleResponse {
+                    result: rule_result.unwrap_err(),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let rule_handle =
+                FinanceCategorizationRuleHandle::new(&db_connection, &user_id, &db_handler);
+            let upsert_response = rule_handle.rule_upsert_sync(&rule_result.unwrap());
+            if upsert_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                upsert_result = upsert_response.unwrap_err();
+            } else {
+                upsert_result = "OK, categorization rule saved".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = CategorizationRuleResponse {
+            result: upsert_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+pub async fn do_delete_categorization_rule(
+    session_data: SessionDataResult,
+    Form(input): Form<DeleteCategorizationRuleFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = CategorizationRuleResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = CategorizationRuleResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let delete_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let rule_id_parse = Uuid::parse_str(&input.rule_id);
+            if rule_id_parse.is_err() {
+                let return_value = CategorizationRuleResponse {
+                    result: format!("error parsing rule_id: {}", rule_id_parse.unwrap_err()),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let rule_handle =
+                FinanceCategorizationRuleHandle::new(&db_connection, &user_id, &db_handler);
+            let delete_response = rule_handle.rule_delete_sync(&rule_id_parse.unwrap());
+            if delete_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                delete_result = delete_response.unwrap_err();
+            } else {
+                delete_result = "OK, categorization rule deleted".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = CategorizationRuleResponse {
+            result: delete_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct CategorizationRuleTestEntryData {
+    pub journal_entry_id: String,
+    pub running_number: u64,
+    pub booking_time: String,
+    pub debit_account_id: String,
+    pub credit_account_id: String,
+    pub amount: u64,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct TestCategorizationRuleResponse {
+    pub result: String,
+    pub entries: Vec<CategorizationRuleTestEntryData>,
+}
+
+impl IntoResponse for TestCategorizationRuleResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+/// lists the journal entries of the time range the entered rule (saved or not) would have matched
+pub async fn do_test_categorization_rule(
+    session_data: SessionDataResult,
+    Form(input): Form<CategorizationRuleFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = TestCategorizationRuleResponse {
+            result: "not logged in".to_string(),
+            entries: Vec::new(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = TestCategorizationRuleResponse {
+            result: "session expired".to_string(),
+            entries: Vec::new(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let rule_result = get_categorization_rule(&input);
+        if rule_result.is_err() {
+            let return_value = TestCategorizationRuleResponse {
+                result: rule_result.unwrap_err(),
+                entries: Vec::new(),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let time_from_result = get_optional_report_time(&input.date_from, false);
+        if time_from_result.is_err() {
+            let return_value = TestCategorizationRuleResponse {
+                result: format!("error parsing date_from: {}", time_from_result.unwrap_err()),
+                entries: Vec::new(),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let time_till_result = get_optional_report_time(&input.date_till, true);
+        if time_till_result.is_err() {
+            let return_value = TestCategorizationRuleResponse {
+                result: format!("error parsing date_till: {}", time_till_result.unwrap_err()),
+                entries: Vec::new(),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let test_result = {
+            let local_settings: SettingStruct = SettingStruct::global().clone();
+            let db_connection = DbConnectionSetting {
+                url: String::from(local_settings.backend_database_url),
+                user: String::from(local_settings.backend_database_user),
+                password: String::from(local_settings.backend_database_password),
+                instance: String::from(local_settings.backend_database_instance),
+            };
+            let db_handler = DbHandler::new(&db_connection);
+            let rule_handle =
+                FinanceCategorizationRuleHandle::new(&db_connection, &user_id, &db_handler);
+            rule_handle.test_rule_sync(
+                &rule_result.unwrap(),
+                time_from_result.unwrap(),
+                time_till_result.unwrap(),
+            )
+        };
+        if test_result.is_err() {
+            let return_value = TestCategorizationRuleResponse {
+                result: test_result.unwrap_err(),
+                entries: Vec::new(),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let matching_entries = test_result.unwrap();
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        let return_value = TestCategorizationRuleResponse {
+            result: format!(
+                "OK, rule matches {} journal entries",
+                matching_entries.len()
+            ),
+            entries: matching_entries
+                .iter()
+                .map(|elem| CategorizationRuleTestEntryData {
+                    journal_entry_id: elem.id.to_string(),
+                    running_number: elem.running_number,
+                    booking_time: elem.booking_time.to_rfc3339(),
+                    debit_account_id: elem.debit_finance_account_id.to_string(),
+                    credit_account_id: elem.credit_finance_account_id.to_string(),
+                    amount: elem.amount,
+                    title: elem.title.clone(),
+                    description: elem.description.clone(),
+                })
+                .collect(),
+        };
+
+        (StatusCode::OK, headers, return_value)
+    }
+}
+
+/// statement as entered on the reconciliation page, the balance in cents with the sign natural
+/// for the account category, the booking entry ids comma separated
+#[derive(Deserialize, Debug)]
+pub struct ReconciliationFormInput {
+    pub finance_account_id: String,
+    pub statement_date: Option<String>,
+    pub statement_balance: i64,
+    pub booking_entry_ids: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct UnreconcileFormInput {
+    pub reconciliation_id: String,
+}
+
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub(crate) struct ReconciliationResponse {
+    pub result: String,
+}
+
+impl IntoResponse for ReconciliationResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct ReconciliationEntryData {
+    pub booking_entry_id: String,
+    pub booking_time: String,
+    /// amount in cents, positive for debit entries and negative for credit entries
+    pub signed_amount: i64,
+    pub title: String,
+    pub description: String,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct ReconciliationStatusResponse {
+    pub result: String,
+    pub book_balance: i64,
+    pub statement_balance: i64,
+    pub difference: i64,
+    pub open_entries: Vec<ReconciliationEntryData>,
+}
+
+impl ReconciliationStatusResponse {
+    fn from_result(result: String) -> Self {
+        Self {
+            result,
+            book_balance: 0,
+            statement_balance: 0,
+            difference: 0,
+            open_entries: Vec::new(),
+        }
+    }
+}
+
+impl IntoResponse for ReconciliationStatusResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+fn get_reconciliation_statement(
+    input: &ReconciliationFormInput,
+) -> Result<(Uuid, DateTime<Utc>, Vec<Uuid>), String> {
+    let account_id_parse = Uuid::parse_str(&input.finance_account_id);
+    if account_id_parse.is_err() {
+        return Err(format!(
+            "error parsing finance_account_id: {}",
+            account_id_parse.unwrap_err()
+        ));
+    }
+    // the statement covers the whole statement day
+    let statement_time_result = get_report_time(&input.statement_date);
+    if statement_time_result.is_err() {
+        return Err(format!(
+            "error parsing statement_date: {}",
+            statement_time_result.unwrap_err()
+        ));
+    }
+    let mut booking_entry_ids = Vec::new();
+    if input.booking_entry_ids.is_some() {
+        for id_text in input.booking_entry_ids.as_ref().unwrap().split(',') {
+            if id_text.trim().is_empty() {
+                continue;
+            }
+            let booking_entry_id_parse = Uuid::parse_str(id_text.trim());
+            if booking_entry_id_parse.is_err() {
+                return Err(format!(
+                    "error parsing booking_entry_ids: {}",
+                    booking_entry_id_parse.unwrap_err()
+                ));
+            }
+            booking_entry_ids.push(booking_entry_id_parse.unwrap());
+        }
+    }
+    return Ok((
+        account_id_parse.unwrap(),
+        statement_time_result.unwrap(),
+        booking_entry_ids,
+    ));
+}
+
+/// compares the book balance of the account with the entered statement and lists the open entries
+pub async fn do_show_reconciliation_status(
+    session_data: SessionDataResult,
+    Form(input): Form<ReconciliationFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = ReconciliationStatusResponse::from_result("not logged in".to_string());
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = ReconciliationStatusResponse::from_result("session expired".to_string());
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let statement_result = get_reconciliation_statement(&input);
+        if statement_result.is_err() {
+            let return_value =
+                ReconciliationStatusResponse::from_result(statement_result.unwrap_err());
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let (finance_account_id, statement_time, _) = statement_result.unwrap();
+
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let status_result = {
+            let local_settings: SettingStruct = SettingStruct::global().clone();
+            let db_connection = DbConnectionSetting {
+                url: String::from(local_settings.backend_database_url),
+                user: String::from(local_settings.backend_database_user),
+                password: String::from(local_settings.backend_database_password),
+                instance: String::from(local_settings.backend_database_instance),
+            };
+            let db_handler = DbHandler::new(&db_connection);
+            let reconciliation_handle =
+                FinanceReconciliationHandle::new(&db_connection, &user_id, &db_handler);
+            reconciliation_handle.reconciliation_status_sync(
+                &finance_account_id,
+                statement_time,
+                input.statement_balance,
+            )
+        };
+        if status_result.is_err() {
+            let return_value =
+                ReconciliationStatusResponse::from_result(status_result.unwrap_err());
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let status = status_result.unwrap();
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        let return_value = ReconciliationStatusResponse {
+            result: format!(
+                "OK, {} open entries, difference {:.2}",
+                status.open_entries.len(),
+                (status.difference as f64) / (100 as f64)
+            ),
+            book_balance: status.book_balance,
+            statement_balance: status.statement_balance,
+            difference: status.difference,
+            open_entries: status
+                .open_entries
+                .iter()
+                .map(|elem| ReconciliationEntryData {
+                    booking_entry_id: elem.id.to_string(),
+                    booking_time: elem.booking_time.to_rfc3339(),
+                    signed_amount: if elem.booking_type.eq(&BookingEntryType::Debit) {
+                        elem.amount as i64
+                    } else {
+                        -(elem.amount as i64)
+                    },
+                    title: elem.title.clone(),
+                    description: elem.description.clone(),
+                })
+                .collect(),
+        };
+
+        (StatusCode::OK, headers, return_value)
+    }
+}
+
+pub async fn do_reconcile_account(
+    session_data: SessionDataResult,
+    Form(input): Form<ReconciliationFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = ReconciliationResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = ReconciliationResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let statement_result = get_reconciliation_statement(&input);
+        if statement_result.is_err() {
+            let return_value = ReconciliationResponse {
+                result: statement_result.unwrap_err(),
+            };
+            return (StatusCode::BAD_REQUEST, headers, return_value);
+        }
+        let (finance_account_id, statement_time, booking_entry_ids) = statement_result.unwrap();
+
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+        let reconcile_result = {
+            let local_settings: SettingStruct = SettingStruct::global().clone();
+            let db_connection = DbConnectionSetting {
+                url: String::from(local_settings.backend_database_url),
+                user: String::from(local_settings.backend_database_user),
+                password: String::from(local_settings.backend_database_password),
+                instance: String::from(local_settings.backend_database_instance),
+            };
+            let db_handler = DbHandler::new(&db_connection);
+            let reconciliation_handle =
+                FinanceReconciliationHandle::new(&db_connection, &user_id, &db_handler);
+            reconciliation_handle.reconcile_sync(
+                &finance_account_id,
+                statement_time,
+                input.statement_balance,
+                &booking_entry_ids,
+            )
+        };
+
+        let mut return_status_code = StatusCode::OK;
+        let reconcile_message: String;
+        if reconcile_result.is_err() {
+            return_status_code = StatusCode::BAD_REQUEST;
+            reconcile_message = reconcile_result.unwrap_err();
+        } else {
+            let reconciliation = reconcile_result.unwrap();
+            reconcile_message = format!(
+                "OK, {} entries reconciled, difference {:.2}",
+                booking_entry_ids.len(),
+                ((reconciliation.statement_balance - reconciliation.book_balance) as f64)
+                    / (100 as f64)
+            );
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        let return_value = ReconciliationResponse {
+            result: reconcile_message,
+        };
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+pub async fn do_unreconcile_account(
+    session_data: SessionDataResult,
+    Form(input): Form<UnreconcileFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = ReconciliationResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = ReconciliationResponse {
+            result: "session expired".to_string(),
+        };
+
+        (StatusCode::BAD_REQUEST, headers, return_value)
+    } else {
+        let unreconcile_result: String;
+
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let mut return_status_code = StatusCode::OK;
+        {
+            let reconciliation_id_parse = Uuid::parse_str(&input.reconciliation_id);
+            if reconciliation_id_parse.is_err() {
+                let return_value = ReconciliationResponse {
+                    result: format!(
+                        "error parsing reconciliation_id: {}",
+                        reconciliation_id_parse.unwrap_err()
+                    ),
+                };
+                return (StatusCode::BAD_REQUEST, headers, return_value);
+            }
+
+            let reconciliation_handle =
+                FinanceReconciliationHandle::new(&db_connection, &user_id, &db_handler);
+            let unreconcile_response =
+                reconciliation_handle.unreconcile_sync(&reconciliation_id_parse.unwrap());
+            if unreconcile_response.is_err() {
+                return_status_code = StatusCode::BAD_REQUEST;
+                unreconcile_result = unreconcile_response.unwrap_err();
+            } else {
+                unreconcile_result = "OK, reconciliation removed".to_string();
+            }
+        }
+
+        session.expire_in(std::time::Duration::from_secs(60 * 10));
+
+        let return_value = ReconciliationResponse {
+            result: unreconcile_result,
+        };
+
+        let _new_cookie = session_data.session_store.store_session(session).await;
+
+        (return_status_code, headers, return_value)
+    }
+}
+
+/// time range and format of a CSV export, \t is used for a tab as delimiter
+#[derive(Deserialize, Debug)]
+pub struct CsvExportFormInput {
+    pub date_from: Option<String>,
+    pub date_till: Option<String>,
+    pub delimiter: String,
+    pub decimal_separator: String,
+}
+
+fn get_export_form_char(value: &str, field_name: &str) -> Result<char, String> {
+    // tab can not be entered into a text field
+    if value.eq("\\t") {
+        return Ok('\t');
+    }
+    let mut value_chars = value.chars();
+    let first_char = value_chars.next();
+    if first_char.is_none() || value_chars.next().is_some() {
+        return Err(format!("{} must be a single character", field_name));
+    }
+    return Ok(first_char.unwrap());
+}
+
+/// time range and settings of a CSV export read from the form
+#[derive(Debug)]
+struct CsvExportRequest {
+    time_from: Option<DateTime<Utc>>,
+    time_till: Option<DateTime<Utc>>,
+    settings: CsvExportSettings,
+}
+
+fn get_csv_export_request(input: &CsvExportFormInput) -> Result<CsvExportRequest, String> {
+    let time_from_result = get_optional_report_time(&input.date_from, false);
+    if time_from_result.is_err() {
+        return Err(format!(
+            "error parsing date_from: {}",
+            time_from_result.unwrap_err()
+        ));
+    }
+    let time_till_result = get_optional_report_time(&input.date_till, true);
+    if time_till_result.is_err() {
+        return Err(format!(
+            "error parsing date_till: {}",
+            time_till_result.unwrap_err()
+        ));
+    }
+    let settings = CsvExportSettings {
+        delimiter: get_export_form_char(&input.delimiter, "delimiter")?,
+        decimal_separator: get_export_form_char(&input.decimal_separator, "decimal_separator")?,
+    };
+    return Ok(CsvExportRequest {
+        time_from: time_from_result.unwrap(),
+        time_till: time_till_result.unwrap(),
+        settings,
+    });
+}
+
+fn create_text_download_response<T: Into<Body>>(
+    export_result: Result<T, String>,
+    file_name: &str,
+    content_type: &'static str,
+) -> Response {
+    let export_content = match export_result {
+        Ok(content) => content,
+        Err(error_text) => {
+            return Response::builder()
+                .status(StatusCode::BAD_REQUEST)
+                .body(Body::from(error_text))
+                .unwrap();
+        }
+    };
+    return Response::builder()
+        .status(StatusCode::OK)
+        .header(header::CONTENT_TYPE, HeaderValue::from_static(content_type))
+        .header(
+            header::CONTENT_DISPOSITION,
+            HeaderValue::from_str(&format!("attachment; filename=\"{}\"", file_name))
+                .unwrap_or(HeaderValue::from_static("attachment")),
+        )
+        .body(export_content.into())
+        .unwrap();
+}
+
+/// returns the journal entries of the time range as CSV file
+pub async fn do_download_journal_csv(
+    session_data: SessionDataResult,
+    Form(input): Form<CsvExportFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in || session.is_expired() {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("not logged in or session expired"))
+            .unwrap();
+    }
+
+    let export_request_result = get_csv_export_request(&input);
+    if export_request_result.is_err() {
+        return create_text_download_response::<String>(
+            Err(export_request_result.unwrap_err()),
+            "",
+            "text/csv; charset=utf-8",
+        );
+    }
+    let CsvExportRequest {
+        time_from,
+        time_till,
+        settings,
+    } = export_request_result.unwrap();
+
+    let export_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let export_handle = FinanceCsvExportHandle::new(&db_connection, &user_id, &db_handler);
+        export_handle.export_journal_sync(time_from, time_till, &settings)
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if export_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_download_journal_csv: {}",export_result.as_ref().unwrap_err());
+    }
+    return create_text_download_response(export_result, "journal.csv", "text/csv; charset=utf-8");
+}
+
+/// returns the account tables of the time range as CSV file
+pub async fn do_download_account_tables_csv(
+    session_data: SessionDataResult,
+    Form(input): Form<CsvExportFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in || session.is_expired() {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("not logged in or session expired"))
+            .unwrap();
+    }
+
+    let export_request_result = get_csv_export_request(&input);
+    if export_request_result.is_err() {
+        return create_text_download_response::<String>(
+            Err(export_request_result.unwrap_err()),
+            "",
+            "text/csv; charset=utf-8",
+        );
+    }
+    let CsvExportRequest {
+        time_from,
+        time_till,
+        settings,
+    } = export_request_result.unwrap();
+
+    let export_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let export_handle = FinanceCsvExportHandle::new(&db_connection, &user_id, &db_handler);
+        export_handle.export_account_tables_sync(time_from, time_till, &settings)
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if export_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_download_account_tables_csv: {}",export_result.as_ref().unwrap_err());
+    }
+    return create_text_download_response(
+        export_result,
+        "account_tables.csv",
+        "text/csv; charset=utf-8",
+    );
+}
+
+/// syntax and commodity of a plain text accounting export
+#[derive(Deserialize, Debug)]
+pub struct PlainTextAccountingExportFormInput {
+    pub format: String,
+    pub commodity: String,
+}
+
+/// returns account types, accounts and all journal entries as ledger or beancount file
+pub async fn do_download_plain_text_accounting(
+    session_data: SessionDataResult,
+    Form(input): Form<PlainTextAccountingExportFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in || session.is_expired() {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("not logged in or session expired"))
+            .unwrap();
+    }
+
+    let format_result = PlainTextAccountingFormat::get_from_string(&input.format);
+    if format_result.is_err() {
+        return create_text_download_response::<String>(
+            Err(format_result.unwrap_err()),
+            "",
+            "text/plain; charset=utf-8",
+        );
+    }
+    let format = format_result.unwrap();
+
+    let export_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let plain_text_handle =
+            FinancePlainTextAccountingHandle::new(&db_connection, &user_id, &db_handler);
+        plain_text_handle.export_text_sync(&format, input.commodity.trim())
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if export_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_download_plain_text_accounting: {}",export_result.as_ref().unwrap_err());
+    }
+    let file_name = match format {
+        PlainTextAccountingFormat::Ledger => "journal.ledger",
+        PlainTextAccountingFormat::Beancount => "journal.beancount",
+    };
+    return create_text_download_response(export_result, file_name, "text/plain; charset=utf-8");
+}
+
+/// header data of a DATEV export, dates as YYYY-MM-DD
+#[derive(Deserialize, Debug)]
+pub struct DatevExportFormInput {
+    pub consultant_number: String,
+    pub client_number: String,
+    pub fiscal_year_start: String,
+    pub account_length: String,
+    pub date_from: String,
+    pub date_till: String,
+}
+
+fn get_datev_export_settings(input: &DatevExportFormInput) -> Result<DatevExportSettings, String> {
+    let consultant_number_result = input.consultant_number.trim().parse::<u32>();
+    if consultant_number_result.is_err() {
+        return Err(format!(
+            "error parsing consultant_number: {}",
+            consultant_number_result.unwrap_err()
+        ));
+    }
+    let client_number_result = input.client_number.trim().parse::<u32>();
+    if client_number_result.is_err() {
+        return Err(format!(
+            "error parsing client_number: {}",
+            client_number_result.unwrap_err()
+        ));
+    }
+    let account_length_result = input.account_length.trim().parse::<u8>();
+    if account_length_result.is_err() {
+        return Err(format!(
+            "error parsing account_length: {}",
+            account_length_result.unwrap_err()
+        ));
+    }
+    let mut dates = Vec::new();
+    for (field_name, date_text) in [
+        ("fiscal_year_start", &input.fiscal_year_start),
+        ("date_from", &input.date_from),
+        ("date_till", &input.date_till),
+    ] {
+        let date_result = NaiveDate::parse_from_str(date_text.trim(), "%Y-%m-%d");
+        if date_result.is_err() {
+            return Err(format!(
+                "error parsing {}: {}",
+                field_name,
+                date_result.unwrap_err()
+            ));
+        }
+        dates.push(date_result.unwrap());
+    }
+    return Ok(DatevExportSettings {
+        consultant_number: consultant_number_result.unwrap(),
+        client_number: client_number_result.unwrap(),
+        fiscal_year_start: dates[0],
+        account_length: account_length_result.unwrap(),
+        period_from: dates[1],
+        period_till: dates[2],
+    });
+}
+
+/// returns the journal entries of a fiscal period as DATEV booking batch ("Buchungsstapel")
+pub async fn do_download_datev_export(
+    session_data: SessionDataResult,
+    Form(input): Form<DatevExportFormInput>,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in || session.is_expired() {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("not logged in or session expired"))
+            .unwrap();
+    }
+
+    let settings_result = get_datev_export_settings(&input);
+    if settings_result.is_err() {
+        return create_text_download_response::<String>(
+            Err(settings_result.unwrap_err()),
+            "",
+            "text/csv; charset=windows-1252",
+        );
+    }
+    let settings = settings_result.unwrap();
+
+    let export_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let datev_handle = FinanceDatevExportHandle::new(&db_connection, &user_id, &db_handler);
+        datev_handle.export_booking_batch_sync(&settings)
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if export_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_download_datev_export: {}",export_result.as_ref().unwrap_err());
+    }
+    // DATEV recognizes imported files by the prefix EXTF_
+    let file_name = format!(
+        "EXTF_Buchungsstapel_{}_{}.csv",
+        settings.period_from.format("%Y%m%d"),
+        settings.period_till.format("%Y%m%d")
+    );
+    return create_text_download_response(
+        export_result,
+        &file_name,
+        "text/csv; charset=windows-1252",
+    );
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct PlainTextAccountingImportResponse {
+    pub result: String,
+}
+
+impl IntoResponse for PlainTextAccountingImportResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+/// multipart upload with the field format and a file of the plain text accounting export as field file,
+/// the file is only imported into an empty journal
+pub async fn do_import_plain_text_accounting(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = PlainTextAccountingImportResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = PlainTextAccountingImportResponse {
+            result: "session expired".to_string(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let upload_result = read_bank_import_upload(&mut multipart).await;
+    if upload_result.is_err() {
+        let return_value = PlainTextAccountingImportResponse {
+            result: upload_result.unwrap_err(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (form_fields, file_content) = upload_result.unwrap();
+    let format_text_result = get_import_form_value(&form_fields, "format");
+    if format_text_result.is_err() {
+        let return_value = PlainTextAccountingImportResponse {
+            result: format_text_result.unwrap_err(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let format_result = PlainTextAccountingFormat::get_from_string(format_text_result.unwrap());
+    if format_result.is_err() {
+        let return_value = PlainTextAccountingImportResponse {
+            result: format_result.unwrap_err(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let content_result = String::from_utf8(file_content);
+    if content_result.is_err() {
+        let return_value = PlainTextAccountingImportResponse {
+            result: format!("file is not UTF-8: {}", content_result.unwrap_err()),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let import_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let plain_text_handle =
+            FinancePlainTextAccountingHandle::new(&db_connection, &user_id, &db_handler);
+        plain_text_handle.import_text_sync(&content_result.unwrap(), &format_result.unwrap())
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if import_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_import_plain_text_accounting: {}",import_result.as_ref().unwrap_err());
+        let return_value = PlainTextAccountingImportResponse {
+            result: format!("Error importing file: {}", import_result.unwrap_err()),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let return_value = PlainTextAccountingImportResponse {
+        result: format!("{} journal entries imported", import_result.unwrap()),
+    };
+    return (StatusCode::OK, headers, return_value);
+}
+
+/// returns all accounting data of the user as versioned JSON archive
+pub async fn do_download_backup(session_data: SessionDataResult) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in || session.is_expired() {
+        return Response::builder()
+            .status(StatusCode::UNAUTHORIZED)
+            .body(Body::from("not logged in or session expired"))
+            .unwrap();
+    }
+
+    let export_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let backup_handle = FinanceBackupHandle::new(&db_connection, &user_id, &db_handler);
+        backup_handle.export_json_sync()
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if export_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_download_backup: {}",export_result.as_ref().unwrap_err());
+    }
+    return create_text_download_response(
+        export_result,
+        "finance_backup.json",
+        "application/json; charset=utf-8",
+    );
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub(crate) struct BackupRestoreResponse {
+    pub result: String,
+}
+
+impl IntoResponse for BackupRestoreResponse {
+    fn into_response(self) -> Response {
+        return Json(json!(self)).into_response();
+    }
+}
+
+/// multipart upload with a backup archive as field file, the archive is only restored into an empty journal
+pub async fn do_restore_backup(
+    session_data: SessionDataResult,
+    mut multipart: Multipart,
+) -> impl IntoResponse {
+    let session_data = SessionData::from_session_data_result(session_data);
+
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    if !is_logged_in {
+        let return_value = BackupRestoreResponse {
+            result: "not logged in".to_string(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = BackupRestoreResponse {
+            result: "session expired".to_string(),
+        };
+
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let upload_result = read_bank_import_upload(&mut multipart).await;
+    if upload_result.is_err() {
+        let return_value = BackupRestoreResponse {
+            result: upload_result.unwrap_err(),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+    let (_form_fields, file_content) = upload_result.unwrap();
+    let content_result = String::from_utf8(file_content);
+    if content_result.is_err() {
+        let return_value = BackupRestoreResponse {
+            result: format!("file is not UTF-8: {}", content_result.unwrap_err()),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
+    }
+
+    let restore_result = {
+        let local_settings: SettingStruct = SettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(local_settings.backend_database_url),
+            user: String::from(local_settings.backend_database_user),
+            password: String::from(local_settings.backend_database_password),
+            instance: String::from(local_settings.backend_database_instance),
+        };
+        let db_handler = DbHandler::new(&db_connection);
+        let user_id: Uuid = session.get("user_account_id").unwrap();
+
+        let backup_handle = FinanceBackupHandle::new(&db_connection, &user_id, &db_handler);
+        backup_handle.import_json_sync(&content_result.unwrap())
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    if restore_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in do_restore_backup: {}",restore_result.as_ref().unwrap_err());
+        let return_value = BackupRestoreResponse {
+            result: format!("Error restoring backup: {}", restore_result.unwrap_err()),
+        };
+        return (StatusCode::BAD_REQUEST, headers, return_value);
     }
+    let return_value = BackupRestoreResponse {
+        result: format!("{} journal entries restored", restore_result.unwrap()),
+    };
+    return (StatusCode::OK, headers, return_value);
 }
@@ -0,0 +1,521 @@
+use async_session::chrono::{DateTime, Utc};
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, Document, Uuid},
+    options::FindOptions,
+    Collection,
+};
+use rusqlite::{params, Row};
+use tokio_postgres::Row as PostgresRow;
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::FinanceJournalAttachment,
+};
+
+/// attachments belong to journal entries, so the accounting functions are required as well,
+/// there is no delete function because attachments have to be kept for audit
+#[async_trait(?Send)]
+pub trait DBFinanceAttachmentFunctions: DBFinanceAccountingFunctions {
+    /// lists the attachments of the user, only the ones of the given journal entry if an id is given
+    async fn finance_journal_attachment_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String>;
+    /// returns None if the attachment does not exist or belongs to another user
+    async fn finance_journal_attachment_get(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> Result<Option<FinanceJournalAttachment>, String>;
+    async fn finance_journal_attachment_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment: &FinanceJournalAttachment,
+    ) -> Result<(), String>;
+}
+
+impl DbHandlerMongoDB {
+    async fn find_journal_attachments(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        filter: Document,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let attachment_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_ATTACHMENTS);
+
+        debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
+        let projection = doc! {"attachment_id":<i32>::from(1),
+        "finance_journal_diary_id":<i32>::from(1),
+        "file_name":<i32>::from(1),
+        "mime_type":<i32>::from(1),
+        "size_bytes":<i32>::from(1),
+        "upload_time":<i32>::from(1),};
+        let options = FindOptions::builder()
+            .projection(projection)
+            .sort(doc! {"upload_time":1})
+            .build();
+
+        let query_execute_result = attachment_collection.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let stored_attachment_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "attachment_id");
+            if stored_attachment_id.is_err() {
+                return Err(stored_attachment_id.unwrap_err());
+            }
+            let stored_journal_entry_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "finance_journal_diary_id");
+            if stored_journal_entry_id.is_err() {
+                return Err(stored_journal_entry_id.unwrap_err());
+            }
+            let stored_file_name = inner_doc.get_str("file_name");
+            if stored_file_name.is_err() {
+                return Err(stored_file_name.unwrap_err().to_string());
+            }
+            let stored_mime_type = inner_doc.get_str("mime_type");
+            if stored_mime_type.is_err() {
+                return Err(stored_mime_type.unwrap_err().to_string());
+            }
+            let stored_size_bytes = inner_doc.get_i64("size_bytes");
+            if stored_size_bytes.is_err() {
+                return Err(stored_size_bytes.unwrap_err().to_string());
+            }
+            let stored_upload_time = inner_doc.get_datetime("upload_time");
+            if stored_upload_time.is_err() {
+                return Err(stored_upload_time.unwrap_err().to_string());
+            }
+
+            let attachment = FinanceJournalAttachment {
+                id: stored_attachment_id.unwrap(),
+                finance_journal_diary_id: stored_journal_entry_id.unwrap(),
+                file_name: stored_file_name.unwrap().into(),
+                mime_type: stored_mime_type.unwrap().into(),
+                size_bytes: stored_size_bytes.unwrap() as u64,
+                upload_time: stored_upload_time.unwrap().to_chrono(),
+            };
+
+            result_list.push(attachment);
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} journal attachments",result_list.len());
+
+        return Ok(result_list);
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceAttachmentFunctions for DbHandlerMongoDB {
+    async fn finance_journal_attachment_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        //get a binary of UUID or it will not work in production
+        let mut filter = doc! {"user_id":mongodb::bson::Binary::from_uuid(user_id.clone())};
+        if journal_entry_id.is_some() {
+            filter.insert(
+                "finance_journal_diary_id",
+                mongodb::bson::Binary::from_uuid(journal_entry_id.unwrap()),
+            );
+        }
+
+        let temp_var_0 = self
+            .find_journal_attachments(conncetion_settings, filter)
+            .await;
+        return temp_var_0;
+    }
+
+    async fn finance_journal_attachment_get(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> Result<Option<FinanceJournalAttachment>, String> {
+        let filter = doc! {"user_id":mongodb::bson::Binary::from_uuid(user_id.clone()),
+        "attachment_id":mongodb::bson::Binary::from_uuid(attachment_id.clone())};
+
+        let find_result = self
+            .find_journal_attachments(conncetion_settings, filter)
+            .await;
+        if find_result.is_err() {
+            return Err(find_result.unwrap_err());
+        }
+        let mut attachment_list = find_result.unwrap();
+        if attachment_list.len() > 1 {
+            return Err(format!(
+                "found {} attachments with id {}",
+                attachment_list.len(),
+                attachment_id
+            ));
+        }
+
+        return Ok(attachment_list.pop());
+    }
+
+    async fn finance_journal_attachment_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment: &FinanceJournalAttachment,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let attachment_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_ATTACHMENTS);
+
+        let insert_doc = doc! {
+            "attachment_id":mongodb::bson::Binary::from_uuid(attachment.id),
+            "user_id":mongodb::bson::Binary::from_uuid(user_id.clone()),
+            "finance_journal_diary_id":mongodb::bson::Binary::from_uuid(attachment.finance_journal_diary_id),
+            "file_name":attachment.file_name.clone(),
+            "mime_type":attachment.mime_type.clone(),
+            "size_bytes":attachment.size_bytes as i64,
+            "upload_time":attachment.upload_time,
+        };
+
+        let insert_result = attachment_collection.insert_one(insert_doc, None).await;
+        if insert_result.is_err() {
+            let insert_err = &insert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","inserted journal attachment for user id {}",&user_id);
+
+        Ok(())
+    }
+}
+
+impl DbHandlerSqlite {
+    fn read_journal_attachment_row(row: &Row) -> Result<FinanceJournalAttachment, String> {
+        let stored_attachment_id = DbHandlerSqlite::get_uuid_from_row(row, "attachment_id");
+        if stored_attachment_id.is_err() {
+            return Err(stored_attachment_id.unwrap_err());
+        }
+        let stored_journal_entry_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "finance_journal_diary_id");
+        if stored_journal_entry_id.is_err() {
+            return Err(stored_journal_entry_id.unwrap_err());
+        }
+        let stored_file_name = row.get::<_, String>("file_name");
+        if stored_file_name.is_err() {
+            return Err(stored_file_name.unwrap_err().to_string());
+        }
+        let stored_mime_type = row.get::<_, String>("mime_type");
+        if stored_mime_type.is_err() {
+            return Err(stored_mime_type.unwrap_err().to_string());
+        }
+        let stored_size_bytes = row.get::<_, i64>("size_bytes");
+        if stored_size_bytes.is_err() {
+            return Err(stored_size_bytes.unwrap_err().to_string());
+        }
+        let stored_upload_time = DbHandlerSqlite::get_datetime_from_row(row, "upload_time");
+        if stored_upload_time.is_err() {
+            return Err(stored_upload_time.unwrap_err());
+        }
+
+        return Ok(FinanceJournalAttachment {
+            id: stored_attachment_id.unwrap(),
+            finance_journal_diary_id: stored_journal_entry_id.unwrap(),
+            file_name: stored_file_name.unwrap(),
+            mime_type: stored_mime_type.unwrap(),
+            size_bytes: stored_size_bytes.unwrap() as u64,
+            upload_time: stored_upload_time.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceAttachmentFunctions for DbHandlerSqlite {
+    async fn finance_journal_attachment_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceJournalAttachments WHERE user_id = ?1
+            AND (?2 IS NULL OR finance_journal_diary_id = ?2) ORDER BY rowid",
+            params![
+                user_id.to_string(),
+                journal_entry_id.map(|elem| elem.to_string())
+            ],
+            DbHandlerSqlite::read_journal_attachment_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} journal attachments",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_journal_attachment_get(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> Result<Option<FinanceJournalAttachment>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceJournalAttachments WHERE user_id = ?1 AND attachment_id = ?2",
+            [user_id.to_string(), attachment_id.to_string()],
+            DbHandlerSqlite::read_journal_attachment_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+
+        return Ok(query_result.unwrap().pop());
+    }
+
+    async fn finance_journal_attachment_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment: &FinanceJournalAttachment,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let insert_result = connection.execute(
+            "INSERT INTO FinanceJournalAttachments (attachment_id, user_id, finance_journal_diary_id, file_name,
+            mime_type, size_bytes, upload_time) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)",
+            params![
+                attachment.id.to_string(),
+                user_id.to_string(),
+                attachment.finance_journal_diary_id.to_string(),
+                &attachment.file_name,
+                &attachment.mime_type,
+                attachment.size_bytes as i64,
+                DbHandlerSqlite::format_datetime(&attachment.upload_time),
+            ],
+        );
+        if insert_result.is_err() {
+            let insert_err = insert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","inserted journal attachment for user id {}",&user_id);
+
+        Ok(())
+    }
+}
+
+impl DbHandlerPostgres {
+    fn read_journal_attachment_row(row: &PostgresRow) -> Result<FinanceJournalAttachment, String> {
+        let stored_attachment_id = DbHandlerPostgres::get_uuid_from_row(row, "attachment_id");
+        if stored_attachment_id.is_err() {
+            return Err(stored_attachment_id.unwrap_err());
+        }
+        let stored_journal_entry_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "finance_journal_diary_id");
+        if stored_journal_entry_id.is_err() {
+            return Err(stored_journal_entry_id.unwrap_err());
+        }
+        let stored_file_name = row.try_get::<_, String>("file_name");
+        if stored_file_name.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_file_name.unwrap_err(),
+            ));
+        }
+        let stored_mime_type = row.try_get::<_, String>("mime_type");
+        if stored_mime_type.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_mime_type.unwrap_err(),
+            ));
+        }
+        let stored_size_bytes = row.try_get::<_, i64>("size_bytes");
+        if stored_size_bytes.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_size_bytes.unwrap_err(),
+            ));
+        }
+        let stored_upload_time = row.try_get::<_, DateTime<Utc>>("upload_time");
+        if stored_upload_time.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_upload_time.unwrap_err(),
+            ));
+        }
+
+        return Ok(FinanceJournalAttachment {
+            id: stored_attachment_id.unwrap(),
+            finance_journal_diary_id: stored_journal_entry_id.unwrap(),
+            file_name: stored_file_name.unwrap(),
+            mime_type: stored_mime_type.unwrap(),
+            size_bytes: stored_size_bytes.unwrap() as u64,
+            upload_time: stored_upload_time.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceAttachmentFunctions for DbHandlerPostgres {
+    async fn finance_journal_attachment_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceJournalAttachments WHERE user_id = $1
+            AND ($2::UUID IS NULL OR finance_journal_diary_id = $2) ORDER BY insert_order",
+            &[
+                &user_id.to_uuid_1(),
+                &journal_entry_id.map(|elem| elem.to_uuid_1()),
+            ],
+            DbHandlerPostgres::read_journal_attachment_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} journal attachments",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_journal_attachment_get(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> Result<Option<FinanceJournalAttachment>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceJournalAttachments WHERE user_id = $1 AND attachment_id = $2",
+            &[&user_id.to_uuid_1(), &attachment_id.to_uuid_1()],
+            DbHandlerPostgres::read_journal_attachment_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+
+        return Ok(query_result.unwrap().pop());
+    }
+
+    async fn finance_journal_attachment_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment: &FinanceJournalAttachment,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let insert_result = client
+.execute(
+            "INSERT INTO FinanceJournalAttachments (attachment_id, user_id, finance_journal_diary_id, file_name,
+            mime_type, size_bytes, upload_time) VALUES ($1, $2, $3, $4, $5, $6, $7)",
+            &[
+                &attachment.id.to_uuid_1(),
+                &user_id.to_uuid_1(),
+                &attachment.finance_journal_diary_id.to_uuid_1(),
+                &attachment.file_name,
+                &attachment.mime_type,
+                &(attachment.size_bytes as i64),
+                &attachment.upload_time,
+            ],
+        )
+.await;
+        if insert_result.is_err() {
+            let insert_err = DbHandlerPostgres::format_error(&insert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","inserted journal attachment for user id {}",&user_id);
+
+        Ok(())
+    }
+}
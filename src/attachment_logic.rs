@@ -0,0 +1,283 @@
+use std::{fs, path::PathBuf};
+
+use async_session::chrono::Utc;
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_logic::FinanceBookingHandle, attachment_database::DBFinanceAttachmentFunctions,
+    database_handler_mongodb::DbConnectionSetting, datatypes::FinanceJournalAttachment,
+    setting_struct::SettingStruct,
+};
+
+/// receipts and invoices are accepted as PDF or image files
+pub const ALLOWED_ATTACHMENT_MIME_TYPES: [&str; 5] = [
+    "application/pdf",
+    "image/png",
+    "image/jpeg",
+    "image/gif",
+    "image/webp",
+];
+
+/// where the attachment files are stored and how large they may be
+pub struct AttachmentStorageSetting {
+    /// files are stored as <storage_directory>/<user id>/<attachment id>
+    pub storage_directory: PathBuf,
+    pub max_size_bytes: u64,
+}
+
+impl AttachmentStorageSetting {
+    pub fn from_setting(setting: &SettingStruct) -> Self {
+        Self {
+            storage_directory: PathBuf::from(&setting.backend_attachment_storage_path),
+            max_size_bytes: setting.backend_attachment_max_size_bytes,
+        }
+    }
+}
+
+pub struct FinanceAttachmentHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceAttachmentFunctions,
+    storage_setting: &'a AttachmentStorageSetting,
+}
+
+impl<'a> FinanceAttachmentHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceAttachmentFunctions,
+        storage_setting: &'a AttachmentStorageSetting,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+            storage_setting,
+        }
+    }
+
+    /// MIME type by the signature at the beginning of the file, None if the type is not allowed
+    pub fn detect_mime_type(content: &[u8]) -> Option<&'static str> {
+        if content.starts_with(b"%PDF-") {
+            return Some("application/pdf");
+        }
+        if content.starts_with(&[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]) {
+            return Some("image/png");
+        }
+        if content.starts_with(&[0xFF, 0xD8, 0xFF]) {
+            return Some("image/jpeg");
+        }
+        if content.starts_with(b"GIF87a") || content.starts_with(b"GIF89a") {
+            return Some("image/gif");
+        }
+        if content.len() >= 12 && content.starts_with(b"RIFF") && content[8..12].eq(b"WEBP") {
+            return Some("image/webp");
+        }
+        return None;
+    }
+
+    /// file name without any directory part, used for the download
+    fn get_clean_file_name(file_name: &str) -> String {
+        let base_name = file_name
+            .rsplit(|elem| elem == '/' || elem == '\\')
+            .next()
+            .unwrap_or("")
+            .trim();
+        if base_name.is_empty() {
+            return "attachment".into();
+        }
+        return base_name
+            .chars()
+            .filter(|elem| !elem.is_control() && !elem.eq(&'"'))
+            .collect();
+    }
+
+    fn get_file_path(&self, attachment_id: &Uuid) -> PathBuf {
+        return self
+            .storage_setting
+            .storage_directory
+            .join(self.user_id.to_string())
+            .join(attachment_id.to_string());
+    }
+
+    pub async fn attachment_list(
+        &self,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        let temp_var_0 = self.db_connector.finance_journal_attachment_list(
+            &self.db_connection_settings,
+            &self.user_id,
+            journal_entry_id,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn attachment_list_sync(
+        &self,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        let temp_var_0 = executor::block_on(self.attachment_list(journal_entry_id));
+        return temp_var_0;
+    }
+
+    /// stores the file and attaches it to the journal entry of the user, the declared MIME type
+    /// (from the upload) has to match the content if it is given
+    pub async fn attachment_upload(
+        &self,
+        journal_entry_id: &Uuid,
+        file_name: &str,
+        declared_mime_type: &str,
+        content: &[u8],
+    ) -> Result<FinanceJournalAttachment, String> {
+        if content.is_empty() {
+            return Err("could not upload attachment because the file is empty".into());
+        }
+        if (content.len() as u64) > self.storage_setting.max_size_bytes {
+            return Err(format!(
+                "could not upload attachment because the file is larger than {} bytes",
+                self.storage_setting.max_size_bytes
+            ));
+        }
+        let detected_mime_type = FinanceAttachmentHandle::detect_mime_type(content);
+        if detected_mime_type.is_none() {
+            return Err(format!(
+                "could not upload attachment, only these file types are allowed: {}",
+                ALLOWED_ATTACHMENT_MIME_TYPES.join(", ")
+            ));
+        }
+        let mime_type = detected_mime_type.unwrap();
+        let declared_mime_type = declared_mime_type.trim().to_lowercase();
+        if !declared_mime_type.is_empty()
+            && !declared_mime_type.eq("application/octet-stream")
+            && !declared_mime_type.eq(mime_type)
+        {
+            return Err(format!(
+                "could not upload attachment because the file is declared as {} but contains {}",
+                declared_mime_type, mime_type
+            ));
+        }
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let journal_entries_result = booking_handle.list_journal_entries(None, None).await;
+        if journal_entries_result.is_err() {
+            return Err(format!(
+                "Error getting journal entries: {}",
+                journal_entries_result.unwrap_err()
+            ));
+        }
+        if !journal_entries_result
+            .unwrap()
+            .iter()
+            .any(|elem| elem.id.eq(journal_entry_id))
+        {
+            return Err(format!(
+                "could not upload attachment because journal entry {} was not found",
+                journal_entry_id
+            ));
+        }
+
+        let new_attachment = FinanceJournalAttachment {
+            id: Uuid::new(),
+            finance_journal_diary_id: journal_entry_id.clone(),
+            file_name: FinanceAttachmentHandle::get_clean_file_name(file_name),
+            mime_type: mime_type.into(),
+            size_bytes: content.len() as u64,
+            upload_time: Utc::now(),
+        };
+
+        let file_path = self.get_file_path(&new_attachment.id);
+        let create_dir_result = fs::create_dir_all(file_path.parent().unwrap());
+        if create_dir_result.is_err() {
+            return Err(format!(
+                "Error creating attachment directory: {}",
+                create_dir_result.unwrap_err()
+            ));
+        }
+        let write_result = fs::write(&file_path, content);
+        if write_result.is_err() {
+            return Err(format!(
+                "Error writing attachment file: {}",
+                write_result.unwrap_err()
+            ));
+        }
+
+        let insert_result = self
+            .db_connector
+            .finance_journal_attachment_insert(
+                &self.db_connection_settings,
+                &self.user_id,
+                &new_attachment,
+            )
+            .await;
+        if insert_result.is_err() {
+            // without the database entry the file can not be found anymore
+            let _remove_result = fs::remove_file(&file_path);
+            return Err(insert_result.unwrap_err());
+        }
+
+        return Ok(new_attachment);
+    }
+
+    pub fn attachment_upload_sync(
+        &self,
+        journal_entry_id: &Uuid,
+        file_name: &str,
+        declared_mime_type: &str,
+        content: &[u8],
+    ) -> Result<FinanceJournalAttachment, String> {
+        let temp_var_0 = executor::block_on(self.attachment_upload(
+            journal_entry_id,
+            file_name,
+            declared_mime_type,
+            content,
+        ));
+        return temp_var_0;
+    }
+
+    /// returns the attachment with its content, only attachments of the user can be loaded
+    pub async fn attachment_download(
+        &self,
+        attachment_id: &Uuid,
+    ) -> Result<(FinanceJournalAttachment, Vec<u8>), String> {
+        let attachment_result = self
+            .db_connector
+            .finance_journal_attachment_get(
+                &self.db_connection_settings,
+                &self.user_id,
+                attachment_id,
+            )
+            .await;
+        if attachment_result.is_err() {
+            return Err(attachment_result.unwrap_err());
+        }
+        let attachment_option = attachment_result.unwrap();
+        if attachment_option.is_none() {
+            return Err(format!("attachment {} not found", attachment_id));
+        }
+        let attachment = attachment_option.unwrap();
+
+        let read_result = fs::read(self.get_file_path(&attachment.id));
+        if read_result.is_err() {
+            return Err(format!(
+                "Error reading attachment file: {}",
+                read_result.unwrap_err()
+            ));
+        }
+
+        return Ok((attachment, read_result.unwrap()));
+    }
+
+    pub fn attachment_download_sync(
+        &self,
+        attachment_id: &Uuid,
+    ) -> Result<(FinanceJournalAttachment, Vec<u8>), String> {
+        let temp_var_0 = executor::block_on(self.attachment_download(attachment_id));
+        return temp_var_0;
+    }
+}
@@ -0,0 +1,456 @@
+use std::collections::{HashMap, HashSet};
+
+use async_session::{chrono::Utc, serde_json};
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_database::{
+        DBFinanceAccountingFunctions, FinanceAccountBookingEntryListSearchOption,
+    },
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{BookingEntryType, FinanceBackupArchive, FinanceJournalEntry},
+};
+
+pub struct FinanceBackupHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceAccountingFunctions,
+}
+
+impl<'a> FinanceBackupHandle<'a> {
+    /// version written to new archives, only archives of this version can be restored
+    pub const SCHEMA_VERSION: u32 = 1;
+
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceAccountingFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    /// collects account types, accounts, journal, booking entries and the counter record,
+    /// reconciliations are not part of the archive so the booking entries are written without them
+    pub async fn create_archive(&self) -> Result<FinanceBackupArchive, String> {
+        let account_types_result = self
+            .db_connector
+            .finance_account_type_list(&self.db_connection_settings, &self.user_id)
+            .await;
+        if account_types_result.is_err() {
+            return Err(account_types_result.unwrap_err());
+        }
+        let accounts_result = self
+            .db_connector
+            .finance_account_list(&self.db_connection_settings, &self.user_id, None)
+            .await;
+        if accounts_result.is_err() {
+            return Err(accounts_result.unwrap_err());
+        }
+        let accounts = accounts_result.unwrap();
+        let journal_entries_result = self
+            .db_connector
+            .finance_journal_entry_list(
+                &self.db_connection_settings,
+                &self.user_id,
+                None,
+                None,
+                None,
+            )
+            .await;
+        if journal_entries_result.is_err() {
+            return Err(journal_entries_result.unwrap_err());
+        }
+        let mut journal_entries = journal_entries_result.unwrap();
+        journal_entries.sort_by_key(|elem| elem.running_number);
+
+        let mut booking_entries = Vec::new();
+        if !accounts.is_empty() {
+            let search_options = accounts
+                .iter()
+                .map(|elem| FinanceAccountBookingEntryListSearchOption::new(&elem.id, None, None))
+                .collect();
+            let booking_entries_result = self
+                .db_connector
+                .finance_account_booking_entry_list(
+                    &self.db_connection_settings,
+                    &self.user_id,
+                    search_options,
+                )
+                .await;
+            if booking_entries_result.is_err() {
+                return Err(booking_entries_result.unwrap_err());
+            }
+            booking_entries = booking_entries_result.unwrap();
+        }
+        let journal_positions: HashMap<Uuid, usize> = journal_entries
+            .iter()
+            .enumerate()
+            .map(|(position, elem)| (elem.id, position))
+            .collect();
+        booking_entries.sort_by_key(|elem| {
+            (
+                journal_positions
+                    .get(&elem.finance_journal_diary_id)
+                    .cloned()
+                    .unwrap_or(usize::MAX),
+                elem.booking_type.to_int(),
+            )
+        });
+        for booking_entry in booking_entries.iter_mut() {
+            booking_entry.reconciliation_id = None;
+        }
+
+        let max_number_result = self
+            .db_connector
+            .finance_get_journal_max_number(&self.db_connection_settings, &self.user_id)
+            .await;
+        if max_number_result.is_err() {
+            return Err(max_number_result.unwrap_err());
+        }
+
+        let return_object = FinanceBackupArchive {
+            schema_version: FinanceBackupHandle::SCHEMA_VERSION,
+            created_at: Utc::now(),
+            account_types: account_types_result.unwrap(),
+            accounts,
+            journal_entries,
+            booking_entries,
+            booking_journal_max_number: max_number_result.unwrap(),
+        };
+        // an archive that can not be restored is not written
+        let verify_result = FinanceBackupHandle::verify_archive(&return_object);
+        if verify_result.is_err() {
+            return Err(format!(
+                "stored data is inconsistent: {}",
+                verify_result.unwrap_err()
+            ));
+        }
+        return Ok(return_object);
+    }
+
+    pub async fn export_json(&self) -> Result<String, String> {
+        let archive_result = self.create_archive().await;
+        if archive_result.is_err() {
+            return Err(archive_result.unwrap_err());
+        }
+        let json_result = serde_json::to_string_pretty(&archive_result.unwrap());
+        if json_result.is_err() {
+            return Err(format!(
+                "could not write archive: {}",
+                json_result.unwrap_err()
+            ));
+        }
+        return Ok(json_result.unwrap());
+    }
+
+    pub fn export_json_sync(&self) -> Result<String, String> {
+        let temp_var_0 = executor::block_on(self.export_json());
+        return temp_var_0;
+    }
+
+    /// reads an archive, the schema version is checked before the content
+    pub fn parse_json(json_text: &str) -> Result<FinanceBackupArchive, String> {
+        let value_result = serde_json::from_str::<serde_json::Value>(json_text);
+        if value_result.is_err() {
+            return Err(format!(
+                "file is not valid JSON: {}",
+                value_result.unwrap_err()
+            ));
+        }
+        let json_value = value_result.unwrap();
+        let schema_version_option = json_value
+            .get("schema_version")
+            .and_then(|elem| elem.as_u64());
+        if schema_version_option.is_none() {
+            return Err("file is not a backup archive, schema_version is missing".into());
+        }
+        if schema_version_option.unwrap() != FinanceBackupHandle::SCHEMA_VERSION as u64 {
+            return Err(format!(
+                "schema version {} is not supported, expected version {}",
+                schema_version_option.unwrap(),
+                FinanceBackupHandle::SCHEMA_VERSION
+            ));
+        }
+        let archive_result = serde_json::from_value::<FinanceBackupArchive>(json_value);
+        if archive_result.is_err() {
+            return Err(format!(
+                "could not read archive: {}",
+                archive_result.unwrap_err()
+            ));
+        }
+        return Ok(archive_result.unwrap());
+    }
+
+    fn check_unique_ids(ids: Vec<&Uuid>, object_name: &str) -> Result<(), String> {
+        let mut found_ids = HashSet::new();
+        for id in ids {
+            if !found_ids.insert(id) {
+                return Err(format!("{} {} is contained twice", object_name, id));
+            }
+        }
+        return Ok(());
+    }
+
+    fn verify_journal_entry_links(
+        journal_entry: &FinanceJournalEntry,
+        journal_entries: &HashMap<Uuid, &FinanceJournalEntry>,
+    ) -> Result<(), String> {
+        if journal_entry.reverse_of_journal_entry_id.is_some() {
+            let original_option =
+                journal_entries.get(&journal_entry.reverse_of_journal_entry_id.unwrap());
+            if original_option.is_none()
+                || original_option
+                    .unwrap()
+                    .reversed_by_journal_entry_id
+                    .ne(&Some(journal_entry.id))
+            {
+                return Err(format!(
+                    "journal entry {} is a reversal of a missing or not reversed entry",
+                    journal_entry.running_number
+                ));
+            }
+        }
+        if journal_entry.reversed_by_journal_entry_id.is_some() {
+            let reversal_option =
+                journal_entries.get(&journal_entry.reversed_by_journal_entry_id.unwrap());
+            if reversal_option.is_none()
+                || reversal_option
+                    .unwrap()
+                    .reverse_of_journal_entry_id
+                    .ne(&Some(journal_entry.id))
+            {
+                return Err(format!(
+                    "journal entry {} is reversed by a missing entry",
+                    journal_entry.running_number
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    /// checks the schema version and that all references inside the archive are valid
+    /// and every journal entry is balanced by its booking entries
+    pub fn verify_archive(archive: &FinanceBackupArchive) -> Result<(), String> {
+        if archive.schema_version != FinanceBackupHandle::SCHEMA_VERSION {
+            return Err(format!(
+                "schema version {} is not supported, expected version {}",
+                archive.schema_version,
+                FinanceBackupHandle::SCHEMA_VERSION
+            ));
+        }
+        let check_result = FinanceBackupHandle::check_unique_ids(
+            archive.account_types.iter().map(|elem| &elem.id).collect(),
+            "account type",
+        )
+        .and(FinanceBackupHandle::check_unique_ids(
+            archive.accounts.iter().map(|elem| &elem.id).collect(),
+            "account",
+        ))
+        .and(FinanceBackupHandle::check_unique_ids(
+            archive
+                .journal_entries
+                .iter()
+                .map(|elem| &elem.id)
+                .collect(),
+            "journal entry",
+        ))
+        .and(FinanceBackupHandle::check_unique_ids(
+            archive
+                .booking_entries
+                .iter()
+                .map(|elem| &elem.id)
+                .collect(),
+            "booking entry",
+        ));
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+
+        let mut account_numbers = HashSet::new();
+        for account in &archive.accounts {
+            if !archive
+                .account_types
+                .iter()
+                .any(|elem| elem.id.eq(&account.finance_account_type_id))
+            {
+                return Err(format!(
+                    "account type of account {} is missing",
+                    account.title
+                ));
+            }
+            if account.account_number.is_some()
+                && !account_numbers.insert(account.account_number.unwrap())
+            {
+                return Err(format!(
+                    "account number {} is used twice",
+                    account.account_number.unwrap()
+                ));
+            }
+        }
+        let account_ids: HashSet<Uuid> = archive.accounts.iter().map(|elem| elem.id).collect();
+
+        let mut running_numbers = HashSet::new();
+        let journal_entries: HashMap<Uuid, &FinanceJournalEntry> = archive
+            .journal_entries
+            .iter()
+            .map(|elem| (elem.id, elem))
+            .collect();
+        for journal_entry in &archive.journal_entries {
+            if journal_entry.running_number == 0
+                || journal_entry.running_number > archive.booking_journal_max_number
+                || !running_numbers.insert(journal_entry.running_number)
+            {
+                return Err(format!(
+                    "running number {} is invalid or used twice",
+                    journal_entry.running_number
+                ));
+            }
+            if !account_ids.contains(&journal_entry.debit_finance_account_id)
+                || !account_ids.contains(&journal_entry.credit_finance_account_id)
+            {
+                return Err(format!(
+                    "account of journal entry {} is missing",
+                    journal_entry.running_number
+                ));
+            }
+            let links_result =
+                FinanceBackupHandle::verify_journal_entry_links(journal_entry, &journal_entries);
+            if links_result.is_err() {
+                return Err(links_result.unwrap_err());
+            }
+        }
+
+        // debit and credit sums per journal entry
+        let mut booking_sums: HashMap<Uuid, (u64, u64)> = HashMap::new();
+        for booking_entry in &archive.booking_entries {
+            if !account_ids.contains(&booking_entry.finance_account_id) {
+                return Err(format!(
+                    "account of booking entry {} is missing",
+                    booking_entry.id
+                ));
+            }
+            let journal_entry_option = journal_entries.get(&booking_entry.finance_journal_diary_id);
+            if journal_entry_option.is_none() {
+                return Err(format!(
+                    "journal entry of booking entry {} is missing",
+                    booking_entry.id
+                ));
+            }
+            let journal_entry = journal_entry_option.unwrap();
+            let is_saldo_type = booking_entry.booking_type.eq(&BookingEntryType::SaldoDebit)
+                || booking_entry
+                    .booking_type
+                    .eq(&BookingEntryType::SaldoCredit);
+            if is_saldo_type != journal_entry.is_saldo
+                || booking_entry.booking_time.ne(&journal_entry.booking_time)
+            {
+                return Err(format!(
+                    "booking entry {} does not match journal entry {}",
+                    booking_entry.id, journal_entry.running_number
+                ));
+            }
+            // the amounts come from an uploaded file, an overflow is reported instead of wrapping around
+            let sums = booking_sums.entry(journal_entry.id).or_insert((0, 0));
+            let side_sum = match booking_entry.booking_type {
+                BookingEntryType::Debit | BookingEntryType::SaldoDebit => &mut sums.0,
+                BookingEntryType::Credit | BookingEntryType::SaldoCredit => &mut sums.1,
+            };
+            let add_result = side_sum.checked_add(booking_entry.amount);
+            if add_result.is_none() {
+                return Err(format!(
+                    "sum of the booking entries of journal entry {} is too large",
+                    journal_entry.running_number
+                ));
+            }
+            *side_sum = add_result.unwrap();
+        }
+        for journal_entry in &archive.journal_entries {
+            let sums = booking_sums.get(&journal_entry.id).unwrap_or(&(0, 0));
+            if sums.0 != journal_entry.amount || sums.1 != journal_entry.amount {
+                return Err(format!(
+                    "booking entries of journal entry {} do not balance its amount",
+                    journal_entry.running_number
+                ));
+            }
+        }
+        return Ok(());
+    }
+
+    /// writes the archive for the current user at once, the journal of the user has to be empty,
+    /// account types and accounts of the user with the same id are overwritten, ids of other users are rejected
+    pub async fn restore_archive(&self, archive: &FinanceBackupArchive) -> Result<usize, String> {
+        let verify_result = FinanceBackupHandle::verify_archive(archive);
+        if verify_result.is_err() {
+            return Err(verify_result.unwrap_err());
+        }
+
+        let existing_entries_result = self
+            .db_connector
+            .finance_journal_entry_list(
+                &self.db_connection_settings,
+                &self.user_id,
+                None,
+                None,
+                None,
+            )
+            .await;
+        if existing_entries_result.is_err() {
+            return Err(existing_entries_result.unwrap_err());
+        }
+        if !existing_entries_result.unwrap().is_empty() {
+            return Err(
+                "the journal already contains entries, a backup can only be restored into an empty journal"
+                    .into(),
+            );
+        }
+        let existing_accounts_result = self
+            .db_connector
+            .finance_account_list(&self.db_connection_settings, &self.user_id, None)
+            .await;
+        if existing_accounts_result.is_err() {
+            return Err(existing_accounts_result.unwrap_err());
+        }
+        for existing_account in existing_accounts_result.unwrap() {
+            let same_number_option = archive.accounts.iter().find(|elem| {
+                elem.id.ne(&existing_account.id)
+                    && elem.account_number.is_some()
+                    && elem.account_number.eq(&existing_account.account_number)
+            });
+            if same_number_option.is_some() {
+                return Err(format!(
+                    "account number {} of account {} is already used by account {}",
+                    existing_account.account_number.unwrap(),
+                    same_number_option.unwrap().title,
+                    existing_account.title
+                ));
+            }
+        }
+
+        let restore_result = self
+            .db_connector
+            .finance_restore_archive(&self.db_connection_settings, &self.user_id, archive)
+            .await;
+        if restore_result.is_err() {
+            return Err(restore_result.unwrap_err());
+        }
+        return Ok(archive.journal_entries.len());
+    }
+
+    pub async fn import_json(&self, json_text: &str) -> Result<usize, String> {
+        let archive_result = FinanceBackupHandle::parse_json(json_text);
+        if archive_result.is_err() {
+            return Err(archive_result.unwrap_err());
+        }
+        return self.restore_archive(&archive_result.unwrap()).await;
+    }
+
+    pub fn import_json_sync(&self, json_text: &str) -> Result<usize, String> {
+        let temp_var_0 = executor::block_on(self.import_json(json_text));
+        return temp_var_0;
+    }
+}
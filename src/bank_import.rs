@@ -0,0 +1,1612 @@
+use std::collections::HashMap;
+
+use async_session::chrono::{DateTime, Datelike, NaiveDate, TimeZone, Utc};
+use encoding_rs::Encoding;
+use futures::executor;
+use mongodb::bson::Uuid;
+use quick_xml::{events::Event, Reader};
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    accounting_logic::FinanceBookingHandle,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{
+        AccountBalanceType, AmountSignConvention, BankImportPreviewRow, BankImportRowResult,
+        BankStatement, BankStatementBalanceCheck, BankStatementTransaction, CsvImportProfile,
+        FinanceBookingRequest, FinanceJournalEntry,
+    },
+};
+
+/// texts of a CAMT entry (Ntry) collected while reading the XML
+#[derive(Default)]
+struct CamtEntryValues {
+    amount: String,
+    credit_debit_indicator: String,
+    status: String,
+    booking_date: String,
+    value_date: String,
+    remittance_information: Vec<String>,
+    debtor_name: String,
+    creditor_name: String,
+    additional_information: String,
+}
+
+impl CamtEntryValues {
+    /// element_path is relative to the Ntry element, e.g. NtryDtls/TxDtls/RmtInf/Ustrd
+    fn set_value(&mut self, element_path: &str, value: String) {
+        match element_path {
+            "Amt" => self.amount = value,
+            "CdtDbtInd" => self.credit_debit_indicator = value,
+            // Sts/Cd since version 8 of camt.053
+            "Sts" | "Sts/Cd" => self.status = value,
+            "BookgDt/Dt" | "BookgDt/DtTm" => self.booking_date = value,
+            "ValDt/Dt" | "ValDt/DtTm" => self.value_date = value,
+            "AddtlNtryInf" => self.additional_information = value,
+            _ => {
+                if element_path.ends_with("RmtInf/Ustrd")
+                    || element_path.ends_with("RmtInf/Strd/CdtrRefInf/Ref")
+                {
+                    self.remittance_information.push(value);
+                } else if element_path.ends_with("RltdPties/Dbtr/Nm")
+                    || element_path.ends_with("RltdPties/Dbtr/Pty/Nm")
+                {
+                    self.debtor_name = value;
+                } else if element_path.ends_with("RltdPties/Cdtr/Nm")
+                    || element_path.ends_with("RltdPties/Cdtr/Pty/Nm")
+                {
+                    self.creditor_name = value;
+                }
+            }
+        }
+    }
+}
+
+/// texts of a CAMT balance (Bal) collected while reading the XML
+#[derive(Default)]
+struct CamtBalanceValues {
+    code: String,
+    amount: String,
+    currency: String,
+    credit_debit_indicator: String,
+    date: String,
+}
+
+impl CamtBalanceValues {
+    /// element_path is relative to the Bal element
+    fn set_value(&mut self, element_path: &str, value: String) {
+        match element_path {
+            "Tp/CdOrPrtry/Cd" => self.code = value,
+            "Amt" => self.amount = value,
+            "CdtDbtInd" => self.credit_debit_indicator = value,
+            "Dt/Dt" | "Dt/DtTm" => self.date = value,
+            _ => {}
+        }
+    }
+}
+
+pub struct FinanceBankImportHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceAccountingFunctions,
+}
+
+impl<'a> FinanceBankImportHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceAccountingFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    /// converts the file content to text, a byte order mark overrides the given encoding
+    pub fn decode_statement_file(content: &[u8], encoding_label: &str) -> Result<String, String> {
+        let encoding_option = Encoding::for_label(encoding_label.trim().as_bytes());
+        if encoding_option.is_none() {
+            return Err(format!("encoding {} is not supported", encoding_label));
+        }
+        let (decoded_text, used_encoding, had_errors) = encoding_option.unwrap().decode(content);
+        if had_errors {
+            return Err(format!(
+                "file contains characters that are not valid {}",
+                used_encoding.name()
+            ));
+        }
+        return Ok(decoded_text.into_owned());
+    }
+
+    /// splits CSV text into records, fields may be quoted with " (a quote inside is doubled)
+    /// and may then contain the delimiter or line breaks, empty lines are returned as empty records
+    pub fn parse_csv_records(text: &str, delimiter: char) -> Result<Vec<Vec<String>>, String> {
+        let mut records: Vec<Vec<String>> = Vec::new();
+        let mut current_record: Vec<String> = Vec::new();
+        let mut current_field = String::new();
+        let mut in_quotes = false;
+        let mut field_was_quoted = false;
+        let mut chars = text.chars().peekable();
+        while let Some(current_char) = chars.next() {
+            if in_quotes {
+                if current_char == '"' {
+                    if chars.peek() == Some(&'"') {
+                        current_field.push('"');
+                        chars.next();
+                    } else {
+                        in_quotes = false;
+                    }
+                } else {
+                    current_field.push(current_char);
+                }
+                continue;
+            }
+            if current_char == '"' && current_field.trim().is_empty() && !field_was_quoted {
+                current_field.clear();
+                in_quotes = true;
+                field_was_quoted = true;
+            } else if current_char == delimiter {
+                current_record.push(current_field.clone());
+                current_field.clear();
+                field_was_quoted = false;
+            } else if current_char == '\r' || current_char == '\n' {
+                if current_char == '\r' && chars.peek() == Some(&'\n') {
+                    chars.next();
+                }
+                if !current_record.is_empty() || !current_field.is_empty() || field_was_quoted {
+                    current_record.push(current_field.clone());
+                }
+                records.push(current_record.clone());
+                current_record.clear();
+                current_field.clear();
+                field_was_quoted = false;
+            } else if !field_was_quoted {
+                current_field.push(current_char);
+            }
+        }
+        if in_quotes {
+            return Err(format!(
+                "line {}: quoted field is not closed",
+                records.len() + 1
+            ));
+        }
+        if !current_record.is_empty() || !current_field.is_empty() || field_was_quoted {
+            current_record.push(current_field);
+            records.push(current_record);
+        }
+        return Ok(records);
+    }
+
+    /// amount in cents, thousands separators are ignored, the sign may be given in front or at the end
+    pub fn parse_amount(amount_text: &str, decimal_separator: char) -> Result<i64, String> {
+        let mut cleaned_text: String = amount_text
+            .chars()
+            .filter(|elem| !elem.is_whitespace() && !elem.eq(&'\''))
+            .filter(|elem| {
+                // the separator that is not the decimal separator is a thousands separator
+                !(decimal_separator == ',' && elem.eq(&'.'))
+                    && !(decimal_separator == '.' && elem.eq(&','))
+            })
+            .collect();
+        let mut is_negative = false;
+        if cleaned_text.ends_with('-') {
+            is_negative = true;
+            cleaned_text.pop();
+        } else if cleaned_text.ends_with('+') {
+            cleaned_text.pop();
+        }
+        if cleaned_text.starts_with('-') {
+            if is_negative {
+                return Err(format!("amount {} has two signs", amount_text));
+            }
+            is_negative = true;
+            cleaned_text.remove(0);
+        } else if cleaned_text.starts_with('+') {
+            cleaned_text.remove(0);
+        }
+
+        let (integer_part, fraction_part) = match cleaned_text.split_once(decimal_separator) {
+            Some((integer_text, fraction_text)) => (integer_text, fraction_text),
+            None => (cleaned_text.as_str(), ""),
+        };
+        if (integer_part.is_empty() && fraction_part.is_empty())
+            || !integer_part.chars().all(|elem| elem.is_ascii_digit())
+            || !fraction_part.chars().all(|elem| elem.is_ascii_digit())
+        {
+            return Err(format!("amount {} is not a number", amount_text));
+        }
+        if fraction_part.len() > 2 {
+            return Err(format!("amount {} has more than two decimals", amount_text));
+        }
+
+        let integer_value = if integer_part.is_empty() {
+            Ok(0)
+        } else {
+            integer_part.parse::<i64>()
+        };
+        let fraction_value = format!("{:0<2}", fraction_part).parse::<i64>();
+        if integer_value.is_err() || fraction_value.is_err() {
+            return Err(format!("amount {} is too large", amount_text));
+        }
+        let cents_result = integer_value
+            .unwrap()
+            .checked_mul(100)
+            .and_then(|elem| elem.checked_add(fraction_value.unwrap()));
+        if cents_result.is_none() {
+            return Err(format!("amount {} is too large", amount_text));
+        }
+        let cents = cents_result.unwrap();
+
+        return Ok(if is_negative { -cents } else { cents });
+    }
+
+    fn get_column_value(
+        record: &Vec<String>,
+        column_number: usize,
+        column_description: &str,
+    ) -> Result<String, String> {
+        if column_number == 0 || column_number > record.len() {
+            return Err(format!(
+                "{} column {} does not exist, the row has {} columns",
+                column_description,
+                column_number,
+                record.len()
+            ));
+        }
+        return Ok(record[column_number - 1].trim().to_string());
+    }
+
+    fn read_csv_transaction(
+        record: &Vec<String>,
+        profile: &CsvImportProfile,
+        row_number: usize,
+    ) -> Result<BankStatementTransaction, String> {
+        let date_text =
+            FinanceBankImportHandle::get_column_value(record, profile.date_column, "date")?;
+        let date_parse_result = NaiveDate::parse_from_str(&date_text, &profile.date_format);
+        if date_parse_result.is_err() {
+            return Err(format!(
+                "date {} does not match format {}: {}",
+                date_text,
+                profile.date_format,
+                date_parse_result.unwrap_err()
+            ));
+        }
+        let booking_time = FinanceBankImportHandle::get_statement_time(date_parse_result.unwrap());
+
+        let amount_text =
+            FinanceBankImportHandle::get_column_value(record, profile.amount_column, "amount")?;
+        let mut amount =
+            FinanceBankImportHandle::parse_amount(&amount_text, profile.decimal_separator)?;
+        if profile
+            .sign_convention
+            .eq(&AmountSignConvention::PositiveIsOutgoing)
+        {
+            amount = -amount;
+        }
+
+        let mut title = String::new();
+        if profile.title_column > 0 {
+            title =
+                FinanceBankImportHandle::get_column_value(record, profile.title_column, "title")?;
+        }
+        if title.is_empty() {
+            title = format!("Bank import row {}", row_number);
+        }
+        let mut description = String::new();
+        if profile.description_column > 0 {
+            description = FinanceBankImportHandle::get_column_value(
+                record,
+                profile.description_column,
+                "description",
+            )?;
+        }
+
+        return Ok(BankStatementTransaction {
+            booking_time,
+            amount,
+            title,
+            description,
+            import_reference: None,
+        });
+    }
+
+    /// booking of a bank statement transaction: received money is booked on the debit side of
+    /// the bank account (against the incoming counter account), paid money on its credit side
+    pub fn create_booking_request(
+        transaction: &BankStatementTransaction,
+        bank_account_id: &Uuid,
+        incoming_account_id: &Uuid,
+        outgoing_account_id: &Uuid,
+    ) -> FinanceBookingRequest {
+        let (debit_account_id, credit_account_id) = if transaction.amount >= 0 {
+            (bank_account_id.clone(), incoming_account_id.clone())
+        } else {
+            (outgoing_account_id.clone(), bank_account_id.clone())
+        };
+        return FinanceBookingRequest {
+            is_simple_entry: true,
+            is_saldo: false,
+            debit_finance_account_id: debit_account_id,
+            credit_finance_account_id: credit_account_id,
+            booking_time: transaction.booking_time,
+            amount: transaction.amount.unsigned_abs(),
+            title: transaction.title.clone(),
+            description: transaction.description.clone(),
+            tags: Vec::new(),
+            import_reference: transaction.import_reference.clone(),
+        };
+    }
+
+    /// reads a CSV bank statement and proposes a booking entry per data row, rows that can not be
+    /// read are returned with an error, empty lines are skipped
+    pub fn preview_csv_import(
+        content: &[u8],
+        profile: &CsvImportProfile,
+    ) -> Result<Vec<BankImportPreviewRow>, String> {
+        if profile.date_column == 0 || profile.amount_column == 0 {
+            return Err("date and amount column are required".into());
+        }
+        if profile.delimiter == profile.decimal_separator {
+            return Err("delimiter and decimal separator must be different".into());
+        }
+        let text_result =
+            FinanceBankImportHandle::decode_statement_file(content, &profile.encoding);
+        if text_result.is_err() {
+            return Err(text_result.unwrap_err());
+        }
+        let records_result =
+            FinanceBankImportHandle::parse_csv_records(&text_result.unwrap(), profile.delimiter);
+        if records_result.is_err() {
+            return Err(records_result.unwrap_err());
+        }
+
+        let mut preview_rows = Vec::new();
+        for (record_index, record) in records_result.unwrap().iter().enumerate() {
+            let row_number = record_index + 1;
+            if row_number <= profile.skip_rows || record.iter().all(|elem| elem.trim().is_empty()) {
+                continue;
+            }
+            let transaction_result =
+                FinanceBankImportHandle::read_csv_transaction(record, profile, row_number);
+            if transaction_result.is_err() {
+                preview_rows.push(BankImportPreviewRow {
+                    row_number,
+                    booking_request: None,
+                    error: transaction_result.unwrap_err(),
+                    duplicate_journal_entry_id: None,
+                });
+                continue;
+            }
+            let transaction = transaction_result.unwrap();
+            if transaction.amount == 0 {
+                preview_rows.push(BankImportPreviewRow {
+                    row_number,
+                    booking_request: None,
+                    error: "amount is zero".into(),
+                    duplicate_journal_entry_id: None,
+                });
+                continue;
+            }
+            preview_rows.push(BankImportPreviewRow {
+                row_number,
+                booking_request: Some(FinanceBankImportHandle::create_booking_request(
+                    &transaction,
+                    &profile.bank_account_id,
+                    &profile.default_incoming_account_id,
+                    &profile.default_outgoing_account_id,
+                )),
+                error: "".into(),
+                duplicate_journal_entry_id: None,
+            });
+        }
+        return Ok(preview_rows);
+    }
+
+    /// splits MT940 text into field tags and values, lines without a tag continue the previous
+    /// field, SWIFT header blocks and end of message marks are skipped
+    fn split_mt940_fields(text: &str) -> Vec<(String, String)> {
+        let mut fields: Vec<(String, String)> = Vec::new();
+        for line in text.lines() {
+            let line = line.trim_end();
+            if line.is_empty() || line.starts_with('{') || line.eq("-") || line.eq("-}") {
+                continue;
+            }
+            let tag_end_option = line
+                .strip_prefix(':')
+                .and_then(|elem| elem.find(':'))
+                .map(|elem| elem + 1);
+            match tag_end_option {
+                Some(tag_end)
+                    if (3..=4).contains(&tag_end)
+                        && line[1..tag_end]
+                            .chars()
+                            .all(|elem| elem.is_ascii_alphanumeric()) =>
+                {
+                    fields.push((
+                        line[1..tag_end].to_string(),
+                        line[tag_end + 1..].to_string(),
+                    ));
+                }
+                _ => {
+                    if let Some(last_field) = fields.last_mut() {
+                        last_field.1.push('\n');
+                        last_field.1.push_str(line);
+                    }
+                }
+            }
+        }
+        return fields;
+    }
+
+    fn parse_mt940_date(date_text: &str) -> Result<NaiveDate, String> {
+        let date_parse_result = NaiveDate::parse_from_str(date_text, "%y%m%d");
+        if date_parse_result.is_err() {
+            return Err(format!(
+                "date {} is not valid: {}",
+                date_text,
+                date_parse_result.unwrap_err()
+            ));
+        }
+        return Ok(date_parse_result.unwrap());
+    }
+
+    fn get_statement_time(booking_date: NaiveDate) -> DateTime<Utc> {
+        // same as a booking entry with a date but without time
+        return Utc.from_utc_datetime(&booking_date.and_hms_opt(12, 0, 0).unwrap());
+    }
+
+    /// balance fields :60F:, :60M:, :62F: and :62M:, e.g. C200131EUR1234,56
+    fn parse_mt940_balance(balance_text: &str) -> Result<(DateTime<Utc>, String, i64), String> {
+        let balance_text = balance_text.trim();
+        if balance_text.len() < 11 || !balance_text.is_ascii() {
+            return Err(format!("balance {} is not valid", balance_text));
+        }
+        let is_debit = match &balance_text[0..1] {
+            "C" => false,
+            "D" => true,
+            _ => return Err(format!("balance {} has no debit/credit mark", balance_text)),
+        };
+        let balance_date = FinanceBankImportHandle::parse_mt940_date(&balance_text[1..7])?;
+        let currency = balance_text[7..10].to_string();
+        let amount = FinanceBankImportHandle::parse_amount(&balance_text[10..], ',')?;
+        return Ok((
+            FinanceBankImportHandle::get_statement_time(balance_date),
+            currency,
+            if is_debit { -amount } else { amount },
+        ));
+    }
+
+    /// statement line :61:, e.g. 2001310131DR45,99NDDTNONREF, the entry date (MMDD) is used
+    /// as booking date when it is given, otherwise the value date
+    fn parse_mt940_statement_line(
+        statement_line_text: &str,
+    ) -> Result<(DateTime<Utc>, i64, String), String> {
+        let (first_line, supplementary_details) = match statement_line_text.split_once('\n') {
+            Some((first_line_text, details_text)) => (first_line_text.trim(), details_text.trim()),
+            None => (statement_line_text.trim(), ""),
+        };
+        if first_line.len() < 8 || !first_line.is_ascii() {
+            return Err(format!("statement line {} is not valid", first_line));
+        }
+        let value_date = FinanceBankImportHandle::parse_mt940_date(&first_line[0..6])?;
+        let mut remaining_text = &first_line[6..];
+
+        let mut booking_date = value_date;
+        if remaining_text.len() >= 4
+            && remaining_text[0..4]
+                .chars()
+                .all(|elem| elem.is_ascii_digit())
+        {
+            let entry_month = remaining_text[0..2].parse::<u32>().unwrap();
+            let entry_day = remaining_text[2..4].parse::<u32>().unwrap();
+            // the entry date may be in the year before or after the value date
+            let mut entry_year = value_date.year();
+            if entry_month == 12 && value_date.month() == 1 {
+                entry_year -= 1;
+            } else if entry_month == 1 && value_date.month() == 12 {
+                entry_year += 1;
+            }
+            let entry_date_option = NaiveDate::from_ymd_opt(entry_year, entry_month, entry_day);
+            if entry_date_option.is_none() {
+                return Err(format!(
+                    "entry date {} of statement line {} is not valid",
+                    &remaining_text[0..4],
+                    first_line
+                ));
+            }
+            booking_date = entry_date_option.unwrap();
+            remaining_text = &remaining_text[4..];
+        }
+
+        // RC and RD are reversals of a credit or debit
+        let is_incoming;
+        if remaining_text.starts_with("RC") {
+            is_incoming = false;
+            remaining_text = &remaining_text[2..];
+        } else if remaining_text.starts_with("RD") {
+            is_incoming = true;
+            remaining_text = &remaining_text[2..];
+        } else if remaining_text.starts_with('C') {
+            is_incoming = true;
+            remaining_text = &remaining_text[1..];
+        } else if remaining_text.starts_with('D') {
+            is_incoming = false;
+            remaining_text = &remaining_text[1..];
+        } else {
+            return Err(format!(
+                "statement line {} has no debit/credit mark",
+                first_line
+            ));
+        }
+        // optional funds code, the third letter of the currency
+        if remaining_text.starts_with(|elem: char| elem.is_ascii_alphabetic()) {
+            remaining_text = &remaining_text[1..];
+        }
+        let amount_length = remaining_text
+            .find(|elem: char| !elem.is_ascii_digit() && elem != ',')
+            .unwrap_or(remaining_text.len());
+        let amount = FinanceBankImportHandle::parse_amount(&remaining_text[0..amount_length], ',')?;
+
+        return Ok((
+            FinanceBankImportHandle::get_statement_time(booking_date),
+            if is_incoming { amount } else { -amount },
+            supplementary_details.replace('\n', " "),
+        ));
+    }
+
+    /// information field :86:, structured information (e.g. 166?00GUTSCHRIFT?20...?32NAME) is
+    /// split into the name of the counterparty as title and the purpose as description
+    fn parse_mt940_information(information_text: &str) -> (String, String) {
+        let is_structured = information_text.len() > 3
+            && information_text.is_char_boundary(3)
+            && information_text[0..3]
+                .chars()
+                .all(|elem| elem.is_ascii_digit())
+            && information_text[3..].starts_with('?');
+        if !is_structured {
+            let text = information_text
+                .lines()
+                .map(|elem| elem.trim())
+                .collect::<Vec<&str>>()
+                .join(" ");
+            return (text, "".into());
+        }
+
+        // line breaks may be anywhere inside a subfield
+        let joined_text: String = information_text.lines().collect();
+        let mut booking_text = String::new();
+        let mut purpose = String::new();
+        let mut counterparty_name = String::new();
+        for subfield in joined_text.split('?').skip(1) {
+            if subfield.len() < 2 || !subfield.is_char_boundary(2) {
+                continue;
+            }
+            let subfield_value = &subfield[2..];
+            match &subfield[0..2] {
+                "00" => booking_text.push_str(subfield_value),
+                "20" | "21" | "22" | "23" | "24" | "25" | "26" | "27" | "28" | "29" | "60"
+                | "61" | "62" | "63" => purpose.push_str(subfield_value),
+                "32" | "33" => counterparty_name.push_str(subfield_value),
+                _ => {}
+            }
+        }
+        let title = if counterparty_name.trim().is_empty() {
+            booking_text
+        } else {
+            counterparty_name
+        };
+        return (title.trim().to_string(), purpose.trim().to_string());
+    }
+
+    /// the balances of every statement have to match its transactions and the opening balance
+    /// has to continue the closing balance of the statement before
+    fn check_statement_balance_fields(statements: &Vec<BankStatement>) -> Result<(), String> {
+        let mut previous_closing_balance: Option<i64> = None;
+        for statement in statements {
+            if statement.currency.is_empty() {
+                return Err(format!(
+                    "statement {}: opening balance is missing",
+                    statement.reference
+                ));
+            }
+            let transaction_sum: i64 = statement.transactions.iter().map(|elem| elem.amount).sum();
+            if statement.opening_balance + transaction_sum != statement.closing_balance {
+                return Err(format!(
+                    "statement {}: opening balance {:.2} plus transactions {:.2} does not match closing balance {:.2}",
+                    statement.reference,
+                    (statement.opening_balance as f64) / (100 as f64),
+                    (transaction_sum as f64) / (100 as f64),
+                    (statement.closing_balance as f64) / (100 as f64)
+                ));
+            }
+            if previous_closing_balance.is_some()
+                && previous_closing_balance.unwrap() != statement.opening_balance
+            {
+                return Err(format!(
+                    "statement {}: opening balance does not match closing balance of the statement before",
+                    statement.reference
+                ));
+            }
+            previous_closing_balance = Some(statement.closing_balance);
+        }
+        return Ok(());
+    }
+
+    /// proposes a booking entry per transaction, the rows are numbered starting with 1
+    fn get_transaction_preview_rows(
+        transactions: &Vec<BankStatementTransaction>,
+        bank_account_id: &Uuid,
+        incoming_account_id: &Uuid,
+        outgoing_account_id: &Uuid,
+    ) -> Vec<BankImportPreviewRow> {
+        let mut preview_rows = Vec::new();
+        for transaction in transactions {
+            let row_number = preview_rows.len() + 1;
+            if transaction.amount == 0 {
+                preview_rows.push(BankImportPreviewRow {
+                    row_number,
+                    booking_request: None,
+                    error: "amount is zero".into(),
+                    duplicate_journal_entry_id: None,
+                });
+                continue;
+            }
+            let mut booking_request = FinanceBankImportHandle::create_booking_request(
+                transaction,
+                bank_account_id,
+                incoming_account_id,
+                outgoing_account_id,
+            );
+            if booking_request.title.is_empty() {
+                booking_request.title = format!("Bank import row {}", row_number);
+            }
+            preview_rows.push(BankImportPreviewRow {
+                row_number,
+                booking_request: Some(booking_request),
+                error: "".into(),
+                duplicate_journal_entry_id: None,
+            });
+        }
+        return preview_rows;
+    }
+
+    /// reads the statements of a MT940 file, the balances of every statement have to match its
+    /// transactions
+    pub fn parse_mt940(text: &str) -> Result<Vec<BankStatement>, String> {
+        let mut statements: Vec<BankStatement> = Vec::new();
+        let mut opening_balance_option: Option<(DateTime<Utc>, String, i64)> = None;
+        for (field_tag, field_value) in FinanceBankImportHandle::split_mt940_fields(text) {
+            if field_tag.eq("20") {
+                opening_balance_option = None;
+                statements.push(BankStatement {
+                    reference: field_value.trim().to_string(),
+                    account_identification: "".into(),
+                    statement_number: "".into(),
+                    currency: "".into(),
+                    opening_balance_time: Utc::now(),
+                    opening_balance: 0,
+                    closing_balance_time: Utc::now(),
+                    closing_balance: 0,
+                    transactions: Vec::new(),
+                });
+                continue;
+            }
+            let current_statement_option = statements.last_mut();
+            if current_statement_option.is_none() {
+                return Err(format!(
+                    "field :{}: found before the first statement (:20:)",
+                    field_tag
+                ));
+            }
+            let current_statement = current_statement_option.unwrap();
+            let statement_reference = current_statement.reference.clone();
+            let add_reference =
+                |error_text: String| format!("statement {}: {}", statement_reference, error_text);
+            match field_tag.as_str() {
+                "25" => current_statement.account_identification = field_value.trim().to_string(),
+                "28" | "28C" => current_statement.statement_number = field_value.trim().to_string(),
+                "60F" | "60M" => {
+                    let balance = FinanceBankImportHandle::parse_mt940_balance(&field_value)
+                        .map_err(add_reference)?;
+                    current_statement.opening_balance_time = balance.0;
+                    current_statement.currency = balance.1.clone();
+                    current_statement.opening_balance = balance.2;
+                    opening_balance_option = Some(balance);
+                }
+                "61" => {
+                    if opening_balance_option.is_none() {
+                        return Err(add_reference(
+                            "statement line before opening balance".into(),
+                        ));
+                    }
+                    let (booking_time, amount, supplementary_details) =
+                        FinanceBankImportHandle::parse_mt940_statement_line(&field_value)
+                            .map_err(add_reference)?;
+                    current_statement
+                        .transactions
+                        .push(BankStatementTransaction {
+                            booking_time,
+                            amount,
+                            title: "".into(),
+                            description: supplementary_details,
+                            import_reference: None,
+                        });
+                }
+                "86" => {
+                    // information of the statement itself is not needed
+                    if let Some(transaction) = current_statement.transactions.last_mut() {
+                        if transaction.title.is_empty() {
+                            let (title, purpose) =
+                                FinanceBankImportHandle::parse_mt940_information(&field_value);
+                            transaction.title = title;
+                            if !purpose.is_empty() {
+                                transaction.description = purpose;
+                            }
+                        }
+                    }
+                }
+                "62F" | "62M" => {
+                    if opening_balance_option.is_none() {
+                        return Err(add_reference(
+                            "closing balance without opening balance".into(),
+                        ));
+                    }
+                    let (closing_time, closing_currency, closing_amount) =
+                        FinanceBankImportHandle::parse_mt940_balance(&field_value)
+                            .map_err(add_reference)?;
+                    if !closing_currency.eq(&current_statement.currency) {
+                        return Err(add_reference(format!(
+                            "currency of closing balance {} differs from opening balance {}",
+                            closing_currency, current_statement.currency
+                        )));
+                    }
+                    current_statement.closing_balance_time = closing_time;
+                    current_statement.closing_balance = closing_amount;
+                }
+                _ => {}
+            }
+        }
+
+        let check_result = FinanceBankImportHandle::check_statement_balance_fields(&statements);
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+        return Ok(statements);
+    }
+
+    /// reads a MT940 file and proposes a booking entry per statement line, the rows are numbered
+    /// over all statements of the file
+    pub fn preview_mt940_import(
+        content: &[u8],
+        encoding_label: &str,
+        bank_account_id: &Uuid,
+        incoming_account_id: &Uuid,
+        outgoing_account_id: &Uuid,
+    ) -> Result<(Vec<BankStatement>, Vec<BankImportPreviewRow>), String> {
+        let text_result = FinanceBankImportHandle::decode_statement_file(content, encoding_label);
+        if text_result.is_err() {
+            return Err(text_result.unwrap_err());
+        }
+        let statements_result = FinanceBankImportHandle::parse_mt940(&text_result.unwrap());
+        if statements_result.is_err() {
+            return Err(statements_result.unwrap_err());
+        }
+        let statements = statements_result.unwrap();
+
+        // the rows are numbered over all statements of the file
+        let preview_rows = FinanceBankImportHandle::get_transaction_preview_rows(
+            &statements
+                .iter()
+                .flat_map(|elem| elem.transactions.clone())
+                .collect(),
+            bank_account_id,
+            incoming_account_id,
+            outgoing_account_id,
+        );
+        return Ok((statements, preview_rows));
+    }
+
+    /// compares the opening and closing balance of the statements with the current balance of the bank account
+    pub async fn check_statement_balances(
+        &self,
+        bank_account_id: &Uuid,
+        statements: &Vec<BankStatement>,
+    ) -> Result<BankStatementBalanceCheck, String> {
+        if statements.is_empty() {
+            return Err("no statements to check".into());
+        }
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let balance_info_result = booking_handle
+            .calculate_balance_info(&vec![bank_account_id.clone()])
+            .await;
+        if balance_info_result.is_err() {
+            return Err(balance_info_result.unwrap_err());
+        }
+        let book_balance = balance_info_result
+            .unwrap()
+            .iter()
+            .find(|elem| elem.account_id.eq(bank_account_id))
+            .map(|elem| {
+                if elem.balance_type.eq(&AccountBalanceType::Debit) {
+                    elem.amount as i128
+                } else {
+                    -(elem.amount as i128)
+                }
+            })
+            .unwrap_or(0);
+
+        return Ok(BankStatementBalanceCheck {
+            book_balance,
+            statement_opening_balance: statements.first().unwrap().opening_balance as i128,
+            statement_closing_balance: statements.last().unwrap().closing_balance as i128,
+        });
+    }
+
+    pub fn check_statement_balances_sync(
+        &self,
+        bank_account_id: &Uuid,
+        statements: &Vec<BankStatement>,
+    ) -> Result<BankStatementBalanceCheck, String> {
+        let temp_var_0 =
+            executor::block_on(self.check_statement_balances(bank_account_id, statements));
+        return temp_var_0;
+    }
+
+    /// date of a CAMT date (2020-01-02) or date time (2020-01-02T10:15:00+01:00) element
+    fn parse_camt_date(date_text: &str) -> Result<NaiveDate, String> {
+        let date_part = date_text.trim().get(0..10).unwrap_or(date_text);
+        let date_parse_result = NaiveDate::parse_from_str(date_part, "%Y-%m-%d");
+        if date_parse_result.is_err() {
+            return Err(format!(
+                "date {} is not valid: {}",
+                date_text,
+                date_parse_result.unwrap_err()
+            ));
+        }
+        return Ok(date_parse_result.unwrap());
+    }
+
+    /// amount in cents, negative when the indicator is DBIT
+    fn get_camt_signed_amount(amount_text: &str, indicator_text: &str) -> Result<i64, String> {
+        let amount = FinanceBankImportHandle::parse_amount(amount_text, '.')?;
+        return match indicator_text {
+            "CRDT" => Ok(amount),
+            "DBIT" => Ok(-amount),
+            _ => Err(format!(
+                "credit/debit indicator {} is not valid",
+                indicator_text
+            )),
+        };
+    }
+
+    fn get_camt_transaction(
+        entry_values: &CamtEntryValues,
+    ) -> Result<BankStatementTransaction, String> {
+        let amount = FinanceBankImportHandle::get_camt_signed_amount(
+            &entry_values.amount,
+            &entry_values.credit_debit_indicator,
+        )?;
+        let booking_date_text = if entry_values.booking_date.is_empty() {
+            &entry_values.value_date
+        } else {
+            &entry_values.booking_date
+        };
+        let booking_date = FinanceBankImportHandle::parse_camt_date(booking_date_text)?;
+
+        // the counterparty of received money is the debtor, of paid money the creditor
+        let counterparty_name = if amount >= 0 {
+            &entry_values.debtor_name
+        } else {
+            &entry_values.creditor_name
+        };
+        let mut title = counterparty_name.trim().to_string();
+        let mut description = entry_values.remittance_information.join(" ");
+        if title.is_empty() {
+            title = entry_values.additional_information.trim().to_string();
+        } else if description.is_empty() {
+            description = entry_values.additional_information.trim().to_string();
+        }
+        return Ok(BankStatementTransaction {
+            booking_time: FinanceBankImportHandle::get_statement_time(booking_date),
+            amount,
+            title,
+            description,
+            import_reference: None,
+        });
+    }
+
+    /// reads the statements (Stmt) of a CAMT.053 file, only booked entries (Ntry) are used,
+    /// the opening (OPBD or PRCD) and closing (CLBD) balances have to match the entries
+    pub fn parse_camt053(text: &str) -> Result<Vec<BankStatement>, String> {
+        let mut reader = Reader::from_str(text);
+        reader.config_mut().trim_text(true);
+
+        let mut statements: Vec<BankStatement> = Vec::new();
+        let mut element_path: Vec<String> = Vec::new();
+        let mut current_statement: Option<BankStatement> = None;
+        let mut current_entry: Option<CamtEntryValues> = None;
+        let mut current_balance: Option<CamtBalanceValues> = None;
+        let mut has_opening_balance = false;
+        let mut has_closing_balance = false;
+        loop {
+            let event_result = reader.read_event();
+            if event_result.is_err() {
+                return Err(format!(
+                    "XML error at position {}: {}",
+                    reader.buffer_position(),
+                    event_result.unwrap_err()
+                ));
+            }
+            match event_result.unwrap() {
+                Event::Start(element) => {
+                    let element_name =
+                        String::from_utf8_lossy(element.local_name().as_ref()).to_string();
+                    match element_name.as_str() {
+                        "Stmt" => {
+                            has_opening_balance = false;
+                            has_closing_balance = false;
+                            current_statement = Some(BankStatement {
+                                reference: "".into(),
+                                account_identification: "".into(),
+                                statement_number: "".into(),
+                                currency: "".into(),
+                                opening_balance_time: Utc::now(),
+                                opening_balance: 0,
+                                closing_balance_time: Utc::now(),
+                                closing_balance: 0,
+                                transactions: Vec::new(),
+                            });
+                        }
+                        "Ntry" if current_statement.is_some() => {
+                            current_entry = Some(CamtEntryValues::default());
+                        }
+                        "Bal" if current_statement.is_some() => {
+                            current_balance = Some(CamtBalanceValues::default());
+                        }
+                        // the currency is only given as attribute of the amount
+                        "Amt"
+                            if current_balance.is_some()
+                                && element_path.last().is_some_and(|elem| elem.eq("Bal")) =>
+                        {
+                            if let Ok(Some(currency_attribute)) = element.try_get_attribute("Ccy") {
+                                current_balance.as_mut().unwrap().currency = currency_attribute
+                                    .unescape_value()
+                                    .unwrap_or_default()
+                                    .to_string();
+                            }
+                        }
+                        _ => {}
+                    }
+                    element_path.push(element_name);
+                }
+                Event::End(_) => {
+                    let element_name = element_path.pop().unwrap_or_default();
+                    if element_name.eq("Ntry") && current_entry.is_some() {
+                        let entry_values = current_entry.take().unwrap();
+                        // pending and information entries are not part of the booked balance
+                        if !entry_values.status.is_empty() && !entry_values.status.eq("BOOK") {
+                            continue;
+                        }
+                        let statement = current_statement.as_mut().unwrap();
+                        let transaction_result =
+                            FinanceBankImportHandle::get_camt_transaction(&entry_values);
+                        if transaction_result.is_err() {
+                            return Err(format!(
+                                "statement {}, entry {}: {}",
+                                statement.reference,
+                                statement.transactions.len() + 1,
+                                transaction_result.unwrap_err()
+                            ));
+                        }
+                        statement.transactions.push(transaction_result.unwrap());
+                    } else if element_name.eq("Bal") && current_balance.is_some() {
+                        let balance_values = current_balance.take().unwrap();
+                        let statement = current_statement.as_mut().unwrap();
+                        let is_opening_balance = balance_values.code.eq("OPBD")
+                            || (balance_values.code.eq("PRCD") && !has_opening_balance);
+                        let is_closing_balance = balance_values.code.eq("CLBD");
+                        if !is_opening_balance && !is_closing_balance {
+                            continue;
+                        }
+                        let add_reference = |error_text: String| {
+                            format!(
+                                "statement {}, balance {}: {}",
+                                statement.reference, balance_values.code, error_text
+                            )
+                        };
+                        let amount = FinanceBankImportHandle::get_camt_signed_amount(
+                            &balance_values.amount,
+                            &balance_values.credit_debit_indicator,
+                        )
+                        .map_err(add_reference)?;
+                        let balance_date =
+                            FinanceBankImportHandle::parse_camt_date(&balance_values.date)
+                                .map_err(add_reference)?;
+                        let balance_time =
+                            FinanceBankImportHandle::get_statement_time(balance_date);
+                        if is_opening_balance {
+                            has_opening_balance = true;
+                            statement.opening_balance = amount;
+                            statement.opening_balance_time = balance_time;
+                        } else {
+                            has_closing_balance = true;
+                            statement.closing_balance = amount;
+                            statement.closing_balance_time = balance_time;
+                        }
+                        if statement.currency.is_empty() {
+                            statement.currency = balance_values.currency;
+                        }
+                    } else if element_name.eq("Stmt") && current_statement.is_some() {
+                        let statement = current_statement.take().unwrap();
+                        if !has_opening_balance || !has_closing_balance {
+                            return Err(format!(
+                                "statement {}: opening (OPBD) or closing (CLBD) balance is missing",
+                                statement.reference
+                            ));
+                        }
+                        statements.push(statement);
+                    }
+                }
+                Event::Text(text_element) => {
+                    let text_result = text_element.unescape();
+                    if text_result.is_err() {
+                        return Err(format!(
+                            "XML error at position {}: {}",
+                            reader.buffer_position(),
+                            text_result.unwrap_err()
+                        ));
+                    }
+                    let text_value = text_result.unwrap().to_string();
+                    if let Some(entry_values) = current_entry.as_mut() {
+                        let entry_position = element_path
+                            .iter()
+                            .rposition(|elem| elem.eq("Ntry"))
+                            .unwrap();
+                        entry_values
+                            .set_value(&element_path[entry_position + 1..].join("/"), text_value);
+                    } else if let Some(balance_values) = current_balance.as_mut() {
+                        let balance_position = element_path
+                            .iter()
+                            .rposition(|elem| elem.eq("Bal"))
+                            .unwrap();
+                        balance_values
+                            .set_value(&element_path[balance_position + 1..].join("/"), text_value);
+                    } else if let Some(statement) = current_statement.as_mut() {
+                        let statement_position = element_path
+                            .iter()
+                            .rposition(|elem| elem.eq("Stmt"))
+                            .unwrap();
+                        match element_path[statement_position + 1..].join("/").as_str() {
+                            "Id" => statement.reference = text_value,
+                            "ElctrncSeqNb" | "LglSeqNb" => statement.statement_number = text_value,
+                            "Acct/Id/IBAN" | "Acct/Id/Othr/Id" => {
+                                statement.account_identification = text_value
+                            }
+                            "Acct/Ccy" => statement.currency = text_value,
+                            _ => {}
+                        }
+                    }
+                }
+                Event::Eof => break,
+                _ => {}
+            }
+        }
+        if current_statement.is_some() {
+            return Err("file ends inside a statement".into());
+        }
+
+        let check_result = FinanceBankImportHandle::check_statement_balance_fields(&statements);
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+        return Ok(statements);
+    }
+
+    /// reads a CAMT.053 file and proposes a booking entry per booked entry
+    pub fn preview_camt053_import(
+        content: &[u8],
+        bank_account_id: &Uuid,
+        incoming_account_id: &Uuid,
+        outgoing_account_id: &Uuid,
+    ) -> Result<(Vec<BankStatement>, Vec<BankImportPreviewRow>), String> {
+        // the XML declaration of CAMT files is always UTF-8
+        let text_result = FinanceBankImportHandle::decode_statement_file(content, "utf-8");
+        if text_result.is_err() {
+            return Err(text_result.unwrap_err());
+        }
+        let statements_result = FinanceBankImportHandle::parse_camt053(&text_result.unwrap());
+        if statements_result.is_err() {
+            return Err(statements_result.unwrap_err());
+        }
+        let statements = statements_result.unwrap();
+        // the rows are numbered over all statements of the file
+        let preview_rows = FinanceBankImportHandle::get_transaction_preview_rows(
+            &statements
+                .iter()
+                .flat_map(|elem| elem.transactions.clone())
+                .collect(),
+            bank_account_id,
+            incoming_account_id,
+            outgoing_account_id,
+        );
+        return Ok((statements, preview_rows));
+    }
+
+    fn unescape_ofx_value(value_text: &str) -> String {
+        return value_text
+            .trim()
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&apos;", "'")
+            .replace("&nbsp;", " ")
+            .replace("&amp;", "&");
+    }
+
+    fn get_ofx_transaction(
+        transaction_fields: &HashMap<String, String>,
+        transaction_number: usize,
+    ) -> Result<BankStatementTransaction, String> {
+        let date_text = transaction_fields
+            .get("DTPOSTED")
+            .cloned()
+            .unwrap_or_default();
+        // the date may be followed by a time and a time zone, e.g. 20240115120000.000[-5:EST]
+        let date_part = date_text.get(0..8).unwrap_or(&date_text);
+        let date_parse_result = NaiveDate::parse_from_str(date_part, "%Y%m%d");
+        if date_parse_result.is_err() {
+            return Err(format!(
+                "transaction {}: date {} is not valid: {}",
+                transaction_number,
+                date_text,
+                date_parse_result.unwrap_err()
+            ));
+        }
+
+        let amount_text = transaction_fields
+            .get("TRNAMT")
+            .cloned()
+            .unwrap_or_default();
+        // some banks write a comma as decimal separator
+        let decimal_separator = if amount_text.contains(',') && !amount_text.contains('.') {
+            ','
+        } else {
+            '.'
+        };
+        let amount_result = FinanceBankImportHandle::parse_amount(&amount_text, decimal_separator);
+        if amount_result.is_err() {
+            return Err(format!(
+                "transaction {}: {}",
+                transaction_number,
+                amount_result.unwrap_err()
+            ));
+        }
+
+        let fitid_option = transaction_fields.get("FITID");
+        let import_reference = if fitid_option.is_none() || fitid_option.unwrap().is_empty() {
+            None
+        } else {
+            Some(format!("OFX:{}", fitid_option.unwrap()))
+        };
+        return Ok(BankStatementTransaction {
+            booking_time: FinanceBankImportHandle::get_statement_time(date_parse_result.unwrap()),
+            amount: amount_result.unwrap(),
+            title: transaction_fields.get("NAME").cloned().unwrap_or_default(),
+            description: transaction_fields.get("MEMO").cloned().unwrap_or_default(),
+            import_reference,
+        });
+    }
+
+    /// reads the transactions (STMTTRN) of an OFX or QFX file, both the SGML format of version 1
+    /// (elements without end tag) and the XML format of version 2 are supported,
+    /// the FITID of a transaction is used as import reference
+    pub fn parse_ofx(text: &str) -> Result<Vec<BankStatementTransaction>, String> {
+        let ofx_start = text.to_uppercase().find("<OFX>");
+        if ofx_start.is_none() {
+            return Err("file does not contain an OFX element".into());
+        }
+
+        let mut transactions: Vec<BankStatementTransaction> = Vec::new();
+        let mut transaction_fields_option: Option<HashMap<String, String>> = None;
+        // every part starts with a tag, the text up to the next tag is the value of the element
+        for tag_part in text[ofx_start.unwrap()..].split('<').skip(1) {
+            let tag_split = tag_part.split_once('>');
+            if tag_split.is_none() {
+                return Err(format!("tag <{} is not closed", tag_part.trim()));
+            }
+            let (tag_text, value_text) = tag_split.unwrap();
+            let tag_name = tag_text.trim().to_uppercase();
+            if tag_name.eq("STMTTRN") {
+                transaction_fields_option = Some(HashMap::new());
+            } else if tag_name.eq("/STMTTRN") {
+                if transaction_fields_option.is_none() {
+                    return Err("end tag of STMTTRN without start tag".into());
+                }
+                let transaction_result = FinanceBankImportHandle::get_ofx_transaction(
+                    &transaction_fields_option.unwrap(),
+                    transactions.len() + 1,
+                );
+                if transaction_result.is_err() {
+                    return Err(transaction_result.unwrap_err());
+                }
+                transactions.push(transaction_result.unwrap());
+                transaction_fields_option = None;
+            } else if transaction_fields_option.is_some() && !tag_name.starts_with('/') {
+                let value = FinanceBankImportHandle::unescape_ofx_value(value_text);
+                if !value.is_empty() {
+                    // NAME may be given directly or inside PAYEE, the first one is used
+                    transaction_fields_option
+                        .as_mut()
+                        .unwrap()
+                        .entry(tag_name)
+                        .or_insert(value);
+                }
+            }
+        }
+        if transaction_fields_option.is_some() {
+            return Err("STMTTRN element is not closed".into());
+        }
+        return Ok(transactions);
+    }
+
+    /// reads an OFX or QFX file and proposes a booking entry per transaction
+    pub fn preview_ofx_import(
+        content: &[u8],
+        bank_account_id: &Uuid,
+        incoming_account_id: &Uuid,
+        outgoing_account_id: &Uuid,
+    ) -> Result<Vec<BankImportPreviewRow>, String> {
+        // files of version 1 are usually windows-1252, files of version 2 UTF-8
+        let mut text_result = FinanceBankImportHandle::decode_statement_file(content, "utf-8");
+        if text_result.is_err() {
+            text_result = FinanceBankImportHandle::decode_statement_file(content, "windows-1252");
+        }
+        if text_result.is_err() {
+            return Err(text_result.unwrap_err());
+        }
+        let transactions_result = FinanceBankImportHandle::parse_ofx(&text_result.unwrap());
+        if transactions_result.is_err() {
+            return Err(transactions_result.unwrap_err());
+        }
+        let preview_rows = FinanceBankImportHandle::get_transaction_preview_rows(
+            &transactions_result.unwrap(),
+            bank_account_id,
+            incoming_account_id,
+            outgoing_account_id,
+        );
+        return Ok(preview_rows);
+    }
+
+    fn get_qif_transaction(
+        record_fields: &HashMap<char, String>,
+        date_format: &str,
+        decimal_separator: char,
+        record_number: usize,
+    ) -> Result<BankStatementTransaction, String> {
+        let date_text = record_fields.get(&'D').cloned().unwrap_or_default();
+        // Quicken writes the year after an apostrophe and pads day and month with spaces, e.g. 1/ 5'24
+        let cleaned_date_text: String = date_text
+            .replace('\'', "/")
+            .chars()
+            .filter(|elem| !elem.is_whitespace())
+            .collect();
+        let date_parse_result = NaiveDate::parse_from_str(&cleaned_date_text, date_format);
+        if date_parse_result.is_err() {
+            return Err(format!(
+                "record {}: date {} does not match format {}: {}",
+                record_number,
+                date_text,
+                date_format,
+                date_parse_result.unwrap_err()
+            ));
+        }
+        let booking_date = date_parse_result.unwrap();
+
+        let amount_option = record_fields.get(&'T').or(record_fields.get(&'U'));
+        if amount_option.is_none() {
+            return Err(format!("record {}: amount is missing", record_number));
+        }
+        let amount_result =
+            FinanceBankImportHandle::parse_amount(amount_option.unwrap(), decimal_separator);
+        if amount_result.is_err() {
+            return Err(format!(
+                "record {}: {}",
+                record_number,
+                amount_result.unwrap_err()
+            ));
+        }
+
+        return Ok(BankStatementTransaction {
+            booking_time: FinanceBankImportHandle::get_statement_time(booking_date),
+            amount: amount_result.unwrap(),
+            title: record_fields.get(&'P').cloned().unwrap_or_default(),
+            description: record_fields.get(&'M').cloned().unwrap_or_default(),
+            import_reference: None,
+        });
+    }
+
+    /// reads the records of the bank, cash and credit card sections of a QIF file,
+    /// QIF has no transaction id, so date, amount and payee are used as import reference,
+    /// a repeated reference inside the file gets the number of its occurrence appended
+    pub fn parse_qif(
+        text: &str,
+        date_format: &str,
+        decimal_separator: char,
+    ) -> Result<Vec<BankStatementTransaction>, String> {
+        let supported_types = ["BANK", "CASH", "CCARD", "OTH A", "OTH L"];
+        let mut transactions: Vec<BankStatementTransaction> = Vec::new();
+        let mut reference_counts: HashMap<String, usize> = HashMap::new();
+        let mut is_supported_section = false;
+        let mut record_fields: HashMap<char, String> = HashMap::new();
+        for line in text.lines() {
+            let line_text = line.trim();
+            if line_text.is_empty() {
+                continue;
+            }
+            if line_text.starts_with('!') {
+                let header_text = line_text.to_uppercase();
+                is_supported_section = match header_text.strip_prefix("!TYPE:") {
+                    Some(type_text) => supported_types.contains(&type_text.trim()),
+                    None => false,
+                };
+                record_fields.clear();
+                continue;
+            }
+            if !is_supported_section {
+                continue;
+            }
+            if !line_text.starts_with('^') {
+                let mut line_chars = line_text.chars();
+                let field_code = line_chars.next().unwrap();
+                // split lines (S, E, $) may be repeated, only the first value of a field is used
+                record_fields
+                    .entry(field_code)
+                    .or_insert(line_chars.as_str().trim().to_string());
+                continue;
+            }
+
+            let mut transaction = FinanceBankImportHandle::get_qif_transaction(
+                &record_fields,
+                date_format,
+                decimal_separator,
+                transactions.len() + 1,
+            )?;
+            let fingerprint = format!(
+                "QIF:{}|{}|{}",
+                transaction.booking_time.format("%Y-%m-%d"),
+                transaction.amount,
+                transaction.title
+            );
+            let occurrence_count = reference_counts.entry(fingerprint.clone()).or_insert(0);
+            *occurrence_count += 1;
+            transaction.import_reference = if *occurrence_count == 1 {
+                Some(fingerprint)
+            } else {
+                Some(format!("{}|{}", fingerprint, occurrence_count))
+            };
+            transactions.push(transaction);
+            record_fields.clear();
+        }
+        if !record_fields.is_empty() {
+            return Err("last record is not closed with ^".into());
+        }
+        return Ok(transactions);
+    }
+
+    /// reads a QIF file and proposes a booking entry per record
+    pub fn preview_qif_import(
+        content: &[u8],
+        encoding_label: &str,
+        date_format: &str,
+        decimal_separator: char,
+        bank_account_id: &Uuid,
+        incoming_account_id: &Uuid,
+        outgoing_account_id: &Uuid,
+    ) -> Result<Vec<BankImportPreviewRow>, String> {
+        let text_result = FinanceBankImportHandle::decode_statement_file(content, encoding_label);
+        if text_result.is_err() {
+            return Err(text_result.unwrap_err());
+        }
+        let transactions_result = FinanceBankImportHandle::parse_qif(
+            &text_result.unwrap(),
+            date_format,
+            decimal_separator,
+        );
+        if transactions_result.is_err() {
+            return Err(transactions_result.unwrap_err());
+        }
+        let preview_rows = FinanceBankImportHandle::get_transaction_preview_rows(
+            &transactions_result.unwrap(),
+            bank_account_id,
+            incoming_account_id,
+            outgoing_account_id,
+        );
+        return Ok(preview_rows);
+    }
+
+    /// marks rows with a journal entry of the same import reference, when the row or the journal entry
+    /// has no import reference they are matched by the same day and amount on the same side of the
+    /// bank account, every journal entry is used for one row only, reversed entries are ignored
+    pub async fn mark_duplicate_rows(
+        &self,
+        bank_account_id: &Uuid,
+        preview_rows: &mut Vec<BankImportPreviewRow>,
+    ) -> Result<(), String> {
+        let booking_times: Vec<DateTime<Utc>> = preview_rows
+            .iter()
+            .filter_map(|elem| elem.booking_request.as_ref())
+            .map(|elem| elem.booking_time)
+            .collect();
+        if booking_times.is_empty() {
+            return Ok(());
+        }
+        let first_day = booking_times.iter().min().unwrap().date_naive();
+        let last_day = booking_times.iter().max().unwrap().date_naive();
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let journal_entries_result = booking_handle
+            .list_journal_entries(
+                Some(Utc.from_utc_datetime(&first_day.and_hms_opt(0, 0, 0).unwrap())),
+                Some(Utc.from_utc_datetime(&last_day.and_hms_opt(23, 59, 59).unwrap())),
+            )
+            .await;
+        if journal_entries_result.is_err() {
+            return Err(journal_entries_result.unwrap_err());
+        }
+        let mut journal_entries: Vec<FinanceJournalEntry> = journal_entries_result
+            .unwrap()
+            .into_iter()
+            .filter(|elem| {
+                elem.reverse_of_journal_entry_id.is_none()
+                    && elem.reversed_by_journal_entry_id.is_none()
+            })
+            .collect();
+
+        for preview_row in preview_rows.iter_mut() {
+            if preview_row.booking_request.is_none() {
+                continue;
+            }
+            let booking_request = preview_row.booking_request.as_ref().unwrap();
+            let is_incoming = booking_request.debit_finance_account_id.eq(bank_account_id);
+            let mut duplicate_position = None;
+            if booking_request.import_reference.is_some() {
+                duplicate_position = journal_entries.iter().position(|elem| {
+                    elem.import_reference.is_some()
+                        && elem.import_reference.eq(&booking_request.import_reference)
+                        && (elem.debit_finance_account_id.eq(bank_account_id)
+                            || elem.credit_finance_account_id.eq(bank_account_id))
+                });
+            }
+            if duplicate_position.is_some() {
+                let duplicate_entry = journal_entries.remove(duplicate_position.unwrap());
+                preview_row.duplicate_journal_entry_id = Some(duplicate_entry.id);
+                continue;
+            }
+            // two different references are two transactions, even with the same day and amount
+            duplicate_position = journal_entries.iter().position(|elem| {
+                (booking_request.import_reference.is_none() || elem.import_reference.is_none())
+                    && elem.amount == booking_request.amount
+                    && elem.booking_time.date_naive() == booking_request.booking_time.date_naive()
+                    && if is_incoming {
+                        elem.debit_finance_account_id.eq(bank_account_id)
+                    } else {
+                        elem.credit_finance_account_id.eq(bank_account_id)
+                    }
+            });
+            if duplicate_position.is_some() {
+                let duplicate_entry = journal_entries.remove(duplicate_position.unwrap());
+                preview_row.duplicate_journal_entry_id = Some(duplicate_entry.id);
+            }
+        }
+        return Ok(());
+    }
+
+    pub fn mark_duplicate_rows_sync(
+        &self,
+        bank_account_id: &Uuid,
+        preview_rows: &mut Vec<BankImportPreviewRow>,
+    ) -> Result<(), String> {
+        let temp_var_0 =
+            executor::block_on(self.mark_duplicate_rows(bank_account_id, preview_rows));
+        return temp_var_0;
+    }
+
+    /// posts the confirmed rows one after another, a failing row does not stop the other rows,
+    /// the duplicate check is repeated with the current journal and rows booked before are not posted
+    pub async fn post_import_rows(
+        &self,
+        bank_account_id: &Uuid,
+        rows_to_post: &Vec<BankImportPreviewRow>,
+    ) -> Vec<BankImportRowResult> {
+        // the marks of the preview come back from the client and are not trusted
+        let mut checked_rows = rows_to_post.clone();
+        for checked_row in checked_rows.iter_mut() {
+            checked_row.duplicate_journal_entry_id = None;
+        }
+        let duplicate_result = self
+            .mark_duplicate_rows(bank_account_id, &mut checked_rows)
+            .await;
+        if duplicate_result.is_err() {
+            let error_message = format!(
+                "duplicates could not be checked: {}",
+                duplicate_result.unwrap_err()
+            );
+            return checked_rows
+                .iter()
+                .map(|elem| BankImportRowResult {
+                    row_number: elem.row_number,
+                    journal_entry_id: None,
+                    error: error_message.clone(),
+                })
+                .collect();
+        }
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let mut row_results = Vec::new();
+        for row_to_post in &checked_rows {
+            if row_to_post.booking_request.is_none() {
+                row_results.push(BankImportRowResult {
+                    row_number: row_to_post.row_number,
+                    journal_entry_id: None,
+                    error: if row_to_post.error.is_empty() {
+                        "no booking entry for this row".into()
+                    } else {
+                        row_to_post.error.clone()
+                    },
+                });
+                continue;
+            }
+            if row_to_post.duplicate_journal_entry_id.is_some() {
+                row_results.push(BankImportRowResult {
+                    row_number: row_to_post.row_number,
+                    journal_entry_id: None,
+                    error: format!(
+                        "already booked as journal entry {}",
+                        row_to_post.duplicate_journal_entry_id.unwrap()
+                    ),
+                });
+                continue;
+            }
+            let insert_result = booking_handle
+                .finance_insert_booking_entry(row_to_post.booking_request.as_ref().unwrap())
+                .await;
+            if insert_result.is_err() {
+                row_results.push(BankImportRowResult {
+                    row_number: row_to_post.row_number,
+                    journal_entry_id: None,
+                    error: insert_result.unwrap_err(),
+                });
+            } else {
+                row_results.push(BankImportRowResult {
+                    row_number: row_to_post.row_number,
+                    journal_entry_id: Some(insert_result.unwrap().journal_entry.id),
+                    error: "".into(),
+                });
+            }
+        }
+        return row_results;
+    }
+
+    pub fn post_import_rows_sync(
+        &self,
+        bank_account_id: &Uuid,
+        rows_to_post: &Vec<BankImportPreviewRow>,
+    ) -> Vec<BankImportRowResult> {
+        let temp_var_0 = executor::block_on(self.post_import_rows(bank_account_id, rows_to_post));
+        return temp_var_0;
+    }
+}
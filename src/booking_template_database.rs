@@ -0,0 +1,555 @@
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, Document, Uuid},
+    options::{FindOptions, UpdateOptions},
+    Collection,
+};
+use rusqlite::{params, Row};
+use tokio_postgres::Row as PostgresRow;
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::FinanceBookingTemplate,
+};
+
+/// templates are booked via the accounting functions, so they are required as well
+#[async_trait(?Send)]
+pub trait DBFinanceBookingTemplateFunctions: DBFinanceAccountingFunctions {
+    async fn finance_booking_template_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBookingTemplate>, String>;
+    async fn finance_booking_template_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String>;
+    async fn finance_booking_template_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template_id: &Uuid,
+    ) -> Result<(), String>;
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBookingTemplateFunctions for DbHandlerMongoDB {
+    async fn finance_booking_template_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBookingTemplate>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let booking_template_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_TEMPLATES);
+
+        //get a binary of UUID or it will not work in production
+        let search_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let filter = doc! {"user_id":search_value};
+
+        debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
+        let projection = doc! {"booking_template_id":<i32>::from(1),
+        "name":<i32>::from(1),
+        "debit_finance_account_id":<i32>::from(1),
+        "credit_finance_account_id":<i32>::from(1),
+        "amount":<i32>::from(1),
+        "title":<i32>::from(1),
+        "description":<i32>::from(1),};
+        let options = FindOptions::builder()
+            .projection(projection)
+            .sort(doc! {"name":<i32>::from(1)})
+            .build();
+
+        let query_execute_result = booking_template_collection.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let stored_booking_template_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "booking_template_id");
+            if stored_booking_template_id.is_err() {
+                return Err(stored_booking_template_id.unwrap_err());
+            }
+            let stored_debit_account_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "debit_finance_account_id");
+            if stored_debit_account_id.is_err() {
+                return Err(stored_debit_account_id.unwrap_err());
+            }
+            let stored_credit_account_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "credit_finance_account_id");
+            if stored_credit_account_id.is_err() {
+                return Err(stored_credit_account_id.unwrap_err());
+            }
+            let stored_name = inner_doc.get_str("name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+            let stored_amount = inner_doc.get_i64("amount");
+            if stored_amount.is_err() {
+                return Err(stored_amount.unwrap_err().to_string());
+            }
+            let stored_title = inner_doc.get_str("title");
+            if stored_title.is_err() {
+                return Err(stored_title.unwrap_err().to_string());
+            }
+            let stored_description = inner_doc.get_str("description");
+            if stored_description.is_err() {
+                return Err(stored_description.unwrap_err().to_string());
+            }
+
+            let booking_template = FinanceBookingTemplate {
+                id: stored_booking_template_id.unwrap(),
+                name: stored_name.unwrap().into(),
+                debit_finance_account_id: stored_debit_account_id.unwrap(),
+                credit_finance_account_id: stored_credit_account_id.unwrap(),
+                amount: stored_amount.unwrap() as u64,
+                title: stored_title.unwrap().into(),
+                description: stored_description.unwrap().into(),
+            };
+
+            result_list.push(booking_template);
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} booking templates",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_booking_template_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let booking_template_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_TEMPLATES);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let booking_template_id_value = mongodb::bson::Binary::from_uuid(booking_template.id);
+        let filter = doc! {"booking_template_id":booking_template_id_value.clone(),"user_id":user_id_value.clone()};
+        let inner_doc = doc! {
+            "booking_template_id":booking_template_id_value,
+            "user_id": user_id_value,
+            "name":&booking_template.name,
+            "debit_finance_account_id":mongodb::bson::Binary::from_uuid(booking_template.debit_finance_account_id),
+            "credit_finance_account_id":mongodb::bson::Binary::from_uuid(booking_template.credit_finance_account_id),
+            "amount":booking_template.amount as i64,
+            "title":&booking_template.title,
+            "description":&booking_template.description,
+        };
+        let upsert_doc = doc! {"$set": inner_doc  };
+        let opts = UpdateOptions::builder().upsert(true).build();
+
+        let upsert_result = booking_template_collection
+            .update_one(filter, upsert_doc, opts)
+            .await;
+        if upsert_result.is_err() {
+            let upsert_err = &upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+        let upsert_info = upsert_result.unwrap();
+
+        if (upsert_info.matched_count > 1) || (upsert_info.modified_count > 1) {
+            return Err(format!(
+                "Error upserting element, matched count was {}, changed count was {}",
+                upsert_info.matched_count, upsert_info.modified_count
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","upserted booking template for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_booking_template_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template_id: &Uuid,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let booking_template_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_TEMPLATES);
+
+        let filter = doc! {"booking_template_id":mongodb::bson::Binary::from_uuid(booking_template_id.clone()),
+        "user_id":mongodb::bson::Binary::from_uuid(user_id.clone())};
+
+        let delete_result = booking_template_collection.delete_one(filter, None).await;
+        if delete_result.is_err() {
+            let delete_err = &delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap().deleted_count != 1 {
+            return Err(format!(
+                "booking template {} not found",
+                booking_template_id
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","deleted booking template for user id {}",&user_id);
+
+        Ok(())
+    }
+}
+
+impl DbHandlerSqlite {
+    fn read_booking_template_row(row: &Row) -> Result<FinanceBookingTemplate, String> {
+        let stored_booking_template_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "booking_template_id");
+        if stored_booking_template_id.is_err() {
+            return Err(stored_booking_template_id.unwrap_err());
+        }
+        let stored_name = row.get::<_, String>("name");
+        if stored_name.is_err() {
+            return Err(stored_name.unwrap_err().to_string());
+        }
+        let stored_debit_account_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "debit_finance_account_id");
+        if stored_debit_account_id.is_err() {
+            return Err(stored_debit_account_id.unwrap_err());
+        }
+        let stored_credit_account_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "credit_finance_account_id");
+        if stored_credit_account_id.is_err() {
+            return Err(stored_credit_account_id.unwrap_err());
+        }
+        let stored_amount = row.get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(stored_amount.unwrap_err().to_string());
+        }
+        let stored_title = row.get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+        let stored_description = row.get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(stored_description.unwrap_err().to_string());
+        }
+
+        return Ok(FinanceBookingTemplate {
+            id: stored_booking_template_id.unwrap(),
+            name: stored_name.unwrap(),
+            debit_finance_account_id: stored_debit_account_id.unwrap(),
+            credit_finance_account_id: stored_credit_account_id.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBookingTemplateFunctions for DbHandlerSqlite {
+    async fn finance_booking_template_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBookingTemplate>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceBookingTemplates WHERE user_id = ?1 ORDER BY rowid",
+            [user_id.to_string()],
+            DbHandlerSqlite::read_booking_template_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} booking templates",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_booking_template_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result = connection.execute(
+            "INSERT INTO FinanceBookingTemplates (booking_template_id, user_id, name, debit_finance_account_id,
+            credit_finance_account_id, amount, title, description) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8)
+            ON CONFLICT (user_id, booking_template_id) DO UPDATE SET name = excluded.name,
+            debit_finance_account_id = excluded.debit_finance_account_id,
+            credit_finance_account_id = excluded.credit_finance_account_id, amount = excluded.amount,
+            title = excluded.title, description = excluded.description",
+            params![
+                booking_template.id.to_string(),
+                user_id.to_string(),
+                &booking_template.name,
+                booking_template.debit_finance_account_id.to_string(),
+                booking_template.credit_finance_account_id.to_string(),
+                booking_template.amount as i64,
+                &booking_template.title,
+                &booking_template.description,
+            ],
+        );
+        if upsert_result.is_err() {
+            let upsert_err = upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","upserted booking template for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_booking_template_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template_id: &Uuid,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let delete_result = connection.execute(
+            "DELETE FROM FinanceBookingTemplates WHERE user_id = ?1 AND booking_template_id = ?2",
+            [user_id.to_string(), booking_template_id.to_string()],
+        );
+        if delete_result.is_err() {
+            let delete_err = delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!(
+                "booking template {} not found",
+                booking_template_id
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl DbHandlerPostgres {
+    fn read_booking_template_row(row: &PostgresRow) -> Result<FinanceBookingTemplate, String> {
+        let stored_booking_template_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "booking_template_id");
+        if stored_booking_template_id.is_err() {
+            return Err(stored_booking_template_id.unwrap_err());
+        }
+        let stored_name = row.try_get::<_, String>("name");
+        if stored_name.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_name.unwrap_err()));
+        }
+        let stored_debit_account_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "debit_finance_account_id");
+        if stored_debit_account_id.is_err() {
+            return Err(stored_debit_account_id.unwrap_err());
+        }
+        let stored_credit_account_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "credit_finance_account_id");
+        if stored_credit_account_id.is_err() {
+            return Err(stored_credit_account_id.unwrap_err());
+        }
+        let stored_amount = row.try_get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_amount.unwrap_err()));
+        }
+        let stored_title = row.try_get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+        let stored_description = row.try_get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_description.unwrap_err(),
+            ));
+        }
+
+        return Ok(FinanceBookingTemplate {
+            id: stored_booking_template_id.unwrap(),
+            name: stored_name.unwrap(),
+            debit_finance_account_id: stored_debit_account_id.unwrap(),
+            credit_finance_account_id: stored_credit_account_id.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+            title: stored_title.unwrap(),
+            description: stored_description.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBookingTemplateFunctions for DbHandlerPostgres {
+    async fn finance_booking_template_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBookingTemplate>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceBookingTemplates WHERE user_id = $1 ORDER BY insert_order",
+            &[&user_id.to_uuid_1()],
+            DbHandlerPostgres::read_booking_template_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} booking templates",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_booking_template_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result = client
+.execute(
+            "INSERT INTO FinanceBookingTemplates (booking_template_id, user_id, name, debit_finance_account_id,
+            credit_finance_account_id, amount, title, description) VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            ON CONFLICT (user_id, booking_template_id) DO UPDATE SET name = excluded.name,
+            debit_finance_account_id = excluded.debit_finance_account_id,
+            credit_finance_account_id = excluded.credit_finance_account_id, amount = excluded.amount,
+            title = excluded.title, description = excluded.description",
+            &[
+                &booking_template.id.to_uuid_1(),
+                &user_id.to_uuid_1(),
+                &booking_template.name,
+                &booking_template.debit_finance_account_id.to_uuid_1(),
+                &booking_template.credit_finance_account_id.to_uuid_1(),
+                &(booking_template.amount as i64),
+                &booking_template.title,
+                &booking_template.description,
+            ],
+        )
+.await;
+        if upsert_result.is_err() {
+            let upsert_err = DbHandlerPostgres::format_error(&upsert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","upserted booking template for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_booking_template_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template_id: &Uuid,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let delete_result = client
+.execute(
+            "DELETE FROM FinanceBookingTemplates WHERE user_id = $1 AND booking_template_id = $2",
+            &[&user_id.to_uuid_1(), &booking_template_id.to_uuid_1()],
+        )
+.await;
+        if delete_result.is_err() {
+            let delete_err = DbHandlerPostgres::format_error(&delete_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err);
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!(
+                "booking template {} not found",
+                booking_template_id
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,204 @@
+use async_session::chrono::{DateTime, Utc};
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
+    accounting_logic::FinanceBookingHandle,
+    booking_template_database::DBFinanceBookingTemplateFunctions,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{FinanceBookingRequest, FinanceBookingResult, FinanceBookingTemplate},
+};
+
+pub struct FinanceBookingTemplateHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceBookingTemplateFunctions,
+}
+
+impl<'a> FinanceBookingTemplateHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceBookingTemplateFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    pub async fn booking_template_list(&self) -> Result<Vec<FinanceBookingTemplate>, String> {
+        let temp_var_0 = self
+            .db_connector
+            .finance_booking_template_list(&self.db_connection_settings, &self.user_id);
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn booking_template_list_sync(&self) -> Result<Vec<FinanceBookingTemplate>, String> {
+        let temp_var_0 = executor::block_on(self.booking_template_list());
+        return temp_var_0;
+    }
+
+    /// the amount of a template can be zero, it has to be given when booking the template then
+    pub async fn booking_template_upsert(
+        &self,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String> {
+        if booking_template.name.trim().is_empty() {
+            return Err("could not upsert booking template because name is empty".into());
+        }
+        if booking_template
+            .debit_finance_account_id
+            .eq(&booking_template.credit_finance_account_id)
+        {
+            return Err(
+                "could not upsert booking template because debit and credit account are the same"
+                    .into(),
+            );
+        }
+
+        let template_list_result = self.booking_template_list().await;
+        if template_list_result.is_err() {
+            return Err(format!(
+                "Error getting booking templates: {}",
+                template_list_result.unwrap_err()
+            ));
+        }
+        if template_list_result
+            .unwrap()
+            .iter()
+            .any(|elem| elem.name.eq(&booking_template.name) && !elem.id.eq(&booking_template.id))
+        {
+            return Err(format!(
+                "could not upsert booking template because name {} is already used",
+                booking_template.name
+            ));
+        }
+
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle
+            .finance_account_list_async(Some(&vec![
+                booking_template.debit_finance_account_id,
+                booking_template.credit_finance_account_id,
+            ]))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        if account_list_result.unwrap().len() != 2 {
+            return Err("could not upsert booking template because an account is unknown".into());
+        }
+
+        let temp_var_0 = self.db_connector.finance_booking_template_upsert(
+            &self.db_connection_settings,
+            &self.user_id,
+            booking_template,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn booking_template_upsert_sync(
+        &self,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.booking_template_upsert(booking_template));
+        return temp_var_0;
+    }
+
+    pub async fn booking_template_delete(&self, booking_template_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = self.db_connector.finance_booking_template_delete(
+            &self.db_connection_settings,
+            &self.user_id,
+            booking_template_id,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn booking_template_delete_sync(&self, booking_template_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.booking_template_delete(booking_template_id));
+        return temp_var_0;
+    }
+
+    /// books the template as a simple booking entry, a given amount replaces the amount of the template,
+    /// the entry gets the same validation as a manually entered one
+    pub async fn booking_template_insert_booking_entry(
+        &self,
+        booking_template_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        amount: Option<u64>,
+    ) -> Result<FinanceBookingResult, String> {
+        let template_list_result = self.booking_template_list().await;
+        if template_list_result.is_err() {
+            return Err(format!(
+                "Error getting booking templates: {}",
+                template_list_result.unwrap_err()
+            ));
+        }
+        let template_list = template_list_result.unwrap();
+        let booking_template_option = template_list
+            .iter()
+            .find(|elem| elem.id.eq(booking_template_id));
+        if booking_template_option.is_none() {
+            return Err(format!(
+                "booking template {} not found",
+                booking_template_id
+            ));
+        }
+        let booking_template = booking_template_option.unwrap();
+        let booking_amount = amount.unwrap_or(booking_template.amount);
+        if booking_amount == 0 {
+            return Err(format!(
+                "booking template {} has no amount, an amount is required",
+                booking_template.name
+            ));
+        }
+
+        let booking_request = FinanceBookingRequest {
+            is_simple_entry: true,
+            is_saldo: false,
+            debit_finance_account_id: booking_template.debit_finance_account_id,
+            credit_finance_account_id: booking_template.credit_finance_account_id,
+            booking_time,
+            amount: booking_amount,
+            title: booking_template.title.clone(),
+            description: booking_template.description.clone(),
+            tags: Vec::new(),
+            import_reference: None,
+        };
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let temp_var_0 = booking_handle.finance_insert_booking_entry(&booking_request);
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn booking_template_insert_booking_entry_sync(
+        &self,
+        booking_template_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        amount: Option<u64>,
+    ) -> Result<FinanceBookingResult, String> {
+        let temp_var_0 = executor::block_on(self.booking_template_insert_booking_entry(
+            booking_template_id,
+            booking_time,
+            amount,
+        ));
+        return temp_var_0;
+    }
+}
@@ -0,0 +1,484 @@
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, Document, Uuid},
+    options::{FindOptions, UpdateOptions},
+    Collection,
+};
+use rusqlite::{params, Row};
+use tokio_postgres::Row as PostgresRow;
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::{BudgetPeriod, FinanceBudget},
+};
+
+/// budgets are compared to the booking entries, so the accounting functions are required as well
+#[async_trait(?Send)]
+pub trait DBFinanceBudgetFunctions: DBFinanceAccountingFunctions {
+    async fn finance_budget_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBudget>, String>;
+    async fn finance_budget_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget: &FinanceBudget,
+    ) -> Result<(), String>;
+    async fn finance_budget_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget_id: &Uuid,
+    ) -> Result<(), String>;
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBudgetFunctions for DbHandlerMongoDB {
+    async fn finance_budget_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBudget>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let budget_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BUDGETS);
+
+        //get a binary of UUID or it will not work in production
+        let search_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let filter = doc! {"user_id":search_value};
+
+        debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
+        let projection = doc! {"budget_id":<i32>::from(1),
+        "finance_account_id":<i32>::from(1),
+        "period":<i32>::from(1),
+        "amount":<i32>::from(1),};
+        let options = FindOptions::builder().projection(projection).build();
+
+        let query_execute_result = budget_collection.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let stored_budget_id = ConvertTools::get_uuid_from_document(&inner_doc, "budget_id");
+            if stored_budget_id.is_err() {
+                return Err(stored_budget_id.unwrap_err());
+            }
+            let stored_account_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "finance_account_id");
+            if stored_account_id.is_err() {
+                return Err(stored_account_id.unwrap_err());
+            }
+            let stored_period_value = inner_doc.get_i32("period");
+            if stored_period_value.is_err() {
+                return Err(stored_period_value.unwrap_err().to_string());
+            }
+            let stored_period = BudgetPeriod::get_from_int(stored_period_value.unwrap());
+            if stored_period.is_err() {
+                return Err(stored_period.unwrap_err());
+            }
+            let stored_amount = inner_doc.get_i64("amount");
+            if stored_amount.is_err() {
+                return Err(stored_amount.unwrap_err().to_string());
+            }
+
+            let budget = FinanceBudget {
+                id: stored_budget_id.unwrap(),
+                finance_account_id: stored_account_id.unwrap(),
+                period: stored_period.unwrap(),
+                amount: stored_amount.unwrap() as u64,
+            };
+
+            result_list.push(budget);
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} budgets",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_budget_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget: &FinanceBudget,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let budget_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BUDGETS);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let budget_id_value = mongodb::bson::Binary::from_uuid(budget.id);
+        let filter = doc! {"budget_id":budget_id_value.clone(),"user_id":user_id_value.clone()};
+        let inner_doc = doc! {
+            "budget_id":budget_id_value,
+            "user_id": user_id_value,
+            "finance_account_id":mongodb::bson::Binary::from_uuid(budget.finance_account_id),
+            "period":budget.period.to_int(),
+            "amount":budget.amount as i64,
+        };
+        let upsert_doc = doc! {"$set": inner_doc  };
+        let opts = UpdateOptions::builder().upsert(true).build();
+
+        let upsert_result = budget_collection.update_one(filter, upsert_doc, opts).await;
+        if upsert_result.is_err() {
+            let upsert_err = &upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+        let upsert_info = upsert_result.unwrap();
+
+        if (upsert_info.matched_count > 1) || (upsert_info.modified_count > 1) {
+            return Err(format!(
+                "Error upserting element, matched count was {}, changed count was {}",
+                upsert_info.matched_count, upsert_info.modified_count
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","upserted budget for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_budget_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget_id: &Uuid,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let budget_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BUDGETS);
+
+        let filter = doc! {"budget_id":mongodb::bson::Binary::from_uuid(budget_id.clone()),
+        "user_id":mongodb::bson::Binary::from_uuid(user_id.clone())};
+
+        let delete_result = budget_collection.delete_one(filter, None).await;
+        if delete_result.is_err() {
+            let delete_err = &delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap().deleted_count != 1 {
+            return Err(format!("budget {} not found", budget_id));
+        }
+
+        debug!(target:"app::FinanceOverView","deleted budget for user id {}",&user_id);
+
+        Ok(())
+    }
+}
+
+impl DbHandlerSqlite {
+    fn read_budget_row(row: &Row) -> Result<FinanceBudget, String> {
+        let stored_budget_id = DbHandlerSqlite::get_uuid_from_row(row, "budget_id");
+        if stored_budget_id.is_err() {
+            return Err(stored_budget_id.unwrap_err());
+        }
+        let stored_account_id = DbHandlerSqlite::get_uuid_from_row(row, "finance_account_id");
+        if stored_account_id.is_err() {
+            return Err(stored_account_id.unwrap_err());
+        }
+        let stored_period_value = row.get::<_, i32>("period");
+        if stored_period_value.is_err() {
+            return Err(stored_period_value.unwrap_err().to_string());
+        }
+        let stored_period = BudgetPeriod::get_from_int(stored_period_value.unwrap());
+        if stored_period.is_err() {
+            return Err(stored_period.unwrap_err());
+        }
+        let stored_amount = row.get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(stored_amount.unwrap_err().to_string());
+        }
+
+        return Ok(FinanceBudget {
+            id: stored_budget_id.unwrap(),
+            finance_account_id: stored_account_id.unwrap(),
+            period: stored_period.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBudgetFunctions for DbHandlerSqlite {
+    async fn finance_budget_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBudget>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceBudgets WHERE user_id = ?1 ORDER BY rowid",
+            [user_id.to_string()],
+            DbHandlerSqlite::read_budget_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} budgets",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_budget_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget: &FinanceBudget,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result = connection.execute(
+            "INSERT INTO FinanceBudgets (budget_id, user_id, finance_account_id, period, amount)
+            VALUES (?1, ?2, ?3, ?4, ?5)
+            ON CONFLICT (user_id, budget_id) DO UPDATE SET finance_account_id = excluded.finance_account_id,
+            period = excluded.period, amount = excluded.amount",
+            params![
+                budget.id.to_string(),
+                user_id.to_string(),
+                budget.finance_account_id.to_string(),
+                budget.period.to_int(),
+                budget.amount as i64,
+            ],
+        );
+        if upsert_result.is_err() {
+            let upsert_err = upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","upserted budget for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_budget_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget_id: &Uuid,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let delete_result = connection.execute(
+            "DELETE FROM FinanceBudgets WHERE user_id = ?1 AND budget_id = ?2",
+            [user_id.to_string(), budget_id.to_string()],
+        );
+        if delete_result.is_err() {
+            let delete_err = delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!("budget {} not found", budget_id));
+        }
+
+        Ok(())
+    }
+}
+
+impl DbHandlerPostgres {
+    fn read_budget_row(row: &PostgresRow) -> Result<FinanceBudget, String> {
+        let stored_budget_id = DbHandlerPostgres::get_uuid_from_row(row, "budget_id");
+        if stored_budget_id.is_err() {
+            return Err(stored_budget_id.unwrap_err());
+        }
+        let stored_account_id = DbHandlerPostgres::get_uuid_from_row(row, "finance_account_id");
+        if stored_account_id.is_err() {
+            return Err(stored_account_id.unwrap_err());
+        }
+        let stored_period_value = row.try_get::<_, i32>("period");
+        if stored_period_value.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_period_value.unwrap_err(),
+            ));
+        }
+        let stored_period = BudgetPeriod::get_from_int(stored_period_value.unwrap());
+        if stored_period.is_err() {
+            return Err(stored_period.unwrap_err());
+        }
+        let stored_amount = row.try_get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_amount.unwrap_err()));
+        }
+
+        return Ok(FinanceBudget {
+            id: stored_budget_id.unwrap(),
+            finance_account_id: stored_account_id.unwrap(),
+            period: stored_period.unwrap(),
+            amount: stored_amount.unwrap() as u64,
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBudgetFunctions for DbHandlerPostgres {
+    async fn finance_budget_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBudget>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceBudgets WHERE user_id = $1 ORDER BY insert_order",
+            &[&user_id.to_uuid_1()],
+            DbHandlerPostgres::read_budget_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} budgets",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_budget_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget: &FinanceBudget,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result = client
+.execute(
+            "INSERT INTO FinanceBudgets (budget_id, user_id, finance_account_id, period, amount)
+            VALUES ($1, $2, $3, $4, $5)
+            ON CONFLICT (user_id, budget_id) DO UPDATE SET finance_account_id = excluded.finance_account_id,
+            period = excluded.period, amount = excluded.amount",
+            &[
+                &budget.id.to_uuid_1(),
+                &user_id.to_uuid_1(),
+                &budget.finance_account_id.to_uuid_1(),
+                &budget.period.to_int(),
+                &(budget.amount as i64),
+            ],
+        )
+.await;
+        if upsert_result.is_err() {
+            let upsert_err = DbHandlerPostgres::format_error(&upsert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","upserted budget for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_budget_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget_id: &Uuid,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let delete_result = client
+            .execute(
+                "DELETE FROM FinanceBudgets WHERE user_id = $1 AND budget_id = $2",
+                &[&user_id.to_uuid_1(), &budget_id.to_uuid_1()],
+            )
+            .await;
+        if delete_result.is_err() {
+            let delete_err = DbHandlerPostgres::format_error(&delete_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err);
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!("budget {} not found", budget_id));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,237 @@
+use async_session::chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
+    accounting_logic::FinanceBookingHandle,
+    budget_database::DBFinanceBudgetFunctions,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{BudgetPeriod, FinanceBudget, FinanceBudgetReport, FinanceBudgetReportLine},
+};
+
+pub struct FinanceBudgetHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceBudgetFunctions,
+}
+
+impl<'a> FinanceBudgetHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceBudgetFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    pub async fn budget_list(&self) -> Result<Vec<FinanceBudget>, String> {
+        let temp_var_0 = self
+            .db_connector
+            .finance_budget_list(&self.db_connection_settings, &self.user_id);
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn budget_list_sync(&self) -> Result<Vec<FinanceBudget>, String> {
+        let temp_var_0 = executor::block_on(self.budget_list());
+        return temp_var_0;
+    }
+
+    pub async fn budget_upsert(&self, budget: &FinanceBudget) -> Result<(), String> {
+        if budget.amount == 0 {
+            return Err("could not upsert budget because amount is zero".into());
+        }
+
+        let budget_list_result = self.budget_list().await;
+        if budget_list_result.is_err() {
+            return Err(format!(
+                "Error getting budgets: {}",
+                budget_list_result.unwrap_err()
+            ));
+        }
+        if budget_list_result.unwrap().iter().any(|elem| {
+            elem.finance_account_id.eq(&budget.finance_account_id)
+                && elem.period.eq(&budget.period)
+                && !elem.id.eq(&budget.id)
+        }) {
+            return Err(format!(
+                "could not upsert budget because there is already a {} budget for this account",
+                budget.period
+            ));
+        }
+
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle
+            .finance_account_list_async(Some(&vec![budget.finance_account_id]))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        if account_list_result.unwrap().len() != 1 {
+            return Err("could not upsert budget because the account is unknown".into());
+        }
+
+        let temp_var_0 = self.db_connector.finance_budget_upsert(
+            &self.db_connection_settings,
+            &self.user_id,
+            budget,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn budget_upsert_sync(&self, budget: &FinanceBudget) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.budget_upsert(budget));
+        return temp_var_0;
+    }
+
+    pub async fn budget_delete(&self, budget_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = self.db_connector.finance_budget_delete(
+            &self.db_connection_settings,
+            &self.user_id,
+            budget_id,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn budget_delete_sync(&self, budget_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.budget_delete(budget_id));
+        return temp_var_0;
+    }
+
+    /// first and last moment (UTC) of the calendar month or year containing report_time
+    pub fn get_period_times(
+        period: &BudgetPeriod,
+        report_time: DateTime<Utc>,
+    ) -> (DateTime<Utc>, DateTime<Utc>) {
+        let (start_year, start_month, end_year, end_month) = match period {
+            BudgetPeriod::Monthly => {
+                if report_time.month() == 12 {
+                    (report_time.year(), 12, report_time.year() + 1, 1)
+                } else {
+                    (
+                        report_time.year(),
+                        report_time.month(),
+                        report_time.year(),
+                        report_time.month() + 1,
+                    )
+                }
+            }
+            BudgetPeriod::Yearly => (report_time.year(), 1, report_time.year() + 1, 1),
+        };
+        let period_start = NaiveDate::from_ymd_opt(start_year, start_month, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        let next_period_start = NaiveDate::from_ymd_opt(end_year, end_month, 1)
+            .unwrap()
+            .and_hms_opt(0, 0, 0)
+            .unwrap();
+        return (
+            Utc.from_utc_datetime(&period_start),
+            Utc.from_utc_datetime(&next_period_start) - Duration::milliseconds(1),
+        );
+    }
+
+    /// compares every budget to the movements of its account in the month or year containing
+    /// report_time, saldo entries and entries closing a period are ignored (same as in the income statement)
+    pub async fn budget_report(
+        &self,
+        report_time: DateTime<Utc>,
+    ) -> Result<FinanceBudgetReport, String> {
+        let budget_list_result = self.budget_list().await;
+        if budget_list_result.is_err() {
+            return Err(format!(
+                "Error getting budgets: {}",
+                budget_list_result.unwrap_err()
+            ));
+        }
+        let budget_list = budget_list_result.unwrap();
+
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle.finance_account_list_async(None).await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        let account_list = account_list_result.unwrap();
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+
+        let mut lines = Vec::new();
+        for budget in budget_list {
+            let account_option = account_list
+                .iter()
+                .find(|elem| elem.id.eq(&budget.finance_account_id));
+            if account_option.is_none() {
+                return Err(format!(
+                    "account {} of budget {} not found",
+                    budget.finance_account_id, budget.id
+                ));
+            }
+            let account = account_option.unwrap();
+            let (time_from, time_till) =
+                FinanceBudgetHandle::get_period_times(&budget.period, report_time);
+
+            let movement_info_result = booking_handle
+                .calculate_movement_info(&vec![account.id], time_from, time_till)
+                .await;
+            if movement_info_result.is_err() {
+                return Err(format!(
+                    "Error getting movements of budget {}: {}",
+                    budget.id,
+                    movement_info_result.unwrap_err()
+                ));
+            }
+
+            let budget_amount = budget.amount as i128;
+            let actual_amount = movement_info_result.unwrap()[0].get_signed_amount();
+            lines.push(FinanceBudgetReportLine {
+                budget_id: budget.id.to_string(),
+                account_id: account.id.to_string(),
+                account_title: account.title.clone(),
+                period: budget.period,
+                time_from,
+                time_till,
+                budget_amount,
+                actual_amount,
+                remaining_amount: budget_amount - actual_amount,
+                percentage_used: (actual_amount as f64) * 100.0 / (budget_amount as f64),
+            });
+        }
+        lines.sort_by(|a, b| a.account_title.cmp(&b.account_title));
+
+        return Ok(FinanceBudgetReport { report_time, lines });
+    }
+
+    pub fn budget_report_sync(
+        &self,
+        report_time: DateTime<Utc>,
+    ) -> Result<FinanceBudgetReport, String> {
+        let temp_var_0 = executor::block_on(self.budget_report(report_time));
+        return temp_var_0;
+    }
+}
@@ -0,0 +1,626 @@
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, Document, Uuid},
+    options::{FindOptions, UpdateOptions},
+    Collection,
+};
+use rusqlite::{params, Row};
+use tokio_postgres::Row as PostgresRow;
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::FinanceCategorizationRule,
+};
+
+/// rules are tested against the journal entries, so the accounting functions are required as well
+#[async_trait(?Send)]
+pub trait DBFinanceCategorizationRuleFunctions: DBFinanceAccountingFunctions {
+    async fn finance_categorization_rule_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceCategorizationRule>, String>;
+    async fn finance_categorization_rule_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule: &FinanceCategorizationRule,
+    ) -> Result<(), String>;
+    async fn finance_categorization_rule_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule_id: &Uuid,
+    ) -> Result<(), String>;
+}
+
+#[async_trait(?Send)]
+impl DBFinanceCategorizationRuleFunctions for DbHandlerMongoDB {
+    async fn finance_categorization_rule_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceCategorizationRule>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let rule_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_CATEGORIZATION_RULES);
+
+        //get a binary of UUID or it will not work in production
+        let search_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let filter = doc! {"user_id":search_value};
+
+        debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
+        let projection = doc! {"rule_id":<i32>::from(1),
+        "title":<i32>::from(1),
+        "priority":<i32>::from(1),
+        "payee_pattern":<i32>::from(1),
+        "text_pattern":<i32>::from(1),
+        "amount_min":<i32>::from(1),
+        "amount_max":<i32>::from(1),
+        "source_account_id":<i32>::from(1),
+        "counter_account_id":<i32>::from(1),
+        "title_rewrite":<i32>::from(1),
+        "tags":<i32>::from(1),};
+        let options = FindOptions::builder()
+            .projection(projection)
+            .sort(doc! {"priority":<i32>::from(1)})
+            .build();
+
+        let query_execute_result = rule_collection.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let stored_rule_id = ConvertTools::get_uuid_from_document(&inner_doc, "rule_id");
+            if stored_rule_id.is_err() {
+                return Err(stored_rule_id.unwrap_err());
+            }
+            let stored_title = inner_doc.get_str("title");
+            if stored_title.is_err() {
+                return Err(stored_title.unwrap_err().to_string());
+            }
+            let stored_priority = inner_doc.get_i32("priority");
+            if stored_priority.is_err() {
+                return Err(stored_priority.unwrap_err().to_string());
+            }
+            let stored_payee_pattern = inner_doc.get_str("payee_pattern");
+            if stored_payee_pattern.is_err() {
+                return Err(stored_payee_pattern.unwrap_err().to_string());
+            }
+            let stored_text_pattern = inner_doc.get_str("text_pattern");
+            if stored_text_pattern.is_err() {
+                return Err(stored_text_pattern.unwrap_err().to_string());
+            }
+            let stored_source_account_id =
+                ConvertTools::get_optional_uuid_from_document(&inner_doc, "source_account_id");
+            if stored_source_account_id.is_err() {
+                return Err(stored_source_account_id.unwrap_err());
+            }
+            let stored_counter_account_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "counter_account_id");
+            if stored_counter_account_id.is_err() {
+                return Err(stored_counter_account_id.unwrap_err());
+            }
+            let stored_title_rewrite = inner_doc.get_str("title_rewrite");
+            if stored_title_rewrite.is_err() {
+                return Err(stored_title_rewrite.unwrap_err().to_string());
+            }
+            let stored_tags = ConvertTools::get_string_list_from_document(&inner_doc, "tags");
+            if stored_tags.is_err() {
+                return Err(stored_tags.unwrap_err());
+            }
+
+            let rule = FinanceCategorizationRule {
+                id: stored_rule_id.unwrap(),
+                title: stored_title.unwrap().to_string(),
+                priority: stored_priority.unwrap(),
+                payee_pattern: stored_payee_pattern.unwrap().to_string(),
+                text_pattern: stored_text_pattern.unwrap().to_string(),
+                // null when the rule has no limit
+                amount_min: inner_doc.get_i64("amount_min").ok(),
+                amount_max: inner_doc.get_i64("amount_max").ok(),
+                source_account_id: stored_source_account_id.unwrap(),
+                counter_account_id: stored_counter_account_id.unwrap(),
+                title_rewrite: stored_title_rewrite.unwrap().to_string(),
+                tags: stored_tags.unwrap(),
+            };
+
+            result_list.push(rule);
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} categorization rules",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_categorization_rule_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule: &FinanceCategorizationRule,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let rule_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_CATEGORIZATION_RULES);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let rule_id_value = mongodb::bson::Binary::from_uuid(rule.id);
+        let filter = doc! {"rule_id":rule_id_value.clone(),"user_id":user_id_value.clone()};
+        let inner_doc = doc! {
+            "rule_id":rule_id_value,
+            "user_id": user_id_value,
+            "title":rule.title.clone(),
+            "priority":rule.priority,
+            "payee_pattern":rule.payee_pattern.clone(),
+            "text_pattern":rule.text_pattern.clone(),
+            "amount_min":rule.amount_min,
+            "amount_max":rule.amount_max,
+            "source_account_id":rule.source_account_id.map(mongodb::bson::Binary::from_uuid),
+            "counter_account_id":mongodb::bson::Binary::from_uuid(rule.counter_account_id),
+            "title_rewrite":rule.title_rewrite.clone(),
+            "tags":rule.tags.clone(),
+        };
+        let upsert_doc = doc! {"$set": inner_doc  };
+        let opts = UpdateOptions::builder().upsert(true).build();
+
+        let upsert_result = rule_collection.update_one(filter, upsert_doc, opts).await;
+        if upsert_result.is_err() {
+            let upsert_err = &upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+        let upsert_info = upsert_result.unwrap();
+
+        if (upsert_info.matched_count > 1) || (upsert_info.modified_count > 1) {
+            return Err(format!(
+                "Error upserting element, matched count was {}, changed count was {}",
+                upsert_info.matched_count, upsert_info.modified_count
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","upserted categorization rule for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_categorization_rule_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule_id: &Uuid,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let rule_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_CATEGORIZATION_RULES);
+
+        let filter = doc! {"rule_id":mongodb::bson::Binary::from_uuid(rule_id.clone()),
+        "user_id":mongodb::bson::Binary::from_uuid(user_id.clone())};
+
+        let delete_result = rule_collection.delete_one(filter, None).await;
+        if delete_result.is_err() {
+            let delete_err = &delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap().deleted_count != 1 {
+            return Err(format!("categorization rule {} not found", rule_id));
+        }
+
+        debug!(target:"app::FinanceOverView","deleted categorization rule for user id {}",&user_id);
+
+        Ok(())
+    }
+}
+
+impl DbHandlerSqlite {
+    fn read_categorization_rule_row(row: &Row) -> Result<FinanceCategorizationRule, String> {
+        let stored_rule_id = DbHandlerSqlite::get_uuid_from_row(row, "rule_id");
+        if stored_rule_id.is_err() {
+            return Err(stored_rule_id.unwrap_err());
+        }
+        let stored_title = row.get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+        let stored_priority = row.get::<_, i32>("priority");
+        if stored_priority.is_err() {
+            return Err(stored_priority.unwrap_err().to_string());
+        }
+        let stored_payee_pattern = row.get::<_, String>("payee_pattern");
+        if stored_payee_pattern.is_err() {
+            return Err(stored_payee_pattern.unwrap_err().to_string());
+        }
+        let stored_text_pattern = row.get::<_, String>("text_pattern");
+        if stored_text_pattern.is_err() {
+            return Err(stored_text_pattern.unwrap_err().to_string());
+        }
+        let stored_amount_min = row.get::<_, Option<i64>>("amount_min");
+        if stored_amount_min.is_err() {
+            return Err(stored_amount_min.unwrap_err().to_string());
+        }
+        let stored_amount_max = row.get::<_, Option<i64>>("amount_max");
+        if stored_amount_max.is_err() {
+            return Err(stored_amount_max.unwrap_err().to_string());
+        }
+        let stored_source_account_id =
+            DbHandlerSqlite::get_optional_uuid_from_row(row, "source_account_id");
+        if stored_source_account_id.is_err() {
+            return Err(stored_source_account_id.unwrap_err());
+        }
+        let stored_counter_account_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "counter_account_id");
+        if stored_counter_account_id.is_err() {
+            return Err(stored_counter_account_id.unwrap_err());
+        }
+        let stored_title_rewrite = row.get::<_, String>("title_rewrite");
+        if stored_title_rewrite.is_err() {
+            return Err(stored_title_rewrite.unwrap_err().to_string());
+        }
+        let stored_tags = DbHandlerSqlite::get_string_list_from_row(row, "tags");
+        if stored_tags.is_err() {
+            return Err(stored_tags.unwrap_err());
+        }
+
+        return Ok(FinanceCategorizationRule {
+            id: stored_rule_id.unwrap(),
+            title: stored_title.unwrap(),
+            priority: stored_priority.unwrap(),
+            payee_pattern: stored_payee_pattern.unwrap(),
+            text_pattern: stored_text_pattern.unwrap(),
+            amount_min: stored_amount_min.unwrap(),
+            amount_max: stored_amount_max.unwrap(),
+            source_account_id: stored_source_account_id.unwrap(),
+            counter_account_id: stored_counter_account_id.unwrap(),
+            title_rewrite: stored_title_rewrite.unwrap(),
+            tags: stored_tags.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceCategorizationRuleFunctions for DbHandlerSqlite {
+    async fn finance_categorization_rule_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceCategorizationRule>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceCategorizationRules WHERE user_id = ?1 ORDER BY rowid",
+            [user_id.to_string()],
+            DbHandlerSqlite::read_categorization_rule_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} categorization rules",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_categorization_rule_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule: &FinanceCategorizationRule,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result = connection.execute(
+            "INSERT INTO FinanceCategorizationRules (rule_id, user_id, title, priority, payee_pattern,
+            text_pattern, amount_min, amount_max, source_account_id, counter_account_id, title_rewrite, tags)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12)
+            ON CONFLICT (user_id, rule_id) DO UPDATE SET title = excluded.title, priority = excluded.priority,
+            payee_pattern = excluded.payee_pattern, text_pattern = excluded.text_pattern,
+            amount_min = excluded.amount_min, amount_max = excluded.amount_max,
+            source_account_id = excluded.source_account_id, counter_account_id = excluded.counter_account_id,
+            title_rewrite = excluded.title_rewrite, tags = excluded.tags",
+            params![
+                rule.id.to_string(),
+                user_id.to_string(),
+                &rule.title,
+                rule.priority,
+                &rule.payee_pattern,
+                &rule.text_pattern,
+                rule.amount_min,
+                rule.amount_max,
+                rule.source_account_id.map(|elem| elem.to_string()),
+                rule.counter_account_id.to_string(),
+                &rule.title_rewrite,
+                DbHandlerSqlite::format_string_list(&rule.tags),
+            ],
+        );
+        if upsert_result.is_err() {
+            let upsert_err = upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","upserted categorization rule for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_categorization_rule_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule_id: &Uuid,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let delete_result = connection.execute(
+            "DELETE FROM FinanceCategorizationRules WHERE user_id = ?1 AND rule_id = ?2",
+            [user_id.to_string(), rule_id.to_string()],
+        );
+        if delete_result.is_err() {
+            let delete_err = delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!("categorization rule {} not found", rule_id));
+        }
+
+        Ok(())
+    }
+}
+
+impl DbHandlerPostgres {
+    fn read_categorization_rule_row(
+        row: &PostgresRow,
+    ) -> Result<FinanceCategorizationRule, String> {
+        let stored_rule_id = DbHandlerPostgres::get_uuid_from_row(row, "rule_id");
+        if stored_rule_id.is_err() {
+            return Err(stored_rule_id.unwrap_err());
+        }
+        let stored_title = row.try_get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+        let stored_priority = row.try_get::<_, i32>("priority");
+        if stored_priority.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_priority.unwrap_err(),
+            ));
+        }
+        let stored_payee_pattern = row.try_get::<_, String>("payee_pattern");
+        if stored_payee_pattern.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_payee_pattern.unwrap_err(),
+            ));
+        }
+        let stored_text_pattern = row.try_get::<_, String>("text_pattern");
+        if stored_text_pattern.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_text_pattern.unwrap_err(),
+            ));
+        }
+        let stored_amount_min = row.try_get::<_, Option<i64>>("amount_min");
+        if stored_amount_min.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_amount_min.unwrap_err(),
+            ));
+        }
+        let stored_amount_max = row.try_get::<_, Option<i64>>("amount_max");
+        if stored_amount_max.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_amount_max.unwrap_err(),
+            ));
+        }
+        let stored_source_account_id =
+            DbHandlerPostgres::get_optional_uuid_from_row(row, "source_account_id");
+        if stored_source_account_id.is_err() {
+            return Err(stored_source_account_id.unwrap_err());
+        }
+        let stored_counter_account_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "counter_account_id");
+        if stored_counter_account_id.is_err() {
+            return Err(stored_counter_account_id.unwrap_err());
+        }
+        let stored_title_rewrite = row.try_get::<_, String>("title_rewrite");
+        if stored_title_rewrite.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_title_rewrite.unwrap_err(),
+            ));
+        }
+        let stored_tags = row.try_get::<_, Vec<String>>("tags");
+        if stored_tags.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_tags.unwrap_err()));
+        }
+
+        return Ok(FinanceCategorizationRule {
+            id: stored_rule_id.unwrap(),
+            title: stored_title.unwrap(),
+            priority: stored_priority.unwrap(),
+            payee_pattern: stored_payee_pattern.unwrap(),
+            text_pattern: stored_text_pattern.unwrap(),
+            amount_min: stored_amount_min.unwrap(),
+            amount_max: stored_amount_max.unwrap(),
+            source_account_id: stored_source_account_id.unwrap(),
+            counter_account_id: stored_counter_account_id.unwrap(),
+            title_rewrite: stored_title_rewrite.unwrap(),
+            tags: stored_tags.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceCategorizationRuleFunctions for DbHandlerPostgres {
+    async fn finance_categorization_rule_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceCategorizationRule>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceCategorizationRules WHERE user_id = $1 ORDER BY insert_order",
+            &[&user_id.to_uuid_1()],
+            DbHandlerPostgres::read_categorization_rule_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} categorization rules",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_categorization_rule_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule: &FinanceCategorizationRule,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result = client
+.execute(
+            "INSERT INTO FinanceCategorizationRules (rule_id, user_id, title, priority, payee_pattern,
+            text_pattern, amount_min, amount_max, source_account_id, counter_account_id, title_rewrite, tags)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11, $12)
+            ON CONFLICT (user_id, rule_id) DO UPDATE SET title = excluded.title, priority = excluded.priority,
+            payee_pattern = excluded.payee_pattern, text_pattern = excluded.text_pattern,
+            amount_min = excluded.amount_min, amount_max = excluded.amount_max,
+            source_account_id = excluded.source_account_id, counter_account_id = excluded.counter_account_id,
+            title_rewrite = excluded.title_rewrite, tags = excluded.tags",
+            &[
+                &rule.id.to_uuid_1(),
+                &user_id.to_uuid_1(),
+                &rule.title,
+                &rule.priority,
+                &rule.payee_pattern,
+                &rule.text_pattern,
+                &rule.amount_min,
+                &rule.amount_max,
+                &rule.source_account_id.map(|elem| elem.to_uuid_1()),
+                &rule.counter_account_id.to_uuid_1(),
+                &rule.title_rewrite,
+                &rule.tags,
+            ],
+        )
+.await;
+        if upsert_result.is_err() {
+            let upsert_err = DbHandlerPostgres::format_error(&upsert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","upserted categorization rule for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_categorization_rule_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule_id: &Uuid,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let delete_result = client
+            .execute(
+                "DELETE FROM FinanceCategorizationRules WHERE user_id = $1 AND rule_id = $2",
+                &[&user_id.to_uuid_1(), &rule_id.to_uuid_1()],
+            )
+            .await;
+        if delete_result.is_err() {
+            let delete_err = DbHandlerPostgres::format_error(&delete_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err);
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!("categorization rule {} not found", rule_id));
+        }
+
+        Ok(())
+    }
+}
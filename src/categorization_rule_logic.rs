@@ -0,0 +1,351 @@
+use async_session::chrono::{DateTime, Utc};
+use futures::executor;
+use mongodb::bson::Uuid;
+use regex_automata::meta::Regex;
+
+use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
+    accounting_logic::{normalize_tags, FinanceBookingHandle},
+    categorization_rule_database::DBFinanceCategorizationRuleFunctions,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{BankImportPreviewRow, FinanceCategorizationRule, FinanceJournalEntry},
+};
+
+/// patterns of a rule compiled once for all transactions of an import
+struct CompiledCategorizationRule<'r> {
+    rule: &'r FinanceCategorizationRule,
+    payee_regex: Option<Regex>,
+    text_regex: Option<Regex>,
+}
+
+impl<'r> CompiledCategorizationRule<'r> {
+    fn compile_pattern(pattern: &str, pattern_description: &str) -> Result<Option<Regex>, String> {
+        if pattern.is_empty() {
+            return Ok(None);
+        }
+        let regex_build = Regex::new(pattern);
+        if regex_build.is_err() {
+            return Err(format!(
+                "{} {} is not a valid regular expression: {}",
+                pattern_description,
+                pattern,
+                regex_build.unwrap_err()
+            ));
+        }
+        return Ok(Some(regex_build.unwrap()));
+    }
+
+    fn new(rule: &'r FinanceCategorizationRule) -> Result<Self, String> {
+        return Ok(Self {
+            rule,
+            payee_regex: CompiledCategorizationRule::compile_pattern(
+                &rule.payee_pattern,
+                "payee pattern",
+            )?,
+            text_regex: CompiledCategorizationRule::compile_pattern(
+                &rule.text_pattern,
+                "text pattern",
+            )?,
+        });
+    }
+
+    /// signed_amount is positive when money was received on the source account
+    fn is_matching(
+        &self,
+        source_account_id: &Uuid,
+        signed_amount: i64,
+        title: &str,
+        description: &str,
+    ) -> bool {
+        if self.rule.source_account_id.is_some()
+            && !self.rule.source_account_id.unwrap().eq(source_account_id)
+        {
+            return false;
+        }
+        if self.rule.amount_min.is_some() && signed_amount < self.rule.amount_min.unwrap() {
+            return false;
+        }
+        if self.rule.amount_max.is_some() && signed_amount > self.rule.amount_max.unwrap() {
+            return false;
+        }
+        if self.payee_regex.is_some() && !self.payee_regex.as_ref().unwrap().is_match(title) {
+            return false;
+        }
+        if self.text_regex.is_some() && !self.text_regex.as_ref().unwrap().is_match(description) {
+            return false;
+        }
+        return true;
+    }
+}
+
+pub struct FinanceCategorizationRuleHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceCategorizationRuleFunctions,
+}
+
+impl<'a> FinanceCategorizationRuleHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceCategorizationRuleFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    /// rules in the order they are evaluated
+    pub async fn rule_list(&self) -> Result<Vec<FinanceCategorizationRule>, String> {
+        let temp_var_0 = self
+            .db_connector
+            .finance_categorization_rule_list(&self.db_connection_settings, &self.user_id);
+        let rules_result = temp_var_0.await;
+        if rules_result.is_err() {
+            return Err(rules_result.unwrap_err());
+        }
+        let mut rules = rules_result.unwrap();
+        rules.sort_by_key(|elem| elem.priority);
+        return Ok(rules);
+    }
+
+    pub fn rule_list_sync(&self) -> Result<Vec<FinanceCategorizationRule>, String> {
+        let temp_var_0 = executor::block_on(self.rule_list());
+        return temp_var_0;
+    }
+
+    pub async fn rule_upsert(&self, rule: &FinanceCategorizationRule) -> Result<(), String> {
+        if rule.title.trim().is_empty() {
+            return Err("could not upsert categorization rule because title is empty".into());
+        }
+        if rule.amount_min.is_some()
+            && rule.amount_max.is_some()
+            && rule.amount_min.unwrap() > rule.amount_max.unwrap()
+        {
+            return Err(
+                "could not upsert categorization rule because minimum amount is greater than maximum amount"
+                    .into(),
+            );
+        }
+        let compile_result = CompiledCategorizationRule::new(rule);
+        if compile_result.is_err() {
+            return Err(format!(
+                "could not upsert categorization rule because {}",
+                compile_result.err().unwrap()
+            ));
+        }
+
+        let mut account_ids = vec![rule.counter_account_id];
+        if rule.source_account_id.is_some() {
+            account_ids.push(rule.source_account_id.unwrap());
+        }
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle
+            .finance_account_list_async(Some(&account_ids))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        let account_list = account_list_result.unwrap();
+        if !account_ids
+            .iter()
+            .all(|account_id| account_list.iter().any(|elem| elem.id.eq(account_id)))
+        {
+            return Err(
+                "could not upsert categorization rule because an account is unknown".into(),
+            );
+        }
+
+        let mut rule_to_save = rule.clone();
+        rule_to_save.title = rule.title.trim().to_string();
+        rule_to_save.tags = normalize_tags(&rule.tags);
+        let temp_var_0 = self.db_connector.finance_categorization_rule_upsert(
+            &self.db_connection_settings,
+            &self.user_id,
+            &rule_to_save,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn rule_upsert_sync(&self, rule: &FinanceCategorizationRule) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.rule_upsert(rule));
+        return temp_var_0;
+    }
+
+    pub async fn rule_delete(&self, rule_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = self.db_connector.finance_categorization_rule_delete(
+            &self.db_connection_settings,
+            &self.user_id,
+            rule_id,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn rule_delete_sync(&self, rule_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.rule_delete(rule_id));
+        return temp_var_0;
+    }
+
+    /// applies the first matching rule to every row with a booking entry: the counter account of
+    /// the bank account is replaced, the title rewritten and the tags added,
+    /// returns the number of changed rows
+    pub fn apply_rules(
+        rules: &Vec<FinanceCategorizationRule>,
+        bank_account_id: &Uuid,
+        preview_rows: &mut Vec<BankImportPreviewRow>,
+    ) -> Result<usize, String> {
+        let mut sorted_rules: Vec<&FinanceCategorizationRule> = rules.iter().collect();
+        sorted_rules.sort_by_key(|elem| elem.priority);
+        let mut compiled_rules = Vec::new();
+        for rule in sorted_rules {
+            let compile_result = CompiledCategorizationRule::new(rule);
+            if compile_result.is_err() {
+                return Err(format!(
+                    "rule {}: {}",
+                    rule.title,
+                    compile_result.err().unwrap()
+                ));
+            }
+            compiled_rules.push(compile_result.ok().unwrap());
+        }
+
+        let mut changed_rows = 0;
+        for preview_row in preview_rows.iter_mut() {
+            if preview_row.booking_request.is_none() {
+                continue;
+            }
+            let booking_request = preview_row.booking_request.as_mut().unwrap();
+            let is_incoming = booking_request.debit_finance_account_id.eq(bank_account_id);
+            let signed_amount = if is_incoming {
+                booking_request.amount as i64
+            } else {
+                -(booking_request.amount as i64)
+            };
+            let matching_rule = compiled_rules.iter().find(|elem| {
+                elem.is_matching(
+                    bank_account_id,
+                    signed_amount,
+                    &booking_request.title,
+                    &booking_request.description,
+                )
+            });
+            if matching_rule.is_none() {
+                continue;
+            }
+            let rule = matching_rule.unwrap().rule;
+            if is_incoming {
+                booking_request.credit_finance_account_id = rule.counter_account_id;
+            } else {
+                booking_request.debit_finance_account_id = rule.counter_account_id;
+            }
+            if !rule.title_rewrite.is_empty() {
+                booking_request.title = rule.title_rewrite.clone();
+            }
+            let mut tags = booking_request.tags.clone();
+            tags.extend(rule.tags.iter().cloned());
+            booking_request.tags = normalize_tags(&tags);
+            changed_rows += 1;
+        }
+        return Ok(changed_rows);
+    }
+
+    /// applies the rules of the user to the preview rows of a bank import
+    pub async fn categorize_preview_rows(
+        &self,
+        bank_account_id: &Uuid,
+        preview_rows: &mut Vec<BankImportPreviewRow>,
+    ) -> Result<usize, String> {
+        let rule_list_result = self.rule_list().await;
+        if rule_list_result.is_err() {
+            return Err(rule_list_result.unwrap_err());
+        }
+        return FinanceCategorizationRuleHandle::apply_rules(
+            &rule_list_result.unwrap(),
+            bank_account_id,
+            preview_rows,
+        );
+    }
+
+    pub fn categorize_preview_rows_sync(
+        &self,
+        bank_account_id: &Uuid,
+        preview_rows: &mut Vec<BankImportPreviewRow>,
+    ) -> Result<usize, String> {
+        let temp_var_0 =
+            executor::block_on(self.categorize_preview_rows(bank_account_id, preview_rows));
+        return temp_var_0;
+    }
+
+    /// journal entries of the time range the rule would have matched, reversed entries are ignored,
+    /// without a source account the entry is checked from the view of its debit and its credit account
+    pub async fn test_rule(
+        &self,
+        rule: &FinanceCategorizationRule,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+    ) -> Result<Vec<FinanceJournalEntry>, String> {
+        let compile_result = CompiledCategorizationRule::new(rule);
+        if compile_result.is_err() {
+            return Err(compile_result.err().unwrap());
+        }
+        let compiled_rule = compile_result.ok().unwrap();
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let journal_entries_result = booking_handle
+            .list_journal_entries(booking_time_from, booking_time_till)
+            .await;
+        if journal_entries_result.is_err() {
+            return Err(journal_entries_result.unwrap_err());
+        }
+
+        let matching_entries = journal_entries_result
+            .unwrap()
+            .into_iter()
+            .filter(|elem| {
+                elem.reverse_of_journal_entry_id.is_none()
+                    && elem.reversed_by_journal_entry_id.is_none()
+            })
+            .filter(|elem| {
+                // money is received on the debit account and paid from the credit account
+                compiled_rule.is_matching(
+                    &elem.debit_finance_account_id,
+                    elem.amount as i64,
+                    &elem.title,
+                    &elem.description,
+                ) || compiled_rule.is_matching(
+                    &elem.credit_finance_account_id,
+                    -(elem.amount as i64),
+                    &elem.title,
+                    &elem.description,
+                )
+            })
+            .collect();
+        return Ok(matching_entries);
+    }
+
+    pub fn test_rule_sync(
+        &self,
+        rule: &FinanceCategorizationRule,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+    ) -> Result<Vec<FinanceJournalEntry>, String> {
+        let temp_var_0 =
+            executor::block_on(self.test_rule(rule, booking_time_from, booking_time_till));
+        return temp_var_0;
+    }
+}
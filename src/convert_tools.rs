@@ -1,6 +1,7 @@
 use std::path::PathBuf;
 
 
+use async_session::chrono::{DateTime, NaiveDate, NaiveTime, TimeZone, Utc};
 use mongodb::bson::{uuid, Document};
 
 pub struct ConvertTools {}
@@ -26,6 +27,127 @@ impl ConvertTools {
         return Ok(uuid_var.into());
     }
 
+    /// returns None if the field is not present or null, used for fields added after first release
+    pub fn get_optional_uuid_from_document(
+        a_doc: &Document,
+        field_name: &str,
+    ) -> Result<Option<uuid::Uuid>, String> {
+        let bson_option = a_doc.get(field_name);
+        if bson_option.is_none() {
+            return Ok(None);
+        }
+        if bson_option.unwrap().eq(&mongodb::bson::Bson::Null) {
+            return Ok(None);
+        }
+        let uuid_parse_result = ConvertTools::get_uuid_from_document(a_doc, field_name);
+        if uuid_parse_result.is_err() {
+            return Err(uuid_parse_result.unwrap_err());
+        }
+
+        return Ok(Some(uuid_parse_result.unwrap()));
+    }
+
+    /// returns None if the field is not present or null
+    pub fn get_optional_datetime_from_document(
+        a_doc: &Document,
+        field_name: &str,
+    ) -> Result<Option<DateTime<Utc>>, String> {
+        let bson_option = a_doc.get(field_name);
+        if bson_option.is_none() {
+            return Ok(None);
+        }
+        if bson_option.unwrap().eq(&mongodb::bson::Bson::Null) {
+            return Ok(None);
+        }
+        let datetime_parse_result = a_doc.get_datetime(field_name);
+        if datetime_parse_result.is_err() {
+            return Err(datetime_parse_result.unwrap_err().to_string());
+        }
+
+        return Ok(Some(datetime_parse_result.unwrap().to_chrono()));
+    }
+
+    /// returns an empty list if the field is not present (e.g. documents stored before the field was introduced)
+    pub fn get_string_list_from_document(
+        a_doc: &Document,
+        field_name: &str,
+    ) -> Result<Vec<String>, String> {
+        if a_doc.get(field_name).is_none() {
+            return Ok(Vec::new());
+        }
+        let array_parse_result = a_doc.get_array(field_name);
+        if array_parse_result.is_err() {
+            return Err(array_parse_result.unwrap_err().to_string());
+        }
+        let mut return_list = Vec::new();
+        for array_element in array_parse_result.unwrap() {
+            let string_option = array_element.as_str();
+            if string_option.is_none() {
+                return Err(format!(
+                    "{} contains a value that is not a string",
+                    field_name
+                ));
+            }
+            return_list.push(string_option.unwrap().to_string());
+        }
+        return Ok(return_list);
+    }
+
+    /// parses a date from a html date input (YYYY-MM-DD), returns start (00:00:00) or end (23:59:59) of that day in UTC
+    pub fn get_utc_time_from_date_input(
+        input: &str,
+        end_of_day: bool,
+    ) -> Result<DateTime<Utc>, String> {
+        let date_parse_result = NaiveDate::parse_from_str(input.trim(), "%Y-%m-%d");
+        if date_parse_result.is_err() {
+            return Err(format!(
+                "could not parse date {}: {}",
+                input,
+                date_parse_result.unwrap_err()
+            ));
+        }
+        let date_value = date_parse_result.unwrap();
+        let time_value = if end_of_day {
+            date_value.and_hms_opt(23, 59, 59).unwrap()
+        } else {
+            date_value.and_hms_opt(0, 0, 0).unwrap()
+        };
+
+        return Ok(Utc.from_utc_datetime(&time_value));
+    }
+
+    /// parses a date (YYYY-MM-DD) and a time (HH:MM or HH:MM:SS) from html inputs as UTC time
+    pub fn get_utc_time_from_date_time_input(
+        date_input: &str,
+        time_input: &str,
+    ) -> Result<DateTime<Utc>, String> {
+        let date_parse_result = NaiveDate::parse_from_str(date_input.trim(), "%Y-%m-%d");
+        if date_parse_result.is_err() {
+            return Err(format!(
+                "could not parse date {}: {}",
+                date_input,
+                date_parse_result.unwrap_err()
+            ));
+        }
+        let mut time_parse_result = NaiveTime::parse_from_str(time_input.trim(), "%H:%M:%S");
+        if time_parse_result.is_err() {
+            time_parse_result = NaiveTime::parse_from_str(time_input.trim(), "%H:%M");
+        }
+        if time_parse_result.is_err() {
+            return Err(format!(
+                "could not parse time {}: {}",
+                time_input,
+                time_parse_result.unwrap_err()
+            ));
+        }
+
+        let date_time_value = date_parse_result
+            .unwrap()
+            .and_time(time_parse_result.unwrap());
+
+        return Ok(Utc.from_utc_datetime(&date_time_value));
+    }
+
     pub fn load_text_from_file(filepath: &PathBuf) -> Result<String, String> {
         let file = std::fs::File::open(filepath);
         if file.is_err() {
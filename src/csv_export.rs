@@ -0,0 +1,283 @@
+use async_session::chrono::{DateTime, Utc};
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
+    accounting_database::DBFinanceAccountingFunctions,
+    accounting_logic::FinanceBookingHandle,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::CsvExportSettings,
+    frontend_functions::{
+        generate_account_tables_in_range, generate_review_journal_entries_in_range,
+    },
+    html_render::{AccountTableTemplate, JournalTableRow},
+};
+
+pub struct FinanceCsvExportHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceAccountingFunctions,
+}
+
+impl<'a> FinanceCsvExportHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceAccountingFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    pub fn check_settings(settings: &CsvExportSettings) -> Result<(), String> {
+        if settings.decimal_separator != '.' && settings.decimal_separator != ',' {
+            return Err(format!(
+                "decimal separator {} is not supported, use . or ,",
+                settings.decimal_separator
+            ));
+        }
+        if settings.delimiter == '"' || settings.delimiter == '\r' || settings.delimiter == '\n' {
+            return Err("the delimiter can not be a quote or a line break".into());
+        }
+        if settings.delimiter == settings.decimal_separator {
+            return Err("delimiter and decimal separator must be different".into());
+        }
+        return Ok(());
+    }
+
+    /// writes an amount in cents as exact decimal with two digits, e.g. -1234 as -12.34
+    pub fn format_amount(amount_cents: i128, decimal_separator: char) -> String {
+        let sign = if amount_cents < 0 { "-" } else { "" };
+        let absolute_amount = amount_cents.unsigned_abs();
+        return format!(
+            "{}{}{}{:02}",
+            sign,
+            absolute_amount / 100,
+            decimal_separator,
+            absolute_amount % 100
+        );
+    }
+
+    /// fields containing the delimiter, quotes or line breaks are quoted, quotes inside are doubled (RFC 4180)
+    pub fn escape_field(value: &str, delimiter: char) -> String {
+        if !value.contains(delimiter)
+            && !value.contains('"')
+            && !value.contains('\r')
+            && !value.contains('\n')
+        {
+            return value.to_string();
+        }
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+
+    fn create_line(fields: Vec<String>, delimiter: char) -> String {
+        let escaped_fields: Vec<String> = fields
+            .iter()
+            .map(|elem| FinanceCsvExportHandle::escape_field(elem, delimiter))
+            .collect();
+        return format!("{}\r\n", escaped_fields.join(&delimiter.to_string()));
+    }
+
+    fn format_time(booking_time: &DateTime<Utc>) -> String {
+        return booking_time.format("%Y-%m-%d %H:%M:%S").to_string();
+    }
+
+    pub fn journal_rows_to_csv(
+        rows: &Vec<JournalTableRow>,
+        settings: &CsvExportSettings,
+    ) -> String {
+        let mut sorted_rows: Vec<&JournalTableRow> = rows.iter().collect();
+        sorted_rows.sort_by_key(|elem| (elem.booking_time, elem.running_number));
+
+        let mut return_object = FinanceCsvExportHandle::create_line(
+            vec![
+                "number".into(),
+                "booking time".into(),
+                "debit account".into(),
+                "credit account".into(),
+                "amount".into(),
+                "title".into(),
+                "description".into(),
+                "tags".into(),
+                "reversal".into(),
+            ],
+            settings.delimiter,
+        );
+        for row in sorted_rows {
+            return_object.push_str(&FinanceCsvExportHandle::create_line(
+                vec![
+                    row.running_number.to_string(),
+                    FinanceCsvExportHandle::format_time(&row.booking_time),
+                    row.debit_account_name.clone(),
+                    row.credit_account_name.clone(),
+                    FinanceCsvExportHandle::format_amount(
+                        row.amount_cents as i128,
+                        settings.decimal_separator,
+                    ),
+                    row.title.clone(),
+                    row.description.clone(),
+                    row.tags.clone(),
+                    row.reversal_info.clone(),
+                ],
+                settings.delimiter,
+            ));
+        }
+        return return_object;
+    }
+
+    /// one line per booking entry, the amount is written to the debit or credit column,
+    /// the last line of every account holds its balance
+    pub fn account_tables_to_csv(
+        tables: &Vec<AccountTableTemplate>,
+        settings: &CsvExportSettings,
+    ) -> String {
+        let mut return_object = FinanceCsvExportHandle::create_line(
+            vec![
+                "account".into(),
+                "booking time".into(),
+                "kind".into(),
+                "debit".into(),
+                "credit".into(),
+                "title".into(),
+                "reversal".into(),
+            ],
+            settings.delimiter,
+        );
+        for table in tables {
+            for row in &table.booking_rows {
+                let amount_text = FinanceCsvExportHandle::format_amount(
+                    row.amount_cents as i128,
+                    settings.decimal_separator,
+                );
+                let kind_text = if row.is_saldo {
+                    "balance"
+                } else if row.is_credit {
+                    "credit"
+                } else {
+                    "debit"
+                };
+                let (debit_text, credit_text) = if row.is_credit {
+                    ("".to_string(), amount_text)
+                } else {
+                    (amount_text, "".to_string())
+                };
+                return_object.push_str(&FinanceCsvExportHandle::create_line(
+                    vec![
+                        table.account_name.clone(),
+                        FinanceCsvExportHandle::format_time(&row.booking_time),
+                        kind_text.into(),
+                        debit_text,
+                        credit_text,
+                        row.title.clone(),
+                        row.reversal_info.clone(),
+                    ],
+                    settings.delimiter,
+                ));
+            }
+        }
+        return return_object;
+    }
+
+    pub async fn export_journal(
+        &self,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        settings: &CsvExportSettings,
+    ) -> Result<String, String> {
+        let check_result = FinanceCsvExportHandle::check_settings(settings);
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let rows_result = generate_review_journal_entries_in_range(
+            &booking_handle,
+            &config_handle,
+            None,
+            booking_time_from,
+            booking_time_till,
+        )
+        .await;
+        if rows_result.is_err() {
+            return Err(rows_result.unwrap_err().to_string());
+        }
+        return Ok(FinanceCsvExportHandle::journal_rows_to_csv(
+            &rows_result.unwrap(),
+            settings,
+        ));
+    }
+
+    pub fn export_journal_sync(
+        &self,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        settings: &CsvExportSettings,
+    ) -> Result<String, String> {
+        let temp_var_0 =
+            executor::block_on(self.export_journal(booking_time_from, booking_time_till, settings));
+        return temp_var_0;
+    }
+
+    pub async fn export_account_tables(
+        &self,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        settings: &CsvExportSettings,
+    ) -> Result<String, String> {
+        let check_result = FinanceCsvExportHandle::check_settings(settings);
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let tables_result = generate_account_tables_in_range(
+            &booking_handle,
+            &config_handle,
+            None,
+            booking_time_from,
+            booking_time_till,
+        )
+        .await;
+        if tables_result.is_err() {
+            return Err(tables_result.unwrap_err().to_string());
+        }
+        return Ok(FinanceCsvExportHandle::account_tables_to_csv(
+            &tables_result.unwrap(),
+            settings,
+        ));
+    }
+
+    pub fn export_account_tables_sync(
+        &self,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        settings: &CsvExportSettings,
+    ) -> Result<String, String> {
+        let temp_var_0 = executor::block_on(self.export_account_tables(
+            booking_time_from,
+            booking_time_till,
+            settings,
+        ));
+        return temp_var_0;
+    }
+}
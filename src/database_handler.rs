@@ -0,0 +1,1545 @@
+use std::collections::HashMap;
+
+use async_session::chrono::{DateTime, Utc};
+use axum::async_trait;
+use mongodb::bson::Uuid;
+use secrecy::Secret;
+
+use crate::{
+    accounting_config_database::DBFinanceConfigFunctions,
+    accounting_database::{
+        DBFinanceAccountingFunctions, FinanceAccountBookingEntryListSearchOption,
+    },
+    attachment_database::DBFinanceAttachmentFunctions,
+    booking_template_database::DBFinanceBookingTemplateFunctions,
+    budget_database::DBFinanceBudgetFunctions,
+    categorization_rule_database::DBFinanceCategorizationRuleFunctions,
+    database_handler_mongodb::{
+        DbBackendType, DbConnectionSetting, DbHandlerMongoDB, EmailVerificationStatus,
+    },
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::{
+        FinanceAccount, FinanceAccountBookingEntry, FinanceAccountType, FinanceBackupArchive,
+        FinanceBookingRequest, FinanceBookingResult, FinanceBookingTemplate, FinanceBudget,
+        FinanceCategorizationRule, FinanceJournalAttachment, FinanceJournalEntry,
+        FinanceReconciliation, FinanceRecurringBooking, FinanceReportSectionMapping,
+        FinanceSplitBookingRequest, FinanceSplitBookingResult, GenerallUserData,
+        PasswordResetTokenRequestResult,
+    },
+    password_handle::{StoredCredentials, UserCredentialsHashed},
+    reconciliation_database::DBFinanceReconciliationFunctions,
+    recurring_booking_database::DBFinanceRecurringBookingFunctions,
+    report_section_database::DBFinanceReportSectionFunctions,
+    user_database::DBUserFunctions,
+};
+
+/// database handler of the backend chosen in the server settings (DB_Type),
+/// the web handlers only use this type so they do not depend on a backend
+pub enum DbHandler {
+    MongoDB(DbHandlerMongoDB),
+    SQLite(DbHandlerSqlite),
+    PostgreSQL(DbHandlerPostgres),
+}
+
+impl DbHandler {
+    pub fn new(connection_settings: &DbConnectionSetting) -> DbHandler {
+        match DbBackendType::from_global_setting() {
+            DbBackendType::MongoDB => {
+                DbHandler::MongoDB(DbHandlerMongoDB::new(connection_settings))
+            }
+            DbBackendType::SQLite => DbHandler::SQLite(DbHandlerSqlite::new()),
+            DbBackendType::PostgreSQL => DbHandler::PostgreSQL(DbHandlerPostgres::new()),
+        }
+    }
+
+    pub fn validate_db_structure(conncetion_settings: &DbConnectionSetting) -> bool {
+        match DbBackendType::from_global_setting() {
+            DbBackendType::MongoDB => DbHandlerMongoDB::validate_db_structure(conncetion_settings),
+            DbBackendType::SQLite => DbHandlerSqlite::validate_db_structure(conncetion_settings),
+            DbBackendType::PostgreSQL => {
+                DbHandlerPostgres::validate_db_structure(conncetion_settings)
+            }
+        }
+    }
+
+    pub fn increase_route_calling_amount(
+        conncetion_settings: &DbConnectionSetting,
+        route_name: &String,
+    ) -> Result<i32, String> {
+        match DbBackendType::from_global_setting() {
+            DbBackendType::MongoDB => {
+                DbHandlerMongoDB::increase_route_calling_amount(conncetion_settings, route_name)
+            }
+            DbBackendType::SQLite => {
+                DbHandlerSqlite::increase_route_calling_amount(conncetion_settings, route_name)
+            }
+            DbBackendType::PostgreSQL => {
+                DbHandlerPostgres::increase_route_calling_amount(conncetion_settings, route_name)
+            }
+        }
+    }
+
+    pub async fn repair_counter_record_for_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .repair_counter_record_for_user(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .repair_counter_record_for_user(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .repair_counter_record_for_user(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait]
+impl DBUserFunctions for DbHandler {
+    async fn check_user_exsists_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<bool, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .check_user_exsists_by_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .check_user_exsists_by_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .check_user_exsists_by_name(conncetion_settings, user_name)
+                    .await
+            }
+        }
+    }
+
+    async fn insert_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<Uuid, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .insert_user(conncetion_settings, some_credentials)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .insert_user(conncetion_settings, some_credentials)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .insert_user(conncetion_settings, some_credentials)
+                    .await
+            }
+        }
+    }
+
+    async fn update_user_password(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<bool, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .update_user_password(conncetion_settings, some_credentials)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .update_user_password(conncetion_settings, some_credentials)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .update_user_password(conncetion_settings, some_credentials)
+                    .await
+            }
+        }
+    }
+
+    async fn get_stored_credentials_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<StoredCredentials, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .get_stored_credentials_by_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .get_stored_credentials_by_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .get_stored_credentials_by_name(conncetion_settings, user_name)
+                    .await
+            }
+        }
+    }
+
+    async fn update_user_email(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        new_email: &String,
+    ) -> Result<String, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .update_user_email(conncetion_settings, user_name, new_email)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .update_user_email(conncetion_settings, user_name, new_email)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .update_user_email(conncetion_settings, user_name, new_email)
+                    .await
+            }
+        }
+    }
+
+    async fn check_email_verfification_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<EmailVerificationStatus, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .check_email_verfification_by_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .check_email_verfification_by_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .check_email_verfification_by_name(conncetion_settings, user_name)
+                    .await
+            }
+        }
+    }
+
+    async fn verify_email_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        email_validation_string: &Secret<String>,
+    ) -> Result<EmailVerificationStatus, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .verify_email_by_name(conncetion_settings, user_name, email_validation_string)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .verify_email_by_name(conncetion_settings, user_name, email_validation_string)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .verify_email_by_name(conncetion_settings, user_name, email_validation_string)
+                    .await
+            }
+        }
+    }
+
+    async fn get_user_general_data_by_user_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<GenerallUserData, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .get_user_general_data_by_user_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .get_user_general_data_by_user_name(conncetion_settings, user_name)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .get_user_general_data_by_user_name(conncetion_settings, user_name)
+                    .await
+            }
+        }
+    }
+
+    async fn update_general_user_data_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        general_user_data: &GenerallUserData,
+    ) -> Result<String, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .update_general_user_data_by_name(
+                        conncetion_settings,
+                        user_name,
+                        general_user_data,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .update_general_user_data_by_name(
+                        conncetion_settings,
+                        user_name,
+                        general_user_data,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .update_general_user_data_by_name(
+                        conncetion_settings,
+                        user_name,
+                        general_user_data,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn update_user_reset_secret(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reset_secret_hash: &Secret<String>,
+    ) -> Result<bool, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .update_user_reset_secret(conncetion_settings, user_id, reset_secret_hash)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .update_user_reset_secret(conncetion_settings, user_id, reset_secret_hash)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .update_user_reset_secret(conncetion_settings, user_id, reset_secret_hash)
+                    .await
+            }
+        }
+    }
+
+    async fn generate_passwort_reset_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_secret: &Secret<String>,
+        passwort_reset_time_limit_minutes: &u16,
+    ) -> Result<PasswordResetTokenRequestResult, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .generate_passwort_reset_token(
+                        conncetion_settings,
+                        user_name,
+                        reset_secret,
+                        passwort_reset_time_limit_minutes,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .generate_passwort_reset_token(
+                        conncetion_settings,
+                        user_name,
+                        reset_secret,
+                        passwort_reset_time_limit_minutes,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .generate_passwort_reset_token(
+                        conncetion_settings,
+                        user_name,
+                        reset_secret,
+                        passwort_reset_time_limit_minutes,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn change_password_with_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_token: &String,
+        new_password: &Secret<String>,
+    ) -> Result<bool, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .change_password_with_token(
+                        conncetion_settings,
+                        user_name,
+                        reset_token,
+                        new_password,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .change_password_with_token(
+                        conncetion_settings,
+                        user_name,
+                        reset_token,
+                        new_password,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .change_password_with_token(
+                        conncetion_settings,
+                        user_name,
+                        reset_token,
+                        new_password,
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceConfigFunctions for DbHandler {
+    async fn finance_account_type_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceAccountType>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_account_type_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_account_type_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_account_type_list(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_account_type_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type: &FinanceAccountType,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_account_type_upsert(conncetion_settings, user_id, finance_account_type)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_account_type_upsert(conncetion_settings, user_id, finance_account_type)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_account_type_upsert(conncetion_settings, user_id, finance_account_type)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_account_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        limit_account_ids: Option<&Vec<Uuid>>,
+    ) -> Result<Vec<FinanceAccount>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_account_list(conncetion_settings, user_id, limit_account_ids)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_account_list(conncetion_settings, user_id, limit_account_ids)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_account_list(conncetion_settings, user_id, limit_account_ids)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_account_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account: &FinanceAccount,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_account_upsert(conncetion_settings, user_id, finance_account)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_account_upsert(conncetion_settings, user_id, finance_account)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_account_upsert(conncetion_settings, user_id, finance_account)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceAccountingFunctions for DbHandler {
+    async fn finance_journal_entry_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_time_from: Option<DateTime<Utc>>,
+        booking_time_till: Option<DateTime<Utc>>,
+        tag: Option<String>,
+    ) -> Result<Vec<FinanceJournalEntry>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_journal_entry_list(
+                        conncetion_settings,
+                        user_id,
+                        booking_time_from,
+                        booking_time_till,
+                        tag,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_journal_entry_list(
+                        conncetion_settings,
+                        user_id,
+                        booking_time_from,
+                        booking_time_till,
+                        tag,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_journal_entry_list(
+                        conncetion_settings,
+                        user_id,
+                        booking_time_from,
+                        booking_time_till,
+                        tag,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_account_booking_entry_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        search_options: Vec<FinanceAccountBookingEntryListSearchOption>,
+    ) -> Result<Vec<FinanceAccountBookingEntry>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_account_booking_entry_list(
+                        conncetion_settings,
+                        user_id,
+                        search_options,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_account_booking_entry_list(
+                        conncetion_settings,
+                        user_id,
+                        search_options,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_account_booking_entry_list(
+                        conncetion_settings,
+                        user_id,
+                        search_options,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_insert_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceBookingRequest,
+    ) -> Result<FinanceBookingResult, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_insert_booking_entry(conncetion_settings, user_id, action_to_insert)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_insert_booking_entry(conncetion_settings, user_id, action_to_insert)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_insert_booking_entry(conncetion_settings, user_id, action_to_insert)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_insert_split_booking_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_insert_split_booking_entry(
+                        conncetion_settings,
+                        user_id,
+                        action_to_insert,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_insert_split_booking_entry(
+                        conncetion_settings,
+                        user_id,
+                        action_to_insert,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_insert_split_booking_entry(
+                        conncetion_settings,
+                        user_id,
+                        action_to_insert,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_insert_split_booking_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_insert_split_booking_entries(
+                        conncetion_settings,
+                        user_id,
+                        actions_to_insert,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_insert_split_booking_entries(
+                        conncetion_settings,
+                        user_id,
+                        actions_to_insert,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_insert_split_booking_entries(
+                        conncetion_settings,
+                        user_id,
+                        actions_to_insert,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_reverse_journal_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_reverse_journal_entry(
+                        conncetion_settings,
+                        user_id,
+                        journal_entry_id,
+                        booking_time,
+                        reason,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_reverse_journal_entry(
+                        conncetion_settings,
+                        user_id,
+                        journal_entry_id,
+                        booking_time,
+                        reason,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_reverse_journal_entry(
+                        conncetion_settings,
+                        user_id,
+                        journal_entry_id,
+                        booking_time,
+                        reason,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_get_last_saldo_account_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        list_account_ids: Option<Vec<Uuid>>,
+    ) -> Result<HashMap<Uuid, FinanceAccountBookingEntry>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_get_last_saldo_account_entries(
+                        conncetion_settings,
+                        user_id,
+                        list_account_ids,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_get_last_saldo_account_entries(
+                        conncetion_settings,
+                        user_id,
+                        list_account_ids,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_get_last_saldo_account_entries(
+                        conncetion_settings,
+                        user_id,
+                        list_account_ids,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_get_journal_max_number(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<u64, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_get_journal_max_number(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_get_journal_max_number(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_get_journal_max_number(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_restore_archive(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_restore_archive(conncetion_settings, user_id, archive)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_restore_archive(conncetion_settings, user_id, archive)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_restore_archive(conncetion_settings, user_id, archive)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceRecurringBookingFunctions for DbHandler {
+    async fn finance_recurring_booking_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceRecurringBooking>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_list(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_recurring_booking_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_upsert(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_upsert(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_upsert(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_recurring_booking_set_last_booking_time(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+        last_booking_time: &DateTime<Utc>,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_set_last_booking_time(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking_id,
+                        last_booking_time,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_set_last_booking_time(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking_id,
+                        last_booking_time,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_set_last_booking_time(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking_id,
+                        last_booking_time,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_recurring_booking_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_delete(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking_id,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_delete(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking_id,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_delete(
+                        conncetion_settings,
+                        user_id,
+                        recurring_booking_id,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_recurring_booking_list_user_ids(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Vec<Uuid>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_list_user_ids(conncetion_settings)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_list_user_ids(conncetion_settings)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_recurring_booking_list_user_ids(conncetion_settings)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBookingTemplateFunctions for DbHandler {
+    async fn finance_booking_template_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBookingTemplate>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_booking_template_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_booking_template_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_booking_template_list(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_booking_template_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_booking_template_upsert(conncetion_settings, user_id, booking_template)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_booking_template_upsert(conncetion_settings, user_id, booking_template)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_booking_template_upsert(conncetion_settings, user_id, booking_template)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_booking_template_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template_id: &Uuid,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_booking_template_delete(
+                        conncetion_settings,
+                        user_id,
+                        booking_template_id,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_booking_template_delete(
+                        conncetion_settings,
+                        user_id,
+                        booking_template_id,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_booking_template_delete(
+                        conncetion_settings,
+                        user_id,
+                        booking_template_id,
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceBudgetFunctions for DbHandler {
+    async fn finance_budget_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBudget>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_budget_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_budget_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_budget_list(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_budget_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget: &FinanceBudget,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_budget_upsert(conncetion_settings, user_id, budget)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_budget_upsert(conncetion_settings, user_id, budget)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_budget_upsert(conncetion_settings, user_id, budget)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_budget_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget_id: &Uuid,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_budget_delete(conncetion_settings, user_id, budget_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_budget_delete(conncetion_settings, user_id, budget_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_budget_delete(conncetion_settings, user_id, budget_id)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReportSectionFunctions for DbHandler {
+    async fn finance_report_section_mapping_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceReportSectionMapping>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_list(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_report_section_mapping_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_upsert(
+                        conncetion_settings,
+                        user_id,
+                        report_section_mapping,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_upsert(
+                        conncetion_settings,
+                        user_id,
+                        report_section_mapping,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_upsert(
+                        conncetion_settings,
+                        user_id,
+                        report_section_mapping,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_report_section_mapping_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_delete(
+                        conncetion_settings,
+                        user_id,
+                        finance_account_type_id,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_delete(
+                        conncetion_settings,
+                        user_id,
+                        finance_account_type_id,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_report_section_mapping_delete(
+                        conncetion_settings,
+                        user_id,
+                        finance_account_type_id,
+                    )
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceCategorizationRuleFunctions for DbHandler {
+    async fn finance_categorization_rule_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceCategorizationRule>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_list(conncetion_settings, user_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_list(conncetion_settings, user_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_categorization_rule_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule: &FinanceCategorizationRule,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_upsert(conncetion_settings, user_id, rule)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_upsert(conncetion_settings, user_id, rule)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_upsert(conncetion_settings, user_id, rule)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_categorization_rule_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule_id: &Uuid,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_delete(conncetion_settings, user_id, rule_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_delete(conncetion_settings, user_id, rule_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_categorization_rule_delete(conncetion_settings, user_id, rule_id)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReconciliationFunctions for DbHandler {
+    async fn finance_reconciliation_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_reconciliation_list(conncetion_settings, user_id, finance_account_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_reconciliation_list(conncetion_settings, user_id, finance_account_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_reconciliation_list(conncetion_settings, user_id, finance_account_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_reconciliation_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation: &FinanceReconciliation,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_reconciliation_insert(
+                        conncetion_settings,
+                        user_id,
+                        reconciliation,
+                        booking_entry_ids,
+                    )
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_reconciliation_insert(
+                        conncetion_settings,
+                        user_id,
+                        reconciliation,
+                        booking_entry_ids,
+                    )
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_reconciliation_insert(
+                        conncetion_settings,
+                        user_id,
+                        reconciliation,
+                        booking_entry_ids,
+                    )
+                    .await
+            }
+        }
+    }
+
+    async fn finance_reconciliation_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation_id: &Uuid,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_reconciliation_delete(conncetion_settings, user_id, reconciliation_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_reconciliation_delete(conncetion_settings, user_id, reconciliation_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_reconciliation_delete(conncetion_settings, user_id, reconciliation_id)
+                    .await
+            }
+        }
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceAttachmentFunctions for DbHandler {
+    async fn finance_journal_attachment_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_list(conncetion_settings, user_id, journal_entry_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_list(conncetion_settings, user_id, journal_entry_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_list(conncetion_settings, user_id, journal_entry_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_journal_attachment_get(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> Result<Option<FinanceJournalAttachment>, String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_get(conncetion_settings, user_id, attachment_id)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_get(conncetion_settings, user_id, attachment_id)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_get(conncetion_settings, user_id, attachment_id)
+                    .await
+            }
+        }
+    }
+
+    async fn finance_journal_attachment_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment: &FinanceJournalAttachment,
+    ) -> Result<(), String> {
+        match self {
+            DbHandler::MongoDB(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_insert(conncetion_settings, user_id, attachment)
+                    .await
+            }
+            DbHandler::SQLite(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_insert(conncetion_settings, user_id, attachment)
+                    .await
+            }
+            DbHandler::PostgreSQL(db_handler) => {
+                db_handler
+                    .finance_journal_attachment_insert(conncetion_settings, user_id, attachment)
+                    .await
+            }
+        }
+    }
+}
@@ -1,25 +1,15 @@
-use argon2::{Argon2, PasswordHasher};
-use async_session::chrono::Duration;
 use futures::{executor, StreamExt};
-use log::{debug, info, trace, warn};
+use log::{info, trace, warn};
 use mongodb::{
-    bson::{doc, uuid, Document, Uuid},
-    options::{ClientOptions, Credential, FindOptions},
+    bson::{doc, Bson, Document, Uuid},
+    options::{ClientOptions, Credential},
     results::{InsertOneResult, UpdateResult},
     Client, Collection, Cursor,
 };
 
-use secrecy::{ExposeSecret, Secret};
-
-use crate::{
-    convert_tools::ConvertTools, datatypes::GenerallUserData, mail_handle::validate_email_format,
-    password_handle::verify_password_hash,
-};
-use crate::{
-    datatypes::PasswordResetTokenRequestResult,
-    password_handle::{StoredCredentials, UserCredentialsHashed},
-};
+use crate::{mdb_convert_tools::MdbConvertTools, setting_struct::GLOBAL_SETTING};
 
+#[derive(Clone)]
 pub struct DbConnectionSetting {
     pub url: String,
     pub user: String,
@@ -27,6 +17,40 @@ pub struct DbConnectionSetting {
     pub instance: String,
 }
 
+/// storage backend of the server, chosen with DB_Type in the server settings
+#[derive(PartialEq, Debug, Clone)]
+pub enum DbBackendType {
+    MongoDB,
+    SQLite,
+    PostgreSQL,
+}
+
+impl DbBackendType {
+    pub fn from_setting_value(setting_value: &str) -> Result<DbBackendType, String> {
+        match setting_value.trim().to_lowercase().as_str() {
+            "mongodb" | "" => std::result::Result::Ok(DbBackendType::MongoDB),
+            "sqlite" => std::result::Result::Ok(DbBackendType::SQLite),
+            "postgresql" | "postgres" => std::result::Result::Ok(DbBackendType::PostgreSQL),
+            _ => Err(format!("database type not supported: {}", setting_value)),
+        }
+    }
+
+    /// the value is checked at start up, MongoDB is used if the settings are not loaded (e.g. in tests)
+    pub fn from_global_setting() -> DbBackendType {
+        let setting_option = GLOBAL_SETTING.get();
+        if setting_option.is_none() {
+            return DbBackendType::MongoDB;
+        }
+        let parse_result =
+            DbBackendType::from_setting_value(&setting_option.unwrap().backend_database_type);
+        if parse_result.is_err() {
+            warn!(target:"app::FinanceOverView","{}, using MongoDB",parse_result.unwrap_err());
+            return DbBackendType::MongoDB;
+        }
+        return parse_result.unwrap();
+    }
+}
+
 #[derive(Debug)]
 pub enum EmailVerificationStatus {
     NotGiven,
@@ -48,6 +72,14 @@ impl DbHandlerMongoDB {
     pub const COLLECTION_NAME_BOOKING_ENTRIES: &'static str = "BookingEntries";
     pub const COLLECTION_NAME_JOURNAL_DIARY: &'static str = "FinanceJournalDiary";
     pub const COLLECTION_NAME_COUNTERS: &'static str = "CounterCollection";
+    pub const COLLECTION_NAME_RECURRING_BOOKINGS: &'static str = "FinanceRecurringBookings";
+    pub const COLLECTION_NAME_BOOKING_TEMPLATES: &'static str = "FinanceBookingTemplates";
+    pub const COLLECTION_NAME_BUDGETS: &'static str = "FinanceBudgets";
+    pub const COLLECTION_NAME_JOURNAL_ATTACHMENTS: &'static str = "FinanceJournalAttachments";
+    pub const COLLECTION_NAME_CATEGORIZATION_RULES: &'static str = "FinanceCategorizationRules";
+    pub const COLLECTION_NAME_RECONCILIATIONS: &'static str = "FinanceReconciliations";
+    pub const COLLECTION_NAME_REPORT_SECTION_MAPPINGS: &'static str =
+        "FinanceReportSectionMappings";
 
     pub fn new(connection_settings: &DbConnectionSetting) -> DbHandlerMongoDB {
         let db_client =
@@ -96,7 +128,7 @@ impl DbHandlerMongoDB {
 
         let db_instance = client.database(&conncetion_settings.instance);
 
-        let arr_required_collection: [&str; 9] = [
+        let arr_required_collection: [&str; 16] = [
             &DbHandlerMongoDB::COLLECTION_NAME_GENERAL_INFORMATION,
             &DbHandlerMongoDB::COLLECTION_NAME_WEBSITE_TRAFFIC,
             &DbHandlerMongoDB::COLLECTION_NAME_SESSION_INFO,
@@ -106,6 +138,13 @@ impl DbHandlerMongoDB {
             &DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES,
             &DbHandlerMongoDB::COLLECTION_NAME_COUNTERS,
             &DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_DIARY,
+            &DbHandlerMongoDB::COLLECTION_NAME_RECURRING_BOOKINGS,
+            &DbHandlerMongoDB::COLLECTION_NAME_BOOKING_TEMPLATES,
+            &DbHandlerMongoDB::COLLECTION_NAME_BUDGETS,
+            &DbHandlerMongoDB::COLLECTION_NAME_JOURNAL_ATTACHMENTS,
+            &DbHandlerMongoDB::COLLECTION_NAME_CATEGORIZATION_RULES,
+            &DbHandlerMongoDB::COLLECTION_NAME_RECONCILIATIONS,
+            &DbHandlerMongoDB::COLLECTION_NAME_REPORT_SECTION_MAPPINGS,
         ];
 
         let query_result_collections = executor::block_on(db_instance.list_collection_names(None));
@@ -217,6 +256,97 @@ impl DbHandlerMongoDB {
         return Result::Ok(update_result_execute_result.unwrap());
     }
 
+    /// increases the calling amount of the route, returns the new amount (1 for the first call)
+    pub fn increase_route_calling_amount(
+        conncetion_settings: &DbConnectionSetting,
+        route_name: &String,
+    ) -> Result<i32, String> {
+        let query_filter = doc! {"RouteName":route_name};
+
+        let query_site_result_cursor = DbHandlerMongoDB::query_table_with_filter(
+            conncetion_settings,
+            &DbHandlerMongoDB::COLLECTION_NAME_WEBSITE_TRAFFIC.to_string(),
+            query_filter,
+        );
+        if query_site_result_cursor.is_err() {
+            return Err(format!(
+                "Error querying database: {}",
+                query_site_result_cursor.unwrap_err()
+            ));
+        }
+
+        let mut current_document = Document::new();
+        let mut documents_found = 0;
+        let document_list =
+            MdbConvertTools::get_vector_from_cursor(query_site_result_cursor.unwrap());
+        for document_entry in document_list {
+            if let Some(&Bson::String(ref route_value)) = document_entry.get("RouteName") {
+                if let Some(&Bson::Int32(ref calling_amount_value)) =
+                    document_entry.get("CallingAmount")
+                {
+                    trace!(target:"app::FinanceOverView","route: {}, called: {}",route_value,calling_amount_value);
+
+                    if route_value.eq(route_name) {
+                        current_document = document_entry.clone();
+                        documents_found = documents_found + 1;
+                    }
+                }
+            }
+        }
+
+        match documents_found {
+            0 => {
+                let new_document = doc! {
+                    "RouteName" : route_name,
+                    "CallingAmount": Bson::Int32(1)
+                };
+
+                let insert_result = DbHandlerMongoDB::insert_document_in_table(
+                    conncetion_settings,
+                    &DbHandlerMongoDB::COLLECTION_NAME_WEBSITE_TRAFFIC.to_string(),
+                    &new_document,
+                );
+                if insert_result.is_err() {
+                    return Err(format!(
+                        "could not insert into database: {}",
+                        insert_result.unwrap_err()
+                    ));
+                }
+                return Ok(1);
+            }
+            1 => {
+                let already_called_amount = current_document
+                    .get_i32("CallingAmount")
+                    .unwrap_or_else(|_| 0);
+                let current_count = already_called_amount + 1;
+
+                let query_info = doc! {
+                    "RouteName" : route_name
+                };
+
+                let update_info = doc! {
+                    "$set": { "CallingAmount": current_count }
+                };
+                let update_result = DbHandlerMongoDB::update_document_in_table(
+                    conncetion_settings,
+                    &DbHandlerMongoDB::COLLECTION_NAME_WEBSITE_TRAFFIC.to_string(),
+                    query_info,
+                    update_info,
+                );
+                if update_result.is_err() {
+                    return Err(format!(
+                        "could not update database: {}",
+                        update_result.unwrap_err()
+                    ));
+                }
+                return Ok(current_count);
+            }
+            _ => {
+                return Err(format!("multiple records found for route {}", route_name));
+            }
+        };
+    }
+
     //private functions
     pub fn create_client_connection_sync(
         conncetion_settings: &DbConnectionSetting,
@@ -268,869 +398,6 @@ impl DbHandlerMongoDB {
         return Result::Ok(client);
     }
 
-    pub async fn check_user_exsists_by_name(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-    ) -> Result<bool, String> {
-        // Get a handle to the deployment.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-
-        let filter = doc! {"user_name":&user_name};
-        let projection = doc! {"user_name":<i32>::from(1)};
-        let options = FindOptions::builder().projection(projection).build();
-
-        let data_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-        let query_execute_result = data_collcetion.find(filter, options).await;
-
-        if query_execute_result.is_err() {
-            return Result::Err(query_execute_result.unwrap_err().to_string());
-        }
-
-        let mut cursor = query_execute_result.unwrap();
-
-        let mut doc_counter = 0;
-
-        while let Some(data_doc) = cursor.next().await {
-            doc_counter = doc_counter + 1;
-            if data_doc.is_err() {
-                return Err(data_doc.unwrap_err().to_string());
-            }
-
-            let inner_doc = data_doc.unwrap();
-            let stored_name = inner_doc.get_str("user_name");
-            if stored_name.is_err() {
-                return Err(stored_name.unwrap_err().to_string());
-            }
-            if stored_name.unwrap().eq(user_name) {
-                return Ok(true);
-            }
-        }
-
-        if doc_counter > 0 {
-            return Err(format!("found {} entries", doc_counter));
-        }
-
-        return Result::Ok(false);
-    }
-
-    pub async fn insert_user(
-        conncetion_settings: &DbConnectionSetting,
-        some_credentials: &UserCredentialsHashed,
-    ) -> Result<uuid::Uuid, String> {
-        // Get a handle to the deployment.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-        let user_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-
-        let new_user_uuid = Uuid::new();
-        let insert_doc = doc! {"user_id": new_user_uuid,
-        "user_name":&some_credentials.username,
-        "password_hash": &some_credentials.password_hash.expose_secret()};
-
-        let insert_result = user_collcetion.insert_one(insert_doc, None).await;
-        if insert_result.is_err() {
-            let insert_err = &insert_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",insert_err);
-            return Err(insert_err.to_string());
-        }
-
-        debug!(target:"app::FinanceOverView","new id of user object (not user_id): {}",insert_result.unwrap().inserted_id);
-
-        return Ok(new_user_uuid);
-    }
-
-    pub async fn update_user_password(
-        conncetion_settings: &DbConnectionSetting,
-        some_credentials: &UserCredentialsHashed,
-    ) -> Result<bool, String> {
-        // Get a handle to the deployment.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-        let user_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-
-        let filter_doc = doc! {
-        "user_name":&some_credentials.username};
-        let inner_update_doc = doc! {
-        "password_hash": &some_credentials.password_hash.expose_secret()};
-        //otherwise we get "update document must have first key starting with '$"
-        let update_doc = doc! {"$set": inner_update_doc};
-
-        let update_result = user_collcetion
-            .update_one(filter_doc, update_doc, None)
-            .await;
-        if update_result.is_err() {
-            let update_err = &update_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",update_err);
-            return Err(update_err.to_string());
-        }
-        let unwrapped_result = update_result.unwrap();
-
-        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
-
-        return Ok(true);
-    }
-
-    pub async fn get_stored_credentials_by_name(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-    ) -> Result<StoredCredentials, String> {
-        // Get a handle to the deployment.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-        let user_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-
-        let filter = doc! {"user_name":&user_name};
-
-        let projection = doc! {"user_name":<i32>::from(1),
-        "user_id":<i32>::from(1),
-        "password_hash":<i32>::from(1)};
-
-        let options = FindOptions::builder().projection(projection).build();
-
-        let query_execute_result = user_collcetion.find(filter, options).await;
-
-        if query_execute_result.is_err() {
-            return Result::Err(query_execute_result.unwrap_err().to_string());
-        }
-
-        let mut cursor = query_execute_result.unwrap();
-
-        let mut doc_counter = 0;
-        let mut result_doc = doc! {};
-
-        while let Some(data_doc) = cursor.next().await {
-            doc_counter = doc_counter + 1;
-            if data_doc.is_err() {
-                return Err(data_doc.unwrap_err().to_string());
-            }
-
-            let inner_doc = data_doc.unwrap();
-            let stored_name = inner_doc.get_str("user_name");
-            if stored_name.is_err() {
-                return Err(stored_name.unwrap_err().to_string());
-            }
-            if stored_name.unwrap().eq(user_name) {
-                result_doc = inner_doc;
-            }
-        }
-
-        if doc_counter != 1 {
-            return Err(format!("found {} entries", doc_counter));
-        }
-
-        let stored_password_hash_read = result_doc.get_str("password_hash");
-        if stored_password_hash_read.is_err() {
-            return Err(stored_password_hash_read.unwrap_err().to_string());
-        }
-
-        let some_uuid_parse_result = ConvertTools::get_uuid_from_document(&result_doc, "user_id");
-
-        if some_uuid_parse_result.is_err() {
-            return Err("Could not parse UUID".to_string());
-        }
-
-        let some_password_hash =
-            secrecy::Secret::<String>::new(stored_password_hash_read.unwrap().to_string());
-
-        let some_cred = StoredCredentials {
-            user_id: some_uuid_parse_result.unwrap(),
-            password_hash: some_password_hash,
-        };
-
-        return Ok(some_cred);
-    }
-
-    pub async fn update_user_email(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-        new_email: &String,
-    ) -> Result<String, String> {
-        //first validate if correct password is given
-        let query_username =
-            DbHandlerMongoDB::check_user_exsists_by_name(conncetion_settings, user_name).await;
-
-        if query_username.is_err() {
-            return Err(format!(
-                "Unable to verify username: {}",
-                query_username.unwrap_err()
-            ));
-        }
-
-        if !query_username.unwrap() {
-            return Err(format!("username does not exists: {}", user_name));
-        }
-
-        //generate a random hash for email validation
-        let salt = argon2::password_hash::SaltString::generate(&mut rand::thread_rng());
-        let current_time = async_session::chrono::Utc::now();
-
-        let mail_validation_token_result =
-            Argon2::default().hash_password(&current_time.to_string().as_bytes(), &salt);
-        if mail_validation_token_result.is_err() {
-            return Err(format!(
-                "Unable to generate email check token: {}",
-                mail_validation_token_result.unwrap_err()
-            ));
-        }
-        let mail_validation_token = mail_validation_token_result.unwrap().to_string();
-
-        // Get a handle to the deployment.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-        let user_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-
-        let filter_doc = doc! {
-        "user_name":user_name};
-        let inner_update_doc = doc! {
-        "user_email": new_email,
-        "mail_validated": false,
-        "mail_validation_token": &mail_validation_token};
-        //otherwise we get "update document must have first key starting with '$"
-        let update_doc = doc! {"$set": inner_update_doc};
-
-        let update_result = user_collcetion
-            .update_one(filter_doc, update_doc, None)
-            .await;
-        if update_result.is_err() {
-            let update_err = &update_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",update_err);
-            return Err(format!("Error updating email address: {}", update_err));
-        }
-        let unwrapped_result = update_result.unwrap();
-
-        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
-
-        return Ok(mail_validation_token);
-    }
-
-    pub async fn check_email_verfification_by_name(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-    ) -> Result<EmailVerificationStatus, String> {
-        // Get a handle to the deployment.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-
-        let filter = doc! {"user_name":&user_name};
-        let projection = doc! {"user_name":<i32>::from(1),
-        "mail_validated":<i32>::from(1),
-        "user_email":<i32>::from(1)};
-        let options = FindOptions::builder().projection(projection).build();
-
-        let data_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-        let query_execute_result = data_collcetion.find(filter, options).await;
-
-        if query_execute_result.is_err() {
-            return Result::Err(query_execute_result.unwrap_err().to_string());
-        }
-
-        let mut cursor = query_execute_result.unwrap();
-
-        let mut doc_counter = 0;
-
-        while let Some(data_doc) = cursor.next().await {
-            doc_counter = doc_counter + 1;
-            if data_doc.is_err() {
-                return Err(data_doc.unwrap_err().to_string());
-            }
-
-            let inner_doc: Document = data_doc.unwrap();
-            let stored_name = inner_doc.get_str("user_name");
-            if stored_name.is_err() {
-                return Err(stored_name.unwrap_err().to_string());
-            }
-
-            if stored_name.unwrap().eq(user_name) {
-                if inner_doc.contains_key("user_email") {
-                    let stored_email = inner_doc.get_str("user_email");
-                    if stored_email.is_err() {
-                        return Err(stored_email.unwrap_err().to_string());
-                    }
-                    if stored_email.unwrap().is_empty() {
-                        return Ok(EmailVerificationStatus::NotGiven);
-                    }
-                    let stored_verfication_flag = inner_doc.get_bool("mail_validated");
-                    if stored_verfication_flag.is_err() {
-                        return Err(stored_verfication_flag.unwrap_err().to_string());
-                    }
-                    if stored_verfication_flag.unwrap() {
-                        return Ok(EmailVerificationStatus::Verified);
-                    } else {
-                        return Ok(EmailVerificationStatus::NotVerified);
-                    };
-                } else {
-                    return Ok(EmailVerificationStatus::NotGiven);
-                }
-            }
-        }
-
-        if doc_counter > 0 {
-            return Err(format!("found {} entries", doc_counter));
-        }
-
-        return Result::Ok(EmailVerificationStatus::NotVerified);
-    }
-
-    pub async fn verify_email_by_name(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-        email_validation_string: &Secret<String>,
-    ) -> Result<EmailVerificationStatus, String> {
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-
-        let filter = doc! {
-            "user_name":user_name
-        };
-        let projection = doc! {"user_name":<i32>::from(1),
-            "mail_validated":<i32>::from(1),
-            "mail_validation_token":<i32>::from(1),
-            "user_email":<i32>::from(1),
-            "user_id":<i32>::from(1),
-        };
-        let options = FindOptions::builder().projection(projection).build();
-
-        let data_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-        let query_execute_result = data_collcetion.find(filter, options).await;
-
-        if query_execute_result.is_err() {
-            return Result::Err(query_execute_result.unwrap_err().to_string());
-        }
-
-        let mut cursor = query_execute_result.unwrap();
-
-        let mut doc_counter = 0;
-
-        while let Some(data_doc) = cursor.next().await {
-            doc_counter = doc_counter + 1;
-            if data_doc.is_err() {
-                return Err(data_doc.unwrap_err().to_string());
-            }
-
-            let inner_doc: Document = data_doc.unwrap();
-            let stored_name = inner_doc.get_str("user_name");
-            if stored_name.is_err() {
-                return Err(stored_name.unwrap_err().to_string());
-            }
-
-            if stored_name.unwrap().eq(user_name) {
-                if inner_doc.contains_key("user_email") {
-                    let stored_email = inner_doc.get_str("user_email");
-                    if stored_email.is_err() {
-                        return Err(stored_email.unwrap_err().to_string());
-                    }
-                    let stored_verfication_flag = inner_doc.get_bool("mail_validated");
-                    if stored_verfication_flag.is_err() {
-                        return Err(stored_verfication_flag.unwrap_err().to_string());
-                    }
-                    let stored_verfication_token = inner_doc.get_str("mail_validation_token");
-                    if stored_verfication_token.is_err() {
-                        return Err(stored_verfication_token.unwrap_err().to_string());
-                    }
-                    let stored_user_id =
-                        ConvertTools::get_uuid_from_document(&inner_doc, "user_id");
-                    if stored_user_id.is_err() {
-                        return Err(stored_user_id.unwrap_err().to_string());
-                    }
-
-                    if stored_verfication_flag.unwrap() {
-                        return Ok(EmailVerificationStatus::Verified);
-                    } else {
-                        if stored_verfication_token
-                            .unwrap()
-                            .eq(email_validation_string.expose_secret())
-                        {
-                            //addtional check for later: include E-Mail in hashed token and compared it to ensure that the token "belongs" to the E-Mail address
-                            //currently only the time plus a salt is used to generate token
-                            let filter_doc = doc! {
-                            "user_id":stored_user_id.unwrap()};
-                            let inner_update_doc = doc! {
-                            "mail_validated": true,
-                            };
-                            //otherwise we get "update document must have first key starting with '$"
-                            let update_doc = doc! {"$set": inner_update_doc};
-
-                            let user_collcetion: Collection<Document> =
-                                db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-                            let update_result = user_collcetion
-                                .update_one(filter_doc, update_doc, None)
-                                .await;
-                            if update_result.is_err() {
-                                let update_err = &update_result.unwrap_err();
-                                warn!(target:"app::FinanceOverView","{}",update_err);
-                                return Err(format!(
-                                    "Error updating email validated token: {}",
-                                    update_err
-                                ));
-                            }
-                            let unwrapped_result = update_result.unwrap();
-
-                            debug!(target:"app::FinanceOverView","count of updated objects during user email verfication: {}",unwrapped_result.modified_count);
-
-                            if unwrapped_result.modified_count == 1 {
-                                return Ok(EmailVerificationStatus::Verified);
-                            } else {
-                                return Err("number of updated monrecord not 1".to_string());
-                            }
-                        } else {
-                            return Err(
-                                "provided E-Mail Validation token not matching stored token"
-                                    .to_string(),
-                            );
-                        }
-                    }
-                } else {
-                    return Err("no E-Mail given".to_string());
-                }
-            }
-        }
-
-        if doc_counter > 0 {
-            return Err(format!("found {} entries", doc_counter));
-        }
-
-        return Result::Ok(EmailVerificationStatus::NotVerified);
-    }
-
-    pub async fn get_user_general_data_by_user_name(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-    ) -> Result<GenerallUserData, String> {
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-
-        let filter = doc! {
-            "user_name":user_name
-        };
-        let projection = doc! {"user_name":<i32>::from(1),
-            "first_name":<i32>::from(1),
-            "last_name":<i32>::from(1),
-        };
-        let options = FindOptions::builder().projection(projection).build();
-
-        let data_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-        let query_execute_result = data_collcetion.find(filter, options).await;
-
-        if query_execute_result.is_err() {
-            return Result::Err(query_execute_result.unwrap_err().to_string());
-        }
-
-        let mut cursor = query_execute_result.unwrap();
-
-        let mut doc_counter = 0;
-
-        while let Some(data_doc) = cursor.next().await {
-            doc_counter = doc_counter + 1;
-            if data_doc.is_err() {
-                return Err(data_doc.unwrap_err().to_string());
-            }
-
-            let inner_doc: Document = data_doc.unwrap();
-            let stored_name = inner_doc.get_str("user_name");
-            if stored_name.is_err() {
-                return Err(stored_name.unwrap_err().to_string());
-            }
-
-            if stored_name.unwrap().eq(user_name) {
-                let mut return_data: GenerallUserData = GenerallUserData {
-                    first_name: "".to_string(),
-                    last_name: "".to_string(),
-                };
-                let stored_first_name = inner_doc.get_str("first_name");
-                if stored_first_name.is_ok() {
-                    return_data.first_name = stored_first_name.unwrap().to_string();
-                }
-                let stored_last_name = inner_doc.get_str("last_name");
-                if stored_last_name.is_ok() {
-                    return_data.last_name = stored_last_name.unwrap().to_string();
-                }
-
-                return Ok(return_data);
-            }
-        }
-
-        return Err(format!("found {} entries", doc_counter));
-    }
-
-    pub async fn update_general_user_data_by_name(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-        general_user_data: &GenerallUserData,
-    ) -> Result<String, String> {
-        //first validate if correct password is given
-        let query_username =
-            DbHandlerMongoDB::check_user_exsists_by_name(conncetion_settings, user_name).await;
-
-        if query_username.is_err() {
-            return Err(format!(
-                "Unable to verify username: {}",
-                query_username.unwrap_err()
-            ));
-        }
-
-        if !query_username.unwrap() {
-            return Err(format!("username does not exists: {}", user_name));
-        }
-
-        // Get a handle to the database.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-        let user_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-
-        let filter_doc = doc! {
-        "user_name":user_name};
-        let inner_update_doc = doc! {
-        "first_name": general_user_data.first_name.clone(),
-        "last_name": general_user_data.last_name.clone(),
-        };
-        //otherwise we get "update document must have first key starting with '$"
-        let update_doc = doc! {"$set": inner_update_doc};
-
-        let update_result = user_collcetion
-            .update_one(filter_doc, update_doc, None)
-            .await;
-        if update_result.is_err() {
-            let update_err = &update_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",update_err);
-            return Err(format!("Error updating email address: {}", update_err));
-        }
-        let unwrapped_result = update_result.unwrap();
-
-        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
-
-        return Ok("updated".to_string());
-    }
-
-    pub async fn update_user_reset_secret(
-        conncetion_settings: &DbConnectionSetting,
-        user_id: &Uuid,
-        reset_secret_hash: &Secret<String>,
-    ) -> Result<bool, String> {
-        // Get a handle to the deployment.
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-        let user_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-
-        let filter_doc = doc! {
-        "user_id":user_id};
-        let inner_update_doc = doc! {
-        "reset_secret_hash": reset_secret_hash.expose_secret()};
-        //otherwise we get "update document must have first key starting with '$"
-        let update_doc = doc! {"$set": inner_update_doc};
-
-        let update_result = user_collcetion
-            .update_one(filter_doc, update_doc, None)
-            .await;
-        if update_result.is_err() {
-            let update_err = &update_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",update_err);
-            return Err(update_err.to_string());
-        }
-        let unwrapped_result = update_result.unwrap();
-
-        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
-
-        return Ok(true);
-    }
-
-    pub async fn generate_passwort_reset_token(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-        reset_secret: &Secret<String>,
-        passwort_reset_time_limit_minutes: &u16,
-    ) -> Result<PasswordResetTokenRequestResult, String> {
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-
-        let filter = doc! {
-            "user_name":user_name
-        };
-        let projection = doc! {
-            "user_name":<i32>::from(1),
-            "user_email":<i32>::from(1),
-            "reset_secret_hash":<i32>::from(1),
-        };
-        let options = FindOptions::builder().projection(projection).build();
-
-        let data_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-        let query_execute_result = data_collcetion.find(filter, options).await;
-
-        if query_execute_result.is_err() {
-            return Result::Err(query_execute_result.unwrap_err().to_string());
-        }
-
-        let mut cursor = query_execute_result.unwrap();
-
-        while let Some(data_doc) = cursor.next().await {
-            if data_doc.is_err() {
-                return Err(data_doc.unwrap_err().to_string());
-            }
-
-            let inner_doc: Document = data_doc.unwrap();
-            let stored_name = inner_doc.get_str("user_name");
-            if stored_name.is_err() {
-                return Err(stored_name.unwrap_err().to_string());
-            }
-            let stored_email = inner_doc.get_str("user_email");
-            if stored_email.is_err() {
-                return Err(stored_email.unwrap_err().to_string());
-            }
-            let stored_user_email = stored_email.unwrap();
-
-            if stored_name.unwrap().eq(user_name) {
-                let stored_reset_secret_raw = inner_doc.get_str("reset_secret_hash");
-                if stored_reset_secret_raw.is_ok() {
-                    let transformed_stored_serect = secrecy::Secret::<String>::new(
-                        stored_reset_secret_raw.unwrap().to_string(),
-                    );
-                    let verify_serect_result =
-                        verify_password_hash(&transformed_stored_serect, &reset_secret);
-                    if verify_serect_result.is_ok() {
-                        let email_validation_result =
-                            validate_email_format(&stored_user_email.to_string());
-                        if email_validation_result.is_err() {
-                            return Err(email_validation_result.unwrap_err().to_string());
-                        }
-                        if !email_validation_result.unwrap() {
-                            return Err("no valid e-mail address for operation".to_string());
-                        }
-                        let reset_token_value = Uuid::new().to_string();
-                        let reset_token_timestamp = async_session::chrono::Utc::now()
-                            + Duration::minutes(passwort_reset_time_limit_minutes.clone() as i64);
-
-                        let inner_update_doc = doc! {
-                        "password_reset_token_value": reset_token_value.clone(),
-                        "password_reset_token_timestamp": reset_token_timestamp.timestamp()
-                        };
-
-                        let filter2 = doc! {
-                            "user_name":user_name
-                        };
-                        //otherwise we get "update document must have first key starting with '$"
-                        let update_doc = doc! {"$set": inner_update_doc};
-
-                        let update_result =
-                            data_collcetion.update_one(filter2, update_doc, None).await;
-                        if update_result.is_err() {
-                            let update_err = &update_result.unwrap_err();
-                            warn!(target:"app::FinanceOverView","{}",update_err);
-                            return Err("Error validating reset token".to_string());
-                        }
-                        let unwrapped_result = update_result.unwrap();
-
-                        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
-
-                        let return_value = PasswordResetTokenRequestResult {
-                            reset_token: reset_token_value,
-                            expires_at: reset_token_timestamp,
-                            user_email: stored_user_email.to_string(),
-                        };
-
-                        return Ok(return_value);
-                    }
-                    return Err("error in token generation".to_string());
-                }
-                return Err("error generating token".to_string());
-            }
-
-            return Err("can not generate token".to_string());
-        }
-        return Err("unable to generate token".to_string());
-    }
-
-    pub async fn change_password_with_token(
-        conncetion_settings: &DbConnectionSetting,
-        user_name: &String,
-        reset_token: &String,
-        new_password: &Secret<String>,
-    ) -> Result<bool, String> {
-        let client_create_result =
-            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
-        if client_create_result.is_err() {
-            let client_err = &client_create_result.unwrap_err();
-            warn!(target:"app::FinanceOverView","{}",client_err);
-            return Err(client_err.to_string());
-        }
-        let client = client_create_result.unwrap();
-
-        let db_instance = client.database(&conncetion_settings.instance);
-
-        let filter = doc! {
-            "user_name":user_name
-        };
-        let projection = doc! {"user_name":<i32>::from(1),
-            "password_reset_token_value":<i32>::from(1),
-            "password_reset_token_timestamp":<i32>::from(1),
-        };
-        let options = FindOptions::builder().projection(projection).build();
-
-        let data_collcetion: Collection<Document> =
-            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
-        let query_execute_result = data_collcetion.find(filter, options).await;
-
-        if query_execute_result.is_err() {
-            return Result::Err(query_execute_result.unwrap_err().to_string());
-        }
-
-        let mut cursor = query_execute_result.unwrap();
-
-        while let Some(data_doc) = cursor.next().await {
-            if data_doc.is_err() {
-                return Err(data_doc.unwrap_err().to_string());
-            }
-
-            let inner_doc: Document = data_doc.unwrap();
-            let stored_name = inner_doc.get_str("user_name");
-            if stored_name.is_err() {
-                return Err(stored_name.unwrap_err().to_string());
-            }
-
-            if stored_name.unwrap().eq(user_name) {
-                let stored_reset_token_value = inner_doc.get_str("password_reset_token_value");
-                let stored_reset_token_timestamp =
-                    inner_doc.get_i64("password_reset_token_timestamp");
-
-                if stored_reset_token_timestamp.is_err() || stored_reset_token_value.is_err() {
-                    return Err("unable to retrive reset settings".to_string());
-                }
-                if stored_reset_token_value.unwrap().ne(reset_token) {
-                    return Err("token missmatch".to_string());
-                }
-                let timestamp_now = async_session::chrono::Utc::now();
-                if timestamp_now.timestamp() > stored_reset_token_timestamp.unwrap() {
-                    return Err("token expired".to_string());
-                }
-
-                let inner_update_doc = doc! {
-                "password_reset_token_value": "",
-                "password_reset_token_timestamp": "",
-                "password_hash":new_password.expose_secret()
-                };
-
-                let filter2 = doc! {
-                    "user_name":user_name
-                };
-                //otherwise we get "update document must have first key starting with '$"
-                let update_doc = doc! {"$set": inner_update_doc};
-
-                let update_result = data_collcetion.update_one(filter2, update_doc, None).await;
-                if update_result.is_err() {
-                    let update_err = &update_result.unwrap_err();
-                    warn!(target:"app::FinanceOverView","{}",update_err);
-                    return Err("Error resetting value".to_string());
-                }
-                let unwrapped_result = update_result.unwrap();
-
-                debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
-
-                return Ok(true);
-            }
-
-            return Err("can not  value".to_string());
-        }
-        return Err("unable to reset value".to_string());
-    }
-
     pub async fn repair_counter_record_for_user(
         &self,
         conncetion_settings: &DbConnectionSetting,
@@ -0,0 +1,730 @@
+use std::str::FromStr;
+
+use async_session::{async_trait, chrono::Utc, serde_json, Session, SessionStore};
+use futures::executor;
+use log::{debug, info, trace, warn};
+use mongodb::bson::Uuid;
+use once_cell::sync::Lazy;
+use tokio::runtime::Runtime;
+use tokio_postgres::{
+    error::SqlState, types::ToSql, Client, Config, GenericClient, IsolationLevel, NoTls, Row,
+    Transaction,
+};
+
+use crate::database_handler_mongodb::DbConnectionSetting;
+
+/// schema changes in the order they were introduced, the table SchemaVersion holds the number of applied entries,
+/// so an entry must never be changed once released, add a new one instead;
+/// insert_order keeps the order of insertion the lists are returned in
+const MIGRATIONS: [&str; 3] = [
+    r#"
+CREATE TABLE UserList (
+    user_id UUID NOT NULL PRIMARY KEY,
+    user_name TEXT NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    user_email TEXT,
+    mail_validated BOOLEAN NOT NULL DEFAULT FALSE,
+    mail_validation_token TEXT,
+    first_name TEXT,
+    last_name TEXT,
+    reset_secret_hash TEXT,
+    password_reset_token_timestamp TIMESTAMPTZ,
+    password_reset_token_value TEXT
+);
+
+CREATE TABLE WebSiteTraffic (
+    RouteName TEXT NOT NULL PRIMARY KEY,
+    CallingAmount INTEGER NOT NULL
+);
+
+CREATE TABLE SessionInfo (
+    session_id TEXT NOT NULL PRIMARY KEY,
+    session_data TEXT NOT NULL,
+    expiry TIMESTAMPTZ
+);
+
+CREATE TABLE FinanceAccountTypes (
+    finance_account_type_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    account_category INTEGER NOT NULL,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, finance_account_type_id)
+);
+
+CREATE TABLE FinanceAccounts (
+    finance_account_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    finance_account_type_id UUID NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    account_number BIGINT,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, finance_account_id),
+    UNIQUE (user_id, account_number),
+    FOREIGN KEY (user_id, finance_account_type_id) REFERENCES FinanceAccountTypes (user_id, finance_account_type_id)
+);
+
+CREATE TABLE FinanceJournalDiary (
+    finance_journal_diary_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    is_simple_entry BOOLEAN NOT NULL,
+    is_saldo BOOLEAN NOT NULL,
+    is_closing BOOLEAN NOT NULL,
+    debit_finance_account_id UUID NOT NULL,
+    credit_finance_account_id UUID NOT NULL,
+    running_number BIGINT NOT NULL,
+    booking_time TIMESTAMPTZ NOT NULL,
+    amount BIGINT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    reverse_of_journal_entry_id UUID,
+    reversed_by_journal_entry_id UUID,
+    tags TEXT[] NOT NULL,
+    import_reference TEXT,
+    PRIMARY KEY (user_id, finance_journal_diary_id),
+    UNIQUE (user_id, running_number),
+    FOREIGN KEY (user_id, debit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, credit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceReconciliations (
+    reconciliation_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    finance_account_id UUID NOT NULL,
+    statement_time TIMESTAMPTZ NOT NULL,
+    statement_balance BIGINT NOT NULL,
+    book_balance BIGINT NOT NULL,
+    PRIMARY KEY (user_id, reconciliation_id),
+    FOREIGN KEY (user_id, finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE BookingEntries (
+    booking_entry_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    finance_account_id UUID NOT NULL,
+    finance_journal_diary_id UUID NOT NULL,
+    booking_type INTEGER NOT NULL,
+    booking_time TIMESTAMPTZ NOT NULL,
+    amount BIGINT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    tags TEXT[] NOT NULL,
+    reconciliation_id UUID,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, booking_entry_id),
+    FOREIGN KEY (user_id, finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, finance_journal_diary_id) REFERENCES FinanceJournalDiary (user_id, finance_journal_diary_id),
+    FOREIGN KEY (user_id, reconciliation_id) REFERENCES FinanceReconciliations (user_id, reconciliation_id)
+);
+
+CREATE INDEX BookingEntries_account ON BookingEntries (user_id, finance_account_id, booking_time);
+CREATE INDEX BookingEntries_journal ON BookingEntries (user_id, finance_journal_diary_id);
+
+CREATE TABLE CounterCollection (
+    user_id UUID NOT NULL PRIMARY KEY REFERENCES UserList (user_id),
+    counter_entry_id UUID NOT NULL,
+    booking_journal_max_number BIGINT NOT NULL
+);
+
+CREATE TABLE FinanceRecurringBookings (
+    recurring_booking_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    debit_finance_account_id UUID NOT NULL,
+    credit_finance_account_id UUID NOT NULL,
+    amount BIGINT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    schedule INTEGER NOT NULL,
+    start_time TIMESTAMPTZ NOT NULL,
+    end_time TIMESTAMPTZ,
+    last_booking_time TIMESTAMPTZ,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, recurring_booking_id),
+    FOREIGN KEY (user_id, debit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, credit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceBookingTemplates (
+    booking_template_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    name TEXT NOT NULL,
+    debit_finance_account_id UUID NOT NULL,
+    credit_finance_account_id UUID NOT NULL,
+    amount BIGINT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, booking_template_id),
+    UNIQUE (user_id, name),
+    FOREIGN KEY (user_id, debit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, credit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceBudgets (
+    budget_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    finance_account_id UUID NOT NULL,
+    period INTEGER NOT NULL,
+    amount BIGINT NOT NULL,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, budget_id),
+    UNIQUE (user_id, finance_account_id, period),
+    FOREIGN KEY (user_id, finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceCategorizationRules (
+    rule_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    title TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    payee_pattern TEXT NOT NULL,
+    text_pattern TEXT NOT NULL,
+    amount_min BIGINT,
+    amount_max BIGINT,
+    source_account_id UUID,
+    counter_account_id UUID NOT NULL,
+    title_rewrite TEXT NOT NULL,
+    tags TEXT[] NOT NULL,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, rule_id),
+    FOREIGN KEY (user_id, source_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, counter_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceJournalAttachments (
+    attachment_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    finance_journal_diary_id UUID NOT NULL,
+    file_name TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    size_bytes BIGINT NOT NULL,
+    upload_time TIMESTAMPTZ NOT NULL,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, attachment_id),
+    FOREIGN KEY (user_id, finance_journal_diary_id) REFERENCES FinanceJournalDiary (user_id, finance_journal_diary_id)
+);
+"#,
+    r#"
+CREATE UNIQUE INDEX FinanceJournalDiary_import_reference ON FinanceJournalDiary
+    (user_id, debit_finance_account_id, credit_finance_account_id, import_reference)
+    WHERE import_reference IS NOT NULL AND reversed_by_journal_entry_id IS NULL;
+"#,
+    r#"
+CREATE TABLE FinanceReportSectionMappings (
+    finance_account_type_id UUID NOT NULL,
+    user_id UUID NOT NULL REFERENCES UserList (user_id),
+    section_category INTEGER NOT NULL,
+    section_title TEXT NOT NULL,
+    insert_order BIGINT GENERATED ALWAYS AS IDENTITY,
+    PRIMARY KEY (user_id, finance_account_type_id),
+    FOREIGN KEY (user_id, finance_account_type_id) REFERENCES FinanceAccountTypes (user_id, finance_account_type_id)
+);
+"#,
+];
+
+/// tokio-postgres needs a tokio reactor for its sockets, but the handlers are also called from executor::block_on
+/// (sync functions, background tasks), so the connections are driven by an own runtime
+static POSTGRES_RUNTIME: Lazy<Runtime> = Lazy::new(|| {
+    tokio::runtime::Builder::new_multi_thread()
+        .worker_threads(2)
+        .thread_name("postgres-connection")
+        .enable_all()
+        .build()
+        .expect("could not create runtime for PostgreSQL connections")
+});
+
+/// error of an attempt to run a transaction, only serialization failures are worth another attempt
+#[derive(Debug)]
+pub(crate) struct PostgresTransactionError {
+    pub(crate) message: String,
+    pub(crate) retry_possible: bool,
+}
+
+impl PostgresTransactionError {
+    pub(crate) fn from_db_error(context: &str, db_error: tokio_postgres::Error) -> Self {
+        let retry_possible = db_error.code() == Some(&SqlState::T_R_SERIALIZATION_FAILURE)
+            || db_error.code() == Some(&SqlState::T_R_DEADLOCK_DETECTED);
+        return PostgresTransactionError {
+            message: format!(
+                "{}: {}",
+                context,
+                DbHandlerPostgres::format_error(&db_error)
+            ),
+            retry_possible,
+        };
+    }
+}
+
+impl From<String> for PostgresTransactionError {
+    fn from(message: String) -> Self {
+        return PostgresTransactionError {
+            message,
+            retry_possible: false,
+        };
+    }
+}
+
+impl From<&str> for PostgresTransactionError {
+    fn from(message: &str) -> Self {
+        return PostgresTransactionError::from(message.to_string());
+    }
+}
+
+/// storage in a PostgreSQL database (DB_URL of the server settings is the connection string, DB_User, DB_Password
+/// and DB_Instance override user, password and database name), a connection is opened per operation,
+/// writes depending on read data run in serializable transactions which are repeated on serialization failures
+pub struct DbHandlerPostgres {}
+
+impl DbHandlerPostgres {
+    pub(crate) const TRANSACTION_ATTEMPT_LIMIT: u32 = 10;
+
+    pub fn new() -> DbHandlerPostgres {
+        return DbHandlerPostgres {};
+    }
+
+    /// applies missing migrations, the database itself has to be created by the administrator
+    pub fn validate_db_structure(conncetion_settings: &DbConnectionSetting) -> bool {
+        let client_result = executor::block_on(DbHandlerPostgres::open_client(conncetion_settings));
+        if client_result.is_err() {
+            warn!(target:"app::FinanceOverView","{}",client_result.unwrap_err());
+            return false;
+        }
+        let mut client = client_result.unwrap();
+
+        let migration_result = executor::block_on(DbHandlerPostgres::apply_migrations(&mut client));
+        if migration_result.is_err() {
+            warn!(target: "app::FinanceOverView","could not migrate database {}: {}",conncetion_settings.instance,migration_result.unwrap_err());
+            return false;
+        }
+        let applied_count = migration_result.unwrap();
+        if applied_count > 0 {
+            info!(target: "app::FinanceOverView","applied {} migrations to database {}",applied_count,conncetion_settings.instance);
+        } else {
+            trace!(target: "app::FinanceOverView","database {} is up to date",conncetion_settings.instance);
+        }
+
+        return true;
+    }
+
+    /// runs all migrations not yet applied inside one transaction, returns the number of applied migrations,
+    /// the lock on SchemaVersion keeps several servers starting at the same time from migrating twice
+    pub(crate) async fn apply_migrations(client: &mut Client) -> Result<usize, String> {
+        let create_result = client
+            .batch_execute(
+                "CREATE TABLE IF NOT EXISTS SchemaVersion (version_number INTEGER NOT NULL);
+                INSERT INTO SchemaVersion (version_number)
+                SELECT 0 WHERE NOT EXISTS (SELECT 1 FROM SchemaVersion)",
+            )
+            .await;
+        if create_result.is_err() {
+            return Err(format!(
+                "could not create schema version table: {}",
+                DbHandlerPostgres::format_error(&create_result.unwrap_err())
+            ));
+        }
+
+        let transaction_result = client.transaction().await;
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                DbHandlerPostgres::format_error(&transaction_result.err().unwrap())
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let version_result = transaction
+            .query_one("SELECT version_number FROM SchemaVersion FOR UPDATE", &[])
+            .await;
+        if version_result.is_err() {
+            return Err(format!(
+                "could not read schema version: {}",
+                DbHandlerPostgres::format_error(&version_result.unwrap_err())
+            ));
+        }
+        let current_version = version_result.unwrap().get::<_, i32>(0) as usize;
+        if current_version > MIGRATIONS.len() {
+            return Err(format!(
+                "schema version {} of the database is newer than the supported version {}",
+                current_version,
+                MIGRATIONS.len()
+            ));
+        }
+
+        for (migration_index, migration) in MIGRATIONS.iter().enumerate().skip(current_version) {
+            let execute_result = transaction.batch_execute(migration).await;
+            if execute_result.is_err() {
+                return Err(format!(
+                    "migration {} failed: {}",
+                    migration_index + 1,
+                    DbHandlerPostgres::format_error(&execute_result.unwrap_err())
+                ));
+            }
+            let version_update_result = transaction
+                .execute(
+                    "UPDATE SchemaVersion SET version_number = $1",
+                    &[&((migration_index + 1) as i32)],
+                )
+                .await;
+            if version_update_result.is_err() {
+                return Err(format!(
+                    "could not update schema version: {}",
+                    DbHandlerPostgres::format_error(&version_update_result.unwrap_err())
+                ));
+            }
+        }
+
+        let commit_result = transaction.commit().await;
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                DbHandlerPostgres::format_error(&commit_result.unwrap_err())
+            ));
+        }
+
+        return Ok(MIGRATIONS.len() - current_version);
+    }
+
+    /// the error of tokio-postgres only names the kind of the error, the message of the server is in the source
+    pub(crate) fn format_error(db_error: &tokio_postgres::Error) -> String {
+        if let Some(server_error) = db_error.as_db_error() {
+            return server_error.to_string();
+        }
+        if let Some(error_source) = std::error::Error::source(db_error) {
+            return format!("{}: {}", db_error, error_source);
+        }
+        return db_error.to_string();
+    }
+
+    fn create_connection_config(
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Config, String> {
+        if conncetion_settings.url.is_empty() {
+            return Err("no connection string of the PostgreSQL database given".into());
+        }
+        let parse_result = Config::from_str(&conncetion_settings.url);
+        if parse_result.is_err() {
+            return Err(format!(
+                "could not parse connection string: {}",
+                DbHandlerPostgres::format_error(&parse_result.unwrap_err())
+            ));
+        }
+        let mut connection_config = parse_result.unwrap();
+        if !conncetion_settings.user.is_empty() {
+            connection_config.user(&conncetion_settings.user);
+        }
+        if !conncetion_settings.password.is_empty() {
+            connection_config.password(&conncetion_settings.password);
+        }
+        if !conncetion_settings.instance.is_empty() {
+            connection_config.dbname(&conncetion_settings.instance);
+        }
+        return Ok(connection_config);
+    }
+
+    pub(crate) async fn open_client(
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Client, String> {
+        let config_result = DbHandlerPostgres::create_connection_config(conncetion_settings);
+        if config_result.is_err() {
+            return Err(config_result.unwrap_err());
+        }
+        let connection_config = config_result.unwrap();
+
+        // the connection object does the communication and has to run until the client is dropped
+        let connect_result = POSTGRES_RUNTIME
+            .spawn(async move {
+                let (client, connection) = connection_config.connect(NoTls).await?;
+                tokio::spawn(async move {
+                    if let Err(connection_err) = connection.await {
+                        warn!(target:"app::FinanceOverView","PostgreSQL connection closed with error: {}",DbHandlerPostgres::format_error(&connection_err));
+                    }
+                });
+                Ok::<Client, tokio_postgres::Error>(client)
+            })
+            .await;
+        if connect_result.is_err() {
+            return Err(format!(
+                "could not connect to database: {}",
+                connect_result.unwrap_err()
+            ));
+        }
+        let client_result = connect_result.unwrap();
+        if client_result.is_err() {
+            return Err(format!(
+                "could not connect to database: {}",
+                DbHandlerPostgres::format_error(&client_result.err().unwrap())
+            ));
+        }
+
+        return Ok(client_result.unwrap());
+    }
+
+    /// opens a connection for the finance functions, they fail for unknown users like the other backends do
+    pub(crate) async fn open_client_for_user(
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Client, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.err().unwrap();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_one(
+                "SELECT COUNT(*) FROM UserList WHERE user_id = $1",
+                &[&user_id.to_uuid_1()],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+        if query_result.unwrap().get::<_, i64>(0) != 1 {
+            return Err("User not found".to_string());
+        }
+        return Ok(client);
+    }
+
+    pub(crate) async fn start_serializable_transaction(
+        client: &mut Client,
+    ) -> Result<Transaction<'_>, PostgresTransactionError> {
+        let transaction_result = client
+            .build_transaction()
+            .isolation_level(IsolationLevel::Serializable)
+            .start()
+            .await;
+        if transaction_result.is_err() {
+            return Err(PostgresTransactionError::from_db_error(
+                "problem starting transaction",
+                transaction_result.err().unwrap(),
+            ));
+        }
+        return Ok(transaction_result.unwrap());
+    }
+
+    /// runs a query and converts every row with the given reader, stops at the first row that can not be converted
+    pub(crate) async fn query_list<T>(
+        client: &impl GenericClient,
+        sql_statement: &str,
+        query_params: &[&(dyn ToSql + Sync)],
+        row_reader: impl Fn(&Row) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let query_result = client.query(sql_statement, query_params).await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+
+        return DbHandlerPostgres::read_rows(query_result.unwrap(), row_reader);
+    }
+
+    /// converts every row with the given reader, stops at the first row that can not be converted
+    pub(crate) fn read_rows<T>(
+        rows: Vec<Row>,
+        row_reader: impl Fn(&Row) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let mut result_list = Vec::new();
+        for row in rows {
+            let read_result = row_reader(&row);
+            if read_result.is_err() {
+                return Err(read_result.err().unwrap());
+            }
+            result_list.push(read_result.unwrap());
+        }
+
+        return Ok(result_list);
+    }
+
+    pub(crate) fn get_uuid_from_row(row: &Row, column_name: &str) -> Result<Uuid, String> {
+        let stored_value = row.try_get::<_, uuid::Uuid>(column_name);
+        if stored_value.is_err() {
+            return Err(format!(
+                "could not read {}: {}",
+                column_name,
+                DbHandlerPostgres::format_error(&stored_value.unwrap_err())
+            ));
+        }
+        return Ok(Uuid::from_uuid_1(stored_value.unwrap()));
+    }
+
+    pub(crate) fn get_optional_uuid_from_row(
+        row: &Row,
+        column_name: &str,
+    ) -> Result<Option<Uuid>, String> {
+        let stored_value = row.try_get::<_, Option<uuid::Uuid>>(column_name);
+        if stored_value.is_err() {
+            return Err(format!(
+                "could not read {}: {}",
+                column_name,
+                DbHandlerPostgres::format_error(&stored_value.unwrap_err())
+            ));
+        }
+        return Ok(stored_value.unwrap().map(Uuid::from_uuid_1));
+    }
+
+    pub fn increase_route_calling_amount(
+        conncetion_settings: &DbConnectionSetting,
+        route_name: &String,
+    ) -> Result<i32, String> {
+        let client_result = executor::block_on(DbHandlerPostgres::open_client(conncetion_settings));
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result = executor::block_on(client.query_one(
+            "INSERT INTO WebSiteTraffic (RouteName, CallingAmount) VALUES ($1, 1)
+            ON CONFLICT (RouteName) DO UPDATE SET CallingAmount = WebSiteTraffic.CallingAmount + 1
+            RETURNING CallingAmount",
+            &[route_name],
+        ));
+        if upsert_result.is_err() {
+            return Err(format!(
+                "could not update calling amount: {}",
+                DbHandlerPostgres::format_error(&upsert_result.unwrap_err())
+            ));
+        }
+        return Ok(upsert_result.unwrap().get::<_, i32>(0));
+    }
+
+    /// creates the counter record of the user if it is missing, the running numbers continue after the journal
+    pub async fn repair_counter_record_for_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<(), String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let insert_result = client
+            .execute(
+                "INSERT INTO CounterCollection (user_id, counter_entry_id, booking_journal_max_number)
+                SELECT $1, $2, COALESCE(MAX(running_number), 0) FROM FinanceJournalDiary WHERE user_id = $1
+                ON CONFLICT (user_id) DO NOTHING",
+                &[&user_id.to_uuid_1(), &Uuid::new().to_uuid_1()],
+            )
+            .await;
+        if insert_result.is_err() {
+            return Err(format!(
+                "Error inserting base counter record: {}",
+                DbHandlerPostgres::format_error(&insert_result.unwrap_err())
+            ));
+        }
+        return Ok(());
+    }
+}
+
+/// session store keeping the sessions in the table SessionInfo of the PostgreSQL database
+#[derive(Clone)]
+pub struct PostgresSessionStore {
+    conncetion_settings: DbConnectionSetting,
+}
+
+/// the connection settings contain the password, so only the database name is printed
+impl std::fmt::Debug for PostgresSessionStore {
+    fn fmt(&self, formatter: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        return formatter
+            .debug_struct("PostgresSessionStore")
+            .field("instance", &self.conncetion_settings.instance)
+            .finish_non_exhaustive();
+    }
+}
+
+impl PostgresSessionStore {
+    pub fn new(conncetion_settings: &DbConnectionSetting) -> PostgresSessionStore {
+        return PostgresSessionStore {
+            conncetion_settings: conncetion_settings.clone(),
+        };
+    }
+
+    /// removes expired sessions, the table itself is created by the migrations
+    pub async fn initialize(&self) -> Result<(), String> {
+        let client_result = DbHandlerPostgres::open_client(&self.conncetion_settings).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let delete_result = client
+            .execute(
+                "DELETE FROM SessionInfo WHERE expiry IS NOT NULL AND expiry < $1",
+                &[&Utc::now()],
+            )
+            .await;
+        if delete_result.is_err() {
+            return Err(format!(
+                "could not remove expired sessions: {}",
+                DbHandlerPostgres::format_error(&delete_result.unwrap_err())
+            ));
+        }
+        debug!(target:"app::FinanceOverView","removed {} expired sessions",delete_result.unwrap());
+        return Ok(());
+    }
+
+    async fn open_client(&self) -> async_session::Result<Client> {
+        return DbHandlerPostgres::open_client(&self.conncetion_settings)
+            .await
+            .map_err(async_session::Error::msg);
+    }
+}
+
+#[async_trait]
+impl SessionStore for PostgresSessionStore {
+    async fn load_session(&self, cookie_value: String) -> async_session::Result<Option<Session>> {
+        let session_id = Session::id_from_cookie_value(&cookie_value)?;
+        let client = self.open_client().await?;
+
+        let stored_session_row = client
+            .query_opt(
+                "SELECT session_data FROM SessionInfo WHERE session_id = $1",
+                &[&session_id],
+            )
+            .await?;
+        if stored_session_row.is_none() {
+            return Ok(None);
+        }
+
+        let session: Session =
+            serde_json::from_str(&stored_session_row.unwrap().try_get::<_, String>(0)?)?;
+        return Ok(session.validate());
+    }
+
+    async fn store_session(&self, session: Session) -> async_session::Result<Option<String>> {
+        let client = self.open_client().await?;
+
+        let session_data = serde_json::to_string(&session)?;
+        client
+            .execute(
+                "INSERT INTO SessionInfo (session_id, session_data, expiry) VALUES ($1, $2, $3)
+                ON CONFLICT (session_id) DO UPDATE SET session_data = excluded.session_data, expiry = excluded.expiry",
+                &[&session.id(), &session_data, &session.expiry()],
+            )
+            .await?;
+
+        session.reset_data_changed();
+        return Ok(session.into_cookie_value());
+    }
+
+    async fn destroy_session(&self, session: Session) -> async_session::Result {
+        let client = self.open_client().await?;
+        client
+            .execute(
+                "DELETE FROM SessionInfo WHERE session_id = $1",
+                &[&session.id()],
+            )
+            .await?;
+        return Ok(());
+    }
+
+    async fn clear_store(&self) -> async_session::Result {
+        let client = self.open_client().await?;
+        client.execute("DELETE FROM SessionInfo", &[]).await?;
+        return Ok(());
+    }
+}
@@ -0,0 +1,683 @@
+use std::path::Path;
+
+use async_session::{
+    async_trait,
+    chrono::{DateTime, SecondsFormat, Utc},
+    serde_json, Session, SessionStore,
+};
+use log::{debug, info, trace, warn};
+use mongodb::bson::Uuid;
+use rusqlite::{params, Connection, OptionalExtension, Row, TransactionBehavior};
+
+use crate::database_handler_mongodb::DbConnectionSetting;
+
+/// schema changes in the order they were introduced, PRAGMA user_version holds the number of applied entries,
+/// so an entry must never be changed once released, add a new one instead
+const MIGRATIONS: [&str; 3] = [
+    r#"
+CREATE TABLE UserList (
+    user_id TEXT NOT NULL PRIMARY KEY,
+    user_name TEXT NOT NULL UNIQUE,
+    password_hash TEXT NOT NULL,
+    user_email TEXT,
+    mail_validated INTEGER NOT NULL DEFAULT 0,
+    mail_validation_token TEXT,
+    first_name TEXT,
+    last_name TEXT,
+    reset_secret_hash TEXT,
+    password_reset_token_timestamp INTEGER,
+    password_reset_token_value TEXT
+);
+
+CREATE TABLE WebSiteTraffic (
+    RouteName TEXT NOT NULL PRIMARY KEY,
+    CallingAmount INTEGER NOT NULL
+);
+
+CREATE TABLE SessionInfo (
+    session_id TEXT NOT NULL PRIMARY KEY,
+    session_data TEXT NOT NULL,
+    expiry TEXT
+);
+
+CREATE TABLE FinanceAccountTypes (
+    finance_account_type_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    account_category INTEGER NOT NULL,
+    PRIMARY KEY (user_id, finance_account_type_id)
+);
+
+CREATE TABLE FinanceAccounts (
+    finance_account_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    finance_account_type_id TEXT NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    account_number INTEGER,
+    PRIMARY KEY (user_id, finance_account_id),
+    UNIQUE (user_id, account_number),
+    FOREIGN KEY (user_id, finance_account_type_id) REFERENCES FinanceAccountTypes (user_id, finance_account_type_id)
+);
+
+CREATE TABLE FinanceJournalDiary (
+    finance_journal_diary_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    is_simple_entry INTEGER NOT NULL,
+    is_saldo INTEGER NOT NULL,
+    is_closing INTEGER NOT NULL,
+    debit_finance_account_id TEXT NOT NULL,
+    credit_finance_account_id TEXT NOT NULL,
+    running_number INTEGER NOT NULL,
+    booking_time TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    reverse_of_journal_entry_id TEXT,
+    reversed_by_journal_entry_id TEXT,
+    tags TEXT NOT NULL,
+    import_reference TEXT,
+    PRIMARY KEY (user_id, finance_journal_diary_id),
+    UNIQUE (user_id, running_number),
+    FOREIGN KEY (user_id, debit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, credit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceReconciliations (
+    reconciliation_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    finance_account_id TEXT NOT NULL,
+    statement_time TEXT NOT NULL,
+    statement_balance INTEGER NOT NULL,
+    book_balance INTEGER NOT NULL,
+    PRIMARY KEY (user_id, reconciliation_id),
+    FOREIGN KEY (user_id, finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE BookingEntries (
+    booking_entry_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    finance_account_id TEXT NOT NULL,
+    finance_journal_diary_id TEXT NOT NULL,
+    booking_type INTEGER NOT NULL,
+    booking_time TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    tags TEXT NOT NULL,
+    reconciliation_id TEXT,
+    PRIMARY KEY (user_id, booking_entry_id),
+    FOREIGN KEY (user_id, finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, finance_journal_diary_id) REFERENCES FinanceJournalDiary (user_id, finance_journal_diary_id),
+    FOREIGN KEY (user_id, reconciliation_id) REFERENCES FinanceReconciliations (user_id, reconciliation_id)
+);
+
+CREATE INDEX BookingEntries_account ON BookingEntries (user_id, finance_account_id, booking_time);
+CREATE INDEX BookingEntries_journal ON BookingEntries (user_id, finance_journal_diary_id);
+
+CREATE TABLE CounterCollection (
+    user_id TEXT NOT NULL PRIMARY KEY REFERENCES UserList (user_id),
+    counter_entry_id TEXT NOT NULL,
+    booking_journal_max_number INTEGER NOT NULL
+);
+
+CREATE TABLE FinanceRecurringBookings (
+    recurring_booking_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    debit_finance_account_id TEXT NOT NULL,
+    credit_finance_account_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    schedule INTEGER NOT NULL,
+    start_time TEXT NOT NULL,
+    end_time TEXT,
+    last_booking_time TEXT,
+    PRIMARY KEY (user_id, recurring_booking_id),
+    FOREIGN KEY (user_id, debit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, credit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceBookingTemplates (
+    booking_template_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    name TEXT NOT NULL,
+    debit_finance_account_id TEXT NOT NULL,
+    credit_finance_account_id TEXT NOT NULL,
+    amount INTEGER NOT NULL,
+    title TEXT NOT NULL,
+    description TEXT NOT NULL,
+    PRIMARY KEY (user_id, booking_template_id),
+    FOREIGN KEY (user_id, debit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, credit_finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceBudgets (
+    budget_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    finance_account_id TEXT NOT NULL,
+    period INTEGER NOT NULL,
+    amount INTEGER NOT NULL,
+    PRIMARY KEY (user_id, budget_id),
+    FOREIGN KEY (user_id, finance_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceCategorizationRules (
+    rule_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    title TEXT NOT NULL,
+    priority INTEGER NOT NULL,
+    payee_pattern TEXT NOT NULL,
+    text_pattern TEXT NOT NULL,
+    amount_min INTEGER,
+    amount_max INTEGER,
+    source_account_id TEXT,
+    counter_account_id TEXT NOT NULL,
+    title_rewrite TEXT NOT NULL,
+    tags TEXT NOT NULL,
+    PRIMARY KEY (user_id, rule_id),
+    FOREIGN KEY (user_id, source_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id),
+    FOREIGN KEY (user_id, counter_account_id) REFERENCES FinanceAccounts (user_id, finance_account_id)
+);
+
+CREATE TABLE FinanceJournalAttachments (
+    attachment_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    finance_journal_diary_id TEXT NOT NULL,
+    file_name TEXT NOT NULL,
+    mime_type TEXT NOT NULL,
+    size_bytes INTEGER NOT NULL,
+    upload_time TEXT NOT NULL,
+    PRIMARY KEY (user_id, attachment_id),
+    FOREIGN KEY (user_id, finance_journal_diary_id) REFERENCES FinanceJournalDiary (user_id, finance_journal_diary_id)
+);
+"#,
+    r#"
+CREATE UNIQUE INDEX FinanceJournalDiary_import_reference ON FinanceJournalDiary
+    (user_id, debit_finance_account_id, credit_finance_account_id, import_reference)
+    WHERE import_reference IS NOT NULL AND reversed_by_journal_entry_id IS NULL;
+"#,
+    r#"
+CREATE TABLE FinanceReportSectionMappings (
+    finance_account_type_id TEXT NOT NULL,
+    user_id TEXT NOT NULL REFERENCES UserList (user_id),
+    section_category INTEGER NOT NULL,
+    section_title TEXT NOT NULL,
+    PRIMARY KEY (user_id, finance_account_type_id),
+    FOREIGN KEY (user_id, finance_account_type_id) REFERENCES FinanceAccountTypes (user_id, finance_account_type_id)
+);
+"#,
+];
+
+/// storage in a single SQLite file (DB_URL of the server settings is the path of the file),
+/// a connection is opened per operation, writes run in immediate transactions so that concurrent
+/// requests wait for each other instead of failing
+pub struct DbHandlerSqlite {}
+
+impl DbHandlerSqlite {
+    const BUSY_TIMEOUT_SECONDS: u64 = 10;
+
+    pub fn new() -> DbHandlerSqlite {
+        return DbHandlerSqlite {};
+    }
+
+    /// creates the database file if needed and applies missing migrations
+    pub fn validate_db_structure(conncetion_settings: &DbConnectionSetting) -> bool {
+        let database_path = Path::new(&conncetion_settings.url);
+        if let Some(parent_folder) = database_path.parent() {
+            if !parent_folder.as_os_str().is_empty() && !parent_folder.exists() {
+                info!(target: "app::FinanceOverView","folder of database {} not found, trying to create it",parent_folder.to_string_lossy());
+                let create_result = std::fs::create_dir_all(parent_folder);
+                if create_result.is_err() {
+                    warn!(target: "app::FinanceOverView","could not create folder {}: {}",parent_folder.to_string_lossy(),create_result.unwrap_err());
+                    return false;
+                }
+            }
+        }
+
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            warn!(target:"app::FinanceOverView","{}",connection_result.unwrap_err());
+            return false;
+        }
+        let mut connection = connection_result.unwrap();
+
+        let migration_result = DbHandlerSqlite::apply_migrations(&mut connection);
+        if migration_result.is_err() {
+            warn!(target: "app::FinanceOverView","could not migrate database {}: {}",conncetion_settings.url,migration_result.unwrap_err());
+            return false;
+        }
+        let applied_count = migration_result.unwrap();
+        if applied_count > 0 {
+            info!(target: "app::FinanceOverView","applied {} migrations to database {}",applied_count,conncetion_settings.url);
+        } else {
+            trace!(target: "app::FinanceOverView","database {} is up to date",conncetion_settings.url);
+        }
+
+        return true;
+    }
+
+    /// runs all migrations not yet applied inside one transaction, returns the number of applied migrations
+    pub(crate) fn apply_migrations(connection: &mut Connection) -> Result<usize, String> {
+        let transaction_result =
+            connection.transaction_with_behavior(TransactionBehavior::Immediate);
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_result.unwrap_err()
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let version_result =
+            transaction.query_row("PRAGMA user_version", [], |row| row.get::<_, i64>(0));
+        if version_result.is_err() {
+            return Err(format!(
+                "could not read schema version: {}",
+                version_result.unwrap_err()
+            ));
+        }
+        let current_version = version_result.unwrap() as usize;
+        if current_version > MIGRATIONS.len() {
+            return Err(format!(
+                "schema version {} of the database is newer than the supported version {}",
+                current_version,
+                MIGRATIONS.len()
+            ));
+        }
+
+        for (migration_index, migration) in MIGRATIONS.iter().enumerate().skip(current_version) {
+            let execute_result = transaction.execute_batch(migration);
+            if execute_result.is_err() {
+                return Err(format!(
+                    "migration {} failed: {}",
+                    migration_index + 1,
+                    execute_result.unwrap_err()
+                ));
+            }
+            let version_update_result =
+                transaction.pragma_update(None, "user_version", (migration_index + 1) as i64);
+            if version_update_result.is_err() {
+                return Err(format!(
+                    "could not update schema version: {}",
+                    version_update_result.unwrap_err()
+                ));
+            }
+        }
+
+        let commit_result = transaction.commit();
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                commit_result.unwrap_err()
+            ));
+        }
+
+        return Ok(MIGRATIONS.len() - current_version);
+    }
+
+    pub(crate) fn open_connection(
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Connection, String> {
+        return DbHandlerSqlite::open_connection_by_path(&conncetion_settings.url);
+    }
+
+    fn open_connection_by_path(database_path: &String) -> Result<Connection, String> {
+        if database_path.is_empty() {
+            return Err("no path of the SQLite database given".into());
+        }
+        let open_result = Connection::open(database_path);
+        if open_result.is_err() {
+            return Err(format!(
+                "could not open database {}: {}",
+                database_path,
+                open_result.unwrap_err()
+            ));
+        }
+        let connection = open_result.unwrap();
+
+        let timeout_result = connection.busy_timeout(std::time::Duration::from_secs(
+            DbHandlerSqlite::BUSY_TIMEOUT_SECONDS,
+        ));
+        if timeout_result.is_err() {
+            return Err(timeout_result.unwrap_err().to_string());
+        }
+        // foreign keys are off by default and have to be enabled per connection
+        let pragma_result = connection.pragma_update(None, "foreign_keys", true);
+        if pragma_result.is_err() {
+            return Err(pragma_result.unwrap_err().to_string());
+        }
+
+        return Ok(connection);
+    }
+
+    /// opens a connection for the finance functions, they fail for unknown users like the other backends do
+    pub(crate) fn open_connection_for_user(
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Connection, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection.query_row(
+            "SELECT COUNT(*) FROM UserList WHERE user_id = ?1",
+            [user_id.to_string()],
+            |row| row.get::<_, i64>(0),
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        if query_result.unwrap() != 1 {
+            return Err("User not found".to_string());
+        }
+        return Ok(connection);
+    }
+
+    /// runs a query and converts every row with the given reader, stops at the first row that can not be converted
+    pub(crate) fn query_list<T, P: rusqlite::Params>(
+        connection: &Connection,
+        sql_statement: &str,
+        query_params: P,
+        row_reader: impl Fn(&Row) -> Result<T, String>,
+    ) -> Result<Vec<T>, String> {
+        let statement_result = connection.prepare(sql_statement);
+        if statement_result.is_err() {
+            return Err(statement_result.unwrap_err().to_string());
+        }
+        let mut statement = statement_result.unwrap();
+
+        let query_execute_result = statement.query(query_params);
+        if query_execute_result.is_err() {
+            return Err(query_execute_result.err().unwrap().to_string());
+        }
+        let mut rows = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+        loop {
+            let next_row = rows.next();
+            if next_row.is_err() {
+                return Err(next_row.unwrap_err().to_string());
+            }
+            let row_option = next_row.unwrap();
+            if row_option.is_none() {
+                break;
+            }
+            let read_result = row_reader(row_option.unwrap());
+            if read_result.is_err() {
+                return Err(read_result.err().unwrap());
+            }
+            result_list.push(read_result.unwrap());
+        }
+
+        return Ok(result_list);
+    }
+
+    /// times are stored as text with a fixed number of fractional digits,
+    /// so comparing the text gives the same order as comparing the times
+    pub(crate) fn format_datetime(time_value: &DateTime<Utc>) -> String {
+        return time_value.to_rfc3339_opts(SecondsFormat::Nanos, true);
+    }
+
+    pub(crate) fn format_optional_datetime(time_value: &Option<DateTime<Utc>>) -> Option<String> {
+        return time_value.as_ref().map(DbHandlerSqlite::format_datetime);
+    }
+
+    pub(crate) fn format_string_list(list_value: &Vec<String>) -> String {
+        return serde_json::to_string(list_value).unwrap_or_else(|_| "[]".to_string());
+    }
+
+    pub(crate) fn get_uuid_from_row(row: &Row, column_name: &str) -> Result<Uuid, String> {
+        let stored_value = row.get::<_, String>(column_name);
+        if stored_value.is_err() {
+            return Err(stored_value.unwrap_err().to_string());
+        }
+        let parse_result = Uuid::parse_str(stored_value.unwrap());
+        if parse_result.is_err() {
+            return Err(format!(
+                "could not parse {}: {}",
+                column_name,
+                parse_result.unwrap_err()
+            ));
+        }
+        return Ok(parse_result.unwrap());
+    }
+
+    pub(crate) fn get_optional_uuid_from_row(
+        row: &Row,
+        column_name: &str,
+    ) -> Result<Option<Uuid>, String> {
+        let stored_value = row.get::<_, Option<String>>(column_name);
+        if stored_value.is_err() {
+            return Err(stored_value.unwrap_err().to_string());
+        }
+        let stored_option = stored_value.unwrap();
+        if stored_option.is_none() {
+            return Ok(None);
+        }
+        let parse_result = Uuid::parse_str(stored_option.unwrap());
+        if parse_result.is_err() {
+            return Err(format!(
+                "could not parse {}: {}",
+                column_name,
+                parse_result.unwrap_err()
+            ));
+        }
+        return Ok(Some(parse_result.unwrap()));
+    }
+
+    pub(crate) fn get_datetime_from_row(
+        row: &Row,
+        column_name: &str,
+    ) -> Result<DateTime<Utc>, String> {
+        let stored_value = row.get::<_, String>(column_name);
+        if stored_value.is_err() {
+            return Err(stored_value.unwrap_err().to_string());
+        }
+        let parse_result = DateTime::parse_from_rfc3339(&stored_value.unwrap());
+        if parse_result.is_err() {
+            return Err(format!(
+                "could not parse {}: {}",
+                column_name,
+                parse_result.unwrap_err()
+            ));
+        }
+        return Ok(parse_result.unwrap().with_timezone(&Utc));
+    }
+
+    pub(crate) fn get_optional_datetime_from_row(
+        row: &Row,
+        column_name: &str,
+    ) -> Result<Option<DateTime<Utc>>, String> {
+        let stored_value = row.get::<_, Option<String>>(column_name);
+        if stored_value.is_err() {
+            return Err(stored_value.unwrap_err().to_string());
+        }
+        let stored_option = stored_value.unwrap();
+        if stored_option.is_none() {
+            return Ok(None);
+        }
+        let parse_result = DateTime::parse_from_rfc3339(&stored_option.unwrap());
+        if parse_result.is_err() {
+            return Err(format!(
+                "could not parse {}: {}",
+                column_name,
+                parse_result.unwrap_err()
+            ));
+        }
+        return Ok(Some(parse_result.unwrap().with_timezone(&Utc)));
+    }
+
+    pub(crate) fn get_string_list_from_row(
+        row: &Row,
+        column_name: &str,
+    ) -> Result<Vec<String>, String> {
+        let stored_value = row.get::<_, String>(column_name);
+        if stored_value.is_err() {
+            return Err(stored_value.unwrap_err().to_string());
+        }
+        let parse_result = serde_json::from_str::<Vec<String>>(&stored_value.unwrap());
+        if parse_result.is_err() {
+            return Err(format!(
+                "could not parse {}: {}",
+                column_name,
+                parse_result.unwrap_err()
+            ));
+        }
+        return Ok(parse_result.unwrap());
+    }
+
+    pub fn increase_route_calling_amount(
+        conncetion_settings: &DbConnectionSetting,
+        route_name: &String,
+    ) -> Result<i32, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result = connection.query_row(
+            "INSERT INTO WebSiteTraffic (RouteName, CallingAmount) VALUES (?1, 1)
+            ON CONFLICT (RouteName) DO UPDATE SET CallingAmount = CallingAmount + 1
+            RETURNING CallingAmount",
+            [route_name],
+            |row| row.get::<_, i32>(0),
+        );
+        if upsert_result.is_err() {
+            return Err(format!(
+                "could not update calling amount: {}",
+                upsert_result.unwrap_err()
+            ));
+        }
+        return Ok(upsert_result.unwrap());
+    }
+
+    /// creates the counter record of the user if it is missing, the running numbers continue after the journal
+    pub async fn repair_counter_record_for_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<(), String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let insert_result = connection.execute(
+            "INSERT INTO CounterCollection (user_id, counter_entry_id, booking_journal_max_number)
+            SELECT ?1, ?2, IFNULL(MAX(running_number), 0) FROM FinanceJournalDiary WHERE user_id = ?1
+            ON CONFLICT (user_id) DO NOTHING",
+            params![user_id.to_string(), Uuid::new().to_string()],
+        );
+        if insert_result.is_err() {
+            return Err(format!(
+                "Error inserting base counter record: {}",
+                insert_result.unwrap_err()
+            ));
+        }
+        return Ok(());
+    }
+}
+
+/// session store keeping the sessions in the table SessionInfo of the SQLite database
+#[derive(Debug, Clone)]
+pub struct SqliteSessionStore {
+    database_path: String,
+}
+
+impl SqliteSessionStore {
+    pub fn new(conncetion_settings: &DbConnectionSetting) -> SqliteSessionStore {
+        return SqliteSessionStore {
+            database_path: conncetion_settings.url.clone(),
+        };
+    }
+
+    /// removes expired sessions, the table itself is created by the migrations
+    pub async fn initialize(&self) -> Result<(), String> {
+        let connection_result = DbHandlerSqlite::open_connection_by_path(&self.database_path);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let delete_result = connection.execute(
+            "DELETE FROM SessionInfo WHERE expiry IS NOT NULL AND expiry < ?1",
+            [DbHandlerSqlite::format_datetime(&Utc::now())],
+        );
+        if delete_result.is_err() {
+            return Err(format!(
+                "could not remove expired sessions: {}",
+                delete_result.unwrap_err()
+            ));
+        }
+        debug!(target:"app::FinanceOverView","removed {} expired sessions",delete_result.unwrap());
+        return Ok(());
+    }
+
+    fn open_connection(&self) -> async_session::Result<Connection> {
+        return DbHandlerSqlite::open_connection_by_path(&self.database_path)
+            .map_err(async_session::Error::msg);
+    }
+}
+
+#[async_trait]
+impl SessionStore for SqliteSessionStore {
+    async fn load_session(&self, cookie_value: String) -> async_session::Result<Option<Session>> {
+        let session_id = Session::id_from_cookie_value(&cookie_value)?;
+        let connection = self.open_connection()?;
+
+        let stored_session_data = connection
+            .query_row(
+                "SELECT session_data FROM SessionInfo WHERE session_id = ?1",
+                [&session_id],
+                |row| row.get::<_, String>(0),
+            )
+            .optional()?;
+        if stored_session_data.is_none() {
+            return Ok(None);
+        }
+
+        let session: Session = serde_json::from_str(&stored_session_data.unwrap())?;
+        return Ok(session.validate());
+    }
+
+    async fn store_session(&self, session: Session) -> async_session::Result<Option<String>> {
+        let connection = self.open_connection()?;
+
+        let session_data = serde_json::to_string(&session)?;
+        connection.execute(
+            "INSERT INTO SessionInfo (session_id, session_data, expiry) VALUES (?1, ?2, ?3)
+            ON CONFLICT (session_id) DO UPDATE SET session_data = excluded.session_data, expiry = excluded.expiry",
+            params![
+                session.id(),
+                session_data,
+                session.expiry().map(DbHandlerSqlite::format_datetime)
+            ],
+        )?;
+
+        session.reset_data_changed();
+        return Ok(session.into_cookie_value());
+    }
+
+    async fn destroy_session(&self, session: Session) -> async_session::Result {
+        let connection = self.open_connection()?;
+        connection.execute(
+            "DELETE FROM SessionInfo WHERE session_id = ?1",
+            [session.id()],
+        )?;
+        return Ok(());
+    }
+
+    async fn clear_store(&self) -> async_session::Result {
+        let connection = self.open_connection()?;
+        connection.execute("DELETE FROM SessionInfo", [])?;
+        return Ok(());
+    }
+}
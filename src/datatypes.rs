@@ -1,6 +1,6 @@
-use async_session::chrono::{DateTime, Utc};
+use async_session::chrono::{DateTime, NaiveDate, Utc};
 use mongodb::bson::Uuid;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 #[derive(Deserialize, Debug)]
 pub struct GenerallUserData {
@@ -28,36 +28,120 @@ pub struct PasswordResetRequest {
     pub new_password: secrecy::Secret<String>,
 }
 
-#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub enum AccountCategory {
+    Asset,
+    Liability,
+    Equity,
+    Income,
+    Expense,
+}
+
+impl AccountCategory {
+    /// category given to account types stored before categories were introduced, their balances
+    /// were reported on the debit side, the user can change the category afterwards
+    pub const DEFAULT_OF_STORED_TYPES: AccountCategory = AccountCategory::Asset;
+
+    pub fn get_from_int(category_value: i32) -> Result<AccountCategory, String> {
+        match category_value {
+            0 => std::result::Result::Ok(AccountCategory::Asset),
+            1 => std::result::Result::Ok(AccountCategory::Liability),
+            2 => std::result::Result::Ok(AccountCategory::Equity),
+            3 => std::result::Result::Ok(AccountCategory::Income),
+            4 => std::result::Result::Ok(AccountCategory::Expense),
+            _ => Err(format!("value not supported: {}", category_value)),
+        }
+    }
+
+    pub fn to_int(&self) -> i32 {
+        match self {
+            AccountCategory::Asset => 0,
+            AccountCategory::Liability => 1,
+            AccountCategory::Equity => 2,
+            AccountCategory::Income => 3,
+            AccountCategory::Expense => 4,
+        }
+    }
+
+    /// asset, liability and equity accounts are shown in the balance sheet, the others in the income statement
+    pub fn is_balance_sheet_category(&self) -> bool {
+        match self {
+            AccountCategory::Asset | AccountCategory::Liability | AccountCategory::Equity => true,
+            AccountCategory::Income | AccountCategory::Expense => false,
+        }
+    }
+
+    /// side on which the balance of an account of this category is normally found
+    pub fn get_normal_balance_type(&self) -> AccountBalanceType {
+        match self {
+            AccountCategory::Asset | AccountCategory::Expense => AccountBalanceType::Debit,
+            AccountCategory::Liability | AccountCategory::Equity | AccountCategory::Income => {
+                AccountBalanceType::Credit
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for AccountCategory {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AccountCategory::Asset => write!(f, "asset"),
+            AccountCategory::Liability => write!(f, "liability"),
+            AccountCategory::Equity => write!(f, "equity"),
+            AccountCategory::Income => write!(f, "income"),
+            AccountCategory::Expense => write!(f, "expense"),
+        }
+    }
+}
+
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub struct FinanceAccountType {
     pub id: Uuid,
     pub title: String,
     pub description: String,
+    pub account_category: AccountCategory,
 }
 
-#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub struct FinanceAccount {
     pub id: Uuid,
     pub finance_account_type_id: Uuid,
     pub title: String,
     pub description: String,
+    /// number of the account in the chart of accounts of the tax advisor (e.g. 1200 in SKR03),
+    /// unique per user, required for the DATEV export
+    pub account_number: Option<u32>,
 }
 
-#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub struct FinanceJournalEntry {
     pub id: Uuid,
     pub is_simple_entry: bool,
     pub is_saldo: bool,
+    /// set for the entries closing a period (see FinanceBookingHandle::close_accounts), not part of income statements
+    pub is_closing: bool,
+    /// main accounts of the entry, for an entry that is not simple these are only the first
+    /// debit and credit leg, the booking entries of the journal entry hold all legs
     pub debit_finance_account_id: Uuid,
     pub credit_finance_account_id: Uuid,
     pub running_number: u64,
     pub booking_time: DateTime<Utc>,
+    /// sum of the debit legs
     pub amount: u64,
     pub title: String,
     pub description: String,
+    /// set when this entry is the reversal (storno) of another journal entry
+    pub reverse_of_journal_entry_id: Option<Uuid>,
+    /// set when this entry was reversed by another journal entry
+    pub reversed_by_journal_entry_id: Option<Uuid>,
+    /// free topics (e.g. "vacation 2026") to group entries across accounts
+    pub tags: Vec<String>,
+    /// identifies the transaction of an imported bank statement (e.g. the FITID of an OFX file),
+    /// used to detect a file that is imported twice
+    pub import_reference: Option<String>,
 }
 
-#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub enum BookingEntryType {
     Credit,
     Debit,
@@ -86,7 +170,7 @@ impl BookingEntryType {
     }
 }
 
-#[derive(PartialEq, Deserialize, Debug, Clone)]
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
 pub struct FinanceAccountBookingEntry {
     pub id: Uuid,
     pub finance_account_id: Uuid,
@@ -96,6 +180,11 @@ pub struct FinanceAccountBookingEntry {
     pub amount: u64,
     pub title: String,
     pub description: String,
+    /// copy of the tags of the journal entry
+    pub tags: Vec<String>,
+    /// set when the entry was reconciled against a bank statement (see FinanceReconciliation),
+    /// reconciled entries can not be reversed until they are unreconciled
+    pub reconciliation_id: Option<Uuid>,
 }
 
 #[derive(PartialEq, Deserialize, Debug, Clone)]
@@ -108,6 +197,98 @@ pub struct FinanceBookingRequest {
     pub amount: u64,
     pub title: String,
     pub description: String,
+    pub tags: Vec<String>,
+    /// see FinanceJournalEntry::import_reference, None for entries not created by an import
+    pub import_reference: Option<String>,
+}
+
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub enum RecurringBookingSchedule {
+    Weekly,
+    /// same day of month as the start, shorter months use their last day
+    Monthly,
+    Yearly,
+    /// last day of every month
+    EndOfMonth,
+}
+
+impl RecurringBookingSchedule {
+    pub fn get_from_int(schedule_value: i32) -> Result<RecurringBookingSchedule, String> {
+        match schedule_value {
+            0 => std::result::Result::Ok(RecurringBookingSchedule::Weekly),
+            1 => std::result::Result::Ok(RecurringBookingSchedule::Monthly),
+            2 => std::result::Result::Ok(RecurringBookingSchedule::Yearly),
+            3 => std::result::Result::Ok(RecurringBookingSchedule::EndOfMonth),
+            _ => Err(format!("value not supported: {}", schedule_value)),
+        }
+    }
+
+    pub fn to_int(&self) -> i32 {
+        match self {
+            RecurringBookingSchedule::Weekly => 0,
+            RecurringBookingSchedule::Monthly => 1,
+            RecurringBookingSchedule::Yearly => 2,
+            RecurringBookingSchedule::EndOfMonth => 3,
+        }
+    }
+}
+
+impl std::fmt::Display for RecurringBookingSchedule {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecurringBookingSchedule::Weekly => write!(f, "weekly"),
+            RecurringBookingSchedule::Monthly => write!(f, "monthly"),
+            RecurringBookingSchedule::Yearly => write!(f, "yearly"),
+            RecurringBookingSchedule::EndOfMonth => write!(f, "end of month"),
+        }
+    }
+}
+
+/// booking repeated by a schedule (e.g. rent, salary), the occurrences are booked by a background task
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+pub struct FinanceRecurringBooking {
+    pub id: Uuid,
+    /// booking_time of the template is ignored, every occurrence gets its own booking time
+    pub booking_template: FinanceBookingRequest,
+    pub schedule: RecurringBookingSchedule,
+    /// first occurrence, its time of day is used for all occurrences
+    pub start_time: DateTime<Utc>,
+    pub end_time: Option<DateTime<Utc>>,
+    /// booking time of the last booked occurrence, later occurrences are still to be booked
+    pub last_booking_time: Option<DateTime<Utc>>,
+}
+
+/// outcome of booking the due occurrences of all recurring bookings of an user
+#[derive(PartialEq, Debug, Clone)]
+pub struct FinanceRecurringBookingRunResult {
+    pub booking_results: Vec<FinanceBookingResult>,
+    /// one message per recurring booking that stopped with an error, the other recurring bookings are booked anyway
+    pub errors: Vec<String>,
+}
+
+/// named prefill for frequently used simple booking entries
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct FinanceBookingTemplate {
+    pub id: Uuid,
+    pub name: String,
+    pub debit_finance_account_id: Uuid,
+    pub credit_finance_account_id: Uuid,
+    pub amount: u64,
+    pub title: String,
+    pub description: String,
+}
+
+/// scanned receipt or invoice attached to a journal entry, the file content is kept in the
+/// attachment storage directory, attachments are never deleted so that they remain available for audit
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct FinanceJournalAttachment {
+    pub id: Uuid,
+    pub finance_journal_diary_id: Uuid,
+    /// original file name of the upload
+    pub file_name: String,
+    pub mime_type: String,
+    pub size_bytes: u64,
+    pub upload_time: DateTime<Utc>,
 }
 
 #[derive(PartialEq, Deserialize, Debug, Clone)]
@@ -117,7 +298,82 @@ pub struct FinanceBookingResult {
     pub credit_account_entry: FinanceAccountBookingEntry,
 }
 
-#[derive(PartialEq, Debug, Clone)]
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+pub struct FinanceBookingLeg {
+    pub finance_account_id: Uuid,
+    pub amount: u64,
+}
+
+/// booking request with several debit and credit legs (e.g. paycheck split into salary, tax and insurance)
+/// sum of debit legs has to be equal to the sum of credit legs
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+pub struct FinanceSplitBookingRequest {
+    pub is_saldo: bool,
+    pub is_closing: bool,
+    pub debit_legs: Vec<FinanceBookingLeg>,
+    pub credit_legs: Vec<FinanceBookingLeg>,
+    pub booking_time: DateTime<Utc>,
+    pub title: String,
+    pub description: String,
+    pub tags: Vec<String>,
+    pub import_reference: Option<String>,
+}
+
+impl FinanceSplitBookingRequest {
+    pub fn from_booking_request(booking_request: &FinanceBookingRequest) -> Self {
+        Self {
+            is_saldo: booking_request.is_saldo,
+            is_closing: false,
+            debit_legs: vec![FinanceBookingLeg {
+                finance_account_id: booking_request.debit_finance_account_id,
+                amount: booking_request.amount,
+            }],
+            credit_legs: vec![FinanceBookingLeg {
+                finance_account_id: booking_request.credit_finance_account_id,
+                amount: booking_request.amount,
+            }],
+            booking_time: booking_request.booking_time,
+            title: booking_request.title.clone(),
+            description: booking_request.description.clone(),
+            tags: booking_request.tags.clone(),
+            import_reference: booking_request.import_reference.clone(),
+        }
+    }
+
+    pub fn is_simple_entry(&self) -> bool {
+        self.debit_legs.len() == 1 && self.credit_legs.len() == 1
+    }
+
+    pub fn sum_debit_amount(&self) -> Result<u64, String> {
+        return FinanceSplitBookingRequest::sum_leg_amounts(&self.debit_legs);
+    }
+
+    pub fn sum_credit_amount(&self) -> Result<u64, String> {
+        return FinanceSplitBookingRequest::sum_leg_amounts(&self.credit_legs);
+    }
+
+    /// the amounts are user input, an overflow is reported instead of wrapping around
+    fn sum_leg_amounts(booking_legs: &Vec<FinanceBookingLeg>) -> Result<u64, String> {
+        let mut sum_amount: u64 = 0;
+        for booking_leg in booking_legs {
+            let add_result = sum_amount.checked_add(booking_leg.amount);
+            if add_result.is_none() {
+                return Err("sum of the amounts is too large".into());
+            }
+            sum_amount = add_result.unwrap();
+        }
+        return Ok(sum_amount);
+    }
+}
+
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+pub struct FinanceSplitBookingResult {
+    pub journal_entry: FinanceJournalEntry,
+    pub debit_account_entries: Vec<FinanceAccountBookingEntry>,
+    pub credit_account_entries: Vec<FinanceAccountBookingEntry>,
+}
+
+#[derive(PartialEq, Serialize, Debug, Clone)]
 pub enum AccountBalanceType {
     Credit,
     Debit,
@@ -145,4 +401,436 @@ pub struct AccountBalanceInfo {
     pub account_id: Uuid,
     pub balance_type: AccountBalanceType,
     pub amount: u64,
+    pub account_category: AccountCategory,
+}
+
+impl AccountBalanceInfo {
+    /// amount with the sign natural for the account category (e.g. positive for a debit balance of an asset account,
+    /// positive for a credit balance of an income account)
+    pub fn get_signed_amount(&self) -> i128 {
+        let normal_balance_type = self.account_category.get_normal_balance_type();
+        if self.balance_type.eq(&normal_balance_type) {
+            return self.amount as i128;
+        } else {
+            return -(self.amount as i128);
+        }
+    }
+}
+
+/// single account inside a report, amount in cents with the sign natural for the account category
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceReportAccountLine {
+    pub account_id: String,
+    pub title: String,
+    pub amount: i128,
+}
+
+/// accounts of one account type inside a report section
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceReportTypeGroup {
+    pub account_type_id: String,
+    pub title: String,
+    pub accounts: Vec<FinanceReportAccountLine>,
+    pub total: i128,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceReportSection {
+    pub title: String,
+    pub account_categories: Vec<AccountCategory>,
+    pub groups: Vec<FinanceReportTypeGroup>,
+    pub total: i128,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceBalanceSheet {
+    pub report_time: DateTime<Utc>,
+    /// assets, liabilities and equity
+    pub sections: Vec<FinanceReportSection>,
+    pub total_assets: i128,
+    pub total_liabilities_and_equity: i128,
+    /// result of the income and expense accounts not yet booked to equity
+    pub difference: i128,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceIncomeStatement {
+    pub time_from: DateTime<Utc>,
+    pub time_till: DateTime<Utc>,
+    /// income and expenses
+    pub sections: Vec<FinanceReportSection>,
+    pub total_income: i128,
+    pub total_expense: i128,
+    pub net_income: i128,
+}
+
+/// places the accounts of an account type in a section of the balance sheet or the income statement,
+/// account types without a mapping are shown in the default section of their own category
+#[derive(PartialEq, Debug, Clone)]
+pub struct FinanceReportSectionMapping {
+    pub finance_account_type_id: Uuid,
+    /// decides the total the accounts are counted in and the sign of their amounts,
+    /// it belongs to the same report as the category of the account type
+    pub section_category: AccountCategory,
+    /// sections of the same category and title are shown as one section
+    pub section_title: String,
+}
+
+/// totals of a single account inside a trial balance, amounts in cents
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceTrialBalanceLine {
+    pub account_id: String,
+    pub account_title: String,
+    pub account_type_title: String,
+    /// start of the period of the account, without a start of the report the last saldo of the account,
+    /// None when all entries since the first booking are used
+    pub time_from: Option<DateTime<Utc>>,
+    pub debit_total: u64,
+    pub credit_total: u64,
+    pub balance_type: AccountBalanceType,
+    pub balance: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceTrialBalance {
+    /// start of the period, None when every account starts with its own last saldo
+    pub time_from: Option<DateTime<Utc>>,
+    pub time_till: Option<DateTime<Utc>>,
+    pub lines: Vec<FinanceTrialBalanceLine>,
+    pub total_debit: u64,
+    pub total_credit: u64,
+    pub total_debit_balance: u64,
+    pub total_credit_balance: u64,
+    /// true if debit and credit totals (and the debit and credit balances) are equal
+    pub is_balanced: bool,
+}
+
+/// spending of one tag inside one calendar month, amounts in cents
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceTagSpendingLine {
+    pub tag: String,
+    /// calendar month as YYYY-MM
+    pub month: String,
+    /// debit minus credit on expense accounts, reversals reduce the amount again
+    pub amount: i128,
+    /// number of journal entries with this tag inside the month
+    pub entry_count: u64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceTagSpendingSummary {
+    pub time_from: DateTime<Utc>,
+    pub time_till: DateTime<Utc>,
+    /// ordered by tag and month
+    pub lines: Vec<FinanceTagSpendingLine>,
+}
+
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub enum BudgetPeriod {
+    /// calendar month
+    Monthly,
+    /// calendar year
+    Yearly,
+}
+
+impl BudgetPeriod {
+    pub fn get_from_int(period_value: i32) -> Result<BudgetPeriod, String> {
+        match period_value {
+            0 => std::result::Result::Ok(BudgetPeriod::Monthly),
+            1 => std::result::Result::Ok(BudgetPeriod::Yearly),
+            _ => Err(format!("value not supported: {}", period_value)),
+        }
+    }
+
+    pub fn to_int(&self) -> i32 {
+        match self {
+            BudgetPeriod::Monthly => 0,
+            BudgetPeriod::Yearly => 1,
+        }
+    }
+}
+
+impl std::fmt::Display for BudgetPeriod {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            BudgetPeriod::Monthly => write!(f, "monthly"),
+            BudgetPeriod::Yearly => write!(f, "yearly"),
+        }
+    }
+}
+
+/// planned movement of an account per month or year, at most one budget per account and period
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+pub struct FinanceBudget {
+    pub id: Uuid,
+    pub finance_account_id: Uuid,
+    pub period: BudgetPeriod,
+    /// amount in cents
+    pub amount: u64,
+}
+
+/// budget compared to the movements of the account in the period containing the report time,
+/// amounts in cents with the sign natural for the account category
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceBudgetReportLine {
+    pub budget_id: String,
+    pub account_id: String,
+    pub account_title: String,
+    pub period: BudgetPeriod,
+    pub time_from: DateTime<Utc>,
+    pub time_till: DateTime<Utc>,
+    pub budget_amount: i128,
+    pub actual_amount: i128,
+    /// negative when the budget is exceeded
+    pub remaining_amount: i128,
+    pub percentage_used: f64,
+}
+
+#[derive(Serialize, Debug, Clone)]
+pub struct FinanceBudgetReport {
+    pub report_time: DateTime<Utc>,
+    pub lines: Vec<FinanceBudgetReportLine>,
+}
+
+/// single transaction read from a bank statement file, amounts in cents
+#[derive(PartialEq, Debug, Clone)]
+pub struct BankStatementTransaction {
+    pub booking_time: DateTime<Utc>,
+    /// positive when money was received on the bank account, negative when money was paid
+    pub amount: i64,
+    pub title: String,
+    pub description: String,
+    /// unique id of the transaction if the file format has one, see FinanceJournalEntry::import_reference
+    pub import_reference: Option<String>,
+}
+
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub enum AmountSignConvention {
+    /// usual for bank accounts: received money is positive
+    PositiveIsIncoming,
+    /// usual for credit card statements: payments are positive
+    PositiveIsOutgoing,
+}
+
+impl AmountSignConvention {
+    pub fn get_from_int(convention_value: i32) -> Result<AmountSignConvention, String> {
+        match convention_value {
+            0 => std::result::Result::Ok(AmountSignConvention::PositiveIsIncoming),
+            1 => std::result::Result::Ok(AmountSignConvention::PositiveIsOutgoing),
+            _ => Err(format!("value not supported: {}", convention_value)),
+        }
+    }
+}
+
+/// describes how the columns of a CSV bank statement are mapped to booking entries
+#[derive(PartialEq, Debug, Clone)]
+pub struct CsvImportProfile {
+    pub delimiter: char,
+    /// number of lines before the data rows (including the header line)
+    pub skip_rows: usize,
+    /// number of the column, the first column is 1
+    pub date_column: usize,
+    /// chrono format of the date, e.g. %d.%m.%Y
+    pub date_format: String,
+    pub amount_column: usize,
+    pub sign_convention: AmountSignConvention,
+    pub decimal_separator: char,
+    /// label of the file encoding, e.g. utf-8 or windows-1252
+    pub encoding: String,
+    /// 0 when the column is not used
+    pub title_column: usize,
+    /// 0 when the column is not used
+    pub description_column: usize,
+    /// account of the bank statement itself
+    pub bank_account_id: Uuid,
+    /// counter account when money was received (e.g. an income account)
+    pub default_incoming_account_id: Uuid,
+    /// counter account when money was paid (e.g. an expense account)
+    pub default_outgoing_account_id: Uuid,
+}
+
+/// format of an exported CSV file
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct CsvExportSettings {
+    pub delimiter: char,
+    /// '.' or ',', amounts are written with two decimals and without thousands separator
+    pub decimal_separator: char,
+}
+
+/// plain text accounting syntax of an export (ledger-cli / hledger or beancount)
+#[derive(PartialEq, Deserialize, Debug, Clone)]
+pub enum PlainTextAccountingFormat {
+    Ledger,
+    Beancount,
+}
+
+impl PlainTextAccountingFormat {
+    pub fn get_from_string(format_value: &str) -> Result<PlainTextAccountingFormat, String> {
+        match format_value {
+            "ledger" => std::result::Result::Ok(PlainTextAccountingFormat::Ledger),
+            "beancount" => std::result::Result::Ok(PlainTextAccountingFormat::Beancount),
+            _ => Err(format!("format not supported: {}", format_value)),
+        }
+    }
+}
+
+/// journal entry of a plain text accounting file, ids and running number are the ones of the exporting journal
+#[derive(PartialEq, Debug, Clone)]
+pub struct FinancePlainTextTransaction {
+    pub journal_entry_id: Uuid,
+    pub running_number: u64,
+    pub reverse_of_journal_entry_id: Option<Uuid>,
+    pub booking_request: FinanceSplitBookingRequest,
+}
+
+/// everything written to (and read from) a plain text accounting file
+#[derive(PartialEq, Debug, Clone)]
+pub struct FinancePlainTextData {
+    pub account_types: Vec<FinanceAccountType>,
+    pub accounts: Vec<FinanceAccount>,
+    /// ordered by booking time and running number
+    pub transactions: Vec<FinancePlainTextTransaction>,
+}
+
+/// versioned archive of the accounting data of one user (see FinanceBackupHandle)
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct FinanceBackupArchive {
+    /// increased when the structure of the archive changes
+    pub schema_version: u32,
+    pub created_at: DateTime<Utc>,
+    pub account_types: Vec<FinanceAccountType>,
+    pub accounts: Vec<FinanceAccount>,
+    /// ordered by running number
+    pub journal_entries: Vec<FinanceJournalEntry>,
+    pub booking_entries: Vec<FinanceAccountBookingEntry>,
+    /// last running number of the counter record, can be higher than the last journal entry
+    pub booking_journal_max_number: u64,
+}
+
+/// header data of a DATEV booking batch ("Buchungsstapel") for one period of a fiscal year
+#[derive(PartialEq, Debug, Clone)]
+pub struct DatevExportSettings {
+    /// "Beraternummer" of the tax advisor (1001 - 9999999)
+    pub consultant_number: u32,
+    /// "Mandantennummer" of the client at the tax advisor (1 - 99999)
+    pub client_number: u32,
+    /// first day of the fiscal year ("WJ-Beginn"), the period has to be inside this fiscal year
+    pub fiscal_year_start: NaiveDate,
+    /// number of digits of general ledger accounts ("Sachkontenlänge", 4 - 8)
+    pub account_length: u8,
+    pub period_from: NaiveDate,
+    pub period_till: NaiveDate,
+}
+
+/// proposed booking entry for one row of an imported bank statement
+#[derive(PartialEq, Debug, Clone)]
+pub struct BankImportPreviewRow {
+    /// line of the data row inside a CSV file or number of the transaction inside other
+    /// statement files, starting with 1
+    pub row_number: usize,
+    /// None when the row could not be read
+    pub booking_request: Option<FinanceBookingRequest>,
+    /// empty when the row could be read
+    pub error: String,
+    /// journal entry that probably already contains this row, such rows are not posted
+    pub duplicate_journal_entry_id: Option<Uuid>,
+}
+
+/// outcome of posting one row of an imported bank statement
+#[derive(PartialEq, Debug, Clone)]
+pub struct BankImportRowResult {
+    pub row_number: usize,
+    /// None when the row was not posted
+    pub journal_entry_id: Option<Uuid>,
+    /// validation message of the booking when the row was not posted
+    pub error: String,
+}
+
+/// statement read from a bank statement file (e.g. MT940), balances and amounts in cents,
+/// positive when the bank account has a credit balance at the bank
+#[derive(PartialEq, Debug, Clone)]
+pub struct BankStatement {
+    pub reference: String,
+    /// account number of the statement, e.g. IBAN or bank code/account number
+    pub account_identification: String,
+    pub statement_number: String,
+    pub currency: String,
+    pub opening_balance_time: DateTime<Utc>,
+    pub opening_balance: i64,
+    pub closing_balance_time: DateTime<Utc>,
+    pub closing_balance: i64,
+    pub transactions: Vec<BankStatementTransaction>,
+}
+
+/// balances of imported statements compared with the balance of the bank account in the journal
+#[derive(PartialEq, Debug, Clone)]
+pub struct BankStatementBalanceCheck {
+    /// current balance of the bank account, positive for a debit balance
+    pub book_balance: i128,
+    /// opening balance of the first statement
+    pub statement_opening_balance: i128,
+    /// closing balance of the last statement
+    pub statement_closing_balance: i128,
+}
+
+impl BankStatementBalanceCheck {
+    /// the statements continue the journal and booking all transactions leads to the closing balance
+    pub fn is_opening_balance_matching(&self) -> bool {
+        return self.book_balance == self.statement_opening_balance;
+    }
+
+    /// the journal already has the closing balance, the transactions were probably booked before
+    pub fn is_closing_balance_matching(&self) -> bool {
+        return self.book_balance == self.statement_closing_balance;
+    }
+}
+
+/// rule to choose the counter account of imported transactions, the rules of an user are
+/// evaluated in ascending priority and the first matching rule is applied,
+/// empty patterns and missing limits match every transaction
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct FinanceCategorizationRule {
+    pub id: Uuid,
+    pub title: String,
+    pub priority: i32,
+    /// regular expression searched in the title (payee) of the transaction
+    pub payee_pattern: String,
+    /// regular expression searched in the description of the transaction
+    pub text_pattern: String,
+    /// amounts in cents, positive when money was received on the source account
+    pub amount_min: Option<i64>,
+    pub amount_max: Option<i64>,
+    /// bank account of the import, None for every account
+    pub source_account_id: Option<Uuid>,
+    pub counter_account_id: Uuid,
+    /// replaces the title of the transaction, empty to keep it
+    pub title_rewrite: String,
+    /// added to the tags of the booking entry
+    pub tags: Vec<String>,
+}
+
+/// bank statement an account was reconciled against, the booking entries covered by the statement
+/// reference the reconciliation by its id
+#[derive(PartialEq, Serialize, Deserialize, Debug, Clone)]
+pub struct FinanceReconciliation {
+    pub id: Uuid,
+    pub finance_account_id: Uuid,
+    pub statement_time: DateTime<Utc>,
+    /// ending balance of the statement in cents, with the sign natural for the account category
+    pub statement_balance: i64,
+    /// balance of the account in cents as of statement_time at the time of reconciling
+    pub book_balance: i64,
+}
+
+/// comparison of the book balance with a bank statement, open entries are the booking entries
+/// up to the statement date that are not reconciled yet
+#[derive(Debug, Clone)]
+pub struct FinanceReconciliationStatus {
+    pub finance_account_id: Uuid,
+    pub statement_time: DateTime<Utc>,
+    pub statement_balance: i64,
+    pub book_balance: i64,
+    /// statement balance minus book balance
+    pub difference: i64,
+    pub open_entries: Vec<FinanceAccountBookingEntry>,
 }
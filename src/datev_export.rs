@@ -0,0 +1,488 @@
+use std::collections::HashMap;
+
+use async_session::chrono::{DateTime, Months, NaiveDate, TimeZone, Utc};
+use encoding_rs::WINDOWS_1252;
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_database::{
+        DBFinanceAccountingFunctions, FinanceAccountBookingEntryListSearchOption,
+    },
+    accounting_logic::FinanceBookingHandle,
+    csv_export::FinanceCsvExportHandle,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{BookingEntryType, DatevExportSettings, FinanceAccount, FinanceJournalEntry},
+};
+
+/// one line of the booking batch, the amount is always booked from the debit account ("Konto")
+/// to the credit account ("Gegenkonto")
+#[derive(PartialEq, Debug, Clone)]
+pub struct DatevBookingRow {
+    pub amount: u64,
+    pub debit_account_number: u32,
+    pub credit_account_number: u32,
+    pub document_date: NaiveDate,
+    pub document_number: String,
+    pub booking_text: String,
+}
+
+pub struct FinanceDatevExportHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceAccountingFunctions,
+}
+
+impl<'a> FinanceDatevExportHandle<'a> {
+    /// maximum length of "Belegfeld 1"
+    const DOCUMENT_NUMBER_LENGTH: usize = 36;
+    /// maximum length of "Buchungstext"
+    const BOOKING_TEXT_LENGTH: usize = 60;
+
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceAccountingFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    pub fn check_settings(settings: &DatevExportSettings) -> Result<(), String> {
+        if settings.consultant_number < 1001 || settings.consultant_number > 9_999_999 {
+            return Err(format!(
+                "consultant number {} is not between 1001 and 9999999",
+                settings.consultant_number
+            ));
+        }
+        if settings.client_number < 1 || settings.client_number > 99_999 {
+            return Err(format!(
+                "client number {} is not between 1 and 99999",
+                settings.client_number
+            ));
+        }
+        if settings.account_length < 4 || settings.account_length > 8 {
+            return Err(format!(
+                "account length {} is not between 4 and 8",
+                settings.account_length
+            ));
+        }
+        if settings.period_from > settings.period_till {
+            return Err("the period has to start before it ends".into());
+        }
+        let fiscal_year_end_option = settings
+            .fiscal_year_start
+            .checked_add_months(Months::new(12));
+        if fiscal_year_end_option.is_none() {
+            return Err("could not calculate the end of the fiscal year".into());
+        }
+        // DATEV expects one batch per fiscal year
+        if settings.period_from < settings.fiscal_year_start
+            || settings.period_till >= fiscal_year_end_option.unwrap()
+        {
+            return Err(format!(
+                "the period {} - {} is not inside the fiscal year starting {}",
+                settings.period_from, settings.period_till, settings.fiscal_year_start
+            ));
+        }
+        return Ok(());
+    }
+
+    /// quotes a text field, quotes inside are doubled and line breaks replaced by spaces
+    fn quote_text(value: &str, max_length: usize) -> String {
+        let single_line_text: String = value
+            .split(|elem: char| elem == '\r' || elem == '\n')
+            .filter(|elem| !elem.is_empty())
+            .collect::<Vec<&str>>()
+            .join(" ");
+        let shortened_text: String = single_line_text.chars().take(max_length).collect();
+        return format!("\"{}\"", shortened_text.replace('"', "\"\""));
+    }
+
+    /// header record ("Vorlaufsatz") of format version 700, category 21 ("Buchungsstapel")
+    pub fn create_header_line(
+        settings: &DatevExportSettings,
+        created_at: &DateTime<Utc>,
+    ) -> String {
+        let created_text = format!(
+            "{}{:03}",
+            created_at.format("%Y%m%d%H%M%S"),
+            created_at.timestamp_subsec_millis().min(999)
+        );
+        let fields: Vec<String> = vec![
+            "\"EXTF\"".into(),
+            "700".into(),
+            "21".into(),
+            "\"Buchungsstapel\"".into(),
+            "13".into(),
+            created_text,
+            "".into(),
+            "\"RE\"".into(),
+            "\"\"".into(),
+            "\"\"".into(),
+            settings.consultant_number.to_string(),
+            settings.client_number.to_string(),
+            settings.fiscal_year_start.format("%Y%m%d").to_string(),
+            settings.account_length.to_string(),
+            settings.period_from.format("%Y%m%d").to_string(),
+            settings.period_till.format("%Y%m%d").to_string(),
+            "\"\"".into(),
+            "\"\"".into(),
+            // 1 = financial accounting, 0 = independent of the accounting purpose
+            "1".into(),
+            "0".into(),
+            // not locked, the tax advisor can still change the entries
+            "0".into(),
+            "\"EUR\"".into(),
+            "".into(),
+            "\"\"".into(),
+            "".into(),
+            "".into(),
+            "\"\"".into(),
+            "".into(),
+            "".into(),
+            "\"\"".into(),
+            "\"\"".into(),
+        ];
+        return format!("{}\r\n", fields.join(";"));
+    }
+
+    /// headings of the columns, only the first columns up to the booking text are written
+    pub fn create_column_line() -> String {
+        let headings = [
+            "Umsatz (ohne Soll/Haben-Kz)",
+            "Soll/Haben-Kennzeichen",
+            "WKZ Umsatz",
+            "Kurs",
+            "Basis-Umsatz",
+            "WKZ Basis-Umsatz",
+            "Konto",
+            "Gegenkonto (ohne BU-Schlüssel)",
+            "BU-Schlüssel",
+            "Belegdatum",
+            "Belegfeld 1",
+            "Belegfeld 2",
+            "Skonto",
+            "Buchungstext",
+        ];
+        return format!("{}\r\n", headings.join(";"));
+    }
+
+    pub fn create_row_line(row: &DatevBookingRow) -> String {
+        let fields: Vec<String> = vec![
+            FinanceCsvExportHandle::format_amount(row.amount as i128, ','),
+            "\"S\"".into(),
+            "\"EUR\"".into(),
+            "".into(),
+            "".into(),
+            "\"\"".into(),
+            row.debit_account_number.to_string(),
+            row.credit_account_number.to_string(),
+            "\"\"".into(),
+            // day and month, the year is taken from the period of the header
+            row.document_date.format("%d%m").to_string(),
+            FinanceDatevExportHandle::quote_text(
+                &row.document_number,
+                FinanceDatevExportHandle::DOCUMENT_NUMBER_LENGTH,
+            ),
+            "\"\"".into(),
+            "".into(),
+            FinanceDatevExportHandle::quote_text(
+                &row.booking_text,
+                FinanceDatevExportHandle::BOOKING_TEXT_LENGTH,
+            ),
+        ];
+        return format!("{}\r\n", fields.join(";"));
+    }
+
+    pub fn rows_to_text(
+        settings: &DatevExportSettings,
+        created_at: &DateTime<Utc>,
+        rows: &Vec<DatevBookingRow>,
+    ) -> String {
+        let mut return_object = FinanceDatevExportHandle::create_header_line(settings, created_at);
+        return_object.push_str(&FinanceDatevExportHandle::create_column_line());
+        for row in rows {
+            return_object.push_str(&FinanceDatevExportHandle::create_row_line(row));
+        }
+        return return_object;
+    }
+
+    /// DATEV reads ANSI files, characters without Windows-1252 code are replaced by '?'
+    pub fn encode_windows_1252(text: &str) -> Vec<u8> {
+        let mut return_object = Vec::new();
+        let mut char_buffer = [0u8; 4];
+        for text_char in text.chars() {
+            let (encoded_bytes, _, had_errors) =
+                WINDOWS_1252.encode(text_char.encode_utf8(&mut char_buffer));
+            if had_errors {
+                return_object.push(b'?');
+            } else {
+                return_object.extend_from_slice(&encoded_bytes);
+            }
+        }
+        return return_object;
+    }
+
+    /// pairs the legs of a split entry greedily, every row books the smaller of the
+    /// remaining amounts from the current debit leg to the current credit leg
+    pub fn pair_booking_legs(
+        debit_legs: &Vec<(u32, u64)>,
+        credit_legs: &Vec<(u32, u64)>,
+    ) -> Result<Vec<(u32, u32, u64)>, String> {
+        let mut return_object = Vec::new();
+        let mut remaining_debit: Vec<(u32, u64)> = debit_legs.clone();
+        let mut remaining_credit: Vec<(u32, u64)> = credit_legs.clone();
+        let mut debit_position = 0;
+        let mut credit_position = 0;
+        while debit_position < remaining_debit.len() && credit_position < remaining_credit.len() {
+            let row_amount = remaining_debit[debit_position]
+                .1
+                .min(remaining_credit[credit_position].1);
+            if row_amount > 0 {
+                return_object.push((
+                    remaining_debit[debit_position].0,
+                    remaining_credit[credit_position].0,
+                    row_amount,
+                ));
+            }
+            remaining_debit[debit_position].1 -= row_amount;
+            remaining_credit[credit_position].1 -= row_amount;
+            if remaining_debit[debit_position].1 == 0 {
+                debit_position += 1;
+            }
+            if remaining_credit[credit_position].1 == 0 {
+                credit_position += 1;
+            }
+        }
+        let open_debit: u64 = remaining_debit.iter().map(|elem| elem.1).sum();
+        let open_credit: u64 = remaining_credit.iter().map(|elem| elem.1).sum();
+        if open_debit != 0 || open_credit != 0 {
+            return Err("debit and credit legs do not balance".into());
+        }
+        return Ok(return_object);
+    }
+
+    fn get_account_number(
+        accounts: &HashMap<Uuid, &FinanceAccount>,
+        finance_account_id: &Uuid,
+        missing_accounts: &mut Vec<String>,
+    ) -> u32 {
+        let account_option = accounts.get(finance_account_id);
+        if account_option.is_none() {
+            let missing_text = finance_account_id.to_string();
+            if !missing_accounts.contains(&missing_text) {
+                missing_accounts.push(missing_text);
+            }
+            return 0;
+        }
+        let account = account_option.unwrap();
+        if account.account_number.is_none() {
+            if !missing_accounts.contains(&account.title) {
+                missing_accounts.push(account.title.clone());
+            }
+            return 0;
+        }
+        return account.account_number.unwrap();
+    }
+
+    /// rows of all journal entries of the period, closing entries are not exported
+    /// because the closing is done by the tax advisor
+    pub async fn create_booking_rows(
+        &self,
+        settings: &DatevExportSettings,
+    ) -> Result<Vec<DatevBookingRow>, String> {
+        let check_result = FinanceDatevExportHandle::check_settings(settings);
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+        let time_from = Utc.from_utc_datetime(&settings.period_from.and_hms_opt(0, 0, 0).unwrap());
+        let time_till = Utc.from_utc_datetime(
+            &settings
+                .period_till
+                .and_hms_milli_opt(23, 59, 59, 999)
+                .unwrap(),
+        );
+
+        let accounts_result = self
+            .db_connector
+            .finance_account_list(&self.db_connection_settings, &self.user_id, None)
+            .await;
+        if accounts_result.is_err() {
+            return Err(accounts_result.unwrap_err());
+        }
+        let account_list = accounts_result.unwrap();
+        let accounts: HashMap<Uuid, &FinanceAccount> =
+            account_list.iter().map(|elem| (elem.id, elem)).collect();
+        let max_account_digits = settings.account_length as usize + 1;
+        let too_long_account = account_list.iter().find(|elem| {
+            elem.account_number.is_some()
+                && elem.account_number.unwrap().to_string().len() > max_account_digits
+        });
+        if too_long_account.is_some() {
+            return Err(format!(
+                "account number {} of account {} is longer than the account length allows",
+                too_long_account.unwrap().account_number.unwrap(),
+                too_long_account.unwrap().title
+            ));
+        }
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let journal_entries_result = booking_handle
+            .list_journal_entries(Some(time_from), Some(time_till))
+            .await;
+        if journal_entries_result.is_err() {
+            return Err(journal_entries_result.unwrap_err());
+        }
+        let mut journal_entries: Vec<FinanceJournalEntry> = journal_entries_result
+            .unwrap()
+            .into_iter()
+            .filter(|elem| !elem.is_closing)
+            .collect();
+        journal_entries.sort_by_key(|elem| (elem.booking_time, elem.running_number));
+
+        let mut booking_entries = Vec::new();
+        if journal_entries.iter().any(|elem| !elem.is_simple_entry) {
+            let search_options = account_list
+                .iter()
+                .map(|elem| {
+                    FinanceAccountBookingEntryListSearchOption::new(
+                        &elem.id,
+                        Some(time_from),
+                        Some(time_till),
+                    )
+                })
+                .collect();
+            let booking_entries_result = booking_handle
+                .list_account_booking_entries(search_options)
+                .await;
+            if booking_entries_result.is_err() {
+                return Err(booking_entries_result.unwrap_err());
+            }
+            booking_entries = booking_entries_result.unwrap();
+        }
+
+        let mut missing_accounts: Vec<String> = Vec::new();
+        let mut return_object = Vec::new();
+        for journal_entry in &journal_entries {
+            let mut row_amounts: Vec<(u32, u32, u64)> = Vec::new();
+            if journal_entry.is_simple_entry {
+                row_amounts.push((
+                    FinanceDatevExportHandle::get_account_number(
+                        &accounts,
+                        &journal_entry.debit_finance_account_id,
+                        &mut missing_accounts,
+                    ),
+                    FinanceDatevExportHandle::get_account_number(
+                        &accounts,
+                        &journal_entry.credit_finance_account_id,
+                        &mut missing_accounts,
+                    ),
+                    journal_entry.amount,
+                ));
+            } else {
+                let mut debit_legs = Vec::new();
+                let mut credit_legs = Vec::new();
+                // the main accounts of the journal entry are paired first
+                let mut journal_booking_entries: Vec<_> = booking_entries
+                    .iter()
+                    .filter(|elem| elem.finance_journal_diary_id.eq(&journal_entry.id))
+                    .collect();
+                journal_booking_entries.sort_by_key(|elem| {
+                    elem.finance_account_id
+                        .ne(&journal_entry.debit_finance_account_id)
+                        && elem
+                            .finance_account_id
+                            .ne(&journal_entry.credit_finance_account_id)
+                });
+                for booking_entry in journal_booking_entries {
+                    let account_number = FinanceDatevExportHandle::get_account_number(
+                        &accounts,
+                        &booking_entry.finance_account_id,
+                        &mut missing_accounts,
+                    );
+                    match booking_entry.booking_type {
+                        BookingEntryType::Debit | BookingEntryType::SaldoDebit => {
+                            debit_legs.push((account_number, booking_entry.amount))
+                        }
+                        BookingEntryType::Credit | BookingEntryType::SaldoCredit => {
+                            credit_legs.push((account_number, booking_entry.amount))
+                        }
+                    }
+                }
+                let pair_result =
+                    FinanceDatevExportHandle::pair_booking_legs(&debit_legs, &credit_legs);
+                if pair_result.is_err() {
+                    return Err(format!(
+                        "could not export journal entry {}: {}",
+                        journal_entry.running_number,
+                        pair_result.unwrap_err()
+                    ));
+                }
+                row_amounts = pair_result.unwrap();
+            }
+            for (debit_account_number, credit_account_number, amount) in row_amounts {
+                return_object.push(DatevBookingRow {
+                    amount,
+                    debit_account_number,
+                    credit_account_number,
+                    document_date: journal_entry.booking_time.date_naive(),
+                    document_number: journal_entry.running_number.to_string(),
+                    booking_text: journal_entry.title.clone(),
+                });
+            }
+        }
+        if !missing_accounts.is_empty() {
+            return Err(format!(
+                "the following accounts need an account number for the DATEV export: {}",
+                missing_accounts.join(", ")
+            ));
+        }
+        return Ok(return_object);
+    }
+
+    /// booking batch as text, use export_booking_batch for the encoded file
+    pub async fn create_booking_batch_text(
+        &self,
+        settings: &DatevExportSettings,
+        created_at: &DateTime<Utc>,
+    ) -> Result<String, String> {
+        let rows_result = self.create_booking_rows(settings).await;
+        if rows_result.is_err() {
+            return Err(rows_result.unwrap_err());
+        }
+        return Ok(FinanceDatevExportHandle::rows_to_text(
+            settings,
+            created_at,
+            &rows_result.unwrap(),
+        ));
+    }
+
+    pub async fn export_booking_batch(
+        &self,
+        settings: &DatevExportSettings,
+    ) -> Result<Vec<u8>, String> {
+        let text_result = self.create_booking_batch_text(settings, &Utc::now()).await;
+        if text_result.is_err() {
+            return Err(text_result.unwrap_err());
+        }
+        return Ok(FinanceDatevExportHandle::encode_windows_1252(
+            &text_result.unwrap(),
+        ));
+    }
+
+    pub fn export_booking_batch_sync(
+        &self,
+        settings: &DatevExportSettings,
+    ) -> Result<Vec<u8>, String> {
+        let temp_var_0 = executor::block_on(self.export_booking_batch(settings));
+        return temp_var_0;
+    }
+}
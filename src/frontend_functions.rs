@@ -1,7 +1,7 @@
-use std::borrow::Borrow;
+use std::{borrow::Borrow, collections::HashMap};
 
 use anyhow::{Error, Ok};
-use async_session::chrono::Utc;
+use async_session::chrono::{DateTime, Datelike, TimeZone, Utc};
 use futures::executor;
 use log::error;
 use mongodb::bson::Uuid;
@@ -12,17 +12,115 @@ use crate::{
     accounting_database::FinanceAccountBookingEntryListSearchOption,
     accounting_logic::FinanceBookingHandle,
     convert_tools::ConvertTools,
-    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_mongodb::DbConnectionSetting,
     datatypes::{
-        AccountBalanceType, BookingEntryType, GenerallUserData, PasswordResetTokenRequestResult,
+        AccountBalanceType, BookingEntryType, FinanceJournalEntry, GenerallUserData,
+        PasswordResetTokenRequestResult,
     },
     html_render::{AccountTableBookingRow, AccountTableTemplate, JournalTableRow},
     mail_handle::{self, validate_email_format, SimpleMailData, SmtpMailSetting},
     setting_struct::SettingStruct,
+    user_database::DBUserFunctions,
 };
 
+/// time of a balance sheet report: end of the given day, current time when no date is given
+pub fn get_report_time(report_date: &Option<String>) -> Result<DateTime<Utc>, Error> {
+    if report_date.is_none() || report_date.as_ref().unwrap().trim().is_empty() {
+        return Ok(Utc::now());
+    }
+    let parse_result =
+        ConvertTools::get_utc_time_from_date_input(report_date.as_ref().unwrap(), true);
+    if parse_result.is_err() {
+        return Err(anyhow::anyhow!(parse_result.unwrap_err()));
+    }
+    return Ok(parse_result.unwrap());
+}
+
+/// booking time of a new entry: current time when no date is given, noon (UTC) of the given day
+/// when only a date is given, otherwise the given date and time (HH:MM or HH:MM:SS)
+pub fn get_booking_time(
+    booking_date: &Option<String>,
+    booking_time: &Option<String>,
+) -> Result<DateTime<Utc>, Error> {
+    let date_given = booking_date.is_some() && !booking_date.as_ref().unwrap().trim().is_empty();
+    let time_given = booking_time.is_some() && !booking_time.as_ref().unwrap().trim().is_empty();
+    if !date_given {
+        if time_given {
+            return Err(anyhow::anyhow!("a booking time requires a booking date"));
+        }
+        return Ok(Utc::now());
+    }
+    let time_input = if time_given {
+        booking_time.as_ref().unwrap().as_str()
+    } else {
+        "12:00"
+    };
+    let parse_result =
+        ConvertTools::get_utc_time_from_date_time_input(booking_date.as_ref().unwrap(), time_input);
+    if parse_result.is_err() {
+        return Err(anyhow::anyhow!(parse_result.unwrap_err()));
+    }
+    return Ok(parse_result.unwrap());
+}
+
+/// optional time of a report period: start or end of the given day, None when no date is given
+pub fn get_optional_report_time(
+    report_date: &Option<String>,
+    end_of_day: bool,
+) -> Result<Option<DateTime<Utc>>, Error> {
+    if report_date.is_none() || report_date.as_ref().unwrap().trim().is_empty() {
+        return Ok(None);
+    }
+    let parse_result =
+        ConvertTools::get_utc_time_from_date_input(report_date.as_ref().unwrap(), end_of_day);
+    if parse_result.is_err() {
+        return Err(anyhow::anyhow!(parse_result.unwrap_err()));
+    }
+    return Ok(Some(parse_result.unwrap()));
+}
+
+/// tags of a new booking entry from a comma separated input, empty parts are skipped
+pub fn get_booking_tags(tags_input: &Option<String>) -> Vec<String> {
+    if tags_input.is_none() {
+        return Vec::new();
+    }
+    return tags_input
+        .as_ref()
+        .unwrap()
+        .split(',')
+        .map(|elem| elem.trim().to_string())
+        .filter(|elem| !elem.is_empty())
+        .collect();
+}
+
+/// time range of an income statement report: from start of date_from till end of date_till,
+/// when no dates are given the current month up to now is used
+pub fn get_report_time_range(
+    date_from: &Option<String>,
+    date_till: &Option<String>,
+) -> Result<(DateTime<Utc>, DateTime<Utc>), Error> {
+    let time_from;
+    if date_from.is_none() || date_from.as_ref().unwrap().trim().is_empty() {
+        let current_time = Utc::now();
+        time_from = Utc
+            .with_ymd_and_hms(current_time.year(), current_time.month(), 1, 0, 0, 0)
+            .unwrap();
+    } else {
+        let parse_result =
+            ConvertTools::get_utc_time_from_date_input(date_from.as_ref().unwrap(), false);
+        if parse_result.is_err() {
+            return Err(anyhow::anyhow!(parse_result.unwrap_err()));
+        }
+        time_from = parse_result.unwrap();
+    }
+    let time_till = get_report_time(date_till)?;
+
+    return Ok((time_from, time_till));
+}
+
 pub async fn register_user_with_email_verfication(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     user_name: &String,
     user_password: &Secret<String>,
     user_email: &String,
@@ -45,8 +143,12 @@ pub async fn register_user_with_email_verfication(
     };
 
     //create_credentials checks if user is already there
-    let create_result =
-        crate::password_handle::create_credentials(db_connection, &new_user_credentials).await;
+    let create_result = crate::password_handle::create_credentials(
+        db_connection,
+        db_connector,
+        &new_user_credentials,
+    )
+    .await;
     if create_result.is_err() {
         return Err(anyhow::anyhow!(
             "error creating user: {}",
@@ -54,8 +156,9 @@ pub async fn register_user_with_email_verfication(
         ));
     }
 
-    let update_result =
-        DbHandlerMongoDB::update_user_email(&db_connection, &user_name, user_email).await;
+    let update_result = db_connector
+        .update_user_email(&db_connection, &user_name, user_email)
+        .await;
     if update_result.is_err() {
         return Err(anyhow::anyhow!(
             "error setting email: {}",
@@ -143,10 +246,11 @@ async fn send_email_verification_mail(
 
 pub async fn get_general_userdata_fromdatabase(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     user_name: &String,
 ) -> Result<GenerallUserData, Error> {
     let get_result_async =
-        DbHandlerMongoDB::get_user_general_data_by_user_name(&db_connection, user_name);
+        db_connector.get_user_general_data_by_user_name(&db_connection, user_name);
 
     let get_result: Result<GenerallUserData, String> = get_result_async.await;
 
@@ -162,14 +266,12 @@ pub async fn get_general_userdata_fromdatabase(
 
 pub async fn save_general_userdata(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     user_name: &String,
     general_user_data: &GenerallUserData,
 ) -> Result<String, Error> {
-    let save_data_result_async = DbHandlerMongoDB::update_general_user_data_by_name(
-        &db_connection,
-        user_name,
-        general_user_data,
-    );
+    let save_data_result_async =
+        db_connector.update_general_user_data_by_name(&db_connection, user_name, general_user_data);
     let save_data_result = save_data_result_async.await;
     if save_data_result.is_err() {
         return Err(anyhow::anyhow!(
@@ -262,6 +364,26 @@ pub async fn generate_account_tables<'a>(
     booking_handler: &FinanceBookingHandle<'a>,
     config_handle: &FinanceAccountingConfigHandle<'a>,
     limit_account_ids: Option<&Vec<Uuid>>,
+) -> Result<Vec<AccountTableTemplate>, Error> {
+    let return_var = generate_account_tables_in_range(
+        booking_handler,
+        config_handle,
+        limit_account_ids,
+        None,
+        None,
+    )
+    .await;
+    return return_var;
+}
+
+/// account tables with the entries of the time range, without a start the entries since the last saldo
+/// are listed, the closing row shows the balance as of the end of the range
+pub async fn generate_account_tables_in_range<'a>(
+    booking_handler: &FinanceBookingHandle<'a>,
+    config_handle: &FinanceAccountingConfigHandle<'a>,
+    limit_account_ids: Option<&Vec<Uuid>>,
+    booking_time_from: Option<DateTime<Utc>>,
+    booking_time_till: Option<DateTime<Utc>>,
 ) -> Result<Vec<AccountTableTemplate>, Error> {
     let mut return_list = Vec::new();
 
@@ -280,7 +402,9 @@ pub async fn generate_account_tables<'a>(
 
     let saldo_info_result_future =
         booking_handler.finance_get_last_saldo_account_entries(Some(account_ids.clone()));
-    let balance_info_result = booking_handler.calculate_balance_info(&account_ids).await;
+    let balance_info_result = booking_handler
+        .calculate_balance_info_till(&account_ids, booking_time_till)
+        .await;
     let saldo_info_result = saldo_info_result_future.await;
 
     if balance_info_result.is_err() {
@@ -300,11 +424,24 @@ pub async fn generate_account_tables<'a>(
         } else {
             None
         };
+        let time_start_option = if booking_time_from.is_some() {
+            booking_time_from
+        } else if booking_time_till.is_some()
+            && last_saldo_time_option.is_some()
+            && last_saldo_time_option
+                .unwrap()
+                .gt(&booking_time_till.unwrap())
+        {
+            // the last saldo is after the range, so all entries up to the end of the range are listed
+            None
+        } else {
+            last_saldo_time_option
+        };
 
         let search_option = FinanceAccountBookingEntryListSearchOption::new(
             &account_info.id,
-            last_saldo_time_option,
-            None,
+            time_start_option,
+            booking_time_till,
         );
         search_options.push(search_option);
     }
@@ -316,7 +453,28 @@ pub async fn generate_account_tables<'a>(
         return Err(anyhow::anyhow!(booking_info_result.unwrap_err()));
     }
 
-    let booking_info = booking_info_result.unwrap();
+    let mut booking_info = booking_info_result.unwrap();
+
+    let journal_entries_result = booking_handler.list_journal_entries(None, None).await;
+    if journal_entries_result.is_err() {
+        return Err(anyhow::anyhow!(journal_entries_result.unwrap_err()));
+    }
+    let journal_entries = journal_entries_result.unwrap();
+
+    // entries can be back-dated and share a booking time, so the running number decides within the same time
+    let running_number_per_journal_entry: HashMap<Uuid, u64> = journal_entries
+        .iter()
+        .map(|elem| (elem.id, elem.running_number))
+        .collect();
+    booking_info.sort_by_key(|elem| {
+        (
+            elem.booking_time,
+            running_number_per_journal_entry
+                .get(&elem.finance_journal_diary_id)
+                .cloned()
+                .unwrap_or_default(),
+        )
+    });
 
     for account_info in &account_info_list {
         let balance_info_position = balance_info
@@ -335,6 +493,17 @@ pub async fn generate_account_tables<'a>(
         let mut booking_rows_per_account = Vec::new();
 
         for booking_entry in booking_info_per_account {
+            let journal_entry_position_option = journal_entries
+                .iter()
+                .position(|elem| elem.id.eq(&booking_entry.finance_journal_diary_id));
+            let reversal_info = if journal_entry_position_option.is_some() {
+                get_reversal_info(
+                    &journal_entries[journal_entry_position_option.unwrap()],
+                    &journal_entries,
+                )
+            } else {
+                "".into()
+            };
             let booking_row = AccountTableBookingRow {
                 booking_time: booking_entry.booking_time,
                 is_credit: booking_entry.booking_type.eq(&BookingEntryType::Credit)
@@ -344,6 +513,8 @@ pub async fn generate_account_tables<'a>(
                 is_saldo: false,
                 title: booking_entry.title.clone(),
                 amount_currency: (booking_entry.amount as f64) / (100 as f64),
+                amount_cents: booking_entry.amount,
+                reversal_info,
             };
             booking_rows_per_account.push(booking_row);
         }
@@ -351,7 +522,7 @@ pub async fn generate_account_tables<'a>(
             let position = balance_info_position.unwrap();
             let account_balance_info = &balance_info[position];
             let saldo_row = AccountTableBookingRow {
-                booking_time: Utc::now(),
+                booking_time: booking_time_till.unwrap_or(Utc::now()),
                 is_credit: account_balance_info
                     .balance_type
                     .eq(&AccountBalanceType::Credit),
@@ -365,6 +536,8 @@ pub async fn generate_account_tables<'a>(
                     "Debit".into()
                 },
                 amount_currency: (account_balance_info.amount as f64) / (100 as f64),
+                amount_cents: account_balance_info.amount,
+                reversal_info: "".into(),
             };
             booking_rows_per_account.push(saldo_row);
         }
@@ -394,10 +567,25 @@ pub fn generate_account_tables_sync<'a>(
 pub async fn generate_review_journal_entries<'a>(
     booking_handler: &FinanceBookingHandle<'a>,
     config_handle: &FinanceAccountingConfigHandle<'a>,
+    tag: Option<String>,
+) -> Result<Vec<JournalTableRow>, Error> {
+    let return_var =
+        generate_review_journal_entries_in_range(booking_handler, config_handle, tag, None, None)
+            .await;
+    return return_var;
+}
+
+pub async fn generate_review_journal_entries_in_range<'a>(
+    booking_handler: &FinanceBookingHandle<'a>,
+    config_handle: &FinanceAccountingConfigHandle<'a>,
+    tag: Option<String>,
+    booking_time_from: Option<DateTime<Utc>>,
+    booking_time_till: Option<DateTime<Utc>>,
 ) -> Result<Vec<JournalTableRow>, Error> {
     let mut return_list = Vec::new();
 
-    let journal_entries_result_future = booking_handler.list_journal_entries(None, None);
+    let journal_entries_result_future =
+        booking_handler.list_journal_entries_with_tag(booking_time_from, booking_time_till, tag);
 
     let accounts_result: Result<Vec<crate::datatypes::FinanceAccount>, String> =
         config_handle.finance_account_list_async(None).await;
@@ -413,50 +601,141 @@ pub async fn generate_review_journal_entries<'a>(
     }
     let journal_entries = journal_entries_result.unwrap();
 
-    for journal_entry in &journal_entries {
-        let credit_account_position_option = account_info_list
-            .iter()
-            .position(|elem| elem.id.eq(&journal_entry.credit_finance_account_id));
-        let debit_account_position_option = account_info_list
+    // the journal entry only holds the first leg of each side, the booking entries
+    // hold all accounts of an entry with more than two accounts
+    let mut booking_entries = Vec::new();
+    if journal_entries.iter().any(|elem| !elem.is_simple_entry) {
+        let search_options = account_info_list
             .iter()
-            .position(|elem| elem.id.eq(&journal_entry.debit_finance_account_id));
+            .map(|elem| {
+                FinanceAccountBookingEntryListSearchOption::new(
+                    &elem.id,
+                    booking_time_from,
+                    booking_time_till,
+                )
+            })
+            .collect();
+        let booking_entries_result = booking_handler
+            .list_account_booking_entries(search_options)
+            .await;
+        if booking_entries_result.is_err() {
+            return Err(anyhow::anyhow!(booking_entries_result.unwrap_err()));
+        }
+        booking_entries = booking_entries_result.unwrap();
+    }
+
+    for journal_entry in &journal_entries {
+        let mut debit_account_ids = vec![journal_entry.debit_finance_account_id];
+        let mut credit_account_ids = vec![journal_entry.credit_finance_account_id];
+        if !journal_entry.is_simple_entry {
+            for booking_entry in booking_entries
+                .iter()
+                .filter(|elem| elem.finance_journal_diary_id.eq(&journal_entry.id))
+            {
+                let account_ids = match booking_entry.booking_type {
+                    BookingEntryType::Debit | BookingEntryType::SaldoDebit => {
+                        &mut debit_account_ids
+                    }
+                    BookingEntryType::Credit | BookingEntryType::SaldoCredit => {
+                        &mut credit_account_ids
+                    }
+                };
+                if !account_ids.contains(&booking_entry.finance_account_id) {
+                    account_ids.push(booking_entry.finance_account_id);
+                }
+            }
+        }
 
         return_list.push(JournalTableRow {
             id: journal_entry.id.to_string(),
             booking_time: journal_entry.booking_time,
             is_simple_entry: journal_entry.is_simple_entry,
             is_saldo: journal_entry.is_saldo,
-            credit_account_name: if credit_account_position_option.is_none() {
-                "unkown account".into()
-            } else {
-                account_info_list[credit_account_position_option.unwrap()]
-                    .title
-                    .clone()
-            },
-            debit_account_name: if debit_account_position_option.is_none() {
-                "unkown account".into()
-            } else {
-                account_info_list[debit_account_position_option.unwrap()]
-                    .title
-                    .clone()
-            },
+            credit_account_name: get_account_names(&credit_account_ids, &account_info_list),
+            debit_account_name: get_account_names(&debit_account_ids, &account_info_list),
             title: journal_entry.title.clone(),
             description: journal_entry.description.clone(),
+            tags: journal_entry.tags.join(", "),
             currency_amount: (journal_entry.amount as f64) / (100 as f64),
+            amount_cents: journal_entry.amount,
             running_number: journal_entry.running_number as i64,
+            reversal_info: get_reversal_info(journal_entry, &journal_entries),
+            can_be_reversed: !journal_entry.is_saldo
+                && !journal_entry.is_closing
+                && journal_entry.reverse_of_journal_entry_id.is_none()
+                && journal_entry.reversed_by_journal_entry_id.is_none(),
+            attachments: Vec::new(),
         })
     }
 
     return Ok(return_list);
 }
 
+/// titles of the given accounts separated by comma
+fn get_account_names(
+    account_ids: &Vec<Uuid>,
+    account_info_list: &Vec<crate::datatypes::FinanceAccount>,
+) -> String {
+    let mut account_names: Vec<String> = Vec::new();
+    for account_id in account_ids {
+        let account_position_option = account_info_list
+            .iter()
+            .position(|elem| elem.id.eq(account_id));
+        if account_position_option.is_none() {
+            account_names.push("unkown account".into());
+        } else {
+            account_names.push(
+                account_info_list[account_position_option.unwrap()]
+                    .title
+                    .clone(),
+            );
+        }
+    }
+    return account_names.join(", ");
+}
+
+/// text showing the reversal pairing of an journal entry, empty if not reversed
+fn get_reversal_info(
+    journal_entry: &FinanceJournalEntry,
+    journal_entries: &Vec<FinanceJournalEntry>,
+) -> String {
+    let get_running_number_text = |journal_entry_id: &Uuid| -> String {
+        let position_option = journal_entries
+            .iter()
+            .position(|elem| elem.id.eq(journal_entry_id));
+        if position_option.is_none() {
+            return "unknown entry".into();
+        }
+        return format!(
+            "#{}",
+            journal_entries[position_option.unwrap()].running_number
+        );
+    };
+
+    if journal_entry.reversed_by_journal_entry_id.is_some() {
+        return format!(
+            "reversed by {}",
+            get_running_number_text(&journal_entry.reversed_by_journal_entry_id.unwrap())
+        );
+    }
+    if journal_entry.reverse_of_journal_entry_id.is_some() {
+        return format!(
+            "reversal of {}",
+            get_running_number_text(&journal_entry.reverse_of_journal_entry_id.unwrap())
+        );
+    }
+    return "".into();
+}
+
 pub fn generate_review_journal_entries_sync<'a>(
     booking_handler: &FinanceBookingHandle<'a>,
     config_handle: &FinanceAccountingConfigHandle<'a>,
+    tag: Option<String>,
 ) -> Result<Vec<JournalTableRow>, Error> {
     let return_var = executor::block_on(generate_review_journal_entries(
         booking_handler,
         config_handle,
+        tag,
     ));
     return return_var;
 }
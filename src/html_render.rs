@@ -16,15 +16,27 @@ use serde::{Deserialize, Serialize};
 use crate::{
     accounting_config_logic::FinanceAccountingConfigHandle,
     accounting_logic::FinanceBookingHandle,
-    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB, EmailVerificationStatus},
+    accounting_report::FinanceReportHandle,
+    attachment_logic::{AttachmentStorageSetting, FinanceAttachmentHandle},
+    booking_template_logic::FinanceBookingTemplateHandle,
+    budget_logic::FinanceBudgetHandle,
+    categorization_rule_logic::FinanceCategorizationRuleHandle,
+    database_handler::DbHandler,
+    database_handler_mongodb::{DbConnectionSetting, EmailVerificationStatus},
+    datatypes::{
+        AccountCategory, FinanceBudgetReportLine, FinanceReportSection, FinanceTrialBalanceLine,
+    },
     frontend_functions::{
         generate_account_tables_sync, generate_review_journal_entries_sync,
-        get_general_userdata_fromdatabase,
+        get_general_userdata_fromdatabase, get_optional_report_time, get_report_time,
+        get_report_time_range,
     },
     password_handle::{
         check_email_status_by_name, create_credentials, validate_credentials, UserCredentials,
     },
-    session_data_handle::{SessionData, SessionDataResult},
+    reconciliation_logic::FinanceReconciliationHandle,
+    recurring_booking_logic::FinanceRecurringBookingHandle,
+    session_data_handle::{ServerSessionStore, SessionData, SessionDataResult},
     setting_struct::SettingStruct,
     user_handling::validate_user_email,
 };
@@ -101,9 +113,10 @@ pub async fn accept_login_form(
     let mut session = session_data.session_option.unwrap();
     let _result = session.insert("user_name", &credentials.username);
 
-    let a_store: async_mongodb_session::MongodbSessionStore = session_data.session_store;
+    let a_store: ServerSessionStore = session_data.session_store;
+    let db_handler = DbHandler::new(&db_connection);
 
-    match validate_credentials(&db_connection, &credentials).await {
+    match validate_credentials(&db_connection, &db_handler, &credentials).await {
         Ok(user_id) => {
             let local_settings: SettingStruct = SettingStruct::global().clone();
             let db_connection = DbConnectionSetting {
@@ -113,7 +126,8 @@ pub async fn accept_login_form(
                 instance: String::from(local_settings.backend_database_instance),
             };
             let mail_check_result =
-                check_email_status_by_name(&db_connection, &credentials.username).await;
+                check_email_status_by_name(&db_connection, &db_handler, &credentials.username)
+                    .await;
             if mail_check_result.is_err() {
                 debug!(target: "app::FinanceOverView","error with email: {}",&mail_check_result.unwrap_err());
                 Redirect::to("/invalid").into_response()
@@ -127,8 +141,7 @@ pub async fn accept_login_form(
                         let _result = session.insert("logged_in", true);
                         let _result2 = session.insert("user_account_id", user_id);
                         let _cookie3 = a_store.store_session(session).await;
-                        let mongo_db = DbHandlerMongoDB::new(&db_connection);
-                        let _repair_result = mongo_db
+                        let _repair_result = db_handler
                             .repair_counter_record_for_user(&db_connection, &user_id)
                             .await;
                         debug!(target: "app::FinanceOverView","user_id is {}",user_id);
@@ -223,8 +236,9 @@ pub async fn user_home_handler(session_data: SessionDataResult) -> impl IntoResp
             password: String::from(local_settings.backend_database_password),
             instance: String::from(local_settings.backend_database_instance),
         };
+        let db_handler = DbHandler::new(&db_connection);
         let user_data_get_result_async =
-            get_general_userdata_fromdatabase(&db_connection, &username);
+            get_general_userdata_fromdatabase(&db_connection, &db_handler, &username);
 
         let user_data_result = user_data_get_result_async.await;
 
@@ -364,7 +378,9 @@ pub async fn create_login_handler(form: Form<LoginFormInput>) -> impl IntoRespon
         instance: String::from(local_settings.backend_database_instance),
     };
 
-    let create_result = create_credentials(&db_connection, &new_user_credentials).await;
+    let db_handler = DbHandler::new(&db_connection);
+    let create_result =
+        create_credentials(&db_connection, &db_handler, &new_user_credentials).await;
     if create_result.is_err() {
         clt_template.user_name = new_user_credentials.username.to_string();
         clt_template.create_result = create_result.unwrap_err().to_string();
@@ -417,7 +433,9 @@ pub async fn validate_user_email_handler(form: Form<ValidateUserEmailInput>) ->
         password: String::from(local_settings.backend_database_password),
         instance: String::from(local_settings.backend_database_instance),
     };
-    let check_result = validate_user_email(&db_connection, &form.user_name, &form.token).await;
+    let db_handler = DbHandler::new(&db_connection);
+    let check_result =
+        validate_user_email(&db_connection, &db_handler, &form.user_name, &form.token).await;
 
     if check_result.is_err() {
         st.validation_detail_result = check_result.unwrap_err();
@@ -488,6 +506,19 @@ pub struct AccountTypeTemplate {
     pub id: String,
     pub name: String,
     pub description: String,
+    /// integer value of the account category, empty when no category is set
+    pub category: String,
+}
+
+impl AccountTypeTemplate {
+    pub fn from_account_type(account_type: &crate::datatypes::FinanceAccountType) -> Self {
+        Self {
+            id: account_type.id.to_string(),
+            name: account_type.title.clone(),
+            description: account_type.description.clone(),
+            category: account_type.account_category.to_int().to_string(),
+        }
+    }
 }
 
 #[derive(Serialize, Deserialize, Debug, Clone)]
@@ -496,6 +527,16 @@ pub struct AccountTemplate {
     pub name: String,
     pub description: String,
     pub type_title: String,
+    pub account_number: String,
+}
+
+impl AccountTemplate {
+    pub fn format_account_number(account_number: &Option<u32>) -> String {
+        return match account_number {
+            Some(number) => number.to_string(),
+            None => "".into(),
+        };
+    }
 }
 
 pub async fn display_accounting_config_main_page(
@@ -551,7 +592,7 @@ pub async fn display_accounting_config_main_page(
         password: String::from(local_setting.backend_database_password),
         instance: String::from(&local_setting.backend_database_instance),
     };
-    let db_handler = DbHandlerMongoDB::new(&db_connection);
+    let db_handler = DbHandler::new(&db_connection);
 
     {
         let accounting_config_handle =
@@ -573,11 +614,7 @@ pub async fn display_accounting_config_main_page(
 
             //let available_account_types = account_types_result.unwrap();
             for some_type in account_types_result.as_ref().unwrap() {
-                return_account_type_list.push(AccountTypeTemplate {
-                    id: some_type.id.to_string(),
-                    name: some_type.title.clone(),
-                    description: some_type.description.clone(),
-                });
+                return_account_type_list.push(AccountTypeTemplate::from_account_type(some_type));
             }
 
             let accounts_result: Result<Vec<crate::datatypes::FinanceAccount>, String> =
@@ -607,6 +644,9 @@ pub async fn display_accounting_config_main_page(
                     name: some_account.title,
                     description: some_account.description,
                     type_title: type_title.into(),
+                    account_number: AccountTemplate::format_account_number(
+                        &some_account.account_number,
+                    ),
                 });
             }
         }
@@ -626,11 +666,24 @@ pub async fn display_accounting_config_main_page(
     HtmlTemplate(return_value)
 }
 
+/// booking template in the view of the booking form (from credit account, to debit account)
+#[derive(Debug, Clone)]
+pub struct BookingTemplateTemplate {
+    pub id: String,
+    pub name: String,
+    pub from_credit_account_id: String,
+    pub to_debit_account_id: String,
+    pub amount_currency: f64,
+    pub title: String,
+    pub description: String,
+}
+
 #[derive(Template)]
 #[template(path = "AccountingOverview/AccountingMain.html")]
 pub struct AccountingMainTemplate {
     username: String,
     accounts: Vec<AccountTemplate>,
+    booking_templates: Vec<BookingTemplateTemplate>,
 }
 
 pub async fn display_accounting_main_page(session_data: SessionDataResult) -> impl IntoResponse {
@@ -650,6 +703,7 @@ pub async fn display_accounting_main_page(session_data: SessionDataResult) -> im
         let return_value = AccountingMainTemplate {
             username: "not logged in".to_string(),
             accounts: empty_account_list,
+            booking_templates: Vec::new(),
         };
         headers.insert(
             axum::http::header::REFRESH,
@@ -662,6 +716,7 @@ pub async fn display_accounting_main_page(session_data: SessionDataResult) -> im
         let return_value = AccountingMainTemplate {
             username: "Session expired".to_string(),
             accounts: empty_account_list,
+            booking_templates: Vec::new(),
         };
         headers.insert(
             axum::http::header::REFRESH,
@@ -680,7 +735,8 @@ pub async fn display_accounting_main_page(session_data: SessionDataResult) -> im
         password: String::from(local_setting.backend_database_password),
         instance: String::from(&local_setting.backend_database_instance),
     };
-    let db_handler = DbHandlerMongoDB::new(&db_connection);
+    let db_handler = DbHandler::new(&db_connection);
+    let mut return_booking_template_list: Vec<BookingTemplateTemplate> = Vec::new();
 
     {
         let accounting_config_handle =
@@ -695,6 +751,7 @@ pub async fn display_accounting_main_page(session_data: SessionDataResult) -> im
                 let return_value = AccountingMainTemplate {
                     username: "problems while getting account list".to_string(),
                     accounts: empty_account_list,
+                    booking_templates: Vec::new(),
                 };
                 return HtmlTemplate(return_value);
             }
@@ -705,14 +762,44 @@ pub async fn display_accounting_main_page(session_data: SessionDataResult) -> im
                     name: some_account.title,
                     description: some_account.description,
                     type_title: "not loaded".into(),
+                    account_number: AccountTemplate::format_account_number(
+                        &some_account.account_number,
+                    ),
                 });
             }
         }
+
+        let booking_template_handle =
+            FinanceBookingTemplateHandle::new(&db_connection, &user_id, &db_handler);
+        let booking_templates_result = booking_template_handle.booking_template_list_sync();
+        if booking_templates_result.is_err() {
+            warn!(target: "app::FinanceOverView","error in display_accounting_main_page for user {}: {}",username,booking_templates_result.unwrap_err());
+            let return_value = AccountingMainTemplate {
+                username: "problems while getting booking templates".to_string(),
+                accounts: empty_account_list,
+                booking_templates: Vec::new(),
+            };
+            return HtmlTemplate(return_value);
+        }
+
+        // same account mapping as do_create_booking_entry, so a template fills the form as it was saved
+        for some_booking_template in booking_templates_result.unwrap() {
+            return_booking_template_list.push(BookingTemplateTemplate {
+                id: some_booking_template.id.to_string(),
+                name: some_booking_template.name,
+                from_credit_account_id: some_booking_template.debit_finance_account_id.to_string(),
+                to_debit_account_id: some_booking_template.credit_finance_account_id.to_string(),
+                amount_currency: (some_booking_template.amount as f64) / (100 as f64),
+                title: some_booking_template.title,
+                description: some_booking_template.description,
+            });
+        }
     }
 
     let return_value = AccountingMainTemplate {
         username: username,
         accounts: return_account_list,
+        booking_templates: return_booking_template_list,
     };
 
     session.expire_in(std::time::Duration::from_secs(60 * 10));
@@ -730,6 +817,9 @@ pub struct AccountTableBookingRow {
     pub is_saldo: bool,
     pub title: String,
     pub amount_currency: f64,
+    /// exact amount for exports
+    pub amount_cents: u64,
+    pub reversal_info: String,
 }
 
 #[derive(Debug)]
@@ -797,7 +887,7 @@ pub async fn display_accounting_review_page(session_data: SessionDataResult) ->
         password: String::from(local_setting.backend_database_password),
         instance: String::from(&local_setting.backend_database_instance),
     };
-    let db_handler = DbHandlerMongoDB::new(&db_connection);
+    let db_handler = DbHandler::new(&db_connection);
 
     {
         let accounting_config_handle =
@@ -836,67 +926,245 @@ pub async fn display_accounting_review_page(session_data: SessionDataResult) ->
     HtmlTemplate(return_value)
 }
 
-#[derive(Debug)]
-pub struct JournalTableRow {
-    pub id: String,
-    pub booking_time: DateTime<Utc>,
-    pub is_simple_entry: bool,
-    pub is_saldo: bool,
-    pub credit_account_name: String,
-    pub debit_account_name: String,
+#[derive(Deserialize, Debug)]
+pub struct BalanceSheetRequest {
+    pub report_date: Option<String>,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct IncomeStatementRequest {
+    pub date_from: Option<String>,
+    pub date_till: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportAccountTemplate {
     pub title: String,
-    pub description: String,
-    pub currency_amount: f64,
-    pub running_number: i64,
+    pub amount_currency: f64,
 }
 
-#[derive(Debug, Template)]
-#[template(path = "AccountingOverview/AccountingJournalView.html")]
-pub struct AccountingJournalReviewTemplate {
+#[derive(Debug, Clone)]
+pub struct ReportGroupTemplate {
+    pub account_type_id: String,
+    pub title: String,
+    pub accounts: Vec<ReportAccountTemplate>,
+    pub total_currency: f64,
+}
+
+#[derive(Debug, Clone)]
+pub struct ReportSectionTemplate {
+    pub title: String,
+    pub groups: Vec<ReportGroupTemplate>,
+    pub total_currency: f64,
+}
+
+impl ReportSectionTemplate {
+    pub fn from_report_section(report_section: &FinanceReportSection) -> Self {
+        Self {
+            title: report_section.title.clone(),
+            groups: report_section
+                .groups
+                .iter()
+                .map(|group| ReportGroupTemplate {
+                    account_type_id: group.account_type_id.clone(),
+                    title: group.title.clone(),
+                    accounts: group
+                        .accounts
+                        .iter()
+                        .map(|account| ReportAccountTemplate {
+                            title: account.title.clone(),
+                            amount_currency: (account.amount as f64) / (100 as f64),
+                        })
+                        .collect(),
+                    total_currency: (group.total as f64) / (100 as f64),
+                })
+                .collect(),
+            total_currency: (report_section.total as f64) / (100 as f64),
+        }
+    }
+}
+
+/// category an account type can be moved to within a report
+#[derive(Debug, Clone)]
+pub struct ReportSectionCategoryTemplate {
+    pub value: i32,
+    pub name: String,
+}
+
+impl ReportSectionCategoryTemplate {
+    fn from_default_sections(default_sections: &Vec<(AccountCategory, &'static str)>) -> Vec<Self> {
+        return default_sections
+            .iter()
+            .map(|(account_category, _)| Self {
+                value: account_category.to_int(),
+                name: account_category.to_string(),
+            })
+            .collect();
+    }
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingBalanceSheet.html")]
+pub struct AccountingBalanceSheetTemplate {
     username: String,
-    journal_entries_list: Vec<JournalTableRow>,
+    report_date: String,
+    sections: Vec<ReportSectionTemplate>,
+    section_categories: Vec<ReportSectionCategoryTemplate>,
+    total_assets_currency: f64,
+    total_liabilities_and_equity_currency: f64,
+    difference_currency: f64,
 }
 
-pub async fn display_journal_page(session_data: SessionDataResult) -> impl IntoResponse {
-    debug!(target: "app::FinanceOverView","display journal review page");
+impl AccountingBalanceSheetTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            report_date: "".into(),
+            sections: Vec::new(),
+            section_categories: Vec::new(),
+            total_assets_currency: 0.0,
+            total_liabilities_and_equity_currency: 0.0,
+            difference_currency: 0.0,
+        }
+    }
+}
+
+pub async fn display_balance_sheet_page(
+    session_data: SessionDataResult,
+    Form(input): Form<BalanceSheetRequest>,
+) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display balance sheet page");
 
     let session_data = SessionData::from_session_data_result(session_data);
     let mut session = session_data.session_option.unwrap().clone();
 
     let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
 
-    let mut headers = HeaderMap::new();
+    if !is_logged_in {
+        return HtmlTemplate(AccountingBalanceSheetTemplate::empty("not logged in"));
+    }
 
-    let empty_journal_list: Vec<JournalTableRow> = Vec::with_capacity(0);
-    let mut return_journal_entries: Vec<JournalTableRow> = Vec::new();
+    if session.is_expired() {
+        return HtmlTemplate(AccountingBalanceSheetTemplate::empty("Session expired"));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let report_time_result = get_report_time(&input.report_date);
+    if report_time_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_balance_sheet_page for user {}: {}",username,report_time_result.unwrap_err());
+        return HtmlTemplate(AccountingBalanceSheetTemplate::empty(
+            "problems while reading report date",
+        ));
+    }
+    let report_time = report_time_result.unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let balance_sheet_result = {
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        report_handle.balance_sheet_sync(report_time)
+    };
+    if balance_sheet_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_balance_sheet_page for user {}: {}",username,balance_sheet_result.unwrap_err());
+        return HtmlTemplate(AccountingBalanceSheetTemplate::empty(
+            "problems while creating balance sheet",
+        ));
+    }
+    let balance_sheet = balance_sheet_result.unwrap();
+
+    let return_value = AccountingBalanceSheetTemplate {
+        username: username,
+        report_date: balance_sheet.report_time.format("%Y-%m-%d").to_string(),
+        sections: balance_sheet
+            .sections
+            .iter()
+            .map(ReportSectionTemplate::from_report_section)
+            .collect(),
+        section_categories: ReportSectionCategoryTemplate::from_default_sections(
+            &FinanceReportHandle::get_balance_sheet_default_sections(),
+        ),
+        total_assets_currency: (balance_sheet.total_assets as f64) / (100 as f64),
+        total_liabilities_and_equity_currency: (balance_sheet.total_liabilities_and_equity as f64)
+            / (100 as f64),
+        difference_currency: (balance_sheet.difference as f64) / (100 as f64),
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded balance sheet user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingIncomeStatement.html")]
+pub struct AccountingIncomeStatementTemplate {
+    username: String,
+    date_from: String,
+    date_till: String,
+    sections: Vec<ReportSectionTemplate>,
+    section_categories: Vec<ReportSectionCategoryTemplate>,
+    total_income_currency: f64,
+    total_expense_currency: f64,
+    net_income_currency: f64,
+}
+
+impl AccountingIncomeStatementTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            date_from: "".into(),
+            date_till: "".into(),
+            sections: Vec::new(),
+            section_categories: Vec::new(),
+            total_income_currency: 0.0,
+            total_expense_currency: 0.0,
+            net_income_currency: 0.0,
+        }
+    }
+}
+
+pub async fn display_income_statement_page(
+    session_data: SessionDataResult,
+    Form(input): Form<IncomeStatementRequest>,
+) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display income statement page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
 
     if !is_logged_in {
-        let return_value = AccountingJournalReviewTemplate {
-            username: "not logged in".to_string(),
-            journal_entries_list: empty_journal_list,
-        };
-        headers.insert(
-            axum::http::header::REFRESH,
-            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
-        );
-        return HtmlTemplate(return_value);
+        return HtmlTemplate(AccountingIncomeStatementTemplate::empty("not logged in"));
     }
 
     if session.is_expired() {
-        let return_value = AccountingJournalReviewTemplate {
-            username: "Session expired".to_string(),
-            journal_entries_list: empty_journal_list,
-        };
-        headers.insert(
-            axum::http::header::REFRESH,
-            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
-        );
-        return HtmlTemplate(return_value);
+        return HtmlTemplate(AccountingIncomeStatementTemplate::empty("Session expired"));
     }
 
     let username: String = session.get("user_name").unwrap();
     let user_id: Uuid = session.get("user_account_id").unwrap();
 
+    let report_time_range_result = get_report_time_range(&input.date_from, &input.date_till);
+    if report_time_range_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_income_statement_page for user {}: {}",username,report_time_range_result.unwrap_err());
+        return HtmlTemplate(AccountingIncomeStatementTemplate::empty(
+            "problems while reading report dates",
+        ));
+    }
+    let (time_from, time_till) = report_time_range_result.unwrap();
+
     let local_setting: SettingStruct = SettingStruct::global().clone();
     let db_connection = DbConnectionSetting {
         url: String::from(&local_setting.backend_database_url),
@@ -904,40 +1172,1091 @@ pub async fn display_journal_page(session_data: SessionDataResult) -> impl IntoR
         password: String::from(local_setting.backend_database_password),
         instance: String::from(&local_setting.backend_database_instance),
     };
-    let db_handler = DbHandlerMongoDB::new(&db_connection);
+    let db_handler = DbHandler::new(&db_connection);
 
-    {
-        let accounting_config_handle =
-            FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
-        let accounting_booking_handle =
-            FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
-        {
-            let table_generate_result = generate_review_journal_entries_sync(
-                &accounting_booking_handle,
-                &accounting_config_handle,
-            );
-            if table_generate_result.is_err() {
-                warn!(target: "app::FinanceOverView","error in display_accounting_review_page for user {}: {}",username,table_generate_result.unwrap_err());
-                let return_value = AccountingJournalReviewTemplate {
-                    username: "problems while getting account tables".to_string(),
-                    journal_entries_list: empty_journal_list,
-                };
-                return HtmlTemplate(return_value);
-            }
+    let income_statement_result = {
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        report_handle.income_statement_sync(time_from, time_till)
+    };
+    if income_statement_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_income_statement_page for user {}: {}",username,income_statement_result.unwrap_err());
+        return HtmlTemplate(AccountingIncomeStatementTemplate::empty(
+            "problems while creating income statement",
+        ));
+    }
+    let income_statement = income_statement_result.unwrap();
 
-            return_journal_entries.append(&mut table_generate_result.unwrap());
+    let return_value = AccountingIncomeStatementTemplate {
+        username: username,
+        date_from: income_statement.time_from.format("%Y-%m-%d").to_string(),
+        date_till: income_statement.time_till.format("%Y-%m-%d").to_string(),
+        sections: income_statement
+            .sections
+            .iter()
+            .map(ReportSectionTemplate::from_report_section)
+            .collect(),
+        section_categories: ReportSectionCategoryTemplate::from_default_sections(
+            &FinanceReportHandle::get_income_statement_default_sections(),
+        ),
+        total_income_currency: (income_statement.total_income as f64) / (100 as f64),
+        total_expense_currency: (income_statement.total_expense as f64) / (100 as f64),
+        net_income_currency: (income_statement.net_income as f64) / (100 as f64),
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded income statement user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+#[derive(Debug, Clone)]
+pub struct TagSpendingLineTemplate {
+    pub tag: String,
+    pub month: String,
+    pub amount_currency: f64,
+    pub entry_count: u64,
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingTagSummary.html")]
+pub struct AccountingTagSummaryTemplate {
+    username: String,
+    date_from: String,
+    date_till: String,
+    lines: Vec<TagSpendingLineTemplate>,
+}
+
+impl AccountingTagSummaryTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            date_from: "".into(),
+            date_till: "".into(),
+            lines: Vec::new(),
         }
     }
+}
 
-    let return_value = AccountingJournalReviewTemplate {
+pub async fn display_tag_summary_page(
+    session_data: SessionDataResult,
+    Form(input): Form<IncomeStatementRequest>,
+) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display tag summary page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in {
+        return HtmlTemplate(AccountingTagSummaryTemplate::empty("not logged in"));
+    }
+
+    if session.is_expired() {
+        return HtmlTemplate(AccountingTagSummaryTemplate::empty("Session expired"));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let report_time_range_result = get_report_time_range(&input.date_from, &input.date_till);
+    if report_time_range_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_tag_summary_page for user {}: {}",username,report_time_range_result.unwrap_err());
+        return HtmlTemplate(AccountingTagSummaryTemplate::empty(
+            "problems while reading report dates",
+        ));
+    }
+    let (time_from, time_till) = report_time_range_result.unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let tag_summary_result = {
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        report_handle.tag_spending_summary_sync(time_from, time_till)
+    };
+    if tag_summary_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_tag_summary_page for user {}: {}",username,tag_summary_result.unwrap_err());
+        return HtmlTemplate(AccountingTagSummaryTemplate::empty(
+            "problems while creating tag summary",
+        ));
+    }
+    let tag_summary = tag_summary_result.unwrap();
+
+    let return_value = AccountingTagSummaryTemplate {
         username: username,
-        journal_entries_list: return_journal_entries,
+        date_from: tag_summary.time_from.format("%Y-%m-%d").to_string(),
+        date_till: tag_summary.time_till.format("%Y-%m-%d").to_string(),
+        lines: tag_summary
+            .lines
+            .iter()
+            .map(|elem| TagSpendingLineTemplate {
+                tag: elem.tag.clone(),
+                month: elem.month.clone(),
+                amount_currency: (elem.amount as f64) / (100 as f64),
+                entry_count: elem.entry_count,
+            })
+            .collect(),
     };
 
     session.expire_in(std::time::Duration::from_secs(60 * 10));
     let _new_cookie = session_data.session_store.store_session(session).await;
 
-    trace!(target: "app::FinanceOverView","Loaded journal review user id {}", user_id);
+    trace!(target: "app::FinanceOverView","Loaded tag summary user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct TrialBalanceRequest {
+    pub date_from: Option<String>,
+    pub date_till: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct TrialBalanceLineTemplate {
+    pub account_title: String,
+    pub account_type_title: String,
+    pub date_from: String,
+    pub debit_total_currency: f64,
+    pub credit_total_currency: f64,
+    pub balance_type: String,
+    pub balance_currency: f64,
+}
+
+impl TrialBalanceLineTemplate {
+    pub fn from_trial_balance_line(trial_balance_line: &FinanceTrialBalanceLine) -> Self {
+        Self {
+            account_title: trial_balance_line.account_title.clone(),
+            account_type_title: trial_balance_line.account_type_title.clone(),
+            date_from: trial_balance_line
+                .time_from
+                .map(|elem| elem.format("%Y-%m-%d").to_string())
+                .unwrap_or_default(),
+            debit_total_currency: (trial_balance_line.debit_total as f64) / (100 as f64),
+            credit_total_currency: (trial_balance_line.credit_total as f64) / (100 as f64),
+            balance_type: trial_balance_line.balance_type.to_string(),
+            balance_currency: (trial_balance_line.balance as f64) / (100 as f64),
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingTrialBalance.html")]
+pub struct AccountingTrialBalanceTemplate {
+    username: String,
+    date_from: String,
+    date_till: String,
+    lines: Vec<TrialBalanceLineTemplate>,
+    total_debit_currency: f64,
+    total_credit_currency: f64,
+    total_debit_balance_currency: f64,
+    total_credit_balance_currency: f64,
+    is_balanced: bool,
+}
+
+impl AccountingTrialBalanceTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            date_from: "".into(),
+            date_till: "".into(),
+            lines: Vec::new(),
+            total_debit_currency: 0.0,
+            total_credit_currency: 0.0,
+            total_debit_balance_currency: 0.0,
+            total_credit_balance_currency: 0.0,
+            is_balanced: false,
+        }
+    }
+}
+
+pub async fn display_trial_balance_page(
+    session_data: SessionDataResult,
+    Form(input): Form<TrialBalanceRequest>,
+) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display trial balance page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in {
+        return HtmlTemplate(AccountingTrialBalanceTemplate::empty("not logged in"));
+    }
+
+    if session.is_expired() {
+        return HtmlTemplate(AccountingTrialBalanceTemplate::empty("Session expired"));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let time_from_result = get_optional_report_time(&input.date_from, false);
+    let time_till_result = get_optional_report_time(&input.date_till, true);
+    if time_from_result.is_err() || time_till_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_trial_balance_page for user {}: could not parse dates {:?} {:?}",username,input.date_from,input.date_till);
+        return HtmlTemplate(AccountingTrialBalanceTemplate::empty(
+            "problems while reading report dates",
+        ));
+    }
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let trial_balance_result = {
+        let report_handle = FinanceReportHandle::new(&db_connection, &user_id, &db_handler);
+        report_handle.trial_balance_sync(time_from_result.unwrap(), time_till_result.unwrap())
+    };
+    if trial_balance_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_trial_balance_page for user {}: {}",username,trial_balance_result.unwrap_err());
+        return HtmlTemplate(AccountingTrialBalanceTemplate::empty(
+            "problems while creating trial balance",
+        ));
+    }
+    let trial_balance = trial_balance_result.unwrap();
+
+    let return_value = AccountingTrialBalanceTemplate {
+        username: username,
+        date_from: trial_balance
+            .time_from
+            .map(|elem| elem.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        date_till: trial_balance
+            .time_till
+            .map(|elem| elem.format("%Y-%m-%d").to_string())
+            .unwrap_or_default(),
+        lines: trial_balance
+            .lines
+            .iter()
+            .map(TrialBalanceLineTemplate::from_trial_balance_line)
+            .collect(),
+        total_debit_currency: (trial_balance.total_debit as f64) / (100 as f64),
+        total_credit_currency: (trial_balance.total_credit as f64) / (100 as f64),
+        total_debit_balance_currency: (trial_balance.total_debit_balance as f64) / (100 as f64),
+        total_credit_balance_currency: (trial_balance.total_credit_balance as f64) / (100 as f64),
+        is_balanced: trial_balance.is_balanced,
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded trial balance user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+#[derive(Debug)]
+pub struct JournalTableRow {
+    pub id: String,
+    pub booking_time: DateTime<Utc>,
+    pub is_simple_entry: bool,
+    pub is_saldo: bool,
+    pub credit_account_name: String,
+    pub debit_account_name: String,
+    pub title: String,
+    pub description: String,
+    pub tags: String,
+    pub currency_amount: f64,
+    /// exact amount for exports
+    pub amount_cents: u64,
+    pub running_number: i64,
+    pub reversal_info: String,
+    pub can_be_reversed: bool,
+    pub attachments: Vec<JournalAttachmentLink>,
+}
+
+#[derive(Debug)]
+pub struct JournalAttachmentLink {
+    pub id: String,
+    pub file_name: String,
+}
+
+#[derive(Debug, Template)]
+#[template(path = "AccountingOverview/AccountingJournalView.html")]
+pub struct AccountingJournalReviewTemplate {
+    username: String,
+    journal_entries_list: Vec<JournalTableRow>,
+    tag_filter: String,
+}
+
+#[derive(Deserialize, Debug)]
+pub struct JournalFilterRequest {
+    pub tag: Option<String>,
+}
+
+pub async fn display_journal_page(
+    session_data: SessionDataResult,
+    Form(input): Form<JournalFilterRequest>,
+) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display journal review page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    let mut headers = HeaderMap::new();
+
+    let empty_journal_list: Vec<JournalTableRow> = Vec::with_capacity(0);
+    let mut return_journal_entries: Vec<JournalTableRow> = Vec::new();
+
+    if !is_logged_in {
+        let return_value = AccountingJournalReviewTemplate {
+            username: "not logged in".to_string(),
+            journal_entries_list: empty_journal_list,
+            tag_filter: "".into(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return HtmlTemplate(return_value);
+    }
+
+    if session.is_expired() {
+        let return_value = AccountingJournalReviewTemplate {
+            username: "Session expired".to_string(),
+            journal_entries_list: empty_journal_list,
+            tag_filter: "".into(),
+        };
+        headers.insert(
+            axum::http::header::REFRESH,
+            axum::http::HeaderValue::from_str("5; url = /").unwrap(),
+        );
+        return HtmlTemplate(return_value);
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    {
+        let accounting_config_handle =
+            FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+        let accounting_booking_handle =
+            FinanceBookingHandle::new(&db_connection, &user_id, &db_handler);
+        {
+            let table_generate_result = generate_review_journal_entries_sync(
+                &accounting_booking_handle,
+                &accounting_config_handle,
+                input.tag.clone(),
+            );
+            if table_generate_result.is_err() {
+                warn!(target: "app::FinanceOverView","error in display_accounting_review_page for user {}: {}",username,table_generate_result.unwrap_err());
+                let return_value = AccountingJournalReviewTemplate {
+                    username: "problems while getting account tables".to_string(),
+                    journal_entries_list: empty_journal_list,
+                    tag_filter: "".into(),
+                };
+                return HtmlTemplate(return_value);
+            }
+
+            return_journal_entries.append(&mut table_generate_result.unwrap());
+        }
+
+        let attachment_storage_setting =
+            AttachmentStorageSetting::from_setting(SettingStruct::global());
+        let attachment_handle = FinanceAttachmentHandle::new(
+            &db_connection,
+            &user_id,
+            &db_handler,
+            &attachment_storage_setting,
+        );
+        let attachment_list_result = attachment_handle.attachment_list_sync(None);
+        if attachment_list_result.is_err() {
+            warn!(target: "app::FinanceOverView","error in display_accounting_review_page for user {}: {}",username,attachment_list_result.unwrap_err());
+            let return_value = AccountingJournalReviewTemplate {
+                username: "problems while getting attachments".to_string(),
+                journal_entries_list: empty_journal_list,
+                tag_filter: "".into(),
+            };
+            return HtmlTemplate(return_value);
+        }
+        for attachment in attachment_list_result.unwrap() {
+            let journal_entry_id = attachment.finance_journal_diary_id.to_string();
+            let row_option = return_journal_entries
+                .iter_mut()
+                .find(|elem| elem.id.eq(&journal_entry_id));
+            if row_option.is_some() {
+                row_option.unwrap().attachments.push(JournalAttachmentLink {
+                    id: attachment.id.to_string(),
+                    file_name: attachment.file_name,
+                });
+            }
+        }
+    }
+
+    let return_value = AccountingJournalReviewTemplate {
+        username: username,
+        journal_entries_list: return_journal_entries,
+        tag_filter: input.tag.unwrap_or_default(),
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded journal review user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+#[derive(Debug, Clone)]
+pub struct RecurringBookingTemplate {
+    pub id: String,
+    pub title: String,
+    pub description: String,
+    pub debit_account_name: String,
+    pub credit_account_name: String,
+    pub amount_currency: f64,
+    pub schedule: String,
+    pub start_time: String,
+    pub end_time: String,
+    pub last_booking_time: String,
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingRecurringBookings.html")]
+pub struct AccountingRecurringBookingsTemplate {
+    username: String,
+    accounts: Vec<AccountTemplate>,
+    recurring_bookings: Vec<RecurringBookingTemplate>,
+}
+
+impl AccountingRecurringBookingsTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            accounts: Vec::new(),
+            recurring_bookings: Vec::new(),
+        }
+    }
+}
+
+pub async fn display_recurring_bookings_page(session_data: SessionDataResult) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display recurring bookings page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in {
+        return HtmlTemplate(AccountingRecurringBookingsTemplate::empty("not logged in"));
+    }
+
+    if session.is_expired() {
+        return HtmlTemplate(AccountingRecurringBookingsTemplate::empty(
+            "Session expired",
+        ));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let (accounts_result, recurring_bookings_result) = {
+        let accounting_config_handle =
+            FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+        let recurring_booking_handle =
+            FinanceRecurringBookingHandle::new(&db_connection, &user_id, &db_handler);
+        (
+            accounting_config_handle.finance_account_list(None),
+            recurring_booking_handle.recurring_booking_list_sync(),
+        )
+    };
+    if accounts_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_recurring_bookings_page for user {}: {}",username,accounts_result.unwrap_err());
+        return HtmlTemplate(AccountingRecurringBookingsTemplate::empty(
+            "problems while getting account list",
+        ));
+    }
+    if recurring_bookings_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_recurring_bookings_page for user {}: {}",username,recurring_bookings_result.unwrap_err());
+        return HtmlTemplate(AccountingRecurringBookingsTemplate::empty(
+            "problems while getting recurring bookings",
+        ));
+    }
+    let account_list = accounts_result.unwrap();
+
+    let get_account_name = |account_id: &Uuid| -> String {
+        return account_list
+            .iter()
+            .find(|elem| elem.id.eq(account_id))
+            .map(|elem| elem.title.clone())
+            .unwrap_or("unkown account".into());
+    };
+    let recurring_booking_list = recurring_bookings_result
+        .unwrap()
+        .iter()
+        .map(|elem| RecurringBookingTemplate {
+            id: elem.id.to_string(),
+            title: elem.booking_template.title.clone(),
+            description: elem.booking_template.description.clone(),
+            debit_account_name: get_account_name(&elem.booking_template.debit_finance_account_id),
+            credit_account_name: get_account_name(&elem.booking_template.credit_finance_account_id),
+            amount_currency: (elem.booking_template.amount as f64) / (100 as f64),
+            schedule: elem.schedule.to_string(),
+            start_time: elem.start_time.to_string(),
+            end_time: elem.end_time.map(|x| x.to_string()).unwrap_or_default(),
+            last_booking_time: elem
+                .last_booking_time
+                .map(|x| x.to_string())
+                .unwrap_or_default(),
+        })
+        .collect();
+
+    let return_value = AccountingRecurringBookingsTemplate {
+        username: username,
+        accounts: account_list
+            .iter()
+            .map(|elem| AccountTemplate {
+                id: elem.id.to_string(),
+                name: elem.title.clone(),
+                description: elem.description.clone(),
+                type_title: "not loaded".into(),
+                account_number: AccountTemplate::format_account_number(&elem.account_number),
+            })
+            .collect(),
+        recurring_bookings: recurring_booking_list,
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded recurring bookings user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+#[derive(Deserialize, Debug)]
+pub struct BudgetReportRequest {
+    pub report_date: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct BudgetReportLineTemplate {
+    pub budget_id: String,
+    pub account_title: String,
+    pub period: String,
+    pub date_from: String,
+    pub date_till: String,
+    pub budget_currency: f64,
+    pub actual_currency: f64,
+    pub remaining_currency: f64,
+    pub percentage_used: f64,
+    pub is_exceeded: bool,
+}
+
+impl BudgetReportLineTemplate {
+    pub fn from_budget_report_line(budget_report_line: &FinanceBudgetReportLine) -> Self {
+        Self {
+            budget_id: budget_report_line.budget_id.clone(),
+            account_title: budget_report_line.account_title.clone(),
+            period: budget_report_line.period.to_string(),
+            date_from: budget_report_line.time_from.format("%Y-%m-%d").to_string(),
+            date_till: budget_report_line.time_till.format("%Y-%m-%d").to_string(),
+            budget_currency: (budget_report_line.budget_amount as f64) / (100 as f64),
+            actual_currency: (budget_report_line.actual_amount as f64) / (100 as f64),
+            remaining_currency: (budget_report_line.remaining_amount as f64) / (100 as f64),
+            percentage_used: budget_report_line.percentage_used,
+            is_exceeded: budget_report_line.remaining_amount < 0,
+        }
+    }
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingBudgets.html")]
+pub struct AccountingBudgetsTemplate {
+    username: String,
+    report_date: String,
+    accounts: Vec<AccountTemplate>,
+    lines: Vec<BudgetReportLineTemplate>,
+}
+
+impl AccountingBudgetsTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            report_date: "".into(),
+            accounts: Vec::new(),
+            lines: Vec::new(),
+        }
+    }
+}
+
+pub async fn display_budgets_page(
+    session_data: SessionDataResult,
+    Form(input): Form<BudgetReportRequest>,
+) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display budgets page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in {
+        return HtmlTemplate(AccountingBudgetsTemplate::empty("not logged in"));
+    }
+
+    if session.is_expired() {
+        return HtmlTemplate(AccountingBudgetsTemplate::empty("Session expired"));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let report_time_result = get_report_time(&input.report_date);
+    if report_time_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_budgets_page for user {}: {}",username,report_time_result.unwrap_err());
+        return HtmlTemplate(AccountingBudgetsTemplate::empty(
+            "problems while reading report date",
+        ));
+    }
+    let report_time = report_time_result.unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let (accounts_result, budget_report_result) = {
+        let accounting_config_handle =
+            FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+        let budget_handle = FinanceBudgetHandle::new(&db_connection, &user_id, &db_handler);
+        (
+            accounting_config_handle.finance_account_list(None),
+            budget_handle.budget_report_sync(report_time),
+        )
+    };
+    if accounts_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_budgets_page for user {}: {}",username,accounts_result.unwrap_err());
+        return HtmlTemplate(AccountingBudgetsTemplate::empty(
+            "problems while getting account list",
+        ));
+    }
+    if budget_report_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_budgets_page for user {}: {}",username,budget_report_result.unwrap_err());
+        return HtmlTemplate(AccountingBudgetsTemplate::empty(
+            "problems while creating budget report",
+        ));
+    }
+
+    let return_value = AccountingBudgetsTemplate {
+        username: username,
+        report_date: report_time.format("%Y-%m-%d").to_string(),
+        accounts: accounts_result
+            .unwrap()
+            .iter()
+            .map(|elem| AccountTemplate {
+                id: elem.id.to_string(),
+                name: elem.title.clone(),
+                description: elem.description.clone(),
+                type_title: "not loaded".into(),
+                account_number: AccountTemplate::format_account_number(&elem.account_number),
+            })
+            .collect(),
+        lines: budget_report_result
+            .unwrap()
+            .lines
+            .iter()
+            .map(BudgetReportLineTemplate::from_budget_report_line)
+            .collect(),
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded budgets user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingBankImport.html")]
+pub struct AccountingBankImportTemplate {
+    username: String,
+    accounts: Vec<AccountTemplate>,
+}
+
+impl AccountingBankImportTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            accounts: Vec::new(),
+        }
+    }
+}
+
+pub async fn display_bank_import_page(session_data: SessionDataResult) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display bank import page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in {
+        return HtmlTemplate(AccountingBankImportTemplate::empty("not logged in"));
+    }
+
+    if session.is_expired() {
+        return HtmlTemplate(AccountingBankImportTemplate::empty("Session expired"));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let accounts_result = {
+        let accounting_config_handle =
+            FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+        accounting_config_handle.finance_account_list(None)
+    };
+    if accounts_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_bank_import_page for user {}: {}",username,accounts_result.unwrap_err());
+        return HtmlTemplate(AccountingBankImportTemplate::empty(
+            "problems while getting account list",
+        ));
+    }
+
+    let return_value = AccountingBankImportTemplate {
+        username: username,
+        accounts: accounts_result
+            .unwrap()
+            .iter()
+            .map(|elem| AccountTemplate {
+                id: elem.id.to_string(),
+                name: elem.title.clone(),
+                description: elem.description.clone(),
+                type_title: "not loaded".into(),
+                account_number: AccountTemplate::format_account_number(&elem.account_number),
+            })
+            .collect(),
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded bank import page user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+pub struct CategorizationRuleTemplate {
+    pub rule_id: String,
+    pub title: String,
+    pub priority: i32,
+    pub payee_pattern: String,
+    pub text_pattern: String,
+    /// amounts as currency, empty when the rule has no limit
+    pub amount_min: String,
+    pub amount_max: String,
+    pub source_account_id: String,
+    pub source_account_title: String,
+    pub counter_account_id: String,
+    pub counter_account_title: String,
+    pub title_rewrite: String,
+    pub tags: String,
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingCategorizationRules.html")]
+pub struct AccountingCategorizationRulesTemplate {
+    username: String,
+    accounts: Vec<AccountTemplate>,
+    rules: Vec<CategorizationRuleTemplate>,
+}
+
+impl AccountingCategorizationRulesTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            accounts: Vec::new(),
+            rules: Vec::new(),
+        }
+    }
+}
+
+pub async fn display_categorization_rules_page(
+    session_data: SessionDataResult,
+) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display categorization rules page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in {
+        return HtmlTemplate(AccountingCategorizationRulesTemplate::empty(
+            "not logged in",
+        ));
+    }
+
+    if session.is_expired() {
+        return HtmlTemplate(AccountingCategorizationRulesTemplate::empty(
+            "Session expired",
+        ));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let (accounts_result, rules_result) = {
+        let accounting_config_handle =
+            FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+        let rule_handle =
+            FinanceCategorizationRuleHandle::new(&db_connection, &user_id, &db_handler);
+        (
+            accounting_config_handle.finance_account_list(None),
+            rule_handle.rule_list_sync(),
+        )
+    };
+    if accounts_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_categorization_rules_page for user {}: {}",username,accounts_result.unwrap_err());
+        return HtmlTemplate(AccountingCategorizationRulesTemplate::empty(
+            "problems while getting account list",
+        ));
+    }
+    if rules_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_categorization_rules_page for user {}: {}",username,rules_result.unwrap_err());
+        return HtmlTemplate(AccountingCategorizationRulesTemplate::empty(
+            "problems while getting categorization rules",
+        ));
+    }
+    let accounts = accounts_result.unwrap();
+    let get_account_title = |account_id: &Uuid| -> String {
+        return accounts
+            .iter()
+            .find(|elem| elem.id.eq(account_id))
+            .map(|elem| elem.title.clone())
+            .unwrap_or(account_id.to_string());
+    };
+
+    let return_value = AccountingCategorizationRulesTemplate {
+        username: username,
+        rules: rules_result
+            .unwrap()
+            .iter()
+            .map(|elem| CategorizationRuleTemplate {
+                rule_id: elem.id.to_string(),
+                title: elem.title.clone(),
+                priority: elem.priority,
+                payee_pattern: elem.payee_pattern.clone(),
+                text_pattern: elem.text_pattern.clone(),
+                amount_min: elem
+                    .amount_min
+                    .map(|x| format!("{:.2}", (x as f64) / (100 as f64)))
+                    .unwrap_or_default(),
+                amount_max: elem
+                    .amount_max
+                    .map(|x| format!("{:.2}", (x as f64) / (100 as f64)))
+                    .unwrap_or_default(),
+                source_account_id: elem
+                    .source_account_id
+                    .map(|x| x.to_string())
+                    .unwrap_or_default(),
+                source_account_title: elem
+                    .source_account_id
+                    .map(|x| get_account_title(&x))
+                    .unwrap_or("every account".into()),
+                counter_account_id: elem.counter_account_id.to_string(),
+                counter_account_title: get_account_title(&elem.counter_account_id),
+                title_rewrite: elem.title_rewrite.clone(),
+                tags: elem.tags.join(", "),
+            })
+            .collect(),
+        accounts: accounts
+            .iter()
+            .map(|elem| AccountTemplate {
+                id: elem.id.to_string(),
+                name: elem.title.clone(),
+                description: elem.description.clone(),
+                type_title: "not loaded".into(),
+                account_number: AccountTemplate::format_account_number(&elem.account_number),
+            })
+            .collect(),
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded categorization rules user id {}", user_id);
+
+    HtmlTemplate(return_value)
+}
+
+pub struct ReconciliationTemplate {
+    pub reconciliation_id: String,
+    pub account_title: String,
+    pub statement_date: String,
+    /// balances as currency
+    pub statement_balance: String,
+    pub book_balance: String,
+    pub difference: String,
+}
+
+#[derive(Template)]
+#[template(path = "AccountingOverview/AccountingReconciliation.html")]
+pub struct AccountingReconciliationTemplate {
+    username: String,
+    accounts: Vec<AccountTemplate>,
+    reconciliations: Vec<ReconciliationTemplate>,
+}
+
+impl AccountingReconciliationTemplate {
+    fn empty(username: &str) -> Self {
+        Self {
+            username: username.into(),
+            accounts: Vec::new(),
+            reconciliations: Vec::new(),
+        }
+    }
+}
+
+pub async fn display_reconciliation_page(session_data: SessionDataResult) -> impl IntoResponse {
+    debug!(target: "app::FinanceOverView","display reconciliation page");
+
+    let session_data = SessionData::from_session_data_result(session_data);
+    let mut session = session_data.session_option.unwrap().clone();
+
+    let is_logged_in: bool = session.get("logged_in").unwrap_or(false);
+
+    if !is_logged_in {
+        return HtmlTemplate(AccountingReconciliationTemplate::empty("not logged in"));
+    }
+
+    if session.is_expired() {
+        return HtmlTemplate(AccountingReconciliationTemplate::empty("Session expired"));
+    }
+
+    let username: String = session.get("user_name").unwrap();
+    let user_id: Uuid = session.get("user_account_id").unwrap();
+
+    let local_setting: SettingStruct = SettingStruct::global().clone();
+    let db_connection = DbConnectionSetting {
+        url: String::from(&local_setting.backend_database_url),
+        user: String::from(local_setting.backend_database_user),
+        password: String::from(local_setting.backend_database_password),
+        instance: String::from(&local_setting.backend_database_instance),
+    };
+    let db_handler = DbHandler::new(&db_connection);
+
+    let (accounts_result, reconciliations_result) = {
+        let accounting_config_handle =
+            FinanceAccountingConfigHandle::new(&db_connection, &user_id, &db_handler);
+        let reconciliation_handle =
+            FinanceReconciliationHandle::new(&db_connection, &user_id, &db_handler);
+        (
+            accounting_config_handle.finance_account_list(None),
+            reconciliation_handle.reconciliation_list_sync(None),
+        )
+    };
+    if accounts_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_reconciliation_page for user {}: {}",username,accounts_result.unwrap_err());
+        return HtmlTemplate(AccountingReconciliationTemplate::empty(
+            "problems while getting account list",
+        ));
+    }
+    if reconciliations_result.is_err() {
+        warn!(target: "app::FinanceOverView","error in display_reconciliation_page for user {}: {}",username,reconciliations_result.unwrap_err());
+        return HtmlTemplate(AccountingReconciliationTemplate::empty(
+            "problems while getting reconciliations",
+        ));
+    }
+    let accounts = accounts_result.unwrap();
+
+    let return_value = AccountingReconciliationTemplate {
+        username: username,
+        reconciliations: reconciliations_result
+            .unwrap()
+            .iter()
+            .map(|elem| ReconciliationTemplate {
+                reconciliation_id: elem.id.to_string(),
+                account_title: accounts
+                    .iter()
+                    .find(|account| account.id.eq(&elem.finance_account_id))
+                    .map(|account| account.title.clone())
+                    .unwrap_or(elem.finance_account_id.to_string()),
+                statement_date: elem.statement_time.format("%Y-%m-%d").to_string(),
+                statement_balance: format!("{:.2}", (elem.statement_balance as f64) / (100 as f64)),
+                book_balance: format!("{:.2}", (elem.book_balance as f64) / (100 as f64)),
+                difference: format!(
+                    "{:.2}",
+                    ((elem.statement_balance - elem.book_balance) as f64) / (100 as f64)
+                ),
+            })
+            .collect(),
+        accounts: accounts
+            .iter()
+            .map(|elem| AccountTemplate {
+                id: elem.id.to_string(),
+                name: elem.title.clone(),
+                description: elem.description.clone(),
+                type_title: "not loaded".into(),
+                account_number: AccountTemplate::format_account_number(&elem.account_number),
+            })
+            .collect(),
+    };
+
+    session.expire_in(std::time::Duration::from_secs(60 * 10));
+    let _new_cookie = session_data.session_store.store_session(session).await;
+
+    trace!(target: "app::FinanceOverView","Loaded reconciliation page user id {}", user_id);
 
     HtmlTemplate(return_value)
 }
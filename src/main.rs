@@ -2,29 +2,69 @@ mod accounting_config_database;
 mod accounting_config_logic;
 mod accounting_database;
 mod accounting_logic;
+mod accounting_report;
 mod ajax_handle;
+mod attachment_database;
+mod attachment_logic;
+mod backup;
+mod bank_import;
+mod booking_template_database;
+mod booking_template_logic;
+mod budget_database;
+mod budget_logic;
+mod categorization_rule_database;
+mod categorization_rule_logic;
 mod convert_tools;
+mod csv_export;
+mod database_handler;
 mod database_handler_mongodb;
+mod database_handler_postgres;
+mod database_handler_sqlite;
 pub mod datatypes;
+mod datev_export;
 mod frontend_functions;
 mod html_render;
 mod mail_handle;
 mod mdb_convert_tools;
 mod password_handle;
+mod plain_text_accounting;
+mod reconciliation_database;
+mod reconciliation_logic;
+mod recurring_booking_database;
+mod recurring_booking_logic;
+mod report_section_database;
 mod session_data_handle;
 pub mod setting_struct;
+mod user_database;
 mod user_handling;
 mod tests {
     mod mocking_database;
     mod testing_accounting_booking;
     mod testing_accounting_config;
+    mod testing_attachment;
+    mod testing_backup;
+    mod testing_bank_import;
+    mod testing_booking_template;
+    mod testing_budget;
+    mod testing_categorization_rule;
     mod testing_convert_tools;
+    mod testing_csv_export;
+    mod testing_database_postgres;
+    mod testing_database_sqlite;
+    mod testing_datev_export;
     mod testing_email_smtp;
     mod testing_email_validation;
+    mod testing_plain_text_accounting;
+    mod testing_reconciliation;
+    mod testing_recurring_booking;
+    mod testing_tags;
+    mod testing_user_handling;
 }
 
 use async_mongodb_session::MongodbSessionStore;
+use async_session::chrono::Utc;
 use axum::{
+    extract::DefaultBodyLimit,
     http::{self, HeaderMap, Uri},
     response::{IntoResponse, Redirect},
     routing::{get, post},
@@ -37,8 +77,7 @@ use log4rs::{
     config::{Appender, Root},
     encode::json::JsonEncoder,
 };
-use mongodb::bson::{doc, Bson, Document};
-use session_data_handle::SessionDataResult;
+use session_data_handle::{ServerSessionStore, SessionDataResult};
 use std::{
     env, fs,
     net::SocketAddr,
@@ -46,11 +85,13 @@ use std::{
 };
 
 use crate::{
-    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler::DbHandler,
+    database_handler_mongodb::{DbBackendType, DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::PostgresSessionStore,
+    database_handler_sqlite::SqliteSessionStore,
     html_render::{
         invalid_handler, registration_incomplete_handler, HtmlTemplate, MainPageTemplate,
     },
-    mdb_convert_tools::MdbConvertTools,
     setting_struct::SettingStruct,
 };
 
@@ -126,7 +167,16 @@ async fn main() {
         instance: String::from(&local_setting.backend_database_instance),
     };
 
-    if !DbHandlerMongoDB::validate_db_structure(&db_connection) {
+    let backend_type_result =
+        DbBackendType::from_setting_value(&local_setting.backend_database_type);
+    if backend_type_result.is_err() {
+        let error_info = backend_type_result.unwrap_err();
+        error!(target: "app::FinanceOverView","Invalid backend database setting: {}, quitting", error_info);
+        println!("Invalid backend database setting: {}, quitting", error_info);
+        return;
+    }
+
+    if !DbHandler::validate_db_structure(&db_connection) {
         error!(target: "app::FinanceOverView","Could not validate backend structure, quitting");
         println!("Could not validate backend structure, quitting");
         return;
@@ -134,9 +184,50 @@ async fn main() {
 
     let http = tokio::spawn(http_server());
     let https = tokio::spawn(https_server());
+    let recurring_bookings = tokio::spawn(recurring_booking_scheduler());
 
     // Ignore errors.
-    let _ = tokio::join!(http, https);
+    let _ = tokio::join!(http, https, recurring_bookings);
+}
+
+const RECURRING_BOOKING_INTERVAL_SECONDS: u64 = 60 * 15;
+
+/// books due occurrences of recurring bookings periodically (and once at startup to catch up after downtime)
+async fn recurring_booking_scheduler() {
+    let mut interval = tokio::time::interval(std::time::Duration::from_secs(
+        RECURRING_BOOKING_INTERVAL_SECONDS,
+    ));
+    loop {
+        interval.tick().await;
+        // the database functions are not Send, so they run on a blocking thread
+        let task_result = tokio::task::spawn_blocking(|| {
+            let local_setting: SettingStruct = SettingStruct::global().clone();
+            let db_connection = DbConnectionSetting {
+                url: String::from(&local_setting.backend_database_url),
+                user: String::from(local_setting.backend_database_user),
+                password: String::from(local_setting.backend_database_password),
+                instance: String::from(&local_setting.backend_database_instance),
+            };
+            let db_handler = DbHandler::new(&db_connection);
+            return recurring_booking_logic::book_due_recurring_bookings_of_all_users_sync(
+                &db_connection,
+                &db_handler,
+                Utc::now(),
+            );
+        })
+        .await;
+        match task_result {
+            Ok(Ok(booked_count)) => {
+                debug!(target: "app::FinanceOverView","recurring bookings: {} entries booked", booked_count)
+            }
+            Ok(Err(booking_error)) => {
+                warn!(target: "app::FinanceOverView","recurring bookings failed: {}", booking_error)
+            }
+            Err(join_error) => {
+                error!(target: "app::FinanceOverView","recurring booking task failed: {}", join_error)
+            }
+        }
+    }
 }
 
 async fn http_server() {
@@ -166,24 +257,9 @@ async fn https_server() {
         instance: String::from(&local_setting.backend_database_instance),
     };
 
-    // Get a handle to the deployment.
-    let mgdb_client_create_result =
-        DbHandlerMongoDB::create_client_connection_async(&db_connection).await;
-    if mgdb_client_create_result.is_err() {
-        warn!(target:"app::FinanceOverView","Could not create mongo DB Client {}",mgdb_client_create_result.unwrap_err());
-        return;
-    }
-    let mgdb_client = mgdb_client_create_result.unwrap();
-
-    let server_session_store = MongodbSessionStore::from_client(
-        mgdb_client,
-        &db_connection.instance,
-        DbHandlerMongoDB::COLLECTION_NAME_SESSION_INFO,
-    );
-
-    let initilize_result = server_session_store.initialize().await;
-    if initilize_result.is_err() {
-        let error_info = initilize_result.unwrap_err();
+    let server_session_store_result = create_server_session_store(&db_connection).await;
+    if server_session_store_result.is_err() {
+        let error_info = server_session_store_result.unwrap_err();
         error!(target: "app::FinanceOverView","Could not initialize session store: {}", error_info);
         println!(
             "Could not initialize session store, quitting: {}",
@@ -191,6 +267,7 @@ async fn https_server() {
         );
         return;
     }
+    let server_session_store = server_session_store_result.unwrap();
 
     let app = Router::new()
         .route("/", get(https_handler))
@@ -262,6 +339,22 @@ async fn https_server() {
             "/request_booking_entry",
             post(ajax_handle::do_create_booking_entry),
         )
+        .route(
+            "/request_upsert_booking_template",
+            post(ajax_handle::do_upsert_booking_template),
+        )
+        .route(
+            "/request_delete_booking_template",
+            post(ajax_handle::do_delete_booking_template),
+        )
+        .route(
+            "/request_booking_entry_from_template",
+            post(ajax_handle::do_create_booking_entry_from_template),
+        )
+        .route(
+            "/request_reverse_journal_entry",
+            post(ajax_handle::do_reverse_journal_entry),
+        )
         .route(
             "/get_single_accounting_table",
             get(ajax_handle::do_get_account_table_request),
@@ -274,6 +367,163 @@ async fn https_server() {
             "/do_show_journal_view",
             get(html_render::display_journal_page),
         )
+        .route(
+            "/request_upload_journal_attachment",
+            // the multipart body contains the file and a few form fields
+            post(ajax_handle::do_upload_journal_attachment).layer(DefaultBodyLimit::max(
+                (local_setting.backend_attachment_max_size_bytes as usize) + 64 * 1024,
+            )),
+        )
+        .route(
+            "/get_journal_attachment",
+            get(ajax_handle::do_download_journal_attachment),
+        )
+        .route(
+            "/get_journal_csv_export",
+            get(ajax_handle::do_download_journal_csv),
+        )
+        .route(
+            "/get_account_tables_csv_export",
+            get(ajax_handle::do_download_account_tables_csv),
+        )
+        .route(
+            "/get_plain_text_accounting_export",
+            get(ajax_handle::do_download_plain_text_accounting),
+        )
+        .route(
+            "/get_datev_export",
+            get(ajax_handle::do_download_datev_export),
+        )
+        .route(
+            "/request_plain_text_accounting_import",
+            post(ajax_handle::do_import_plain_text_accounting),
+        )
+        .route("/get_backup_export", get(ajax_handle::do_download_backup))
+        .route(
+            "/request_backup_restore",
+            post(ajax_handle::do_restore_backup),
+        )
+        .route(
+            "/do_show_bank_import",
+            get(html_render::display_bank_import_page),
+        )
+        .route(
+            "/request_preview_bank_import",
+            post(ajax_handle::do_preview_bank_import),
+        )
+        .route(
+            "/request_preview_mt940_import",
+            post(ajax_handle::do_preview_mt940_import),
+        )
+        .route(
+            "/request_preview_camt053_import",
+            post(ajax_handle::do_preview_camt053_import),
+        )
+        .route(
+            "/request_preview_ofx_import",
+            post(ajax_handle::do_preview_ofx_import),
+        )
+        .route(
+            "/request_preview_qif_import",
+            post(ajax_handle::do_preview_qif_import),
+        )
+        .route(
+            "/request_post_bank_import",
+            post(ajax_handle::do_post_bank_import),
+        )
+        .route(
+            "/request_split_booking_entry",
+            post(ajax_handle::do_create_split_booking_entry),
+        )
+        .route(
+            "/request_close_accounts",
+            post(ajax_handle::do_close_accounts),
+        )
+        .route(
+            "/do_show_balance_sheet",
+            get(html_render::display_balance_sheet_page),
+        )
+        .route(
+            "/do_show_income_statement",
+            get(html_render::display_income_statement_page),
+        )
+        .route("/get_balance_sheet", get(ajax_handle::do_get_balance_sheet))
+        .route(
+            "/request_upsert_report_section_mapping",
+            post(ajax_handle::do_upsert_report_section_mapping),
+        )
+        .route(
+            "/request_delete_report_section_mapping",
+            post(ajax_handle::do_delete_report_section_mapping),
+        )
+        .route(
+            "/get_income_statement",
+            get(ajax_handle::do_get_income_statement),
+        )
+        .route(
+            "/do_show_trial_balance",
+            get(html_render::display_trial_balance_page),
+        )
+        .route("/get_trial_balance", get(ajax_handle::do_get_trial_balance))
+        .route("/do_show_budgets", get(html_render::display_budgets_page))
+        .route(
+            "/do_show_categorization_rules",
+            get(html_render::display_categorization_rules_page),
+        )
+        .route(
+            "/request_upsert_categorization_rule",
+            post(ajax_handle::do_upsert_categorization_rule),
+        )
+        .route(
+            "/request_delete_categorization_rule",
+            post(ajax_handle::do_delete_categorization_rule),
+        )
+        .route(
+            "/request_test_categorization_rule",
+            post(ajax_handle::do_test_categorization_rule),
+        )
+        .route(
+            "/do_show_reconciliation",
+            get(html_render::display_reconciliation_page),
+        )
+        .route(
+            "/request_reconciliation_status",
+            post(ajax_handle::do_show_reconciliation_status),
+        )
+        .route(
+            "/request_reconcile_account",
+            post(ajax_handle::do_reconcile_account),
+        )
+        .route(
+            "/request_unreconcile_account",
+            post(ajax_handle::do_unreconcile_account),
+        )
+        .route(
+            "/request_upsert_budget",
+            post(ajax_handle::do_upsert_budget),
+        )
+        .route(
+            "/request_delete_budget",
+            post(ajax_handle::do_delete_budget),
+        )
+        .route("/get_budget_report", get(ajax_handle::do_get_budget_report))
+        .route(
+            "/do_show_tag_summary",
+            get(html_render::display_tag_summary_page),
+        )
+        .route("/get_tag_summary", get(ajax_handle::do_get_tag_summary))
+        .route(
+            "/do_show_recurring_bookings",
+            get(html_render::display_recurring_bookings_page),
+        )
+        .route(
+            "/request_create_recurring_booking",
+            post(ajax_handle::do_create_recurring_booking),
+        )
+        .route(
+            "/request_delete_recurring_booking",
+            post(ajax_handle::do_delete_recurring_booking),
+        )
         .route("/js_code/*path", get(ajax_handle::get_js_files))
         .layer(Extension(server_session_store));
 
@@ -339,6 +589,53 @@ async fn http_handler(uri: Uri) -> Redirect {
     Redirect::temporary(&new_uri)
 }
 
+/// creates and initializes the session store of the configured backend
+async fn create_server_session_store(
+    db_connection: &DbConnectionSetting,
+) -> Result<ServerSessionStore, String> {
+    match DbBackendType::from_global_setting() {
+        DbBackendType::MongoDB => {
+            // Get a handle to the deployment.
+            let mgdb_client_create_result =
+                DbHandlerMongoDB::create_client_connection_async(db_connection).await;
+            if mgdb_client_create_result.is_err() {
+                return Err(format!(
+                    "Could not create mongo DB Client {}",
+                    mgdb_client_create_result.unwrap_err()
+                ));
+            }
+            let mgdb_client = mgdb_client_create_result.unwrap();
+
+            let mongodb_session_store = MongodbSessionStore::from_client(
+                mgdb_client,
+                &db_connection.instance,
+                DbHandlerMongoDB::COLLECTION_NAME_SESSION_INFO,
+            );
+            let initilize_result = mongodb_session_store.initialize().await;
+            if initilize_result.is_err() {
+                return Err(initilize_result.unwrap_err().to_string());
+            }
+            return Ok(ServerSessionStore::MongoDB(mongodb_session_store));
+        }
+        DbBackendType::SQLite => {
+            let sqlite_session_store = SqliteSessionStore::new(db_connection);
+            let initilize_result = sqlite_session_store.initialize().await;
+            if initilize_result.is_err() {
+                return Err(initilize_result.unwrap_err());
+            }
+            return Ok(ServerSessionStore::SQLite(sqlite_session_store));
+        }
+        DbBackendType::PostgreSQL => {
+            let postgres_session_store = PostgresSessionStore::new(db_connection);
+            let initilize_result = postgres_session_store.initialize().await;
+            if initilize_result.is_err() {
+                return Err(initilize_result.unwrap_err());
+            }
+            return Ok(ServerSessionStore::PostgreSQL(postgres_session_store));
+        }
+    }
+}
+
 async fn https_handler(session_data: SessionDataResult) -> impl IntoResponse {
     let (headers, user_id, create_cookie) = match session_data {
         SessionDataResult::FoundSessionData(session_data) => {
@@ -364,94 +661,21 @@ async fn https_handler(session_data: SessionDataResult) -> impl IntoResponse {
     };
     let current_route = "main";
     let mut current_count = 1;
-    let mut current_document = Document::new();
-    let mut documents_found = 0;
-
-    let mut addtional_info; //see https://github.com/rust-lang/rust/issues/49171
-
-    let query_filter = doc! {"RouteName":current_route};
 
-    let query_site_result_cursor = DbHandlerMongoDB::query_table_with_filter(
+    let addtional_info = match DbHandler::increase_route_calling_amount(
         &db_connection,
-        &DbHandlerMongoDB::COLLECTION_NAME_WEBSITE_TRAFFIC.to_string(),
-        query_filter,
-    );
-    if query_site_result_cursor.is_ok() {
-        let document_list =
-            MdbConvertTools::get_vector_from_cursor(query_site_result_cursor.unwrap());
-        for document_entry in document_list {
-            if let Some(&Bson::String(ref route_value)) = document_entry.get("RouteName") {
-                if let Some(&Bson::Int32(ref calling_amount_value)) =
-                    document_entry.get("CallingAmount")
-                {
-                    trace!(target:"app::FinanceOverView","route: {}, called: {}",route_value,calling_amount_value);
-
-                    if route_value.eq(current_route) {
-                        current_document = document_entry.clone();
-                        documents_found = documents_found + 1;
-                    }
-                }
-            }
+        &current_route.to_string(),
+    ) {
+        Ok(1) => "<br> first documented call".to_string(),
+        Ok(calling_amount) => {
+            current_count = calling_amount;
+            format!("<br> already called {} times", calling_amount)
         }
-
-        match documents_found {
-            0 => {
-                addtional_info = "<br> first documented call".to_string();
-
-                current_document = doc! {
-                    "RouteName" : current_route,
-                    "CallingAmount": Bson::Int32(current_count)
-                };
-
-                let insert_result = DbHandlerMongoDB::insert_document_in_table(
-                    &db_connection,
-                    &DbHandlerMongoDB::COLLECTION_NAME_WEBSITE_TRAFFIC.to_string(),
-                    &current_document,
-                );
-                if insert_result.is_err() {
-                    addtional_info =
-                        format!("{}<br>could not insert into database", addtional_info);
-                }
-            }
-            1 => {
-                let already_called_amount = current_document
-                    .get_i32("CallingAmount")
-                    .unwrap_or_else(|_| 0);
-                addtional_info = format!(
-                    "<br> already called {} times",
-                    already_called_amount + current_count
-                );
-
-                current_count = current_count + already_called_amount;
-
-                let query_info = doc! {
-                    "RouteName" : current_route
-                };
-
-                let update_info = doc! {
-                    "$set": { "CallingAmount": current_count }
-                };
-                let update_result = DbHandlerMongoDB::update_document_in_table(
-                    &db_connection,
-                    &DbHandlerMongoDB::COLLECTION_NAME_WEBSITE_TRAFFIC.to_string(),
-                    query_info,
-                    update_info,
-                );
-                if update_result.is_err() {
-                    addtional_info = format!("{}<br>could not update database", addtional_info);
-                }
-            }
-            _ => {
-                addtional_info = "<br> multiple records found".to_string();
-            }
-        };
-    } else {
-        warn!(
-            "Error querying database: {}",
-            query_site_result_cursor.unwrap_err()
-        );
-        addtional_info = "<br> Could not get calling information".to_string();
-    }
+        Err(error_info) => {
+            warn!("Error updating calling information: {}", error_info);
+            "<br> Could not get calling information".to_string()
+        }
+    };
 
     //Html(format!("<h1>Hello, World!</h1><br>running on port {}{}",local_settings.web_server_port_https, addtional_info))
 
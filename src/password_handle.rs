@@ -11,8 +11,8 @@ use crate::datatypes::{
     PasswordResetRequest, PasswordResetTokenRequest, PasswordResetTokenRequestResult,
 };
 use crate::{
-    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
-    setting_struct::SettingStruct,
+    database_handler_mongodb::DbConnectionSetting, setting_struct::SettingStruct,
+    user_database::DBUserFunctions,
 };
 
 pub struct UserCredentials {
@@ -36,9 +36,11 @@ pub struct StoredCredentials {
 
 pub async fn validate_credentials(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     credentials: &UserCredentials,
 ) -> Result<Uuid, Error> {
-    let get_result = get_stored_credentials(db_connection, &credentials.username).await;
+    let get_result =
+        get_stored_credentials(db_connection, db_connector, &credentials.username).await;
     if get_result.is_err() {
         return Err(anyhow::anyhow!("Problem getting credentials"));
     }
@@ -61,11 +63,12 @@ pub async fn validate_credentials(
 
 async fn get_stored_credentials(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     _user_name: &str,
 ) -> Result<StoredCredentials, Error> {
-    let query_credentials =
-        DbHandlerMongoDB::get_stored_credentials_by_name(&db_connection, &_user_name.to_string())
-            .await;
+    let query_credentials = db_connector
+        .get_stored_credentials_by_name(&db_connection, &_user_name.to_string())
+        .await;
 
     if query_credentials.is_err() {
         return Err(anyhow::anyhow!(query_credentials.unwrap_err()));
@@ -111,9 +114,10 @@ pub fn compare_password(
 
 pub async fn create_credentials(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     credentials: &UserCredentials,
 ) -> Result<Uuid, Error> {
-    let check_result = check_user_exsits(&db_connection, &credentials.username).await;
+    let check_result = check_user_exsits(&db_connection, db_connector, &credentials.username).await;
     if check_result.is_err() {
         return Err(check_result.unwrap_err());
     }
@@ -123,7 +127,7 @@ pub async fn create_credentials(
         return Err(anyhow::anyhow!("User already exsists, can not recreate"));
     }
 
-    let insert_result = insert_user(&db_connection, &credentials).await;
+    let insert_result = insert_user(&db_connection, db_connector, &credentials).await;
     if insert_result.is_err() {
         return Err(insert_result.unwrap_err());
     }
@@ -133,10 +137,12 @@ pub async fn create_credentials(
 
 pub(crate) async fn check_user_exsits(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     user_name: &str,
 ) -> Result<bool, Error> {
-    let check_result =
-        DbHandlerMongoDB::check_user_exsists_by_name(&db_connection, &user_name.to_string()).await;
+    let check_result = db_connector
+        .check_user_exsists_by_name(&db_connection, &user_name.to_string())
+        .await;
 
     if check_result.is_err() {
         return Err(anyhow::anyhow!(check_result.unwrap_err()));
@@ -147,6 +153,7 @@ pub(crate) async fn check_user_exsits(
 
 pub(crate) async fn insert_user(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     some_credentials: &UserCredentials,
 ) -> Result<Uuid, Error> {
     let salt = SaltString::generate(&mut rand::thread_rng());
@@ -160,8 +167,9 @@ pub(crate) async fn insert_user(
         password_hash: Secret::new(user_password_hashed),
     };
 
-    let insert_result =
-        DbHandlerMongoDB::insert_user(&db_connection, &some_credentials_hashed).await;
+    let insert_result = db_connector
+        .insert_user(&db_connection, &some_credentials_hashed)
+        .await;
 
     if insert_result.is_err() {
         return Err(anyhow::anyhow!(insert_result.unwrap_err()));
@@ -172,6 +180,7 @@ pub(crate) async fn insert_user(
 
 pub async fn update_user_password(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     some_credentials: &UserCredentials,
 ) -> Result<bool, Error> {
     let salt = SaltString::generate(&mut rand::thread_rng());
@@ -185,8 +194,9 @@ pub async fn update_user_password(
         password_hash: Secret::new(user_password_hashed),
     };
 
-    let update_result =
-        DbHandlerMongoDB::update_user_password(&db_connection, &some_credentials_hashed).await;
+    let update_result = db_connector
+        .update_user_password(&db_connection, &some_credentials_hashed)
+        .await;
 
     if update_result.is_err() {
         return Err(anyhow::anyhow!(update_result.unwrap_err()));
@@ -197,6 +207,7 @@ pub async fn update_user_password(
 
 pub async fn update_user_reset_secret(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     user_id: &Uuid,
     reset_secret: &Secret<String>,
 ) -> Result<bool, Error> {
@@ -208,9 +219,9 @@ pub async fn update_user_reset_secret(
             .to_string(),
     );
 
-    let update_result =
-        DbHandlerMongoDB::update_user_reset_secret(&db_connection, user_id, &reset_secret_hashed)
-            .await;
+    let update_result = db_connector
+        .update_user_reset_secret(&db_connection, user_id, &reset_secret_hashed)
+        .await;
 
     if update_result.is_err() {
         return Err(anyhow::anyhow!(update_result.unwrap_err()));
@@ -221,11 +232,12 @@ pub async fn update_user_reset_secret(
 
 pub async fn check_email_status_by_name(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     user_name: &str,
 ) -> Result<EmailVerificationStatus, Error> {
-    let check_result =
-        DbHandlerMongoDB::check_email_verfification_by_name(&db_connection, &user_name.to_string())
-            .await;
+    let check_result = db_connector
+        .check_email_verfification_by_name(&db_connection, &user_name.to_string())
+        .await;
 
     if check_result.is_err() {
         return Err(anyhow::anyhow!(check_result.unwrap_err()));
@@ -235,11 +247,12 @@ pub async fn check_email_status_by_name(
 
 pub async fn request_password_reset_token(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     request_data: &PasswordResetTokenRequest,
 ) -> Result<PasswordResetTokenRequestResult, Error> {
     let local_settings: SettingStruct = SettingStruct::global().clone();
 
-    let generate_token_result_async = DbHandlerMongoDB::generate_passwort_reset_token(
+    let generate_token_result_async = db_connector.generate_passwort_reset_token(
         &db_connection,
         request_data.user_name.borrow(),
         request_data.reset_secret.borrow(),
@@ -260,6 +273,7 @@ pub async fn request_password_reset_token(
 
 pub async fn reset_password_with_token(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     request_data: &PasswordResetRequest,
 ) -> Result<bool, Error> {
     let salt = SaltString::generate(&mut rand::thread_rng());
@@ -268,13 +282,14 @@ pub async fn reset_password_with_token(
         .unwrap()
         .to_string();
 
-    let passwort_reset_result = DbHandlerMongoDB::change_password_with_token(
-        &db_connection,
-        &request_data.username,
-        &request_data.reset_token,
-        &Secret::new(user_password_hashed),
-    )
-    .await;
+    let passwort_reset_result = db_connector
+        .change_password_with_token(
+            &db_connection,
+            &request_data.username,
+            &request_data.reset_token,
+            &Secret::new(user_password_hashed),
+        )
+        .await;
 
     if passwort_reset_result.is_err() {
         return Err(anyhow::anyhow!(
@@ -0,0 +1,1419 @@
+use std::collections::{HashMap, HashSet};
+
+use async_session::chrono::{Duration, NaiveDate, NaiveTime, TimeZone, Utc};
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_database::{
+        DBFinanceAccountingFunctions, FinanceAccountBookingEntryListSearchOption,
+    },
+    accounting_logic::{normalize_tags, FinanceBookingHandle},
+    csv_export::FinanceCsvExportHandle,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{
+        AccountCategory, BookingEntryType, FinanceAccount, FinanceAccountType, FinanceBookingLeg,
+        FinancePlainTextData, FinancePlainTextTransaction, FinanceSplitBookingRequest,
+        PlainTextAccountingFormat,
+    },
+};
+
+/// header line of a plain text accounting file with its indented metadata and posting lines
+#[derive(Debug)]
+struct PlainTextBlock {
+    line_number: usize,
+    header: String,
+    /// values are kept escaped, lists are split before unescaping
+    metadata: HashMap<String, String>,
+    postings: Vec<(String, String)>,
+}
+
+impl PlainTextBlock {
+    fn get_text(&self, key: &str) -> Option<String> {
+        return self
+            .metadata
+            .get(key)
+            .map(|elem| FinancePlainTextAccountingHandle::unescape_text(elem));
+    }
+
+    fn get_required_text(&self, key: &str) -> Result<String, String> {
+        let value_option = self.get_text(key);
+        if value_option.is_none() {
+            return Err(format!(
+                "line {}: metadata {} is missing, only files written by the export can be imported",
+                self.line_number, key
+            ));
+        }
+        return Ok(value_option.unwrap());
+    }
+
+    fn get_uuid(&self, key: &str) -> Result<Option<Uuid>, String> {
+        let value_option = self.get_text(key);
+        if value_option.is_none() {
+            return Ok(None);
+        }
+        let parse_result = Uuid::parse_str(value_option.unwrap());
+        if parse_result.is_err() {
+            return Err(format!(
+                "line {}: could not parse {}: {}",
+                self.line_number,
+                key,
+                parse_result.unwrap_err()
+            ));
+        }
+        return Ok(Some(parse_result.unwrap()));
+    }
+
+    fn get_required_uuid(&self, key: &str) -> Result<Uuid, String> {
+        let uuid_result = self.get_uuid(key);
+        if uuid_result.is_err() {
+            return Err(uuid_result.unwrap_err());
+        }
+        let uuid_option = uuid_result.unwrap();
+        if uuid_option.is_none() {
+            return Err(format!(
+                "line {}: metadata {} is missing, only files written by the export can be imported",
+                self.line_number, key
+            ));
+        }
+        return Ok(uuid_option.unwrap());
+    }
+
+    fn get_flag(&self, key: &str) -> bool {
+        let value_option = self.metadata.get(key);
+        return value_option.is_some_and(|elem| elem.eq_ignore_ascii_case("true"));
+    }
+
+    fn get_list(&self, key: &str) -> Vec<String> {
+        let value_option = self.metadata.get(key);
+        if value_option.is_none() {
+            return Vec::new();
+        }
+        return FinancePlainTextAccountingHandle::split_escaped_list(value_option.unwrap());
+    }
+}
+
+pub struct FinancePlainTextAccountingHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceAccountingFunctions,
+}
+
+impl<'a> FinancePlainTextAccountingHandle<'a> {
+    /// beancount only accepts open directives after this date, so all accounts are opened at it
+    const BEANCOUNT_OPEN_DATE: &'static str = "1970-01-01";
+
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceAccountingFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    /// the commodity is written after every amount and has to be valid in ledger and beancount (e.g. EUR)
+    pub fn check_commodity(commodity: &str) -> Result<(), String> {
+        let mut commodity_chars = commodity.chars();
+        let first_char = commodity_chars.next();
+        if first_char.is_none() || !first_char.unwrap().is_ascii_uppercase() {
+            return Err("commodity has to start with an uppercase letter".into());
+        }
+        if commodity.len() > 24
+            || !commodity_chars.all(|elem| elem.is_ascii_uppercase() || elem.is_ascii_digit())
+        {
+            return Err("commodity may only contain up to 24 uppercase letters and digits".into());
+        }
+        return Ok(());
+    }
+
+    /// line breaks and backslashes are escaped so that every value fits on one line
+    pub fn escape_text(value: &str) -> String {
+        return value
+            .replace('\\', "\\\\")
+            .replace('\n', "\\n")
+            .replace('\r', "\\r");
+    }
+
+    pub fn unescape_text(value: &str) -> String {
+        let mut return_object = String::new();
+        let mut value_chars = value.chars();
+        while let Some(value_char) = value_chars.next() {
+            if value_char != '\\' {
+                return_object.push(value_char);
+                continue;
+            }
+            match value_chars.next() {
+                Some('n') => return_object.push('\n'),
+                Some('r') => return_object.push('\r'),
+                Some(escaped_char) => return_object.push(escaped_char),
+                None => return_object.push('\\'),
+            }
+        }
+        return return_object;
+    }
+
+    fn escape_list(values: &Vec<String>) -> String {
+        let escaped_values: Vec<String> = values
+            .iter()
+            .map(|elem| FinancePlainTextAccountingHandle::escape_text(elem).replace(',', "\\,"))
+            .collect();
+        return escaped_values.join(", ");
+    }
+
+    fn split_escaped_list(value: &str) -> Vec<String> {
+        let mut raw_values: Vec<String> = Vec::new();
+        let mut current_value = String::new();
+        let mut value_chars = value.chars();
+        while let Some(value_char) = value_chars.next() {
+            if value_char == '\\' {
+                current_value.push(value_char);
+                if let Some(escaped_char) = value_chars.next() {
+                    current_value.push(escaped_char);
+                }
+            } else if value_char == ',' {
+                raw_values.push(current_value);
+                current_value = String::new();
+            } else {
+                current_value.push(value_char);
+            }
+        }
+        raw_values.push(current_value);
+        return raw_values
+            .iter()
+            .map(|elem| FinancePlainTextAccountingHandle::unescape_text(elem.trim()))
+            .filter(|elem| !elem.is_empty())
+            .collect();
+    }
+
+    fn quote_text(value: &str) -> String {
+        return format!(
+            "\"{}\"",
+            FinancePlainTextAccountingHandle::escape_text(value).replace('"', "\\\"")
+        );
+    }
+
+    /// ':' separates sub accounts, two spaces end an account name, ';' starts a note
+    /// and brackets around the name mark virtual postings in ledger
+    fn ledger_account_component(title: &str) -> String {
+        let cleaned_title: String = title
+            .chars()
+            .map(|elem| match elem {
+                ':' => ' ',
+                ';' => ',',
+                _ => elem,
+            })
+            .collect();
+        let component = cleaned_title
+            .trim_start_matches(|elem: char| elem == '(' || elem == '[' || elem.is_whitespace())
+            .split_whitespace()
+            .collect::<Vec<&str>>()
+            .join(" ");
+        if component.is_empty() {
+            return "Unnamed".into();
+        }
+        return component;
+    }
+
+    /// beancount components start with an uppercase letter or digit followed by letters, digits or dashes
+    fn beancount_account_component(title: &str) -> String {
+        let mut component = String::new();
+        for title_char in title.chars() {
+            if title_char.is_ascii_alphanumeric() {
+                component.push(title_char);
+            } else if !component.is_empty() && !component.ends_with('-') {
+                component.push('-');
+            }
+        }
+        let trimmed_component = component.trim_end_matches('-');
+        if trimmed_component.is_empty() {
+            return "Unnamed".into();
+        }
+        let mut component_chars = trimmed_component.chars();
+        let first_char = component_chars.next().unwrap();
+        return format!(
+            "{}{}",
+            first_char.to_ascii_uppercase(),
+            component_chars.as_str()
+        );
+    }
+
+    fn beancount_root_account(account_category: &AccountCategory) -> &'static str {
+        match account_category {
+            AccountCategory::Liability => "Liabilities",
+            AccountCategory::Equity => "Equity",
+            AccountCategory::Income => "Income",
+            AccountCategory::Expense => "Expenses",
+            AccountCategory::Asset => "Assets",
+        }
+    }
+
+    fn get_unique_name(name: String, used_names: &mut HashSet<String>) -> String {
+        let mut unique_name = name.clone();
+        let mut counter = 2;
+        while used_names.contains(&unique_name) {
+            unique_name = format!("{}-{}", name, counter);
+            counter += 1;
+        }
+        used_names.insert(unique_name.clone());
+        return unique_name;
+    }
+
+    /// account names are built from the title of the account type and the title of the account,
+    /// beancount names additionally start with the root account of the category
+    pub fn create_account_names(
+        data: &FinancePlainTextData,
+        format: &PlainTextAccountingFormat,
+    ) -> (HashMap<Uuid, String>, HashMap<Uuid, String>) {
+        let mut used_names: HashSet<String> = HashSet::new();
+        let mut type_names: HashMap<Uuid, String> = HashMap::new();
+        for account_type in &data.account_types {
+            let type_name = match format {
+                PlainTextAccountingFormat::Ledger => {
+                    FinancePlainTextAccountingHandle::ledger_account_component(&account_type.title)
+                }
+                PlainTextAccountingFormat::Beancount => format!(
+                    "{}:{}",
+                    FinancePlainTextAccountingHandle::beancount_root_account(
+                        &account_type.account_category
+                    ),
+                    FinancePlainTextAccountingHandle::beancount_account_component(
+                        &account_type.title
+                    )
+                ),
+            };
+            type_names.insert(
+                account_type.id,
+                FinancePlainTextAccountingHandle::get_unique_name(type_name, &mut used_names),
+            );
+        }
+        let mut account_names: HashMap<Uuid, String> = HashMap::new();
+        for account in &data.accounts {
+            let type_name = match type_names.get(&account.finance_account_type_id) {
+                Some(type_name) => type_name.clone(),
+                None => match format {
+                    PlainTextAccountingFormat::Ledger => "Unknown".to_string(),
+                    PlainTextAccountingFormat::Beancount => "Assets:Unknown".to_string(),
+                },
+            };
+            let account_component = match format {
+                PlainTextAccountingFormat::Ledger => {
+                    FinancePlainTextAccountingHandle::ledger_account_component(&account.title)
+                }
+                PlainTextAccountingFormat::Beancount => {
+                    FinancePlainTextAccountingHandle::beancount_account_component(&account.title)
+                }
+            };
+            account_names.insert(
+                account.id,
+                FinancePlainTextAccountingHandle::get_unique_name(
+                    format!("{}:{}", type_name, account_component),
+                    &mut used_names,
+                ),
+            );
+        }
+        return (type_names, account_names);
+    }
+
+    fn write_metadata(
+        text: &mut String,
+        format: &PlainTextAccountingFormat,
+        key: &str,
+        escaped_value: &str,
+    ) {
+        match format {
+            PlainTextAccountingFormat::Ledger => {
+                text.push_str(&format!("    ; {}: {}\r\n", key, escaped_value))
+            }
+            PlainTextAccountingFormat::Beancount => text.push_str(&format!(
+                "  {}: \"{}\"\r\n",
+                key,
+                escaped_value.replace('"', "\\\"")
+            )),
+        }
+    }
+
+    fn write_flag(text: &mut String, format: &PlainTextAccountingFormat, key: &str) {
+        match format {
+            PlainTextAccountingFormat::Ledger => text.push_str(&format!("    ; {}: true\r\n", key)),
+            PlainTextAccountingFormat::Beancount => text.push_str(&format!("  {}: TRUE\r\n", key)),
+        }
+    }
+
+    fn signed_legs(booking_request: &FinanceSplitBookingRequest) -> Vec<(Uuid, i128)> {
+        let debit_legs = booking_request
+            .debit_legs
+            .iter()
+            .map(|elem| (elem.finance_account_id, elem.amount as i128));
+        let credit_legs = booking_request
+            .credit_legs
+            .iter()
+            .map(|elem| (elem.finance_account_id, -(elem.amount as i128)));
+        return debit_legs.chain(credit_legs).collect();
+    }
+
+    /// writes account types, accounts and journal entries, debit amounts are positive and credit amounts negative,
+    /// the accounts of saldo entries get balance assertions
+    pub fn data_to_text(
+        data: &FinancePlainTextData,
+        format: &PlainTextAccountingFormat,
+        commodity: &str,
+    ) -> Result<String, String> {
+        let check_result = FinancePlainTextAccountingHandle::check_commodity(commodity);
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+        let (type_names, account_names) =
+            FinancePlainTextAccountingHandle::create_account_names(data, format);
+
+        let mut return_object = String::new();
+        return_object.push_str(&format!(
+            "; journal of FinanceOverView, amounts in {}, ids in the metadata are used by the import\r\n",
+            commodity
+        ));
+        if format.eq(&PlainTextAccountingFormat::Beancount) {
+            return_object.push_str(&format!(
+                "option \"operating_currency\" \"{}\"\r\n",
+                commodity
+            ));
+        }
+
+        for account_type in &data.account_types {
+            return_object.push_str("\r\n");
+            let type_name = type_names.get(&account_type.id).unwrap();
+            match format {
+                PlainTextAccountingFormat::Ledger => {
+                    return_object.push_str(&format!("account {}\r\n", type_name))
+                }
+                PlainTextAccountingFormat::Beancount => return_object.push_str(&format!(
+                    "{} open {}\r\n",
+                    FinancePlainTextAccountingHandle::BEANCOUNT_OPEN_DATE,
+                    type_name
+                )),
+            }
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "type_id",
+                &account_type.id.to_string(),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "title",
+                &FinancePlainTextAccountingHandle::escape_text(&account_type.title),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "description",
+                &FinancePlainTextAccountingHandle::escape_text(&account_type.description),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "category",
+                &account_type.account_category.to_string(),
+            );
+        }
+
+        for account in &data.accounts {
+            return_object.push_str("\r\n");
+            let account_name = account_names.get(&account.id).unwrap();
+            match format {
+                PlainTextAccountingFormat::Ledger => {
+                    return_object.push_str(&format!("account {}\r\n", account_name))
+                }
+                PlainTextAccountingFormat::Beancount => return_object.push_str(&format!(
+                    "{} open {} {}\r\n",
+                    FinancePlainTextAccountingHandle::BEANCOUNT_OPEN_DATE,
+                    account_name,
+                    commodity
+                )),
+            }
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "account_id",
+                &account.id.to_string(),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "type_id",
+                &account.finance_account_type_id.to_string(),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "title",
+                &FinancePlainTextAccountingHandle::escape_text(&account.title),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "description",
+                &FinancePlainTextAccountingHandle::escape_text(&account.description),
+            );
+            if account.account_number.is_some() {
+                FinancePlainTextAccountingHandle::write_metadata(
+                    &mut return_object,
+                    format,
+                    "account_number",
+                    &account.account_number.unwrap().to_string(),
+                );
+            }
+        }
+
+        let mut running_balances: HashMap<Uuid, i128> = HashMap::new();
+        // beancount checks balances at the beginning of a day, so the assertions of a saldo
+        // are written for the following day after all entries of the saldo day
+        let mut pending_assertion_accounts: Vec<Uuid> = Vec::new();
+        for (transaction_index, transaction) in data.transactions.iter().enumerate() {
+            let booking_request = &transaction.booking_request;
+            let signed_legs = FinancePlainTextAccountingHandle::signed_legs(booking_request);
+            let unknown_account = signed_legs
+                .iter()
+                .find(|elem| !account_names.contains_key(&elem.0));
+            if unknown_account.is_some() {
+                return Err(format!(
+                    "journal entry #{} uses unknown account {}",
+                    transaction.running_number,
+                    unknown_account.unwrap().0
+                ));
+            }
+
+            return_object.push_str("\r\n");
+            let booking_date = booking_request.booking_time.date_naive();
+            match format {
+                PlainTextAccountingFormat::Ledger => {
+                    // the payee is only informative, the exact title is part of the metadata
+                    let payee: String = booking_request
+                        .title
+                        .chars()
+                        .map(|elem| if elem == ';' { ',' } else { elem })
+                        .collect();
+                    return_object.push_str(&format!(
+                        "{} * {}\r\n",
+                        booking_date.format("%Y-%m-%d"),
+                        payee.split_whitespace().collect::<Vec<&str>>().join(" ")
+                    ));
+                }
+                PlainTextAccountingFormat::Beancount => return_object.push_str(&format!(
+                    "{} * {}\r\n",
+                    booking_date.format("%Y-%m-%d"),
+                    FinancePlainTextAccountingHandle::quote_text(&booking_request.title)
+                )),
+            }
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "journal_id",
+                &transaction.journal_entry_id.to_string(),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "number",
+                &transaction.running_number.to_string(),
+            );
+            FinancePlainTextAccountingHandle::write_metadata(
+                &mut return_object,
+                format,
+                "time",
+                &booking_request
+                    .booking_time
+                    .format("%H:%M:%S%.f")
+                    .to_string(),
+            );
+            if format.eq(&PlainTextAccountingFormat::Ledger) {
+                FinancePlainTextAccountingHandle::write_metadata(
+                    &mut return_object,
+                    format,
+                    "title",
+                    &FinancePlainTextAccountingHandle::escape_text(&booking_request.title),
+                );
+            }
+            if !booking_request.description.is_empty() {
+                FinancePlainTextAccountingHandle::write_metadata(
+                    &mut return_object,
+                    format,
+                    "description",
+                    &FinancePlainTextAccountingHandle::escape_text(&booking_request.description),
+                );
+            }
+            if !booking_request.tags.is_empty() {
+                FinancePlainTextAccountingHandle::write_metadata(
+                    &mut return_object,
+                    format,
+                    "tags",
+                    &FinancePlainTextAccountingHandle::escape_list(&booking_request.tags),
+                );
+            }
+            if booking_request.is_saldo {
+                FinancePlainTextAccountingHandle::write_flag(&mut return_object, format, "saldo");
+            }
+            if booking_request.is_closing {
+                FinancePlainTextAccountingHandle::write_flag(&mut return_object, format, "closing");
+            }
+            if transaction.reverse_of_journal_entry_id.is_some() {
+                FinancePlainTextAccountingHandle::write_metadata(
+                    &mut return_object,
+                    format,
+                    "reverse_of",
+                    &transaction.reverse_of_journal_entry_id.unwrap().to_string(),
+                );
+            }
+            if booking_request.import_reference.is_some() {
+                FinancePlainTextAccountingHandle::write_metadata(
+                    &mut return_object,
+                    format,
+                    "import_reference",
+                    &FinancePlainTextAccountingHandle::escape_text(
+                        booking_request.import_reference.as_ref().unwrap(),
+                    ),
+                );
+            }
+
+            for (finance_account_id, signed_amount) in &signed_legs {
+                let running_balance = running_balances.entry(*finance_account_id).or_insert(0);
+                *running_balance += signed_amount;
+                let mut posting_line = format!(
+                    "    {:<50}  {:>12} {}",
+                    account_names.get(finance_account_id).unwrap(),
+                    FinanceCsvExportHandle::format_amount(*signed_amount, '.'),
+                    commodity
+                );
+                if booking_request.is_saldo {
+                    match format {
+                        PlainTextAccountingFormat::Ledger => posting_line.push_str(&format!(
+                            " = {} {}",
+                            FinanceCsvExportHandle::format_amount(*running_balance, '.'),
+                            commodity
+                        )),
+                        PlainTextAccountingFormat::Beancount => {
+                            if !pending_assertion_accounts.contains(finance_account_id) {
+                                pending_assertion_accounts.push(*finance_account_id);
+                            }
+                        }
+                    }
+                }
+                return_object.push_str(&format!("{}\r\n", posting_line));
+            }
+
+            let next_transaction = data.transactions.get(transaction_index + 1);
+            let is_last_of_day = next_transaction.is_none()
+                || next_transaction
+                    .unwrap()
+                    .booking_request
+                    .booking_time
+                    .date_naive()
+                    .ne(&booking_date);
+            if is_last_of_day && !pending_assertion_accounts.is_empty() {
+                let assertion_date = booking_date + Duration::days(1);
+                return_object.push_str("\r\n");
+                for finance_account_id in &pending_assertion_accounts {
+                    return_object.push_str(&format!(
+                        "{} balance {:<50}  {:>12} {}\r\n",
+                        assertion_date.format("%Y-%m-%d"),
+                        account_names.get(finance_account_id).unwrap(),
+                        FinanceCsvExportHandle::format_amount(
+                            *running_balances.get(finance_account_id).unwrap(),
+                            '.'
+                        ),
+                        commodity
+                    ));
+                }
+                pending_assertion_accounts.clear();
+            }
+        }
+        return Ok(return_object);
+    }
+
+    /// exact amount in cents, up to two decimals with '.' as separator
+    fn parse_amount(amount_text: &str) -> Result<i128, String> {
+        let (is_negative, unsigned_text) = match amount_text.strip_prefix('-') {
+            Some(unsigned_text) => (true, unsigned_text),
+            None => (false, amount_text.strip_prefix('+').unwrap_or(amount_text)),
+        };
+        let (integer_text, fraction_text) =
+            unsigned_text.split_once('.').unwrap_or((unsigned_text, ""));
+        if integer_text.is_empty()
+            || fraction_text.len() > 2
+            || !integer_text.chars().all(|elem| elem.is_ascii_digit())
+            || !fraction_text.chars().all(|elem| elem.is_ascii_digit())
+        {
+            return Err(format!("could not parse amount {}", amount_text));
+        }
+        let integer_result = integer_text.parse::<i128>();
+        if integer_result.is_err() {
+            return Err(format!("could not parse amount {}", amount_text));
+        }
+        let mut fraction_value: i128 = 0;
+        for fraction_index in 0..2 {
+            fraction_value *= 10;
+            if let Some(fraction_char) = fraction_text.chars().nth(fraction_index) {
+                fraction_value += fraction_char.to_digit(10).unwrap() as i128;
+            }
+        }
+        let amount_cents = integer_result.unwrap() * 100 + fraction_value;
+        return Ok(if is_negative {
+            -amount_cents
+        } else {
+            amount_cents
+        });
+    }
+
+    /// reads a quoted beancount string, returns the still escaped content and the rest of the line
+    fn read_quoted_text(text: &str) -> Result<(String, &str), String> {
+        if !text.starts_with('"') {
+            return Err(format!("expected quoted text: {}", text));
+        }
+        let mut escaped_content = String::new();
+        let mut text_chars = text[1..].char_indices();
+        while let Some((char_index, text_char)) = text_chars.next() {
+            if text_char == '\\' {
+                escaped_content.push(text_char);
+                if let Some((_, escaped_char)) = text_chars.next() {
+                    escaped_content.push(escaped_char);
+                }
+            } else if text_char == '"' {
+                return Ok((escaped_content, &text[char_index + 2..]));
+            } else {
+                escaped_content.push(text_char);
+            }
+        }
+        return Err(format!("quoted text is not closed: {}", text));
+    }
+
+    fn split_blocks(
+        content: &str,
+        format: &PlainTextAccountingFormat,
+    ) -> Result<Vec<PlainTextBlock>, String> {
+        let mut return_object: Vec<PlainTextBlock> = Vec::new();
+        for (line_index, line) in content.lines().enumerate() {
+            let line_number = line_index + 1;
+            let trimmed_line = line.trim();
+            if trimmed_line.is_empty() {
+                continue;
+            }
+            if !line.starts_with(char::is_whitespace) {
+                if trimmed_line.starts_with(';')
+                    || trimmed_line.starts_with('#')
+                    || trimmed_line.starts_with("option ")
+                {
+                    continue;
+                }
+                return_object.push(PlainTextBlock {
+                    line_number,
+                    header: trimmed_line.to_string(),
+                    metadata: HashMap::new(),
+                    postings: Vec::new(),
+                });
+                continue;
+            }
+            let current_block_option = return_object.last_mut();
+            if current_block_option.is_none() {
+                return Err(format!(
+                    "line {}: indented line without transaction or account",
+                    line_number
+                ));
+            }
+            let current_block = current_block_option.unwrap();
+
+            let metadata_text = match format {
+                PlainTextAccountingFormat::Ledger => trimmed_line.strip_prefix(';'),
+                PlainTextAccountingFormat::Beancount => {
+                    if trimmed_line.starts_with(';') {
+                        continue;
+                    }
+                    if trimmed_line.starts_with(|elem: char| elem.is_ascii_lowercase()) {
+                        Some(trimmed_line)
+                    } else {
+                        None
+                    }
+                }
+            };
+            if metadata_text.is_some() {
+                let key_value_option = metadata_text.unwrap().split_once(':');
+                if key_value_option.is_none() {
+                    // comment without key
+                    continue;
+                }
+                let (key_text, value_text) = key_value_option.unwrap();
+                let value = match format {
+                    PlainTextAccountingFormat::Ledger => value_text
+                        .strip_prefix(' ')
+                        .unwrap_or(value_text)
+                        .to_string(),
+                    PlainTextAccountingFormat::Beancount => {
+                        let trimmed_value = value_text.trim();
+                        if trimmed_value.starts_with('"') {
+                            let quoted_result =
+                                FinancePlainTextAccountingHandle::read_quoted_text(trimmed_value);
+                            if quoted_result.is_err() {
+                                return Err(format!(
+                                    "line {}: {}",
+                                    line_number,
+                                    quoted_result.unwrap_err()
+                                ));
+                            }
+                            quoted_result.unwrap().0
+                        } else {
+                            trimmed_value.to_string()
+                        }
+                    }
+                };
+                current_block
+                    .metadata
+                    .insert(key_text.trim().to_string(), value);
+                continue;
+            }
+
+            // account and amount are separated by at least two spaces or a tab
+            let separator_position = [trimmed_line.find("  "), trimmed_line.find('\t')]
+                .iter()
+                .flatten()
+                .min()
+                .cloned();
+            if separator_position.is_none() {
+                return Err(format!(
+                    "line {}: posting without amount: {}",
+                    line_number, trimmed_line
+                ));
+            }
+            let (account_text, amount_text) = trimmed_line.split_at(separator_position.unwrap());
+            current_block.postings.push((
+                account_text.trim().to_string(),
+                amount_text.trim().to_string(),
+            ));
+        }
+        return Ok(return_object);
+    }
+
+    fn parse_category(category_text: &str) -> Result<AccountCategory, String> {
+        for category_value in 0..5 {
+            let account_category = AccountCategory::get_from_int(category_value).unwrap();
+            if account_category.to_string().eq(category_text) {
+                return Ok(account_category);
+            }
+        }
+        return Err(format!("unknown account category {}", category_text));
+    }
+
+    fn parse_account_block(
+        block: &PlainTextBlock,
+        account_name: &str,
+        data: &mut FinancePlainTextData,
+        account_ids: &mut HashMap<String, Uuid>,
+    ) -> Result<(), String> {
+        let account_id_result = block.get_uuid("account_id");
+        if account_id_result.is_err() {
+            return Err(account_id_result.unwrap_err());
+        }
+        let type_id_result = block.get_required_uuid("type_id");
+        if type_id_result.is_err() {
+            return Err(type_id_result.unwrap_err());
+        }
+        let title_result = block.get_required_text("title");
+        if title_result.is_err() {
+            return Err(title_result.unwrap_err());
+        }
+        let description = block.get_text("description").unwrap_or_default();
+
+        let account_id_option = account_id_result.unwrap();
+        if account_id_option.is_none() {
+            let account_category = match block.get_text("category") {
+                Some(category_text) => {
+                    let category_result =
+                        FinancePlainTextAccountingHandle::parse_category(&category_text);
+                    if category_result.is_err() {
+                        return Err(format!(
+                            "line {}: {}",
+                            block.line_number,
+                            category_result.unwrap_err()
+                        ));
+                    }
+                    category_result.unwrap()
+                }
+                None => {
+                    return Err(format!(
+                        "line {}: account type has no category",
+                        block.line_number
+                    ));
+                }
+            };
+            data.account_types.push(FinanceAccountType {
+                id: type_id_result.unwrap(),
+                title: title_result.unwrap(),
+                description,
+                account_category,
+            });
+            return Ok(());
+        }
+
+        let account_number = match block.get_text("account_number") {
+            Some(number_text) => {
+                let number_result = number_text.parse::<u32>();
+                if number_result.is_err() {
+                    return Err(format!(
+                        "line {}: could not parse account number: {}",
+                        block.line_number,
+                        number_result.unwrap_err()
+                    ));
+                }
+                Some(number_result.unwrap())
+            }
+            None => None,
+        };
+        let finance_account = FinanceAccount {
+            id: account_id_option.unwrap(),
+            finance_account_type_id: type_id_result.unwrap(),
+            title: title_result.unwrap(),
+            description,
+            account_number,
+        };
+        account_ids.insert(account_name.to_string(), finance_account.id);
+        data.accounts.push(finance_account);
+        return Ok(());
+    }
+
+    fn parse_transaction_block(
+        block: &PlainTextBlock,
+        booking_date: NaiveDate,
+        title_option: Option<String>,
+        account_ids: &HashMap<String, Uuid>,
+    ) -> Result<FinancePlainTextTransaction, String> {
+        let journal_entry_id_result = block.get_required_uuid("journal_id");
+        if journal_entry_id_result.is_err() {
+            return Err(journal_entry_id_result.unwrap_err());
+        }
+        let number_text_result = block.get_required_text("number");
+        if number_text_result.is_err() {
+            return Err(number_text_result.unwrap_err());
+        }
+        let running_number_result = number_text_result.unwrap().parse::<u64>();
+        if running_number_result.is_err() {
+            return Err(format!(
+                "line {}: could not parse number: {}",
+                block.line_number,
+                running_number_result.unwrap_err()
+            ));
+        }
+        let time_text = block.get_text("time").unwrap_or("00:00:00".into());
+        let booking_time_result = NaiveTime::parse_from_str(&time_text, "%H:%M:%S%.f");
+        if booking_time_result.is_err() {
+            return Err(format!(
+                "line {}: could not parse time {}: {}",
+                block.line_number,
+                time_text,
+                booking_time_result.unwrap_err()
+            ));
+        }
+        let title = match title_option {
+            Some(title_value) => title_value,
+            None => {
+                let title_result = block.get_required_text("title");
+                if title_result.is_err() {
+                    return Err(title_result.unwrap_err());
+                }
+                title_result.unwrap()
+            }
+        };
+        let reverse_of_result = block.get_uuid("reverse_of");
+        if reverse_of_result.is_err() {
+            return Err(reverse_of_result.unwrap_err());
+        }
+
+        let mut debit_legs: Vec<FinanceBookingLeg> = Vec::new();
+        let mut credit_legs: Vec<FinanceBookingLeg> = Vec::new();
+        for (account_name, amount_text) in &block.postings {
+            let finance_account_id_option = account_ids.get(account_name);
+            if finance_account_id_option.is_none() {
+                return Err(format!(
+                    "line {}: account {} is not declared",
+                    block.line_number, account_name
+                ));
+            }
+            // balance assertions (= ...) and the commodity follow the amount
+            let amount_result = FinancePlainTextAccountingHandle::parse_amount(
+                amount_text.split_whitespace().next().unwrap_or_default(),
+            );
+            if amount_result.is_err() {
+                return Err(format!(
+                    "line {}: {}",
+                    block.line_number,
+                    amount_result.unwrap_err()
+                ));
+            }
+            let signed_amount = amount_result.unwrap();
+            if signed_amount == 0 || signed_amount.unsigned_abs() > u64::MAX as u128 {
+                return Err(format!(
+                    "line {}: amount of account {} is not supported",
+                    block.line_number, account_name
+                ));
+            }
+            let booking_leg = FinanceBookingLeg {
+                finance_account_id: *finance_account_id_option.unwrap(),
+                amount: signed_amount.unsigned_abs() as u64,
+            };
+            if signed_amount > 0 {
+                debit_legs.push(booking_leg);
+            } else {
+                credit_legs.push(booking_leg);
+            }
+        }
+
+        let booking_request = FinanceSplitBookingRequest {
+            is_saldo: block.get_flag("saldo"),
+            is_closing: block.get_flag("closing"),
+            debit_legs,
+            credit_legs,
+            booking_time: Utc
+                .from_utc_datetime(&booking_date.and_time(booking_time_result.unwrap())),
+            title,
+            description: block.get_text("description").unwrap_or_default(),
+            tags: block.get_list("tags"),
+            import_reference: block.get_text("import_reference"),
+        };
+        if booking_request.debit_legs.is_empty()
+            || booking_request.credit_legs.is_empty()
+            || booking_request.sum_debit_amount().is_err()
+            || booking_request.sum_debit_amount() != booking_request.sum_credit_amount()
+        {
+            return Err(format!(
+                "line {}: transaction is not balanced",
+                block.line_number
+            ));
+        }
+        return Ok(FinancePlainTextTransaction {
+            journal_entry_id: journal_entry_id_result.unwrap(),
+            running_number: running_number_result.unwrap(),
+            reverse_of_journal_entry_id: reverse_of_result.unwrap(),
+            booking_request,
+        });
+    }
+
+    /// reads a file written by data_to_text, ids and flags are taken from the metadata,
+    /// balance assertions are skipped because the balances follow from the postings
+    pub fn parse_text(
+        content: &str,
+        format: &PlainTextAccountingFormat,
+    ) -> Result<FinancePlainTextData, String> {
+        let blocks_result = FinancePlainTextAccountingHandle::split_blocks(content, format);
+        if blocks_result.is_err() {
+            return Err(blocks_result.unwrap_err());
+        }
+        let mut return_object = FinancePlainTextData {
+            account_types: Vec::new(),
+            accounts: Vec::new(),
+            transactions: Vec::new(),
+        };
+        let mut account_ids: HashMap<String, Uuid> = HashMap::new();
+        for block in blocks_result.unwrap() {
+            if format.eq(&PlainTextAccountingFormat::Ledger) && block.header.starts_with("account ")
+            {
+                let parse_result = FinancePlainTextAccountingHandle::parse_account_block(
+                    &block,
+                    block.header["account ".len()..].trim(),
+                    &mut return_object,
+                    &mut account_ids,
+                );
+                if parse_result.is_err() {
+                    return Err(parse_result.unwrap_err());
+                }
+                continue;
+            }
+
+            let (date_text, directive_text) =
+                block.header.split_once(' ').unwrap_or((&block.header, ""));
+            let booking_date_result = NaiveDate::parse_from_str(date_text, "%Y-%m-%d");
+            if booking_date_result.is_err() {
+                return Err(format!(
+                    "line {}: unsupported directive {}",
+                    block.line_number, block.header
+                ));
+            }
+            let booking_date = booking_date_result.unwrap();
+            let directive_text = directive_text.trim();
+
+            let title_option = match format {
+                PlainTextAccountingFormat::Ledger => None,
+                PlainTextAccountingFormat::Beancount => {
+                    if let Some(open_text) = directive_text.strip_prefix("open ") {
+                        let account_name = open_text.split_whitespace().next().unwrap_or_default();
+                        let parse_result = FinancePlainTextAccountingHandle::parse_account_block(
+                            &block,
+                            account_name,
+                            &mut return_object,
+                            &mut account_ids,
+                        );
+                        if parse_result.is_err() {
+                            return Err(parse_result.unwrap_err());
+                        }
+                        continue;
+                    }
+                    if directive_text.starts_with("balance ") {
+                        continue;
+                    }
+                    let flag_option = directive_text.split_once(' ');
+                    if flag_option.is_none() || !["*", "!", "txn"].contains(&flag_option.unwrap().0)
+                    {
+                        return Err(format!(
+                            "line {}: unsupported directive {}",
+                            block.line_number, block.header
+                        ));
+                    }
+                    // the narration is the last quoted text, a payee may be in front of it
+                    let mut remaining_text = flag_option.unwrap().1.trim();
+                    let mut narration_text: Option<String> = None;
+                    while remaining_text.starts_with('"') {
+                        let quoted_result =
+                            FinancePlainTextAccountingHandle::read_quoted_text(remaining_text);
+                        if quoted_result.is_err() {
+                            return Err(format!(
+                                "line {}: {}",
+                                block.line_number,
+                                quoted_result.unwrap_err()
+                            ));
+                        }
+                        let (quoted_content, rest_text) = quoted_result.unwrap();
+                        narration_text = Some(FinancePlainTextAccountingHandle::unescape_text(
+                            &quoted_content,
+                        ));
+                        remaining_text = rest_text.trim();
+                    }
+                    Some(narration_text.unwrap_or_default())
+                }
+            };
+
+            let transaction_result = FinancePlainTextAccountingHandle::parse_transaction_block(
+                &block,
+                booking_date,
+                title_option,
+                &account_ids,
+            );
+            if transaction_result.is_err() {
+                return Err(transaction_result.unwrap_err());
+            }
+            return_object.transactions.push(transaction_result.unwrap());
+        }
+        return Ok(return_object);
+    }
+
+    /// collects account types, accounts and all journal entries with their booking legs,
+    /// the main accounts of a journal entry stay the first leg of each side
+    pub async fn load_data(&self) -> Result<FinancePlainTextData, String> {
+        let account_types_result = self
+            .db_connector
+            .finance_account_type_list(&self.db_connection_settings, &self.user_id)
+            .await;
+        if account_types_result.is_err() {
+            return Err(account_types_result.unwrap_err());
+        }
+        let accounts_result = self
+            .db_connector
+            .finance_account_list(&self.db_connection_settings, &self.user_id, None)
+            .await;
+        if accounts_result.is_err() {
+            return Err(accounts_result.unwrap_err());
+        }
+        let accounts = accounts_result.unwrap();
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let journal_entries_result = booking_handle.list_journal_entries(None, None).await;
+        if journal_entries_result.is_err() {
+            return Err(journal_entries_result.unwrap_err());
+        }
+        let mut journal_entries = journal_entries_result.unwrap();
+        journal_entries.sort_by_key(|elem| (elem.booking_time, elem.running_number));
+
+        let mut booking_entries = Vec::new();
+        if !accounts.is_empty() {
+            let search_options = accounts
+                .iter()
+                .map(|elem| FinanceAccountBookingEntryListSearchOption::new(&elem.id, None, None))
+                .collect();
+            let booking_entries_result = booking_handle
+                .list_account_booking_entries(search_options)
+                .await;
+            if booking_entries_result.is_err() {
+                return Err(booking_entries_result.unwrap_err());
+            }
+            booking_entries = booking_entries_result.unwrap();
+        }
+        let account_positions: HashMap<Uuid, usize> = accounts
+            .iter()
+            .enumerate()
+            .map(|(position, elem)| (elem.id, position))
+            .collect();
+
+        let mut transactions = Vec::new();
+        for journal_entry in &journal_entries {
+            let mut journal_booking_entries: Vec<_> = booking_entries
+                .iter()
+                .filter(|elem| elem.finance_journal_diary_id.eq(&journal_entry.id))
+                .collect();
+            journal_booking_entries.sort_by_key(|elem| {
+                let main_account_id = match elem.booking_type {
+                    BookingEntryType::Debit | BookingEntryType::SaldoDebit => {
+                        journal_entry.debit_finance_account_id
+                    }
+                    BookingEntryType::Credit | BookingEntryType::SaldoCredit => {
+                        journal_entry.credit_finance_account_id
+                    }
+                };
+                (
+                    elem.finance_account_id.ne(&main_account_id),
+                    account_positions
+                        .get(&elem.finance_account_id)
+                        .cloned()
+                        .unwrap_or(usize::MAX),
+                )
+            });
+            let mut debit_legs = Vec::new();
+            let mut credit_legs = Vec::new();
+            for booking_entry in journal_booking_entries {
+                let booking_leg = FinanceBookingLeg {
+                    finance_account_id: booking_entry.finance_account_id,
+                    amount: booking_entry.amount,
+                };
+                match booking_entry.booking_type {
+                    BookingEntryType::Debit | BookingEntryType::SaldoDebit => {
+                        debit_legs.push(booking_leg)
+                    }
+                    BookingEntryType::Credit | BookingEntryType::SaldoCredit => {
+                        credit_legs.push(booking_leg)
+                    }
+                }
+            }
+            transactions.push(FinancePlainTextTransaction {
+                journal_entry_id: journal_entry.id,
+                running_number: journal_entry.running_number,
+                reverse_of_journal_entry_id: journal_entry.reverse_of_journal_entry_id,
+                booking_request: FinanceSplitBookingRequest {
+                    is_saldo: journal_entry.is_saldo,
+                    is_closing: journal_entry.is_closing,
+                    debit_legs,
+                    credit_legs,
+                    booking_time: journal_entry.booking_time,
+                    title: journal_entry.title.clone(),
+                    description: journal_entry.description.clone(),
+                    tags: journal_entry.tags.clone(),
+                    import_reference: journal_entry.import_reference.clone(),
+                },
+            });
+        }
+
+        return Ok(FinancePlainTextData {
+            account_types: account_types_result.unwrap(),
+            accounts,
+            transactions,
+        });
+    }
+
+    pub async fn export_text(
+        &self,
+        format: &PlainTextAccountingFormat,
+        commodity: &str,
+    ) -> Result<String, String> {
+        let check_result = FinancePlainTextAccountingHandle::check_commodity(commodity);
+        if check_result.is_err() {
+            return Err(check_result.unwrap_err());
+        }
+        let data_result = self.load_data().await;
+        if data_result.is_err() {
+            return Err(data_result.unwrap_err());
+        }
+        return FinancePlainTextAccountingHandle::data_to_text(
+            &data_result.unwrap(),
+            format,
+            commodity,
+        );
+    }
+
+    pub fn export_text_sync(
+        &self,
+        format: &PlainTextAccountingFormat,
+        commodity: &str,
+    ) -> Result<String, String> {
+        let temp_var_0 = executor::block_on(self.export_text(format, commodity));
+        return temp_var_0;
+    }
+
+    /// writes the parsed data into an empty journal, account types and accounts keep their ids,
+    /// journal entries get new ids and reversals are linked to the new entries again
+    pub async fn import_data(&self, data: &FinancePlainTextData) -> Result<usize, String> {
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let existing_entries_result = booking_handle.list_journal_entries(None, None).await;
+        if existing_entries_result.is_err() {
+            return Err(existing_entries_result.unwrap_err());
+        }
+        if !existing_entries_result.unwrap().is_empty() {
+            return Err(
+                "the journal already contains entries, plain text files can only be imported into an empty journal"
+                    .into(),
+            );
+        }
+
+        for account_type in &data.account_types {
+            let upsert_result = self
+                .db_connector
+                .finance_account_type_upsert(
+                    &self.db_connection_settings,
+                    &self.user_id,
+                    account_type,
+                )
+                .await;
+            if upsert_result.is_err() {
+                return Err(upsert_result.unwrap_err());
+            }
+        }
+        let existing_types_result = self
+            .db_connector
+            .finance_account_type_list(&self.db_connection_settings, &self.user_id)
+            .await;
+        if existing_types_result.is_err() {
+            return Err(existing_types_result.unwrap_err());
+        }
+        let existing_types = existing_types_result.unwrap();
+        for account in &data.accounts {
+            if !existing_types
+                .iter()
+                .any(|elem| elem.id.eq(&account.finance_account_type_id))
+            {
+                return Err(format!(
+                    "account type of account {} is not available",
+                    account.title
+                ));
+            }
+            let upsert_result = self
+                .db_connector
+                .finance_account_upsert(&self.db_connection_settings, &self.user_id, account)
+                .await;
+            if upsert_result.is_err() {
+                return Err(upsert_result.unwrap_err());
+            }
+        }
+
+        // entries are written in the order of their running numbers so that the numbers are kept,
+        // consecutive entries are written together, a reversal needs the new id of its original entry
+        let mut sorted_transactions: Vec<&FinancePlainTextTransaction> =
+            data.transactions.iter().collect();
+        sorted_transactions.sort_by_key(|elem| elem.running_number);
+        let mut new_journal_entry_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut pending_transactions: Vec<&FinancePlainTextTransaction> = Vec::new();
+        let mut imported_count = 0;
+        for (transaction_index, transaction) in sorted_transactions.iter().enumerate() {
+            if transaction.reverse_of_journal_entry_id.is_none() {
+                pending_transactions.push(transaction);
+            }
+            let is_last = transaction_index + 1 == sorted_transactions.len();
+            if !pending_transactions.is_empty()
+                && (is_last || transaction.reverse_of_journal_entry_id.is_some())
+            {
+                let actions_to_insert = pending_transactions
+                    .iter()
+                    .map(|elem| {
+                        let mut booking_request = elem.booking_request.clone();
+                        booking_request.tags = normalize_tags(&booking_request.tags);
+                        booking_request
+                    })
+                    .collect();
+                let insert_result = self
+                    .db_connector
+                    .finance_insert_split_booking_entries(
+                        &self.db_connection_settings,
+                        &self.user_id,
+                        actions_to_insert,
+                    )
+                    .await;
+                if insert_result.is_err() {
+                    return Err(format!(
+                        "error importing journal entries, {} entries were imported before: {}",
+                        imported_count,
+                        insert_result.unwrap_err()
+                    ));
+                }
+                for (pending_transaction, booking_result) in
+                    pending_transactions.iter().zip(insert_result.unwrap())
+                {
+                    new_journal_entry_ids.insert(
+                        pending_transaction.journal_entry_id,
+                        booking_result.journal_entry.id,
+                    );
+                    imported_count += 1;
+                }
+                pending_transactions.clear();
+            }
+
+            if transaction.reverse_of_journal_entry_id.is_some() {
+                let original_id_option =
+                    new_journal_entry_ids.get(&transaction.reverse_of_journal_entry_id.unwrap());
+                if original_id_option.is_none() {
+                    return Err(format!(
+                        "reversed entry of journal entry #{} is not part of the file, {} entries were imported before",
+                        transaction.running_number, imported_count
+                    ));
+                }
+                let reverse_result = self
+                    .db_connector
+                    .finance_reverse_journal_entry(
+                        &self.db_connection_settings,
+                        &self.user_id,
+                        original_id_option.unwrap(),
+                        transaction.booking_request.booking_time,
+                        &transaction.booking_request.description,
+                    )
+                    .await;
+                if reverse_result.is_err() {
+                    return Err(format!(
+                        "error importing reversal #{}, {} entries were imported before: {}",
+                        transaction.running_number,
+                        imported_count,
+                        reverse_result.unwrap_err()
+                    ));
+                }
+                new_journal_entry_ids.insert(
+                    transaction.journal_entry_id,
+                    reverse_result.unwrap().journal_entry.id,
+                );
+                imported_count += 1;
+            }
+        }
+        return Ok(imported_count);
+    }
+
+    pub async fn import_text(
+        &self,
+        content: &str,
+        format: &PlainTextAccountingFormat,
+    ) -> Result<usize, String> {
+        let data_result = FinancePlainTextAccountingHandle::parse_text(content, format);
+        if data_result.is_err() {
+            return Err(data_result.unwrap_err());
+        }
+        let temp_var_0 = self.import_data(&data_result.unwrap()).await;
+        return temp_var_0;
+    }
+
+    pub fn import_text_sync(
+        &self,
+        content: &str,
+        format: &PlainTextAccountingFormat,
+    ) -> Result<usize, String> {
+        let temp_var_0 = executor::block_on(self.import_text(content, format));
+        return temp_var_0;
+    }
+}
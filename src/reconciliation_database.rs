@@ -0,0 +1,840 @@
+use async_session::chrono::{DateTime, Utc};
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, Bson, Document, Uuid},
+    error::{TRANSIENT_TRANSACTION_ERROR, UNKNOWN_TRANSACTION_COMMIT_RESULT},
+    options::{Acknowledgment, FindOptions, ReadConcern, TransactionOptions, WriteConcern},
+    ClientSession, Collection,
+};
+use rusqlite::{params, Row, TransactionBehavior};
+use tokio_postgres::Row as PostgresRow;
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::FinanceReconciliation,
+};
+
+/// reconciliations mark booking entries, so the accounting functions are required as well
+#[async_trait(?Send)]
+pub trait DBFinanceReconciliationFunctions: DBFinanceAccountingFunctions {
+    /// lists the reconciliations of an account (or all accounts), latest statement first
+    async fn finance_reconciliation_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String>;
+    /// stores the reconciliation and marks the given booking entries of the account,
+    /// fails if one of the entries does not exist or is already reconciled
+    async fn finance_reconciliation_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation: &FinanceReconciliation,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<(), String>;
+    /// removes the reconciliation and the mark of its booking entries
+    async fn finance_reconciliation_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation_id: &Uuid,
+    ) -> Result<(), String>;
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReconciliationFunctions for DbHandlerMongoDB {
+    async fn finance_reconciliation_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let reconciliation_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECONCILIATIONS);
+
+        //get a binary of UUID or it will not work in production
+        let mut filter = doc! {"user_id":mongodb::bson::Binary::from_uuid(user_id.clone())};
+        if finance_account_id.is_some() {
+            filter.insert(
+                "finance_account_id",
+                mongodb::bson::Binary::from_uuid(finance_account_id.unwrap()),
+            );
+        }
+
+        debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
+        let projection = doc! {"reconciliation_id":<i32>::from(1),
+        "finance_account_id":<i32>::from(1),
+        "statement_time":<i32>::from(1),
+        "statement_balance":<i32>::from(1),
+        "book_balance":<i32>::from(1),};
+        let options = FindOptions::builder()
+            .projection(projection)
+            .sort(doc! {"statement_time":<i32>::from(-1)})
+            .build();
+
+        let query_execute_result = reconciliation_collection.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let stored_reconciliation_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "reconciliation_id");
+            if stored_reconciliation_id.is_err() {
+                return Err(stored_reconciliation_id.unwrap_err());
+            }
+            let stored_finance_account_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "finance_account_id");
+            if stored_finance_account_id.is_err() {
+                return Err(stored_finance_account_id.unwrap_err());
+            }
+            let stored_statement_time = inner_doc.get_datetime("statement_time");
+            if stored_statement_time.is_err() {
+                return Err(stored_statement_time.unwrap_err().to_string());
+            }
+            let stored_statement_balance = inner_doc.get_i64("statement_balance");
+            if stored_statement_balance.is_err() {
+                return Err(stored_statement_balance.unwrap_err().to_string());
+            }
+            let stored_book_balance = inner_doc.get_i64("book_balance");
+            if stored_book_balance.is_err() {
+                return Err(stored_book_balance.unwrap_err().to_string());
+            }
+
+            let reconciliation = FinanceReconciliation {
+                id: stored_reconciliation_id.unwrap(),
+                finance_account_id: stored_finance_account_id.unwrap(),
+                statement_time: stored_statement_time.unwrap().to_chrono(),
+                statement_balance: stored_statement_balance.unwrap(),
+                book_balance: stored_book_balance.unwrap(),
+            };
+
+            result_list.push(reconciliation);
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} reconciliations",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_reconciliation_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation: &FinanceReconciliation,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let session_result = client.start_session(None).await;
+        if session_result.is_err() {
+            return Err(format!(
+                "problem getting session: {}",
+                session_result.unwrap_err()
+            ));
+        }
+
+        let options = TransactionOptions::builder()
+            .read_concern(ReadConcern::majority())
+            .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+            .build();
+
+        let mut session = session_result.unwrap();
+        let transaction_start_result = session.start_transaction(options).await;
+        if transaction_start_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_start_result.unwrap_err()
+            ));
+        }
+
+        loop {
+            let execute_result =
+                DbHandlerMongoDB::execute_finance_reconciliation_insert_with_transaction(
+                    &mut session,
+                    &conncetion_settings.instance,
+                    &user_id,
+                    &reconciliation,
+                    &booking_entry_ids,
+                )
+                .await;
+            if execute_result.is_ok() {
+                debug!(target:"app::FinanceOverView","inserted reconciliation for user id {}",&user_id);
+                return Ok(());
+            } else {
+                let error_var = execute_result.unwrap_err();
+
+                if !error_var.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+                    let error_message;
+                    let custom_info = error_var.get_custom::<String>();
+                    if custom_info.is_some() {
+                        error_message = custom_info.unwrap().to_string();
+                    } else {
+                        error_message = error_var.to_string();
+                    }
+                    return Err(format!("Problem closing transaction: {}", error_message));
+                }
+            }
+        }
+    }
+
+    async fn finance_reconciliation_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation_id: &Uuid,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let session_result = client.start_session(None).await;
+        if session_result.is_err() {
+            return Err(format!(
+                "problem getting session: {}",
+                session_result.unwrap_err()
+            ));
+        }
+
+        let options = TransactionOptions::builder()
+            .read_concern(ReadConcern::majority())
+            .write_concern(WriteConcern::builder().w(Acknowledgment::Majority).build())
+            .build();
+
+        let mut session = session_result.unwrap();
+        let transaction_start_result = session.start_transaction(options).await;
+        if transaction_start_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_start_result.unwrap_err()
+            ));
+        }
+
+        loop {
+            let execute_result =
+                DbHandlerMongoDB::execute_finance_reconciliation_delete_with_transaction(
+                    &mut session,
+                    &conncetion_settings.instance,
+                    &user_id,
+                    &reconciliation_id,
+                )
+                .await;
+            if execute_result.is_ok() {
+                debug!(target:"app::FinanceOverView","deleted reconciliation for user id {}",&user_id);
+                return Ok(());
+            } else {
+                let error_var = execute_result.unwrap_err();
+
+                if !error_var.contains_label(TRANSIENT_TRANSACTION_ERROR) {
+                    let error_message;
+                    let custom_info = error_var.get_custom::<String>();
+                    if custom_info.is_some() {
+                        error_message = custom_info.unwrap().to_string();
+                    } else {
+                        error_message = error_var.to_string();
+                    }
+                    return Err(format!("Problem closing transaction: {}", error_message));
+                }
+            }
+        }
+    }
+}
+
+impl DbHandlerMongoDB {
+    async fn execute_finance_reconciliation_insert_with_transaction(
+        session: &mut ClientSession,
+        db_instance_name: &String,
+        user_id: &Uuid,
+        reconciliation: &FinanceReconciliation,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<(), mongodb::error::Error> {
+        let client = session.client();
+        let db_instance = client.database(&db_instance_name);
+
+        let booking_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES);
+
+        let reconciliation_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECONCILIATIONS);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let reconciliation_id_value = mongodb::bson::Binary::from_uuid(reconciliation.id);
+        let finance_account_id_value =
+            mongodb::bson::Binary::from_uuid(reconciliation.finance_account_id);
+        let statement_time: DateTime<Utc> = reconciliation.statement_time;
+
+        reconciliation_collection
+            .insert_one_with_session(
+                doc! {
+                    "reconciliation_id":reconciliation_id_value.clone(),
+                    "user_id":user_id_value.clone(),
+                    "finance_account_id":finance_account_id_value.clone(),
+                    "statement_time":statement_time,
+                    "statement_balance":reconciliation.statement_balance,
+                    "book_balance":reconciliation.book_balance,
+                },
+                None,
+                session,
+            )
+            .await?;
+
+        if !booking_entry_ids.is_empty() {
+            let booking_entry_id_values: Vec<mongodb::bson::Binary> = booking_entry_ids
+                .iter()
+                .map(|elem| mongodb::bson::Binary::from_uuid(elem.clone()))
+                .collect();
+            // entries stored before reconciliation was introduced have no field, null matches them as well
+            let update_result = booking_entries_collection
+                .update_many_with_session(
+                    doc! {"user_id":user_id_value.clone(),
+                    "finance_account_id":finance_account_id_value.clone(),
+                    "booking_entry_id":doc! {"$in":booking_entry_id_values},
+                    "booking_time":doc! {"$lte":statement_time},
+                    "reconciliation_id":Bson::Null},
+                    doc! {"$set": doc! {"reconciliation_id":reconciliation_id_value.clone()}},
+                    None,
+                    session,
+                )
+                .await?;
+            if update_result
+                .modified_count
+                .ne(&(booking_entry_ids.len() as u64))
+            {
+                return Err(mongodb::error::Error::custom(format!(
+                    "only {} of {} booking entries could be reconciled, they must belong to the account, be dated up to the statement and not be reconciled yet",
+                    update_result.modified_count,
+                    booking_entry_ids.len()
+                )));
+            }
+        }
+
+        loop {
+            let result = session.commit_transaction().await;
+            if let Err(ref error) = result {
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue;
+                }
+            }
+
+            return Ok(());
+        }
+    }
+
+    async fn execute_finance_reconciliation_delete_with_transaction(
+        session: &mut ClientSession,
+        db_instance_name: &String,
+        user_id: &Uuid,
+        reconciliation_id: &Uuid,
+    ) -> Result<(), mongodb::error::Error> {
+        let client = session.client();
+        let db_instance = client.database(&db_instance_name);
+
+        let booking_entries_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_BOOKING_ENTRIES);
+
+        let reconciliation_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECONCILIATIONS);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let reconciliation_id_value = mongodb::bson::Binary::from_uuid(reconciliation_id.clone());
+
+        let delete_result = reconciliation_collection
+            .delete_one_with_session(
+                doc! {"user_id":user_id_value.clone(),"reconciliation_id":reconciliation_id_value.clone()},
+                None,
+                session,
+            )
+            .await?;
+        if delete_result.deleted_count.ne(&1) {
+            return Err(mongodb::error::Error::custom(format!(
+                "reconciliation {} not found",
+                reconciliation_id
+            )));
+        }
+
+        booking_entries_collection
+            .update_many_with_session(
+                doc! {"user_id":user_id_value.clone(),"reconciliation_id":reconciliation_id_value.clone()},
+                doc! {"$set": doc! {"reconciliation_id":Bson::Null}},
+                None,
+                session,
+            )
+            .await?;
+
+        loop {
+            let result = session.commit_transaction().await;
+            if let Err(ref error) = result {
+                if error.contains_label(UNKNOWN_TRANSACTION_COMMIT_RESULT) {
+                    continue;
+                }
+            }
+
+            return Ok(());
+        }
+    }
+}
+
+impl DbHandlerSqlite {
+    fn read_reconciliation_row(row: &Row) -> Result<FinanceReconciliation, String> {
+        let stored_reconciliation_id = DbHandlerSqlite::get_uuid_from_row(row, "reconciliation_id");
+        if stored_reconciliation_id.is_err() {
+            return Err(stored_reconciliation_id.unwrap_err());
+        }
+        let stored_account_id = DbHandlerSqlite::get_uuid_from_row(row, "finance_account_id");
+        if stored_account_id.is_err() {
+            return Err(stored_account_id.unwrap_err());
+        }
+        let stored_statement_time = DbHandlerSqlite::get_datetime_from_row(row, "statement_time");
+        if stored_statement_time.is_err() {
+            return Err(stored_statement_time.unwrap_err());
+        }
+        let stored_statement_balance = row.get::<_, i64>("statement_balance");
+        if stored_statement_balance.is_err() {
+            return Err(stored_statement_balance.unwrap_err().to_string());
+        }
+        let stored_book_balance = row.get::<_, i64>("book_balance");
+        if stored_book_balance.is_err() {
+            return Err(stored_book_balance.unwrap_err().to_string());
+        }
+
+        return Ok(FinanceReconciliation {
+            id: stored_reconciliation_id.unwrap(),
+            finance_account_id: stored_account_id.unwrap(),
+            statement_time: stored_statement_time.unwrap(),
+            statement_balance: stored_statement_balance.unwrap(),
+            book_balance: stored_book_balance.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReconciliationFunctions for DbHandlerSqlite {
+    async fn finance_reconciliation_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceReconciliations WHERE user_id = ?1
+            AND (?2 IS NULL OR finance_account_id = ?2) ORDER BY statement_time DESC",
+            params![
+                user_id.to_string(),
+                finance_account_id.map(|elem| elem.to_string())
+            ],
+            DbHandlerSqlite::read_reconciliation_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} reconciliations",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_reconciliation_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation: &FinanceReconciliation,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let mut connection = connection_result.unwrap();
+
+        let transaction_result =
+            connection.transaction_with_behavior(TransactionBehavior::Immediate);
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_result.unwrap_err()
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let insert_result = transaction.execute(
+            "INSERT INTO FinanceReconciliations (reconciliation_id, user_id, finance_account_id, statement_time,
+            statement_balance, book_balance) VALUES (?1, ?2, ?3, ?4, ?5, ?6)",
+            params![
+                reconciliation.id.to_string(),
+                user_id.to_string(),
+                reconciliation.finance_account_id.to_string(),
+                DbHandlerSqlite::format_datetime(&reconciliation.statement_time),
+                reconciliation.statement_balance,
+                reconciliation.book_balance,
+            ],
+        );
+        if insert_result.is_err() {
+            let insert_err = insert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err.to_string());
+        }
+
+        // only entries matching all conditions are marked, the transaction is not committed if one is missing
+        let mut matching_count = 0;
+        for booking_entry_id in booking_entry_ids {
+            let update_result = transaction.execute(
+                "UPDATE BookingEntries SET reconciliation_id = ?1 WHERE user_id = ?2 AND booking_entry_id = ?3
+                AND finance_account_id = ?4 AND booking_time <= ?5 AND reconciliation_id IS NULL",
+                params![
+                    reconciliation.id.to_string(),
+                    user_id.to_string(),
+                    booking_entry_id.to_string(),
+                    reconciliation.finance_account_id.to_string(),
+                    DbHandlerSqlite::format_datetime(&reconciliation.statement_time),
+                ],
+            );
+            if update_result.is_err() {
+                return Err(update_result.unwrap_err().to_string());
+            }
+            matching_count += update_result.unwrap();
+        }
+        if matching_count != booking_entry_ids.len() {
+            return Err(format!(
+                "only {} of {} booking entries could be reconciled, they must belong to the account, be dated up to the statement and not be reconciled yet",
+                matching_count,
+                booking_entry_ids.len()
+            ));
+        }
+
+        let commit_result = transaction.commit();
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                commit_result.unwrap_err()
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","reconciled {} booking entries for user id {}",matching_count,&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_reconciliation_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation_id: &Uuid,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let mut connection = connection_result.unwrap();
+
+        let transaction_result =
+            connection.transaction_with_behavior(TransactionBehavior::Immediate);
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_result.unwrap_err()
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let update_result = transaction.execute(
+            "UPDATE BookingEntries SET reconciliation_id = NULL WHERE user_id = ?1 AND reconciliation_id = ?2",
+            [user_id.to_string(), reconciliation_id.to_string()],
+        );
+        if update_result.is_err() {
+            return Err(update_result.unwrap_err().to_string());
+        }
+        let delete_result = transaction.execute(
+            "DELETE FROM FinanceReconciliations WHERE user_id = ?1 AND reconciliation_id = ?2",
+            [user_id.to_string(), reconciliation_id.to_string()],
+        );
+        if delete_result.is_err() {
+            let delete_err = delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!("reconciliation {} not found", reconciliation_id));
+        }
+
+        let commit_result = transaction.commit();
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                commit_result.unwrap_err()
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl DbHandlerPostgres {
+    fn read_reconciliation_row(row: &PostgresRow) -> Result<FinanceReconciliation, String> {
+        let stored_reconciliation_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "reconciliation_id");
+        if stored_reconciliation_id.is_err() {
+            return Err(stored_reconciliation_id.unwrap_err());
+        }
+        let stored_account_id = DbHandlerPostgres::get_uuid_from_row(row, "finance_account_id");
+        if stored_account_id.is_err() {
+            return Err(stored_account_id.unwrap_err());
+        }
+        let stored_statement_time = row.try_get::<_, DateTime<Utc>>("statement_time");
+        if stored_statement_time.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_statement_time.unwrap_err(),
+            ));
+        }
+        let stored_statement_balance = row.try_get::<_, i64>("statement_balance");
+        if stored_statement_balance.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_statement_balance.unwrap_err(),
+            ));
+        }
+        let stored_book_balance = row.try_get::<_, i64>("book_balance");
+        if stored_book_balance.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_book_balance.unwrap_err(),
+            ));
+        }
+
+        return Ok(FinanceReconciliation {
+            id: stored_reconciliation_id.unwrap(),
+            finance_account_id: stored_account_id.unwrap(),
+            statement_time: stored_statement_time.unwrap(),
+            statement_balance: stored_statement_balance.unwrap(),
+            book_balance: stored_book_balance.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReconciliationFunctions for DbHandlerPostgres {
+    async fn finance_reconciliation_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceReconciliations WHERE user_id = $1
+            AND ($2::UUID IS NULL OR finance_account_id = $2) ORDER BY statement_time DESC",
+            &[
+                &user_id.to_uuid_1(),
+                &finance_account_id.map(|elem| elem.to_uuid_1()),
+            ],
+            DbHandlerPostgres::read_reconciliation_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} reconciliations",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_reconciliation_insert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation: &FinanceReconciliation,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let mut client = client_result.unwrap();
+
+        let transaction_result = client.transaction().await;
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                DbHandlerPostgres::format_error(&transaction_result.err().unwrap())
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let insert_result = transaction
+            .execute(
+                "INSERT INTO FinanceReconciliations (reconciliation_id, user_id, finance_account_id, statement_time,
+                statement_balance, book_balance) VALUES ($1, $2, $3, $4, $5, $6)",
+                &[
+                    &reconciliation.id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &reconciliation.finance_account_id.to_uuid_1(),
+                    &reconciliation.statement_time,
+                    &reconciliation.statement_balance,
+                    &reconciliation.book_balance,
+                ],
+            )
+            .await;
+        if insert_result.is_err() {
+            let insert_err = DbHandlerPostgres::format_error(&insert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err);
+        }
+
+        // only entries matching all conditions are marked, the transaction is rolled back if one is missing
+        let mut matching_count = 0;
+        for booking_entry_id in booking_entry_ids {
+            let update_result = transaction
+                .execute(
+                    "UPDATE BookingEntries SET reconciliation_id = $1 WHERE user_id = $2 AND booking_entry_id = $3
+                    AND finance_account_id = $4 AND booking_time <= $5 AND reconciliation_id IS NULL",
+                    &[
+                        &reconciliation.id.to_uuid_1(),
+                        &user_id.to_uuid_1(),
+                        &booking_entry_id.to_uuid_1(),
+                        &reconciliation.finance_account_id.to_uuid_1(),
+                        &reconciliation.statement_time,
+                    ],
+                )
+                .await;
+            if update_result.is_err() {
+                return Err(DbHandlerPostgres::format_error(&update_result.unwrap_err()));
+            }
+            matching_count += update_result.unwrap() as usize;
+        }
+        if matching_count != booking_entry_ids.len() {
+            return Err(format!(
+                "only {} of {} booking entries could be reconciled, they must belong to the account, be dated up to the statement and not be reconciled yet",
+                matching_count,
+                booking_entry_ids.len()
+            ));
+        }
+
+        let commit_result = transaction.commit().await;
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                DbHandlerPostgres::format_error(&commit_result.unwrap_err())
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","reconciled {} booking entries for user id {}",matching_count,&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_reconciliation_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation_id: &Uuid,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let mut client = client_result.unwrap();
+
+        let transaction_result = client.transaction().await;
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                DbHandlerPostgres::format_error(&transaction_result.err().unwrap())
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let update_result = transaction
+            .execute(
+                "UPDATE BookingEntries SET reconciliation_id = NULL WHERE user_id = $1 AND reconciliation_id = $2",
+                &[&user_id.to_uuid_1(), &reconciliation_id.to_uuid_1()],
+            )
+            .await;
+        if update_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&update_result.unwrap_err()));
+        }
+        let delete_result = transaction
+            .execute(
+                "DELETE FROM FinanceReconciliations WHERE user_id = $1 AND reconciliation_id = $2",
+                &[&user_id.to_uuid_1(), &reconciliation_id.to_uuid_1()],
+            )
+            .await;
+        if delete_result.is_err() {
+            let delete_err = DbHandlerPostgres::format_error(&delete_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err);
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!("reconciliation {} not found", reconciliation_id));
+        }
+
+        let commit_result = transaction.commit().await;
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                DbHandlerPostgres::format_error(&commit_result.unwrap_err())
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,253 @@
+use async_session::chrono::{DateTime, Utc};
+use futures::executor;
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
+    accounting_database::FinanceAccountBookingEntryListSearchOption,
+    accounting_logic::FinanceBookingHandle,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{BookingEntryType, FinanceReconciliation, FinanceReconciliationStatus},
+    reconciliation_database::DBFinanceReconciliationFunctions,
+};
+
+pub struct FinanceReconciliationHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceReconciliationFunctions,
+}
+
+impl<'a> FinanceReconciliationHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceReconciliationFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    /// reconciliations of an account (or all accounts), latest statement first
+    pub async fn reconciliation_list(
+        &self,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String> {
+        let temp_var_0 = self.db_connector.finance_reconciliation_list(
+            &self.db_connection_settings,
+            &self.user_id,
+            finance_account_id,
+        );
+        let reconciliations_result = temp_var_0.await;
+        if reconciliations_result.is_err() {
+            return Err(reconciliations_result.unwrap_err());
+        }
+        let mut reconciliations = reconciliations_result.unwrap();
+        reconciliations.sort_by(|a, b| b.statement_time.cmp(&a.statement_time));
+        return Ok(reconciliations);
+    }
+
+    pub fn reconciliation_list_sync(
+        &self,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String> {
+        let temp_var_0 = executor::block_on(self.reconciliation_list(finance_account_id));
+        return temp_var_0;
+    }
+
+    /// compares the balance of the account as of the statement date with the statement balance
+    /// and lists the entries up to the statement date that are not reconciled yet,
+    /// saldo entries only carry balances forward and are never listed
+    pub async fn reconciliation_status(
+        &self,
+        finance_account_id: &Uuid,
+        statement_time: DateTime<Utc>,
+        statement_balance: i64,
+    ) -> Result<FinanceReconciliationStatus, String> {
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle
+            .finance_account_list_async(Some(&vec![finance_account_id.clone()]))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        if account_list_result.unwrap().is_empty() {
+            return Err(format!("account {} not found", finance_account_id));
+        }
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let balance_result = booking_handle
+            .calculate_balance_info_till(&vec![finance_account_id.clone()], Some(statement_time))
+            .await;
+        if balance_result.is_err() {
+            return Err(balance_result.unwrap_err());
+        }
+        let book_balance = balance_result
+            .unwrap()
+            .iter()
+            .find(|elem| elem.account_id.eq(finance_account_id))
+            .map(|elem| elem.get_signed_amount() as i64)
+            .unwrap_or(0);
+
+        let entries_result = booking_handle
+            .list_account_booking_entries(vec![FinanceAccountBookingEntryListSearchOption::new(
+                finance_account_id,
+                None,
+                Some(statement_time),
+            )])
+            .await;
+        if entries_result.is_err() {
+            return Err(entries_result.unwrap_err());
+        }
+        // listed by booking time and running number
+        let open_entries: Vec<_> = entries_result
+            .unwrap()
+            .into_iter()
+            .filter(|elem| {
+                elem.reconciliation_id.is_none()
+                    && (elem.booking_type.eq(&BookingEntryType::Debit)
+                        || elem.booking_type.eq(&BookingEntryType::Credit))
+            })
+            .collect();
+
+        return Ok(FinanceReconciliationStatus {
+            finance_account_id: finance_account_id.clone(),
+            statement_time,
+            statement_balance,
+            book_balance,
+            difference: statement_balance - book_balance,
+            open_entries,
+        });
+    }
+
+    pub fn reconciliation_status_sync(
+        &self,
+        finance_account_id: &Uuid,
+        statement_time: DateTime<Utc>,
+        statement_balance: i64,
+    ) -> Result<FinanceReconciliationStatus, String> {
+        let temp_var_0 = executor::block_on(self.reconciliation_status(
+            finance_account_id,
+            statement_time,
+            statement_balance,
+        ));
+        return temp_var_0;
+    }
+
+    /// marks the given open entries as reconciled against the statement, the statement date
+    /// must be after the last statement the account was reconciled against
+    pub async fn reconcile(
+        &self,
+        finance_account_id: &Uuid,
+        statement_time: DateTime<Utc>,
+        statement_balance: i64,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<FinanceReconciliation, String> {
+        let reconciliation_list_result = self
+            .reconciliation_list(Some(finance_account_id.clone()))
+            .await;
+        if reconciliation_list_result.is_err() {
+            return Err(reconciliation_list_result.unwrap_err());
+        }
+        let last_reconciliation_option = reconciliation_list_result
+            .unwrap()
+            .into_iter()
+            .find(|elem| elem.finance_account_id.eq(finance_account_id));
+        if last_reconciliation_option.is_some()
+            && last_reconciliation_option
+                .as_ref()
+                .unwrap()
+                .statement_time
+                .ge(&statement_time)
+        {
+            return Err(format!(
+                "could not reconcile because the account is already reconciled up to {}",
+                last_reconciliation_option.unwrap().statement_time
+            ));
+        }
+
+        let status_result = self
+            .reconciliation_status(finance_account_id, statement_time, statement_balance)
+            .await;
+        if status_result.is_err() {
+            return Err(status_result.unwrap_err());
+        }
+        let status = status_result.unwrap();
+        for booking_entry_id in booking_entry_ids {
+            if !status
+                .open_entries
+                .iter()
+                .any(|elem| elem.id.eq(booking_entry_id))
+            {
+                return Err(format!(
+                    "could not reconcile because booking entry {} is not an open entry of the account up to the statement date",
+                    booking_entry_id
+                ));
+            }
+        }
+
+        let reconciliation = FinanceReconciliation {
+            id: Uuid::new(),
+            finance_account_id: finance_account_id.clone(),
+            statement_time,
+            statement_balance,
+            book_balance: status.book_balance,
+        };
+        let temp_var_0 = self.db_connector.finance_reconciliation_insert(
+            &self.db_connection_settings,
+            &self.user_id,
+            &reconciliation,
+            booking_entry_ids,
+        );
+        let temp_var_1 = temp_var_0.await;
+        if temp_var_1.is_err() {
+            return Err(temp_var_1.unwrap_err());
+        }
+        return Ok(reconciliation);
+    }
+
+    pub fn reconcile_sync(
+        &self,
+        finance_account_id: &Uuid,
+        statement_time: DateTime<Utc>,
+        statement_balance: i64,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<FinanceReconciliation, String> {
+        let temp_var_0 = executor::block_on(self.reconcile(
+            finance_account_id,
+            statement_time,
+            statement_balance,
+            booking_entry_ids,
+        ));
+        return temp_var_0;
+    }
+
+    /// removes the reconciliation, its booking entries can be changed again afterwards
+    pub async fn unreconcile(&self, reconciliation_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = self.db_connector.finance_reconciliation_delete(
+            &self.db_connection_settings,
+            &self.user_id,
+            reconciliation_id,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn unreconcile_sync(&self, reconciliation_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.unreconcile(reconciliation_id));
+        return temp_var_0;
+    }
+}
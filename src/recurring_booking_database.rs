@@ -0,0 +1,884 @@
+use async_session::chrono::{DateTime, Utc};
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, Document, Uuid},
+    options::{FindOptions, UpdateOptions},
+    Collection,
+};
+use rusqlite::{params, Row};
+use tokio_postgres::Row as PostgresRow;
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::{FinanceBookingRequest, FinanceRecurringBooking, RecurringBookingSchedule},
+};
+
+/// recurring bookings are booked via the accounting functions, so they are required as well
+#[async_trait(?Send)]
+pub trait DBFinanceRecurringBookingFunctions: DBFinanceAccountingFunctions {
+    async fn finance_recurring_booking_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceRecurringBooking>, String>;
+    async fn finance_recurring_booking_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String>;
+    /// stores the progress of a booking run, only the last booking time is changed and never moved backwards,
+    /// so edits of the definition saved during the run are kept
+    async fn finance_recurring_booking_set_last_booking_time(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+        last_booking_time: &DateTime<Utc>,
+    ) -> Result<(), String>;
+    async fn finance_recurring_booking_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+    ) -> Result<(), String>;
+    /// ids of all users with at least one recurring booking, used by the background task
+    async fn finance_recurring_booking_list_user_ids(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Vec<Uuid>, String>;
+}
+
+#[async_trait(?Send)]
+impl DBFinanceRecurringBookingFunctions for DbHandlerMongoDB {
+    async fn finance_recurring_booking_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceRecurringBooking>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let recurring_booking_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECURRING_BOOKINGS);
+
+        //get a binary of UUID or it will not work in production
+        let search_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let filter = doc! {"user_id":search_value};
+
+        debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
+        let projection = doc! {"recurring_booking_id":<i32>::from(1),
+        "debit_finance_account_id":<i32>::from(1),
+        "credit_finance_account_id":<i32>::from(1),
+        "amount":<i32>::from(1),
+        "title":<i32>::from(1),
+        "description":<i32>::from(1),
+        "schedule":<i32>::from(1),
+        "start_time":<i32>::from(1),
+        "end_time":<i32>::from(1),
+        "last_booking_time":<i32>::from(1),};
+        let options = FindOptions::builder().projection(projection).build();
+
+        let query_execute_result = recurring_booking_collection.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let stored_recurring_booking_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "recurring_booking_id");
+            if stored_recurring_booking_id.is_err() {
+                return Err(stored_recurring_booking_id.unwrap_err());
+            }
+            let stored_debit_account_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "debit_finance_account_id");
+            if stored_debit_account_id.is_err() {
+                return Err(stored_debit_account_id.unwrap_err());
+            }
+            let stored_credit_account_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "credit_finance_account_id");
+            if stored_credit_account_id.is_err() {
+                return Err(stored_credit_account_id.unwrap_err());
+            }
+            let stored_amount = inner_doc.get_i64("amount");
+            if stored_amount.is_err() {
+                return Err(stored_amount.unwrap_err().to_string());
+            }
+            let stored_title = inner_doc.get_str("title");
+            if stored_title.is_err() {
+                return Err(stored_title.unwrap_err().to_string());
+            }
+            let stored_description = inner_doc.get_str("description");
+            if stored_description.is_err() {
+                return Err(stored_description.unwrap_err().to_string());
+            }
+            let stored_schedule_value = inner_doc.get_i32("schedule");
+            if stored_schedule_value.is_err() {
+                return Err(stored_schedule_value.unwrap_err().to_string());
+            }
+            let stored_schedule =
+                RecurringBookingSchedule::get_from_int(stored_schedule_value.unwrap());
+            if stored_schedule.is_err() {
+                return Err(stored_schedule.unwrap_err());
+            }
+            let stored_start_time = inner_doc.get_datetime("start_time");
+            if stored_start_time.is_err() {
+                return Err(stored_start_time.unwrap_err().to_string());
+            }
+            let stored_end_time =
+                ConvertTools::get_optional_datetime_from_document(&inner_doc, "end_time");
+            if stored_end_time.is_err() {
+                return Err(stored_end_time.unwrap_err());
+            }
+            let stored_last_booking_time =
+                ConvertTools::get_optional_datetime_from_document(&inner_doc, "last_booking_time");
+            if stored_last_booking_time.is_err() {
+                return Err(stored_last_booking_time.unwrap_err());
+            }
+            let start_time = stored_start_time.unwrap().to_chrono();
+
+            let recurring_booking = FinanceRecurringBooking {
+                id: stored_recurring_booking_id.unwrap(),
+                booking_template: FinanceBookingRequest {
+                    is_simple_entry: true,
+                    is_saldo: false,
+                    debit_finance_account_id: stored_debit_account_id.unwrap(),
+                    credit_finance_account_id: stored_credit_account_id.unwrap(),
+                    booking_time: start_time,
+                    amount: stored_amount.unwrap() as u64,
+                    title: stored_title.unwrap().into(),
+                    description: stored_description.unwrap().into(),
+                    tags: Vec::new(),
+                    import_reference: None,
+                },
+                schedule: stored_schedule.unwrap(),
+                start_time,
+                end_time: stored_end_time.unwrap(),
+                last_booking_time: stored_last_booking_time.unwrap(),
+            };
+
+            result_list.push(recurring_booking);
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} recurring bookings",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_recurring_booking_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let recurring_booking_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECURRING_BOOKINGS);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let recurring_booking_id_value = mongodb::bson::Binary::from_uuid(recurring_booking.id);
+        let filter = doc! {"recurring_booking_id":recurring_booking_id_value.clone(),"user_id":user_id_value.clone()};
+        let inner_doc = doc! {
+            "recurring_booking_id":recurring_booking_id_value,
+            "user_id": user_id_value,
+            "debit_finance_account_id":mongodb::bson::Binary::from_uuid(recurring_booking.booking_template.debit_finance_account_id),
+            "credit_finance_account_id":mongodb::bson::Binary::from_uuid(recurring_booking.booking_template.credit_finance_account_id),
+            "amount":recurring_booking.booking_template.amount as i64,
+            "title":&recurring_booking.booking_template.title,
+            "description":&recurring_booking.booking_template.description,
+            "schedule":recurring_booking.schedule.to_int(),
+            "start_time":recurring_booking.start_time,
+            "end_time":recurring_booking.end_time,
+            "last_booking_time":recurring_booking.last_booking_time,
+        };
+        let upsert_doc = doc! {"$set": inner_doc  };
+        let opts = UpdateOptions::builder().upsert(true).build();
+
+        let upsert_result = recurring_booking_collection
+            .update_one(filter, upsert_doc, opts)
+            .await;
+        if upsert_result.is_err() {
+            let upsert_err = &upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+        let upsert_info = upsert_result.unwrap();
+
+        if (upsert_info.matched_count > 1) || (upsert_info.modified_count > 1) {
+            return Err(format!(
+                "Error upserting element, matched count was {}, changed count was {}",
+                upsert_info.matched_count, upsert_info.modified_count
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","upserted recurring booking for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_set_last_booking_time(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+        last_booking_time: &DateTime<Utc>,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let recurring_booking_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECURRING_BOOKINGS);
+
+        let filter = doc! {"recurring_booking_id":mongodb::bson::Binary::from_uuid(recurring_booking_id.clone()),
+        "user_id":mongodb::bson::Binary::from_uuid(user_id.clone()),
+        "$or":[{"last_booking_time":null},{"last_booking_time":{"$lt":last_booking_time}}]};
+        let update_doc = doc! {"$set": {"last_booking_time":last_booking_time}};
+
+        let update_result = recurring_booking_collection
+            .update_one(filter, update_doc, None)
+            .await;
+        if update_result.is_err() {
+            let update_err = &update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","updated last booking time of recurring booking {} for user id {}",&recurring_booking_id,&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let recurring_booking_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECURRING_BOOKINGS);
+
+        let filter = doc! {"recurring_booking_id":mongodb::bson::Binary::from_uuid(recurring_booking_id.clone()),
+        "user_id":mongodb::bson::Binary::from_uuid(user_id.clone())};
+
+        let delete_result = recurring_booking_collection.delete_one(filter, None).await;
+        if delete_result.is_err() {
+            let delete_err = &delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap().deleted_count != 1 {
+            return Err(format!(
+                "recurring booking {} not found",
+                recurring_booking_id
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","deleted recurring booking for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_list_user_ids(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Vec<Uuid>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let recurring_booking_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_RECURRING_BOOKINGS);
+
+        let query_execute_result = recurring_booking_collection
+            .distinct("user_id", None, None)
+            .await;
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut result_list = Vec::new();
+        for user_id_bson in query_execute_result.unwrap() {
+            let user_id_parse_result: Result<Uuid, mongodb::bson::de::Error> =
+                mongodb::bson::from_bson(user_id_bson);
+            if user_id_parse_result.is_err() {
+                return Err(user_id_parse_result.unwrap_err().to_string());
+            }
+            result_list.push(user_id_parse_result.unwrap());
+        }
+
+        return Ok(result_list);
+    }
+}
+
+impl DbHandlerSqlite {
+    fn read_recurring_booking_row(row: &Row) -> Result<FinanceRecurringBooking, String> {
+        let stored_recurring_booking_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "recurring_booking_id");
+        if stored_recurring_booking_id.is_err() {
+            return Err(stored_recurring_booking_id.unwrap_err());
+        }
+        let stored_debit_account_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "debit_finance_account_id");
+        if stored_debit_account_id.is_err() {
+            return Err(stored_debit_account_id.unwrap_err());
+        }
+        let stored_credit_account_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "credit_finance_account_id");
+        if stored_credit_account_id.is_err() {
+            return Err(stored_credit_account_id.unwrap_err());
+        }
+        let stored_amount = row.get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(stored_amount.unwrap_err().to_string());
+        }
+        let stored_title = row.get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+        let stored_description = row.get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(stored_description.unwrap_err().to_string());
+        }
+        let stored_schedule_value = row.get::<_, i32>("schedule");
+        if stored_schedule_value.is_err() {
+            return Err(stored_schedule_value.unwrap_err().to_string());
+        }
+        let stored_schedule =
+            RecurringBookingSchedule::get_from_int(stored_schedule_value.unwrap());
+        if stored_schedule.is_err() {
+            return Err(stored_schedule.unwrap_err());
+        }
+        let stored_start_time = DbHandlerSqlite::get_datetime_from_row(row, "start_time");
+        if stored_start_time.is_err() {
+            return Err(stored_start_time.unwrap_err());
+        }
+        let stored_end_time = DbHandlerSqlite::get_optional_datetime_from_row(row, "end_time");
+        if stored_end_time.is_err() {
+            return Err(stored_end_time.unwrap_err());
+        }
+        let stored_last_booking_time =
+            DbHandlerSqlite::get_optional_datetime_from_row(row, "last_booking_time");
+        if stored_last_booking_time.is_err() {
+            return Err(stored_last_booking_time.unwrap_err());
+        }
+
+        let start_time = stored_start_time.unwrap();
+        return Ok(FinanceRecurringBooking {
+            id: stored_recurring_booking_id.unwrap(),
+            booking_template: FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: stored_debit_account_id.unwrap(),
+                credit_finance_account_id: stored_credit_account_id.unwrap(),
+                booking_time: start_time,
+                amount: stored_amount.unwrap() as u64,
+                title: stored_title.unwrap(),
+                description: stored_description.unwrap(),
+                tags: Vec::new(),
+                import_reference: None,
+            },
+            schedule: stored_schedule.unwrap(),
+            start_time,
+            end_time: stored_end_time.unwrap(),
+            last_booking_time: stored_last_booking_time.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceRecurringBookingFunctions for DbHandlerSqlite {
+    async fn finance_recurring_booking_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceRecurringBooking>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceRecurringBookings WHERE user_id = ?1 ORDER BY rowid",
+            [user_id.to_string()],
+            DbHandlerSqlite::read_recurring_booking_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} recurring bookings",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_recurring_booking_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result = connection.execute(
+            "INSERT INTO FinanceRecurringBookings (recurring_booking_id, user_id, debit_finance_account_id,
+            credit_finance_account_id, amount, title, description, schedule, start_time, end_time, last_booking_time)
+            VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11)
+            ON CONFLICT (user_id, recurring_booking_id) DO UPDATE SET
+            debit_finance_account_id = excluded.debit_finance_account_id,
+            credit_finance_account_id = excluded.credit_finance_account_id, amount = excluded.amount,
+            title = excluded.title, description = excluded.description, schedule = excluded.schedule,
+            start_time = excluded.start_time, end_time = excluded.end_time,
+            last_booking_time = excluded.last_booking_time",
+            params![
+                recurring_booking.id.to_string(),
+                user_id.to_string(),
+                recurring_booking
+                    .booking_template
+                    .debit_finance_account_id
+                    .to_string(),
+                recurring_booking
+                    .booking_template
+                    .credit_finance_account_id
+                    .to_string(),
+                recurring_booking.booking_template.amount as i64,
+                &recurring_booking.booking_template.title,
+                &recurring_booking.booking_template.description,
+                recurring_booking.schedule.to_int(),
+                DbHandlerSqlite::format_datetime(&recurring_booking.start_time),
+                DbHandlerSqlite::format_optional_datetime(&recurring_booking.end_time),
+                DbHandlerSqlite::format_optional_datetime(&recurring_booking.last_booking_time),
+            ],
+        );
+        if upsert_result.is_err() {
+            let upsert_err = upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","upserted recurring booking for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_set_last_booking_time(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+        last_booking_time: &DateTime<Utc>,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        // the stored format sorts like the time values, so the text comparison keeps the time from moving backwards
+        let update_result = connection.execute(
+            "UPDATE FinanceRecurringBookings SET last_booking_time = ?3
+            WHERE user_id = ?1 AND recurring_booking_id = ?2
+            AND (last_booking_time IS NULL OR last_booking_time < ?3)",
+            [
+                user_id.to_string(),
+                recurring_booking_id.to_string(),
+                DbHandlerSqlite::format_datetime(last_booking_time),
+            ],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","updated last booking time of recurring booking {} for user id {}",&recurring_booking_id,&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let delete_result = connection.execute(
+            "DELETE FROM FinanceRecurringBookings WHERE user_id = ?1 AND recurring_booking_id = ?2",
+            [user_id.to_string(), recurring_booking_id.to_string()],
+        );
+        if delete_result.is_err() {
+            let delete_err = delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!(
+                "recurring booking {} not found",
+                recurring_booking_id
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_list_user_ids(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Vec<Uuid>, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        return DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT DISTINCT user_id FROM FinanceRecurringBookings",
+            [],
+            |row| DbHandlerSqlite::get_uuid_from_row(row, "user_id"),
+        );
+    }
+}
+
+impl DbHandlerPostgres {
+    fn read_recurring_booking_row(row: &PostgresRow) -> Result<FinanceRecurringBooking, String> {
+        let stored_recurring_booking_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "recurring_booking_id");
+        if stored_recurring_booking_id.is_err() {
+            return Err(stored_recurring_booking_id.unwrap_err());
+        }
+        let stored_debit_account_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "debit_finance_account_id");
+        if stored_debit_account_id.is_err() {
+            return Err(stored_debit_account_id.unwrap_err());
+        }
+        let stored_credit_account_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "credit_finance_account_id");
+        if stored_credit_account_id.is_err() {
+            return Err(stored_credit_account_id.unwrap_err());
+        }
+        let stored_amount = row.try_get::<_, i64>("amount");
+        if stored_amount.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_amount.unwrap_err()));
+        }
+        let stored_title = row.try_get::<_, String>("title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+        let stored_description = row.try_get::<_, String>("description");
+        if stored_description.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_description.unwrap_err(),
+            ));
+        }
+        let stored_schedule_value = row.try_get::<_, i32>("schedule");
+        if stored_schedule_value.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_schedule_value.unwrap_err(),
+            ));
+        }
+        let stored_schedule =
+            RecurringBookingSchedule::get_from_int(stored_schedule_value.unwrap());
+        if stored_schedule.is_err() {
+            return Err(stored_schedule.unwrap_err());
+        }
+        let stored_start_time = row.try_get::<_, DateTime<Utc>>("start_time");
+        if stored_start_time.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_start_time.unwrap_err(),
+            ));
+        }
+        let stored_end_time = row.try_get::<_, Option<DateTime<Utc>>>("end_time");
+        if stored_end_time.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_end_time.unwrap_err(),
+            ));
+        }
+        let stored_last_booking_time = row.try_get::<_, Option<DateTime<Utc>>>("last_booking_time");
+        if stored_last_booking_time.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_last_booking_time.unwrap_err(),
+            ));
+        }
+
+        let start_time = stored_start_time.unwrap();
+        return Ok(FinanceRecurringBooking {
+            id: stored_recurring_booking_id.unwrap(),
+            booking_template: FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: stored_debit_account_id.unwrap(),
+                credit_finance_account_id: stored_credit_account_id.unwrap(),
+                booking_time: start_time,
+                amount: stored_amount.unwrap() as u64,
+                title: stored_title.unwrap(),
+                description: stored_description.unwrap(),
+                tags: Vec::new(),
+                import_reference: None,
+            },
+            schedule: stored_schedule.unwrap(),
+            start_time,
+            end_time: stored_end_time.unwrap(),
+            last_booking_time: stored_last_booking_time.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceRecurringBookingFunctions for DbHandlerPostgres {
+    async fn finance_recurring_booking_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceRecurringBooking>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceRecurringBookings WHERE user_id = $1 ORDER BY insert_order",
+            &[&user_id.to_uuid_1()],
+            DbHandlerPostgres::read_recurring_booking_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} recurring bookings",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_recurring_booking_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result = client
+            .execute(
+                "INSERT INTO FinanceRecurringBookings (recurring_booking_id, user_id, debit_finance_account_id,
+                credit_finance_account_id, amount, title, description, schedule, start_time, end_time, last_booking_time)
+                VALUES ($1, $2, $3, $4, $5, $6, $7, $8, $9, $10, $11)
+                ON CONFLICT (user_id, recurring_booking_id) DO UPDATE SET
+                debit_finance_account_id = excluded.debit_finance_account_id,
+                credit_finance_account_id = excluded.credit_finance_account_id, amount = excluded.amount,
+                title = excluded.title, description = excluded.description, schedule = excluded.schedule,
+                start_time = excluded.start_time, end_time = excluded.end_time,
+                last_booking_time = excluded.last_booking_time",
+                &[
+                    &recurring_booking.id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &recurring_booking
+                        .booking_template
+                        .debit_finance_account_id
+                        .to_uuid_1(),
+                    &recurring_booking
+                        .booking_template
+                        .credit_finance_account_id
+                        .to_uuid_1(),
+                    &(recurring_booking.booking_template.amount as i64),
+                    &recurring_booking.booking_template.title,
+                    &recurring_booking.booking_template.description,
+                    &recurring_booking.schedule.to_int(),
+                    &recurring_booking.start_time,
+                    &recurring_booking.end_time,
+                    &recurring_booking.last_booking_time,
+                ],
+            )
+            .await;
+        if upsert_result.is_err() {
+            let upsert_err = DbHandlerPostgres::format_error(&upsert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","upserted recurring booking for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_set_last_booking_time(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+        last_booking_time: &DateTime<Utc>,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let update_result = client
+            .execute(
+                "UPDATE FinanceRecurringBookings SET last_booking_time = $3
+                WHERE user_id = $1 AND recurring_booking_id = $2
+                AND (last_booking_time IS NULL OR last_booking_time < $3)",
+                &[
+                    &user_id.to_uuid_1(),
+                    &recurring_booking_id.to_uuid_1(),
+                    last_booking_time,
+                ],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err);
+        }
+
+        debug!(target:"app::FinanceOverView","updated last booking time of recurring booking {} for user id {}",&recurring_booking_id,&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let delete_result = client
+            .execute(
+                "DELETE FROM FinanceRecurringBookings WHERE user_id = $1 AND recurring_booking_id = $2",
+                &[&user_id.to_uuid_1(), &recurring_booking_id.to_uuid_1()],
+            )
+            .await;
+        if delete_result.is_err() {
+            let delete_err = DbHandlerPostgres::format_error(&delete_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err);
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!(
+                "recurring booking {} not found",
+                recurring_booking_id
+            ));
+        }
+
+        Ok(())
+    }
+
+    async fn finance_recurring_booking_list_user_ids(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Vec<Uuid>, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        return DbHandlerPostgres::query_list(
+            &client,
+            "SELECT DISTINCT user_id FROM FinanceRecurringBookings",
+            &[],
+            |row| DbHandlerPostgres::get_uuid_from_row(row, "user_id"),
+        )
+        .await;
+    }
+}
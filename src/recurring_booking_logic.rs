@@ -0,0 +1,439 @@
+use async_session::chrono::{DateTime, Datelike, Duration, NaiveDate, TimeZone, Utc};
+use futures::executor;
+use log::{debug, warn};
+use mongodb::bson::Uuid;
+
+use crate::{
+    accounting_config_logic::FinanceAccountingConfigHandle,
+    accounting_logic::FinanceBookingHandle,
+    database_handler_mongodb::DbConnectionSetting,
+    datatypes::{
+        FinanceBookingRequest, FinanceBookingResult, FinanceRecurringBooking,
+        FinanceRecurringBookingRunResult, RecurringBookingSchedule,
+    },
+    recurring_booking_database::DBFinanceRecurringBookingFunctions,
+};
+
+pub struct FinanceRecurringBookingHandle<'a> {
+    db_connection_settings: &'a DbConnectionSetting,
+    user_id: &'a Uuid,
+    db_connector: &'a dyn DBFinanceRecurringBookingFunctions,
+}
+
+impl<'a> FinanceRecurringBookingHandle<'a> {
+    pub fn new(
+        connection_settings: &'a DbConnectionSetting,
+        user_id: &'a Uuid,
+        db_connector: &'a dyn DBFinanceRecurringBookingFunctions,
+    ) -> Self {
+        Self {
+            db_connection_settings: connection_settings,
+            user_id,
+            db_connector,
+        }
+    }
+
+    pub async fn recurring_booking_list(&self) -> Result<Vec<FinanceRecurringBooking>, String> {
+        let temp_var_0 = self
+            .db_connector
+            .finance_recurring_booking_list(&self.db_connection_settings, &self.user_id);
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn recurring_booking_list_sync(&self) -> Result<Vec<FinanceRecurringBooking>, String> {
+        let temp_var_0 = executor::block_on(self.recurring_booking_list());
+        return temp_var_0;
+    }
+
+    pub async fn recurring_booking_upsert(
+        &self,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String> {
+        let booking_template = &recurring_booking.booking_template;
+        if booking_template.amount == 0 {
+            return Err("could not upsert recurring booking because amount is zero".into());
+        }
+        if booking_template
+            .debit_finance_account_id
+            .eq(&booking_template.credit_finance_account_id)
+        {
+            return Err(
+                "could not upsert recurring booking because debit and credit account are the same"
+                    .into(),
+            );
+        }
+        if recurring_booking.end_time.is_some()
+            && recurring_booking
+                .end_time
+                .unwrap()
+                .lt(&recurring_booking.start_time)
+        {
+            return Err("could not upsert recurring booking because end is before start".into());
+        }
+
+        let config_handle = FinanceAccountingConfigHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let account_list_result = config_handle
+            .finance_account_list_async(Some(&vec![
+                booking_template.debit_finance_account_id,
+                booking_template.credit_finance_account_id,
+            ]))
+            .await;
+        if account_list_result.is_err() {
+            return Err(format!(
+                "Error getting account information: {}",
+                account_list_result.unwrap_err()
+            ));
+        }
+        if account_list_result.unwrap().len() != 2 {
+            return Err("could not upsert recurring booking because an account is unknown".into());
+        }
+
+        let temp_var_0 = self.db_connector.finance_recurring_booking_upsert(
+            &self.db_connection_settings,
+            &self.user_id,
+            recurring_booking,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn recurring_booking_upsert_sync(
+        &self,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.recurring_booking_upsert(recurring_booking));
+        return temp_var_0;
+    }
+
+    pub async fn recurring_booking_delete(
+        &self,
+        recurring_booking_id: &Uuid,
+    ) -> Result<(), String> {
+        let temp_var_0 = self.db_connector.finance_recurring_booking_delete(
+            &self.db_connection_settings,
+            &self.user_id,
+            recurring_booking_id,
+        );
+        let temp_var_1 = temp_var_0.await;
+        return temp_var_1;
+    }
+
+    pub fn recurring_booking_delete_sync(&self, recurring_booking_id: &Uuid) -> Result<(), String> {
+        let temp_var_0 = executor::block_on(self.recurring_booking_delete(recurring_booking_id));
+        return temp_var_0;
+    }
+
+    /// booking time of the occurrence with the given index (0 is the first occurrence)
+    pub fn get_occurrence_time(
+        recurring_booking: &FinanceRecurringBooking,
+        occurrence_index: u32,
+    ) -> Option<DateTime<Utc>> {
+        let start_time = recurring_booking.start_time;
+        let start_date = start_time.date_naive();
+        let month_offset = match recurring_booking.schedule {
+            RecurringBookingSchedule::Weekly => {
+                return Some(start_time + Duration::weeks(occurrence_index as i64));
+            }
+            RecurringBookingSchedule::Monthly | RecurringBookingSchedule::EndOfMonth => {
+                occurrence_index as i32
+            }
+            RecurringBookingSchedule::Yearly => (occurrence_index as i32) * 12,
+        };
+
+        let month_count = start_date.year() * 12 + start_date.month0() as i32 + month_offset;
+        let occurrence_year = month_count.div_euclid(12);
+        let occurrence_month = (month_count.rem_euclid(12) + 1) as u32;
+        let last_day_of_month = get_last_day_of_month(occurrence_year, occurrence_month)?;
+        let occurrence_day = if recurring_booking.schedule == RecurringBookingSchedule::EndOfMonth {
+            last_day_of_month
+        } else {
+            start_date.day().min(last_day_of_month)
+        };
+
+        let occurrence_date =
+            NaiveDate::from_ymd_opt(occurrence_year, occurrence_month, occurrence_day)?;
+        return Some(Utc.from_utc_datetime(&occurrence_date.and_time(start_time.time())));
+    }
+
+    /// booking times of all occurrences up to booking_time_till which are not booked yet
+    pub fn get_due_occurrence_times(
+        recurring_booking: &FinanceRecurringBooking,
+        booking_time_till: DateTime<Utc>,
+    ) -> Vec<DateTime<Utc>> {
+        let mut return_object = Vec::new();
+        let mut occurrence_index = 0;
+        while let Some(occurrence_time) =
+            FinanceRecurringBookingHandle::get_occurrence_time(recurring_booking, occurrence_index)
+        {
+            if occurrence_time.gt(&booking_time_till)
+                || (recurring_booking.end_time.is_some()
+                    && occurrence_time.gt(&recurring_booking.end_time.unwrap()))
+            {
+                break;
+            }
+            if recurring_booking.last_booking_time.is_none()
+                || occurrence_time.gt(&recurring_booking.last_booking_time.unwrap())
+            {
+                return_object.push(occurrence_time);
+            }
+            occurrence_index += 1;
+        }
+        return return_object;
+    }
+
+    /// import reference of the journal entry of an occurrence, identifies the occurrence in the journal
+    pub fn get_occurrence_import_reference(
+        recurring_booking: &FinanceRecurringBooking,
+        occurrence_time: DateTime<Utc>,
+    ) -> String {
+        return format!(
+            "RECURRING:{}|{}",
+            recurring_booking.id,
+            occurrence_time.to_rfc3339()
+        );
+    }
+
+    /// books all due occurrences of the recurring bookings of the user, runs may overlap (background task,
+    /// requests, several server processes) because the database accepts an import reference only once,
+    /// an error of one recurring booking is collected and the other recurring bookings are booked anyway
+    pub async fn book_due_occurrences(
+        &self,
+        booking_time_till: DateTime<Utc>,
+    ) -> Result<FinanceRecurringBookingRunResult, String> {
+        let recurring_booking_list_result = self.recurring_booking_list().await;
+        if recurring_booking_list_result.is_err() {
+            return Err(format!(
+                "Error getting recurring bookings: {}",
+                recurring_booking_list_result.unwrap_err()
+            ));
+        }
+
+        let mut return_object = FinanceRecurringBookingRunResult {
+            booking_results: Vec::new(),
+            errors: Vec::new(),
+        };
+        for recurring_booking in recurring_booking_list_result.unwrap() {
+            let booking_result = self
+                .book_due_occurrences_of_recurring_booking(
+                    &recurring_booking,
+                    booking_time_till,
+                    &mut return_object.booking_results,
+                )
+                .await;
+            if booking_result.is_err() {
+                let booking_error = format!(
+                    "recurring booking {} ({}): {}",
+                    recurring_booking.id,
+                    recurring_booking.booking_template.title,
+                    booking_result.unwrap_err()
+                );
+                warn!(target:"app::FinanceOverView","user {}: {}",self.user_id,booking_error);
+                return_object.errors.push(booking_error);
+            }
+        }
+
+        return Ok(return_object);
+    }
+
+    /// books the due occurrences of one recurring booking and adds the new entries to booking_results,
+    /// the progress is stored after every occurrence and an occurrence which already has a journal entry
+    /// with its import reference is not booked again, so an interrupted run can be repeated without duplicate postings
+    async fn book_due_occurrences_of_recurring_booking(
+        &self,
+        recurring_booking: &FinanceRecurringBooking,
+        booking_time_till: DateTime<Utc>,
+        booking_results: &mut Vec<FinanceBookingResult>,
+    ) -> Result<(), String> {
+        let due_occurrence_times = FinanceRecurringBookingHandle::get_due_occurrence_times(
+            recurring_booking,
+            booking_time_till,
+        );
+        if due_occurrence_times.is_empty() {
+            return Ok(());
+        }
+
+        let booking_handle = FinanceBookingHandle::new(
+            &self.db_connection_settings,
+            &self.user_id,
+            self.db_connector,
+        );
+        let booking_template = recurring_booking.booking_template.clone();
+        let saldo_information_result = booking_handle
+            .finance_get_last_saldo_account_entries(Some(vec![
+                booking_template.debit_finance_account_id,
+                booking_template.credit_finance_account_id,
+            ]))
+            .await;
+        if saldo_information_result.is_err() {
+            return Err(format!(
+                "Error getting saldo information: {}",
+                saldo_information_result.unwrap_err()
+            ));
+        }
+        let last_saldo_time = saldo_information_result
+            .unwrap()
+            .values()
+            .map(|elem| elem.booking_time)
+            .max();
+
+        for occurrence_time in due_occurrence_times {
+            if last_saldo_time.is_some() && occurrence_time.le(&last_saldo_time.unwrap()) {
+                warn!(target:"app::FinanceOverView","recurring booking {} of user {}: occurrence at {} is in a closed period and is skipped",recurring_booking.id,self.user_id,occurrence_time);
+            } else {
+                let import_reference =
+                    FinanceRecurringBookingHandle::get_occurrence_import_reference(
+                        recurring_booking,
+                        occurrence_time,
+                    );
+                let existing_journal_entries_result = booking_handle
+                    .list_journal_entries(Some(occurrence_time), Some(occurrence_time))
+                    .await;
+                if existing_journal_entries_result.is_err() {
+                    return Err(format!(
+                        "Error checking already existing entries: {}",
+                        existing_journal_entries_result.unwrap_err()
+                    ));
+                }
+                // entries booked before the import reference was stored are recognized by their content
+                let already_booked = existing_journal_entries_result.unwrap().iter().any(|elem| {
+                    if elem.import_reference.is_some() {
+                        return elem.import_reference.eq(&Some(import_reference.clone()));
+                    }
+                    !elem.is_saldo
+                        && elem
+                            .debit_finance_account_id
+                            .eq(&booking_template.debit_finance_account_id)
+                        && elem
+                            .credit_finance_account_id
+                            .eq(&booking_template.credit_finance_account_id)
+                        && elem.amount == booking_template.amount
+                        && elem.title.eq(&booking_template.title)
+                });
+
+                if already_booked {
+                    debug!(target:"app::FinanceOverView","recurring booking {} of user {}: occurrence at {} already booked",recurring_booking.id,self.user_id,occurrence_time);
+                } else {
+                    let booking_request = FinanceBookingRequest {
+                        is_simple_entry: true,
+                        is_saldo: false,
+                        booking_time: occurrence_time,
+                        import_reference: Some(import_reference.clone()),
+                        ..booking_template.clone()
+                    };
+                    let insert_result = booking_handle
+                        .finance_insert_booking_entry(&booking_request)
+                        .await;
+                    if insert_result.is_err() {
+                        let insert_err = insert_result.unwrap_err();
+                        // the database rejects a second entry with the same import reference, so a run
+                        // in parallel (e.g. another server process) may have booked the occurrence meanwhile
+                        let booked_meanwhile_result = booking_handle
+                            .list_journal_entries(Some(occurrence_time), Some(occurrence_time))
+                            .await;
+                        if booked_meanwhile_result.is_err()
+                            || !booked_meanwhile_result.unwrap().iter().any(|elem| {
+                                elem.import_reference.eq(&Some(import_reference.clone()))
+                            })
+                        {
+                            return Err(format!(
+                                "Error booking occurrence at {}: {}",
+                                occurrence_time, insert_err
+                            ));
+                        }
+                        debug!(target:"app::FinanceOverView","recurring booking {} of user {}: occurrence at {} booked by a parallel run",recurring_booking.id,self.user_id,occurrence_time);
+                    } else {
+                        booking_results.push(insert_result.unwrap());
+                    }
+                }
+            }
+
+            let update_result = self
+                .db_connector
+                .finance_recurring_booking_set_last_booking_time(
+                    &self.db_connection_settings,
+                    &self.user_id,
+                    &recurring_booking.id,
+                    &occurrence_time,
+                )
+                .await;
+            if update_result.is_err() {
+                return Err(format!(
+                    "Error updating progress: {}",
+                    update_result.unwrap_err()
+                ));
+            }
+        }
+
+        return Ok(());
+    }
+
+    pub fn book_due_occurrences_sync(
+        &self,
+        booking_time_till: DateTime<Utc>,
+    ) -> Result<FinanceRecurringBookingRunResult, String> {
+        let temp_var_0 = executor::block_on(self.book_due_occurrences(booking_time_till));
+        return temp_var_0;
+    }
+}
+
+/// books the due occurrences of all users, used by the background task,
+/// returns the number of new booking entries
+pub async fn book_due_recurring_bookings_of_all_users(
+    connection_settings: &DbConnectionSetting,
+    db_connector: &dyn DBFinanceRecurringBookingFunctions,
+    booking_time_till: DateTime<Utc>,
+) -> Result<usize, String> {
+    let user_ids_result = db_connector
+        .finance_recurring_booking_list_user_ids(connection_settings)
+        .await;
+    if user_ids_result.is_err() {
+        return Err(format!(
+            "Error getting users with recurring bookings: {}",
+            user_ids_result.unwrap_err()
+        ));
+    }
+
+    let mut booked_count = 0;
+    for user_id in user_ids_result.unwrap() {
+        let recurring_booking_handle =
+            FinanceRecurringBookingHandle::new(connection_settings, &user_id, db_connector);
+        let booking_result = recurring_booking_handle
+            .book_due_occurrences(booking_time_till)
+            .await;
+        // a problem of one user must not stop the bookings of the other users,
+        // the errors of single recurring bookings are already logged
+        if booking_result.is_err() {
+            warn!(target:"app::FinanceOverView","could not book recurring bookings of user {}: {}",user_id,booking_result.unwrap_err());
+            continue;
+        }
+        booked_count += booking_result.unwrap().booking_results.len();
+    }
+    return Ok(booked_count);
+}
+
+pub fn book_due_recurring_bookings_of_all_users_sync(
+    connection_settings: &DbConnectionSetting,
+    db_connector: &dyn DBFinanceRecurringBookingFunctions,
+    booking_time_till: DateTime<Utc>,
+) -> Result<usize, String> {
+    let temp_var_0 = executor::block_on(book_due_recurring_bookings_of_all_users(
+        connection_settings,
+        db_connector,
+        booking_time_till,
+    ));
+    return temp_var_0;
+}
+
+fn get_last_day_of_month(year: i32, month: u32) -> Option<u32> {
+    let first_day_of_next_month = if month == 12 {
+        NaiveDate::from_ymd_opt(year + 1, 1, 1)
+    } else {
+        NaiveDate::from_ymd_opt(year, month + 1, 1)
+    }?;
+    return Some(first_day_of_next_month.pred_opt()?.day());
+}
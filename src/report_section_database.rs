@@ -0,0 +1,482 @@
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, Document, Uuid},
+    options::{FindOptions, UpdateOptions},
+    Collection,
+};
+use rusqlite::{params, Row};
+use tokio_postgres::Row as PostgresRow;
+
+use crate::{
+    accounting_database::DBFinanceAccountingFunctions,
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::{AccountCategory, FinanceReportSectionMapping},
+};
+
+/// the reports are built from the booking entries, so the accounting functions are required as well
+#[async_trait(?Send)]
+pub trait DBFinanceReportSectionFunctions: DBFinanceAccountingFunctions {
+    async fn finance_report_section_mapping_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceReportSectionMapping>, String>;
+    /// an account type has at most one mapping, an existing mapping of the account type is replaced
+    async fn finance_report_section_mapping_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String>;
+    async fn finance_report_section_mapping_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String>;
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReportSectionFunctions for DbHandlerMongoDB {
+    async fn finance_report_section_mapping_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceReportSectionMapping>, String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let mapping_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_REPORT_SECTION_MAPPINGS);
+
+        //get a binary of UUID or it will not work in production
+        let search_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let filter = doc! {"user_id":search_value};
+
+        debug!(target:"app::FinanceOverView","Filter document: {}",&filter);
+        let projection = doc! {"finance_account_type_id":<i32>::from(1),
+        "section_category":<i32>::from(1),
+        "section_title":<i32>::from(1),};
+        let options = FindOptions::builder().projection(projection).build();
+
+        let query_execute_result = mapping_collection.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut result_list = Vec::new();
+
+        while let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+
+            let stored_account_type_id =
+                ConvertTools::get_uuid_from_document(&inner_doc, "finance_account_type_id");
+            if stored_account_type_id.is_err() {
+                return Err(stored_account_type_id.unwrap_err());
+            }
+            let stored_category_value = inner_doc.get_i32("section_category");
+            if stored_category_value.is_err() {
+                return Err(stored_category_value.unwrap_err().to_string());
+            }
+            let stored_category = AccountCategory::get_from_int(stored_category_value.unwrap());
+            if stored_category.is_err() {
+                return Err(stored_category.unwrap_err());
+            }
+            let stored_title = inner_doc.get_str("section_title");
+            if stored_title.is_err() {
+                return Err(stored_title.unwrap_err().to_string());
+            }
+
+            let report_section_mapping = FinanceReportSectionMapping {
+                finance_account_type_id: stored_account_type_id.unwrap(),
+                section_category: stored_category.unwrap(),
+                section_title: stored_title.unwrap().into(),
+            };
+
+            result_list.push(report_section_mapping);
+        }
+
+        debug!(target:"app::FinanceOverView","returned {} report section mappings",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_report_section_mapping_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let mapping_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_REPORT_SECTION_MAPPINGS);
+
+        let user_id_value = mongodb::bson::Binary::from_uuid(user_id.clone());
+        let account_type_id_value =
+            mongodb::bson::Binary::from_uuid(report_section_mapping.finance_account_type_id);
+        let filter = doc! {"finance_account_type_id":account_type_id_value.clone(),"user_id":user_id_value.clone()};
+        let inner_doc = doc! {
+            "finance_account_type_id":account_type_id_value,
+            "user_id": user_id_value,
+            "section_category":report_section_mapping.section_category.to_int(),
+            "section_title":&report_section_mapping.section_title,
+        };
+        let upsert_doc = doc! {"$set": inner_doc  };
+        let opts = UpdateOptions::builder().upsert(true).build();
+
+        let upsert_result = mapping_collection
+            .update_one(filter, upsert_doc, opts)
+            .await;
+        if upsert_result.is_err() {
+            let upsert_err = &upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+        let upsert_info = upsert_result.unwrap();
+
+        if (upsert_info.matched_count > 1) || (upsert_info.modified_count > 1) {
+            return Err(format!(
+                "Error upserting element, matched count was {}, changed count was {}",
+                upsert_info.matched_count, upsert_info.modified_count
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","upserted report section mapping for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_report_section_mapping_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String> {
+        // Get a handle to the deployment.
+        let client_create_result = self.get_internal_db_client();
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let mapping_collection: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_REPORT_SECTION_MAPPINGS);
+
+        let filter = doc! {"finance_account_type_id":mongodb::bson::Binary::from_uuid(finance_account_type_id.clone()),
+        "user_id":mongodb::bson::Binary::from_uuid(user_id.clone())};
+
+        let delete_result = mapping_collection.delete_one(filter, None).await;
+        if delete_result.is_err() {
+            let delete_err = &delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap().deleted_count != 1 {
+            return Err(format!(
+                "report section mapping of account type {} not found",
+                finance_account_type_id
+            ));
+        }
+
+        debug!(target:"app::FinanceOverView","deleted report section mapping for user id {}",&user_id);
+
+        Ok(())
+    }
+}
+
+impl DbHandlerSqlite {
+    fn read_report_section_mapping_row(row: &Row) -> Result<FinanceReportSectionMapping, String> {
+        let stored_account_type_id =
+            DbHandlerSqlite::get_uuid_from_row(row, "finance_account_type_id");
+        if stored_account_type_id.is_err() {
+            return Err(stored_account_type_id.unwrap_err());
+        }
+        let stored_category_value = row.get::<_, i32>("section_category");
+        if stored_category_value.is_err() {
+            return Err(stored_category_value.unwrap_err().to_string());
+        }
+        let stored_category = AccountCategory::get_from_int(stored_category_value.unwrap());
+        if stored_category.is_err() {
+            return Err(stored_category.unwrap_err());
+        }
+        let stored_title = row.get::<_, String>("section_title");
+        if stored_title.is_err() {
+            return Err(stored_title.unwrap_err().to_string());
+        }
+
+        return Ok(FinanceReportSectionMapping {
+            finance_account_type_id: stored_account_type_id.unwrap(),
+            section_category: stored_category.unwrap(),
+            section_title: stored_title.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReportSectionFunctions for DbHandlerSqlite {
+    async fn finance_report_section_mapping_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceReportSectionMapping>, String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = DbHandlerSqlite::query_list(
+            &connection,
+            "SELECT * FROM FinanceReportSectionMappings WHERE user_id = ?1 ORDER BY rowid",
+            [user_id.to_string()],
+            DbHandlerSqlite::read_report_section_mapping_row,
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} report section mappings",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_report_section_mapping_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let upsert_result = connection.execute(
+            "INSERT INTO FinanceReportSectionMappings (finance_account_type_id, user_id, section_category, section_title)
+            VALUES (?1, ?2, ?3, ?4)
+            ON CONFLICT (user_id, finance_account_type_id) DO UPDATE SET
+            section_category = excluded.section_category, section_title = excluded.section_title",
+            params![
+                report_section_mapping.finance_account_type_id.to_string(),
+                user_id.to_string(),
+                report_section_mapping.section_category.to_int(),
+                &report_section_mapping.section_title,
+            ],
+        );
+        if upsert_result.is_err() {
+            let upsert_err = upsert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","upserted report section mapping for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_report_section_mapping_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String> {
+        let connection_result =
+            DbHandlerSqlite::open_connection_for_user(conncetion_settings, user_id);
+        if connection_result.is_err() {
+            return Err(connection_result.unwrap_err());
+        }
+        let connection = connection_result.unwrap();
+
+        let delete_result = connection.execute(
+            "DELETE FROM FinanceReportSectionMappings WHERE user_id = ?1 AND finance_account_type_id = ?2",
+            [user_id.to_string(), finance_account_type_id.to_string()],
+        );
+        if delete_result.is_err() {
+            let delete_err = delete_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err.to_string());
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!(
+                "report section mapping of account type {} not found",
+                finance_account_type_id
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl DbHandlerPostgres {
+    fn read_report_section_mapping_row(
+        row: &PostgresRow,
+    ) -> Result<FinanceReportSectionMapping, String> {
+        let stored_account_type_id =
+            DbHandlerPostgres::get_uuid_from_row(row, "finance_account_type_id");
+        if stored_account_type_id.is_err() {
+            return Err(stored_account_type_id.unwrap_err());
+        }
+        let stored_category_value = row.try_get::<_, i32>("section_category");
+        if stored_category_value.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_category_value.unwrap_err(),
+            ));
+        }
+        let stored_category = AccountCategory::get_from_int(stored_category_value.unwrap());
+        if stored_category.is_err() {
+            return Err(stored_category.unwrap_err());
+        }
+        let stored_title = row.try_get::<_, String>("section_title");
+        if stored_title.is_err() {
+            return Err(DbHandlerPostgres::format_error(&stored_title.unwrap_err()));
+        }
+
+        return Ok(FinanceReportSectionMapping {
+            finance_account_type_id: stored_account_type_id.unwrap(),
+            section_category: stored_category.unwrap(),
+            section_title: stored_title.unwrap(),
+        });
+    }
+}
+
+#[async_trait(?Send)]
+impl DBFinanceReportSectionFunctions for DbHandlerPostgres {
+    async fn finance_report_section_mapping_list(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceReportSectionMapping>, String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let query_result = DbHandlerPostgres::query_list(
+            &client,
+            "SELECT * FROM FinanceReportSectionMappings WHERE user_id = $1 ORDER BY insert_order",
+            &[&user_id.to_uuid_1()],
+            DbHandlerPostgres::read_report_section_mapping_row,
+        )
+        .await;
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err());
+        }
+        let result_list = query_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","returned {} report section mappings",result_list.len());
+
+        return Ok(result_list);
+    }
+
+    async fn finance_report_section_mapping_upsert(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let upsert_result = client
+            .execute(
+                "INSERT INTO FinanceReportSectionMappings (finance_account_type_id, user_id, section_category, section_title)
+                VALUES ($1, $2, $3, $4)
+                ON CONFLICT (user_id, finance_account_type_id) DO UPDATE SET
+                section_category = excluded.section_category, section_title = excluded.section_title",
+                &[
+                    &report_section_mapping.finance_account_type_id.to_uuid_1(),
+                    &user_id.to_uuid_1(),
+                    &report_section_mapping.section_category.to_int(),
+                    &report_section_mapping.section_title,
+                ],
+            )
+            .await;
+        if upsert_result.is_err() {
+            let upsert_err = DbHandlerPostgres::format_error(&upsert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",upsert_err);
+            return Err(upsert_err);
+        }
+
+        debug!(target:"app::FinanceOverView","upserted report section mapping for user id {}",&user_id);
+
+        Ok(())
+    }
+
+    async fn finance_report_section_mapping_delete(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String> {
+        let client_result =
+            DbHandlerPostgres::open_client_for_user(conncetion_settings, user_id).await;
+        if client_result.is_err() {
+            return Err(client_result.unwrap_err());
+        }
+        let client = client_result.unwrap();
+
+        let delete_result = client
+            .execute(
+                "DELETE FROM FinanceReportSectionMappings WHERE user_id = $1 AND finance_account_type_id = $2",
+                &[&user_id.to_uuid_1(), &finance_account_type_id.to_uuid_1()],
+            )
+            .await;
+        if delete_result.is_err() {
+            let delete_err = DbHandlerPostgres::format_error(&delete_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",delete_err);
+            return Err(delete_err);
+        }
+        if delete_result.unwrap() == 0 {
+            return Err(format!(
+                "report section mapping of account type {} not found",
+                finance_account_type_id
+            ));
+        }
+
+        Ok(())
+    }
+}
@@ -14,11 +14,57 @@ use log::trace;
 use mongodb::bson::Uuid;
 use serde::{Deserialize, Serialize};
 
+use crate::database_handler_postgres::PostgresSessionStore;
+use crate::database_handler_sqlite::SqliteSessionStore;
+
 const AXUM_SESSION_COOKIE_NAME: &str = "axum_session";
+
+/// session store of the backend chosen in the server settings
+#[derive(Debug, Clone)]
+pub enum ServerSessionStore {
+    MongoDB(MongodbSessionStore),
+    SQLite(SqliteSessionStore),
+    PostgreSQL(PostgresSessionStore),
+}
+
+#[async_trait]
+impl SessionStore for ServerSessionStore {
+    async fn load_session(&self, cookie_value: String) -> async_session::Result<Option<Session>> {
+        match self {
+            ServerSessionStore::MongoDB(store) => store.load_session(cookie_value).await,
+            ServerSessionStore::SQLite(store) => store.load_session(cookie_value).await,
+            ServerSessionStore::PostgreSQL(store) => store.load_session(cookie_value).await,
+        }
+    }
+
+    async fn store_session(&self, session: Session) -> async_session::Result<Option<String>> {
+        match self {
+            ServerSessionStore::MongoDB(store) => store.store_session(session).await,
+            ServerSessionStore::SQLite(store) => store.store_session(session).await,
+            ServerSessionStore::PostgreSQL(store) => store.store_session(session).await,
+        }
+    }
+
+    async fn destroy_session(&self, session: Session) -> async_session::Result {
+        match self {
+            ServerSessionStore::MongoDB(store) => store.destroy_session(session).await,
+            ServerSessionStore::SQLite(store) => store.destroy_session(session).await,
+            ServerSessionStore::PostgreSQL(store) => store.destroy_session(session).await,
+        }
+    }
+
+    async fn clear_store(&self) -> async_session::Result {
+        match self {
+            ServerSessionStore::MongoDB(store) => store.clear_store().await,
+            ServerSessionStore::SQLite(store) => store.clear_store().await,
+            ServerSessionStore::PostgreSQL(store) => store.clear_store().await,
+        }
+    }
+}
 pub struct SessionData {
     pub session_user_id: UserId,
     pub session_option: Option<Session>,
-    pub session_store: MongodbSessionStore,
+    pub session_store: ServerSessionStore,
 }
 
 impl SessionData {
@@ -41,7 +87,7 @@ impl SessionData {
 pub struct FreshSessionData {
     pub session_user_id: UserId,
     pub session_option: Option<Session>,
-    pub session_store: MongodbSessionStore,
+    pub session_store: ServerSessionStore,
     pub cookie: HeaderValue,
 }
 
@@ -58,9 +104,9 @@ where
     type Rejection = (StatusCode, HeaderMap, &'static str);
 
     async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
-        let Extension(store) = Extension::<MongodbSessionStore>::from_request_parts(parts, state)
+        let Extension(store) = Extension::<ServerSessionStore>::from_request_parts(parts, state)
             .await
-            .expect("`MongoDBSessionStore` extension missing");
+            .expect("`ServerSessionStore` extension missing");
 
         let cookie = parts.extract::<TypedHeader<Cookie>>().await.unwrap();
         let session_cookie = cookie.get(AXUM_SESSION_COOKIE_NAME);
@@ -153,7 +199,7 @@ impl UserId {
     }
 }
 
-async fn create_new_session(store: MongodbSessionStore) -> FreshSessionData {
+async fn create_new_session(store: ServerSessionStore) -> FreshSessionData {
     let session_user_id = UserId::new();
     let mut session = Session::new();
     session.insert("session_user_id", session_user_id).unwrap();
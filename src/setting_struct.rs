@@ -13,6 +13,8 @@ pub struct SettingStruct {
     pub web_server_port_https: u16,
     pub web_server_cert_cert_path: String,
     pub web_server_cert_key_path: String,
+    /// MongoDB, SQLite or PostgreSQL, for SQLite the url is the path of the database file
+    pub backend_database_type: String,
     pub backend_database_url: String,
     pub backend_database_user: String,
     pub backend_database_password: String,
@@ -31,6 +33,8 @@ pub struct SettingStruct {
     pub frontend_password_reset_mail_info_body_path: String,
     pub frontend_password_reset_mail_server_address: String,
     pub frontend_password_reset_token_time_limit_minutes: u16,
+    pub backend_attachment_storage_path: String,
+    pub backend_attachment_max_size_bytes: u64,
 }
 
 #[derive(Clone)]
@@ -42,6 +46,10 @@ pub struct TestSettingStruct {
     pub backend_database_user: String,
     pub backend_database_password: String,
     pub backend_database_instance: String,
+    /// server for the postgres integration tests, every test creates its own database there
+    pub postgres_database_url: String,
+    pub postgres_database_user: String,
+    pub postgres_database_password: String,
     pub test_user_account_user_login: String,
     pub test_user_account_user_password: String,
 }
@@ -73,6 +81,7 @@ impl SettingStruct {
             .set("cert_cert_path", "config/self-signed-certs/cert.pem")
             .set("cert_key_path", "config/self-signed-certs/key.pem");
         conf.with_section(Some("BackendDatabase"))
+            .set("DB_Type", "MongoDB")
             .set("DB_URL", "mongodb://localhost:27017")
             .set("DB_User", "Administrator")
             .set("DB_Password", "password")
@@ -113,6 +122,9 @@ impl SettingStruct {
             )
             .set("PasswordReset_Mail_Server_Address", "https:127.0.0.1:3300")
             .set("PasswordReset_Token_Time_Limit_Minutes", "5");
+        conf.with_section(Some("BackendAttachments"))
+            .set("Attachment_Storage_Path", "attachments")
+            .set("Attachment_Max_Size_Bytes", "10485760");
         conf.write_to_file(&settingpath).unwrap();
     }
 
@@ -148,6 +160,9 @@ impl SettingStruct {
         let _web_server_cert_key_path: String = conf
             .get_from_or(Some("WebServer"), "cert_key_path", "")
             .to_string();
+        let _db_type: String = conf
+            .get_from_or(Some("BackendDatabase"), "DB_Type", "MongoDB")
+            .to_string();
         let _db_url: String = conf
             .get_from_or(Some("BackendDatabase"), "DB_URL", "")
             .to_string();
@@ -243,6 +258,21 @@ impl SettingStruct {
             )
             .parse()
             .unwrap();
+        let _backend_attachment_storage_path: String = conf
+            .get_from_or(
+                Some("BackendAttachments"),
+                "Attachment_Storage_Path",
+                "attachments",
+            )
+            .to_string();
+        let _backend_attachment_max_size_bytes: u64 = conf
+            .get_from_or(
+                Some("BackendAttachments"),
+                "Attachment_Max_Size_Bytes",
+                "10485760",
+            )
+            .parse()
+            .unwrap();
 
         return SettingStruct {
             web_server_ip_part1: _web_server_ip_part1,
@@ -253,6 +283,7 @@ impl SettingStruct {
             web_server_port_https: _web_server_port_https,
             web_server_cert_cert_path: _web_server_cert_cert_path,
             web_server_cert_key_path: _web_server_cert_key_path,
+            backend_database_type: _db_type,
             backend_database_url: _db_url,
             backend_database_user: _db_user,
             backend_database_password: _db_password,
@@ -275,6 +306,8 @@ impl SettingStruct {
                 _frontend_password_reset_mail_server_address,
             frontend_password_reset_token_time_limit_minutes:
                 _frontend_password_reset_token_time_limit_minutes,
+            backend_attachment_storage_path: _backend_attachment_storage_path,
+            backend_attachment_max_size_bytes: _backend_attachment_max_size_bytes,
         };
     }
 }
@@ -304,6 +337,10 @@ impl TestSettingStruct {
             .set("DB_User", "Administrator")
             .set("DB_Password", "password")
             .set("DB_Instance", "StructureName");
+        conf.with_section(Some("PostgreSQL"))
+            .set("DB_URL", "postgresql://localhost:5432")
+            .set("DB_User", "postgres")
+            .set("DB_Password", "password");
         conf.with_section(Some("TestUserAccount"))
             .set("User_Login", "someUser")
             .set("User_Password", "somePassword");
@@ -336,6 +373,15 @@ impl TestSettingStruct {
         let _db_instance: String = conf
             .get_from_or(Some("BackendDatabase"), "DB_Instance", "")
             .to_string();
+        let _postgres_db_url: String = conf
+            .get_from_or(Some("PostgreSQL"), "DB_URL", "")
+            .to_string();
+        let _postgres_db_user: String = conf
+            .get_from_or(Some("PostgreSQL"), "DB_User", "")
+            .to_string();
+        let _postgres_db_password: String = conf
+            .get_from_or(Some("PostgreSQL"), "DB_Password", "")
+            .to_string();
         let _test_user_account_user_login: String = conf
             .get_from_or(Some("TestUserAccount"), "User_Login", "")
             .parse()
@@ -353,6 +399,9 @@ impl TestSettingStruct {
             backend_database_user: _db_user,
             backend_database_password: _db_password,
             backend_database_instance: _db_instance,
+            postgres_database_url: _postgres_db_url,
+            postgres_database_user: _postgres_db_user,
+            postgres_database_password: _postgres_db_password,
             test_user_account_user_login: _test_user_account_user_login,
             test_user_account_user_password: _test_user_account_user_password,
         };
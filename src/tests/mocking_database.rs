@@ -3,6 +3,8 @@ use crate::accounting_database::FinanceAccountBookingEntryListSearchOption;
 #[cfg(test)]
 use crate::database_handler_mongodb::DbConnectionSetting;
 #[cfg(test)]
+use crate::database_handler_mongodb::EmailVerificationStatus;
+#[cfg(test)]
 use crate::datatypes::BookingEntryType;
 #[cfg(test)]
 use crate::datatypes::FinanceAccount;
@@ -10,15 +12,24 @@ use crate::datatypes::FinanceAccount;
 use crate::datatypes::FinanceAccountType;
 #[cfg(test)]
 use crate::datatypes::{
-    FinanceAccountBookingEntry, FinanceBookingRequest, FinanceBookingResult, FinanceJournalEntry,
+    FinanceAccountBookingEntry, FinanceBackupArchive, FinanceBookingLeg, FinanceBookingRequest,
+    FinanceBookingResult, FinanceBookingTemplate, FinanceBudget, FinanceCategorizationRule,
+    FinanceJournalAttachment, FinanceJournalEntry, FinanceReconciliation, FinanceRecurringBooking,
+    FinanceReportSectionMapping, FinanceSplitBookingRequest, FinanceSplitBookingResult,
 };
 #[cfg(test)]
+use crate::datatypes::{GenerallUserData, PasswordResetTokenRequestResult};
+#[cfg(test)]
+use crate::password_handle::{StoredCredentials, UserCredentialsHashed};
+#[cfg(test)]
 use async_session::chrono::{DateTime, Utc};
 #[cfg(test)]
 use mongodb::bson::Uuid;
 #[cfg(test)]
 use once_cell::sync::OnceCell;
 #[cfg(test)]
+use secrecy::{ExposeSecret, Secret};
+#[cfg(test)]
 use std::collections::HashMap;
 #[cfg(test)]
 use std::sync::Mutex;
@@ -32,11 +43,33 @@ pub struct InMemoryDatabaseEntryObj {
     accounts_per_user: Vec<FinanceAccount>,
     booking_entries_per_user: Vec<FinanceAccountBookingEntry>,
     journal_entries_per_user: Vec<FinanceJournalEntry>,
+    recurring_bookings_per_user: Vec<FinanceRecurringBooking>,
+    booking_templates_per_user: Vec<FinanceBookingTemplate>,
+    budgets_per_user: Vec<FinanceBudget>,
+    report_section_mappings_per_user: Vec<FinanceReportSectionMapping>,
+    categorization_rules_per_user: Vec<FinanceCategorizationRule>,
+    reconciliations_per_user: Vec<FinanceReconciliation>,
+    journal_attachments_per_user: Vec<FinanceJournalAttachment>,
+    journal_max_number_per_user: u64,
+}
+
+#[cfg(test)]
+pub struct InMemoryUserEntryObj {
+    user_id: Uuid,
+    user_name: String,
+    password_hash: Secret<String>,
+    user_email: Option<String>,
+    mail_validated: bool,
+    mail_validation_token: Option<String>,
+    general_user_data: GenerallUserData,
+    reset_secret_hash: Option<Secret<String>>,
+    password_reset_token: Option<(String, DateTime<Utc>)>,
 }
 
 #[cfg(test)]
 pub struct InMemoryDatabaseData {
     data_per_user: Vec<InMemoryDatabaseEntryObj>,
+    users: Vec<InMemoryUserEntryObj>,
 }
 
 #[cfg(test)]
@@ -178,6 +211,51 @@ impl crate::accounting_config_database::DBFinanceConfigFunctions for InMemoryDat
     }
 }
 
+/// orders booking entries like the database backends: by booking time, then by running number
+#[cfg(test)]
+fn sort_booking_entries_by_journal(
+    booking_entries: &mut Vec<FinanceAccountBookingEntry>,
+    journal_entries: &Vec<FinanceJournalEntry>,
+) {
+    booking_entries.sort_by_key(|booking_entry| {
+        (
+            booking_entry.booking_time,
+            journal_entries
+                .iter()
+                .find(|elem| elem.id.eq(&booking_entry.finance_journal_diary_id))
+                .map(|elem| elem.running_number)
+                .unwrap_or_default(),
+        )
+    });
+}
+
+/// same rule as the unique index of the SQL backends: an import reference is booked once
+/// on the same accounts, reversed entries do not count
+#[cfg(test)]
+fn check_import_reference_not_booked(
+    journal_entries: &Vec<FinanceJournalEntry>,
+    import_reference: &Option<String>,
+    debit_finance_account_id: &Uuid,
+    credit_finance_account_id: &Uuid,
+) -> Result<(), String> {
+    if import_reference.is_none() {
+        return Ok(());
+    }
+    let is_booked = journal_entries.iter().any(|elem| {
+        elem.import_reference.eq(import_reference)
+            && elem.debit_finance_account_id.eq(debit_finance_account_id)
+            && elem.credit_finance_account_id.eq(credit_finance_account_id)
+            && elem.reversed_by_journal_entry_id.is_none()
+    });
+    if is_booked {
+        return Err(format!(
+            "import reference {} is already booked",
+            import_reference.as_ref().unwrap()
+        ));
+    }
+    return Ok(());
+}
+
 #[cfg(test)]
 #[axum::async_trait(?Send)]
 impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDatabaseHandler {
@@ -187,6 +265,7 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
         user_id: &Uuid,
         booking_time_from: Option<DateTime<Utc>>,
         booking_time_till: Option<DateTime<Utc>>,
+        tag: Option<String>,
     ) -> Result<Vec<FinanceJournalEntry>, String> {
         let data_obj = GLOBAL_IN_MEMORY_DATA.get();
         let data_obj2 = data_obj.unwrap();
@@ -209,6 +288,10 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
             if booking_time_till.is_some() {
                 return_object.retain(|elem| elem.booking_time.le(&booking_time_till.unwrap()))
             }
+            if tag.is_some() {
+                return_object.retain(|elem| elem.tags.contains(tag.as_ref().unwrap()))
+            }
+            return_object.sort_by_key(|elem| (elem.booking_time, elem.running_number));
             drop(data_obj3);
             Ok(return_object)
         } else {
@@ -266,6 +349,10 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
                             .le(&search_option.booking_time_till.unwrap())
                     })
                 }
+                sort_booking_entries_by_journal(
+                    &mut list_per_account,
+                    &user_object.journal_entries_per_user,
+                );
                 return_object.append(&mut list_per_account);
             }
             drop(data_obj3);
@@ -308,6 +395,15 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
             if check_debit_account_check_option.is_none() {
                 return Err("debit account is not available".into());
             }
+            let reference_check_result = check_import_reference_not_booked(
+                journal_entries_list,
+                &action_to_insert.import_reference,
+                &action_to_insert.debit_finance_account_id,
+                &action_to_insert.credit_finance_account_id,
+            );
+            if reference_check_result.is_err() {
+                return Err(reference_check_result.unwrap_err());
+            }
 
             let max_current_running_number_option = journal_entries_list
                 .iter()
@@ -317,12 +413,16 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
             } else {
                 0
             };
-            let new_running_number = max_current_running_number + 1;
+            // the counter record can be ahead of the journal after a restore
+            let new_running_number =
+                max_current_running_number.max(user_object.journal_max_number_per_user) + 1;
+            user_object.journal_max_number_per_user = new_running_number;
 
             let new_journal_entry = FinanceJournalEntry {
                 id: Uuid::new(),
                 is_simple_entry: action_to_insert.is_simple_entry,
                 is_saldo: action_to_insert.is_saldo,
+                is_closing: false,
                 debit_finance_account_id: action_to_insert.debit_finance_account_id,
                 credit_finance_account_id: action_to_insert.credit_finance_account_id,
                 running_number: new_running_number,
@@ -330,6 +430,10 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
                 amount: action_to_insert.amount,
                 title: action_to_insert.title.clone(),
                 description: action_to_insert.description.clone(),
+                reverse_of_journal_entry_id: None,
+                reversed_by_journal_entry_id: None,
+                tags: action_to_insert.tags.clone(),
+                import_reference: action_to_insert.import_reference.clone(),
             };
             let credit_booking_type = if action_to_insert.is_saldo {
                 BookingEntryType::SaldoCredit
@@ -345,6 +449,8 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
                 amount: action_to_insert.amount,
                 title: action_to_insert.title.clone(),
                 description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
             };
             let debit_booking_type = if action_to_insert.is_saldo {
                 BookingEntryType::SaldoDebit
@@ -360,6 +466,8 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
                 amount: action_to_insert.amount,
                 title: action_to_insert.title.clone(),
                 description: action_to_insert.description.clone(),
+                tags: action_to_insert.tags.clone(),
+                reconciliation_id: None,
             };
 
             journal_entries_list.push(new_journal_entry.clone());
@@ -379,6 +487,342 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
         }
     }
 
+    async fn finance_insert_split_booking_entry(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        action_to_insert: FinanceSplitBookingRequest,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let user_object = &mut data_obj3.data_per_user.get_mut(position).unwrap();
+            let booking_entries_list = &mut user_object.booking_entries_per_user;
+            let journal_entries_list = &mut user_object.journal_entries_per_user;
+
+            if action_to_insert.debit_legs.is_empty() || action_to_insert.credit_legs.is_empty() {
+                return Err("at least one debit and one credit leg is required".into());
+            }
+            let account_list = &user_object.accounts_per_user;
+            for debit_leg in &action_to_insert.debit_legs {
+                let check_debit_account_check_option = account_list
+                    .iter()
+                    .position(|elem| elem.id.eq(&debit_leg.finance_account_id));
+                if check_debit_account_check_option.is_none() {
+                    return Err("debit account is not available".into());
+                }
+            }
+            for credit_leg in &action_to_insert.credit_legs {
+                let check_credit_account_check_option = account_list
+                    .iter()
+                    .position(|elem| elem.id.eq(&credit_leg.finance_account_id));
+                if check_credit_account_check_option.is_none() {
+                    return Err("credit account is not available".into());
+                }
+            }
+            let journal_amount_result = action_to_insert.sum_debit_amount();
+            if journal_amount_result.is_err() {
+                return Err(journal_amount_result.unwrap_err());
+            }
+            let reference_check_result = check_import_reference_not_booked(
+                journal_entries_list,
+                &action_to_insert.import_reference,
+                &action_to_insert.debit_legs[0].finance_account_id,
+                &action_to_insert.credit_legs[0].finance_account_id,
+            );
+            if reference_check_result.is_err() {
+                return Err(reference_check_result.unwrap_err());
+            }
+
+            let max_current_running_number_option = journal_entries_list
+                .iter()
+                .max_by_key(|elem| elem.running_number);
+            let max_current_running_number = if max_current_running_number_option.is_some() {
+                max_current_running_number_option.unwrap().running_number
+            } else {
+                0
+            };
+            // the counter record can be ahead of the journal after a restore
+            let new_running_number =
+                max_current_running_number.max(user_object.journal_max_number_per_user) + 1;
+            user_object.journal_max_number_per_user = new_running_number;
+
+            let new_journal_entry = FinanceJournalEntry {
+                id: Uuid::new(),
+                is_simple_entry: action_to_insert.is_simple_entry(),
+                is_saldo: action_to_insert.is_saldo,
+                is_closing: action_to_insert.is_closing,
+                debit_finance_account_id: action_to_insert.debit_legs[0].finance_account_id,
+                credit_finance_account_id: action_to_insert.credit_legs[0].finance_account_id,
+                running_number: new_running_number,
+                booking_time: action_to_insert.booking_time,
+                amount: journal_amount_result.unwrap(),
+                title: action_to_insert.title.clone(),
+                description: action_to_insert.description.clone(),
+                reverse_of_journal_entry_id: None,
+                reversed_by_journal_entry_id: None,
+                tags: action_to_insert.tags.clone(),
+                import_reference: action_to_insert.import_reference.clone(),
+            };
+            let credit_booking_type = if action_to_insert.is_saldo {
+                BookingEntryType::SaldoCredit
+            } else {
+                BookingEntryType::Credit
+            };
+            let debit_booking_type = if action_to_insert.is_saldo {
+                BookingEntryType::SaldoDebit
+            } else {
+                BookingEntryType::Debit
+            };
+            let mut new_debit_account_entries = Vec::new();
+            for debit_leg in &action_to_insert.debit_legs {
+                new_debit_account_entries.push(FinanceAccountBookingEntry {
+                    id: Uuid::new(),
+                    finance_account_id: debit_leg.finance_account_id,
+                    finance_journal_diary_id: new_journal_entry.id.clone(),
+                    booking_type: debit_booking_type.clone(),
+                    booking_time: action_to_insert.booking_time,
+                    amount: debit_leg.amount,
+                    title: action_to_insert.title.clone(),
+                    description: action_to_insert.description.clone(),
+                    tags: action_to_insert.tags.clone(),
+                    reconciliation_id: None,
+                });
+            }
+            let mut new_credit_account_entries = Vec::new();
+            for credit_leg in &action_to_insert.credit_legs {
+                new_credit_account_entries.push(FinanceAccountBookingEntry {
+                    id: Uuid::new(),
+                    finance_account_id: credit_leg.finance_account_id,
+                    finance_journal_diary_id: new_journal_entry.id.clone(),
+                    booking_type: credit_booking_type.clone(),
+                    booking_time: action_to_insert.booking_time,
+                    amount: credit_leg.amount,
+                    title: action_to_insert.title.clone(),
+                    description: action_to_insert.description.clone(),
+                    tags: action_to_insert.tags.clone(),
+                    reconciliation_id: None,
+                });
+            }
+
+            journal_entries_list.push(new_journal_entry.clone());
+            booking_entries_list.append(&mut new_debit_account_entries.clone());
+            booking_entries_list.append(&mut new_credit_account_entries.clone());
+
+            let return_object = FinanceSplitBookingResult {
+                journal_entry: new_journal_entry,
+                debit_account_entries: new_debit_account_entries,
+                credit_account_entries: new_credit_account_entries,
+            };
+            drop(data_obj3);
+            Ok(return_object)
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_insert_split_booking_entries(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        actions_to_insert: Vec<FinanceSplitBookingRequest>,
+    ) -> Result<Vec<FinanceSplitBookingResult>, String> {
+        if actions_to_insert.is_empty() {
+            return Err("no booking entries given".into());
+        }
+
+        // check everything before inserting so that either all or no entries are written,
+        // the lock is released before the entries are inserted
+        {
+            let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+            let data_obj2 = data_obj.unwrap();
+            let data_obj3 = data_obj2.lock().unwrap();
+            let position_option = data_obj3
+                .data_per_user
+                .iter()
+                .position(|elem| elem.user_id.eq(&user_id));
+            if position_option.is_none() {
+                return Err("User not found".to_string());
+            }
+            let account_list = &data_obj3.data_per_user[position_option.unwrap()].accounts_per_user;
+            for action_to_insert in &actions_to_insert {
+                if action_to_insert.debit_legs.is_empty() || action_to_insert.credit_legs.is_empty()
+                {
+                    return Err("at least one debit and one credit leg is required".into());
+                }
+                for booking_leg in action_to_insert
+                    .debit_legs
+                    .iter()
+                    .chain(action_to_insert.credit_legs.iter())
+                {
+                    let check_account_check_option = account_list
+                        .iter()
+                        .position(|elem| elem.id.eq(&booking_leg.finance_account_id));
+                    if check_account_check_option.is_none() {
+                        return Err(format!(
+                            "account {} is not available",
+                            booking_leg.finance_account_id
+                        ));
+                    }
+                }
+            }
+        }
+
+        let mut return_object = Vec::new();
+        for action_to_insert in actions_to_insert {
+            let insert_result = self
+                .finance_insert_split_booking_entry(conncetion_settings, user_id, action_to_insert)
+                .await;
+            if insert_result.is_err() {
+                return Err(insert_result.unwrap_err());
+            }
+            return_object.push(insert_result.unwrap());
+        }
+        Ok(return_object)
+    }
+
+    async fn finance_reverse_journal_entry(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: &Uuid,
+        booking_time: DateTime<Utc>,
+        reason: &String,
+    ) -> Result<FinanceSplitBookingResult, String> {
+        // the lock is released before the reversal is inserted
+        let (position, original_entry, debit_legs, credit_legs) = {
+            let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+            let data_obj2 = data_obj.unwrap();
+            let data_obj3 = data_obj2.lock().unwrap();
+            let position_option = data_obj3
+                .data_per_user
+                .iter()
+                .position(|elem| elem.user_id.eq(&user_id));
+            if position_option.is_none() {
+                return Err("User not found".to_string());
+            }
+            let user_object = data_obj3
+                .data_per_user
+                .get(position_option.unwrap())
+                .unwrap();
+
+            let original_entry_position_option = user_object
+                .journal_entries_per_user
+                .iter()
+                .position(|elem| elem.id.eq(journal_entry_id));
+            if original_entry_position_option.is_none() {
+                return Err(format!("journal entry {} not found", journal_entry_id));
+            }
+            let original_entry = user_object.journal_entries_per_user
+                [original_entry_position_option.unwrap()]
+            .clone();
+            if original_entry.reversed_by_journal_entry_id.is_some() {
+                return Err("journal entry is already reversed".into());
+            }
+            if original_entry.reverse_of_journal_entry_id.is_some() {
+                return Err("a reversal entry can not be reversed".into());
+            }
+            if original_entry.is_saldo {
+                return Err("saldo entries can not be reversed".into());
+            }
+            if original_entry.is_closing {
+                return Err("closing entries can not be reversed".into());
+            }
+
+            let mut debit_legs = Vec::new();
+            let mut credit_legs = Vec::new();
+            for booking_entry in user_object
+                .booking_entries_per_user
+                .iter()
+                .filter(|elem| elem.finance_journal_diary_id.eq(journal_entry_id))
+            {
+                if booking_entry.reconciliation_id.is_some() {
+                    return Err(
+                        "journal entry contains reconciled booking entries, unreconcile them first"
+                            .into(),
+                    );
+                }
+                let later_saldo_position =
+                    user_object
+                        .booking_entries_per_user
+                        .iter()
+                        .position(|elem| {
+                            elem.finance_account_id
+                                .eq(&booking_entry.finance_account_id)
+                                && (elem.booking_type.eq(&BookingEntryType::SaldoCredit)
+                                    || elem.booking_type.eq(&BookingEntryType::SaldoDebit))
+                                && elem.booking_time.ge(&booking_time)
+                        });
+                if later_saldo_position.is_some() {
+                    return Err(
+                        "Can not insert reversal before saldo of an involved account".into(),
+                    );
+                }
+                // debit and credit are swapped for the reversal
+                let reversed_leg = FinanceBookingLeg {
+                    finance_account_id: booking_entry.finance_account_id,
+                    amount: booking_entry.amount,
+                };
+                if booking_entry.booking_type.eq(&BookingEntryType::Debit) {
+                    credit_legs.push(reversed_leg);
+                } else {
+                    debit_legs.push(reversed_leg);
+                }
+            }
+            (
+                position_option.unwrap(),
+                original_entry,
+                debit_legs,
+                credit_legs,
+            )
+        };
+
+        let reversal_request = FinanceSplitBookingRequest {
+            is_saldo: false,
+            is_closing: false,
+            debit_legs,
+            credit_legs,
+            booking_time,
+            title: format!(
+                "Reversal of #{}: {}",
+                original_entry.running_number, original_entry.title
+            ),
+            description: reason.clone(),
+            tags: original_entry.tags.clone(),
+            import_reference: None,
+        };
+        let insert_result = self
+            .finance_insert_split_booking_entry(conncetion_settings, user_id, reversal_request)
+            .await;
+        if insert_result.is_err() {
+            return Err(insert_result.unwrap_err());
+        }
+        let mut return_object = insert_result.unwrap();
+
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj4 = data_obj2.lock().unwrap();
+        let user_object_2 = data_obj4.data_per_user.get_mut(position).unwrap();
+        for journal_entry in user_object_2.journal_entries_per_user.iter_mut() {
+            if journal_entry.id.eq(journal_entry_id) {
+                journal_entry.reversed_by_journal_entry_id = Some(return_object.journal_entry.id);
+            }
+            if journal_entry.id.eq(&return_object.journal_entry.id) {
+                journal_entry.reverse_of_journal_entry_id = Some(journal_entry_id.clone());
+            }
+        }
+        drop(data_obj4);
+        return_object.journal_entry.reverse_of_journal_entry_id = Some(journal_entry_id.clone());
+
+        Ok(return_object)
+    }
+
     async fn finance_get_last_saldo_account_entries(
         &self,
         _conncetion_settings: &DbConnectionSetting,
@@ -393,11 +837,12 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
             .iter()
             .position(|elem| elem.user_id.eq(&user_id));
         if let Some(position) = position_option {
-            let booking_entries_list = &data_obj3
-                .data_per_user
-                .get(position)
-                .unwrap()
-                .booking_entries_per_user;
+            let user_object = &data_obj3.data_per_user.get(position).unwrap();
+            let mut booking_entries_list = user_object.booking_entries_per_user.clone();
+            sort_booking_entries_by_journal(
+                &mut booking_entries_list,
+                &user_object.journal_entries_per_user,
+            );
 
             let mut return_object = HashMap::new();
 
@@ -420,9 +865,7 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
                         })
                         .collect();
 
-                let oldest_saldo_entry_option = saldo_entries_per_account
-                    .iter()
-                    .max_by_key(|elem| elem.booking_time);
+                let oldest_saldo_entry_option = saldo_entries_per_account.last();
                 if oldest_saldo_entry_option.is_some() {
                     return_object.insert(
                         account_id_to_check,
@@ -438,10 +881,1187 @@ impl crate::accounting_database::DBFinanceAccountingFunctions for InMemoryDataba
             Err("User not found".to_string())
         }
     }
+
+    async fn finance_get_journal_max_number(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<u64, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let return_object = data_obj3.data_per_user[position].journal_max_number_per_user;
+            drop(data_obj3);
+            Ok(return_object)
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_restore_archive(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        archive: &FinanceBackupArchive,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let user_object = &mut data_obj3.data_per_user.get_mut(position).unwrap();
+            if !user_object.journal_entries_per_user.is_empty() {
+                drop(data_obj3);
+                return Err(
+                    "the journal is not empty, entries can only be restored into an empty journal"
+                        .into(),
+                );
+            }
+
+            for other_user_object in data_obj3
+                .data_per_user
+                .iter()
+                .filter(|elem| elem.user_id.ne(&user_id))
+            {
+                let used_id_option = archive
+                    .account_types
+                    .iter()
+                    .map(|elem| ("account type", elem.id))
+                    .chain(archive.accounts.iter().map(|elem| ("account", elem.id)))
+                    .chain(
+                        archive
+                            .journal_entries
+                            .iter()
+                            .map(|elem| ("journal entry", elem.id)),
+                    )
+                    .chain(
+                        archive
+                            .booking_entries
+                            .iter()
+                            .map(|elem| ("booking entry", elem.id)),
+                    )
+                    .find(|(_, id)| {
+                        other_user_object
+                            .account_types_per_user
+                            .iter()
+                            .any(|elem| elem.id.eq(id))
+                            || other_user_object
+                                .accounts_per_user
+                                .iter()
+                                .any(|elem| elem.id.eq(id))
+                            || other_user_object
+                                .journal_entries_per_user
+                                .iter()
+                                .any(|elem| elem.id.eq(id))
+                            || other_user_object
+                                .booking_entries_per_user
+                                .iter()
+                                .any(|elem| elem.id.eq(id))
+                    });
+                if let Some((object_name, used_id)) = used_id_option {
+                    drop(data_obj3);
+                    return Err(format!(
+                        "{} {} of the archive is used by another user",
+                        object_name, used_id
+                    ));
+                }
+            }
+
+            let user_object = &mut data_obj3.data_per_user.get_mut(position).unwrap();
+            for account_type in &archive.account_types {
+                user_object
+                    .account_types_per_user
+                    .retain(|elem| elem.id.ne(&account_type.id));
+                user_object
+                    .account_types_per_user
+                    .push(account_type.clone());
+            }
+            for account in &archive.accounts {
+                user_object
+                    .accounts_per_user
+                    .retain(|elem| elem.id.ne(&account.id));
+                user_object.accounts_per_user.push(account.clone());
+            }
+            user_object.journal_entries_per_user = archive.journal_entries.clone();
+            user_object.booking_entries_per_user = archive.booking_entries.clone();
+            user_object.journal_max_number_per_user = archive.booking_journal_max_number;
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
 }
 
 #[cfg(test)]
-impl InMemoryDatabaseData {
+#[axum::async_trait(?Send)]
+impl crate::recurring_booking_database::DBFinanceRecurringBookingFunctions
+    for InMemoryDatabaseHandler
+{
+    async fn finance_recurring_booking_list(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceRecurringBooking>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let copy_list = data_obj2.data_per_user[position]
+                .recurring_bookings_per_user
+                .clone();
+            drop(data_obj2);
+            Ok(copy_list)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_recurring_booking_upsert(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking: &FinanceRecurringBooking,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .recurring_bookings_per_user;
+            let position2_option = current_list
+                .iter()
+                .position(|elem| elem.id.eq(&recurring_booking.id));
+            if let Some(position2) = position2_option {
+                current_list[position2] = recurring_booking.clone();
+            } else {
+                current_list.push(recurring_booking.clone());
+            }
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_recurring_booking_set_last_booking_time(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+        last_booking_time: &DateTime<Utc>,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .recurring_bookings_per_user;
+            if let Some(recurring_booking) = current_list
+                .iter_mut()
+                .find(|elem| elem.id.eq(recurring_booking_id))
+            {
+                if recurring_booking.last_booking_time.is_none()
+                    || recurring_booking.last_booking_time.unwrap() < *last_booking_time
+                {
+                    recurring_booking.last_booking_time = Some(*last_booking_time);
+                }
+            }
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_recurring_booking_delete(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        recurring_booking_id: &Uuid,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .recurring_bookings_per_user;
+            let position2_option = current_list
+                .iter()
+                .position(|elem| elem.id.eq(recurring_booking_id));
+            if position2_option.is_none() {
+                drop(data_obj3);
+                return Err(format!(
+                    "recurring booking {} not found",
+                    recurring_booking_id
+                ));
+            }
+            current_list.remove(position2_option.unwrap());
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_recurring_booking_list_user_ids(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+    ) -> Result<Vec<Uuid>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+        let return_object = data_obj2
+            .data_per_user
+            .iter()
+            .filter(|elem| !elem.recurring_bookings_per_user.is_empty())
+            .map(|elem| elem.user_id)
+            .collect();
+        drop(data_obj2);
+        Ok(return_object)
+    }
+}
+
+#[cfg(test)]
+#[axum::async_trait(?Send)]
+impl crate::booking_template_database::DBFinanceBookingTemplateFunctions
+    for InMemoryDatabaseHandler
+{
+    async fn finance_booking_template_list(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBookingTemplate>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let copy_list = data_obj2.data_per_user[position]
+                .booking_templates_per_user
+                .clone();
+            drop(data_obj2);
+            Ok(copy_list)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_booking_template_upsert(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template: &FinanceBookingTemplate,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .booking_templates_per_user;
+            let position2_option = current_list
+                .iter()
+                .position(|elem| elem.id.eq(&booking_template.id));
+            if let Some(position2) = position2_option {
+                current_list[position2] = booking_template.clone();
+            } else {
+                current_list.push(booking_template.clone());
+            }
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_booking_template_delete(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        booking_template_id: &Uuid,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .booking_templates_per_user;
+            let position2_option = current_list
+                .iter()
+                .position(|elem| elem.id.eq(booking_template_id));
+            if position2_option.is_none() {
+                drop(data_obj3);
+                return Err(format!(
+                    "booking template {} not found",
+                    booking_template_id
+                ));
+            }
+            current_list.remove(position2_option.unwrap());
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[axum::async_trait(?Send)]
+impl crate::budget_database::DBFinanceBudgetFunctions for InMemoryDatabaseHandler {
+    async fn finance_budget_list(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceBudget>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let copy_list = data_obj2.data_per_user[position].budgets_per_user.clone();
+            drop(data_obj2);
+            Ok(copy_list)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_budget_upsert(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget: &FinanceBudget,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .budgets_per_user;
+            let position2_option = current_list.iter().position(|elem| elem.id.eq(&budget.id));
+            if let Some(position2) = position2_option {
+                current_list[position2] = budget.clone();
+            } else {
+                current_list.push(budget.clone());
+            }
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_budget_delete(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        budget_id: &Uuid,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .budgets_per_user;
+            let position2_option = current_list.iter().position(|elem| elem.id.eq(budget_id));
+            if position2_option.is_none() {
+                drop(data_obj3);
+                return Err(format!("budget {} not found", budget_id));
+            }
+            current_list.remove(position2_option.unwrap());
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[axum::async_trait(?Send)]
+impl crate::report_section_database::DBFinanceReportSectionFunctions for InMemoryDatabaseHandler {
+    async fn finance_report_section_mapping_list(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceReportSectionMapping>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let copy_list = data_obj2.data_per_user[position]
+                .report_section_mappings_per_user
+                .clone();
+            drop(data_obj2);
+            Ok(copy_list)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_report_section_mapping_upsert(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        report_section_mapping: &FinanceReportSectionMapping,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .report_section_mappings_per_user;
+            let position2_option = current_list.iter().position(|elem| {
+                elem.finance_account_type_id
+                    .eq(&report_section_mapping.finance_account_type_id)
+            });
+            if let Some(position2) = position2_option {
+                current_list[position2] = report_section_mapping.clone();
+            } else {
+                current_list.push(report_section_mapping.clone());
+            }
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_report_section_mapping_delete(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_type_id: &Uuid,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .report_section_mappings_per_user;
+            let position2_option = current_list
+                .iter()
+                .position(|elem| elem.finance_account_type_id.eq(finance_account_type_id));
+            if position2_option.is_none() {
+                drop(data_obj3);
+                return Err(format!(
+                    "report section mapping of account type {} not found",
+                    finance_account_type_id
+                ));
+            }
+            current_list.remove(position2_option.unwrap());
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[axum::async_trait(?Send)]
+impl crate::categorization_rule_database::DBFinanceCategorizationRuleFunctions
+    for InMemoryDatabaseHandler
+{
+    async fn finance_categorization_rule_list(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+    ) -> Result<Vec<FinanceCategorizationRule>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let copy_list = data_obj2.data_per_user[position]
+                .categorization_rules_per_user
+                .clone();
+            drop(data_obj2);
+            Ok(copy_list)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_categorization_rule_upsert(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule: &FinanceCategorizationRule,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .categorization_rules_per_user;
+            let position2_option = current_list.iter().position(|elem| elem.id.eq(&rule.id));
+            if let Some(position2) = position2_option {
+                current_list[position2] = rule.clone();
+            } else {
+                current_list.push(rule.clone());
+            }
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_categorization_rule_delete(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        rule_id: &Uuid,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let current_list = &mut data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .categorization_rules_per_user;
+            let position2_option = current_list.iter().position(|elem| elem.id.eq(rule_id));
+            if position2_option.is_none() {
+                drop(data_obj3);
+                return Err(format!("categorization rule {} not found", rule_id));
+            }
+            current_list.remove(position2_option.unwrap());
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[axum::async_trait(?Send)]
+impl crate::reconciliation_database::DBFinanceReconciliationFunctions for InMemoryDatabaseHandler {
+    async fn finance_reconciliation_list(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        finance_account_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceReconciliation>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let mut copy_list: Vec<FinanceReconciliation> = data_obj2.data_per_user[position]
+                .reconciliations_per_user
+                .iter()
+                .filter(|elem| {
+                    finance_account_id.is_none()
+                        || elem.finance_account_id.eq(&finance_account_id.unwrap())
+                })
+                .cloned()
+                .collect();
+            drop(data_obj2);
+            copy_list.sort_by(|a, b| b.statement_time.cmp(&a.statement_time));
+            Ok(copy_list)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_reconciliation_insert(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation: &FinanceReconciliation,
+        booking_entry_ids: &Vec<Uuid>,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let user_object = data_obj3.data_per_user.get_mut(position).unwrap();
+            let matching_count = user_object
+                .booking_entries_per_user
+                .iter()
+                .filter(|elem| {
+                    booking_entry_ids.contains(&elem.id)
+                        && elem
+                            .finance_account_id
+                            .eq(&reconciliation.finance_account_id)
+                        && elem.booking_time.le(&reconciliation.statement_time)
+                        && elem.reconciliation_id.is_none()
+                })
+                .count();
+            if matching_count != booking_entry_ids.len() {
+                drop(data_obj3);
+                return Err(format!(
+                    "only {} of {} booking entries could be reconciled, they must belong to the account, be dated up to the statement and not be reconciled yet",
+                    matching_count,
+                    booking_entry_ids.len()
+                ));
+            }
+            for booking_entry in user_object.booking_entries_per_user.iter_mut() {
+                if booking_entry_ids.contains(&booking_entry.id) {
+                    booking_entry.reconciliation_id = Some(reconciliation.id);
+                }
+            }
+            user_object
+                .reconciliations_per_user
+                .push(reconciliation.clone());
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_reconciliation_delete(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reconciliation_id: &Uuid,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let user_object = data_obj3.data_per_user.get_mut(position).unwrap();
+            let position2_option = user_object
+                .reconciliations_per_user
+                .iter()
+                .position(|elem| elem.id.eq(reconciliation_id));
+            if position2_option.is_none() {
+                drop(data_obj3);
+                return Err(format!("reconciliation {} not found", reconciliation_id));
+            }
+            user_object
+                .reconciliations_per_user
+                .remove(position2_option.unwrap());
+            for booking_entry in user_object.booking_entries_per_user.iter_mut() {
+                if booking_entry
+                    .reconciliation_id
+                    .eq(&Some(reconciliation_id.clone()))
+                {
+                    booking_entry.reconciliation_id = None;
+                }
+            }
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[axum::async_trait(?Send)]
+impl crate::attachment_database::DBFinanceAttachmentFunctions for InMemoryDatabaseHandler {
+    async fn finance_journal_attachment_list(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        journal_entry_id: Option<Uuid>,
+    ) -> Result<Vec<FinanceJournalAttachment>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let mut copy_list = data_obj2.data_per_user[position]
+                .journal_attachments_per_user
+                .clone();
+            if journal_entry_id.is_some() {
+                copy_list.retain(|elem| {
+                    elem.finance_journal_diary_id
+                        .eq(journal_entry_id.as_ref().unwrap())
+                });
+            }
+            drop(data_obj2);
+            Ok(copy_list)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_journal_attachment_get(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment_id: &Uuid,
+    ) -> Result<Option<FinanceJournalAttachment>, String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get().unwrap();
+        let data_obj2 = data_obj.lock().unwrap();
+
+        let position_option = data_obj2
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            let attachment_option = data_obj2.data_per_user[position]
+                .journal_attachments_per_user
+                .iter()
+                .find(|elem| elem.id.eq(attachment_id))
+                .cloned();
+            drop(data_obj2);
+            Ok(attachment_option)
+        } else {
+            drop(data_obj2);
+            Err("User not found".to_string())
+        }
+    }
+
+    async fn finance_journal_attachment_insert(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        attachment: &FinanceJournalAttachment,
+    ) -> Result<(), String> {
+        let data_obj = GLOBAL_IN_MEMORY_DATA.get();
+        let data_obj2 = data_obj.unwrap();
+        let mut data_obj3 = data_obj2.lock().unwrap();
+        let position_option = data_obj3
+            .data_per_user
+            .iter()
+            .position(|elem| elem.user_id.eq(&user_id));
+        if let Some(position) = position_option {
+            data_obj3
+                .data_per_user
+                .get_mut(position)
+                .unwrap()
+                .journal_attachments_per_user
+                .push(attachment.clone());
+            drop(data_obj3);
+            Ok(())
+        } else {
+            drop(data_obj3);
+            Err("User not found".to_string())
+        }
+    }
+}
+
+#[cfg(test)]
+#[axum::async_trait]
+impl crate::user_database::DBUserFunctions for InMemoryDatabaseHandler {
+    async fn check_user_exsists_by_name(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<bool, String> {
+        let data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        return Ok(data_obj
+            .users
+            .iter()
+            .any(|elem| elem.user_name.eq(user_name)));
+    }
+
+    async fn insert_user(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<Uuid, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        if data_obj
+            .users
+            .iter()
+            .any(|elem| elem.user_name.eq(&some_credentials.username))
+        {
+            return Err(format!("user {} already exists", some_credentials.username));
+        }
+
+        // registered users get an empty finance data set, like a new user in the database
+        let new_user_id = Uuid::new();
+        data_obj.users.push(InMemoryUserEntryObj {
+            user_id: new_user_id,
+            user_name: some_credentials.username.clone(),
+            password_hash: some_credentials.password_hash.clone(),
+            user_email: None,
+            mail_validated: false,
+            mail_validation_token: None,
+            general_user_data: GenerallUserData {
+                first_name: "".into(),
+                last_name: "".into(),
+            },
+            reset_secret_hash: None,
+            password_reset_token: None,
+        });
+        data_obj
+            .data_per_user
+            .push(InMemoryDatabaseData::create_in_memory_database_entry_object(&new_user_id));
+        return Ok(new_user_id);
+    }
+
+    async fn update_user_password(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<bool, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_entry = data_obj.get_user_by_name_mut(&some_credentials.username)?;
+        user_entry.password_hash = some_credentials.password_hash.clone();
+        return Ok(true);
+    }
+
+    async fn get_stored_credentials_by_name(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<StoredCredentials, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_entry = data_obj.get_user_by_name_mut(user_name)?;
+        return Ok(StoredCredentials {
+            user_id: user_entry.user_id,
+            password_hash: user_entry.password_hash.clone(),
+        });
+    }
+
+    async fn update_user_email(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        new_email: &String,
+    ) -> Result<String, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_entry = data_obj.get_user_by_name_mut(user_name)?;
+        let mail_validation_token = Uuid::new().to_string();
+        user_entry.user_email = Some(new_email.clone());
+        user_entry.mail_validated = false;
+        user_entry.mail_validation_token = Some(mail_validation_token.clone());
+        return Ok(mail_validation_token);
+    }
+
+    async fn check_email_verfification_by_name(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<EmailVerificationStatus, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_result = data_obj.get_user_by_name_mut(user_name);
+        if user_result.is_err() {
+            return Ok(EmailVerificationStatus::NotVerified);
+        }
+        let user_entry = user_result.unwrap();
+        if user_entry.user_email.clone().unwrap_or_default().is_empty() {
+            return Ok(EmailVerificationStatus::NotGiven);
+        }
+        if user_entry.mail_validated {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        return Ok(EmailVerificationStatus::NotVerified);
+    }
+
+    async fn verify_email_by_name(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        email_validation_string: &Secret<String>,
+    ) -> Result<EmailVerificationStatus, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_result = data_obj.get_user_by_name_mut(user_name);
+        if user_result.is_err() {
+            return Ok(EmailVerificationStatus::NotVerified);
+        }
+        let user_entry = user_result.unwrap();
+        if user_entry.user_email.is_none() {
+            return Err("no E-Mail given".to_string());
+        }
+        if user_entry.mail_validated {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        if !user_entry
+            .mail_validation_token
+            .clone()
+            .unwrap_or_default()
+            .eq(email_validation_string.expose_secret())
+        {
+            return Err("provided E-Mail Validation token not matching stored token".to_string());
+        }
+        user_entry.mail_validated = true;
+        return Ok(EmailVerificationStatus::Verified);
+    }
+
+    async fn get_user_general_data_by_user_name(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<GenerallUserData, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_entry = data_obj.get_user_by_name_mut(user_name)?;
+        return Ok(GenerallUserData {
+            first_name: user_entry.general_user_data.first_name.clone(),
+            last_name: user_entry.general_user_data.last_name.clone(),
+        });
+    }
+
+    async fn update_general_user_data_by_name(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        general_user_data: &GenerallUserData,
+    ) -> Result<String, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_entry = data_obj.get_user_by_name_mut(user_name)?;
+        user_entry.general_user_data = GenerallUserData {
+            first_name: general_user_data.first_name.clone(),
+            last_name: general_user_data.last_name.clone(),
+        };
+        return Ok("updated".to_string());
+    }
+
+    async fn update_user_reset_secret(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reset_secret_hash: &Secret<String>,
+    ) -> Result<bool, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let position_option = data_obj
+            .users
+            .iter()
+            .position(|elem| elem.user_id.eq(user_id));
+        if position_option.is_none() {
+            return Err("User not found".to_string());
+        }
+        data_obj.users[position_option.unwrap()].reset_secret_hash =
+            Some(reset_secret_hash.clone());
+        return Ok(true);
+    }
+
+    async fn generate_passwort_reset_token(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_secret: &Secret<String>,
+        passwort_reset_time_limit_minutes: &u16,
+    ) -> Result<PasswordResetTokenRequestResult, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_result = data_obj.get_user_by_name_mut(user_name);
+        if user_result.is_err() {
+            return Err("unable to generate token".to_string());
+        }
+        let user_entry = user_result.unwrap();
+        if user_entry.reset_secret_hash.is_none() {
+            return Err("error generating token".to_string());
+        }
+        let verify_serect_result = crate::password_handle::verify_password_hash(
+            user_entry.reset_secret_hash.as_ref().unwrap(),
+            reset_secret,
+        );
+        if verify_serect_result.is_err() {
+            return Err("error in token generation".to_string());
+        }
+
+        let stored_user_email = user_entry.user_email.clone().unwrap_or_default();
+        let email_validation_result = crate::mail_handle::validate_email_format(&stored_user_email);
+        if email_validation_result.is_err() {
+            return Err(email_validation_result.unwrap_err().to_string());
+        }
+        if !email_validation_result.unwrap() {
+            return Err("no valid e-mail address for operation".to_string());
+        }
+
+        let reset_token_value = Uuid::new().to_string();
+        let reset_token_timestamp = Utc::now()
+            + async_session::chrono::Duration::minutes(*passwort_reset_time_limit_minutes as i64);
+        user_entry.password_reset_token = Some((reset_token_value.clone(), reset_token_timestamp));
+
+        return Ok(PasswordResetTokenRequestResult {
+            reset_token: reset_token_value,
+            expires_at: reset_token_timestamp,
+            user_email: stored_user_email,
+        });
+    }
+
+    async fn change_password_with_token(
+        &self,
+        _conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_token: &String,
+        new_password: &Secret<String>,
+    ) -> Result<bool, String> {
+        let mut data_obj = InMemoryDatabaseData::get_global_data().lock().unwrap();
+        let user_result = data_obj.get_user_by_name_mut(user_name);
+        if user_result.is_err() {
+            return Err("unable to reset value".to_string());
+        }
+        let user_entry = user_result.unwrap();
+        if user_entry.password_reset_token.is_none() {
+            return Err("unable to retrive reset settings".to_string());
+        }
+        let (stored_reset_token_value, stored_reset_token_timestamp) =
+            user_entry.password_reset_token.clone().unwrap();
+        if stored_reset_token_value.ne(reset_token) {
+            return Err("token missmatch".to_string());
+        }
+        if Utc::now() > stored_reset_token_timestamp {
+            return Err("token expired".to_string());
+        }
+
+        user_entry.password_reset_token = None;
+        user_entry.password_hash = new_password.clone();
+        return Ok(true);
+    }
+}
+
+#[cfg(test)]
+impl InMemoryDatabaseData {
+    /// the user functions can be called before any finance data was inserted
+    fn get_global_data() -> &'static Mutex<InMemoryDatabaseData> {
+        return GLOBAL_IN_MEMORY_DATA.get_or_init(|| {
+            Mutex::new(InMemoryDatabaseData {
+                data_per_user: Vec::new(),
+                users: Vec::new(),
+            })
+        });
+    }
+
+    fn get_user_by_name_mut(
+        &mut self,
+        user_name: &String,
+    ) -> Result<&mut InMemoryUserEntryObj, String> {
+        let position_option = self
+            .users
+            .iter()
+            .position(|elem| elem.user_name.eq(user_name));
+        if position_option.is_none() {
+            return Err("found 0 entries".to_string());
+        }
+        return Ok(&mut self.users[position_option.unwrap()]);
+    }
+
     pub fn insert_in_memory_database(
         mut data_per_user: Vec<InMemoryDatabaseEntryObj>,
     ) -> Result<(), String> {
@@ -449,6 +2069,7 @@ impl InMemoryDatabaseData {
         if data_obj.is_none() {
             let new_data_obj = InMemoryDatabaseData {
                 data_per_user: Vec::new(),
+                users: Vec::new(),
             };
             let mutex_obj = Mutex::new(new_data_obj);
 
@@ -473,6 +2094,14 @@ impl InMemoryDatabaseData {
             accounts_per_user: Vec::new(),
             booking_entries_per_user: Vec::new(),
             journal_entries_per_user: Vec::new(),
+            recurring_bookings_per_user: Vec::new(),
+            booking_templates_per_user: Vec::new(),
+            budgets_per_user: Vec::new(),
+            report_section_mappings_per_user: Vec::new(),
+            categorization_rules_per_user: Vec::new(),
+            reconciliations_per_user: Vec::new(),
+            journal_attachments_per_user: Vec::new(),
+            journal_max_number_per_user: 0,
         };
     }
     fn clone_finance_account_type(object_to_clone: &FinanceAccountType) -> FinanceAccountType {
@@ -480,6 +2109,7 @@ impl InMemoryDatabaseData {
             id: object_to_clone.id,
             title: object_to_clone.title.to_owned(),
             description: object_to_clone.description.to_owned(),
+            account_category: object_to_clone.account_category.clone(),
         };
         return return_obj;
     }
@@ -503,6 +2133,7 @@ impl InMemoryDatabaseData {
             finance_account_type_id: object_to_clone.finance_account_type_id,
             title: object_to_clone.title.to_owned(),
             description: object_to_clone.description.to_owned(),
+            account_number: object_to_clone.account_number,
         };
         return return_obj;
     }
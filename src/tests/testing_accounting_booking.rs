@@ -5,24 +5,39 @@ pub static GLOBAL_PREPARED_MONGODB: std::sync::Once = std::sync::Once::new();
 mod test_accounting_handle {
     use std::collections::HashMap;
 
-    use async_session::chrono::{Datelike, Duration, TimeZone, Utc};
+    use async_session::chrono::{Datelike, Duration, SubsecRound, TimeZone, Utc};
     use mongodb::bson::Uuid;
 
     use crate::{
+        accounting_config_database::DBFinanceConfigFunctions,
         accounting_config_logic::FinanceAccountingConfigHandle,
-        accounting_database::FinanceAccountBookingEntryListSearchOption,
+        accounting_database::{
+            DBFinanceAccountingFunctions, FinanceAccountBookingEntryListSearchOption,
+        },
         accounting_logic::FinanceBookingHandle,
+        accounting_report::FinanceReportHandle,
         database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
+        database_handler_postgres::DbHandlerPostgres,
+        database_handler_sqlite::DbHandlerSqlite,
         datatypes::{
-            AccountBalanceInfo, AccountBalanceType, BookingEntryType, FinanceAccount,
-            FinanceAccountBookingEntry, FinanceAccountType, FinanceBookingRequest,
-            FinanceBookingResult, FinanceJournalEntry,
+            AccountBalanceInfo, AccountBalanceType, AccountCategory, BookingEntryType,
+            FinanceAccount, FinanceAccountBookingEntry, FinanceAccountType, FinanceBookingLeg,
+            FinanceBookingRequest, FinanceBookingResult, FinanceJournalEntry,
+            FinanceReportSectionMapping, FinanceSplitBookingRequest,
         },
+        frontend_functions::generate_review_journal_entries_in_range,
         password_handle::{validate_credentials, UserCredentials},
+        report_section_database::DBFinanceReportSectionFunctions,
         setting_struct::TestSettingStruct,
         tests::{
             mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
             testing_accounting_config,
+            testing_database_postgres::test_database_postgres::{
+                create_postgres_test_connection, create_postgres_test_user,
+            },
+            testing_database_sqlite::test_database_sqlite::{
+                create_sqlite_test_connection, create_sqlite_test_user,
+            },
         },
     };
 
@@ -51,95 +66,155 @@ mod test_accounting_handle {
 
         let in_memory_db = InMemoryDatabaseHandler {};
 
-        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_1,
-            &in_memory_db,
-        );
-        let mut account_handle_2 = FinanceAccountingConfigHandle::new(
+        check_accounting_booking_creating(
             &dummy_connection_settings,
-            &user_id_2,
+            user_id_1,
+            user_id_2,
+            user_id_3,
             &in_memory_db,
-        );
-        let mut account_handle_3 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_3,
-            &in_memory_db,
-        );
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_creating_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+        let user_id_2 = create_sqlite_test_user(&connection_settings, "sqlite_user_2").await;
+        let user_id_3 = Uuid::new();
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_booking_creating(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            &sqlite_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_creating_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+        let user_id_2 = create_postgres_test_user(&connection_settings, "postgres_user_2").await;
+        let user_id_3 = Uuid::new();
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_booking_creating(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            &postgres_db,
+        )
+        .await;
+    }
+
+    async fn check_accounting_booking_creating<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        user_id_2: Uuid,
+        user_id_3: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+        let mut account_handle_2 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_2, db_handler);
+        let mut account_handle_3 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_3, db_handler);
 
         let booking_handle_1 =
-            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
         let booking_handle_2 =
-            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_2, &in_memory_db);
+            FinanceBookingHandle::new(connection_settings, &user_id_2, db_handler);
         let booking_handle_3 =
-            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_3, &in_memory_db);
+            FinanceBookingHandle::new(connection_settings, &user_id_3, db_handler);
 
         let mut finance_account_type_1_1 = FinanceAccountType {
             description: "SomeTypeDescription_1_1".to_string(),
             title: "SomeType_1_1".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let mut finance_account_type_1_2 = FinanceAccountType {
             description: "SomeTypeDescription_1_1".to_string(),
             title: "SomeType_1_2".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let mut finance_account_type_2_1 = FinanceAccountType {
             description: "SomeTypeDescription_1_1".to_string(),
             title: "SomeType_2_1".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let mut finance_account_type_2_2 = FinanceAccountType {
             description: "SomeTypeDescription_1_1".to_string(),
             title: "SomeType_1_2".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let mut finance_account_type_3_1 = FinanceAccountType {
             description: "SomeTypeDescription_3_1".to_string(),
             title: "SomeType_3_1".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let finance_account_1_1 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_1_1.id,
             title: "account_1_1".into(),
             description: "description_1_1".into(),
+            account_number: None,
         };
         let finance_account_1_2 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_1_2.id,
             title: "account_1_2".into(),
             description: "description_1_2".into(),
+            account_number: None,
         };
         let finance_account_2_1 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_2_1.id,
             title: "account_2_1".into(),
             description: "description_2_1".into(),
+            account_number: None,
         };
         let finance_account_2_2 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_2_2.id,
             title: "account_2_2".into(),
             description: "description_2_2".into(),
+            account_number: None,
         };
         let finance_account_2_3 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_2_2.id,
             title: "account_2_3".into(),
             description: "description_2_3".into(),
+            account_number: None,
         };
         let finance_account_3_1 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_3_1.id,
             title: "account_3_1".into(),
             description: "description_3_1".into(),
+            account_number: None,
         };
         let finance_account_3_2 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_3_1.id,
             title: "account_3_2".into(),
             description: "description_3_2".into(),
+            account_number: None,
         };
         let insert_finance_account_type_1_1_result =
             account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
@@ -252,6 +327,8 @@ mod test_accounting_handle {
             amount: 100,
             title: "f_b_r_1_1".into(),
             description: "description_f_b_r_1_1".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let finance_booking_request_1_2 = FinanceBookingRequest {
             is_simple_entry: true,
@@ -262,6 +339,8 @@ mod test_accounting_handle {
             amount: finance_booking_request_1_1.amount + 1,
             title: "f_b_r_1_2".into(),
             description: "description_f_b_r_1_2".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let finance_booking_request_2_1 = FinanceBookingRequest {
             is_simple_entry: true,
@@ -272,6 +351,8 @@ mod test_accounting_handle {
             amount: 100,
             title: "f_b_r_2_1".into(),
             description: "description_f_b_r_2_1".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let finance_booking_request_2_2 = FinanceBookingRequest {
             is_simple_entry: true,
@@ -282,6 +363,8 @@ mod test_accounting_handle {
             amount: finance_booking_request_2_1.amount + 1,
             title: "f_b_r_2_2".into(),
             description: "description_f_b_r_2_2".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let finance_booking_request_2_3 = FinanceBookingRequest {
             is_simple_entry: true,
@@ -292,6 +375,8 @@ mod test_accounting_handle {
             amount: finance_booking_request_2_2.amount + 1,
             title: "f_b_r_2_3".into(),
             description: "description_f_b_r_2_3".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let finance_booking_request_3_1 = FinanceBookingRequest {
             is_simple_entry: true,
@@ -302,6 +387,8 @@ mod test_accounting_handle {
             amount: 100,
             title: "f_b_r_3_1".into(),
             description: "description_f_b_r_3_1".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_1_1_result = booking_handle_1
             .finance_insert_booking_entry(&finance_booking_request_1_1)
@@ -441,6 +528,8 @@ mod test_accounting_handle {
             amount: finance_booking_request_1_2.amount + 1,
             title: "f_b_r_1_3".into(),
             description: "description_f_b_r_1_3".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_1_3_result = booking_handle_1
             .finance_insert_booking_entry(&finance_booking_request_1_3)
@@ -700,7 +789,7 @@ mod test_accounting_handle {
         a) using datetime filtering where till datetime is before from datetime
             a1) for journal entries
             a2) for account entries
-        b) insert a booking entry with a booking time already presents
+        b) insert a booking entry with a booking time already present (allowed, ordered by running number)
             b1) for credit account
             b2) for debit account
         c) using a account from another user
@@ -737,6 +826,7 @@ mod test_accounting_handle {
             finance_account_type_id: finance_account_type_2_2.id,
             title: "account_2_4".into(),
             description: "description_2_4".into(),
+            account_number: None,
         };
         let insert_finance_account_2_4_result =
             account_handle_2.finance_account_upsert(&finance_account_2_4);
@@ -754,13 +844,16 @@ mod test_accounting_handle {
             amount: 117,
             title: "f_b_r_2_4".into(),
             description: "description_f_b_r_2_4".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_4_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_4)
             .await;
         assert!(
-            insert_finance_booking_request_2_4_result.is_err(),
-            "inserting booking request for credit account with same booking time twice must fail"
+            insert_finance_booking_request_2_4_result.is_ok(),
+            "inserting booking request for credit account with same booking time twice must work: {}",
+            insert_finance_booking_request_2_4_result.unwrap_err()
         );
 
         let finance_booking_request_2_5 = FinanceBookingRequest {
@@ -772,13 +865,26 @@ mod test_accounting_handle {
             amount: 119,
             title: "f_b_r_2_5".into(),
             description: "description_f_b_r_2_5".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_5_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_5)
             .await;
         assert!(
-            insert_finance_booking_request_2_5_result.is_err(),
-            "inserting booking request for debit account with same booking time twice must fail"
+            insert_finance_booking_request_2_5_result.is_ok(),
+            "inserting booking request for debit account with same booking time twice must work: {}",
+            insert_finance_booking_request_2_5_result.unwrap_err()
+        );
+        assert!(
+            insert_finance_booking_request_2_5_result
+                .unwrap()
+                .journal_entry
+                .running_number
+                > insert_finance_booking_request_2_4_result
+                    .unwrap()
+                    .journal_entry
+                    .running_number
         );
 
         let booking_time_8: async_session::chrono::DateTime<Utc> =
@@ -792,6 +898,8 @@ mod test_accounting_handle {
             amount: 127,
             title: "f_b_r_2_6".into(),
             description: "description_f_b_r_2_6".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_6_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_6)
@@ -810,6 +918,8 @@ mod test_accounting_handle {
             amount: 127,
             title: "f_b_r_2_7".into(),
             description: "description_f_b_r_2_7".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_7_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_7)
@@ -830,6 +940,8 @@ mod test_accounting_handle {
             amount: 127,
             title: "f_b_r_2_8".into(),
             description: "description_f_b_r_2_8".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_8_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_8)
@@ -859,6 +971,8 @@ mod test_accounting_handle {
             amount: 127,
             title: "f_b_r_2_9".into(),
             description: "description_f_b_r_2_9".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_9_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_9)
@@ -881,6 +995,8 @@ mod test_accounting_handle {
             amount: 127,
             title: "f_b_r_2_10".into(),
             description: "description_f_b_r_2_10".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_10_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_10)
@@ -903,6 +1019,8 @@ mod test_accounting_handle {
             amount: 127,
             title: "f_b_r_2_11".into(),
             description: "description_f_b_r_2_11".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_11_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_11)
@@ -925,6 +1043,8 @@ mod test_accounting_handle {
             amount: 127,
             title: "f_b_r_2_12".into(),
             description: "description_f_b_r_2_12".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_finance_booking_request_2_12_result = booking_handle_2
             .finance_insert_booking_entry(&finance_booking_request_2_12)
@@ -981,24 +1101,54 @@ mod test_accounting_handle {
 
         let in_memory_db = InMemoryDatabaseHandler {};
 
-        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_1,
-            &in_memory_db,
-        );
+        check_accounting_booking_calculate(&dummy_connection_settings, user_id_1, &in_memory_db)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_calculate_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_booking_calculate(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_calculate_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_booking_calculate(&connection_settings, user_id_1, &postgres_db).await;
+    }
+
+    async fn check_accounting_booking_calculate<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
 
         let booking_handle_1 =
-            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
 
         let mut finance_account_type_1_1 = FinanceAccountType {
             description: "SomeTypeDescription_1_1".to_string(),
             title: "SomeType_1_1".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let mut finance_account_type_1_2 = FinanceAccountType {
             description: "SomeTypeDescription_1_1".to_string(),
             title: "SomeType_1_2".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
 
         let insert_finance_account_type_1_1_result =
@@ -1011,18 +1161,21 @@ mod test_accounting_handle {
             finance_account_type_id: finance_account_type_1_1.id,
             title: "account_1_1".into(),
             description: "description_1_1".into(),
+            account_number: None,
         };
         let finance_account_1_2 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_1_2.id,
             title: "account_1_2".into(),
             description: "description_1_2".into(),
+            account_number: None,
         };
         let finance_account_1_3 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_1_2.id,
             title: "account_1_3".into(),
             description: "description_1_3".into(),
+            account_number: None,
         };
 
         let insert_finance_account_1_1_result =
@@ -1078,6 +1231,8 @@ mod test_accounting_handle {
             amount: amount_1,
             title: "f_b_r_1_1".into(),
             description: "description_f_b_r_1_1".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let finance_booking_request_1_2 = FinanceBookingRequest {
             is_simple_entry: true,
@@ -1088,6 +1243,8 @@ mod test_accounting_handle {
             amount: amount_2,
             title: "f_b_r_1_2".into(),
             description: "description_f_b_r_1_2".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let finance_booking_request_1_3 = FinanceBookingRequest {
             is_simple_entry: true,
@@ -1098,6 +1255,8 @@ mod test_accounting_handle {
             amount: amount_3,
             title: "f_b_r_1_3".into(),
             description: "description_f_b_r_1_3".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
         let account_1_running_saldo_amount = amount_1 + amount_2;
         let account_1_running_saldo_type = AccountBalanceType::Debit;
@@ -1215,198 +1374,1923 @@ mod test_accounting_handle {
     }
 
     #[tokio::test]
-    async fn test_accounting_booking_with_mongodb() {
-        testing_accounting_config::test_accounting_handle::init();
-        //let local_setting: SettingStruct = SettingStruct::global().clone();
-        let test_setting = TestSettingStruct::global().clone();
-        let db_connection = DbConnectionSetting {
-            url: String::from(test_setting.backend_database_url),
-            user: String::from(test_setting.backend_database_user),
-            password: String::from(test_setting.backend_database_password),
-            instance: String::from(test_setting.backend_database_instance),
-        };
-        //
-        let credentials = UserCredentials {
-            username: test_setting.test_user_account_user_login,
-            password: test_setting.test_user_account_user_password.into(),
+    async fn test_accounting_split_booking_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
         };
+        let user_id_1 = Uuid::new();
 
-        let validate_result = validate_credentials(&db_connection, &credentials).await;
-        if validate_result.is_err() {
-            panic!(
-                "test user {} not valid: {}",
-                credentials.username,
-                validate_result.unwrap_err()
-            );
-        }
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
 
-        let user_id_1 = validate_result.unwrap();
-        let mongo_db = DbHandlerMongoDB::new(&db_connection);
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
 
-        let account_handle_1 =
-            FinanceAccountingConfigHandle::new(&db_connection, &user_id_1, &mongo_db);
-        let booking_handle_1 = FinanceBookingHandle::new(&db_connection, &user_id_1, &mongo_db);
+        let in_memory_db = InMemoryDatabaseHandler {};
 
-        let repair_result = mongo_db
-            .repair_counter_record_for_user(&db_connection, &user_id_1)
-            .await;
-        assert!(repair_result.is_ok(), "{}", repair_result.unwrap_err());
-        let _ = prepare_mongo_db_for_tests(&account_handle_1, &booking_handle_1, &db_connection);
+        check_accounting_split_booking(&dummy_connection_settings, user_id_1, &in_memory_db).await;
+    }
 
-        /* Test 0, further requirements
-         * ensure that at least 4 finance accounts are available, at least 2 accounta with debit balance, at least 2 accounts with debit balance
-         * get balance information for each account
-         * get last booking entry for each account
-         */
-        let accounts_per_user_result = account_handle_1.finance_account_list_async(None).await;
-        assert!(
-            accounts_per_user_result.is_ok(),
-            "{}",
-            accounts_per_user_result.unwrap_err()
-        );
-        let accounts_per_user = accounts_per_user_result.unwrap();
+    #[tokio::test]
+    async fn test_accounting_split_booking_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
 
-        let account_ids: Vec<Uuid> = accounts_per_user.iter().map(|elem| elem.id).collect();
-        let balance_info_all_accounts_result =
-            booking_handle_1.calculate_balance_info(&account_ids).await;
-        assert!(
-            balance_info_all_accounts_result.is_ok(),
-            "{}",
-            balance_info_all_accounts_result.unwrap_err()
-        );
-        let balance_info = balance_info_all_accounts_result.unwrap();
+        let sqlite_db = DbHandlerSqlite::new();
 
-        let debit_accounts_info: Vec<&AccountBalanceInfo> = balance_info
-            .iter()
-            .filter(|elem| elem.balance_type.eq(&AccountBalanceType::Debit))
-            .collect();
-        let credit_accounts_info: Vec<&AccountBalanceInfo> = balance_info
-            .iter()
-            .filter(|elem| elem.balance_type.eq(&AccountBalanceType::Credit))
-            .collect();
-        assert!(
-            debit_accounts_info.len() > 1,
-            "Not enought accounts with debit balance"
-        );
+        check_accounting_split_booking(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_split_booking_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_split_booking(&connection_settings, user_id_1, &postgres_db).await;
+    }
+
+    async fn check_accounting_split_booking<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
         assert!(
-            credit_accounts_info.len() > 1,
-            "Not enought accounts with credit balance"
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
         );
 
-        let test_account_a_balance_info = debit_accounts_info[0];
-        let test_account_b_balance_info = debit_accounts_info[1];
-        let test_account_c_balance_info = credit_accounts_info[0];
-        let test_account_d_balance_info = credit_accounts_info[1];
-        let list_test_accounts_ids = vec![
-            test_account_a_balance_info.account_id,
-            test_account_b_balance_info.account_id,
-            test_account_c_balance_info.account_id,
-            test_account_d_balance_info.account_id,
-        ];
-        let mut hashmap_last_booking_entries_per_account = HashMap::new();
-        let mut max_booking_time = Utc
-            .with_ymd_and_hms(Utc::now().year(), 1, 1, 10, 15, 25)
-            .unwrap();
-        for account_id in list_test_accounts_ids.iter() {
-            let account_entries_result = booking_handle_1
-                .list_account_booking_entries(vec![
-                    FinanceAccountBookingEntryListSearchOption::new(&account_id, None, None),
-                ])
-                .await;
+        let mut accounts = Vec::new();
+        for account_title in ["salary", "bank", "tax", "insurance"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
             assert!(
-                account_entries_result.is_ok(),
+                insert_finance_account_result.is_ok(),
                 "{}",
-                account_entries_result.unwrap_err()
+                insert_finance_account_result.unwrap_err()
             );
-            let account_entries = account_entries_result.unwrap();
-            let latest_pos_option = account_entries.iter().max_by_key(|elem| elem.booking_time);
-            if latest_pos_option.is_some() {
-                let latest_pos = latest_pos_option.unwrap();
-                hashmap_last_booking_entries_per_account.insert(account_id, latest_pos.clone());
-                if latest_pos.booking_time.gt(&max_booking_time) {
-                    max_booking_time = latest_pos.booking_time;
-                }
-            }
+            accounts.push(finance_account);
         }
+        let account_salary = &accounts[0];
+        let account_bank = &accounts[1];
+        let account_tax = &accounts[2];
+        let account_insurance = &accounts[3];
 
-        /* Test 1, change balance, credit to debit
-        select 4 accounts A, B, C and D, so that a and B have a debit balance, C and D a credit balance (see above)
-        insert amount 1 with A to B
-        check balance information for all 4 accounts
-        insert amount 2 with C to D
-        check balance information for all 4 accounts
-        insert amount 3 with B to C, so that balance type switches
-        check balance information  for all 4 accounts
-        insert amount 4 with A to D, so that balance type switches
-        check balance information for all 4 accounts
-         */
-        let test_run_id = Uuid::new().to_string();
-        let booking_time_1 = max_booking_time + Duration::hours(1);
-        let amount_a_b = std::cmp::min(
-            test_account_a_balance_info.amount,
-            test_account_b_balance_info.amount,
-        ) / 2;
-        let insert_request_a_b = FinanceBookingRequest {
-            amount: amount_a_b,
-            booking_time: booking_time_1,
-            credit_finance_account_id: test_account_a_balance_info.account_id,
-            debit_finance_account_id: test_account_b_balance_info.account_id,
-            description: format!("A to B: {}, test run {}", amount_a_b, test_run_id),
-            title: "A to B".into(),
+        /* Test 1 paycheck split
+        one credit leg (salary) and three debit legs (bank, tax, insurance)
+
+        checks: one journal entry with total amount, one booking entry per leg, balances per account
+        */
+        let booking_time_1 = Utc.with_ymd_and_hms(2024, 1, 31, 10, 0, 0).unwrap();
+        let split_request_1 = FinanceSplitBookingRequest {
             is_saldo: false,
-            is_simple_entry: true,
+            is_closing: false,
+            debit_legs: vec![
+                FinanceBookingLeg {
+                    finance_account_id: account_bank.id,
+                    amount: 210000,
+                },
+                FinanceBookingLeg {
+                    finance_account_id: account_tax.id,
+                    amount: 60000,
+                },
+                FinanceBookingLeg {
+                    finance_account_id: account_insurance.id,
+                    amount: 30000,
+                },
+            ],
+            credit_legs: vec![FinanceBookingLeg {
+                finance_account_id: account_salary.id,
+                amount: 300000,
+            }],
+            booking_time: booking_time_1,
+            title: "paycheck".into(),
+            description: "paycheck january".into(),
+            tags: Vec::new(),
+            import_reference: None,
         };
-        let insert_request_a_b_result = booking_handle_1
-            .finance_insert_booking_entry(&insert_request_a_b)
+        let insert_split_request_1_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_1)
             .await;
         assert!(
-            insert_request_a_b_result.is_ok(),
+            insert_split_request_1_result.is_ok(),
             "{}",
-            insert_request_a_b_result.unwrap_err()
+            insert_split_request_1_result.unwrap_err()
+        );
+        let split_result_1 = insert_split_request_1_result.unwrap();
+        assert!(!split_result_1.journal_entry.is_simple_entry);
+        assert_eq!(split_result_1.journal_entry.amount, 300000);
+        assert_eq!(split_result_1.debit_account_entries.len(), 3);
+        assert_eq!(split_result_1.credit_account_entries.len(), 1);
+        for booking_entry in split_result_1
+            .debit_account_entries
+            .iter()
+            .chain(split_result_1.credit_account_entries.iter())
+        {
+            assert_eq!(
+                booking_entry.finance_journal_diary_id,
+                split_result_1.journal_entry.id
+            );
+        }
+
+        let journal_listing_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert!(
+            journal_listing_result.is_ok(),
+            "{}",
+            journal_listing_result.unwrap_err()
         );
-        let balance_info_1_accounts_result = booking_handle_1
-            .calculate_balance_info(&list_test_accounts_ids)
+        assert_eq!(journal_listing_result.unwrap().len(), 1);
+
+        let balance_info_result = booking_handle_1
+            .calculate_balance_info(&accounts.iter().map(|elem| elem.id).collect())
             .await;
         assert!(
-            balance_info_1_accounts_result.is_ok(),
+            balance_info_result.is_ok(),
             "{}",
-            balance_info_1_accounts_result.unwrap_err()
+            balance_info_result.unwrap_err()
         );
-        let test_account_a_balance_info_1 = AccountBalanceInfo {
-            account_id: test_account_a_balance_info.account_id,
-            amount: test_account_a_balance_info.amount.abs_diff(amount_a_b),
-            balance_type: test_account_a_balance_info.balance_type.clone(),
+        let expected_salary = AccountBalanceInfo {
+            account_id: account_salary.id,
+            balance_type: AccountBalanceType::Credit,
+            amount: 300000,
+            account_category: AccountCategory::Asset,
         };
-        let test_account_b_balance_info_1 = AccountBalanceInfo {
-            account_id: test_account_b_balance_info.account_id,
-            amount: test_account_b_balance_info.amount + amount_a_b,
-            balance_type: test_account_b_balance_info.balance_type.clone(),
+        let expected_bank = AccountBalanceInfo {
+            account_id: account_bank.id,
+            balance_type: AccountBalanceType::Debit,
+            amount: 210000,
+            account_category: AccountCategory::Asset,
         };
-        let balance_check_1_result = check_multiple_balance_info(
-            &balance_info_1_accounts_result.unwrap(),
-            &vec![
-                &test_account_a_balance_info_1,
-                &test_account_b_balance_info_1,
-                test_account_c_balance_info,
-                test_account_d_balance_info,
-            ],
+        let expected_tax = AccountBalanceInfo {
+            account_id: account_tax.id,
+            balance_type: AccountBalanceType::Debit,
+            amount: 60000,
+            account_category: AccountCategory::Asset,
+        };
+        let expected_insurance = AccountBalanceInfo {
+            account_id: account_insurance.id,
+            balance_type: AccountBalanceType::Debit,
+            amount: 30000,
+            account_category: AccountCategory::Asset,
+        };
+        assert_eq!(
+            check_multiple_balance_info(
+                &balance_info_result.unwrap(),
+                &vec![
+                    &expected_salary,
+                    &expected_bank,
+                    &expected_tax,
+                    &expected_insurance
+                ]
+            ),
+            ""
         );
-        assert_eq!(balance_check_1_result, "");
 
-        let booking_time_2 = booking_time_1 + Duration::hours(1);
-        let amount_c_d = std::cmp::min(
-            test_account_c_balance_info.amount,
-            test_account_d_balance_info.amount,
-        ) / 2;
-        let insert_request_c_d = FinanceBookingRequest {
-            amount: amount_c_d,
-            booking_time: booking_time_2,
-            credit_finance_account_id: test_account_c_balance_info.account_id,
-            debit_finance_account_id: test_account_d_balance_info.account_id,
-            description: format!("C to D: {}, test run {}", amount_c_d, test_run_id),
-            title: "C to D".into(),
-            is_saldo: false,
+        /* Test 2 invalid split requests
+        a) sums of debit and credit legs differ
+        b) account used on both sides
+        c) no credit leg
+        d) leg with zero amount
+        e) account already has an entry at booking time is allowed
+        f) sum of the legs does not fit into the amount
+        */
+        let booking_time_2 = booking_time_1 + Duration::days(1);
+        let mut split_request_2 = split_request_1.clone();
+        split_request_2.booking_time = booking_time_2;
+        split_request_2.credit_legs[0].amount = 299999;
+        let insert_split_request_2_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_2)
+            .await;
+        assert!(insert_split_request_2_result
+            .unwrap_err()
+            .contains("is not equal to sum of credit amounts"));
+
+        let mut split_request_3 = split_request_1.clone();
+        split_request_3.booking_time = booking_time_2;
+        split_request_3.credit_legs[0].finance_account_id = account_bank.id;
+        let insert_split_request_3_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_3)
+            .await;
+        assert!(insert_split_request_3_result
+            .unwrap_err()
+            .contains("is used more than once"));
+
+        let mut split_request_4 = split_request_1.clone();
+        split_request_4.booking_time = booking_time_2;
+        split_request_4.credit_legs.clear();
+        let insert_split_request_4_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_4)
+            .await;
+        assert!(insert_split_request_4_result
+            .unwrap_err()
+            .contains("no credit account given"));
+
+        let mut split_request_5 = split_request_1.clone();
+        split_request_5.booking_time = booking_time_2;
+        split_request_5.debit_legs[0].amount = 0;
+        split_request_5.credit_legs[0].amount = 90000;
+        let insert_split_request_5_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_5)
+            .await;
+        assert!(insert_split_request_5_result
+            .unwrap_err()
+            .contains("must not be zero"));
+
+        let insert_split_request_6_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_1)
+            .await;
+        assert!(
+            insert_split_request_6_result.is_ok(),
+            "{}",
+            insert_split_request_6_result.unwrap_err()
+        );
+
+        let mut split_request_7 = split_request_1.clone();
+        split_request_7.booking_time = booking_time_2;
+        split_request_7.debit_legs[0].amount = u64::MAX;
+        split_request_7.credit_legs[0].amount = u64::MAX;
+        let insert_split_request_7_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_7)
+            .await;
+        assert!(insert_split_request_7_result
+            .unwrap_err()
+            .contains("sum of the amounts is too large"));
+
+        let journal_listing_2_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert_eq!(journal_listing_2_result.unwrap().len(), 2);
+
+        /* Test 3 journal review of split entries
+        the journal entry only stores the first leg of each side, the review lists all accounts
+        */
+        let review_rows_result = generate_review_journal_entries_in_range(
+            &booking_handle_1,
+            &account_handle_1,
+            None,
+            None,
+            None,
+        )
+        .await;
+        assert!(
+            review_rows_result.is_ok(),
+            "{}",
+            review_rows_result.unwrap_err()
+        );
+        let review_rows = review_rows_result.unwrap();
+        assert_eq!(review_rows.len(), 2);
+        for review_row in &review_rows {
+            assert!(!review_row.is_simple_entry);
+            assert_eq!(review_row.debit_account_name, "bank, tax, insurance");
+            assert_eq!(review_row.credit_account_name, "salary");
+            assert_eq!(review_row.amount_cents, 300000);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_reverse_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_accounting_booking_reverse(&dummy_connection_settings, user_id_1, &in_memory_db)
+            .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_reverse_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_booking_reverse(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_reverse_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_booking_reverse(&connection_settings, user_id_1, &postgres_db).await;
+    }
+
+    async fn check_accounting_booking_reverse<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["account_1_1", "account_1_2", "account_1_3"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+        let account_ids: Vec<Uuid> = accounts.iter().map(|elem| elem.id).collect();
+
+        let booking_time_1 = Utc::now() - Duration::days(2);
+        let finance_booking_request_1 = FinanceBookingRequest {
+            is_simple_entry: true,
+            is_saldo: false,
+            debit_finance_account_id: accounts[0].id,
+            credit_finance_account_id: accounts[1].id,
+            booking_time: booking_time_1,
+            amount: 1500,
+            title: "f_b_r_1".into(),
+            description: "description_f_b_r_1".into(),
+            tags: Vec::new(),
+            import_reference: None,
+        };
+        let split_request_2 = FinanceSplitBookingRequest {
+            is_saldo: false,
+            is_closing: false,
+            debit_legs: vec![FinanceBookingLeg {
+                finance_account_id: accounts[0].id,
+                amount: 1000,
+            }],
+            credit_legs: vec![
+                FinanceBookingLeg {
+                    finance_account_id: accounts[1].id,
+                    amount: 400,
+                },
+                FinanceBookingLeg {
+                    finance_account_id: accounts[2].id,
+                    amount: 600,
+                },
+            ],
+            booking_time: booking_time_1 + Duration::days(1),
+            title: "f_s_b_r_2".into(),
+            description: "description_f_s_b_r_2".into(),
+            tags: Vec::new(),
+            import_reference: None,
+        };
+        let insert_request_1_result = booking_handle_1
+            .finance_insert_booking_entry(&finance_booking_request_1)
+            .await;
+        assert!(
+            insert_request_1_result.is_ok(),
+            "{}",
+            insert_request_1_result.unwrap_err()
+        );
+        let insert_request_2_result = booking_handle_1
+            .finance_insert_split_booking_entry(&split_request_2)
+            .await;
+        assert!(
+            insert_request_2_result.is_ok(),
+            "{}",
+            insert_request_2_result.unwrap_err()
+        );
+        let journal_entry_1 = insert_request_1_result.unwrap().journal_entry;
+        let journal_entry_2 = insert_request_2_result.unwrap().journal_entry;
+
+        /* Test 1 reverse a simple entry
+        checks: mirrored entry with new running number, link between both entries, balances of the reversed amount are gone
+        */
+        let reverse_1_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_1.id, &"wrong amount".into())
+            .await;
+        assert!(
+            reverse_1_result.is_ok(),
+            "{}",
+            reverse_1_result.unwrap_err()
+        );
+        let reverse_1 = reverse_1_result.unwrap();
+        assert_eq!(
+            reverse_1.journal_entry.reverse_of_journal_entry_id,
+            Some(journal_entry_1.id)
+        );
+        assert_eq!(reverse_1.journal_entry.description, "wrong amount");
+        assert!(reverse_1.journal_entry.running_number > journal_entry_2.running_number);
+        assert_eq!(
+            reverse_1.journal_entry.debit_finance_account_id,
+            journal_entry_1.credit_finance_account_id
+        );
+        assert_eq!(
+            reverse_1.journal_entry.credit_finance_account_id,
+            journal_entry_1.debit_finance_account_id
+        );
+        assert_eq!(reverse_1.journal_entry.amount, journal_entry_1.amount);
+
+        let journal_listing_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert!(
+            journal_listing_result.is_ok(),
+            "{}",
+            journal_listing_result.unwrap_err()
+        );
+        let journal_listing = journal_listing_result.unwrap();
+        assert_eq!(journal_listing.len(), 3);
+        let original_position = journal_listing
+            .iter()
+            .position(|elem| elem.id.eq(&journal_entry_1.id))
+            .unwrap();
+        assert_eq!(
+            journal_listing[original_position].reversed_by_journal_entry_id,
+            Some(reverse_1.journal_entry.id)
+        );
+
+        /* Test 2 reverse a split entry
+        checks: legs are mirrored, all balances are zero afterwards
+        */
+        let reverse_2_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_2.id, &"booked twice".into())
+            .await;
+        assert!(
+            reverse_2_result.is_ok(),
+            "{}",
+            reverse_2_result.unwrap_err()
+        );
+        let reverse_2 = reverse_2_result.unwrap();
+        assert_eq!(reverse_2.debit_account_entries.len(), 2);
+        assert_eq!(reverse_2.credit_account_entries.len(), 1);
+        assert!(!reverse_2.journal_entry.is_simple_entry);
+
+        let balance_info_result = booking_handle_1.calculate_balance_info(&account_ids).await;
+        assert!(
+            balance_info_result.is_ok(),
+            "{}",
+            balance_info_result.unwrap_err()
+        );
+        for balance_info in balance_info_result.unwrap() {
+            assert_eq!(balance_info.amount, 0);
+        }
+
+        /* Test 3 invalid reversals
+        a) entry already reversed
+        b) reversal entry itself
+        c) missing reason
+        d) unknown entry
+        */
+        let reverse_3_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_1.id, &"again".into())
+            .await;
+        assert!(reverse_3_result
+            .unwrap_err()
+            .contains("journal entry is already reversed"));
+        let reverse_4_result = booking_handle_1
+            .reverse_journal_entry(&reverse_1.journal_entry.id, &"again".into())
+            .await;
+        assert!(reverse_4_result
+            .unwrap_err()
+            .contains("a reversal entry can not be reversed"));
+        let reverse_5_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_2.id, &" ".into())
+            .await;
+        assert!(reverse_5_result
+            .unwrap_err()
+            .contains("a reason for the reversal is required"));
+        let reverse_6_result = booking_handle_1
+            .reverse_journal_entry(&Uuid::new(), &"unknown".into())
+            .await;
+        assert!(reverse_6_result.unwrap_err().contains("not found"));
+    }
+
+    #[tokio::test]
+    async fn test_accounting_balance_category_sign_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_accounting_balance_category_sign(
+            &dummy_connection_settings,
+            user_id_1,
+            &in_memory_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_balance_category_sign_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_balance_category_sign(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_balance_category_sign_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_balance_category_sign(&connection_settings, user_id_1, &postgres_db).await;
+    }
+
+    async fn check_accounting_balance_category_sign<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let mut accounts = Vec::new();
+        for (account_title, account_category) in [
+            ("bank", AccountCategory::Asset),
+            ("loan", AccountCategory::Liability),
+            ("capital", AccountCategory::Equity),
+            ("salary", AccountCategory::Income),
+            ("rent", AccountCategory::Expense),
+        ] {
+            let mut finance_account_type = FinanceAccountType {
+                description: format!("type_description_{}", account_title),
+                title: format!("type_{}", account_title),
+                id: Uuid::new(),
+                account_category: account_category,
+            };
+            let insert_finance_account_type_result =
+                account_handle_1.finance_account_type_upsert(&mut finance_account_type);
+            assert!(
+                insert_finance_account_type_result.is_ok(),
+                "{}",
+                insert_finance_account_type_result.unwrap_err()
+            );
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+        let account_ids: Vec<Uuid> = accounts.iter().map(|elem| elem.id).collect();
+
+        // every stored account type keeps its category
+        let account_type_list = account_handle_1.finance_account_type_list().unwrap();
+        for (account_title, account_category) in [
+            ("bank", AccountCategory::Asset),
+            ("loan", AccountCategory::Liability),
+            ("capital", AccountCategory::Equity),
+            ("salary", AccountCategory::Income),
+            ("rent", AccountCategory::Expense),
+        ] {
+            let stored_account_type = account_type_list
+                .iter()
+                .find(|elem| elem.title.eq(&format!("type_{}", account_title)))
+                .unwrap();
+            assert_eq!(stored_account_type.account_category, account_category);
+        }
+
+        let booking_time_start = Utc::now() - Duration::days(10);
+        // (debit account, credit account, amount)
+        let booking_data = [
+            (0, 2, 1000),
+            (0, 1, 500),
+            (0, 3, 2000),
+            (4, 0, 800),
+            (1, 0, 700),
+        ];
+        for (booking_index, (debit_index, credit_index, amount)) in
+            booking_data.into_iter().enumerate()
+        {
+            let finance_booking_request = FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[debit_index].id,
+                credit_finance_account_id: accounts[credit_index].id,
+                booking_time: booking_time_start + Duration::days(booking_index as i64),
+                amount,
+                title: "f_b_r".into(),
+                description: "description_f_b_r".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            };
+            let insert_request_result = booking_handle_1
+                .finance_insert_booking_entry(&finance_booking_request)
+                .await;
+            assert!(
+                insert_request_result.is_ok(),
+                "{}",
+                insert_request_result.unwrap_err()
+            );
+        }
+
+        /* balances are positive on the natural side of the category,
+        the loan was paid back more than borrowed so its (debit) balance is negative
+        */
+        let balance_info_result = booking_handle_1.calculate_balance_info(&account_ids).await;
+        assert!(
+            balance_info_result.is_ok(),
+            "{}",
+            balance_info_result.unwrap_err()
+        );
+        let balance_info_list = balance_info_result.unwrap();
+        let expected_signed_amounts: [i128; 5] = [2000, -200, 1000, 2000, 800];
+        for (index, expected_signed_amount) in expected_signed_amounts.iter().enumerate() {
+            let balance_info = balance_info_list
+                .iter()
+                .find(|elem| elem.account_id.eq(&accounts[index].id))
+                .unwrap();
+            assert_eq!(&balance_info.get_signed_amount(), expected_signed_amount);
+        }
+        let loan_balance_info = balance_info_list
+            .iter()
+            .find(|elem| elem.account_id.eq(&accounts[1].id))
+            .unwrap();
+        assert_eq!(loan_balance_info.balance_type, AccountBalanceType::Debit);
+        assert_eq!(
+            loan_balance_info.account_category,
+            AccountCategory::Liability
+        );
+    }
+
+    #[tokio::test]
+    async fn test_accounting_close_accounts_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_accounting_close_accounts(&dummy_connection_settings, user_id_1, &in_memory_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_close_accounts_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_close_accounts(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_close_accounts_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_close_accounts(&connection_settings, user_id_1, &postgres_db).await;
+    }
+
+    async fn check_accounting_close_accounts<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["equity", "bank", "income", "expense"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+        let closing_account_id = accounts[0].id;
+        let accounts_to_close: Vec<Uuid> = accounts[1..].iter().map(|elem| elem.id).collect();
+        let all_account_ids: Vec<Uuid> = accounts.iter().map(|elem| elem.id).collect();
+
+        let booking_time_start = Utc::now() - Duration::days(10);
+        let closing_time = Utc::now() - Duration::days(3);
+        // (debit account, credit account, amount, booking time)
+        let booking_data = [
+            (1, 0, 100000, booking_time_start),
+            (1, 2, 5000, booking_time_start + Duration::days(2)),
+            (3, 1, 2000, booking_time_start + Duration::days(4)),
+            (1, 2, 300, closing_time + Duration::days(2)),
+        ];
+        for (debit_index, credit_index, amount, booking_time) in booking_data {
+            let finance_booking_request = FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[debit_index].id,
+                credit_finance_account_id: accounts[credit_index].id,
+                booking_time,
+                amount,
+                title: "f_b_r".into(),
+                description: "description_f_b_r".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            };
+            let insert_request_result = booking_handle_1
+                .finance_insert_booking_entry(&finance_booking_request)
+                .await;
+            assert!(
+                insert_request_result.is_ok(),
+                "{}",
+                insert_request_result.unwrap_err()
+            );
+        }
+
+        /* Test 1 close accounts
+        checks: two closing and two carry forward entries, balances stay the same after closing
+        */
+        let close_1_result = booking_handle_1
+            .close_accounts(&accounts_to_close, &closing_account_id, closing_time)
+            .await;
+        assert!(close_1_result.is_ok(), "{}", close_1_result.unwrap_err());
+        let close_1 = close_1_result.unwrap();
+        assert_eq!(close_1.len(), 4);
+        assert_eq!(
+            close_1
+                .iter()
+                .filter(|elem| elem.journal_entry.is_saldo)
+                .count(),
+            2
+        );
+        assert_eq!(close_1[0].journal_entry.amount, 105000);
+        assert_eq!(close_1[1].journal_entry.amount, 5000);
+
+        let balance_info_result = booking_handle_1
+            .calculate_balance_info(&all_account_ids)
+            .await;
+        assert!(
+            balance_info_result.is_ok(),
+            "{}",
+            balance_info_result.unwrap_err()
+        );
+        let expected_balances = [
+            (AccountBalanceType::Credit, 100000),
+            (AccountBalanceType::Debit, 103300),
+            (AccountBalanceType::Credit, 5300),
+            (AccountBalanceType::Debit, 2000),
+        ];
+        let balance_info_list = balance_info_result.unwrap();
+        for (index, (balance_type, amount)) in expected_balances.iter().enumerate() {
+            let balance_info = balance_info_list
+                .iter()
+                .find(|elem| elem.account_id.eq(&accounts[index].id))
+                .unwrap();
+            assert_eq!(&balance_info.balance_type, balance_type);
+            assert_eq!(&balance_info.amount, amount);
+        }
+
+        let saldo_information_result = booking_handle_1
+            .finance_get_last_saldo_account_entries(Some(all_account_ids.clone()))
+            .await;
+        assert!(
+            saldo_information_result.is_ok(),
+            "{}",
+            saldo_information_result.unwrap_err()
+        );
+        assert_eq!(saldo_information_result.unwrap().len(), 4);
+
+        /* Test 2 invalid closings
+        a) closing again at the same time
+        b) closing account is part of the accounts to close
+        c) only a part of the accounts is closed, closing account would not be balanced
+        d) no accounts given
+        */
+        let close_2_result = booking_handle_1
+            .close_accounts(&accounts_to_close, &closing_account_id, closing_time)
+            .await;
+        assert!(close_2_result
+            .unwrap_err()
+            .contains("Can not close accounts before saldo"));
+        let close_3_result = booking_handle_1
+            .close_accounts(&all_account_ids, &closing_account_id, Utc::now())
+            .await;
+        assert!(close_3_result
+            .unwrap_err()
+            .contains("can not be closed against itself"));
+        let close_4_result = booking_handle_1
+            .close_accounts(&vec![accounts[1].id], &closing_account_id, Utc::now())
+            .await;
+        assert!(close_4_result
+            .unwrap_err()
+            .contains("would not be balanced"));
+        let close_5_result = booking_handle_1
+            .close_accounts(&Vec::new(), &closing_account_id, Utc::now())
+            .await;
+        assert!(close_5_result
+            .unwrap_err()
+            .contains("no accounts to close given"));
+
+        /* Test 3 booking into the closed period is not possible */
+        let finance_booking_request_closed = FinanceBookingRequest {
+            is_simple_entry: true,
+            is_saldo: false,
+            debit_finance_account_id: accounts[3].id,
+            credit_finance_account_id: accounts[1].id,
+            booking_time: closing_time - Duration::days(1),
+            amount: 100,
+            title: "f_b_r_closed".into(),
+            description: "description_f_b_r_closed".into(),
+            tags: Vec::new(),
+            import_reference: None,
+        };
+        let insert_closed_result = booking_handle_1
+            .finance_insert_booking_entry(&finance_booking_request_closed)
+            .await;
+        assert!(insert_closed_result
+            .unwrap_err()
+            .contains("Can not insert before saldo"));
+    }
+
+    #[tokio::test]
+    async fn test_accounting_same_booking_time_order_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_accounting_same_booking_time_order(
+            &dummy_connection_settings,
+            user_id_1,
+            &in_memory_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_same_booking_time_order_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_same_booking_time_order(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_same_booking_time_order_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_same_booking_time_order(&connection_settings, user_id_1, &postgres_db)
+            .await;
+    }
+
+    async fn check_accounting_same_booking_time_order<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["equity", "bank", "income", "expense"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+        let all_account_ids: Vec<Uuid> = accounts.iter().map(|elem| elem.id).collect();
+
+        let booking_time = (Utc::now() - Duration::days(5)).trunc_subsecs(0);
+        // (debit account, credit account, amount, booking time, title)
+        // the last entry is back-dated, so its running number is the highest
+        let booking_data = [
+            (1, 2, 500, booking_time, "same_time_1"),
+            (3, 1, 200, booking_time, "same_time_2"),
+            (1, 2, 100, booking_time - Duration::hours(1), "back_dated"),
+        ];
+        let mut journal_entries_inserted = Vec::new();
+        for (debit_index, credit_index, amount, booking_time, title) in booking_data {
+            let finance_booking_request = FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[debit_index].id,
+                credit_finance_account_id: accounts[credit_index].id,
+                booking_time,
+                amount,
+                title: title.into(),
+                description: "description_f_b_r".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            };
+            let insert_request_result = booking_handle_1
+                .finance_insert_booking_entry(&finance_booking_request)
+                .await;
+            assert!(
+                insert_request_result.is_ok(),
+                "{}",
+                insert_request_result.unwrap_err()
+            );
+            journal_entries_inserted.push(insert_request_result.unwrap().journal_entry);
+        }
+        assert!(
+            journal_entries_inserted[0].running_number < journal_entries_inserted[1].running_number
+        );
+        assert!(
+            journal_entries_inserted[1].running_number < journal_entries_inserted[2].running_number
+        );
+
+        /* Test 1 journal and booking entries are ordered by booking time and running number */
+        let journal_list_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert!(
+            journal_list_result.is_ok(),
+            "{}",
+            journal_list_result.unwrap_err()
+        );
+        let journal_titles: Vec<String> = journal_list_result
+            .unwrap()
+            .iter()
+            .map(|elem| elem.title.clone())
+            .collect();
+        assert_eq!(journal_titles, ["back_dated", "same_time_1", "same_time_2"]);
+
+        let booking_list_result =
+            booking_handle_1
+                .list_account_booking_entries(vec![
+                    FinanceAccountBookingEntryListSearchOption::new(&accounts[1].id, None, None),
+                ])
+                .await;
+        assert!(
+            booking_list_result.is_ok(),
+            "{}",
+            booking_list_result.unwrap_err()
+        );
+        let booking_journal_ids: Vec<Uuid> = booking_list_result
+            .unwrap()
+            .iter()
+            .map(|elem| elem.finance_journal_diary_id)
+            .collect();
+        assert_eq!(
+            booking_journal_ids,
+            [
+                journal_entries_inserted[2].id,
+                journal_entries_inserted[0].id,
+                journal_entries_inserted[1].id
+            ]
+        );
+
+        let balance_info_result = booking_handle_1
+            .calculate_balance_info_till(&vec![accounts[1].id], Some(booking_time))
+            .await;
+        assert!(
+            balance_info_result.is_ok(),
+            "{}",
+            balance_info_result.unwrap_err()
+        );
+        let balance_info_list = balance_info_result.unwrap();
+        assert_eq!(balance_info_list[0].balance_type, AccountBalanceType::Debit);
+        assert_eq!(balance_info_list[0].amount, 400);
+
+        /* Test 2 closing and saldo entries share their booking times
+        checks: the journal keeps the running numbers within the same time, the saldo lookup returns the carry forward entry
+        */
+        let closing_time = booking_time + Duration::days(1);
+        let close_result = booking_handle_1
+            .close_accounts(
+                &all_account_ids[1..].to_vec(),
+                &accounts[0].id,
+                closing_time,
+            )
+            .await;
+        assert!(close_result.is_ok(), "{}", close_result.unwrap_err());
+
+        let journal_list_result = booking_handle_1
+            .list_journal_entries(Some(closing_time), None)
+            .await;
+        assert!(
+            journal_list_result.is_ok(),
+            "{}",
+            journal_list_result.unwrap_err()
+        );
+        let journal_list = journal_list_result.unwrap();
+        assert_eq!(journal_list.len(), 4);
+        for window in journal_list.windows(2) {
+            assert!(
+                (window[0].booking_time, window[0].running_number)
+                    < (window[1].booking_time, window[1].running_number)
+            );
+        }
+
+        let saldo_information_result = booking_handle_1
+            .finance_get_last_saldo_account_entries(Some(vec![accounts[1].id]))
+            .await;
+        assert!(
+            saldo_information_result.is_ok(),
+            "{}",
+            saldo_information_result.unwrap_err()
+        );
+        let saldo_information = saldo_information_result.unwrap();
+        let bank_saldo = saldo_information.get(&accounts[1].id).unwrap();
+        assert_eq!(bank_saldo.booking_time, closing_time + Duration::seconds(1));
+        assert_eq!(bank_saldo.booking_type, BookingEntryType::SaldoDebit);
+        assert_eq!(bank_saldo.amount, 400);
+    }
+
+    #[tokio::test]
+    async fn test_accounting_reports_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_accounting_reports(&dummy_connection_settings, user_id_1, &in_memory_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_reports_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_reports(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_reports_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_reports(&connection_settings, user_id_1, &postgres_db).await;
+    }
+
+    async fn check_accounting_reports<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions + DBFinanceReportSectionFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let report_handle_1 = FinanceReportHandle::new(connection_settings, &user_id_1, db_handler);
+
+        // (account title, account category)
+        let account_data = [
+            ("closing", AccountCategory::Equity),
+            ("bank", AccountCategory::Asset),
+            ("equity", AccountCategory::Equity),
+            ("income", AccountCategory::Income),
+            ("expense", AccountCategory::Expense),
+        ];
+        let mut accounts = Vec::new();
+        for (account_title, account_category) in account_data {
+            let mut finance_account_type = FinanceAccountType {
+                description: format!("type_description_{}", account_title),
+                title: format!("type_{}", account_title),
+                id: Uuid::new(),
+                account_category: account_category,
+            };
+            let insert_finance_account_type_result =
+                account_handle_1.finance_account_type_upsert(&mut finance_account_type);
+            assert!(
+                insert_finance_account_type_result.is_ok(),
+                "{}",
+                insert_finance_account_type_result.unwrap_err()
+            );
+
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        let booking_time_start = Utc::now() - Duration::days(10);
+        // the databases store the time with less precision, the report times are compared exactly
+        let closing_time = (Utc::now() - Duration::days(3)).trunc_subsecs(0);
+        // (debit account, credit account, amount, booking time)
+        let booking_data = [
+            (1, 2, 100000, booking_time_start),
+            (1, 3, 5000, booking_time_start + Duration::days(2)),
+            (4, 1, 2000, booking_time_start + Duration::days(4)),
+            (1, 3, 300, closing_time + Duration::days(2)),
+        ];
+        for (debit_index, credit_index, amount, booking_time) in booking_data {
+            let finance_booking_request = FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[debit_index].id,
+                credit_finance_account_id: accounts[credit_index].id,
+                booking_time,
+                amount,
+                title: "f_b_r".into(),
+                description: "description_f_b_r".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            };
+            let insert_request_result = booking_handle_1
+                .finance_insert_booking_entry(&finance_booking_request)
+                .await;
+            assert!(
+                insert_request_result.is_ok(),
+                "{}",
+                insert_request_result.unwrap_err()
+            );
+        }
+
+        let accounts_to_close: Vec<Uuid> = accounts[1..].iter().map(|elem| elem.id).collect();
+        let close_result = booking_handle_1
+            .close_accounts(&accounts_to_close, &accounts[0].id, closing_time)
+            .await;
+        assert!(close_result.is_ok(), "{}", close_result.unwrap_err());
+
+        /* Test 1 balance sheet after closing
+        checks: saldo entries are used, income and expense accounts are not part of the balance sheet
+        */
+        let balance_sheet_1_result = report_handle_1.balance_sheet(Utc::now()).await;
+        assert!(
+            balance_sheet_1_result.is_ok(),
+            "{}",
+            balance_sheet_1_result.unwrap_err()
+        );
+        let balance_sheet_1 = balance_sheet_1_result.unwrap();
+        assert_eq!(balance_sheet_1.sections.len(), 3);
+        assert_eq!(balance_sheet_1.sections[0].total, 103300);
+        assert_eq!(balance_sheet_1.sections[1].total, 0);
+        assert_eq!(balance_sheet_1.sections[2].total, 100000);
+        assert_eq!(balance_sheet_1.sections[2].groups.len(), 2);
+        assert_eq!(balance_sheet_1.total_assets, 103300);
+        assert_eq!(balance_sheet_1.total_liabilities_and_equity, 100000);
+        assert_eq!(balance_sheet_1.difference, 3300);
+
+        /* Test 2 balance sheet before closing
+        checks: saldo entries after the report date are ignored
+        */
+        let balance_sheet_2_result = report_handle_1
+            .balance_sheet(closing_time - Duration::days(1))
+            .await;
+        assert!(
+            balance_sheet_2_result.is_ok(),
+            "{}",
+            balance_sheet_2_result.unwrap_err()
+        );
+        let balance_sheet_2 = balance_sheet_2_result.unwrap();
+        assert_eq!(balance_sheet_2.total_assets, 103000);
+        assert_eq!(balance_sheet_2.total_liabilities_and_equity, 100000);
+        assert_eq!(balance_sheet_2.difference, 3000);
+
+        /* Test 3 income statement over the whole time
+        checks: closing and saldo entries are not part of the income statement
+        */
+        let income_statement_1_result = report_handle_1
+            .income_statement(booking_time_start - Duration::days(1), Utc::now())
+            .await;
+        assert!(
+            income_statement_1_result.is_ok(),
+            "{}",
+            income_statement_1_result.unwrap_err()
+        );
+        let income_statement_1 = income_statement_1_result.unwrap();
+        assert_eq!(income_statement_1.sections.len(), 2);
+        assert_eq!(
+            income_statement_1.sections[0].groups[0].accounts[0].amount,
+            5300
+        );
+        assert_eq!(
+            income_statement_1.sections[1].groups[0].accounts[0].amount,
+            2000
+        );
+        assert_eq!(income_statement_1.total_income, 5300);
+        assert_eq!(income_statement_1.total_expense, 2000);
+        assert_eq!(income_statement_1.net_income, 3300);
+
+        /* Test 4 income statement after closing
+        checks: only entries inside the date range are used
+        */
+        let income_statement_2_result = report_handle_1
+            .income_statement(closing_time, Utc::now())
+            .await;
+        assert!(
+            income_statement_2_result.is_ok(),
+            "{}",
+            income_statement_2_result.unwrap_err()
+        );
+        let income_statement_2 = income_statement_2_result.unwrap();
+        assert_eq!(income_statement_2.total_income, 300);
+        assert_eq!(income_statement_2.total_expense, 0);
+        assert_eq!(income_statement_2.net_income, 300);
+
+        /* Test 5 trial balance of the current period
+        checks: every account starts with its last saldo, debits equal credits
+        */
+        let trial_balance_1_result = report_handle_1.trial_balance(None, None).await;
+        assert!(
+            trial_balance_1_result.is_ok(),
+            "{}",
+            trial_balance_1_result.unwrap_err()
+        );
+        let trial_balance_1 = trial_balance_1_result.unwrap();
+        assert!(trial_balance_1.time_from.is_none());
+        assert_eq!(trial_balance_1.lines.len(), 5);
+        // (account title, debit total, credit total, balance type, balance)
+        let expected_lines = [
+            ("bank", 103300, 0, AccountBalanceType::Debit, 103300),
+            ("closing", 105000, 105000, AccountBalanceType::Debit, 0),
+            ("equity", 0, 100000, AccountBalanceType::Credit, 100000),
+            ("expense", 2000, 0, AccountBalanceType::Debit, 2000),
+            ("income", 0, 5300, AccountBalanceType::Credit, 5300),
+        ];
+        for (trial_balance_line, expected_line) in
+            trial_balance_1.lines.iter().zip(expected_lines.iter())
+        {
+            assert_eq!(trial_balance_line.account_title, expected_line.0);
+            assert_eq!(trial_balance_line.debit_total, expected_line.1);
+            assert_eq!(trial_balance_line.credit_total, expected_line.2);
+            assert_eq!(trial_balance_line.balance_type, expected_line.3);
+            assert_eq!(trial_balance_line.balance, expected_line.4);
+        }
+        assert_eq!(trial_balance_1.total_debit, 210300);
+        assert_eq!(trial_balance_1.total_credit, 210300);
+        assert_eq!(trial_balance_1.total_debit_balance, 105300);
+        assert_eq!(trial_balance_1.total_credit_balance, 105300);
+        assert!(trial_balance_1.is_balanced);
+
+        /* Test 6 trial balance before closing
+        checks: saldo after time_till is not used as period start
+        */
+        let trial_balance_2_result = report_handle_1
+            .trial_balance(None, Some(closing_time - Duration::days(1)))
+            .await;
+        assert!(
+            trial_balance_2_result.is_ok(),
+            "{}",
+            trial_balance_2_result.unwrap_err()
+        );
+        let trial_balance_2 = trial_balance_2_result.unwrap();
+        assert!(trial_balance_2.time_from.is_none());
+        assert_eq!(trial_balance_2.total_debit, 107000);
+        assert_eq!(trial_balance_2.total_credit, 107000);
+        assert_eq!(trial_balance_2.total_debit_balance, 105000);
+        assert!(trial_balance_2.is_balanced);
+
+        /* Test 7 balance sheets inside closed periods
+        a second period is closed, reports before the last saldo use the saldo of their own period
+        a) at the first closing time: the closing entries are already booked, the closed balances are reported
+        b) between the first and the second closing
+        c) at the second closing time
+        */
+        let closing_time_2 = closing_time + Duration::days(2) + Duration::hours(1);
+        let close_2_result = booking_handle_1
+            .close_accounts(&accounts_to_close, &accounts[0].id, closing_time_2)
+            .await;
+        assert!(close_2_result.is_ok(), "{}", close_2_result.unwrap_err());
+
+        // (report time, total assets, total liabilities and equity, difference)
+        let expected_balance_sheets = [
+            (closing_time, 103000, 100000, 3000),
+            (closing_time + Duration::days(1), 103000, 100000, 3000),
+            (closing_time_2, 103300, 100000, 3300),
+            (Utc::now(), 103300, 100000, 3300),
+        ];
+        for (report_time, total_assets, total_liabilities_and_equity, difference) in
+            expected_balance_sheets
+        {
+            let balance_sheet_result = report_handle_1.balance_sheet(report_time).await;
+            assert!(
+                balance_sheet_result.is_ok(),
+                "{}",
+                balance_sheet_result.unwrap_err()
+            );
+            let balance_sheet = balance_sheet_result.unwrap();
+            assert_eq!(balance_sheet.total_assets, total_assets);
+            assert_eq!(
+                balance_sheet.total_liabilities_and_equity,
+                total_liabilities_and_equity
+            );
+            assert_eq!(balance_sheet.difference, difference);
+        }
+
+        /* Test 8 report section mappings
+        a) an equity account type is moved to an own section after the default equity section
+        b) an asset account type is moved to a liability section, its balance is shown with the liability sign
+        c) a section of the other report and an empty title are rejected
+        d) an expense account type is moved to the income section, it is merged into the default section
+        e) deleting the mappings shows the account types in their default sections again
+        */
+        let equity_type_id = accounts[2].finance_account_type_id;
+        let bank_type_id = accounts[1].finance_account_type_id;
+        let expense_type_id = accounts[4].finance_account_type_id;
+
+        let upsert_own_funds_result = report_handle_1
+            .report_section_mapping_upsert(&FinanceReportSectionMapping {
+                finance_account_type_id: equity_type_id,
+                section_category: AccountCategory::Equity,
+                section_title: " own funds ".into(),
+            })
+            .await;
+        assert!(
+            upsert_own_funds_result.is_ok(),
+            "{}",
+            upsert_own_funds_result.unwrap_err()
+        );
+        let balance_sheet_8a_result = report_handle_1.balance_sheet(Utc::now()).await;
+        assert!(
+            balance_sheet_8a_result.is_ok(),
+            "{}",
+            balance_sheet_8a_result.unwrap_err()
+        );
+        let balance_sheet_8a = balance_sheet_8a_result.unwrap();
+        assert_eq!(balance_sheet_8a.sections.len(), 4);
+        assert_eq!(balance_sheet_8a.sections[2].title, "equity");
+        assert_eq!(balance_sheet_8a.sections[2].groups.len(), 1);
+        assert_eq!(balance_sheet_8a.sections[3].title, "own funds");
+        assert_eq!(balance_sheet_8a.sections[3].total, 100000);
+        assert_eq!(balance_sheet_8a.total_liabilities_and_equity, 100000);
+
+        let upsert_negative_bank_result = report_handle_1
+            .report_section_mapping_upsert(&FinanceReportSectionMapping {
+                finance_account_type_id: bank_type_id,
+                section_category: AccountCategory::Liability,
+                section_title: "bank".into(),
+            })
+            .await;
+        assert!(
+            upsert_negative_bank_result.is_ok(),
+            "{}",
+            upsert_negative_bank_result.unwrap_err()
+        );
+        let balance_sheet_8b_result = report_handle_1.balance_sheet(Utc::now()).await;
+        assert!(
+            balance_sheet_8b_result.is_ok(),
+            "{}",
+            balance_sheet_8b_result.unwrap_err()
+        );
+        let balance_sheet_8b = balance_sheet_8b_result.unwrap();
+        assert_eq!(balance_sheet_8b.sections.len(), 5);
+        assert_eq!(balance_sheet_8b.sections[0].total, 0);
+        assert_eq!(balance_sheet_8b.sections[2].title, "bank");
+        assert_eq!(balance_sheet_8b.sections[2].total, -103300);
+        assert_eq!(balance_sheet_8b.total_assets, 0);
+        assert_eq!(balance_sheet_8b.total_liabilities_and_equity, -3300);
+        assert_eq!(balance_sheet_8b.difference, 3300);
+
+        let upsert_other_report_result = report_handle_1
+            .report_section_mapping_upsert(&FinanceReportSectionMapping {
+                finance_account_type_id: expense_type_id,
+                section_category: AccountCategory::Equity,
+                section_title: "expenses".into(),
+            })
+            .await;
+        assert!(upsert_other_report_result.is_err());
+        let upsert_empty_title_result = report_handle_1
+            .report_section_mapping_upsert(&FinanceReportSectionMapping {
+                finance_account_type_id: expense_type_id,
+                section_category: AccountCategory::Expense,
+                section_title: "  ".into(),
+            })
+            .await;
+        assert!(upsert_empty_title_result.is_err());
+
+        let upsert_expense_result = report_handle_1
+            .report_section_mapping_upsert(&FinanceReportSectionMapping {
+                finance_account_type_id: expense_type_id,
+                section_category: AccountCategory::Income,
+                section_title: "income".into(),
+            })
+            .await;
+        assert!(
+            upsert_expense_result.is_ok(),
+            "{}",
+            upsert_expense_result.unwrap_err()
+        );
+        let mapping_list_result = report_handle_1.report_section_mapping_list().await;
+        assert!(
+            mapping_list_result.is_ok(),
+            "{}",
+            mapping_list_result.unwrap_err()
+        );
+        assert_eq!(mapping_list_result.unwrap().len(), 3);
+        let income_statement_8d_result = report_handle_1
+            .income_statement(booking_time_start - Duration::days(1), Utc::now())
+            .await;
+        assert!(
+            income_statement_8d_result.is_ok(),
+            "{}",
+            income_statement_8d_result.unwrap_err()
+        );
+        let income_statement_8d = income_statement_8d_result.unwrap();
+        assert_eq!(income_statement_8d.sections.len(), 2);
+        assert_eq!(income_statement_8d.sections[0].groups.len(), 2);
+        assert_eq!(income_statement_8d.sections[1].groups.len(), 0);
+        assert_eq!(income_statement_8d.total_income, 3300);
+        assert_eq!(income_statement_8d.total_expense, 0);
+        assert_eq!(income_statement_8d.net_income, 3300);
+
+        for finance_account_type_id in [equity_type_id, bank_type_id, expense_type_id] {
+            let delete_result = report_handle_1
+                .report_section_mapping_delete(&finance_account_type_id)
+                .await;
+            assert!(delete_result.is_ok(), "{}", delete_result.unwrap_err());
+        }
+        let delete_again_result = report_handle_1
+            .report_section_mapping_delete(&equity_type_id)
+            .await;
+        assert!(delete_again_result.is_err());
+        let balance_sheet_8e_result = report_handle_1.balance_sheet(Utc::now()).await;
+        assert!(
+            balance_sheet_8e_result.is_ok(),
+            "{}",
+            balance_sheet_8e_result.unwrap_err()
+        );
+        let balance_sheet_8e = balance_sheet_8e_result.unwrap();
+        assert_eq!(balance_sheet_8e.sections.len(), 3);
+        assert_eq!(balance_sheet_8e.total_assets, 103300);
+        assert_eq!(balance_sheet_8e.total_liabilities_and_equity, 100000);
+
+        /* Test 9 trial balance with accounts closed at different times
+        a cash account is balanced again before the third closing, so it gets no saldo
+        checks: every account starts with its own last saldo, the cash account with its first booking
+        */
+        let mut cash_account_type = FinanceAccountType {
+            description: "type_description_cash".into(),
+            title: "type_cash".into(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_cash_account_type_result =
+            account_handle_1.finance_account_type_upsert(&mut cash_account_type);
+        assert!(
+            insert_cash_account_type_result.is_ok(),
+            "{}",
+            insert_cash_account_type_result.unwrap_err()
+        );
+        let cash_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: cash_account_type.id,
+            title: "cash".into(),
+            description: "description_cash".into(),
+            account_number: None,
+        };
+        let insert_cash_account_result = account_handle_1.finance_account_upsert(&cash_account);
+        assert!(
+            insert_cash_account_result.is_ok(),
+            "{}",
+            insert_cash_account_result.unwrap_err()
+        );
+        let closing_time_3 = (Utc::now() - Duration::minutes(1)).trunc_subsecs(0);
+        // (debit account, credit account, booking time)
+        let cash_booking_data = [
+            (
+                cash_account.id,
+                accounts[1].id,
+                closing_time_3 - Duration::minutes(2),
+            ),
+            (
+                accounts[1].id,
+                cash_account.id,
+                closing_time_3 - Duration::minutes(1),
+            ),
+        ];
+        for (debit_finance_account_id, credit_finance_account_id, booking_time) in cash_booking_data
+        {
+            let finance_booking_request = FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id,
+                credit_finance_account_id,
+                booking_time,
+                amount: 500,
+                title: "f_b_r".into(),
+                description: "description_f_b_r".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            };
+            let insert_request_result = booking_handle_1
+                .finance_insert_booking_entry(&finance_booking_request)
+                .await;
+            assert!(
+                insert_request_result.is_ok(),
+                "{}",
+                insert_request_result.unwrap_err()
+            );
+        }
+        let mut accounts_to_close_3 = accounts_to_close.clone();
+        accounts_to_close_3.push(cash_account.id);
+        let close_3_result = booking_handle_1
+            .close_accounts(&accounts_to_close_3, &accounts[0].id, closing_time_3)
+            .await;
+        assert!(close_3_result.is_ok(), "{}", close_3_result.unwrap_err());
+
+        let trial_balance_3_result = report_handle_1.trial_balance(None, None).await;
+        assert!(
+            trial_balance_3_result.is_ok(),
+            "{}",
+            trial_balance_3_result.unwrap_err()
+        );
+        let trial_balance_3 = trial_balance_3_result.unwrap();
+        assert!(trial_balance_3.time_from.is_none());
+        assert_eq!(trial_balance_3.lines.len(), 6);
+        let bank_line = &trial_balance_3.lines[0];
+        assert_eq!(bank_line.account_title, "bank");
+        assert_eq!(
+            bank_line.time_from,
+            Some(closing_time_3 + Duration::seconds(1))
+        );
+        assert_eq!(bank_line.debit_total, 103300);
+        assert_eq!(bank_line.credit_total, 0);
+        let cash_line = &trial_balance_3.lines[1];
+        assert_eq!(cash_line.account_title, "cash");
+        assert!(cash_line.time_from.is_none());
+        assert_eq!(cash_line.debit_total, 500);
+        assert_eq!(cash_line.credit_total, 500);
+        assert_eq!(cash_line.balance, 0);
+        assert_eq!(trial_balance_3.total_debit_balance, 105300);
+        assert_eq!(trial_balance_3.total_credit_balance, 105300);
+        assert!(trial_balance_3.is_balanced);
+    }
+
+    #[tokio::test]
+    async fn test_accounting_booking_with_mongodb() {
+        testing_accounting_config::test_accounting_handle::init();
+        //let local_setting: SettingStruct = SettingStruct::global().clone();
+        let test_setting = TestSettingStruct::global().clone();
+        let db_connection = DbConnectionSetting {
+            url: String::from(test_setting.backend_database_url),
+            user: String::from(test_setting.backend_database_user),
+            password: String::from(test_setting.backend_database_password),
+            instance: String::from(test_setting.backend_database_instance),
+        };
+        //
+        let credentials = UserCredentials {
+            username: test_setting.test_user_account_user_login,
+            password: test_setting.test_user_account_user_password.into(),
+        };
+
+        let mongo_db = DbHandlerMongoDB::new(&db_connection);
+        let validate_result = validate_credentials(&db_connection, &mongo_db, &credentials).await;
+        if validate_result.is_err() {
+            panic!(
+                "test user {} not valid: {}",
+                credentials.username,
+                validate_result.unwrap_err()
+            );
+        }
+
+        let user_id_1 = validate_result.unwrap();
+
+        let account_handle_1 =
+            FinanceAccountingConfigHandle::new(&db_connection, &user_id_1, &mongo_db);
+        let booking_handle_1 = FinanceBookingHandle::new(&db_connection, &user_id_1, &mongo_db);
+
+        let repair_result = mongo_db
+            .repair_counter_record_for_user(&db_connection, &user_id_1)
+            .await;
+        assert!(repair_result.is_ok(), "{}", repair_result.unwrap_err());
+        let _ = prepare_mongo_db_for_tests(&account_handle_1, &booking_handle_1, &db_connection);
+
+        /* Test 0, further requirements
+         * ensure that at least 4 finance accounts are available, at least 2 accounta with debit balance, at least 2 accounts with debit balance
+         * get balance information for each account
+         * get last booking entry for each account
+         */
+        let accounts_per_user_result = account_handle_1.finance_account_list_async(None).await;
+        assert!(
+            accounts_per_user_result.is_ok(),
+            "{}",
+            accounts_per_user_result.unwrap_err()
+        );
+        let accounts_per_user = accounts_per_user_result.unwrap();
+
+        let account_ids: Vec<Uuid> = accounts_per_user.iter().map(|elem| elem.id).collect();
+        let balance_info_all_accounts_result =
+            booking_handle_1.calculate_balance_info(&account_ids).await;
+        assert!(
+            balance_info_all_accounts_result.is_ok(),
+            "{}",
+            balance_info_all_accounts_result.unwrap_err()
+        );
+        let balance_info = balance_info_all_accounts_result.unwrap();
+
+        let debit_accounts_info: Vec<&AccountBalanceInfo> = balance_info
+            .iter()
+            .filter(|elem| elem.balance_type.eq(&AccountBalanceType::Debit))
+            .collect();
+        let credit_accounts_info: Vec<&AccountBalanceInfo> = balance_info
+            .iter()
+            .filter(|elem| elem.balance_type.eq(&AccountBalanceType::Credit))
+            .collect();
+        assert!(
+            debit_accounts_info.len() > 1,
+            "Not enought accounts with debit balance"
+        );
+        assert!(
+            credit_accounts_info.len() > 1,
+            "Not enought accounts with credit balance"
+        );
+
+        let test_account_a_balance_info = debit_accounts_info[0];
+        let test_account_b_balance_info = debit_accounts_info[1];
+        let test_account_c_balance_info = credit_accounts_info[0];
+        let test_account_d_balance_info = credit_accounts_info[1];
+        let list_test_accounts_ids = vec![
+            test_account_a_balance_info.account_id,
+            test_account_b_balance_info.account_id,
+            test_account_c_balance_info.account_id,
+            test_account_d_balance_info.account_id,
+        ];
+        let mut hashmap_last_booking_entries_per_account = HashMap::new();
+        let mut max_booking_time = Utc
+            .with_ymd_and_hms(Utc::now().year(), 1, 1, 10, 15, 25)
+            .unwrap();
+        for account_id in list_test_accounts_ids.iter() {
+            let account_entries_result = booking_handle_1
+                .list_account_booking_entries(vec![
+                    FinanceAccountBookingEntryListSearchOption::new(&account_id, None, None),
+                ])
+                .await;
+            assert!(
+                account_entries_result.is_ok(),
+                "{}",
+                account_entries_result.unwrap_err()
+            );
+            let account_entries = account_entries_result.unwrap();
+            let latest_pos_option = account_entries.iter().max_by_key(|elem| elem.booking_time);
+            if latest_pos_option.is_some() {
+                let latest_pos = latest_pos_option.unwrap();
+                hashmap_last_booking_entries_per_account.insert(account_id, latest_pos.clone());
+                if latest_pos.booking_time.gt(&max_booking_time) {
+                    max_booking_time = latest_pos.booking_time;
+                }
+            }
+        }
+
+        /* Test 1, change balance, credit to debit
+        select 4 accounts A, B, C and D, so that a and B have a debit balance, C and D a credit balance (see above)
+        insert amount 1 with A to B
+        check balance information for all 4 accounts
+        insert amount 2 with C to D
+        check balance information for all 4 accounts
+        insert amount 3 with B to C, so that balance type switches
+        check balance information  for all 4 accounts
+        insert amount 4 with A to D, so that balance type switches
+        check balance information for all 4 accounts
+         */
+        let test_run_id = Uuid::new().to_string();
+        let booking_time_1 = max_booking_time + Duration::hours(1);
+        let amount_a_b = std::cmp::min(
+            test_account_a_balance_info.amount,
+            test_account_b_balance_info.amount,
+        ) / 2;
+        let insert_request_a_b = FinanceBookingRequest {
+            amount: amount_a_b,
+            booking_time: booking_time_1,
+            credit_finance_account_id: test_account_a_balance_info.account_id,
+            debit_finance_account_id: test_account_b_balance_info.account_id,
+            description: format!("A to B: {}, test run {}", amount_a_b, test_run_id),
+            title: "A to B".into(),
+            is_saldo: false,
+            is_simple_entry: true,
+            tags: Vec::new(),
+            import_reference: None,
+        };
+        let insert_request_a_b_result = booking_handle_1
+            .finance_insert_booking_entry(&insert_request_a_b)
+            .await;
+        assert!(
+            insert_request_a_b_result.is_ok(),
+            "{}",
+            insert_request_a_b_result.unwrap_err()
+        );
+        let balance_info_1_accounts_result = booking_handle_1
+            .calculate_balance_info(&list_test_accounts_ids)
+            .await;
+        assert!(
+            balance_info_1_accounts_result.is_ok(),
+            "{}",
+            balance_info_1_accounts_result.unwrap_err()
+        );
+        let test_account_a_balance_info_1 = AccountBalanceInfo {
+            account_id: test_account_a_balance_info.account_id,
+            amount: test_account_a_balance_info.amount.abs_diff(amount_a_b),
+            balance_type: test_account_a_balance_info.balance_type.clone(),
+            account_category: test_account_a_balance_info.account_category.clone(),
+        };
+        let test_account_b_balance_info_1 = AccountBalanceInfo {
+            account_id: test_account_b_balance_info.account_id,
+            amount: test_account_b_balance_info.amount + amount_a_b,
+            balance_type: test_account_b_balance_info.balance_type.clone(),
+            account_category: test_account_b_balance_info.account_category.clone(),
+        };
+        let balance_check_1_result = check_multiple_balance_info(
+            &balance_info_1_accounts_result.unwrap(),
+            &vec![
+                &test_account_a_balance_info_1,
+                &test_account_b_balance_info_1,
+                test_account_c_balance_info,
+                test_account_d_balance_info,
+            ],
+        );
+        assert_eq!(balance_check_1_result, "");
+
+        let booking_time_2 = booking_time_1 + Duration::hours(1);
+        let amount_c_d = std::cmp::min(
+            test_account_c_balance_info.amount,
+            test_account_d_balance_info.amount,
+        ) / 2;
+        let insert_request_c_d = FinanceBookingRequest {
+            amount: amount_c_d,
+            booking_time: booking_time_2,
+            credit_finance_account_id: test_account_c_balance_info.account_id,
+            debit_finance_account_id: test_account_d_balance_info.account_id,
+            description: format!("C to D: {}, test run {}", amount_c_d, test_run_id),
+            title: "C to D".into(),
+            is_saldo: false,
             is_simple_entry: true,
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_request_c_d_result = booking_handle_1
             .finance_insert_booking_entry(&insert_request_c_d)
@@ -1428,11 +3312,13 @@ mod test_accounting_handle {
             account_id: test_account_c_balance_info.account_id,
             amount: test_account_c_balance_info.amount + amount_c_d,
             balance_type: test_account_c_balance_info.balance_type.clone(),
+            account_category: test_account_c_balance_info.account_category.clone(),
         };
         let test_account_d_balance_info_2 = AccountBalanceInfo {
             account_id: test_account_d_balance_info.account_id,
             amount: test_account_d_balance_info.amount.abs_diff(amount_c_d),
             balance_type: test_account_d_balance_info.balance_type.clone(),
+            account_category: test_account_d_balance_info.account_category.clone(),
         };
         let balance_check_2_result = check_multiple_balance_info(
             &balance_info_2_accounts_result.unwrap(),
@@ -1459,6 +3345,8 @@ mod test_accounting_handle {
             title: "B to C".into(),
             is_saldo: false,
             is_simple_entry: true,
+            tags: Vec::new(),
+            import_reference: None,
         };
 
         let insert_request_b_c_result = booking_handle_1
@@ -1482,11 +3370,13 @@ mod test_accounting_handle {
             account_id: test_account_b_balance_info.account_id,
             amount: test_account_b_balance_info_1.amount.abs_diff(amount_b_c),
             balance_type: AccountBalanceType::Credit,
+            account_category: test_account_b_balance_info.account_category.clone(),
         };
         let test_account_c_balance_info_3 = AccountBalanceInfo {
             account_id: test_account_c_balance_info.account_id,
             amount: test_account_c_balance_info_2.amount.abs_diff(amount_b_c),
             balance_type: AccountBalanceType::Debit,
+            account_category: test_account_c_balance_info.account_category.clone(),
         };
         let balance_check_3_result = check_multiple_balance_info(
             &balance_info_3_accounts_result.unwrap(),
@@ -1513,6 +3403,8 @@ mod test_accounting_handle {
             title: "A to D".into(),
             is_saldo: false,
             is_simple_entry: true,
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_request_a_d_result = booking_handle_1
             .finance_insert_booking_entry(&insert_request_a_d)
@@ -1534,11 +3426,13 @@ mod test_accounting_handle {
             account_id: test_account_a_balance_info.account_id,
             amount: test_account_a_balance_info_1.amount.abs_diff(amount_a_d),
             balance_type: AccountBalanceType::Credit,
+            account_category: test_account_a_balance_info.account_category.clone(),
         };
         let test_account_d_balance_info_4 = AccountBalanceInfo {
             account_id: test_account_d_balance_info.account_id,
             amount: test_account_d_balance_info_2.amount.abs_diff(amount_a_d),
             balance_type: AccountBalanceType::Debit,
+            account_category: test_account_d_balance_info.account_category.clone(),
         };
 
         let balance_check_4_result = check_multiple_balance_info(
@@ -1587,6 +3481,8 @@ mod test_accounting_handle {
             title: "A to A".into(),
             is_saldo: false,
             is_simple_entry: true,
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_request_a_a_response_result = booking_handle_1
             .finance_insert_booking_entry(&insert_request_a_a)
@@ -1601,6 +3497,7 @@ mod test_accounting_handle {
             finance_account_type_id: accounts_per_user[0].finance_account_type_id,
             description: format!("description for invalid account, test run {}", test_run_id),
             title: "invalid account".into(),
+            account_number: None,
         };
         let booking_time_6 = booking_time_5 + Duration::hours(1);
         let amount_a_i = std::cmp::max(
@@ -1616,6 +3513,8 @@ mod test_accounting_handle {
             title: "A to I".into(),
             is_saldo: false,
             is_simple_entry: true,
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_request_a_i_response_result = booking_handle_1
             .finance_insert_booking_entry(&insert_request_a_i)
@@ -1639,6 +3538,8 @@ mod test_accounting_handle {
             title: "I to A".into(),
             is_saldo: false,
             is_simple_entry: true,
+            tags: Vec::new(),
+            import_reference: None,
         };
         let insert_request_i_a_response_result = booking_handle_1
             .finance_insert_booking_entry(&insert_request_i_a)
@@ -1923,6 +3824,17 @@ mod test_accounting_handle {
                     balance_expected.balance_type
                 );
             }
+            if balance_to_check
+                .get_signed_amount()
+                .ne(&balance_expected.get_signed_amount())
+            {
+                return format!(
+                    "signed amount for account {} does not match: {} instead of {}",
+                    balance_to_check.account_id,
+                    balance_to_check.get_signed_amount(),
+                    balance_expected.get_signed_amount()
+                );
+            }
         }
 
         return "".into();
@@ -2046,6 +3958,8 @@ mod test_accounting_handle {
                             title: "Prepare".into(),
                             is_saldo: false,
                             is_simple_entry: true,
+                            tags: Vec::new(),
+                            import_reference: None,
                         };
                         let insert_request_mod_response_result = futures::executor::block_on(
                             booking_handle_1.finance_insert_booking_entry(&insert_request_mod),
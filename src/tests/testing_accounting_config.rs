@@ -6,14 +6,23 @@ pub(crate) mod test_accounting_handle {
     use mongodb::bson::Uuid;
 
     use crate::{
+        accounting_config_database::DBFinanceConfigFunctions,
         accounting_config_logic::FinanceAccountingConfigHandle,
         database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB},
-        datatypes::{FinanceAccount, FinanceAccountType},
+        database_handler_postgres::DbHandlerPostgres,
+        database_handler_sqlite::DbHandlerSqlite,
+        datatypes::{AccountCategory, FinanceAccount, FinanceAccountType},
         password_handle::{validate_credentials, UserCredentials},
         setting_struct::{self, SettingStruct, TestSettingStruct},
         tests::{
             mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
             testing_accounting_config::test_accounting_handle,
+            testing_database_postgres::test_database_postgres::{
+                create_postgres_test_connection, create_postgres_test_user,
+            },
+            testing_database_sqlite::test_database_sqlite::{
+                create_sqlite_test_connection, create_sqlite_test_user,
+            },
         },
     };
 
@@ -45,26 +54,77 @@ pub(crate) mod test_accounting_handle {
 
         let in_memory_db = InMemoryDatabaseHandler {};
 
-        let account_handle_1 = FinanceAccountingConfigHandle::new(
+        check_accounting_type_config_handling(
             &dummy_connection_settings,
-            &user_id_1,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            user_id_4,
             &in_memory_db,
-        );
-        let mut account_handle_2 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_2,
-            &in_memory_db,
-        );
-        let mut account_handle_3 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_3,
-            &in_memory_db,
-        );
-        let mut account_handle_4 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_4,
-            &in_memory_db,
-        );
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_type_config_handling_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+        let user_id_2 = create_sqlite_test_user(&connection_settings, "sqlite_user_2").await;
+        let user_id_3 = create_sqlite_test_user(&connection_settings, "sqlite_user_3").await;
+        let user_id_4 = Uuid::new();
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_type_config_handling(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            user_id_4,
+            &sqlite_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_type_config_handling_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+        let user_id_2 = create_postgres_test_user(&connection_settings, "postgres_user_2").await;
+        let user_id_3 = create_postgres_test_user(&connection_settings, "postgres_user_3").await;
+        let user_id_4 = Uuid::new();
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_type_config_handling(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            user_id_4,
+            &postgres_db,
+        )
+        .await;
+    }
+
+    async fn check_accounting_type_config_handling<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        user_id_2: Uuid,
+        user_id_3: Uuid,
+        user_id_4: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions,
+    {
+        let account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+        let mut account_handle_2 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_2, db_handler);
+        let mut account_handle_3 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_3, db_handler);
+        let mut account_handle_4 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_4, db_handler);
 
         //prepare data
         //empty list
@@ -74,6 +134,7 @@ pub(crate) mod test_accounting_handle {
             description: "SomeTypeDescription".to_string(),
             title: "SomeType".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let insert_result_1 =
             account_handle_2.finance_account_type_upsert(&mut finance_account_type_1.clone());
@@ -82,11 +143,13 @@ pub(crate) mod test_accounting_handle {
             description: "SomeTypeDescription2".to_string(),
             title: "SomeType2".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let finance_account_type_3 = FinanceAccountType {
             description: "SomeTypeDescription3".to_string(),
             title: "SomeType3".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let insert_result_2 =
             account_handle_3.finance_account_type_upsert(&mut finance_account_type_2.clone());
@@ -101,6 +164,7 @@ pub(crate) mod test_accounting_handle {
             description: "SomeTypeDescription4".to_string(),
             title: "SomeType4".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let insert_result_4 =
             account_handle_4.finance_account_type_upsert(&mut finance_account_type_4.clone());
@@ -161,7 +225,8 @@ pub(crate) mod test_accounting_handle {
             password: test_setting.test_user_account_user_password.into(),
         };
 
-        let validate_result = validate_credentials(&db_connection, &credentials).await;
+        let mongo_db = DbHandlerMongoDB::new(&db_connection);
+        let validate_result = validate_credentials(&db_connection, &mongo_db, &credentials).await;
         if validate_result.is_err() {
             panic!(
                 "test user {} not valid: {}",
@@ -171,7 +236,6 @@ pub(crate) mod test_accounting_handle {
         }
 
         let user_id_1 = validate_result.unwrap();
-        let mongo_db = DbHandlerMongoDB::new(&db_connection);
 
         let mut account_handle_1 =
             FinanceAccountingConfigHandle::new(&db_connection, &user_id_1, &mongo_db);
@@ -184,6 +248,7 @@ pub(crate) mod test_accounting_handle {
             description: "SomeTypeDescription_".to_string() + &Uuid::new().to_string(),
             title: "SomeType_".to_string() + &Uuid::new().to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let insert_result_1 =
             account_handle_1.finance_account_type_upsert(&mut finance_account_type_1.clone());
@@ -191,6 +256,7 @@ pub(crate) mod test_accounting_handle {
             description: "SomeTypeDescription2_".to_string() + &Uuid::new().to_string(),
             title: "SomeType2_".to_string() + &Uuid::new().to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let insert_result_2 =
             account_handle_1.finance_account_type_upsert(&mut finance_account_type_2.clone());
@@ -260,23 +326,6 @@ pub(crate) mod test_accounting_handle {
         let user_id_2 = Uuid::new();
         let user_id_3 = Uuid::new();
 
-        let mut account_handle_1: FinanceAccountingConfigHandle =
-            FinanceAccountingConfigHandle::new(
-                &dummy_connection_settings,
-                &user_id_1,
-                &in_memory_db,
-            );
-        let mut account_handle_2 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_2,
-            &in_memory_db,
-        );
-        let mut account_handle_3 = FinanceAccountingConfigHandle::new(
-            &dummy_connection_settings,
-            &user_id_3,
-            &in_memory_db,
-        );
-
         let entry_object1 =
             InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
         let entry_object2 =
@@ -290,20 +339,87 @@ pub(crate) mod test_accounting_handle {
             "Could not prepare database for test"
         );
 
+        check_accounting_config_handle(
+            &dummy_connection_settings,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            &in_memory_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_config_handle_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+        let user_id_2 = create_sqlite_test_user(&connection_settings, "sqlite_user_2").await;
+        let user_id_3 = Uuid::new();
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_accounting_config_handle(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            &sqlite_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_accounting_config_handle_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+        let user_id_2 = create_postgres_test_user(&connection_settings, "postgres_user_2").await;
+        let user_id_3 = Uuid::new();
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_accounting_config_handle(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            user_id_3,
+            &postgres_db,
+        )
+        .await;
+    }
+
+    async fn check_accounting_config_handle<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        user_id_2: Uuid,
+        user_id_3: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions,
+    {
+        let mut account_handle_1: FinanceAccountingConfigHandle =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+        let mut account_handle_2 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_2, db_handler);
+        let mut account_handle_3 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_3, db_handler);
+
         let finance_account_type_a_1 = FinanceAccountType {
             description: "SomeTypeDescription_a_1".to_string(),
             title: "SomeType_a_1".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let finance_account_type_a_2 = FinanceAccountType {
             description: "SomeTypeDescription_a_2".to_string(),
             title: "SomeType_a_2".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let finance_account_type_b_1 = FinanceAccountType {
             description: "SomeTypeDescription_b_1".to_string(),
             title: "SomeType_b_1".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let insert_result_fat_a1 =
             account_handle_1.finance_account_type_upsert(&mut finance_account_type_a_1.clone());
@@ -333,18 +449,21 @@ pub(crate) mod test_accounting_handle {
             finance_account_type_id: finance_account_type_a_1.id,
             title: "account_1_1".into(),
             description: "description_1_1".into(),
+            account_number: None,
         };
         let finance_account_1_2 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_a_2.id,
             title: "account_1_2".into(),
             description: "description_1_2".into(),
+            account_number: None,
         };
         let finance_account_1_3 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_a_1.id,
             title: "account_1_3".into(),
             description: "description_1_3".into(),
+            account_number: None,
         };
         let list_0_result = account_handle_1.finance_account_list(None);
         let insert_1_result = account_handle_1.finance_account_upsert(&finance_account_1_1);
@@ -393,17 +512,20 @@ pub(crate) mod test_accounting_handle {
             finance_account_type_id: finance_account_type_b_1.id,
             title: "account_2_1".into(),
             description: "description_2_1".into(),
+            account_number: None,
         };
         let finance_account_type_b_2 = FinanceAccountType {
             description: "SomeTypeDescription_b_2".to_string(),
             title: "SomeType_b_2".to_string(),
             id: Uuid::new(),
+            account_category: AccountCategory::Asset,
         };
         let finance_account_2_2 = FinanceAccount {
             id: Uuid::new(),
             finance_account_type_id: finance_account_type_b_2.id,
             title: "account_2_2".into(),
             description: "description_2_2".into(),
+            account_number: None,
         };
         let list_2_0_result = account_handle_2.finance_account_list(None);
         let insert_2_1_result = account_handle_2.finance_account_upsert(&finance_account_2_1);
@@ -442,6 +564,7 @@ pub(crate) mod test_accounting_handle {
             finance_account_type_id: finance_account_1_2.finance_account_type_id,
             title: "Update_1_2".into(),
             description: "Another description for 1_2".into(),
+            account_number: None,
         };
         let upsert_result = account_handle_1.finance_account_upsert(&finance_account_1_2_update);
         let list_update_result = account_handle_1.finance_account_list(None);
@@ -534,7 +657,8 @@ pub(crate) mod test_accounting_handle {
             password: test_setting.test_user_account_user_password.into(),
         };
 
-        let validate_result = validate_credentials(&db_connection, &credentials).await;
+        let mongo_db = DbHandlerMongoDB::new(&db_connection);
+        let validate_result = validate_credentials(&db_connection, &mongo_db, &credentials).await;
         if validate_result.is_err() {
             panic!(
                 "test user {} not valid: {}",
@@ -544,7 +668,6 @@ pub(crate) mod test_accounting_handle {
         }
 
         let user_id_1 = validate_result.unwrap();
-        let mongo_db = DbHandlerMongoDB::new(&db_connection);
 
         let mut account_handle_1 =
             FinanceAccountingConfigHandle::new(&db_connection, &user_id_1, &mongo_db);
@@ -576,12 +699,14 @@ pub(crate) mod test_accounting_handle {
             finance_account_type_id: available_finance_account_type[0].id,
             title: "SomeTitle".to_string() + &id1.to_string(),
             description: "some Decription for ".to_string() + &id1.to_string(),
+            account_number: None,
         };
         let account_2 = FinanceAccount {
             id: id2,
             finance_account_type_id: available_finance_account_type[0].id,
             title: "SomeTitle".to_string() + &id2.to_string(),
             description: "some Decription for ".to_string() + &id2.to_string(),
+            account_number: None,
         };
         let list_accounts_0_result = account_handle_1.finance_account_list(None);
         let insert_1_result = account_handle_1.finance_account_upsert(&account_1);
@@ -631,6 +756,7 @@ pub(crate) mod test_accounting_handle {
             finance_account_type_id: account_2.finance_account_type_id,
             title: "Updated".to_string() + &account_2.id.to_string(),
             description: "changed description".to_string() + &account_2.id.to_string(),
+            account_number: None,
         };
         let insert_updated_result = account_handle_1.finance_account_upsert(&account_updated);
         let list_updated_result = account_handle_1.finance_account_list(None);
@@ -0,0 +1,251 @@
+#[cfg(test)]
+mod test_attachment_handle {
+    use async_session::chrono::{Duration, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        attachment_logic::{AttachmentStorageSetting, FinanceAttachmentHandle},
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{AccountCategory, FinanceAccount, FinanceAccountType, FinanceBookingRequest},
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    const PDF_CONTENT: &[u8] = b"%PDF-1.4\n1 0 obj\n<< /Type /Catalog >>\nendobj\n%%EOF\n";
+
+    #[test]
+    fn test_attachment_mime_type_detection() {
+        assert_eq!(
+            FinanceAttachmentHandle::detect_mime_type(PDF_CONTENT),
+            Some("application/pdf")
+        );
+        assert_eq!(
+            FinanceAttachmentHandle::detect_mime_type(&[
+                0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A, 0x00
+            ]),
+            Some("image/png")
+        );
+        assert_eq!(
+            FinanceAttachmentHandle::detect_mime_type(&[0xFF, 0xD8, 0xFF, 0xE0]),
+            Some("image/jpeg")
+        );
+        assert_eq!(
+            FinanceAttachmentHandle::detect_mime_type(b"GIF89a...."),
+            Some("image/gif")
+        );
+        assert_eq!(
+            FinanceAttachmentHandle::detect_mime_type(b"RIFF\x10\x00\x00\x00WEBPVP8 "),
+            Some("image/webp")
+        );
+        assert_eq!(
+            FinanceAttachmentHandle::detect_mime_type(b"RIFF\x10\x00\x00\x00WAVEfmt "),
+            None
+        );
+        assert_eq!(
+            FinanceAttachmentHandle::detect_mime_type(b"<html><script></script></html>"),
+            None
+        );
+        assert_eq!(FinanceAttachmentHandle::detect_mime_type(b""), None);
+    }
+
+    #[tokio::test]
+    async fn test_attachment_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+        let user_id_2 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let entry_object2 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_2);
+
+        let _insert_result = InMemoryDatabaseData::insert_in_memory_database(Vec::from([
+            entry_object1,
+            entry_object2,
+        ]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let storage_setting = AttachmentStorageSetting {
+            storage_directory: std::env::temp_dir()
+                .join(format!("finance_attachment_test_{}", Uuid::new())),
+            max_size_bytes: 1024,
+        };
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let attachment_handle_1 = FinanceAttachmentHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+            &storage_setting,
+        );
+        let attachment_handle_2 = FinanceAttachmentHandle::new(
+            &dummy_connection_settings,
+            &user_id_2,
+            &in_memory_db,
+            &storage_setting,
+        );
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["office supplies", "bank"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        let booking_request = FinanceBookingRequest {
+            is_simple_entry: true,
+            is_saldo: false,
+            debit_finance_account_id: accounts[0].id,
+            credit_finance_account_id: accounts[1].id,
+            booking_time: Utc::now() - Duration::days(2),
+            amount: 2599,
+            title: "printer paper".into(),
+            description: "invoice 4711".into(),
+            tags: Vec::new(),
+            import_reference: None,
+        };
+        let booking_result = booking_handle_1
+            .finance_insert_booking_entry(&booking_request)
+            .await;
+        assert!(booking_result.is_ok(), "{}", booking_result.unwrap_err());
+        let journal_entry_id = booking_result.unwrap().journal_entry.id;
+
+        // 1) upload a receipt, the directory part of the file name is dropped
+        let upload_result = attachment_handle_1
+            .attachment_upload(
+                &journal_entry_id,
+                "C:\\scans\\invoice_4711.pdf",
+                "application/pdf",
+                PDF_CONTENT,
+            )
+            .await;
+        assert!(upload_result.is_ok(), "{}", upload_result.unwrap_err());
+        let attachment = upload_result.unwrap();
+        assert_eq!(attachment.file_name, "invoice_4711.pdf");
+        assert_eq!(attachment.mime_type, "application/pdf");
+        assert_eq!(attachment.size_bytes, PDF_CONTENT.len() as u64);
+
+        let list_result = attachment_handle_1
+            .attachment_list(Some(journal_entry_id))
+            .await;
+        assert_eq!(list_result.unwrap(), vec![attachment.clone()]);
+
+        let download_result = attachment_handle_1
+            .attachment_download(&attachment.id)
+            .await;
+        assert!(download_result.is_ok(), "{}", download_result.unwrap_err());
+        let (downloaded_attachment, downloaded_content) = download_result.unwrap();
+        assert_eq!(downloaded_attachment, attachment);
+        assert_eq!(downloaded_content, PDF_CONTENT);
+
+        // 2) another user can neither download the file nor attach to the entry
+        assert!(attachment_handle_2
+            .attachment_download(&attachment.id)
+            .await
+            .is_err());
+        assert!(attachment_handle_2
+            .attachment_upload(&journal_entry_id, "other.pdf", "", PDF_CONTENT)
+            .await
+            .is_err());
+        assert_eq!(
+            attachment_handle_2
+                .attachment_list(None)
+                .await
+                .unwrap()
+                .len(),
+            0
+        );
+
+        // 3) size and type limits
+        let too_large_content = [PDF_CONTENT, &[b' '; 1024]].concat();
+        let invalid_uploads: [(&str, &[u8]); 4] = [
+            ("application/pdf", &too_large_content),
+            ("application/pdf", b""),
+            ("text/html", b"<html><script></script></html>"),
+            ("image/png", PDF_CONTENT),
+        ];
+        for (declared_mime_type, content) in invalid_uploads {
+            assert!(
+                attachment_handle_1
+                    .attachment_upload(&journal_entry_id, "scan", declared_mime_type, content)
+                    .await
+                    .is_err(),
+                "upload declared as {} should be rejected",
+                declared_mime_type
+            );
+        }
+        assert!(attachment_handle_1
+            .attachment_upload(&Uuid::new(), "scan.pdf", "application/pdf", PDF_CONTENT)
+            .await
+            .is_err());
+        // a generic declared type is checked against the content only
+        let generic_upload_result = attachment_handle_1
+            .attachment_upload(
+                &journal_entry_id,
+                "scan",
+                "application/octet-stream",
+                PDF_CONTENT,
+            )
+            .await;
+        assert!(
+            generic_upload_result.is_ok(),
+            "{}",
+            generic_upload_result.unwrap_err()
+        );
+
+        // 4) the attachments stay with a reversed entry
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_id, &"wrong account".into())
+            .await;
+        assert!(reverse_result.is_ok(), "{}", reverse_result.unwrap_err());
+        let list_result = attachment_handle_1
+            .attachment_list(Some(journal_entry_id))
+            .await;
+        assert_eq!(list_result.unwrap().len(), 2);
+        assert!(attachment_handle_1
+            .attachment_download(&attachment.id)
+            .await
+            .is_ok());
+
+        let _remove_result = std::fs::remove_dir_all(&storage_setting.storage_directory);
+    }
+}
@@ -0,0 +1,536 @@
+#[cfg(test)]
+mod test_backup {
+    use std::collections::HashMap;
+
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_database::DBFinanceAccountingFunctions,
+        accounting_logic::FinanceBookingHandle,
+        backup::FinanceBackupHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        database_handler_postgres::DbHandlerPostgres,
+        database_handler_sqlite::DbHandlerSqlite,
+        datatypes::{
+            AccountCategory, FinanceAccount, FinanceAccountType, FinanceBackupArchive,
+            FinanceBookingLeg, FinanceBookingRequest, FinanceSplitBookingRequest,
+        },
+        tests::{
+            mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+            testing_database_postgres::test_database_postgres::{
+                create_postgres_test_connection, create_postgres_test_user,
+            },
+            testing_database_sqlite::test_database_sqlite::{
+                create_sqlite_test_connection, create_sqlite_test_user,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_backup_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+        let user_id_2 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let entry_object2 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_2);
+
+        let _insert_result = InMemoryDatabaseData::insert_in_memory_database(Vec::from([
+            entry_object1,
+            entry_object2,
+        ]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let booking_handle_2 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_2, &in_memory_db);
+        let backup_handle_1 =
+            FinanceBackupHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let backup_handle_2 =
+            FinanceBackupHandle::new(&dummy_connection_settings, &user_id_2, &in_memory_db);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for (account_title, account_number) in
+            [("bank", Some(1200)), ("cash", None), ("shop", Some(4900))]
+        {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        let saldo_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: true,
+                debit_finance_account_id: accounts[0].id,
+                credit_finance_account_id: accounts[1].id,
+                booking_time: Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+                amount: 100000,
+                title: "opening".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(saldo_result.is_ok(), "{}", saldo_result.unwrap_err());
+        let split_result = booking_handle_1
+            .finance_insert_split_booking_entry(&FinanceSplitBookingRequest {
+                is_saldo: false,
+                is_closing: false,
+                debit_legs: vec![
+                    FinanceBookingLeg {
+                        finance_account_id: accounts[2].id,
+                        amount: 1500,
+                    },
+                    FinanceBookingLeg {
+                        finance_account_id: accounts[1].id,
+                        amount: 500,
+                    },
+                ],
+                credit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: accounts[0].id,
+                    amount: 2000,
+                }],
+                booking_time: Utc.with_ymd_and_hms(2024, 2, 1, 12, 0, 0).unwrap(),
+                title: "market and cash".into(),
+                description: "".into(),
+                tags: vec!["market".into()],
+                import_reference: Some("FITID-7".into()),
+            })
+            .await;
+        assert!(split_result.is_ok(), "{}", split_result.unwrap_err());
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(
+                &split_result.unwrap().journal_entry.id,
+                &"booked twice".to_string(),
+            )
+            .await;
+        assert!(reverse_result.is_ok(), "{}", reverse_result.unwrap_err());
+
+        // 1) the archive survives the JSON round trip
+        let archive_result = backup_handle_1.create_archive().await;
+        assert!(archive_result.is_ok(), "{}", archive_result.unwrap_err());
+        let archive = archive_result.unwrap();
+        assert_eq!(archive.schema_version, FinanceBackupHandle::SCHEMA_VERSION);
+        assert_eq!(archive.account_types.len(), 1);
+        assert_eq!(archive.accounts.len(), 3);
+        assert_eq!(archive.journal_entries.len(), 3);
+        assert_eq!(archive.booking_entries.len(), 8);
+        assert_eq!(archive.booking_journal_max_number, 3);
+
+        let json_result = backup_handle_1.export_json().await;
+        assert!(json_result.is_ok(), "{}", json_result.unwrap_err());
+        let json_text = json_result.unwrap();
+        let parse_result = FinanceBackupHandle::parse_json(&json_text);
+        assert!(parse_result.is_ok(), "{}", parse_result.unwrap_err());
+        let mut parsed_archive = parse_result.unwrap();
+        parsed_archive.created_at = archive.created_at;
+        assert_eq!(parsed_archive, archive);
+
+        // 2) restoring into an empty journal keeps ids, running numbers and the counter,
+        // the ids of the archive of user 1 are still used, so user 2 gets a copy with new ids
+        let used_ids_result = backup_handle_2.import_json(&json_text).await;
+        assert!(used_ids_result
+            .unwrap_err()
+            .contains("of the archive is used by another user"));
+        let renewed_archive = renew_archive_ids(&archive);
+        let restore_result = backup_handle_2.restore_archive(&renewed_archive).await;
+        assert!(restore_result.is_ok(), "{}", restore_result.unwrap_err());
+        assert_eq!(restore_result.unwrap(), 3);
+        let restored_archive = backup_handle_2.create_archive().await.unwrap();
+        assert_eq!(
+            restored_archive.account_types,
+            renewed_archive.account_types
+        );
+        assert_eq!(restored_archive.accounts, renewed_archive.accounts);
+        assert_eq!(
+            restored_archive.journal_entries,
+            renewed_archive.journal_entries
+        );
+        assert_eq!(
+            restored_archive.booking_entries,
+            renewed_archive.booking_entries
+        );
+        assert_eq!(restored_archive.booking_journal_max_number, 3);
+
+        let renewed_account_id = |account_id: &Uuid| {
+            let account_position = archive
+                .accounts
+                .iter()
+                .position(|elem| elem.id.eq(account_id))
+                .unwrap();
+            return renewed_archive.accounts[account_position].id;
+        };
+        let next_entry_result = booking_handle_2
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: renewed_account_id(&accounts[2].id),
+                credit_finance_account_id: renewed_account_id(&accounts[0].id),
+                booking_time: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+                amount: 700,
+                title: "after restore".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(
+            next_entry_result.is_ok(),
+            "{}",
+            next_entry_result.unwrap_err()
+        );
+        assert_eq!(next_entry_result.unwrap().journal_entry.running_number, 4);
+
+        // 3) a journal with entries is not overwritten
+        assert!(backup_handle_2
+            .restore_archive(&renewed_archive)
+            .await
+            .is_err());
+
+        // 4) archives of other versions or with broken references are rejected
+        let other_version_text = json_text.replacen(
+            &format!(
+                "\"schema_version\": {}",
+                FinanceBackupHandle::SCHEMA_VERSION
+            ),
+            "\"schema_version\": 99",
+            1,
+        );
+        let other_version_result = FinanceBackupHandle::parse_json(&other_version_text);
+        assert!(other_version_result.is_err());
+        assert!(other_version_result.unwrap_err().contains("99"));
+        assert!(FinanceBackupHandle::parse_json("{\"accounts\": []}").is_err());
+
+        let mut unbalanced_archive = archive.clone();
+        unbalanced_archive.booking_entries[0].amount += 1;
+        assert!(FinanceBackupHandle::verify_archive(&unbalanced_archive).is_err());
+
+        let mut missing_account_archive = archive.clone();
+        missing_account_archive.accounts.remove(1);
+        assert!(FinanceBackupHandle::verify_archive(&missing_account_archive).is_err());
+
+        let mut duplicate_number_archive = archive.clone();
+        duplicate_number_archive.journal_entries[1].running_number = 1;
+        assert!(FinanceBackupHandle::verify_archive(&duplicate_number_archive).is_err());
+
+        let mut broken_reversal_archive = archive.clone();
+        broken_reversal_archive.journal_entries[1].reversed_by_journal_entry_id = None;
+        assert!(FinanceBackupHandle::verify_archive(&broken_reversal_archive).is_err());
+
+        let mut counter_behind_archive = archive.clone();
+        counter_behind_archive.booking_journal_max_number = 2;
+        assert!(FinanceBackupHandle::verify_archive(&counter_behind_archive).is_err());
+
+        // two more entries on the same side that would wrap around to the amount of the entry
+        let mut wrapping_amount_archive = archive.clone();
+        let first_booking_entry = wrapping_amount_archive
+            .booking_entries
+            .iter()
+            .find(|elem| {
+                elem.finance_journal_diary_id
+                    .eq(&archive.journal_entries[0].id)
+            })
+            .unwrap()
+            .clone();
+        for wrapping_amount in [u64::MAX, 1] {
+            let mut wrapping_booking_entry = first_booking_entry.clone();
+            wrapping_booking_entry.id = Uuid::new();
+            wrapping_booking_entry.amount = wrapping_amount;
+            wrapping_amount_archive
+                .booking_entries
+                .push(wrapping_booking_entry);
+        }
+        let wrapping_amount_result = FinanceBackupHandle::verify_archive(&wrapping_amount_archive);
+        assert!(wrapping_amount_result.unwrap_err().contains("too large"));
+    }
+
+    #[tokio::test]
+    async fn test_backup_restore_with_other_user_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+        let user_id_2 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let entry_object2 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_2);
+
+        let _insert_result = InMemoryDatabaseData::insert_in_memory_database(Vec::from([
+            entry_object1,
+            entry_object2,
+        ]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_backup_restore_with_other_user(
+            &dummy_connection_settings,
+            user_id_1,
+            user_id_2,
+            &in_memory_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_backup_restore_with_other_user_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+        let user_id_2 = create_sqlite_test_user(&connection_settings, "sqlite_user_2").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_backup_restore_with_other_user(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            &sqlite_db,
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_backup_restore_with_other_user_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+        let user_id_2 = create_postgres_test_user(&connection_settings, "postgres_user_2").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_backup_restore_with_other_user(
+            &connection_settings,
+            user_id_1,
+            user_id_2,
+            &postgres_db,
+        )
+        .await;
+    }
+
+    /// restores the archive of user 1 into the journal of user 2 in the same instance
+    async fn check_backup_restore_with_other_user<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        user_id_2: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+        let account_handle_2 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_2, db_handler);
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+        let booking_handle_2 =
+            FinanceBookingHandle::new(connection_settings, &user_id_2, db_handler);
+        let backup_handle_1 = FinanceBackupHandle::new(connection_settings, &user_id_1, db_handler);
+        let backup_handle_2 = FinanceBackupHandle::new(connection_settings, &user_id_2, db_handler);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for (account_title, account_number) in [("bank", Some(1200)), ("cash", None)] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        let insert_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[0].id,
+                credit_finance_account_id: accounts[1].id,
+                booking_time: Utc.with_ymd_and_hms(2024, 2, 1, 12, 0, 0).unwrap(),
+                amount: 2500,
+                title: "deposit".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(insert_result.is_ok(), "{}", insert_result.unwrap_err());
+
+        let archive_result = backup_handle_1.create_archive().await;
+        assert!(archive_result.is_ok(), "{}", archive_result.unwrap_err());
+        let archive = archive_result.unwrap();
+
+        // 1) the ids of user 1 are rejected, user 2 gets nothing and user 1 keeps its data
+        let mut changed_archive = archive.clone();
+        changed_archive.account_types[0].title = "taken over".into();
+        changed_archive.accounts[0].title = "taken over".into();
+        let restore_result = backup_handle_2.restore_archive(&changed_archive).await;
+        assert!(restore_result.unwrap_err().contains("account type"));
+
+        // 2) with new ids for the configuration only the journal entries are still used,
+        // the configuration is not written either
+        let mut partly_renewed_archive = renew_archive_ids(&archive);
+        partly_renewed_archive.journal_entries = archive.journal_entries.clone();
+        partly_renewed_archive.booking_entries = archive.booking_entries.clone();
+        for journal_entry in partly_renewed_archive.journal_entries.iter_mut() {
+            journal_entry.debit_finance_account_id = partly_renewed_archive.accounts[0].id;
+            journal_entry.credit_finance_account_id = partly_renewed_archive.accounts[1].id;
+        }
+        for booking_entry in partly_renewed_archive.booking_entries.iter_mut() {
+            booking_entry.finance_account_id =
+                if booking_entry.finance_account_id.eq(&accounts[0].id) {
+                    partly_renewed_archive.accounts[0].id
+                } else {
+                    partly_renewed_archive.accounts[1].id
+                };
+        }
+        let restore_result = backup_handle_2
+            .restore_archive(&partly_renewed_archive)
+            .await;
+        assert!(restore_result
+            .unwrap_err()
+            .contains("of the archive is used by another user"));
+
+        let types_2_result = account_handle_2.finance_account_type_list();
+        assert!(types_2_result.is_ok(), "{}", types_2_result.unwrap_err());
+        assert_eq!(types_2_result.unwrap().len(), 0);
+        let accounts_2_result = account_handle_2.finance_account_list(None);
+        assert!(
+            accounts_2_result.is_ok(),
+            "{}",
+            accounts_2_result.unwrap_err()
+        );
+        assert_eq!(accounts_2_result.unwrap().len(), 0);
+        let journal_2_result = booking_handle_2.list_journal_entries(None, None).await;
+        assert_eq!(journal_2_result.unwrap().len(), 0);
+
+        let archive_after_result = backup_handle_1.create_archive().await;
+        assert!(
+            archive_after_result.is_ok(),
+            "{}",
+            archive_after_result.unwrap_err()
+        );
+        let archive_after = archive_after_result.unwrap();
+        assert_eq!(archive_after.account_types, archive.account_types);
+        assert_eq!(archive_after.accounts, archive.accounts);
+        assert_eq!(archive_after.journal_entries, archive.journal_entries);
+
+        // 3) a copy with new ids is restored next to the data of user 1
+        let renewed_archive = renew_archive_ids(&archive);
+        let restore_result = backup_handle_2.restore_archive(&renewed_archive).await;
+        assert!(restore_result.is_ok(), "{}", restore_result.unwrap_err());
+        let restored_archive = backup_handle_2.create_archive().await.unwrap();
+        assert_eq!(
+            restored_archive.account_types,
+            renewed_archive.account_types
+        );
+        assert_eq!(restored_archive.accounts, renewed_archive.accounts);
+        assert_eq!(
+            restored_archive.journal_entries,
+            renewed_archive.journal_entries
+        );
+        let archive_after = backup_handle_1.create_archive().await.unwrap();
+        assert_eq!(archive_after.accounts, archive.accounts);
+    }
+
+    /// copy of the archive with new ids, the references inside the archive are kept
+    fn renew_archive_ids(archive: &FinanceBackupArchive) -> FinanceBackupArchive {
+        let mut new_ids: HashMap<Uuid, Uuid> = HashMap::new();
+        let mut renew_id = |id: &Uuid| *new_ids.entry(*id).or_insert_with(Uuid::new);
+
+        let mut renewed_archive = archive.clone();
+        for account_type in renewed_archive.account_types.iter_mut() {
+            account_type.id = renew_id(&account_type.id);
+        }
+        for account in renewed_archive.accounts.iter_mut() {
+            account.id = renew_id(&account.id);
+            account.finance_account_type_id = renew_id(&account.finance_account_type_id);
+        }
+        for journal_entry in renewed_archive.journal_entries.iter_mut() {
+            journal_entry.id = renew_id(&journal_entry.id);
+            journal_entry.debit_finance_account_id =
+                renew_id(&journal_entry.debit_finance_account_id);
+            journal_entry.credit_finance_account_id =
+                renew_id(&journal_entry.credit_finance_account_id);
+            journal_entry.reverse_of_journal_entry_id = journal_entry
+                .reverse_of_journal_entry_id
+                .map(|elem| renew_id(&elem));
+            journal_entry.reversed_by_journal_entry_id = journal_entry
+                .reversed_by_journal_entry_id
+                .map(|elem| renew_id(&elem));
+        }
+        for booking_entry in renewed_archive.booking_entries.iter_mut() {
+            booking_entry.id = renew_id(&booking_entry.id);
+            booking_entry.finance_account_id = renew_id(&booking_entry.finance_account_id);
+            booking_entry.finance_journal_diary_id =
+                renew_id(&booking_entry.finance_journal_diary_id);
+        }
+        return renewed_archive;
+    }
+}
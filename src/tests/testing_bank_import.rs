@@ -0,0 +1,1236 @@
+#[cfg(test)]
+mod test_bank_import {
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_database::DBFinanceAccountingFunctions,
+        accounting_logic::FinanceBookingHandle,
+        bank_import::FinanceBankImportHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        database_handler_postgres::DbHandlerPostgres,
+        database_handler_sqlite::DbHandlerSqlite,
+        datatypes::{
+            AccountCategory, AmountSignConvention, CsvImportProfile, FinanceAccount,
+            FinanceAccountType, FinanceBookingRequest,
+        },
+        tests::{
+            mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+            testing_database_postgres::test_database_postgres::{
+                create_postgres_test_connection, create_postgres_test_user,
+            },
+            testing_database_sqlite::test_database_sqlite::{
+                create_sqlite_test_connection, create_sqlite_test_user,
+            },
+        },
+    };
+
+    fn create_profile(
+        bank_account_id: &Uuid,
+        incoming_account_id: &Uuid,
+        outgoing_account_id: &Uuid,
+    ) -> CsvImportProfile {
+        return CsvImportProfile {
+            delimiter: ';',
+            skip_rows: 1,
+            date_column: 1,
+            date_format: "%d.%m.%Y".into(),
+            amount_column: 2,
+            sign_convention: AmountSignConvention::PositiveIsIncoming,
+            decimal_separator: ',',
+            encoding: "windows-1252".into(),
+            title_column: 3,
+            description_column: 4,
+            bank_account_id: bank_account_id.clone(),
+            default_incoming_account_id: incoming_account_id.clone(),
+            default_outgoing_account_id: outgoing_account_id.clone(),
+        };
+    }
+
+    #[test]
+    fn test_bank_import_csv_records() {
+        let records_result = FinanceBankImportHandle::parse_csv_records(
+            "a;\"b;c\";\"say \"\"hi\"\"\"\r\n\r\n\"multi\nline\";;x\n",
+            ';',
+        );
+        assert!(records_result.is_ok(), "{}", records_result.unwrap_err());
+        let records = records_result.unwrap();
+        assert_eq!(records.len(), 3);
+        assert_eq!(records[0], vec!["a", "b;c", "say \"hi\""]);
+        assert!(records[1].is_empty());
+        assert_eq!(records[2], vec!["multi\nline", "", "x"]);
+
+        let unclosed_result = FinanceBankImportHandle::parse_csv_records("a;\"b\nc", ';');
+        assert!(unclosed_result.is_err());
+    }
+
+    #[test]
+    fn test_bank_import_amounts() {
+        let valid_amounts = [
+            ("1.234,56", ',', 123456),
+            ("-12,5", ',', -1250),
+            ("12,50-", ',', -1250),
+            ("+3", ',', 300),
+            ("1,234.56", '.', 123456),
+            ("-0.99", '.', -99),
+            ("1'000.00", '.', 100000),
+            (",5", ',', 50),
+        ];
+        for (amount_text, decimal_separator, expected_cents) in valid_amounts {
+            let parse_result =
+                FinanceBankImportHandle::parse_amount(amount_text, decimal_separator);
+            assert!(
+                parse_result.is_ok(),
+                "{}: {}",
+                amount_text,
+                parse_result.unwrap_err()
+            );
+            assert_eq!(parse_result.unwrap(), expected_cents, "{}", amount_text);
+        }
+        for amount_text in ["", "-", "1,234", "12a", "-5-", "1,2,3"] {
+            let parse_result = FinanceBankImportHandle::parse_amount(amount_text, ',');
+            assert!(parse_result.is_err(), "{} should not be valid", amount_text);
+        }
+    }
+
+    #[test]
+    fn test_bank_import_decoding() {
+        let decode_result = FinanceBankImportHandle::decode_statement_file(
+            b"B\xe4ckerei M\xfcller",
+            "windows-1252",
+        );
+        assert!(decode_result.is_ok(), "{}", decode_result.unwrap_err());
+        assert_eq!(decode_result.unwrap(), "Bäckerei Müller");
+
+        let invalid_utf8_result =
+            FinanceBankImportHandle::decode_statement_file(b"B\xe4ckerei", "utf-8");
+        assert!(invalid_utf8_result.is_err());
+
+        let unknown_encoding_result =
+            FinanceBankImportHandle::decode_statement_file(b"abc", "no-such-encoding");
+        assert!(unknown_encoding_result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bank_import_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let bank_import_handle_1 =
+            FinanceBankImportHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["bank", "income", "expenses"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) preview: header skipped, empty line skipped, invalid date and zero amount reported
+        let statement_content: &[u8] = b"Datum;Betrag;Empf\xe4nger;Verwendungszweck\r\n\
+            01.02.2020;1.500,00;Arbeitgeber;\"Gehalt; Februar\"\r\n\
+            \r\n\
+            03.02.2020;-45,99;B\xe4ckerei;Kuchen\r\n\
+            2020-02-04;-1,00;Kiosk;\r\n\
+            05.02.2020;0,00;Bank;Info\r\n";
+        let profile = create_profile(&accounts[0].id, &accounts[1].id, &accounts[2].id);
+        let preview_result =
+            FinanceBankImportHandle::preview_csv_import(statement_content, &profile);
+        assert!(preview_result.is_ok(), "{}", preview_result.unwrap_err());
+        let mut preview_rows = preview_result.unwrap();
+        assert_eq!(preview_rows.len(), 4);
+
+        assert_eq!(preview_rows[0].row_number, 2);
+        assert!(preview_rows[0].error.is_empty());
+        let salary_request = preview_rows[0].booking_request.as_ref().unwrap();
+        assert_eq!(salary_request.debit_finance_account_id, accounts[0].id);
+        assert_eq!(salary_request.credit_finance_account_id, accounts[1].id);
+        assert_eq!(salary_request.amount, 150000);
+        assert_eq!(salary_request.title, "Arbeitgeber");
+        assert_eq!(salary_request.description, "Gehalt; Februar");
+        assert_eq!(
+            salary_request.booking_time,
+            Utc.with_ymd_and_hms(2020, 2, 1, 12, 0, 0).unwrap()
+        );
+
+        assert_eq!(preview_rows[1].row_number, 4);
+        let bakery_request = preview_rows[1].booking_request.as_ref().unwrap();
+        assert_eq!(bakery_request.debit_finance_account_id, accounts[2].id);
+        assert_eq!(bakery_request.credit_finance_account_id, accounts[0].id);
+        assert_eq!(bakery_request.amount, 4599);
+        assert_eq!(bakery_request.title, "Bäckerei");
+
+        assert_eq!(preview_rows[2].row_number, 5);
+        assert!(preview_rows[2].booking_request.is_none());
+        assert!(preview_rows[2].error.contains("2020-02-04"));
+
+        assert_eq!(preview_rows[3].row_number, 6);
+        assert!(preview_rows[3].booking_request.is_none());
+        assert!(!preview_rows[3].error.is_empty());
+
+        // 2) the sign convention switches incoming and outgoing
+        let mut outgoing_profile = profile.clone();
+        outgoing_profile.sign_convention = AmountSignConvention::PositiveIsOutgoing;
+        let outgoing_preview_result =
+            FinanceBankImportHandle::preview_csv_import(statement_content, &outgoing_profile);
+        assert!(
+            outgoing_preview_result.is_ok(),
+            "{}",
+            outgoing_preview_result.unwrap_err()
+        );
+        let outgoing_salary_request = outgoing_preview_result.unwrap()[0]
+            .booking_request
+            .clone()
+            .unwrap();
+        assert_eq!(
+            outgoing_salary_request.debit_finance_account_id,
+            accounts[2].id
+        );
+        assert_eq!(
+            outgoing_salary_request.credit_finance_account_id,
+            accounts[0].id
+        );
+
+        // 3) a missing column is an error of that row
+        let mut missing_column_profile = profile.clone();
+        missing_column_profile.description_column = 7;
+        let missing_column_result =
+            FinanceBankImportHandle::preview_csv_import(statement_content, &missing_column_profile);
+        assert!(missing_column_result.is_ok());
+        assert!(missing_column_result
+            .unwrap()
+            .iter()
+            .all(|elem| elem.booking_request.is_none()));
+
+        // 4) post the rows, the changed row fails with the validation message of the booking,
+        // the other rows are booked anyway
+        preview_rows[1]
+            .booking_request
+            .as_mut()
+            .unwrap()
+            .debit_finance_account_id = accounts[0].id;
+        let row_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &preview_rows)
+            .await;
+        assert_eq!(row_results.len(), 4);
+        assert!(row_results[0].journal_entry_id.is_some());
+        assert!(row_results[0].error.is_empty());
+        assert!(row_results[1].journal_entry_id.is_none());
+        assert!(row_results[1].error.contains("same account"));
+        assert!(row_results[2].journal_entry_id.is_none());
+        assert_eq!(row_results[2].error, preview_rows[2].error);
+        assert!(row_results[3].journal_entry_id.is_none());
+
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let journal_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert!(journal_result.is_ok(), "{}", journal_result.unwrap_err());
+        let journal_entries = journal_result.unwrap();
+        assert_eq!(journal_entries.len(), 1);
+        assert_eq!(
+            journal_entries[0].id,
+            row_results[0].journal_entry_id.unwrap()
+        );
+        assert_eq!(journal_entries[0].amount, 150000);
+    }
+
+    const MT940_STATEMENT: &[u8] = include_bytes!("sample_files/mt940_statement.sta");
+    const MT940_UNBALANCED: &[u8] = include_bytes!("sample_files/mt940_unbalanced.sta");
+
+    #[test]
+    fn test_bank_import_mt940_parser() {
+        let text_result =
+            FinanceBankImportHandle::decode_statement_file(MT940_STATEMENT, "iso-8859-1");
+        assert!(text_result.is_ok(), "{}", text_result.unwrap_err());
+        let statements_result = FinanceBankImportHandle::parse_mt940(&text_result.unwrap());
+        assert!(
+            statements_result.is_ok(),
+            "{}",
+            statements_result.unwrap_err()
+        );
+        let statements = statements_result.unwrap();
+        assert_eq!(statements.len(), 2);
+
+        assert_eq!(statements[0].reference, "STARTUMS");
+        assert_eq!(statements[0].account_identification, "10020030/1234567890");
+        assert_eq!(statements[0].statement_number, "00001/001");
+        assert_eq!(statements[0].currency, "EUR");
+        assert_eq!(statements[0].opening_balance, 100000);
+        assert_eq!(
+            statements[0].opening_balance_time,
+            Utc.with_ymd_and_hms(2019, 12, 31, 12, 0, 0).unwrap()
+        );
+        assert_eq!(statements[0].closing_balance, 245401);
+        assert_eq!(statements[0].transactions.len(), 2);
+
+        // entry date in the year after the value date, structured information split over two lines
+        let salary_transaction = &statements[0].transactions[0];
+        assert_eq!(
+            salary_transaction.booking_time,
+            Utc.with_ymd_and_hms(2020, 1, 2, 12, 0, 0).unwrap()
+        );
+        assert_eq!(salary_transaction.amount, 150000);
+        assert_eq!(salary_transaction.title, "Arbeitgeber GmbH");
+        assert_eq!(
+            salary_transaction.description,
+            "Gehalt Januar 2020 Arbeitgeber GmbH Lohnbuchhaltung"
+        );
+
+        // debit with funds code, umlauts from ISO-8859-1
+        let bakery_transaction = &statements[0].transactions[1];
+        assert_eq!(bakery_transaction.amount, -4599);
+        assert_eq!(bakery_transaction.title, "Bäckerei Müller");
+        assert_eq!(bakery_transaction.description, "Kuchen und Brot");
+
+        // without entry date the value date is used, unstructured information is the title
+        let kiosk_transaction = &statements[1].transactions[0];
+        assert_eq!(
+            kiosk_transaction.booking_time,
+            Utc.with_ymd_and_hms(2020, 1, 5, 12, 0, 0).unwrap()
+        );
+        assert_eq!(kiosk_transaction.amount, -1250);
+        assert_eq!(
+            kiosk_transaction.title,
+            "Kartenzahlung Kiosk am Bahnhof Filiale 12"
+        );
+
+        // reversal of a debit is received money
+        let reversal_transaction = &statements[1].transactions[1];
+        assert_eq!(reversal_transaction.amount, 500);
+        assert_eq!(reversal_transaction.title, "Baeckerei Mueller");
+        assert_eq!(statements[1].closing_balance, 244651);
+
+        let unbalanced_text_result =
+            FinanceBankImportHandle::decode_statement_file(MT940_UNBALANCED, "iso-8859-1");
+        assert!(unbalanced_text_result.is_ok());
+        let unbalanced_result =
+            FinanceBankImportHandle::parse_mt940(&unbalanced_text_result.unwrap());
+        assert!(unbalanced_result.is_err());
+        assert!(unbalanced_result
+            .unwrap_err()
+            .contains("does not match closing balance"));
+
+        let missing_opening_result = FinanceBankImportHandle::parse_mt940(
+            ":20:STARTUMS\r\n:25:10020030/1234567890\r\n:61:200107C100,00NTRFNONREF\r\n",
+        );
+        assert!(missing_opening_result.is_err());
+
+        let invalid_line_result = FinanceBankImportHandle::parse_mt940(
+            ":20:STARTUMS\r\n:60F:C200106EUR0,00\r\n:61:200107X100,00NTRFNONREF\r\n:62F:C200107EUR100,00\r\n",
+        );
+        assert!(invalid_line_result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bank_import_mt940_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let bank_import_handle_1 =
+            FinanceBankImportHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["bank", "income", "expenses", "equity"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) preview of all statement lines of the file
+        let preview_result = FinanceBankImportHandle::preview_mt940_import(
+            MT940_STATEMENT,
+            "iso-8859-1",
+            &accounts[0].id,
+            &accounts[1].id,
+            &accounts[2].id,
+        );
+        assert!(preview_result.is_ok(), "{}", preview_result.unwrap_err());
+        let (statements, preview_rows) = preview_result.unwrap();
+        assert_eq!(preview_rows.len(), 4);
+        assert_eq!(
+            preview_rows
+                .iter()
+                .map(|elem| elem.row_number)
+                .collect::<Vec<usize>>(),
+            vec![1, 2, 3, 4]
+        );
+        let salary_request = preview_rows[0].booking_request.as_ref().unwrap();
+        assert_eq!(salary_request.debit_finance_account_id, accounts[0].id);
+        assert_eq!(salary_request.credit_finance_account_id, accounts[1].id);
+        assert_eq!(salary_request.amount, 150000);
+        let bakery_request = preview_rows[1].booking_request.as_ref().unwrap();
+        assert_eq!(bakery_request.debit_finance_account_id, accounts[2].id);
+        assert_eq!(bakery_request.credit_finance_account_id, accounts[0].id);
+        assert_eq!(bakery_request.amount, 4599);
+
+        // 2) the empty bank account does not match the opening balance
+        let first_check_result = bank_import_handle_1
+            .check_statement_balances(&accounts[0].id, &statements)
+            .await;
+        assert!(
+            first_check_result.is_ok(),
+            "{}",
+            first_check_result.unwrap_err()
+        );
+        let first_check = first_check_result.unwrap();
+        assert_eq!(first_check.book_balance, 0);
+        assert_eq!(first_check.statement_opening_balance, 100000);
+        assert_eq!(first_check.statement_closing_balance, 244651);
+        assert!(!first_check.is_opening_balance_matching());
+        assert!(!first_check.is_closing_balance_matching());
+
+        // 3) after booking the opening balance the statement continues the journal
+        let opening_booking_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[0].id,
+                credit_finance_account_id: accounts[3].id,
+                booking_time: Utc.with_ymd_and_hms(2019, 12, 31, 12, 0, 0).unwrap(),
+                amount: 100000,
+                title: "opening balance".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(
+            opening_booking_result.is_ok(),
+            "{}",
+            opening_booking_result.unwrap_err()
+        );
+        let second_check_result = bank_import_handle_1
+            .check_statement_balances(&accounts[0].id, &statements)
+            .await;
+        assert!(
+            second_check_result.is_ok(),
+            "{}",
+            second_check_result.unwrap_err()
+        );
+        assert!(second_check_result.unwrap().is_opening_balance_matching());
+
+        // 4) after posting all rows the bank account has the closing balance
+        let row_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &preview_rows)
+            .await;
+        assert!(row_results
+            .iter()
+            .all(|elem| elem.journal_entry_id.is_some() && elem.error.is_empty()));
+        let third_check_result = bank_import_handle_1
+            .check_statement_balances(&accounts[0].id, &statements)
+            .await;
+        assert!(
+            third_check_result.is_ok(),
+            "{}",
+            third_check_result.unwrap_err()
+        );
+        let third_check = third_check_result.unwrap();
+        assert_eq!(third_check.book_balance, 244651);
+        assert!(third_check.is_closing_balance_matching());
+    }
+
+    const CAMT053_STATEMENT: &[u8] = include_bytes!("sample_files/camt053_statement.xml");
+
+    #[test]
+    fn test_bank_import_camt053_parser() {
+        let text_result =
+            FinanceBankImportHandle::decode_statement_file(CAMT053_STATEMENT, "utf-8");
+        assert!(text_result.is_ok(), "{}", text_result.unwrap_err());
+        let statements_result = FinanceBankImportHandle::parse_camt053(&text_result.unwrap());
+        assert!(
+            statements_result.is_ok(),
+            "{}",
+            statements_result.unwrap_err()
+        );
+        let statements = statements_result.unwrap();
+        assert_eq!(statements.len(), 1);
+
+        let statement = &statements[0];
+        assert_eq!(statement.reference, "STMT-2020-02-001");
+        assert_eq!(statement.statement_number, "2");
+        assert_eq!(statement.account_identification, "DE02120300000000202051");
+        assert_eq!(statement.currency, "EUR");
+        assert_eq!(statement.opening_balance, 100000);
+        assert_eq!(
+            statement.opening_balance_time,
+            Utc.with_ymd_and_hms(2020, 1, 31, 12, 0, 0).unwrap()
+        );
+        assert_eq!(statement.closing_balance, 244151);
+
+        // the pending entry is not part of the statement
+        assert_eq!(statement.transactions.len(), 3);
+        let salary_transaction = &statement.transactions[0];
+        assert_eq!(salary_transaction.amount, 150000);
+        assert_eq!(
+            salary_transaction.booking_time,
+            Utc.with_ymd_and_hms(2020, 2, 3, 12, 0, 0).unwrap()
+        );
+        assert_eq!(salary_transaction.title, "Arbeitgeber GmbH & Co. KG");
+        assert_eq!(salary_transaction.description, "Gehalt Februar 2020");
+
+        let bakery_transaction = &statement.transactions[1];
+        assert_eq!(bakery_transaction.amount, -4599);
+        assert_eq!(bakery_transaction.title, "Bäckerei Müller");
+        assert_eq!(bakery_transaction.description, "Kuchen und Brot");
+
+        // booking date as date time, no related parties
+        let kiosk_transaction = &statement.transactions[2];
+        assert_eq!(kiosk_transaction.amount, -1250);
+        assert_eq!(
+            kiosk_transaction.booking_time,
+            Utc.with_ymd_and_hms(2020, 2, 5, 12, 0, 0).unwrap()
+        );
+        assert_eq!(kiosk_transaction.title, "Kartenzahlung Kiosk am Bahnhof");
+        assert_eq!(kiosk_transaction.description, "");
+
+        // namespace prefix and status code of newer versions, balances do not match
+        let unbalanced_result = FinanceBankImportHandle::parse_camt053(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <c:Document xmlns:c="urn:iso:std:iso:20022:tech:xsd:camt.053.001.08"><c:BkToCstmrStmt><c:Stmt>
+            <c:Id>STMT-2</c:Id>
+            <c:Bal><c:Tp><c:CdOrPrtry><c:Cd>OPBD</c:Cd></c:CdOrPrtry></c:Tp><c:Amt Ccy="EUR">10.00</c:Amt><c:CdtDbtInd>DBIT</c:CdtDbtInd><c:Dt><c:Dt>2020-03-01</c:Dt></c:Dt></c:Bal>
+            <c:Bal><c:Tp><c:CdOrPrtry><c:Cd>CLBD</c:Cd></c:CdOrPrtry></c:Tp><c:Amt Ccy="EUR">5.00</c:Amt><c:CdtDbtInd>CRDT</c:CdtDbtInd><c:Dt><c:Dt>2020-03-02</c:Dt></c:Dt></c:Bal>
+            <c:Ntry><c:Amt Ccy="EUR">10.00</c:Amt><c:CdtDbtInd>CRDT</c:CdtDbtInd><c:Sts><c:Cd>BOOK</c:Cd></c:Sts><c:BookgDt><c:Dt>2020-03-02</c:Dt></c:BookgDt></c:Ntry>
+            </c:Stmt></c:BkToCstmrStmt></c:Document>"#,
+        );
+        assert!(unbalanced_result.is_err());
+        let unbalanced_error = unbalanced_result.unwrap_err();
+        assert!(
+            unbalanced_error.contains("does not match closing balance"),
+            "{}",
+            unbalanced_error
+        );
+
+        let missing_closing_result = FinanceBankImportHandle::parse_camt053(
+            "<Document><BkToCstmrStmt><Stmt><Id>STMT-3</Id>\
+            <Bal><Tp><CdOrPrtry><Cd>OPBD</Cd></CdOrPrtry></Tp><Amt Ccy=\"EUR\">1.00</Amt><CdtDbtInd>CRDT</CdtDbtInd><Dt><Dt>2020-03-01</Dt></Dt></Bal>\
+            </Stmt></BkToCstmrStmt></Document>",
+        );
+        assert!(missing_closing_result.is_err());
+
+        let broken_xml_result =
+            FinanceBankImportHandle::parse_camt053("<Document><Stmt><Id>1</Stmt></Document>");
+        assert!(broken_xml_result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bank_import_camt053_duplicates_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let bank_import_handle_1 =
+            FinanceBankImportHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["bank", "income", "expenses"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) nothing booked yet, no duplicates
+        let preview_result = FinanceBankImportHandle::preview_camt053_import(
+            CAMT053_STATEMENT,
+            &accounts[0].id,
+            &accounts[1].id,
+            &accounts[2].id,
+        );
+        assert!(preview_result.is_ok(), "{}", preview_result.unwrap_err());
+        let (statements, mut preview_rows) = preview_result.unwrap();
+        assert_eq!(preview_rows.len(), 3);
+        let duplicate_result = bank_import_handle_1
+            .mark_duplicate_rows(&accounts[0].id, &mut preview_rows)
+            .await;
+        assert!(
+            duplicate_result.is_ok(),
+            "{}",
+            duplicate_result.unwrap_err()
+        );
+        assert!(preview_rows
+            .iter()
+            .all(|elem| elem.duplicate_journal_entry_id.is_none()));
+
+        // 2) the salary was booked before, a second row with the same day and amount (even with
+        // another counter account) is no duplicate because the journal entry is used once only
+        let row_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &vec![preview_rows[0].clone()])
+            .await;
+        assert!(
+            row_results[0].journal_entry_id.is_some(),
+            "{}",
+            row_results[0].error
+        );
+        let mut changed_salary_row = preview_rows[0].clone();
+        changed_salary_row.row_number = 4;
+        changed_salary_row
+            .booking_request
+            .as_mut()
+            .unwrap()
+            .credit_finance_account_id = accounts[2].id;
+        let (_, mut second_preview_rows) = FinanceBankImportHandle::preview_camt053_import(
+            CAMT053_STATEMENT,
+            &accounts[0].id,
+            &accounts[1].id,
+            &accounts[2].id,
+        )
+        .unwrap();
+        second_preview_rows.push(changed_salary_row);
+        let second_duplicate_result = bank_import_handle_1
+            .mark_duplicate_rows(&accounts[0].id, &mut second_preview_rows)
+            .await;
+        assert!(
+            second_duplicate_result.is_ok(),
+            "{}",
+            second_duplicate_result.unwrap_err()
+        );
+        assert_eq!(
+            second_preview_rows[0].duplicate_journal_entry_id,
+            row_results[0].journal_entry_id
+        );
+        assert!(second_preview_rows[1].duplicate_journal_entry_id.is_none());
+        assert!(second_preview_rows[2].duplicate_journal_entry_id.is_none());
+        assert!(second_preview_rows[3].duplicate_journal_entry_id.is_none());
+
+        // 3) the opening balance of the statement was never booked
+        let remaining_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &preview_rows[1..].to_vec())
+            .await;
+        assert!(remaining_results
+            .iter()
+            .all(|elem| elem.journal_entry_id.is_some()));
+        let balance_check_result = bank_import_handle_1
+            .check_statement_balances(&accounts[0].id, &statements)
+            .await;
+        assert!(
+            balance_check_result.is_ok(),
+            "{}",
+            balance_check_result.unwrap_err()
+        );
+        let balance_check = balance_check_result.unwrap();
+        assert_eq!(balance_check.book_balance, 150000 - 4599 - 1250);
+        assert!(!balance_check.is_opening_balance_matching());
+        assert!(!balance_check.is_closing_balance_matching());
+    }
+
+    const OFX_STATEMENT: &[u8] = include_bytes!("sample_files/ofx_statement.ofx");
+    const QIF_STATEMENT: &[u8] = include_bytes!("sample_files/qif_statement.qif");
+
+    #[test]
+    fn test_bank_import_ofx_parser() {
+        // the sample file is windows-1252, the preview falls back to it
+        let bank_account_id = Uuid::new();
+        let incoming_account_id = Uuid::new();
+        let outgoing_account_id = Uuid::new();
+        let preview_result = FinanceBankImportHandle::preview_ofx_import(
+            OFX_STATEMENT,
+            &bank_account_id,
+            &incoming_account_id,
+            &outgoing_account_id,
+        );
+        assert!(preview_result.is_ok(), "{}", preview_result.unwrap_err());
+        let preview_rows = preview_result.unwrap();
+        assert_eq!(preview_rows.len(), 3);
+
+        let salary_request = preview_rows[0].booking_request.as_ref().unwrap();
+        assert_eq!(salary_request.debit_finance_account_id, bank_account_id);
+        assert_eq!(
+            salary_request.credit_finance_account_id,
+            incoming_account_id
+        );
+        assert_eq!(salary_request.amount, 150000);
+        assert_eq!(
+            salary_request.booking_time,
+            Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap()
+        );
+        assert_eq!(salary_request.title, "Arbeitgeber GmbH");
+        assert_eq!(salary_request.description, "Gehalt Januar");
+        assert_eq!(
+            salary_request.import_reference,
+            Some("OFX:2024011501".into())
+        );
+
+        // comma as decimal separator and escaped text
+        let bakery_request = preview_rows[1].booking_request.as_ref().unwrap();
+        assert_eq!(bakery_request.debit_finance_account_id, outgoing_account_id);
+        assert_eq!(bakery_request.credit_finance_account_id, bank_account_id);
+        assert_eq!(bakery_request.amount, 4599);
+        assert_eq!(bakery_request.title, "Bäckerei Müller");
+        assert_eq!(bakery_request.description, "Brot & Kuchen");
+
+        // date with time and time zone
+        let kiosk_request = preview_rows[2].booking_request.as_ref().unwrap();
+        assert_eq!(kiosk_request.amount, 1250);
+        assert_eq!(
+            kiosk_request.booking_time,
+            Utc.with_ymd_and_hms(2024, 1, 20, 12, 0, 0).unwrap()
+        );
+
+        // XML format of version 2 with the name inside PAYEE and without FITID
+        let xml_result = FinanceBankImportHandle::parse_ofx(
+            r#"<?xml version="1.0" encoding="UTF-8"?>
+            <?OFX OFXHEADER="200" VERSION="220"?>
+            <OFX><BANKMSGSRSV1><STMTTRNRS><STMTRS><BANKTRANLIST>
+            <STMTTRN><TRNTYPE>DEBIT</TRNTYPE><DTPOSTED>20240201</DTPOSTED><TRNAMT>-7.00</TRNAMT>
+            <PAYEE><NAME>Kino</NAME><ADDR1>Hauptstraße 1</ADDR1></PAYEE></STMTTRN>
+            </BANKTRANLIST></STMTRS></STMTTRNRS></BANKMSGSRSV1></OFX>"#,
+        );
+        assert!(xml_result.is_ok(), "{}", xml_result.unwrap_err());
+        let xml_transactions = xml_result.unwrap();
+        assert_eq!(xml_transactions.len(), 1);
+        assert_eq!(xml_transactions[0].amount, -700);
+        assert_eq!(xml_transactions[0].title, "Kino");
+        assert!(xml_transactions[0].import_reference.is_none());
+
+        let missing_amount_result = FinanceBankImportHandle::parse_ofx(
+            "<OFX><STMTTRN><DTPOSTED>20240201<FITID>1</STMTTRN></OFX>",
+        );
+        assert!(missing_amount_result.is_err());
+        let not_closed_result = FinanceBankImportHandle::parse_ofx(
+            "<OFX><STMTTRN><DTPOSTED>20240201<TRNAMT>1.00</OFX>",
+        );
+        assert!(not_closed_result.is_err());
+        let no_ofx_result = FinanceBankImportHandle::parse_ofx("Datum;Betrag\n01.02.2024;1,00");
+        assert!(no_ofx_result.is_err());
+    }
+
+    #[test]
+    fn test_bank_import_qif_parser() {
+        let bank_account_id = Uuid::new();
+        let incoming_account_id = Uuid::new();
+        let outgoing_account_id = Uuid::new();
+        let preview_result = FinanceBankImportHandle::preview_qif_import(
+            QIF_STATEMENT,
+            "windows-1252",
+            "%m/%d/%Y",
+            '.',
+            &bank_account_id,
+            &incoming_account_id,
+            &outgoing_account_id,
+        );
+        assert!(preview_result.is_ok(), "{}", preview_result.unwrap_err());
+        let preview_rows = preview_result.unwrap();
+
+        // the records of the account list and the categories are skipped
+        assert_eq!(preview_rows.len(), 4);
+        let salary_request = preview_rows[0].booking_request.as_ref().unwrap();
+        assert_eq!(salary_request.debit_finance_account_id, bank_account_id);
+        assert_eq!(salary_request.amount, 150000);
+        assert_eq!(
+            salary_request.booking_time,
+            Utc.with_ymd_and_hms(2024, 1, 15, 12, 0, 0).unwrap()
+        );
+        assert_eq!(salary_request.title, "Arbeitgeber GmbH");
+        assert_eq!(salary_request.description, "Gehalt Januar");
+        assert_eq!(
+            salary_request.import_reference,
+            Some("QIF:2024-01-15|150000|Arbeitgeber GmbH".into())
+        );
+
+        let bakery_request = preview_rows[1].booking_request.as_ref().unwrap();
+        assert_eq!(bakery_request.credit_finance_account_id, bank_account_id);
+        assert_eq!(bakery_request.title, "Bäckerei Müller");
+        assert_eq!(
+            bakery_request.import_reference,
+            Some("QIF:2024-01-17|-4599|Bäckerei Müller".into())
+        );
+
+        // the same purchase twice on one day gets two different references
+        assert_eq!(
+            preview_rows[2]
+                .booking_request
+                .as_ref()
+                .unwrap()
+                .import_reference,
+            Some("QIF:2024-01-20|-1250|Kiosk am Bahnhof".into())
+        );
+        assert_eq!(
+            preview_rows[3]
+                .booking_request
+                .as_ref()
+                .unwrap()
+                .import_reference,
+            Some("QIF:2024-01-20|-1250|Kiosk am Bahnhof|2".into())
+        );
+
+        // date written by Quicken
+        let quicken_result = FinanceBankImportHandle::parse_qif(
+            "!Type:CCard\nD1/ 5'24\nU-3,50\nPKino\n^\n",
+            "%m/%d/%y",
+            ',',
+        );
+        assert!(quicken_result.is_ok(), "{}", quicken_result.unwrap_err());
+        let quicken_transactions = quicken_result.unwrap();
+        assert_eq!(quicken_transactions.len(), 1);
+        assert_eq!(
+            quicken_transactions[0].booking_time,
+            Utc.with_ymd_and_hms(2024, 1, 5, 12, 0, 0).unwrap()
+        );
+        assert_eq!(quicken_transactions[0].amount, -350);
+
+        let wrong_date_result = FinanceBankImportHandle::parse_qif(
+            "!Type:Bank\nD2024-01-05\nT1.00\n^\n",
+            "%m/%d/%Y",
+            '.',
+        );
+        assert!(wrong_date_result.is_err());
+        let not_closed_result =
+            FinanceBankImportHandle::parse_qif("!Type:Bank\nD01/05/2024\nT1.00\n", "%m/%d/%Y", '.');
+        assert!(not_closed_result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_bank_import_ofx_twice_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let bank_import_handle_1 =
+            FinanceBankImportHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["bank", "income", "expenses", "credit card"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) a manual booking of the kiosk payment without import reference
+        let manual_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[2].id,
+                credit_finance_account_id: accounts[0].id,
+                booking_time: Utc.with_ymd_and_hms(2024, 1, 20, 18, 0, 0).unwrap(),
+                amount: 1250,
+                title: "Kiosk".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(manual_result.is_ok(), "{}", manual_result.unwrap_err());
+
+        // 2) first import, only the kiosk payment is found by day and amount
+        let mut preview_rows = FinanceBankImportHandle::preview_ofx_import(
+            OFX_STATEMENT,
+            &accounts[0].id,
+            &accounts[1].id,
+            &accounts[2].id,
+        )
+        .unwrap();
+        let duplicate_result = bank_import_handle_1
+            .mark_duplicate_rows(&accounts[0].id, &mut preview_rows)
+            .await;
+        assert!(
+            duplicate_result.is_ok(),
+            "{}",
+            duplicate_result.unwrap_err()
+        );
+        assert!(preview_rows[0].duplicate_journal_entry_id.is_none());
+        assert!(preview_rows[1].duplicate_journal_entry_id.is_none());
+        assert!(preview_rows[2].duplicate_journal_entry_id.is_some());
+        let row_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &preview_rows[0..2].to_vec())
+            .await;
+        assert!(row_results
+            .iter()
+            .all(|elem| elem.journal_entry_id.is_some()));
+
+        // the FITID is stored on the journal entries
+        let journal_entries = booking_handle_1
+            .list_journal_entries(None, None)
+            .await
+            .unwrap();
+        let salary_entry = journal_entries
+            .iter()
+            .find(|elem| elem.id.eq(&row_results[0].journal_entry_id.unwrap()))
+            .unwrap();
+        assert_eq!(salary_entry.import_reference, Some("OFX:2024011501".into()));
+
+        // 3) the same file again, the rows are found by their reference
+        let mut second_preview_rows = FinanceBankImportHandle::preview_ofx_import(
+            OFX_STATEMENT,
+            &accounts[0].id,
+            &accounts[1].id,
+            &accounts[2].id,
+        )
+        .unwrap();
+        let _second_duplicate_result = bank_import_handle_1
+            .mark_duplicate_rows(&accounts[0].id, &mut second_preview_rows)
+            .await;
+        assert_eq!(
+            second_preview_rows[0].duplicate_journal_entry_id,
+            row_results[0].journal_entry_id
+        );
+        assert_eq!(
+            second_preview_rows[1].duplicate_journal_entry_id,
+            row_results[1].journal_entry_id
+        );
+        assert_eq!(
+            second_preview_rows[2].duplicate_journal_entry_id,
+            preview_rows[2].duplicate_journal_entry_id
+        );
+
+        // 4) the reference is only compared with entries of the same bank account
+        let mut other_account_rows = FinanceBankImportHandle::preview_ofx_import(
+            OFX_STATEMENT,
+            &accounts[3].id,
+            &accounts[1].id,
+            &accounts[2].id,
+        )
+        .unwrap();
+        let _other_duplicate_result = bank_import_handle_1
+            .mark_duplicate_rows(&accounts[3].id, &mut other_account_rows)
+            .await;
+        assert!(other_account_rows
+            .iter()
+            .all(|elem| elem.duplicate_journal_entry_id.is_none()));
+
+        // 5) a payment of the same day and amount with another FITID is a second transaction,
+        // without a reference it is still found by day and amount
+        let mut same_amount_rows = vec![preview_rows[1].clone(), preview_rows[1].clone()];
+        same_amount_rows[0]
+            .booking_request
+            .as_mut()
+            .unwrap()
+            .import_reference = Some("OFX:2024011699".into());
+        same_amount_rows[1]
+            .booking_request
+            .as_mut()
+            .unwrap()
+            .import_reference = None;
+        let same_amount_result = bank_import_handle_1
+            .mark_duplicate_rows(&accounts[0].id, &mut same_amount_rows)
+            .await;
+        assert!(
+            same_amount_result.is_ok(),
+            "{}",
+            same_amount_result.unwrap_err()
+        );
+        assert!(same_amount_rows[0].duplicate_journal_entry_id.is_none());
+        assert_eq!(
+            same_amount_rows[1].duplicate_journal_entry_id,
+            row_results[1].journal_entry_id
+        );
+    }
+
+    #[tokio::test]
+    async fn test_bank_import_post_twice_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_bank_import_post_twice(&dummy_connection_settings, user_id_1, &in_memory_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_bank_import_post_twice_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_bank_import_post_twice(&connection_settings, user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_bank_import_post_twice_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user_1").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_bank_import_post_twice(&connection_settings, user_id_1, &postgres_db).await;
+    }
+
+    /// posts the rows of the same statement twice, as a replayed request would do
+    async fn check_bank_import_post_twice<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceAccountingFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, &user_id_1, db_handler);
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, &user_id_1, db_handler);
+        let bank_import_handle_1 =
+            FinanceBankImportHandle::new(connection_settings, &user_id_1, db_handler);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["bank", "income", "expenses"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        let preview_rows = FinanceBankImportHandle::preview_ofx_import(
+            OFX_STATEMENT,
+            &accounts[0].id,
+            &accounts[1].id,
+            &accounts[2].id,
+        )
+        .unwrap();
+        let row_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &preview_rows)
+            .await;
+        assert!(row_results
+            .iter()
+            .all(|elem| elem.journal_entry_id.is_some()));
+
+        // 1) the same rows again, the rows are checked on posting and nothing is booked
+        let second_row_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &preview_rows)
+            .await;
+        for (row_result, second_row_result) in row_results.iter().zip(second_row_results.iter()) {
+            assert!(second_row_result.journal_entry_id.is_none());
+            assert_eq!(
+                second_row_result.error,
+                format!(
+                    "already booked as journal entry {}",
+                    row_result.journal_entry_id.unwrap()
+                )
+            );
+        }
+        let journal_entries = booking_handle_1
+            .list_journal_entries(None, None)
+            .await
+            .unwrap();
+        assert_eq!(journal_entries.len(), 3);
+
+        // 2) the storage rejects a second entry of the same reference on the same accounts
+        let insert_result = booking_handle_1
+            .finance_insert_booking_entry(preview_rows[0].booking_request.as_ref().unwrap())
+            .await;
+        assert!(insert_result.is_err());
+
+        // 3) after a reversal the transaction can be imported again
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(&row_results[0].journal_entry_id.unwrap(), &"wrong".into())
+            .await;
+        assert!(reverse_result.is_ok(), "{}", reverse_result.unwrap_err());
+        let third_row_results = bank_import_handle_1
+            .post_import_rows(&accounts[0].id, &vec![preview_rows[0].clone()])
+            .await;
+        assert!(
+            third_row_results[0].journal_entry_id.is_some(),
+            "{}",
+            third_row_results[0].error
+        );
+    }
+}
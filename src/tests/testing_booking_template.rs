@@ -0,0 +1,220 @@
+#[cfg(test)]
+mod test_booking_template_handle {
+    use async_session::chrono::{Duration, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        booking_template_logic::FinanceBookingTemplateHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{AccountCategory, FinanceAccount, FinanceAccountType, FinanceBookingTemplate},
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    #[tokio::test]
+    async fn test_booking_template_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+        let user_id_2 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let entry_object2 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_2);
+
+        let _insert_result = InMemoryDatabaseData::insert_in_memory_database(Vec::from([
+            entry_object1,
+            entry_object2,
+        ]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let template_handle_1 = FinanceBookingTemplateHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let template_handle_2 = FinanceBookingTemplateHandle::new(
+            &dummy_connection_settings,
+            &user_id_2,
+            &in_memory_db,
+        );
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["groceries", "bank"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) create templates, one of them without amount
+        let mut groceries_template = FinanceBookingTemplate {
+            id: Uuid::new(),
+            name: "groceries".into(),
+            debit_finance_account_id: accounts[0].id,
+            credit_finance_account_id: accounts[1].id,
+            amount: 4500,
+            title: "weekly groceries".into(),
+            description: "supermarket".into(),
+        };
+        let upsert_result = template_handle_1
+            .booking_template_upsert(&groceries_template)
+            .await;
+        assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+        let variable_template = FinanceBookingTemplate {
+            id: Uuid::new(),
+            name: "variable".into(),
+            amount: 0,
+            ..groceries_template.clone()
+        };
+        let upsert_result = template_handle_1
+            .booking_template_upsert(&variable_template)
+            .await;
+        assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+
+        // 2) invalid templates are rejected
+        let invalid_templates = [
+            FinanceBookingTemplate {
+                id: Uuid::new(),
+                name: " ".into(),
+                ..groceries_template.clone()
+            },
+            FinanceBookingTemplate {
+                id: Uuid::new(),
+                ..groceries_template.clone()
+            },
+            FinanceBookingTemplate {
+                id: Uuid::new(),
+                name: "same accounts".into(),
+                credit_finance_account_id: accounts[0].id,
+                ..groceries_template.clone()
+            },
+            FinanceBookingTemplate {
+                id: Uuid::new(),
+                name: "unknown account".into(),
+                credit_finance_account_id: Uuid::new(),
+                ..groceries_template.clone()
+            },
+        ];
+        for invalid_template in invalid_templates {
+            assert!(
+                template_handle_1
+                    .booking_template_upsert(&invalid_template)
+                    .await
+                    .is_err(),
+                "template {:?} should be rejected",
+                invalid_template
+            );
+        }
+        // accounts of another user are unknown
+        assert!(template_handle_2
+            .booking_template_upsert(&groceries_template)
+            .await
+            .is_err());
+
+        // 3) update an existing template
+        groceries_template.amount = 5000;
+        let upsert_result = template_handle_1
+            .booking_template_upsert(&groceries_template)
+            .await;
+        assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+        let list_result = template_handle_1.booking_template_list().await;
+        assert!(list_result.is_ok());
+        let template_list = list_result.unwrap();
+        assert_eq!(template_list.len(), 2);
+        assert!(template_list.contains(&groceries_template));
+        assert_eq!(
+            template_handle_2
+                .booking_template_list()
+                .await
+                .unwrap()
+                .len(),
+            0
+        );
+
+        // 4) booking a template uses the normal validation
+        let booking_time = Utc::now() - Duration::days(1);
+        let booking_result = template_handle_1
+            .booking_template_insert_booking_entry(&groceries_template.id, booking_time, None)
+            .await;
+        assert!(booking_result.is_ok(), "{}", booking_result.unwrap_err());
+        let journal_entry = booking_result.unwrap().journal_entry;
+        assert_eq!(journal_entry.amount, 5000);
+        assert_eq!(journal_entry.title, groceries_template.title);
+        assert_eq!(
+            journal_entry.debit_finance_account_id,
+            groceries_template.debit_finance_account_id
+        );
+        assert_eq!(journal_entry.booking_time, booking_time);
+
+        assert!(template_handle_1
+            .booking_template_insert_booking_entry(&variable_template.id, booking_time, None)
+            .await
+            .is_err());
+        let booking_result = template_handle_1
+            .booking_template_insert_booking_entry(&variable_template.id, booking_time, Some(1234))
+            .await;
+        assert!(booking_result.is_ok(), "{}", booking_result.unwrap_err());
+        assert_eq!(booking_result.unwrap().journal_entry.amount, 1234);
+        assert!(template_handle_2
+            .booking_template_insert_booking_entry(&groceries_template.id, booking_time, None)
+            .await
+            .is_err());
+
+        let journal_entries_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert_eq!(journal_entries_result.unwrap().len(), 2);
+
+        // 5) delete
+        let delete_result = template_handle_1
+            .booking_template_delete(&variable_template.id)
+            .await;
+        assert!(delete_result.is_ok());
+        assert!(template_handle_1
+            .booking_template_delete(&variable_template.id)
+            .await
+            .is_err());
+        assert_eq!(
+            template_handle_1.booking_template_list().await.unwrap(),
+            vec![groceries_template]
+        );
+    }
+}
@@ -0,0 +1,323 @@
+#[cfg(test)]
+mod test_budget_handle {
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        budget_logic::FinanceBudgetHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{
+            AccountCategory, BudgetPeriod, FinanceAccount, FinanceAccountType,
+            FinanceBookingRequest, FinanceBudget,
+        },
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    #[test]
+    fn test_budget_period_times() {
+        let report_time = Utc.with_ymd_and_hms(2023, 12, 15, 8, 30, 0).unwrap();
+        let (month_from, month_till) =
+            FinanceBudgetHandle::get_period_times(&BudgetPeriod::Monthly, report_time);
+        assert_eq!(
+            month_from,
+            Utc.with_ymd_and_hms(2023, 12, 1, 0, 0, 0).unwrap()
+        );
+        assert_eq!(
+            month_till,
+            Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap()
+                + async_session::chrono::Duration::milliseconds(999)
+        );
+        let (year_from, year_till) =
+            FinanceBudgetHandle::get_period_times(&BudgetPeriod::Yearly, report_time);
+        assert_eq!(
+            year_from,
+            Utc.with_ymd_and_hms(2023, 1, 1, 0, 0, 0).unwrap()
+        );
+        assert!(year_till < Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert!(year_till > Utc.with_ymd_and_hms(2023, 12, 31, 23, 59, 59).unwrap());
+    }
+
+    #[tokio::test]
+    async fn test_budget_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let budget_handle_1 =
+            FinanceBudgetHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        // (account title, category)
+        let account_data = [
+            ("groceries", AccountCategory::Expense),
+            ("bank", AccountCategory::Asset),
+            ("salary", AccountCategory::Income),
+            ("closing", AccountCategory::Equity),
+        ];
+        let mut accounts = Vec::new();
+        for (account_title, account_category) in account_data {
+            let mut finance_account_type = FinanceAccountType {
+                description: format!("type_description_{}", account_title),
+                title: format!("type_{}", account_title),
+                id: Uuid::new(),
+                account_category: account_category,
+            };
+            let insert_finance_account_type_result =
+                account_handle_1.finance_account_type_upsert(&mut finance_account_type);
+            assert!(
+                insert_finance_account_type_result.is_ok(),
+                "{}",
+                insert_finance_account_type_result.unwrap_err()
+            );
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // (debit account, credit account, amount, booking time)
+        let booking_data = [
+            (
+                0,
+                1,
+                10000,
+                Utc.with_ymd_and_hms(2023, 4, 28, 12, 0, 0).unwrap(),
+            ),
+            (
+                0,
+                1,
+                15000,
+                Utc.with_ymd_and_hms(2023, 5, 3, 12, 0, 0).unwrap(),
+            ),
+            (
+                0,
+                1,
+                30000,
+                Utc.with_ymd_and_hms(2023, 5, 20, 12, 0, 0).unwrap(),
+            ),
+            (
+                1,
+                0,
+                5000,
+                Utc.with_ymd_and_hms(2023, 5, 25, 12, 0, 0).unwrap(),
+            ),
+            (
+                0,
+                1,
+                7000,
+                Utc.with_ymd_and_hms(2023, 6, 2, 12, 0, 0).unwrap(),
+            ),
+            (
+                1,
+                2,
+                250000,
+                Utc.with_ymd_and_hms(2023, 1, 31, 12, 0, 0).unwrap(),
+            ),
+            (
+                1,
+                2,
+                250000,
+                Utc.with_ymd_and_hms(2023, 2, 28, 12, 0, 0).unwrap(),
+            ),
+            (
+                1,
+                2,
+                250000,
+                Utc.with_ymd_and_hms(2022, 12, 31, 12, 0, 0).unwrap(),
+            ),
+        ];
+        for (debit_index, credit_index, amount, booking_time) in booking_data {
+            let finance_booking_request = FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[debit_index].id,
+                credit_finance_account_id: accounts[credit_index].id,
+                booking_time,
+                amount,
+                title: "budget booking".into(),
+                description: "budget booking description".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            };
+            let insert_result = booking_handle_1
+                .finance_insert_booking_entry(&finance_booking_request)
+                .await;
+            assert!(insert_result.is_ok(), "{}", insert_result.unwrap_err());
+        }
+
+        // 1) create budgets, invalid budgets are rejected
+        let groceries_budget = FinanceBudget {
+            id: Uuid::new(),
+            finance_account_id: accounts[0].id,
+            period: BudgetPeriod::Monthly,
+            amount: 40000,
+        };
+        let upsert_result = budget_handle_1.budget_upsert(&groceries_budget).await;
+        assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+        let salary_budget = FinanceBudget {
+            id: Uuid::new(),
+            finance_account_id: accounts[2].id,
+            period: BudgetPeriod::Yearly,
+            amount: 3000000,
+        };
+        let upsert_result = budget_handle_1.budget_upsert(&salary_budget).await;
+        assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+
+        let invalid_budgets = [
+            FinanceBudget {
+                amount: 0,
+                ..groceries_budget.clone()
+            },
+            FinanceBudget {
+                id: Uuid::new(),
+                ..groceries_budget.clone()
+            },
+            FinanceBudget {
+                id: Uuid::new(),
+                finance_account_id: Uuid::new(),
+                ..groceries_budget.clone()
+            },
+        ];
+        for invalid_budget in invalid_budgets {
+            assert!(
+                budget_handle_1
+                    .budget_upsert(&invalid_budget)
+                    .await
+                    .is_err(),
+                "budget {:?} should be rejected",
+                invalid_budget
+            );
+        }
+        // a yearly budget for the same account is possible
+        let groceries_yearly_budget = FinanceBudget {
+            id: Uuid::new(),
+            period: BudgetPeriod::Yearly,
+            amount: 500000,
+            ..groceries_budget.clone()
+        };
+        let upsert_result = budget_handle_1
+            .budget_upsert(&groceries_yearly_budget)
+            .await;
+        assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+        assert_eq!(budget_handle_1.budget_list().await.unwrap().len(), 3);
+
+        // 2) budget vs actual in May 2023
+        let report_time = Utc.with_ymd_and_hms(2023, 5, 15, 0, 0, 0).unwrap();
+        let report_result = budget_handle_1.budget_report(report_time).await;
+        assert!(report_result.is_ok(), "{}", report_result.unwrap_err());
+        let report = report_result.unwrap();
+        assert_eq!(report.lines.len(), 3);
+
+        let groceries_line = report
+            .lines
+            .iter()
+            .find(|elem| elem.budget_id.eq(&groceries_budget.id.to_string()))
+            .unwrap();
+        // refund is subtracted, April and June are outside of the period
+        assert_eq!(groceries_line.actual_amount, 40000);
+        assert_eq!(groceries_line.remaining_amount, 0);
+        assert_eq!(groceries_line.percentage_used, 100.0);
+
+        let groceries_yearly_line = report
+            .lines
+            .iter()
+            .find(|elem| elem.budget_id.eq(&groceries_yearly_budget.id.to_string()))
+            .unwrap();
+        assert_eq!(groceries_yearly_line.actual_amount, 57000);
+        assert_eq!(groceries_yearly_line.remaining_amount, 443000);
+        assert_eq!(groceries_yearly_line.percentage_used, 11.4);
+
+        // income is positive on the credit side, last year is not counted
+        let salary_line = report
+            .lines
+            .iter()
+            .find(|elem| elem.budget_id.eq(&salary_budget.id.to_string()))
+            .unwrap();
+        assert_eq!(salary_line.actual_amount, 500000);
+        assert_eq!(salary_line.remaining_amount, 2500000);
+
+        // 3) exceeded budget
+        let mut smaller_groceries_budget = groceries_budget.clone();
+        smaller_groceries_budget.amount = 32000;
+        let upsert_result = budget_handle_1
+            .budget_upsert(&smaller_groceries_budget)
+            .await;
+        assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+        let report = budget_handle_1.budget_report(report_time).await.unwrap();
+        let groceries_line = report
+            .lines
+            .iter()
+            .find(|elem| elem.budget_id.eq(&groceries_budget.id.to_string()))
+            .unwrap();
+        assert_eq!(groceries_line.remaining_amount, -8000);
+        assert_eq!(groceries_line.percentage_used, 125.0);
+
+        // 4) delete
+        let delete_result = budget_handle_1.budget_delete(&salary_budget.id).await;
+        assert!(delete_result.is_ok());
+        assert!(budget_handle_1
+            .budget_delete(&salary_budget.id)
+            .await
+            .is_err());
+        let report = budget_handle_1.budget_report(report_time).await.unwrap();
+        assert_eq!(report.lines.len(), 2);
+
+        // 5) closing the period inside June, the closing entries and the saldos are not counted
+        let close_result = booking_handle_1
+            .close_accounts(
+                &vec![accounts[0].id, accounts[1].id, accounts[2].id],
+                &accounts[3].id,
+                Utc.with_ymd_and_hms(2023, 6, 30, 12, 0, 0).unwrap(),
+            )
+            .await;
+        assert!(close_result.is_ok(), "{}", close_result.unwrap_err());
+        let june_report_time = Utc.with_ymd_and_hms(2023, 6, 15, 0, 0, 0).unwrap();
+        let report = budget_handle_1
+            .budget_report(june_report_time)
+            .await
+            .unwrap();
+        let groceries_line = report
+            .lines
+            .iter()
+            .find(|elem| elem.budget_id.eq(&groceries_budget.id.to_string()))
+            .unwrap();
+        assert_eq!(groceries_line.actual_amount, 7000);
+        let groceries_yearly_line = report
+            .lines
+            .iter()
+            .find(|elem| elem.budget_id.eq(&groceries_yearly_budget.id.to_string()))
+            .unwrap();
+        assert_eq!(groceries_yearly_line.actual_amount, 57000);
+    }
+}
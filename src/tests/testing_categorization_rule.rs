@@ -0,0 +1,405 @@
+#[cfg(test)]
+mod test_categorization_rule_handle {
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        categorization_rule_logic::FinanceCategorizationRuleHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{
+            AccountCategory, BankImportPreviewRow, FinanceAccount, FinanceAccountType,
+            FinanceBookingRequest, FinanceCategorizationRule,
+        },
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    fn create_rule(
+        title: &str,
+        priority: i32,
+        counter_account_id: &Uuid,
+    ) -> FinanceCategorizationRule {
+        return FinanceCategorizationRule {
+            id: Uuid::new(),
+            title: title.into(),
+            priority,
+            payee_pattern: "".into(),
+            text_pattern: "".into(),
+            amount_min: None,
+            amount_max: None,
+            source_account_id: None,
+            counter_account_id: counter_account_id.clone(),
+            title_rewrite: "".into(),
+            tags: Vec::new(),
+        };
+    }
+
+    fn create_preview_row(
+        row_number: usize,
+        debit_account_id: &Uuid,
+        credit_account_id: &Uuid,
+        amount: u64,
+        title: &str,
+        description: &str,
+    ) -> BankImportPreviewRow {
+        return BankImportPreviewRow {
+            row_number,
+            booking_request: Some(FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: debit_account_id.clone(),
+                credit_finance_account_id: credit_account_id.clone(),
+                booking_time: Utc
+                    .with_ymd_and_hms(2024, 3, row_number as u32, 12, 0, 0)
+                    .unwrap(),
+                amount,
+                title: title.into(),
+                description: description.into(),
+                tags: vec!["import".into()],
+                import_reference: None,
+            }),
+            error: "".into(),
+            duplicate_journal_entry_id: None,
+        };
+    }
+
+    #[test]
+    fn test_categorization_rule_apply() {
+        let bank_account_id = Uuid::new();
+        let other_bank_account_id = Uuid::new();
+        let unassigned_income_id = Uuid::new();
+        let unassigned_expenses_id = Uuid::new();
+        let groceries_id = Uuid::new();
+        let salary_id = Uuid::new();
+        let small_purchases_id = Uuid::new();
+
+        let mut groceries_rule = create_rule("groceries", 10, &groceries_id);
+        groceries_rule.payee_pattern = "(?i)^(rewe|edeka)".into();
+        groceries_rule.amount_max = Some(0);
+        groceries_rule.title_rewrite = "Groceries".into();
+        groceries_rule.tags = vec!["food".into(), "import".into()];
+        let mut salary_rule = create_rule("salary", 20, &salary_id);
+        salary_rule.text_pattern = "Gehalt".into();
+        salary_rule.amount_min = Some(1);
+        salary_rule.source_account_id = Some(bank_account_id);
+        // would match the groceries too, but is evaluated after the groceries rule
+        let mut small_purchases_rule = create_rule("small purchases", 30, &small_purchases_id);
+        small_purchases_rule.amount_min = Some(-2000);
+        small_purchases_rule.amount_max = Some(-1);
+        let rules = vec![small_purchases_rule, salary_rule, groceries_rule];
+
+        let mut preview_rows = vec![
+            create_preview_row(
+                1,
+                &unassigned_expenses_id,
+                &bank_account_id,
+                1599,
+                "REWE Markt 123",
+                "",
+            ),
+            create_preview_row(
+                2,
+                &bank_account_id,
+                &unassigned_income_id,
+                250000,
+                "Arbeitgeber GmbH",
+                "Gehalt Maerz",
+            ),
+            create_preview_row(
+                3,
+                &unassigned_expenses_id,
+                &bank_account_id,
+                450,
+                "Kiosk",
+                "",
+            ),
+            create_preview_row(
+                4,
+                &unassigned_expenses_id,
+                &bank_account_id,
+                8000,
+                "Tankstelle",
+                "",
+            ),
+            // received money from the groceries store does not match the amount range
+            create_preview_row(
+                5,
+                &bank_account_id,
+                &unassigned_income_id,
+                500,
+                "Edeka Pfand",
+                "",
+            ),
+            BankImportPreviewRow {
+                row_number: 6,
+                booking_request: None,
+                error: "amount is zero".into(),
+                duplicate_journal_entry_id: None,
+            },
+        ];
+        let apply_result = FinanceCategorizationRuleHandle::apply_rules(
+            &rules,
+            &bank_account_id,
+            &mut preview_rows,
+        );
+        assert!(apply_result.is_ok(), "{}", apply_result.unwrap_err());
+        assert_eq!(apply_result.unwrap(), 3);
+
+        let groceries_request = preview_rows[0].booking_request.as_ref().unwrap();
+        assert_eq!(groceries_request.debit_finance_account_id, groceries_id);
+        assert_eq!(groceries_request.credit_finance_account_id, bank_account_id);
+        assert_eq!(groceries_request.title, "Groceries");
+        assert_eq!(
+            groceries_request.tags,
+            vec!["import".to_string(), "food".to_string()]
+        );
+
+        let salary_request = preview_rows[1].booking_request.as_ref().unwrap();
+        assert_eq!(salary_request.debit_finance_account_id, bank_account_id);
+        assert_eq!(salary_request.credit_finance_account_id, salary_id);
+        assert_eq!(salary_request.title, "Arbeitgeber GmbH");
+
+        let kiosk_request = preview_rows[2].booking_request.as_ref().unwrap();
+        assert_eq!(kiosk_request.debit_finance_account_id, small_purchases_id);
+
+        let fuel_request = preview_rows[3].booking_request.as_ref().unwrap();
+        assert_eq!(
+            fuel_request.debit_finance_account_id,
+            unassigned_expenses_id
+        );
+        let deposit_request = preview_rows[4].booking_request.as_ref().unwrap();
+        assert_eq!(
+            deposit_request.credit_finance_account_id,
+            unassigned_income_id
+        );
+        assert_eq!(deposit_request.tags, vec!["import".to_string()]);
+
+        // the salary rule is limited to the first bank account
+        let mut other_bank_rows = vec![create_preview_row(
+            1,
+            &other_bank_account_id,
+            &unassigned_income_id,
+            250000,
+            "Arbeitgeber GmbH",
+            "Gehalt Maerz",
+        )];
+        let other_bank_result = FinanceCategorizationRuleHandle::apply_rules(
+            &rules,
+            &other_bank_account_id,
+            &mut other_bank_rows,
+        );
+        assert_eq!(other_bank_result, Ok(0));
+
+        let mut broken_rule = create_rule("broken", 1, &groceries_id);
+        broken_rule.payee_pattern = "(unclosed".into();
+        let broken_result = FinanceCategorizationRuleHandle::apply_rules(
+            &vec![broken_rule],
+            &bank_account_id,
+            &mut preview_rows,
+        );
+        assert!(broken_result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_categorization_rule_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let rule_handle_1 = FinanceCategorizationRuleHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["bank", "income", "expenses", "groceries"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) invalid rules are rejected
+        let mut groceries_rule = create_rule(" groceries ", 10, &accounts[3].id);
+        groceries_rule.payee_pattern = "(?i)rewe".into();
+        groceries_rule.source_account_id = Some(accounts[0].id);
+        groceries_rule.tags = vec![" food ".into(), "food".into()];
+
+        let mut empty_title_rule = groceries_rule.clone();
+        empty_title_rule.title = " ".into();
+        assert!(rule_handle_1.rule_upsert(&empty_title_rule).await.is_err());
+        let mut invalid_pattern_rule = groceries_rule.clone();
+        invalid_pattern_rule.text_pattern = "[a-".into();
+        assert!(rule_handle_1
+            .rule_upsert(&invalid_pattern_rule)
+            .await
+            .is_err());
+        let mut invalid_range_rule = groceries_rule.clone();
+        invalid_range_rule.amount_min = Some(100);
+        invalid_range_rule.amount_max = Some(-100);
+        assert!(rule_handle_1
+            .rule_upsert(&invalid_range_rule)
+            .await
+            .is_err());
+        let mut unknown_account_rule = groceries_rule.clone();
+        unknown_account_rule.counter_account_id = Uuid::new();
+        assert!(rule_handle_1
+            .rule_upsert(&unknown_account_rule)
+            .await
+            .is_err());
+
+        // 2) saved rules are listed in priority order
+        let fallback_rule = create_rule("everything else", 99, &accounts[2].id);
+        let upsert_fallback_result = rule_handle_1.rule_upsert(&fallback_rule).await;
+        assert!(
+            upsert_fallback_result.is_ok(),
+            "{}",
+            upsert_fallback_result.unwrap_err()
+        );
+        let upsert_groceries_result = rule_handle_1.rule_upsert(&groceries_rule).await;
+        assert!(
+            upsert_groceries_result.is_ok(),
+            "{}",
+            upsert_groceries_result.unwrap_err()
+        );
+        let rule_list = rule_handle_1.rule_list().await.unwrap();
+        assert_eq!(rule_list.len(), 2);
+        assert_eq!(rule_list[0].id, groceries_rule.id);
+        assert_eq!(rule_list[0].title, "groceries");
+        assert_eq!(rule_list[0].tags, vec!["food".to_string()]);
+        assert_eq!(rule_list[1].id, fallback_rule.id);
+
+        // 3) the rules are applied to the rows of an import
+        let mut preview_rows = vec![
+            create_preview_row(1, &accounts[2].id, &accounts[0].id, 2599, "REWE Markt", ""),
+            create_preview_row(2, &accounts[2].id, &accounts[0].id, 999, "Buchhandlung", ""),
+        ];
+        let categorize_result = rule_handle_1
+            .categorize_preview_rows(&accounts[0].id, &mut preview_rows)
+            .await;
+        assert_eq!(categorize_result, Ok(2));
+        assert_eq!(
+            preview_rows[0]
+                .booking_request
+                .as_ref()
+                .unwrap()
+                .debit_finance_account_id,
+            accounts[3].id
+        );
+        assert_eq!(
+            preview_rows[1]
+                .booking_request
+                .as_ref()
+                .unwrap()
+                .debit_finance_account_id,
+            accounts[2].id
+        );
+
+        // 4) test the rule against the journal entries, reversed entries are ignored
+        let mut journal_entry_ids = Vec::new();
+        for (title, amount, day) in [
+            ("Rewe City", 1250, 5),
+            ("REWE Markt", 3000, 12),
+            ("Bank fee", 500, 12),
+        ] {
+            let insert_result = booking_handle_1
+                .finance_insert_booking_entry(&FinanceBookingRequest {
+                    is_simple_entry: true,
+                    is_saldo: false,
+                    debit_finance_account_id: accounts[2].id,
+                    credit_finance_account_id: accounts[0].id,
+                    booking_time: Utc.with_ymd_and_hms(2024, 2, day, 12, 0, 0).unwrap(),
+                    amount,
+                    title: title.into(),
+                    description: "".into(),
+                    tags: Vec::new(),
+                    import_reference: None,
+                })
+                .await;
+            assert!(insert_result.is_ok(), "{}", insert_result.unwrap_err());
+            journal_entry_ids.push(insert_result.unwrap().journal_entry.id);
+        }
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_ids[1], &"wrong amount".to_string())
+            .await;
+        assert!(reverse_result.is_ok(), "{}", reverse_result.unwrap_err());
+
+        let test_result = rule_handle_1.test_rule(&groceries_rule, None, None).await;
+        assert!(test_result.is_ok(), "{}", test_result.unwrap_err());
+        let matching_entries = test_result.unwrap();
+        assert_eq!(matching_entries.len(), 1);
+        assert_eq!(matching_entries[0].id, journal_entry_ids[0]);
+
+        // paid money from the view of the credit account
+        let mut amount_rule = create_rule("small payments", 1, &accounts[2].id);
+        amount_rule.amount_min = Some(-1000);
+        amount_rule.amount_max = Some(-1);
+        let amount_test_result = rule_handle_1
+            .test_rule(
+                &amount_rule,
+                Some(Utc.with_ymd_and_hms(2024, 2, 10, 0, 0, 0).unwrap()),
+                None,
+            )
+            .await;
+        assert!(
+            amount_test_result.is_ok(),
+            "{}",
+            amount_test_result.unwrap_err()
+        );
+        let amount_matching_entries = amount_test_result.unwrap();
+        assert_eq!(amount_matching_entries.len(), 1);
+        assert_eq!(amount_matching_entries[0].id, journal_entry_ids[2]);
+
+        // 5) delete
+        let delete_result = rule_handle_1.rule_delete(&fallback_rule.id).await;
+        assert!(delete_result.is_ok(), "{}", delete_result.unwrap_err());
+        assert_eq!(rule_handle_1.rule_list().await.unwrap().len(), 1);
+        assert!(rule_handle_1.rule_delete(&fallback_rule.id).await.is_err());
+    }
+}
@@ -2,6 +2,8 @@
 
 mod test_email_validation {
 
+    use async_session::chrono::{TimeZone, Utc};
+
     use crate::convert_tools::ConvertTools;
 
     static TEST_INIT: std::sync::Once = std::sync::Once::new();
@@ -24,4 +26,21 @@ mod test_email_validation {
         assert_ne!(test_input,conversion_value);
 
     }
+
+    #[test]
+    fn test_date_time_input() {
+        init();
+
+        let with_seconds = ConvertTools::get_utc_time_from_date_time_input("2024-02-29", "08:15:30");
+        assert_eq!(with_seconds.unwrap(), Utc.with_ymd_and_hms(2024, 2, 29, 8, 15, 30).unwrap());
+
+        let without_seconds = ConvertTools::get_utc_time_from_date_time_input("2024-02-29", "08:15");
+        assert_eq!(without_seconds.unwrap(), Utc.with_ymd_and_hms(2024, 2, 29, 8, 15, 0).unwrap());
+
+        let end_of_day = ConvertTools::get_utc_time_from_date_input("2024-02-29", true);
+        assert_eq!(end_of_day.unwrap(), Utc.with_ymd_and_hms(2024, 2, 29, 23, 59, 59).unwrap());
+
+        assert!(ConvertTools::get_utc_time_from_date_time_input("2023-02-29", "08:15").is_err());
+        assert!(ConvertTools::get_utc_time_from_date_time_input("2024-02-29", "25:00").is_err());
+    }
 }
\ No newline at end of file
@@ -0,0 +1,216 @@
+#[cfg(test)]
+mod test_csv_export {
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        bank_import::FinanceBankImportHandle,
+        csv_export::FinanceCsvExportHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{
+            AccountCategory, CsvExportSettings, FinanceAccount, FinanceAccountType,
+            FinanceBookingRequest,
+        },
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    #[test]
+    fn test_csv_export_formatting() {
+        assert_eq!(
+            FinanceCsvExportHandle::format_amount(123456, ','),
+            "1234,56"
+        );
+        assert_eq!(FinanceCsvExportHandle::format_amount(5, '.'), "0.05");
+        assert_eq!(FinanceCsvExportHandle::format_amount(-1999, '.'), "-19.99");
+        assert_eq!(FinanceCsvExportHandle::format_amount(0, ','), "0,00");
+        // no rounding errors for amounts that can not be represented as f64
+        assert_eq!(
+            FinanceCsvExportHandle::format_amount(9007199254740993, '.'),
+            "90071992547409.93"
+        );
+
+        assert_eq!(FinanceCsvExportHandle::escape_field("rent", ';'), "rent");
+        assert_eq!(
+            FinanceCsvExportHandle::escape_field("rent; March", ';'),
+            "\"rent; March\""
+        );
+        assert_eq!(
+            FinanceCsvExportHandle::escape_field("rent; March", ','),
+            "rent; March"
+        );
+        assert_eq!(
+            FinanceCsvExportHandle::escape_field("the \"big\" shop", ';'),
+            "\"the \"\"big\"\" shop\""
+        );
+        assert_eq!(
+            FinanceCsvExportHandle::escape_field("line 1\nline 2", ';'),
+            "\"line 1\nline 2\""
+        );
+
+        assert!(FinanceCsvExportHandle::check_settings(&CsvExportSettings {
+            delimiter: ';',
+            decimal_separator: ','
+        })
+        .is_ok());
+        assert!(FinanceCsvExportHandle::check_settings(&CsvExportSettings {
+            delimiter: ',',
+            decimal_separator: ','
+        })
+        .is_err());
+        assert!(FinanceCsvExportHandle::check_settings(&CsvExportSettings {
+            delimiter: '"',
+            decimal_separator: '.'
+        })
+        .is_err());
+        assert!(FinanceCsvExportHandle::check_settings(&CsvExportSettings {
+            delimiter: ';',
+            decimal_separator: '\''
+        })
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_csv_export_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let export_handle_1 =
+            FinanceCsvExportHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["bank", "expenses; misc"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        for (title, amount, day) in [
+            ("groceries \"organic\"", 1999, 3),
+            ("rent\nMarch", 80000, 15),
+            ("book", 1250, 28),
+        ] {
+            let insert_result = booking_handle_1
+                .finance_insert_booking_entry(&FinanceBookingRequest {
+                    is_simple_entry: true,
+                    is_saldo: false,
+                    debit_finance_account_id: accounts[1].id,
+                    credit_finance_account_id: accounts[0].id,
+                    booking_time: Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap(),
+                    amount,
+                    title: title.into(),
+                    description: "".into(),
+                    tags: Vec::new(),
+                    import_reference: None,
+                })
+                .await;
+            assert!(insert_result.is_ok(), "{}", insert_result.unwrap_err());
+        }
+
+        let settings = CsvExportSettings {
+            delimiter: ';',
+            decimal_separator: ',',
+        };
+        let journal_result = export_handle_1
+            .export_journal(
+                Some(Utc.with_ymd_and_hms(2024, 3, 1, 0, 0, 0).unwrap()),
+                Some(Utc.with_ymd_and_hms(2024, 3, 20, 23, 59, 59).unwrap()),
+                &settings,
+            )
+            .await;
+        assert!(journal_result.is_ok(), "{}", journal_result.unwrap_err());
+        let journal_csv = journal_result.unwrap();
+
+        // the exported text is read back with the parser of the bank import
+        let journal_records = FinanceBankImportHandle::parse_csv_records(&journal_csv, ';');
+        assert!(journal_records.is_ok(), "{}", journal_records.unwrap_err());
+        let journal_records = journal_records.unwrap();
+        assert_eq!(journal_records.len(), 3);
+        assert_eq!(journal_records[0][0], "number");
+        assert_eq!(journal_records[1][2], "expenses; misc");
+        assert_eq!(journal_records[1][4], "19,99");
+        assert_eq!(journal_records[1][5], "groceries \"organic\"");
+        assert_eq!(journal_records[2][4], "800,00");
+        assert_eq!(journal_records[2][5], "rent\nMarch");
+
+        let account_result = export_handle_1
+            .export_account_tables(
+                None,
+                Some(Utc.with_ymd_and_hms(2024, 3, 20, 23, 59, 59).unwrap()),
+                &CsvExportSettings {
+                    delimiter: ',',
+                    decimal_separator: '.',
+                },
+            )
+            .await;
+        assert!(account_result.is_ok(), "{}", account_result.unwrap_err());
+        let account_records =
+            FinanceBankImportHandle::parse_csv_records(&account_result.unwrap(), ',').unwrap();
+        // header, two entries and the balance per account
+        assert_eq!(account_records.len(), 7);
+        let bank_balance_record = account_records
+            .iter()
+            .find(|elem| elem[0].eq("bank") && elem[2].eq("balance"))
+            .unwrap();
+        assert_eq!(bank_balance_record[4], "819.99");
+
+        assert!(export_handle_1
+            .export_journal(
+                None,
+                None,
+                &CsvExportSettings {
+                    delimiter: '.',
+                    decimal_separator: '.',
+                },
+            )
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,664 @@
+#[cfg(test)]
+pub(crate) mod test_database_postgres {
+    use std::ops::Deref;
+
+    use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
+    use async_session::{
+        chrono::{TimeZone, Utc},
+        Session, SessionStore,
+    };
+    use futures::{executor, future::join_all};
+    use log::warn;
+    use mongodb::bson::Uuid;
+    use secrecy::{ExposeSecret, Secret};
+
+    use crate::{
+        accounting_config_database::DBFinanceConfigFunctions,
+        accounting_database::DBFinanceAccountingFunctions,
+        attachment_database::DBFinanceAttachmentFunctions,
+        categorization_rule_database::DBFinanceCategorizationRuleFunctions,
+        database_handler_mongodb::{DbBackendType, DbConnectionSetting, EmailVerificationStatus},
+        database_handler_postgres::{DbHandlerPostgres, PostgresSessionStore},
+        datatypes::{
+            AccountCategory, FinanceAccount, FinanceAccountType, FinanceBookingRequest,
+            FinanceCategorizationRule, FinanceJournalAttachment, FinanceRecurringBooking,
+            GenerallUserData, RecurringBookingSchedule,
+        },
+        password_handle::UserCredentialsHashed,
+        recurring_booking_database::DBFinanceRecurringBookingFunctions,
+        recurring_booking_logic::FinanceRecurringBookingHandle,
+        setting_struct::TestSettingStruct,
+        tests::testing_accounting_config::test_accounting_handle::init,
+        user_database::DBUserFunctions,
+    };
+
+    /// connection settings of a test database, the database is dropped when the test is finished
+    pub(crate) struct PostgresTestConnection {
+        connection_settings: DbConnectionSetting,
+    }
+
+    impl Deref for PostgresTestConnection {
+        type Target = DbConnectionSetting;
+
+        fn deref(&self) -> &DbConnectionSetting {
+            return &self.connection_settings;
+        }
+    }
+
+    impl Drop for PostgresTestConnection {
+        fn drop(&mut self) {
+            let server_settings = DbConnectionSetting {
+                instance: "".into(),
+                ..self.connection_settings.clone()
+            };
+            let database_name = self.connection_settings.instance.clone();
+            // FORCE also closes connections a failed test left open
+            let drop_result = executor::block_on(async move {
+                let client = DbHandlerPostgres::open_client(&server_settings).await?;
+                let execute_result = client
+                    .execute(
+                        format!("DROP DATABASE IF EXISTS {} WITH (FORCE)", database_name).as_str(),
+                        &[],
+                    )
+                    .await;
+                if execute_result.is_err() {
+                    return Err(DbHandlerPostgres::format_error(
+                        &execute_result.unwrap_err(),
+                    ));
+                }
+                return Ok(());
+            });
+            if drop_result.is_err() {
+                warn!(target:"app::FinanceOverView","could not drop test database {}: {}",self.connection_settings.instance,drop_result.unwrap_err());
+            }
+        }
+    }
+
+    /// creates a new database on the postgres server of the test settings with all migrations applied
+    pub(crate) async fn create_postgres_test_connection() -> PostgresTestConnection {
+        init();
+        let test_setting = TestSettingStruct::global().clone();
+        let server_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: test_setting.postgres_database_password,
+            url: test_setting.postgres_database_url,
+            user: test_setting.postgres_database_user,
+        };
+        let client_result = DbHandlerPostgres::open_client(&server_settings).await;
+        assert!(client_result.is_ok(), "{}", client_result.unwrap_err());
+
+        let database_name = format!(
+            "finance_overview_test_{}",
+            Uuid::new().to_string().replace('-', "")
+        );
+        let create_result = client_result
+            .unwrap()
+            .execute(format!("CREATE DATABASE {}", database_name).as_str(), &[])
+            .await;
+        assert!(
+            create_result.is_ok(),
+            "{}",
+            DbHandlerPostgres::format_error(&create_result.unwrap_err())
+        );
+
+        let connection_settings = PostgresTestConnection {
+            connection_settings: DbConnectionSetting {
+                instance: database_name,
+                ..server_settings
+            },
+        };
+        assert!(DbHandlerPostgres::validate_db_structure(
+            &connection_settings
+        ));
+        return connection_settings;
+    }
+
+    pub(crate) async fn create_postgres_test_user(
+        connection_settings: &DbConnectionSetting,
+        user_name: &str,
+    ) -> Uuid {
+        let insert_result = DbHandlerPostgres::new()
+            .insert_user(
+                connection_settings,
+                &UserCredentialsHashed {
+                    username: user_name.to_string(),
+                    password_hash: Secret::new("some_password_hash".to_string()),
+                },
+            )
+            .await;
+        assert!(insert_result.is_ok(), "{}", insert_result.unwrap_err());
+        return insert_result.unwrap();
+    }
+
+    #[test]
+    fn test_backend_type_setting() {
+        assert_eq!(
+            DbBackendType::from_setting_value("PostgreSQL").unwrap(),
+            DbBackendType::PostgreSQL
+        );
+        assert_eq!(
+            DbBackendType::from_setting_value("postgres").unwrap(),
+            DbBackendType::PostgreSQL
+        );
+    }
+
+    #[tokio::test]
+    async fn test_postgres_structure_and_user_handling() {
+        let connection_settings = create_postgres_test_connection().await;
+        let postgres_db = DbHandlerPostgres::new();
+
+        // migrations are only applied once
+        assert!(DbHandlerPostgres::validate_db_structure(
+            &connection_settings
+        ));
+        let mut client = DbHandlerPostgres::open_client(&connection_settings)
+            .await
+            .unwrap();
+        assert_eq!(
+            DbHandlerPostgres::apply_migrations(&mut client)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let user_name = "postgres_user".to_string();
+        assert!(!postgres_db
+            .check_user_exsists_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap());
+        let user_id = create_postgres_test_user(&connection_settings, &user_name).await;
+        assert!(postgres_db
+            .check_user_exsists_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap());
+        let stored_credentials = postgres_db
+            .get_stored_credentials_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap();
+        assert_eq!(stored_credentials.user_id, user_id);
+        assert_eq!(
+            stored_credentials.password_hash.expose_secret(),
+            "some_password_hash"
+        );
+
+        // email verification
+        assert!(matches!(
+            postgres_db
+                .check_email_verfification_by_name(&connection_settings, &user_name)
+                .await
+                .unwrap(),
+            EmailVerificationStatus::NotGiven
+        ));
+        let validation_token = postgres_db
+            .update_user_email(
+                &connection_settings,
+                &user_name,
+                &"postgres_user@example.com".to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(postgres_db
+            .verify_email_by_name(
+                &connection_settings,
+                &user_name,
+                &Secret::new("wrong token".to_string())
+            )
+            .await
+            .is_err());
+        assert!(matches!(
+            postgres_db
+                .verify_email_by_name(
+                    &connection_settings,
+                    &user_name,
+                    &Secret::new(validation_token)
+                )
+                .await
+                .unwrap(),
+            EmailVerificationStatus::Verified
+        ));
+
+        // general data
+        let general_data = GenerallUserData {
+            first_name: "first".into(),
+            last_name: "last".into(),
+        };
+        assert!(postgres_db
+            .update_general_user_data_by_name(&connection_settings, &user_name, &general_data)
+            .await
+            .is_ok());
+        let stored_general_data = postgres_db
+            .get_user_general_data_by_user_name(&connection_settings, &user_name)
+            .await
+            .unwrap();
+        assert_eq!(stored_general_data.first_name, general_data.first_name);
+        assert_eq!(stored_general_data.last_name, general_data.last_name);
+
+        // a password reset needs a requested token
+        let salt = SaltString::generate(&mut rand::thread_rng());
+        let reset_secret_hash = Secret::new(
+            Argon2::default()
+                .hash_password("reset secret".as_bytes(), &salt)
+                .unwrap()
+                .to_string(),
+        );
+        assert!(postgres_db
+            .update_user_reset_secret(&connection_settings, &user_id, &reset_secret_hash)
+            .await
+            .unwrap());
+        assert!(postgres_db
+            .change_password_with_token(
+                &connection_settings,
+                &user_name,
+                &Uuid::new().to_string(),
+                &Secret::new("new_password_hash".to_string())
+            )
+            .await
+            .is_err());
+
+        assert!(postgres_db
+            .update_user_password(
+                &connection_settings,
+                &UserCredentialsHashed {
+                    username: user_name.clone(),
+                    password_hash: Secret::new("new_password_hash".to_string()),
+                }
+            )
+            .await
+            .unwrap());
+        let changed_credentials = postgres_db
+            .get_stored_credentials_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap();
+        assert_eq!(
+            changed_credentials.password_hash.expose_secret(),
+            "new_password_hash"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_postgres_route_calling_amount_and_sessions() {
+        let connection_settings = create_postgres_test_connection().await;
+        let route_name = "main".to_string();
+        for expected_amount in 1..4 {
+            assert_eq!(
+                DbHandlerPostgres::increase_route_calling_amount(&connection_settings, &route_name)
+                    .unwrap(),
+                expected_amount
+            );
+        }
+
+        let session_store = PostgresSessionStore::new(&connection_settings);
+        assert!(session_store.initialize().await.is_ok());
+        let mut session = Session::new();
+        session.insert("user_name", "postgres_user").unwrap();
+        let cookie_value = session_store.store_session(session).await.unwrap().unwrap();
+        let loaded_session = session_store
+            .load_session(cookie_value.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            loaded_session.get::<String>("user_name").unwrap(),
+            "postgres_user"
+        );
+        session_store.destroy_session(loaded_session).await.unwrap();
+        assert!(session_store
+            .load_session(cookie_value)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_postgres_concurrent_running_numbers() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id = create_postgres_test_user(&connection_settings, "postgres_user").await;
+        let postgres_db = DbHandlerPostgres::new();
+
+        let asset_type = FinanceAccountType {
+            description: "SomeTypeDescription".to_string(),
+            title: "SomeType".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        assert!(postgres_db
+            .finance_account_type_upsert(&connection_settings, &user_id, &asset_type)
+            .await
+            .is_ok());
+        let mut finance_account_ids = Vec::new();
+        for title in ["bank", "cash"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: asset_type.id,
+                title: title.into(),
+                description: "".into(),
+                account_number: None,
+            };
+            assert!(postgres_db
+                .finance_account_upsert(&connection_settings, &user_id, &finance_account)
+                .await
+                .is_ok());
+            finance_account_ids.push(finance_account.id);
+        }
+
+        // every booking uses its own connection, conflicting transactions are repeated
+        let booking_amount = 8;
+        let booking_futures = (0..booking_amount).map(|booking_index| {
+            postgres_db.finance_insert_booking_entry(
+                &connection_settings,
+                &user_id,
+                FinanceBookingRequest {
+                    is_simple_entry: true,
+                    is_saldo: false,
+                    debit_finance_account_id: finance_account_ids[1],
+                    credit_finance_account_id: finance_account_ids[0],
+                    booking_time: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+                    amount: 100 + booking_index,
+                    title: format!("withdrawal {}", booking_index),
+                    description: "".into(),
+                    tags: Vec::new(),
+                    import_reference: None,
+                },
+            )
+        });
+        let mut running_numbers = Vec::new();
+        for booking_result in join_all(booking_futures).await {
+            assert!(booking_result.is_ok(), "{}", booking_result.unwrap_err());
+            running_numbers.push(booking_result.unwrap().journal_entry.running_number);
+        }
+        running_numbers.sort();
+        assert_eq!(running_numbers, (1..=booking_amount).collect::<Vec<u64>>());
+        assert_eq!(
+            postgres_db
+                .finance_get_journal_max_number(&connection_settings, &user_id)
+                .await
+                .unwrap(),
+            booking_amount
+        );
+    }
+
+    #[tokio::test]
+    async fn test_postgres_concurrent_recurring_bookings() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id = create_postgres_test_user(&connection_settings, "postgres_user").await;
+        let postgres_db = DbHandlerPostgres::new();
+
+        let asset_type = FinanceAccountType {
+            description: "SomeTypeDescription".to_string(),
+            title: "SomeType".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        assert!(postgres_db
+            .finance_account_type_upsert(&connection_settings, &user_id, &asset_type)
+            .await
+            .is_ok());
+        let mut finance_account_ids = Vec::new();
+        for title in ["bank", "cash"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: asset_type.id,
+                title: title.into(),
+                description: "".into(),
+                account_number: None,
+            };
+            assert!(postgres_db
+                .finance_account_upsert(&connection_settings, &user_id, &finance_account)
+                .await
+                .is_ok());
+            finance_account_ids.push(finance_account.id);
+        }
+
+        let recurring_booking = FinanceRecurringBooking {
+            id: Uuid::new(),
+            booking_template: FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: finance_account_ids[1],
+                credit_finance_account_id: finance_account_ids[0],
+                booking_time: Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap(),
+                amount: 5000,
+                title: "pocket money".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            },
+            schedule: RecurringBookingSchedule::Weekly,
+            start_time: Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap(),
+            end_time: Some(Utc.with_ymd_and_hms(2024, 6, 30, 8, 0, 0).unwrap()),
+            last_booking_time: None,
+        };
+        assert!(postgres_db
+            .finance_recurring_booking_upsert(&connection_settings, &user_id, &recurring_booking)
+            .await
+            .is_ok());
+        let booking_time_till = Utc.with_ymd_and_hms(2024, 12, 31, 8, 0, 0).unwrap();
+        let expected_times = FinanceRecurringBookingHandle::get_due_occurrence_times(
+            &recurring_booking,
+            booking_time_till,
+        );
+
+        // runs of several server processes are not serialized, the database accepts every occurrence only once
+        let recurring_booking_handle =
+            FinanceRecurringBookingHandle::new(&connection_settings, &user_id, &postgres_db);
+        let run_futures =
+            (0..3).map(|_| recurring_booking_handle.book_due_occurrences(booking_time_till));
+        let mut booked_count = 0;
+        for run_result in join_all(run_futures).await {
+            assert!(run_result.is_ok(), "{}", run_result.unwrap_err());
+            let run = run_result.unwrap();
+            assert!(run.errors.is_empty(), "{:?}", run.errors);
+            booked_count += run.booking_results.len();
+        }
+        assert_eq!(booked_count, expected_times.len());
+        assert_eq!(
+            postgres_db
+                .finance_journal_entry_list(&connection_settings, &user_id, None, None, None)
+                .await
+                .unwrap()
+                .len(),
+            expected_times.len()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_postgres_foreign_keys() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id = create_postgres_test_user(&connection_settings, "postgres_user").await;
+        let postgres_db = DbHandlerPostgres::new();
+
+        // accounts need an existing account type of the user
+        let asset_type = FinanceAccountType {
+            description: "SomeTypeDescription".to_string(),
+            title: "SomeType".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let bank_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: asset_type.id,
+            title: "bank".into(),
+            description: "".into(),
+            account_number: Some(1200),
+        };
+        assert!(postgres_db
+            .finance_account_upsert(&connection_settings, &user_id, &bank_account)
+            .await
+            .is_err());
+        assert!(postgres_db
+            .finance_account_type_upsert(&connection_settings, &user_id, &asset_type)
+            .await
+            .is_ok());
+        assert!(postgres_db
+            .finance_account_upsert(&connection_settings, &user_id, &bank_account)
+            .await
+            .is_ok());
+        let cash_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: asset_type.id,
+            title: "cash".into(),
+            description: "".into(),
+            account_number: None,
+        };
+        assert!(postgres_db
+            .finance_account_upsert(&connection_settings, &user_id, &cash_account)
+            .await
+            .is_ok());
+
+        let mut recurring_booking = FinanceRecurringBooking {
+            id: Uuid::new(),
+            booking_template: FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: Uuid::new(),
+                credit_finance_account_id: bank_account.id,
+                booking_time: Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap(),
+                amount: 5000,
+                title: "pocket money".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            },
+            schedule: RecurringBookingSchedule::Monthly,
+            start_time: Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap(),
+            end_time: None,
+            last_booking_time: None,
+        };
+        assert!(postgres_db
+            .finance_recurring_booking_upsert(&connection_settings, &user_id, &recurring_booking)
+            .await
+            .is_err());
+        recurring_booking.booking_template.debit_finance_account_id = cash_account.id;
+        assert!(postgres_db
+            .finance_recurring_booking_upsert(&connection_settings, &user_id, &recurring_booking)
+            .await
+            .is_ok());
+        recurring_booking.last_booking_time =
+            Some(Utc.with_ymd_and_hms(2024, 2, 1, 8, 0, 0).unwrap());
+        assert!(postgres_db
+            .finance_recurring_booking_upsert(&connection_settings, &user_id, &recurring_booking)
+            .await
+            .is_ok());
+        let recurring_booking_list = postgres_db
+            .finance_recurring_booking_list(&connection_settings, &user_id)
+            .await
+            .unwrap();
+        assert_eq!(recurring_booking_list.len(), 1);
+        assert_eq!(
+            recurring_booking_list[0].last_booking_time,
+            recurring_booking.last_booking_time
+        );
+        // the progress of a booking run only moves the last booking time forward and keeps edits
+        recurring_booking.booking_template.amount = 6000;
+        assert!(postgres_db
+            .finance_recurring_booking_upsert(&connection_settings, &user_id, &recurring_booking)
+            .await
+            .is_ok());
+        assert!(postgres_db
+            .finance_recurring_booking_set_last_booking_time(
+                &connection_settings,
+                &user_id,
+                &recurring_booking.id,
+                &Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap()
+            )
+            .await
+            .is_ok());
+        assert!(postgres_db
+            .finance_recurring_booking_set_last_booking_time(
+                &connection_settings,
+                &user_id,
+                &recurring_booking.id,
+                &Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap()
+            )
+            .await
+            .is_ok());
+        let recurring_booking_list = postgres_db
+            .finance_recurring_booking_list(&connection_settings, &user_id)
+            .await
+            .unwrap();
+        assert_eq!(recurring_booking_list[0].booking_template.amount, 6000);
+        assert_eq!(
+            recurring_booking_list[0].last_booking_time,
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap())
+        );
+        assert_eq!(
+            postgres_db
+                .finance_recurring_booking_list_user_ids(&connection_settings)
+                .await
+                .unwrap(),
+            vec![user_id]
+        );
+
+        let categorization_rule = FinanceCategorizationRule {
+            id: Uuid::new(),
+            title: "supermarket".into(),
+            priority: 1,
+            payee_pattern: "(?i)market".into(),
+            text_pattern: "".into(),
+            amount_min: None,
+            amount_max: Some(0),
+            source_account_id: Some(bank_account.id),
+            counter_account_id: cash_account.id,
+            title_rewrite: "".into(),
+            tags: vec!["food".into()],
+        };
+        assert!(postgres_db
+            .finance_categorization_rule_upsert(
+                &connection_settings,
+                &user_id,
+                &categorization_rule
+            )
+            .await
+            .is_ok());
+        let categorization_rule_list = postgres_db
+            .finance_categorization_rule_list(&connection_settings, &user_id)
+            .await
+            .unwrap();
+        assert_eq!(categorization_rule_list, vec![categorization_rule]);
+
+        // attachments need an existing journal entry
+        let mut attachment = FinanceJournalAttachment {
+            id: Uuid::new(),
+            finance_journal_diary_id: Uuid::new(),
+            file_name: "receipt.pdf".into(),
+            mime_type: "application/pdf".into(),
+            size_bytes: 1024,
+            upload_time: Utc.with_ymd_and_hms(2024, 3, 2, 9, 0, 0).unwrap(),
+        };
+        assert!(postgres_db
+            .finance_journal_attachment_insert(&connection_settings, &user_id, &attachment)
+            .await
+            .is_err());
+        let booking_result = postgres_db
+            .finance_insert_booking_entry(
+                &connection_settings,
+                &user_id,
+                recurring_booking.booking_template.clone(),
+            )
+            .await;
+        assert!(booking_result.is_ok(), "{}", booking_result.unwrap_err());
+        attachment.finance_journal_diary_id = booking_result.unwrap().journal_entry.id;
+        assert!(postgres_db
+            .finance_journal_attachment_insert(&connection_settings, &user_id, &attachment)
+            .await
+            .is_ok());
+        assert_eq!(
+            postgres_db
+                .finance_journal_attachment_list(
+                    &connection_settings,
+                    &user_id,
+                    Some(attachment.finance_journal_diary_id)
+                )
+                .await
+                .unwrap(),
+            vec![attachment.clone()]
+        );
+        assert_eq!(
+            postgres_db
+                .finance_journal_attachment_get(&connection_settings, &user_id, &attachment.id)
+                .await
+                .unwrap(),
+            Some(attachment)
+        );
+    }
+}
@@ -0,0 +1,631 @@
+#[cfg(test)]
+pub(crate) mod test_database_sqlite {
+    use std::ops::Deref;
+
+    use argon2::{password_hash::SaltString, Argon2, PasswordHasher};
+    use async_session::{
+        chrono::{TimeZone, Utc},
+        Session, SessionStore,
+    };
+    use log::warn;
+    use mongodb::bson::Uuid;
+    use secrecy::{ExposeSecret, Secret};
+
+    use crate::{
+        accounting_config_database::DBFinanceConfigFunctions,
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_database::DBFinanceAccountingFunctions,
+        accounting_logic::FinanceBookingHandle,
+        booking_template_database::DBFinanceBookingTemplateFunctions,
+        budget_database::DBFinanceBudgetFunctions,
+        database_handler_mongodb::{DbBackendType, DbConnectionSetting, EmailVerificationStatus},
+        database_handler_sqlite::{DbHandlerSqlite, SqliteSessionStore},
+        datatypes::{
+            AccountCategory, BudgetPeriod, FinanceAccount, FinanceAccountType,
+            FinanceBackupArchive, FinanceBookingLeg, FinanceBookingRequest, FinanceBookingTemplate,
+            FinanceBudget, FinanceRecurringBooking, FinanceSplitBookingRequest, GenerallUserData,
+            RecurringBookingSchedule,
+        },
+        password_handle::UserCredentialsHashed,
+        recurring_booking_database::DBFinanceRecurringBookingFunctions,
+        user_database::DBUserFunctions,
+    };
+
+    /// connection settings of a test database, the file is deleted when the test is finished
+    pub(crate) struct SqliteTestConnection {
+        connection_settings: DbConnectionSetting,
+    }
+
+    impl Deref for SqliteTestConnection {
+        type Target = DbConnectionSetting;
+
+        fn deref(&self) -> &DbConnectionSetting {
+            return &self.connection_settings;
+        }
+    }
+
+    impl Drop for SqliteTestConnection {
+        fn drop(&mut self) {
+            let remove_result = std::fs::remove_file(&self.connection_settings.url);
+            if remove_result.is_err() {
+                warn!(target:"app::FinanceOverView","could not delete test database {}: {}",self.connection_settings.url,remove_result.unwrap_err());
+            }
+        }
+    }
+
+    /// creates a new database file in the temp folder with all migrations applied
+    pub(crate) fn create_sqlite_test_connection() -> SqliteTestConnection {
+        let database_path =
+            std::env::temp_dir().join(format!("finance_overview_test_{}.sqlite", Uuid::new()));
+        let connection_settings = SqliteTestConnection {
+            connection_settings: DbConnectionSetting {
+                instance: "".into(),
+                password: "".into(),
+                url: database_path.to_string_lossy().to_string(),
+                user: "".into(),
+            },
+        };
+        assert!(DbHandlerSqlite::validate_db_structure(&connection_settings));
+        return connection_settings;
+    }
+
+    pub(crate) async fn create_sqlite_test_user(
+        connection_settings: &DbConnectionSetting,
+        user_name: &str,
+    ) -> Uuid {
+        let insert_result = DbHandlerSqlite::new()
+            .insert_user(
+                connection_settings,
+                &UserCredentialsHashed {
+                    username: user_name.to_string(),
+                    password_hash: Secret::new("some_password_hash".to_string()),
+                },
+            )
+            .await;
+        assert!(insert_result.is_ok(), "{}", insert_result.unwrap_err());
+        return insert_result.unwrap();
+    }
+
+    #[test]
+    fn test_backend_type_setting() {
+        assert_eq!(
+            DbBackendType::from_setting_value("MongoDB").unwrap(),
+            DbBackendType::MongoDB
+        );
+        assert_eq!(
+            DbBackendType::from_setting_value("").unwrap(),
+            DbBackendType::MongoDB
+        );
+        assert_eq!(
+            DbBackendType::from_setting_value("sqlite").unwrap(),
+            DbBackendType::SQLite
+        );
+        assert!(DbBackendType::from_setting_value("Oracle").is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_structure_and_user_handling() {
+        let connection_settings = create_sqlite_test_connection();
+        let sqlite_db = DbHandlerSqlite::new();
+
+        // migrations are only applied once
+        assert!(DbHandlerSqlite::validate_db_structure(&connection_settings));
+        let mut connection = DbHandlerSqlite::open_connection(&connection_settings).unwrap();
+        assert_eq!(
+            DbHandlerSqlite::apply_migrations(&mut connection).unwrap(),
+            0
+        );
+
+        let user_name = "sqlite_user".to_string();
+        assert!(!sqlite_db
+            .check_user_exsists_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap());
+        let user_id = create_sqlite_test_user(&connection_settings, &user_name).await;
+        assert!(sqlite_db
+            .check_user_exsists_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap());
+        let stored_credentials = sqlite_db
+            .get_stored_credentials_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap();
+        assert_eq!(stored_credentials.user_id, user_id);
+        assert_eq!(
+            stored_credentials.password_hash.expose_secret(),
+            "some_password_hash"
+        );
+
+        // email verification
+        assert!(matches!(
+            sqlite_db
+                .check_email_verfification_by_name(&connection_settings, &user_name)
+                .await
+                .unwrap(),
+            EmailVerificationStatus::NotGiven
+        ));
+        let validation_token = sqlite_db
+            .update_user_email(
+                &connection_settings,
+                &user_name,
+                &"sqlite_user@example.com".to_string(),
+            )
+            .await
+            .unwrap();
+        assert!(sqlite_db
+            .verify_email_by_name(
+                &connection_settings,
+                &user_name,
+                &Secret::new("wrong token".to_string())
+            )
+            .await
+            .is_err());
+        assert!(matches!(
+            sqlite_db
+                .verify_email_by_name(
+                    &connection_settings,
+                    &user_name,
+                    &Secret::new(validation_token)
+                )
+                .await
+                .unwrap(),
+            EmailVerificationStatus::Verified
+        ));
+
+        // general data
+        let general_data = GenerallUserData {
+            first_name: "first".into(),
+            last_name: "last".into(),
+        };
+        assert!(sqlite_db
+            .update_general_user_data_by_name(&connection_settings, &user_name, &general_data)
+            .await
+            .is_ok());
+        let stored_general_data = sqlite_db
+            .get_user_general_data_by_user_name(&connection_settings, &user_name)
+            .await
+            .unwrap();
+        assert_eq!(stored_general_data.first_name, general_data.first_name);
+        assert_eq!(stored_general_data.last_name, general_data.last_name);
+
+        // password reset with token
+        // a password reset needs a requested token
+        let salt = SaltString::generate(&mut rand::thread_rng());
+        let reset_secret_hash = Secret::new(
+            Argon2::default()
+                .hash_password("reset secret".as_bytes(), &salt)
+                .unwrap()
+                .to_string(),
+        );
+        assert!(sqlite_db
+            .update_user_reset_secret(&connection_settings, &user_id, &reset_secret_hash)
+            .await
+            .unwrap());
+        assert!(sqlite_db
+            .change_password_with_token(
+                &connection_settings,
+                &user_name,
+                &Uuid::new().to_string(),
+                &Secret::new("new_password_hash".to_string())
+            )
+            .await
+            .is_err());
+
+        assert!(sqlite_db
+            .update_user_password(
+                &connection_settings,
+                &UserCredentialsHashed {
+                    username: user_name.clone(),
+                    password_hash: Secret::new("new_password_hash".to_string()),
+                }
+            )
+            .await
+            .unwrap());
+        let changed_credentials = sqlite_db
+            .get_stored_credentials_by_name(&connection_settings, &user_name)
+            .await
+            .unwrap();
+        assert_eq!(
+            changed_credentials.password_hash.expose_secret(),
+            "new_password_hash"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_route_calling_amount_and_sessions() {
+        let connection_settings = create_sqlite_test_connection();
+        let route_name = "main".to_string();
+        for expected_amount in 1..4 {
+            assert_eq!(
+                DbHandlerSqlite::increase_route_calling_amount(&connection_settings, &route_name)
+                    .unwrap(),
+                expected_amount
+            );
+        }
+
+        let session_store = SqliteSessionStore::new(&connection_settings);
+        assert!(session_store.initialize().await.is_ok());
+        let mut session = Session::new();
+        session.insert("user_name", "sqlite_user").unwrap();
+        let cookie_value = session_store.store_session(session).await.unwrap().unwrap();
+        let loaded_session = session_store
+            .load_session(cookie_value.clone())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            loaded_session.get::<String>("user_name").unwrap(),
+            "sqlite_user"
+        );
+        session_store.destroy_session(loaded_session).await.unwrap();
+        assert!(session_store
+            .load_session(cookie_value)
+            .await
+            .unwrap()
+            .is_none());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_booking_handling() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user_1").await;
+        let user_id_2 = create_sqlite_test_user(&connection_settings, "sqlite_user_2").await;
+        let sqlite_db = DbHandlerSqlite::new();
+
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(&connection_settings, &user_id_1, &sqlite_db);
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&connection_settings, &user_id_1, &sqlite_db);
+
+        let mut asset_type = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        assert!(account_handle_1
+            .finance_account_type_upsert(&mut asset_type)
+            .is_ok());
+        let mut expense_type = FinanceAccountType {
+            description: "SomeTypeDescription_1_2".to_string(),
+            title: "SomeType_1_2".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Expense,
+        };
+        assert!(account_handle_1
+            .finance_account_type_upsert(&mut expense_type)
+            .is_ok());
+
+        let bank_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: asset_type.id,
+            title: "bank".into(),
+            description: "description_bank".into(),
+            account_number: Some(1200),
+        };
+        let rent_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: expense_type.id,
+            title: "rent".into(),
+            description: "description_rent".into(),
+            account_number: Some(4210),
+        };
+        let food_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: expense_type.id,
+            title: "food".into(),
+            description: "description_food".into(),
+            account_number: None,
+        };
+        for finance_account in [&bank_account, &rent_account, &food_account] {
+            let upsert_result = account_handle_1.finance_account_upsert(finance_account);
+            assert!(upsert_result.is_ok(), "{}", upsert_result.unwrap_err());
+        }
+        assert_eq!(
+            account_handle_1.finance_account_list(None).unwrap().len(),
+            3
+        );
+        // account numbers are unique per user
+        assert!(account_handle_1
+            .finance_account_upsert(&FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: asset_type.id,
+                title: "cash".into(),
+                description: "description_cash".into(),
+                account_number: Some(1200),
+            })
+            .is_err());
+
+        // accounts of other users can not be used
+        let mut account_handle_2 =
+            FinanceAccountingConfigHandle::new(&connection_settings, &user_id_2, &sqlite_db);
+        assert_eq!(
+            account_handle_2.finance_account_list(None).unwrap().len(),
+            0
+        );
+        let mut other_type = FinanceAccountType {
+            description: "SomeTypeDescription_2_1".to_string(),
+            title: "SomeType_2_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        assert!(account_handle_2
+            .finance_account_type_upsert(&mut other_type)
+            .is_ok());
+        let booking_handle_2 =
+            FinanceBookingHandle::new(&connection_settings, &user_id_2, &sqlite_db);
+
+        let simple_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: rent_account.id,
+                credit_finance_account_id: bank_account.id,
+                booking_time: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+                amount: 80000,
+                title: "rent march".into(),
+                description: "".into(),
+                tags: vec!["Home".into()],
+                import_reference: None,
+            })
+            .await;
+        assert!(simple_result.is_ok(), "{}", simple_result.unwrap_err());
+        let simple_booking = simple_result.unwrap();
+        assert_eq!(simple_booking.journal_entry.running_number, 1);
+
+        let split_result = booking_handle_1
+            .finance_insert_split_booking_entry(&FinanceSplitBookingRequest {
+                is_saldo: false,
+                is_closing: false,
+                debit_legs: vec![
+                    FinanceBookingLeg {
+                        finance_account_id: rent_account.id,
+                        amount: 1000,
+                    },
+                    FinanceBookingLeg {
+                        finance_account_id: food_account.id,
+                        amount: 2500,
+                    },
+                ],
+                credit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: bank_account.id,
+                    amount: 3500,
+                }],
+                booking_time: Utc.with_ymd_and_hms(2024, 3, 2, 12, 0, 0).unwrap(),
+                title: "shopping".into(),
+                description: "".into(),
+                tags: vec!["shopping".into()],
+                import_reference: None,
+            })
+            .await;
+        assert!(split_result.is_ok(), "{}", split_result.unwrap_err());
+        let split_booking = split_result.unwrap();
+        assert_eq!(split_booking.journal_entry.running_number, 2);
+        assert_eq!(split_booking.debit_account_entries.len(), 2);
+        assert_eq!(split_booking.credit_account_entries.len(), 1);
+
+        // the running numbers are counted per user
+        assert!(booking_handle_2
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: rent_account.id,
+                credit_finance_account_id: bank_account.id,
+                booking_time: Utc.with_ymd_and_hms(2024, 3, 1, 12, 0, 0).unwrap(),
+                amount: 100,
+                title: "foreign accounts".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await
+            .is_err());
+        assert_eq!(
+            sqlite_db
+                .finance_get_journal_max_number(&connection_settings, &user_id_2)
+                .await
+                .unwrap(),
+            0
+        );
+
+        let tag_list = booking_handle_1
+            .list_journal_entries_with_tag(None, None, Some("Home".into()))
+            .await
+            .unwrap();
+        assert_eq!(tag_list.len(), 1);
+        assert_eq!(tag_list[0].id, simple_booking.journal_entry.id);
+
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(&split_booking.journal_entry.id, &"wrong amount".to_string())
+            .await;
+        assert!(reverse_result.is_ok(), "{}", reverse_result.unwrap_err());
+        assert_eq!(reverse_result.unwrap().journal_entry.running_number, 3);
+        assert!(booking_handle_1
+            .reverse_journal_entry(&split_booking.journal_entry.id, &"wrong amount".to_string())
+            .await
+            .is_err());
+
+        let journal_list = booking_handle_1
+            .list_journal_entries(None, None)
+            .await
+            .unwrap();
+        assert_eq!(journal_list.len(), 3);
+        assert_eq!(
+            journal_list[1].reversed_by_journal_entry_id,
+            Some(journal_list[2].id)
+        );
+        assert_eq!(
+            sqlite_db
+                .finance_get_journal_max_number(&connection_settings, &user_id_1)
+                .await
+                .unwrap(),
+            3
+        );
+
+        // restoring is only possible into an empty journal
+        let archive = FinanceBackupArchive {
+            schema_version: 1,
+            created_at: Utc::now(),
+            account_types: Vec::new(),
+            accounts: Vec::new(),
+            journal_entries: journal_list,
+            booking_entries: Vec::new(),
+            booking_journal_max_number: 3,
+        };
+        assert!(sqlite_db
+            .finance_restore_archive(&connection_settings, &user_id_1, &archive)
+            .await
+            .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_sqlite_templates_and_budgets() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id = create_sqlite_test_user(&connection_settings, "sqlite_user").await;
+        let sqlite_db = DbHandlerSqlite::new();
+
+        let asset_type = FinanceAccountType {
+            description: "SomeTypeDescription".to_string(),
+            title: "SomeType".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        assert!(sqlite_db
+            .finance_account_type_upsert(&connection_settings, &user_id, &asset_type)
+            .await
+            .is_ok());
+        let mut finance_account_ids = Vec::new();
+        for title in ["bank", "cash"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: asset_type.id,
+                title: title.into(),
+                description: "".into(),
+                account_number: None,
+            };
+            assert!(sqlite_db
+                .finance_account_upsert(&connection_settings, &user_id, &finance_account)
+                .await
+                .is_ok());
+            finance_account_ids.push(finance_account.id);
+        }
+
+        // templates can only refer to existing accounts of the user
+        let mut booking_template = FinanceBookingTemplate {
+            id: Uuid::new(),
+            name: "rent".into(),
+            debit_finance_account_id: Uuid::new(),
+            credit_finance_account_id: finance_account_ids[1],
+            amount: 80000,
+            title: "rent".into(),
+            description: "".into(),
+        };
+        assert!(sqlite_db
+            .finance_booking_template_upsert(&connection_settings, &user_id, &booking_template)
+            .await
+            .is_err());
+        booking_template.debit_finance_account_id = finance_account_ids[0];
+        assert!(sqlite_db
+            .finance_booking_template_upsert(&connection_settings, &user_id, &booking_template)
+            .await
+            .is_ok());
+        booking_template.amount = 85000;
+        assert!(sqlite_db
+            .finance_booking_template_upsert(&connection_settings, &user_id, &booking_template)
+            .await
+            .is_ok());
+        let template_list = sqlite_db
+            .finance_booking_template_list(&connection_settings, &user_id)
+            .await
+            .unwrap();
+        assert_eq!(template_list.len(), 1);
+        assert_eq!(template_list[0].amount, 85000);
+        assert!(sqlite_db
+            .finance_booking_template_delete(&connection_settings, &user_id, &booking_template.id)
+            .await
+            .is_ok());
+        assert!(sqlite_db
+            .finance_booking_template_delete(&connection_settings, &user_id, &booking_template.id)
+            .await
+            .is_err());
+
+        let budget = FinanceBudget {
+            id: Uuid::new(),
+            finance_account_id: finance_account_ids[0],
+            period: BudgetPeriod::Yearly,
+            amount: 120000,
+        };
+        assert!(sqlite_db
+            .finance_budget_upsert(&connection_settings, &user_id, &budget)
+            .await
+            .is_ok());
+        let budget_list = sqlite_db
+            .finance_budget_list(&connection_settings, &user_id)
+            .await
+            .unwrap();
+        assert_eq!(budget_list.len(), 1);
+        assert_eq!(budget_list[0].period, BudgetPeriod::Yearly);
+        assert!(sqlite_db
+            .finance_budget_list(&connection_settings, &Uuid::new())
+            .await
+            .is_err());
+
+        // the progress of a booking run only moves the last booking time forward and keeps edits
+        let mut recurring_booking = FinanceRecurringBooking {
+            id: Uuid::new(),
+            booking_template: FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: finance_account_ids[0],
+                credit_finance_account_id: finance_account_ids[1],
+                booking_time: Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap(),
+                amount: 5000,
+                title: "pocket money".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            },
+            schedule: RecurringBookingSchedule::Monthly,
+            start_time: Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap(),
+            end_time: None,
+            last_booking_time: Some(Utc.with_ymd_and_hms(2024, 2, 1, 8, 0, 0).unwrap()),
+        };
+        assert!(sqlite_db
+            .finance_recurring_booking_upsert(&connection_settings, &user_id, &recurring_booking)
+            .await
+            .is_ok());
+        recurring_booking.booking_template.amount = 6000;
+        assert!(sqlite_db
+            .finance_recurring_booking_upsert(&connection_settings, &user_id, &recurring_booking)
+            .await
+            .is_ok());
+        assert!(sqlite_db
+            .finance_recurring_booking_set_last_booking_time(
+                &connection_settings,
+                &user_id,
+                &recurring_booking.id,
+                &Utc.with_ymd_and_hms(2024, 1, 1, 8, 0, 0).unwrap()
+            )
+            .await
+            .is_ok());
+        assert!(sqlite_db
+            .finance_recurring_booking_set_last_booking_time(
+                &connection_settings,
+                &user_id,
+                &recurring_booking.id,
+                &Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap()
+            )
+            .await
+            .is_ok());
+        let recurring_booking_list = sqlite_db
+            .finance_recurring_booking_list(&connection_settings, &user_id)
+            .await
+            .unwrap();
+        assert_eq!(recurring_booking_list.len(), 1);
+        assert_eq!(recurring_booking_list[0].booking_template.amount, 6000);
+        assert_eq!(
+            recurring_booking_list[0].last_booking_time,
+            Some(Utc.with_ymd_and_hms(2024, 3, 1, 8, 0, 0).unwrap())
+        );
+    }
+}
@@ -0,0 +1,303 @@
+#[cfg(test)]
+mod test_datev_export {
+    use async_session::chrono::{NaiveDate, TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{
+            AccountCategory, DatevExportSettings, FinanceAccount, FinanceAccountType,
+            FinanceBookingLeg, FinanceBookingRequest, FinanceSplitBookingRequest,
+        },
+        datev_export::{DatevBookingRow, FinanceDatevExportHandle},
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    fn create_settings(year: i32) -> DatevExportSettings {
+        return DatevExportSettings {
+            consultant_number: 29098,
+            client_number: 55003,
+            fiscal_year_start: NaiveDate::from_ymd_opt(year, 1, 1).unwrap(),
+            account_length: 4,
+            period_from: NaiveDate::from_ymd_opt(year, 1, 1).unwrap(),
+            period_till: NaiveDate::from_ymd_opt(year, 12, 31).unwrap(),
+        };
+    }
+
+    #[test]
+    fn test_datev_export_formatting() {
+        let settings = create_settings(2024);
+        assert!(FinanceDatevExportHandle::check_settings(&settings).is_ok());
+        let mut wrong_settings = settings.clone();
+        wrong_settings.consultant_number = 1000;
+        assert!(FinanceDatevExportHandle::check_settings(&wrong_settings).is_err());
+        wrong_settings = settings.clone();
+        wrong_settings.account_length = 9;
+        assert!(FinanceDatevExportHandle::check_settings(&wrong_settings).is_err());
+        // the period has to be inside one fiscal year
+        wrong_settings = settings.clone();
+        wrong_settings.fiscal_year_start = NaiveDate::from_ymd_opt(2023, 7, 1).unwrap();
+        assert!(FinanceDatevExportHandle::check_settings(&wrong_settings).is_err());
+        wrong_settings.period_till = NaiveDate::from_ymd_opt(2024, 6, 30).unwrap();
+        assert!(FinanceDatevExportHandle::check_settings(&wrong_settings).is_ok());
+
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap();
+        let header_line = FinanceDatevExportHandle::create_header_line(&settings, &created_at);
+        assert!(header_line.starts_with(
+            "\"EXTF\";700;21;\"Buchungsstapel\";13;20250115103000000;;\"RE\";\"\";\"\";29098;55003;20240101;4;20240101;20241231;"
+        ));
+        assert!(header_line.ends_with("\r\n"));
+        // the header record has 31 fields
+        assert_eq!(header_line.trim_end().split(';').count(), 31);
+
+        let row_line = FinanceDatevExportHandle::create_row_line(&DatevBookingRow {
+            amount: 123456,
+            debit_account_number: 4900,
+            credit_account_number: 1200,
+            document_date: NaiveDate::from_ymd_opt(2024, 3, 5).unwrap(),
+            document_number: "17".into(),
+            booking_text: "the \"big\" shop\nline 2".into(),
+        });
+        assert_eq!(
+            row_line,
+            "1234,56;\"S\";\"EUR\";;;\"\";4900;1200;\"\";0503;\"17\";\"\";;\"the \"\"big\"\" shop line 2\"\r\n"
+        );
+        let long_text_line = FinanceDatevExportHandle::create_row_line(&DatevBookingRow {
+            amount: 1,
+            debit_account_number: 4900,
+            credit_account_number: 1200,
+            document_date: NaiveDate::from_ymd_opt(2024, 3, 5).unwrap(),
+            document_number: "17".into(),
+            booking_text: "x".repeat(100),
+        });
+        assert!(long_text_line.ends_with(&format!(";\"{}\"\r\n", "x".repeat(60))));
+
+        assert_eq!(
+            FinanceDatevExportHandle::encode_windows_1252("Bär 5€ 日"),
+            vec![b'B', 0xE4, b'r', b' ', b'5', 0x80, b' ', b'?']
+        );
+
+        // split entries are paired greedily
+        let pair_result = FinanceDatevExportHandle::pair_booking_legs(
+            &vec![(4900, 1500), (1000, 500)],
+            &vec![(1200, 1200), (1800, 800)],
+        );
+        assert!(pair_result.is_ok(), "{}", pair_result.unwrap_err());
+        assert_eq!(
+            pair_result.unwrap(),
+            vec![(4900, 1200, 1200), (4900, 1800, 300), (1000, 1800, 500)]
+        );
+        assert!(FinanceDatevExportHandle::pair_booking_legs(
+            &vec![(4900, 1500)],
+            &vec![(1200, 1000)]
+        )
+        .is_err());
+    }
+
+    #[tokio::test]
+    async fn test_datev_export_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let datev_handle_1 =
+            FinanceDatevExportHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for (account_title, account_number) in [
+            ("bank", Some(1200)),
+            ("cash", Some(1000)),
+            ("revenue", Some(8400)),
+            ("expenses", Some(4900)),
+            ("closing", Some(9000)),
+            ("private", None),
+        ] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // account numbers are unique and not 0
+        let mut changed_account = accounts[5].clone();
+        changed_account.account_number = Some(1200);
+        assert!(account_handle_1
+            .finance_account_upsert(&changed_account)
+            .is_err());
+        changed_account.account_number = Some(0);
+        assert!(account_handle_1
+            .finance_account_upsert(&changed_account)
+            .is_err());
+        // an account keeps its own number on update
+        assert!(account_handle_1
+            .finance_account_upsert(&accounts[0])
+            .is_ok());
+
+        let salary_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[0].id,
+                credit_finance_account_id: accounts[2].id,
+                booking_time: Utc.with_ymd_and_hms(2024, 1, 5, 8, 30, 0).unwrap(),
+                amount: 300000,
+                title: "Gehalt Januar; ä".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(salary_result.is_ok(), "{}", salary_result.unwrap_err());
+        let split_result = booking_handle_1
+            .finance_insert_split_booking_entry(&FinanceSplitBookingRequest {
+                is_saldo: false,
+                is_closing: false,
+                debit_legs: vec![
+                    FinanceBookingLeg {
+                        finance_account_id: accounts[3].id,
+                        amount: 1500,
+                    },
+                    FinanceBookingLeg {
+                        finance_account_id: accounts[1].id,
+                        amount: 500,
+                    },
+                ],
+                credit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: accounts[0].id,
+                    amount: 2000,
+                }],
+                booking_time: Utc.with_ymd_and_hms(2024, 2, 1, 12, 0, 0).unwrap(),
+                title: "market and cash".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(split_result.is_ok(), "{}", split_result.unwrap_err());
+        let close_result = booking_handle_1
+            .close_accounts(
+                &vec![
+                    accounts[0].id,
+                    accounts[1].id,
+                    accounts[2].id,
+                    accounts[3].id,
+                ],
+                &accounts[4].id,
+                Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).unwrap(),
+            )
+            .await;
+        assert!(close_result.is_ok(), "{}", close_result.unwrap_err());
+        let private_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[5].id,
+                credit_finance_account_id: accounts[1].id,
+                booking_time: Utc.with_ymd_and_hms(2025, 2, 1, 12, 0, 0).unwrap(),
+                amount: 5000,
+                title: "withdrawal".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(private_result.is_ok(), "{}", private_result.unwrap_err());
+
+        // the closing entries are not exported, the split entry becomes two rows
+        let created_at = Utc.with_ymd_and_hms(2025, 1, 15, 10, 30, 0).unwrap();
+        let text_result = datev_handle_1
+            .create_booking_batch_text(&create_settings(2024), &created_at)
+            .await;
+        assert!(text_result.is_ok(), "{}", text_result.unwrap_err());
+        let batch_text = text_result.unwrap();
+        let lines: Vec<&str> = batch_text.split_terminator("\r\n").collect();
+        assert_eq!(lines.len(), 5);
+        assert!(lines[0].starts_with("\"EXTF\";700;21;"));
+        assert!(lines[1].starts_with("Umsatz (ohne Soll/Haben-Kz);Soll/Haben-Kennzeichen;"));
+        assert_eq!(
+            lines[2],
+            "3000,00;\"S\";\"EUR\";;;\"\";1200;8400;\"\";0501;\"1\";\"\";;\"Gehalt Januar; ä\""
+        );
+        assert_eq!(
+            lines[3],
+            "15,00;\"S\";\"EUR\";;;\"\";4900;1200;\"\";0102;\"2\";\"\";;\"market and cash\""
+        );
+        assert_eq!(
+            lines[4],
+            "5,00;\"S\";\"EUR\";;;\"\";1000;1200;\"\";0102;\"2\";\"\";;\"market and cash\""
+        );
+
+        let export_result = datev_handle_1
+            .export_booking_batch(&create_settings(2024))
+            .await;
+        assert!(export_result.is_ok(), "{}", export_result.unwrap_err());
+        let exported_bytes = export_result.unwrap();
+        assert!(exported_bytes.starts_with(b"\"EXTF\";700;21;"));
+        // ä in Windows-1252
+        assert!(exported_bytes.contains(&0xE4));
+
+        // accounts without number can not be exported
+        let missing_number_result = datev_handle_1
+            .create_booking_rows(&create_settings(2025))
+            .await;
+        assert!(missing_number_result.is_err());
+        assert!(missing_number_result.unwrap_err().contains("private"));
+
+        // with account length 4 numbers may have at most 5 digits (personal accounts)
+        changed_account = accounts[1].clone();
+        changed_account.account_number = Some(100000);
+        assert!(account_handle_1
+            .finance_account_upsert(&changed_account)
+            .is_ok());
+        assert!(datev_handle_1
+            .create_booking_rows(&create_settings(2024))
+            .await
+            .is_err());
+    }
+}
@@ -0,0 +1,333 @@
+#[cfg(test)]
+mod test_plain_text_accounting {
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{
+            AccountCategory, FinanceAccount, FinanceAccountType, FinanceBookingLeg,
+            FinanceBookingRequest, FinanceSplitBookingRequest, PlainTextAccountingFormat,
+        },
+        plain_text_accounting::FinancePlainTextAccountingHandle,
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    #[test]
+    fn test_plain_text_accounting_formatting() {
+        assert!(FinancePlainTextAccountingHandle::check_commodity("EUR").is_ok());
+        assert!(FinancePlainTextAccountingHandle::check_commodity("eur").is_err());
+        assert!(FinancePlainTextAccountingHandle::check_commodity("").is_err());
+        assert!(FinancePlainTextAccountingHandle::check_commodity("E U").is_err());
+
+        let text = "line 1\nline 2 \\ \"quoted\"";
+        let escaped_text = FinancePlainTextAccountingHandle::escape_text(text);
+        assert!(!escaped_text.contains('\n'));
+        assert_eq!(
+            FinancePlainTextAccountingHandle::unescape_text(&escaped_text),
+            text
+        );
+
+        assert_eq!(
+            PlainTextAccountingFormat::get_from_string("beancount"),
+            Ok(PlainTextAccountingFormat::Beancount)
+        );
+        assert!(PlainTextAccountingFormat::get_from_string("gnucash").is_err());
+
+        // only files of the export can be read, postings need declared accounts and have to balance
+        let unknown_account_text = "2024-01-05 * salary\r\n    ; journal_id: 67e55044-10b1-426f-9247-bb680e5fe0c8\r\n    ; number: 1\r\n    ; title: salary\r\n    Assets:Bank  10.00 EUR\r\n    Income:Salary  -10.00 EUR\r\n";
+        assert!(FinancePlainTextAccountingHandle::parse_text(
+            unknown_account_text,
+            &PlainTextAccountingFormat::Ledger
+        )
+        .is_err());
+        let unbalanced_text = "account Bank\r\n    ; type_id: 67e55044-10b1-426f-9247-bb680e5fe0c8\r\n    ; title: Bank\r\n    ; category: asset\r\n\r\naccount Bank:Giro\r\n    ; account_id: 77e55044-10b1-426f-9247-bb680e5fe0c8\r\n    ; type_id: 67e55044-10b1-426f-9247-bb680e5fe0c8\r\n    ; title: Giro\r\n\r\naccount Bank:Cash\r\n    ; account_id: 87e55044-10b1-426f-9247-bb680e5fe0c8\r\n    ; type_id: 67e55044-10b1-426f-9247-bb680e5fe0c8\r\n    ; title: Cash\r\n\r\n2024-01-05 * withdrawal\r\n    ; journal_id: 97e55044-10b1-426f-9247-bb680e5fe0c8\r\n    ; number: 1\r\n    ; title: withdrawal\r\n    Bank:Cash  10.00 EUR\r\n    Bank:Giro  -10.01 EUR\r\n";
+        let unbalanced_result = FinancePlainTextAccountingHandle::parse_text(
+            unbalanced_text,
+            &PlainTextAccountingFormat::Ledger,
+        );
+        assert!(unbalanced_result.is_err());
+        let balanced_result = FinancePlainTextAccountingHandle::parse_text(
+            &unbalanced_text.replace("-10.01", "-10"),
+            &PlainTextAccountingFormat::Ledger,
+        );
+        assert!(balanced_result.is_ok(), "{}", balanced_result.unwrap_err());
+        let balanced_data = balanced_result.unwrap();
+        assert_eq!(balanced_data.account_types.len(), 1);
+        assert_eq!(balanced_data.accounts.len(), 2);
+        assert_eq!(
+            balanced_data.transactions[0].booking_request.debit_legs[0].amount,
+            1000
+        );
+        // every account type needs a category
+        let missing_category_result = FinancePlainTextAccountingHandle::parse_text(
+            &unbalanced_text
+                .replace("-10.01", "-10")
+                .replace("    ; category: asset\r\n", ""),
+            &PlainTextAccountingFormat::Ledger,
+        );
+        assert!(missing_category_result
+            .unwrap_err()
+            .contains("account type has no category"));
+    }
+
+    #[tokio::test]
+    async fn test_plain_text_accounting_round_trip_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+        let user_id_2 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+        let entry_object2 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_2);
+
+        let _insert_result = InMemoryDatabaseData::insert_in_memory_database(Vec::from([
+            entry_object1,
+            entry_object2,
+        ]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let plain_text_handle_1 = FinancePlainTextAccountingHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let plain_text_handle_2 = FinancePlainTextAccountingHandle::new(
+            &dummy_connection_settings,
+            &user_id_2,
+            &in_memory_db,
+        );
+
+        let mut type_ids = Vec::new();
+        for (type_title, account_category) in [
+            ("Bank accounts", AccountCategory::Asset),
+            ("Income: Salary", AccountCategory::Income),
+            ("Equity", AccountCategory::Equity),
+            ("Kosten & Ausgaben", AccountCategory::Expense),
+        ] {
+            let mut finance_account_type = FinanceAccountType {
+                description: format!("description of {}", type_title),
+                title: type_title.into(),
+                id: Uuid::new(),
+                account_category: account_category,
+            };
+            let insert_type_result =
+                account_handle_1.finance_account_type_upsert(&mut finance_account_type);
+            assert!(
+                insert_type_result.is_ok(),
+                "{}",
+                insert_type_result.unwrap_err()
+            );
+            type_ids.push(finance_account_type.id);
+        }
+
+        // giro and giro! result in the same beancount name
+        let mut accounts = Vec::new();
+        for (type_index, account_title, account_number) in [
+            (0, "Giro", Some(1200)),
+            (0, "Giro!", None),
+            (1, "salary", Some(8400)),
+            (2, "closing", None),
+            (3, "food; misc", Some(4900)),
+        ] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: type_ids[type_index],
+                title: account_title.into(),
+                description: format!("description\nof {}", account_title),
+                account_number,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        let salary_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[0].id,
+                credit_finance_account_id: accounts[2].id,
+                booking_time: Utc.with_ymd_and_hms(2024, 1, 5, 8, 30, 0).unwrap(),
+                amount: 300000,
+                title: "salary; January".into(),
+                description: "line 1\nline 2".into(),
+                tags: vec!["job".into(), "a,b".into()],
+                import_reference: Some("FITID-1".into()),
+            })
+            .await;
+        assert!(salary_result.is_ok(), "{}", salary_result.unwrap_err());
+        let split_result = booking_handle_1
+            .finance_insert_split_booking_entry(&FinanceSplitBookingRequest {
+                is_saldo: false,
+                is_closing: false,
+                debit_legs: vec![
+                    FinanceBookingLeg {
+                        finance_account_id: accounts[4].id,
+                        amount: 1500,
+                    },
+                    FinanceBookingLeg {
+                        finance_account_id: accounts[1].id,
+                        amount: 500,
+                    },
+                ],
+                credit_legs: vec![FinanceBookingLeg {
+                    finance_account_id: accounts[0].id,
+                    amount: 2000,
+                }],
+                booking_time: Utc.with_ymd_and_hms(2024, 2, 1, 12, 0, 0).unwrap(),
+                title: "market and cash".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(split_result.is_ok(), "{}", split_result.unwrap_err());
+        let groceries_result = booking_handle_1
+            .finance_insert_booking_entry(&FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id: accounts[4].id,
+                credit_finance_account_id: accounts[0].id,
+                booking_time: Utc.with_ymd_and_hms(2024, 3, 3, 12, 0, 0).unwrap(),
+                amount: 1999,
+                title: "groceries \"organic\"".into(),
+                description: "".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            })
+            .await;
+        assert!(
+            groceries_result.is_ok(),
+            "{}",
+            groceries_result.unwrap_err()
+        );
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(
+                &groceries_result.unwrap().journal_entry.id,
+                &"booked twice".to_string(),
+            )
+            .await;
+        assert!(reverse_result.is_ok(), "{}", reverse_result.unwrap_err());
+        let close_result = booking_handle_1
+            .close_accounts(
+                &vec![
+                    accounts[0].id,
+                    accounts[1].id,
+                    accounts[2].id,
+                    accounts[4].id,
+                ],
+                &accounts[3].id,
+                Utc.with_ymd_and_hms(2024, 12, 31, 23, 59, 59).unwrap(),
+            )
+            .await;
+        assert!(close_result.is_ok(), "{}", close_result.unwrap_err());
+
+        let data_result = plain_text_handle_1.load_data().await;
+        assert!(data_result.is_ok(), "{}", data_result.unwrap_err());
+        let data = data_result.unwrap();
+        assert_eq!(data.transactions.len(), 8);
+
+        // 1) ledger: account names from type and account title, saldo postings assert the balance
+        let ledger_result = plain_text_handle_1
+            .export_text(&PlainTextAccountingFormat::Ledger, "EUR")
+            .await;
+        assert!(ledger_result.is_ok(), "{}", ledger_result.unwrap_err());
+        let ledger_text = ledger_result.unwrap();
+        assert!(ledger_text.contains("account Bank accounts:Giro\r\n"));
+        assert!(ledger_text.contains("account Income Salary:salary\r\n"));
+        assert!(ledger_text.contains("account Kosten & Ausgaben:food, misc\r\n"));
+        assert!(ledger_text.contains("2960.01 EUR = 2960.01 EUR\r\n"));
+        assert!(ledger_text.contains("2024-01-05 * salary, January\r\n"));
+
+        // 2) beancount: root account from the category, saldo becomes a balance directive
+        let beancount_result = plain_text_handle_1
+            .export_text(&PlainTextAccountingFormat::Beancount, "EUR")
+            .await;
+        assert!(
+            beancount_result.is_ok(),
+            "{}",
+            beancount_result.unwrap_err()
+        );
+        let beancount_text = beancount_result.unwrap();
+        assert!(beancount_text.contains("1970-01-01 open Assets:Bank-accounts:Giro EUR\r\n"));
+        assert!(beancount_text.contains("1970-01-01 open Assets:Bank-accounts:Giro-2 EUR\r\n"));
+        assert!(
+            beancount_text.contains("1970-01-01 open Expenses:Kosten-Ausgaben:Food-misc EUR\r\n")
+        );
+        assert!(beancount_text.contains("2024-03-03 * \"groceries \\\"organic\\\"\"\r\n"));
+        let giro_balance_line = beancount_text
+            .lines()
+            .find(|elem| elem.starts_with("2025-01-02 balance Assets:Bank-accounts:Giro "));
+        assert!(giro_balance_line.is_some());
+        assert!(giro_balance_line.unwrap().ends_with(" 2960.01 EUR"));
+
+        // 3) both formats are read back to the same data and written to the same text
+        for (format, text) in [
+            (PlainTextAccountingFormat::Ledger, &ledger_text),
+            (PlainTextAccountingFormat::Beancount, &beancount_text),
+        ] {
+            let parse_result = FinancePlainTextAccountingHandle::parse_text(text, &format);
+            assert!(parse_result.is_ok(), "{}", parse_result.unwrap_err());
+            let parsed_data = parse_result.unwrap();
+            assert_eq!(parsed_data, data);
+            let written_again =
+                FinancePlainTextAccountingHandle::data_to_text(&parsed_data, &format, "EUR");
+            assert_eq!(&written_again.unwrap(), text);
+        }
+
+        // 4) importing into an empty journal recreates the entries including the reversal link
+        let import_result = plain_text_handle_2
+            .import_text(&beancount_text, &PlainTextAccountingFormat::Beancount)
+            .await;
+        assert!(import_result.is_ok(), "{}", import_result.unwrap_err());
+        assert_eq!(import_result.unwrap(), 8);
+        let imported_data = plain_text_handle_2.load_data().await.unwrap();
+        assert_eq!(imported_data.account_types, data.account_types);
+        assert_eq!(imported_data.accounts, data.accounts);
+        assert_eq!(imported_data.transactions.len(), data.transactions.len());
+        for (imported_transaction, transaction) in imported_data
+            .transactions
+            .iter()
+            .zip(data.transactions.iter())
+        {
+            assert_eq!(
+                imported_transaction.booking_request,
+                transaction.booking_request
+            );
+            assert_eq!(
+                imported_transaction.running_number,
+                transaction.running_number
+            );
+            assert_eq!(
+                imported_transaction.reverse_of_journal_entry_id.is_some(),
+                transaction.reverse_of_journal_entry_id.is_some()
+            );
+        }
+        assert!(plain_text_handle_2
+            .import_text(&ledger_text, &PlainTextAccountingFormat::Ledger)
+            .await
+            .is_err());
+    }
+}
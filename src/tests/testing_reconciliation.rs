@@ -0,0 +1,270 @@
+#[cfg(test)]
+mod test_reconciliation_handle {
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_database::DBFinanceConfigFunctions,
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_database::DBFinanceAccountingFunctions,
+        accounting_logic::FinanceBookingHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        database_handler_postgres::DbHandlerPostgres,
+        database_handler_sqlite::DbHandlerSqlite,
+        datatypes::{AccountCategory, FinanceAccount, FinanceAccountType, FinanceBookingRequest},
+        reconciliation_database::DBFinanceReconciliationFunctions,
+        reconciliation_logic::FinanceReconciliationHandle,
+        tests::{
+            mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+            testing_database_postgres::test_database_postgres::{
+                create_postgres_test_connection, create_postgres_test_user,
+            },
+            testing_database_sqlite::test_database_sqlite::{
+                create_sqlite_test_connection, create_sqlite_test_user,
+            },
+        },
+    };
+
+    #[tokio::test]
+    async fn test_reconciliation_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        check_reconciliation(&dummy_connection_settings, &user_id_1, &in_memory_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_with_sqlite() {
+        let connection_settings = create_sqlite_test_connection();
+        let user_id_1 = create_sqlite_test_user(&connection_settings, "sqlite_user").await;
+
+        let sqlite_db = DbHandlerSqlite::new();
+
+        check_reconciliation(&connection_settings, &user_id_1, &sqlite_db).await;
+    }
+
+    #[tokio::test]
+    async fn test_reconciliation_with_postgres() {
+        let connection_settings = create_postgres_test_connection().await;
+        let user_id_1 = create_postgres_test_user(&connection_settings, "postgres_user").await;
+
+        let postgres_db = DbHandlerPostgres::new();
+
+        check_reconciliation(&connection_settings, &user_id_1, &postgres_db).await;
+    }
+
+    async fn check_reconciliation<T>(
+        connection_settings: &DbConnectionSetting,
+        user_id_1: &Uuid,
+        db_handler: &T,
+    ) where
+        T: DBFinanceConfigFunctions
+            + DBFinanceAccountingFunctions
+            + DBFinanceReconciliationFunctions,
+    {
+        let mut account_handle_1 =
+            FinanceAccountingConfigHandle::new(connection_settings, user_id_1, db_handler);
+        let booking_handle_1 =
+            FinanceBookingHandle::new(connection_settings, user_id_1, db_handler);
+        let reconciliation_handle_1 =
+            FinanceReconciliationHandle::new(connection_settings, user_id_1, db_handler);
+
+        let mut asset_type = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_asset_type_result =
+            account_handle_1.finance_account_type_upsert(&mut asset_type);
+        assert!(
+            insert_asset_type_result.is_ok(),
+            "{}",
+            insert_asset_type_result.unwrap_err()
+        );
+        let mut income_type = FinanceAccountType {
+            description: "SomeTypeDescription_1_2".to_string(),
+            title: "SomeType_1_2".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Income,
+        };
+        let insert_income_type_result =
+            account_handle_1.finance_account_type_upsert(&mut income_type);
+        assert!(
+            insert_income_type_result.is_ok(),
+            "{}",
+            insert_income_type_result.unwrap_err()
+        );
+
+        let bank_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: asset_type.id,
+            title: "bank".into(),
+            description: "description_bank".into(),
+            account_number: None,
+        };
+        let income_account = FinanceAccount {
+            id: Uuid::new(),
+            finance_account_type_id: income_type.id,
+            title: "income".into(),
+            description: "description_income".into(),
+            account_number: None,
+        };
+        for finance_account in [&bank_account, &income_account] {
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+        }
+
+        let mut journal_entry_ids = Vec::new();
+        let mut bank_entry_ids = Vec::new();
+        for (amount, day) in [(10000, 1), (2500, 10), (700, 20)] {
+            let insert_result = booking_handle_1
+                .finance_insert_booking_entry(&FinanceBookingRequest {
+                    is_simple_entry: true,
+                    is_saldo: false,
+                    debit_finance_account_id: bank_account.id,
+                    credit_finance_account_id: income_account.id,
+                    booking_time: Utc.with_ymd_and_hms(2024, 3, day, 12, 0, 0).unwrap(),
+                    amount,
+                    title: format!("transfer {}", day),
+                    description: "".into(),
+                    tags: Vec::new(),
+                    import_reference: None,
+                })
+                .await;
+            assert!(insert_result.is_ok(), "{}", insert_result.unwrap_err());
+            let booking_result = insert_result.unwrap();
+            journal_entry_ids.push(booking_result.journal_entry.id);
+            bank_entry_ids.push(booking_result.debit_account_entry.id);
+        }
+
+        // 1) the difference between book and statement balance is shown
+        let first_statement_time = Utc.with_ymd_and_hms(2024, 3, 15, 23, 59, 59).unwrap();
+        let status_result = reconciliation_handle_1
+            .reconciliation_status(&bank_account.id, first_statement_time, 12000)
+            .await;
+        assert!(status_result.is_ok(), "{}", status_result.unwrap_err());
+        let status = status_result.unwrap();
+        assert_eq!(status.book_balance, 12500);
+        assert_eq!(status.difference, -500);
+        assert_eq!(status.open_entries.len(), 2);
+        assert_eq!(status.open_entries[0].id, bank_entry_ids[0]);
+
+        // 2) only open entries of the account up to the statement date can be reconciled
+        assert!(reconciliation_handle_1
+            .reconcile(
+                &bank_account.id,
+                first_statement_time,
+                12000,
+                &vec![bank_entry_ids[2]]
+            )
+            .await
+            .is_err());
+        let reconcile_result = reconciliation_handle_1
+            .reconcile(
+                &bank_account.id,
+                first_statement_time,
+                12000,
+                &vec![bank_entry_ids[0]],
+            )
+            .await;
+        assert!(
+            reconcile_result.is_ok(),
+            "{}",
+            reconcile_result.unwrap_err()
+        );
+        let first_reconciliation = reconcile_result.unwrap();
+        assert_eq!(first_reconciliation.book_balance, 12500);
+        let status_after_reconcile = reconciliation_handle_1
+            .reconciliation_status(&bank_account.id, first_statement_time, 12500)
+            .await
+            .unwrap();
+        assert_eq!(status_after_reconcile.difference, 0);
+        assert_eq!(status_after_reconcile.open_entries.len(), 1);
+        assert_eq!(status_after_reconcile.open_entries[0].id, bank_entry_ids[1]);
+
+        // 3) reconciled entries are protected and statements must follow each other
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_ids[0], &"wrong amount".to_string())
+            .await;
+        assert!(reverse_result.is_err());
+        assert!(reconciliation_handle_1
+            .reconcile(
+                &bank_account.id,
+                Utc.with_ymd_and_hms(2024, 3, 14, 23, 59, 59).unwrap(),
+                12500,
+                &Vec::new()
+            )
+            .await
+            .is_err());
+        let second_statement_time = Utc.with_ymd_and_hms(2024, 3, 31, 23, 59, 59).unwrap();
+        assert!(reconciliation_handle_1
+            .reconcile(
+                &bank_account.id,
+                second_statement_time,
+                13200,
+                &vec![bank_entry_ids[0]]
+            )
+            .await
+            .is_err());
+        let second_reconcile_result = reconciliation_handle_1
+            .reconcile(
+                &bank_account.id,
+                second_statement_time,
+                13200,
+                &vec![bank_entry_ids[1], bank_entry_ids[2]],
+            )
+            .await;
+        assert!(
+            second_reconcile_result.is_ok(),
+            "{}",
+            second_reconcile_result.unwrap_err()
+        );
+        let reconciliation_list = reconciliation_handle_1
+            .reconciliation_list(Some(bank_account.id))
+            .await
+            .unwrap();
+        assert_eq!(reconciliation_list.len(), 2);
+        assert_eq!(reconciliation_list[0].statement_time, second_statement_time);
+
+        // 4) after unreconciling the entries can be changed again
+        let unreconcile_result = reconciliation_handle_1
+            .unreconcile(&first_reconciliation.id)
+            .await;
+        assert!(
+            unreconcile_result.is_ok(),
+            "{}",
+            unreconcile_result.unwrap_err()
+        );
+        assert!(reconciliation_handle_1
+            .unreconcile(&first_reconciliation.id)
+            .await
+            .is_err());
+        let reverse_after_unreconcile_result = booking_handle_1
+            .reverse_journal_entry(&journal_entry_ids[0], &"wrong amount".to_string())
+            .await;
+        assert!(
+            reverse_after_unreconcile_result.is_ok(),
+            "{}",
+            reverse_after_unreconcile_result.unwrap_err()
+        );
+    }
+}
@@ -0,0 +1,494 @@
+#[cfg(test)]
+mod test_recurring_booking_handle {
+    use async_session::chrono::{Duration, TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_logic::FinanceBookingHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{
+            AccountCategory, FinanceAccount, FinanceAccountType, FinanceBookingRequest,
+            FinanceRecurringBooking, RecurringBookingSchedule,
+        },
+        recurring_booking_database::DBFinanceRecurringBookingFunctions,
+        recurring_booking_logic::{
+            book_due_recurring_bookings_of_all_users, FinanceRecurringBookingHandle,
+        },
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    fn create_recurring_booking(
+        debit_finance_account_id: Uuid,
+        credit_finance_account_id: Uuid,
+        schedule: RecurringBookingSchedule,
+        start_time: async_session::chrono::DateTime<Utc>,
+    ) -> FinanceRecurringBooking {
+        return FinanceRecurringBooking {
+            id: Uuid::new(),
+            booking_template: FinanceBookingRequest {
+                is_simple_entry: true,
+                is_saldo: false,
+                debit_finance_account_id,
+                credit_finance_account_id,
+                booking_time: start_time,
+                amount: 50000,
+                title: "recurring".into(),
+                description: "recurring description".into(),
+                tags: Vec::new(),
+                import_reference: None,
+            },
+            schedule,
+            start_time,
+            end_time: None,
+            last_booking_time: None,
+        };
+    }
+
+    #[test]
+    fn test_recurring_booking_occurrence_times() {
+        let dummy_account_id = Uuid::new();
+        let time_of_day = |year: i32, month: u32, day: u32| {
+            Utc.with_ymd_and_hms(year, month, day, 12, 0, 0).unwrap()
+        };
+
+        // a) monthly on the 31st is moved to the last day of shorter months
+        let monthly = create_recurring_booking(
+            dummy_account_id,
+            dummy_account_id,
+            RecurringBookingSchedule::Monthly,
+            time_of_day(2024, 1, 31),
+        );
+        let expected_monthly = [
+            time_of_day(2024, 1, 31),
+            time_of_day(2024, 2, 29),
+            time_of_day(2024, 3, 31),
+            time_of_day(2024, 4, 30),
+        ];
+        for (index, expected_time) in expected_monthly.iter().enumerate() {
+            assert_eq!(
+                FinanceRecurringBookingHandle::get_occurrence_time(&monthly, index as u32),
+                Some(*expected_time)
+            );
+        }
+
+        // b) end of month always uses the last day, also in the first month
+        let end_of_month = create_recurring_booking(
+            dummy_account_id,
+            dummy_account_id,
+            RecurringBookingSchedule::EndOfMonth,
+            time_of_day(2023, 1, 15),
+        );
+        let expected_end_of_month = [
+            time_of_day(2023, 1, 31),
+            time_of_day(2023, 2, 28),
+            time_of_day(2023, 3, 31),
+        ];
+        for (index, expected_time) in expected_end_of_month.iter().enumerate() {
+            assert_eq!(
+                FinanceRecurringBookingHandle::get_occurrence_time(&end_of_month, index as u32),
+                Some(*expected_time)
+            );
+        }
+
+        // c) yearly on a leap day
+        let yearly = create_recurring_booking(
+            dummy_account_id,
+            dummy_account_id,
+            RecurringBookingSchedule::Yearly,
+            time_of_day(2024, 2, 29),
+        );
+        assert_eq!(
+            FinanceRecurringBookingHandle::get_occurrence_time(&yearly, 1),
+            Some(time_of_day(2025, 2, 28))
+        );
+        assert_eq!(
+            FinanceRecurringBookingHandle::get_occurrence_time(&yearly, 4),
+            Some(time_of_day(2028, 2, 29))
+        );
+
+        // d) weekly, due occurrences respect the end time and the last booking time
+        let mut weekly = create_recurring_booking(
+            dummy_account_id,
+            dummy_account_id,
+            RecurringBookingSchedule::Weekly,
+            time_of_day(2024, 12, 25),
+        );
+        assert_eq!(
+            FinanceRecurringBookingHandle::get_occurrence_time(&weekly, 1),
+            Some(time_of_day(2025, 1, 1))
+        );
+        weekly.end_time = Some(time_of_day(2025, 1, 15));
+        let due_times = FinanceRecurringBookingHandle::get_due_occurrence_times(
+            &weekly,
+            time_of_day(2025, 6, 1),
+        );
+        assert_eq!(due_times.len(), 4);
+        weekly.last_booking_time = Some(time_of_day(2025, 1, 1));
+        let due_times = FinanceRecurringBookingHandle::get_due_occurrence_times(
+            &weekly,
+            time_of_day(2025, 6, 1),
+        );
+        assert_eq!(
+            due_times,
+            vec![time_of_day(2025, 1, 8), time_of_day(2025, 1, 15)]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_recurring_booking_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let recurring_booking_handle_1 = FinanceRecurringBookingHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+
+        let mut finance_account_type_1_1 = FinanceAccountType {
+            description: "SomeTypeDescription_1_1".to_string(),
+            title: "SomeType_1_1".to_string(),
+            id: Uuid::new(),
+            account_category: AccountCategory::Asset,
+        };
+        let insert_finance_account_type_1_1_result =
+            account_handle_1.finance_account_type_upsert(&mut finance_account_type_1_1);
+        assert!(
+            insert_finance_account_type_1_1_result.is_ok(),
+            "{}",
+            insert_finance_account_type_1_1_result.unwrap_err()
+        );
+
+        let mut accounts = Vec::new();
+        for account_title in ["rent", "bank", "insurance"] {
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type_1_1.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) invalid definitions are rejected
+        let mut invalid_recurring_booking = create_recurring_booking(
+            accounts[0].id,
+            accounts[1].id,
+            RecurringBookingSchedule::Monthly,
+            Utc::now(),
+        );
+        invalid_recurring_booking.booking_template.amount = 0;
+        assert!(recurring_booking_handle_1
+            .recurring_booking_upsert(&invalid_recurring_booking)
+            .await
+            .is_err());
+        invalid_recurring_booking.booking_template.amount = 100;
+        invalid_recurring_booking
+            .booking_template
+            .credit_finance_account_id = accounts[0].id;
+        assert!(recurring_booking_handle_1
+            .recurring_booking_upsert(&invalid_recurring_booking)
+            .await
+            .is_err());
+        invalid_recurring_booking
+            .booking_template
+            .credit_finance_account_id = Uuid::new();
+        assert!(recurring_booking_handle_1
+            .recurring_booking_upsert(&invalid_recurring_booking)
+            .await
+            .is_err());
+        invalid_recurring_booking
+            .booking_template
+            .credit_finance_account_id = accounts[1].id;
+        invalid_recurring_booking.end_time = Some(Utc::now() - Duration::days(1));
+        assert!(recurring_booking_handle_1
+            .recurring_booking_upsert(&invalid_recurring_booking)
+            .await
+            .is_err());
+
+        // 2) a monthly rent started in the past books all due occurrences
+        let rent = create_recurring_booking(
+            accounts[0].id,
+            accounts[1].id,
+            RecurringBookingSchedule::Monthly,
+            Utc::now() - Duration::days(70),
+        );
+        let upsert_rent_result = recurring_booking_handle_1
+            .recurring_booking_upsert(&rent)
+            .await;
+        assert!(
+            upsert_rent_result.is_ok(),
+            "{}",
+            upsert_rent_result.unwrap_err()
+        );
+        let booking_time_till = Utc::now();
+        let expected_rent_times =
+            FinanceRecurringBookingHandle::get_due_occurrence_times(&rent, booking_time_till);
+        assert!(expected_rent_times.len() >= 2);
+
+        let first_run_result = recurring_booking_handle_1
+            .book_due_occurrences(booking_time_till)
+            .await;
+        assert!(
+            first_run_result.is_ok(),
+            "{}",
+            first_run_result.unwrap_err()
+        );
+        let first_run_bookings = first_run_result.unwrap();
+        assert!(first_run_bookings.errors.is_empty());
+        assert_eq!(
+            first_run_bookings.booking_results.len(),
+            expected_rent_times.len()
+        );
+        for (booking_result, expected_time) in first_run_bookings
+            .booking_results
+            .iter()
+            .zip(expected_rent_times.iter())
+        {
+            assert_eq!(booking_result.journal_entry.booking_time, *expected_time);
+            assert_eq!(booking_result.journal_entry.amount, 50000);
+            assert_eq!(
+                booking_result.journal_entry.import_reference,
+                Some(format!(
+                    "RECURRING:{}|{}",
+                    rent.id,
+                    expected_time.to_rfc3339()
+                ))
+            );
+        }
+
+        let stored_list_result = recurring_booking_handle_1.recurring_booking_list().await;
+        assert!(stored_list_result.is_ok());
+        let stored_list = stored_list_result.unwrap();
+        assert_eq!(stored_list.len(), 1);
+        assert_eq!(
+            stored_list[0].last_booking_time,
+            expected_rent_times.last().copied()
+        );
+
+        // 3) running again does not book anything
+        let second_run_result = recurring_booking_handle_1
+            .book_due_occurrences(booking_time_till)
+            .await;
+        assert!(second_run_result.is_ok());
+        assert_eq!(second_run_result.unwrap().booking_results.len(), 0);
+
+        // 4) even without the stored progress (e.g. interrupted run) nothing is booked twice
+        let reset_result = recurring_booking_handle_1
+            .recurring_booking_upsert(&rent)
+            .await;
+        assert!(reset_result.is_ok());
+        let third_run_result = recurring_booking_handle_1
+            .book_due_occurrences(booking_time_till)
+            .await;
+        assert!(third_run_result.is_ok());
+        assert_eq!(third_run_result.unwrap().booking_results.len(), 0);
+        let journal_entries_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert!(journal_entries_result.is_ok());
+        assert_eq!(
+            journal_entries_result.unwrap().len(),
+            expected_rent_times.len()
+        );
+
+        // 5) the background function books weekly occurrences until the end time
+        let mut insurance = create_recurring_booking(
+            accounts[2].id,
+            accounts[1].id,
+            RecurringBookingSchedule::Weekly,
+            Utc::now() - Duration::days(30),
+        );
+        insurance.end_time = Some(insurance.start_time + Duration::days(15));
+        let upsert_insurance_result = recurring_booking_handle_1
+            .recurring_booking_upsert(&insurance)
+            .await;
+        assert!(upsert_insurance_result.is_ok());
+        let all_users_result = book_due_recurring_bookings_of_all_users(
+            &dummy_connection_settings,
+            &in_memory_db,
+            booking_time_till,
+        )
+        .await;
+        assert!(
+            all_users_result.is_ok(),
+            "{}",
+            all_users_result.unwrap_err()
+        );
+        assert_eq!(all_users_result.unwrap(), 3);
+
+        // 6) deleting a definition keeps the already booked entries
+        let delete_result = recurring_booking_handle_1
+            .recurring_booking_delete(&insurance.id)
+            .await;
+        assert!(delete_result.is_ok());
+        assert!(recurring_booking_handle_1
+            .recurring_booking_delete(&insurance.id)
+            .await
+            .is_err());
+        let stored_list_result = recurring_booking_handle_1.recurring_booking_list().await;
+        assert_eq!(stored_list_result.unwrap().len(), 1);
+        let journal_entries_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert_eq!(
+            journal_entries_result.unwrap().len(),
+            expected_rent_times.len() + 3
+        );
+
+        // 7) the background task and a request booking at the same time do not book an occurrence twice
+        let salary = create_recurring_booking(
+            accounts[1].id,
+            accounts[0].id,
+            RecurringBookingSchedule::Weekly,
+            Utc::now() - Duration::days(700),
+        );
+        let upsert_salary_result = recurring_booking_handle_1
+            .recurring_booking_upsert(&salary)
+            .await;
+        assert!(upsert_salary_result.is_ok());
+        let expected_salary_times =
+            FinanceRecurringBookingHandle::get_due_occurrence_times(&salary, booking_time_till);
+        let start_barrier = std::sync::Arc::new(std::sync::Barrier::new(2));
+        let booking_threads: Vec<_> = (0..2)
+            .map(|_| {
+                let thread_connection_settings = dummy_connection_settings.clone();
+                let thread_start_barrier = start_barrier.clone();
+                std::thread::spawn(move || {
+                    thread_start_barrier.wait();
+                    let thread_db = InMemoryDatabaseHandler {};
+                    let thread_handle = FinanceRecurringBookingHandle::new(
+                        &thread_connection_settings,
+                        &user_id_1,
+                        &thread_db,
+                    );
+                    return thread_handle.book_due_occurrences_sync(booking_time_till);
+                })
+            })
+            .collect();
+        let mut concurrent_booked_count = 0;
+        for booking_thread in booking_threads {
+            let thread_result = booking_thread.join().unwrap();
+            assert!(thread_result.is_ok(), "{}", thread_result.unwrap_err());
+            let thread_run_result = thread_result.unwrap();
+            assert!(thread_run_result.errors.is_empty());
+            concurrent_booked_count += thread_run_result.booking_results.len();
+        }
+        assert_eq!(concurrent_booked_count, expected_salary_times.len());
+        let journal_entries_result = booking_handle_1.list_journal_entries(None, None).await;
+        assert_eq!(
+            journal_entries_result.unwrap().len(),
+            expected_rent_times.len() + 3 + expected_salary_times.len()
+        );
+
+        // 8) a definition that can not be booked does not stop the other definitions
+        let mut broken = create_recurring_booking(
+            accounts[2].id,
+            accounts[1].id,
+            RecurringBookingSchedule::Weekly,
+            Utc::now() - Duration::days(20),
+        );
+        broken.booking_template.credit_finance_account_id = Uuid::new();
+        let upsert_broken_result = in_memory_db
+            .finance_recurring_booking_upsert(&dummy_connection_settings, &user_id_1, &broken)
+            .await;
+        assert!(upsert_broken_result.is_ok());
+        let parking = create_recurring_booking(
+            accounts[2].id,
+            accounts[1].id,
+            RecurringBookingSchedule::Weekly,
+            Utc::now() - Duration::days(20),
+        );
+        let upsert_parking_result = recurring_booking_handle_1
+            .recurring_booking_upsert(&parking)
+            .await;
+        assert!(upsert_parking_result.is_ok());
+        let expected_parking_times =
+            FinanceRecurringBookingHandle::get_due_occurrence_times(&parking, booking_time_till);
+        let broken_run_result = recurring_booking_handle_1
+            .book_due_occurrences(booking_time_till)
+            .await;
+        assert!(
+            broken_run_result.is_ok(),
+            "{}",
+            broken_run_result.unwrap_err()
+        );
+        let broken_run = broken_run_result.unwrap();
+        assert_eq!(broken_run.errors.len(), 1);
+        assert!(broken_run.errors[0].contains(&broken.id.to_string()));
+        assert_eq!(
+            broken_run.booking_results.len(),
+            expected_parking_times.len()
+        );
+        let stored_list_result = recurring_booking_handle_1.recurring_booking_list().await;
+        let stored_broken = stored_list_result
+            .unwrap()
+            .into_iter()
+            .find(|elem| elem.id.eq(&broken.id))
+            .unwrap();
+        assert_eq!(stored_broken.last_booking_time, None);
+
+        // 9) storing the progress keeps an edit of the definition saved during the run
+        let mut edited_parking = parking.clone();
+        edited_parking.booking_template.amount = 70000;
+        edited_parking.last_booking_time = expected_parking_times.first().copied();
+        let upsert_edited_result = recurring_booking_handle_1
+            .recurring_booking_upsert(&edited_parking)
+            .await;
+        assert!(upsert_edited_result.is_ok());
+        let progress_result = in_memory_db
+            .finance_recurring_booking_set_last_booking_time(
+                &dummy_connection_settings,
+                &user_id_1,
+                &parking.id,
+                expected_parking_times.last().unwrap(),
+            )
+            .await;
+        assert!(progress_result.is_ok());
+        let backwards_result = in_memory_db
+            .finance_recurring_booking_set_last_booking_time(
+                &dummy_connection_settings,
+                &user_id_1,
+                &parking.id,
+                &parking.start_time,
+            )
+            .await;
+        assert!(backwards_result.is_ok());
+        let stored_list_result = recurring_booking_handle_1.recurring_booking_list().await;
+        let stored_parking = stored_list_result
+            .unwrap()
+            .into_iter()
+            .find(|elem| elem.id.eq(&parking.id))
+            .unwrap();
+        assert_eq!(stored_parking.booking_template.amount, 70000);
+        assert_eq!(
+            stored_parking.last_booking_time,
+            expected_parking_times.last().copied()
+        );
+    }
+}
@@ -0,0 +1,282 @@
+#[cfg(test)]
+mod test_tags {
+    use async_session::chrono::{TimeZone, Utc};
+    use mongodb::bson::Uuid;
+
+    use crate::{
+        accounting_config_logic::FinanceAccountingConfigHandle,
+        accounting_database::FinanceAccountBookingEntryListSearchOption,
+        accounting_logic::{normalize_tags, FinanceBookingHandle},
+        accounting_report::FinanceReportHandle,
+        database_handler_mongodb::DbConnectionSetting,
+        datatypes::{
+            AccountCategory, FinanceAccount, FinanceAccountType, FinanceBookingLeg,
+            FinanceBookingRequest, FinanceSplitBookingRequest,
+        },
+        frontend_functions::get_booking_tags,
+        tests::mocking_database::{InMemoryDatabaseData, InMemoryDatabaseHandler},
+    };
+
+    #[test]
+    fn test_tag_input_parsing() {
+        assert_eq!(get_booking_tags(&None), Vec::<String>::new());
+        assert_eq!(get_booking_tags(&Some(" , ".into())), Vec::<String>::new());
+        assert_eq!(
+            get_booking_tags(&Some("vacation 2026, food ,,car".into())),
+            vec!["vacation 2026", "food", "car"]
+        );
+        assert_eq!(
+            normalize_tags(&vec![
+                " food".into(),
+                "".into(),
+                "car".into(),
+                "food ".into()
+            ]),
+            vec!["food", "car"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_tags_with_mock() {
+        let dummy_connection_settings = DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+        let user_id_1 = Uuid::new();
+
+        let entry_object1 =
+            InMemoryDatabaseData::create_in_memory_database_entry_object(&user_id_1);
+
+        let _insert_result =
+            InMemoryDatabaseData::insert_in_memory_database(Vec::from([entry_object1]));
+
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let mut account_handle_1 = FinanceAccountingConfigHandle::new(
+            &dummy_connection_settings,
+            &user_id_1,
+            &in_memory_db,
+        );
+        let booking_handle_1 =
+            FinanceBookingHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+        let report_handle_1 =
+            FinanceReportHandle::new(&dummy_connection_settings, &user_id_1, &in_memory_db);
+
+        // (account title, category)
+        let account_data = [
+            ("hotel", AccountCategory::Expense),
+            ("restaurant", AccountCategory::Expense),
+            ("bank", AccountCategory::Asset),
+        ];
+        let mut accounts = Vec::new();
+        for (account_title, account_category) in account_data {
+            let mut finance_account_type = FinanceAccountType {
+                description: format!("type_description_{}", account_title),
+                title: format!("type_{}", account_title),
+                id: Uuid::new(),
+                account_category: account_category,
+            };
+            let insert_finance_account_type_result =
+                account_handle_1.finance_account_type_upsert(&mut finance_account_type);
+            assert!(
+                insert_finance_account_type_result.is_ok(),
+                "{}",
+                insert_finance_account_type_result.unwrap_err()
+            );
+            let finance_account = FinanceAccount {
+                id: Uuid::new(),
+                finance_account_type_id: finance_account_type.id,
+                title: account_title.into(),
+                description: format!("description_{}", account_title),
+                account_number: None,
+            };
+            let insert_finance_account_result =
+                account_handle_1.finance_account_upsert(&finance_account);
+            assert!(
+                insert_finance_account_result.is_ok(),
+                "{}",
+                insert_finance_account_result.unwrap_err()
+            );
+            accounts.push(finance_account);
+        }
+
+        // 1) tags are normalized and copied to the booking entries
+        let hotel_request = FinanceBookingRequest {
+            is_simple_entry: true,
+            is_saldo: false,
+            debit_finance_account_id: accounts[0].id,
+            credit_finance_account_id: accounts[2].id,
+            booking_time: Utc.with_ymd_and_hms(2020, 7, 10, 12, 0, 0).unwrap(),
+            amount: 40000,
+            title: "hotel".into(),
+            description: "hotel booking".into(),
+            tags: vec![" vacation 2026".into(), "travel".into(), "travel".into()],
+            import_reference: None,
+        };
+        let hotel_result = booking_handle_1
+            .finance_insert_booking_entry(&hotel_request)
+            .await;
+        assert!(hotel_result.is_ok(), "{}", hotel_result.unwrap_err());
+        let hotel_result = hotel_result.unwrap();
+        assert_eq!(
+            hotel_result.journal_entry.tags,
+            vec!["vacation 2026", "travel"]
+        );
+        assert_eq!(
+            hotel_result.debit_account_entry.tags,
+            hotel_result.journal_entry.tags
+        );
+        assert_eq!(
+            hotel_result.credit_account_entry.tags,
+            hotel_result.journal_entry.tags
+        );
+
+        let dinner_request = FinanceSplitBookingRequest {
+            is_saldo: false,
+            is_closing: false,
+            debit_legs: vec![
+                FinanceBookingLeg {
+                    finance_account_id: accounts[1].id,
+                    amount: 6000,
+                },
+                FinanceBookingLeg {
+                    finance_account_id: accounts[0].id,
+                    amount: 1000,
+                },
+            ],
+            credit_legs: vec![FinanceBookingLeg {
+                finance_account_id: accounts[2].id,
+                amount: 7000,
+            }],
+            booking_time: Utc.with_ymd_and_hms(2020, 8, 2, 12, 0, 0).unwrap(),
+            title: "dinner".into(),
+            description: "dinner with city tax".into(),
+            tags: vec!["vacation 2026".into()],
+            import_reference: None,
+        };
+        let dinner_result = booking_handle_1
+            .finance_insert_split_booking_entry(&dinner_request)
+            .await;
+        assert!(dinner_result.is_ok(), "{}", dinner_result.unwrap_err());
+
+        let untagged_request = FinanceBookingRequest {
+            is_simple_entry: true,
+            is_saldo: false,
+            debit_finance_account_id: accounts[1].id,
+            credit_finance_account_id: accounts[2].id,
+            booking_time: Utc.with_ymd_and_hms(2020, 8, 5, 12, 0, 0).unwrap(),
+            amount: 2500,
+            title: "lunch".into(),
+            description: "lunch at work".into(),
+            tags: Vec::new(),
+            import_reference: None,
+        };
+        let untagged_result = booking_handle_1
+            .finance_insert_booking_entry(&untagged_request)
+            .await;
+        assert!(untagged_result.is_ok(), "{}", untagged_result.unwrap_err());
+
+        let booking_entries_result =
+            booking_handle_1
+                .list_account_booking_entries(vec![
+                    FinanceAccountBookingEntryListSearchOption::new(&accounts[1].id, None, None),
+                ])
+                .await;
+        assert!(booking_entries_result.is_ok());
+        let booking_entries = booking_entries_result.unwrap();
+        assert_eq!(booking_entries.len(), 2);
+        assert!(booking_entries
+            .iter()
+            .any(|elem| elem.tags.eq(&vec!["vacation 2026".to_string()])));
+
+        // 2) filter the journal by tag
+        let all_entries = booking_handle_1.list_journal_entries(None, None).await;
+        assert_eq!(all_entries.unwrap().len(), 3);
+        let vacation_entries = booking_handle_1
+            .list_journal_entries_with_tag(None, None, Some("vacation 2026".into()))
+            .await;
+        assert_eq!(vacation_entries.unwrap().len(), 2);
+        let travel_entries = booking_handle_1
+            .list_journal_entries_with_tag(None, None, Some(" travel ".into()))
+            .await
+            .unwrap();
+        assert_eq!(travel_entries.len(), 1);
+        assert_eq!(travel_entries[0].id, hotel_result.journal_entry.id);
+        let unknown_tag_entries = booking_handle_1
+            .list_journal_entries_with_tag(None, None, Some("vacation".into()))
+            .await;
+        assert_eq!(unknown_tag_entries.unwrap().len(), 0);
+        // an empty tag does not filter
+        let empty_tag_entries = booking_handle_1
+            .list_journal_entries_with_tag(None, None, Some("".into()))
+            .await;
+        assert_eq!(empty_tag_entries.unwrap().len(), 3);
+
+        // 3) a reversal keeps the tags of the reversed entry
+        let reverse_result = booking_handle_1
+            .reverse_journal_entry(&hotel_result.journal_entry.id, &"cancelled".into())
+            .await;
+        assert!(reverse_result.is_ok(), "{}", reverse_result.unwrap_err());
+        let reverse_result = reverse_result.unwrap();
+        assert_eq!(
+            reverse_result.journal_entry.tags,
+            hotel_result.journal_entry.tags
+        );
+        let travel_entries = booking_handle_1
+            .list_journal_entries_with_tag(None, None, Some("travel".into()))
+            .await;
+        assert_eq!(travel_entries.unwrap().len(), 2);
+
+        // 4) monthly spending per tag, the reversal is booked in the current month
+        let summary_result = report_handle_1
+            .tag_spending_summary(
+                Utc.with_ymd_and_hms(2020, 1, 1, 0, 0, 0).unwrap(),
+                Utc::now(),
+            )
+            .await;
+        assert!(summary_result.is_ok(), "{}", summary_result.unwrap_err());
+        let summary = summary_result.unwrap();
+        let summary_values: Vec<(&str, &str, i128, u64)> = summary
+            .lines
+            .iter()
+            .map(|elem| {
+                (
+                    elem.tag.as_str(),
+                    elem.month.as_str(),
+                    elem.amount,
+                    elem.entry_count,
+                )
+            })
+            .collect();
+        let current_month = Utc::now().format("%Y-%m").to_string();
+        assert_eq!(
+            summary_values,
+            vec![
+                ("travel", "2020-07", 40000, 1),
+                ("travel", current_month.as_str(), -40000, 1),
+                ("vacation 2026", "2020-07", 40000, 1),
+                ("vacation 2026", "2020-08", 7000, 1),
+                ("vacation 2026", current_month.as_str(), -40000, 1),
+            ]
+        );
+
+        // the time range limits the summary
+        let july_summary = report_handle_1
+            .tag_spending_summary(
+                Utc.with_ymd_and_hms(2020, 7, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 7, 31, 23, 59, 59).unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(july_summary.lines.len(), 2);
+        assert!(report_handle_1
+            .tag_spending_summary(
+                Utc.with_ymd_and_hms(2020, 8, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2020, 7, 1, 0, 0, 0).unwrap(),
+            )
+            .await
+            .is_err());
+    }
+}
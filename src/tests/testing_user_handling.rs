@@ -0,0 +1,331 @@
+#[cfg(test)]
+mod test_user_handling {
+    use secrecy::Secret;
+
+    use crate::{
+        database_handler_mongodb::{DbConnectionSetting, EmailVerificationStatus},
+        datatypes::{GenerallUserData, PasswordResetRequest, PasswordResetTokenRequest},
+        frontend_functions::{
+            get_general_userdata_fromdatabase, register_user_with_email_verfication,
+            save_general_userdata,
+        },
+        password_handle::{
+            check_email_status_by_name, create_credentials, request_password_reset_token,
+            reset_password_with_token, update_user_password, update_user_reset_secret,
+            validate_credentials, UserCredentials,
+        },
+        tests::{
+            mocking_database::InMemoryDatabaseHandler,
+            testing_accounting_config::test_accounting_handle::init,
+        },
+        user_database::DBUserFunctions,
+        user_handling::validate_user_email,
+    };
+
+    fn get_dummy_connection_settings() -> DbConnectionSetting {
+        return DbConnectionSetting {
+            instance: "".into(),
+            password: "".into(),
+            url: "".into(),
+            user: "".into(),
+        };
+    }
+
+    #[tokio::test]
+    async fn test_register_and_login_with_mock() {
+        let dummy_connection_settings = get_dummy_connection_settings();
+        let in_memory_db = InMemoryDatabaseHandler {};
+
+        let user_credentials = UserCredentials {
+            username: "mock_user_login".into(),
+            password: Secret::new("first_password".into()),
+        };
+
+        let create_result =
+            create_credentials(&dummy_connection_settings, &in_memory_db, &user_credentials).await;
+        assert!(create_result.is_ok());
+        let user_id = create_result.unwrap();
+
+        //creating the same user again is not allowed
+        let recreate_result =
+            create_credentials(&dummy_connection_settings, &in_memory_db, &user_credentials).await;
+        assert!(recreate_result.is_err());
+
+        let login_result =
+            validate_credentials(&dummy_connection_settings, &in_memory_db, &user_credentials)
+                .await;
+        assert_eq!(login_result.unwrap(), user_id);
+
+        let wrong_credentials = UserCredentials {
+            username: "mock_user_login".into(),
+            password: Secret::new("wrong_password".into()),
+        };
+        let wrong_login_result = validate_credentials(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &wrong_credentials,
+        )
+        .await;
+        assert!(wrong_login_result.is_err());
+
+        let unknown_credentials = UserCredentials {
+            username: "mock_user_unknown".into(),
+            password: Secret::new("first_password".into()),
+        };
+        let unknown_login_result = validate_credentials(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &unknown_credentials,
+        )
+        .await;
+        assert!(unknown_login_result.is_err());
+
+        //after changing the password only the new one is accepted
+        let new_credentials = UserCredentials {
+            username: "mock_user_login".into(),
+            password: Secret::new("second_password".into()),
+        };
+        let update_result =
+            update_user_password(&dummy_connection_settings, &in_memory_db, &new_credentials).await;
+        assert!(update_result.unwrap());
+
+        let old_login_result =
+            validate_credentials(&dummy_connection_settings, &in_memory_db, &user_credentials)
+                .await;
+        assert!(old_login_result.is_err());
+        let new_login_result =
+            validate_credentials(&dummy_connection_settings, &in_memory_db, &new_credentials).await;
+        assert_eq!(new_login_result.unwrap(), user_id);
+
+        //general user data
+        let general_data = GenerallUserData {
+            first_name: "Mock".into(),
+            last_name: "User".into(),
+        };
+        let save_result = save_general_userdata(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &new_credentials.username,
+            &general_data,
+        )
+        .await;
+        assert!(save_result.is_ok());
+        let get_result = get_general_userdata_fromdatabase(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &new_credentials.username,
+        )
+        .await
+        .unwrap();
+        assert_eq!(get_result.first_name, "Mock");
+        assert_eq!(get_result.last_name, "User");
+    }
+
+    #[tokio::test]
+    async fn test_email_verification_with_mock() {
+        //mail format check is configured in the server settings
+        init();
+
+        let dummy_connection_settings = get_dummy_connection_settings();
+        let in_memory_db = InMemoryDatabaseHandler {};
+        let user_name: String = "mock_user_email".into();
+
+        //registration is rejected before anything is stored when the mail is not valid
+        let register_result = register_user_with_email_verfication(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &user_name,
+            &Secret::new("some_password".into()),
+            &"not_a_mail_address".into(),
+        )
+        .await;
+        assert!(register_result.is_err());
+        let exists_result = in_memory_db
+            .check_user_exsists_by_name(&dummy_connection_settings, &user_name)
+            .await;
+        assert!(!exists_result.unwrap());
+
+        let user_credentials = UserCredentials {
+            username: user_name.clone(),
+            password: Secret::new("some_password".into()),
+        };
+        let _create_result =
+            create_credentials(&dummy_connection_settings, &in_memory_db, &user_credentials)
+                .await
+                .unwrap();
+
+        let status_result =
+            check_email_status_by_name(&dummy_connection_settings, &in_memory_db, &user_name).await;
+        assert!(matches!(
+            status_result.unwrap(),
+            EmailVerificationStatus::NotGiven
+        ));
+
+        let validation_token = in_memory_db
+            .update_user_email(
+                &dummy_connection_settings,
+                &user_name,
+                &"mock_user@example.com".into(),
+            )
+            .await
+            .unwrap();
+
+        let status_result =
+            check_email_status_by_name(&dummy_connection_settings, &in_memory_db, &user_name).await;
+        assert!(matches!(
+            status_result.unwrap(),
+            EmailVerificationStatus::NotVerified
+        ));
+
+        let wrong_validate_result = validate_user_email(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &user_name,
+            &Secret::new("wrong_token".into()),
+        )
+        .await;
+        assert!(wrong_validate_result.is_err());
+
+        let validate_result = validate_user_email(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &user_name,
+            &Secret::new(validation_token),
+        )
+        .await;
+        assert!(matches!(
+            validate_result.unwrap(),
+            EmailVerificationStatus::Verified
+        ));
+
+        let status_result =
+            check_email_status_by_name(&dummy_connection_settings, &in_memory_db, &user_name).await;
+        assert!(matches!(
+            status_result.unwrap(),
+            EmailVerificationStatus::Verified
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_password_reset_with_mock() {
+        //token time limit is taken from the server settings
+        init();
+
+        let dummy_connection_settings = get_dummy_connection_settings();
+        let in_memory_db = InMemoryDatabaseHandler {};
+        let user_name: String = "mock_user_reset".into();
+
+        let user_credentials = UserCredentials {
+            username: user_name.clone(),
+            password: Secret::new("forgotten_password".into()),
+        };
+        let user_id =
+            create_credentials(&dummy_connection_settings, &in_memory_db, &user_credentials)
+                .await
+                .unwrap();
+
+        let reset_secret: Secret<String> = Secret::new("reset_secret".into());
+        let update_secret_result = update_user_reset_secret(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &user_id,
+            &reset_secret,
+        )
+        .await;
+        assert!(update_secret_result.unwrap());
+
+        //a token needs a valid mail address
+        let no_mail_token_result = request_password_reset_token(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &PasswordResetTokenRequest {
+                user_name: user_name.clone(),
+                reset_secret: reset_secret.clone(),
+            },
+        )
+        .await;
+        assert!(no_mail_token_result.is_err());
+
+        let _validation_token = in_memory_db
+            .update_user_email(
+                &dummy_connection_settings,
+                &user_name,
+                &"mock_reset@example.com".into(),
+            )
+            .await
+            .unwrap();
+
+        let wrong_secret_token_result = request_password_reset_token(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &PasswordResetTokenRequest {
+                user_name: user_name.clone(),
+                reset_secret: Secret::new("wrong_secret".into()),
+            },
+        )
+        .await;
+        assert!(wrong_secret_token_result.is_err());
+
+        let token_result = request_password_reset_token(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &PasswordResetTokenRequest {
+                user_name: user_name.clone(),
+                reset_secret: reset_secret.clone(),
+            },
+        )
+        .await
+        .unwrap();
+        assert_eq!(token_result.user_email, "mock_reset@example.com");
+
+        let wrong_token_reset_result = reset_password_with_token(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &PasswordResetRequest {
+                username: user_name.clone(),
+                reset_token: "wrong_token".into(),
+                new_password: Secret::new("new_password".into()),
+            },
+        )
+        .await;
+        assert!(wrong_token_reset_result.is_err());
+
+        let reset_result = reset_password_with_token(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &PasswordResetRequest {
+                username: user_name.clone(),
+                reset_token: token_result.reset_token.clone(),
+                new_password: Secret::new("new_password".into()),
+            },
+        )
+        .await;
+        assert!(reset_result.unwrap());
+
+        //token can only be used once
+        let reused_token_result = reset_password_with_token(
+            &dummy_connection_settings,
+            &in_memory_db,
+            &PasswordResetRequest {
+                username: user_name.clone(),
+                reset_token: token_result.reset_token,
+                new_password: Secret::new("another_password".into()),
+            },
+        )
+        .await;
+        assert!(reused_token_result.is_err());
+
+        let old_login_result =
+            validate_credentials(&dummy_connection_settings, &in_memory_db, &user_credentials)
+                .await;
+        assert!(old_login_result.is_err());
+
+        let new_credentials = UserCredentials {
+            username: user_name.clone(),
+            password: Secret::new("new_password".into()),
+        };
+        let new_login_result =
+            validate_credentials(&dummy_connection_settings, &in_memory_db, &new_credentials).await;
+        assert_eq!(new_login_result.unwrap(), user_id);
+    }
+}
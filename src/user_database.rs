@@ -0,0 +1,2139 @@
+use argon2::{Argon2, PasswordHasher};
+use async_session::chrono::{DateTime, Duration, Utc};
+use axum::async_trait;
+use futures::StreamExt;
+use log::{debug, warn};
+use mongodb::{
+    bson::{doc, uuid, Document, Uuid},
+    options::FindOptions,
+    Collection,
+};
+use rusqlite::{params, OptionalExtension, TransactionBehavior};
+use secrecy::{ExposeSecret, Secret};
+
+use crate::{
+    convert_tools::ConvertTools,
+    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB, EmailVerificationStatus},
+    database_handler_postgres::DbHandlerPostgres,
+    database_handler_sqlite::DbHandlerSqlite,
+    datatypes::{GenerallUserData, PasswordResetTokenRequestResult},
+    mail_handle::validate_email_format,
+    password_handle::{verify_password_hash, StoredCredentials, UserCredentialsHashed},
+};
+
+/// storage of the user accounts used for registration, login and password reset,
+/// the futures are Send because the web handlers await them directly
+#[async_trait]
+pub trait DBUserFunctions: Send + Sync {
+    async fn check_user_exsists_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<bool, String>;
+    async fn insert_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<Uuid, String>;
+    async fn update_user_password(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<bool, String>;
+    async fn get_stored_credentials_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<StoredCredentials, String>;
+    async fn update_user_email(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        new_email: &String,
+    ) -> Result<String, String>;
+    async fn check_email_verfification_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<EmailVerificationStatus, String>;
+    async fn verify_email_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        email_validation_string: &Secret<String>,
+    ) -> Result<EmailVerificationStatus, String>;
+    async fn get_user_general_data_by_user_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<GenerallUserData, String>;
+    async fn update_general_user_data_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        general_user_data: &GenerallUserData,
+    ) -> Result<String, String>;
+    async fn update_user_reset_secret(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reset_secret_hash: &Secret<String>,
+    ) -> Result<bool, String>;
+    async fn generate_passwort_reset_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_secret: &Secret<String>,
+        passwort_reset_time_limit_minutes: &u16,
+    ) -> Result<PasswordResetTokenRequestResult, String>;
+    async fn change_password_with_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_token: &String,
+        new_password: &Secret<String>,
+    ) -> Result<bool, String>;
+}
+
+#[async_trait]
+impl DBUserFunctions for DbHandlerMongoDB {
+    async fn check_user_exsists_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<bool, String> {
+        // Get a handle to the deployment.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let filter = doc! {"user_name":&user_name};
+        let projection = doc! {"user_name":<i32>::from(1)};
+        let options = FindOptions::builder().projection(projection).build();
+
+        let data_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+        let query_execute_result = data_collcetion.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut doc_counter = 0;
+
+        while let Some(data_doc) = cursor.next().await {
+            doc_counter = doc_counter + 1;
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+            let stored_name = inner_doc.get_str("user_name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+            if stored_name.unwrap().eq(user_name) {
+                return Ok(true);
+            }
+        }
+
+        if doc_counter > 0 {
+            return Err(format!("found {} entries", doc_counter));
+        }
+
+        return Result::Ok(false);
+    }
+
+    async fn insert_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<uuid::Uuid, String> {
+        // Get a handle to the deployment.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+        let user_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+
+        let new_user_uuid = Uuid::new();
+        let insert_doc = doc! {"user_id": new_user_uuid,
+        "user_name":&some_credentials.username,
+        "password_hash": &some_credentials.password_hash.expose_secret()};
+
+        let insert_result = user_collcetion.insert_one(insert_doc, None).await;
+        if insert_result.is_err() {
+            let insert_err = &insert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","new id of user object (not user_id): {}",insert_result.unwrap().inserted_id);
+
+        return Ok(new_user_uuid);
+    }
+
+    async fn update_user_password(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<bool, String> {
+        // Get a handle to the deployment.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+        let user_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+
+        let filter_doc = doc! {
+        "user_name":&some_credentials.username};
+        let inner_update_doc = doc! {
+        "password_hash": &some_credentials.password_hash.expose_secret()};
+        //otherwise we get "update document must have first key starting with '$"
+        let update_doc = doc! {"$set": inner_update_doc};
+
+        let update_result = user_collcetion
+            .update_one(filter_doc, update_doc, None)
+            .await;
+        if update_result.is_err() {
+            let update_err = &update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err.to_string());
+        }
+        let unwrapped_result = update_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
+
+        return Ok(true);
+    }
+
+    async fn get_stored_credentials_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<StoredCredentials, String> {
+        // Get a handle to the deployment.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+        let user_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+
+        let filter = doc! {"user_name":&user_name};
+
+        let projection = doc! {"user_name":<i32>::from(1),
+        "user_id":<i32>::from(1),
+        "password_hash":<i32>::from(1)};
+
+        let options = FindOptions::builder().projection(projection).build();
+
+        let query_execute_result = user_collcetion.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut doc_counter = 0;
+        let mut result_doc = doc! {};
+
+        while let Some(data_doc) = cursor.next().await {
+            doc_counter = doc_counter + 1;
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc = data_doc.unwrap();
+            let stored_name = inner_doc.get_str("user_name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+            if stored_name.unwrap().eq(user_name) {
+                result_doc = inner_doc;
+            }
+        }
+
+        if doc_counter != 1 {
+            return Err(format!("found {} entries", doc_counter));
+        }
+
+        let stored_password_hash_read = result_doc.get_str("password_hash");
+        if stored_password_hash_read.is_err() {
+            return Err(stored_password_hash_read.unwrap_err().to_string());
+        }
+
+        let some_uuid_parse_result = ConvertTools::get_uuid_from_document(&result_doc, "user_id");
+
+        if some_uuid_parse_result.is_err() {
+            return Err("Could not parse UUID".to_string());
+        }
+
+        let some_password_hash =
+            secrecy::Secret::<String>::new(stored_password_hash_read.unwrap().to_string());
+
+        let some_cred = StoredCredentials {
+            user_id: some_uuid_parse_result.unwrap(),
+            password_hash: some_password_hash,
+        };
+
+        return Ok(some_cred);
+    }
+
+    async fn update_user_email(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        new_email: &String,
+    ) -> Result<String, String> {
+        //first validate if correct password is given
+        let query_username = self
+            .check_user_exsists_by_name(conncetion_settings, user_name)
+            .await;
+
+        if query_username.is_err() {
+            return Err(format!(
+                "Unable to verify username: {}",
+                query_username.unwrap_err()
+            ));
+        }
+
+        if !query_username.unwrap() {
+            return Err(format!("username does not exists: {}", user_name));
+        }
+
+        //generate a random hash for email validation
+        let salt = argon2::password_hash::SaltString::generate(&mut rand::thread_rng());
+        let current_time = async_session::chrono::Utc::now();
+
+        let mail_validation_token_result =
+            Argon2::default().hash_password(&current_time.to_string().as_bytes(), &salt);
+        if mail_validation_token_result.is_err() {
+            return Err(format!(
+                "Unable to generate email check token: {}",
+                mail_validation_token_result.unwrap_err()
+            ));
+        }
+        let mail_validation_token = mail_validation_token_result.unwrap().to_string();
+
+        // Get a handle to the deployment.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+        let user_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+
+        let filter_doc = doc! {
+        "user_name":user_name};
+        let inner_update_doc = doc! {
+        "user_email": new_email,
+        "mail_validated": false,
+        "mail_validation_token": &mail_validation_token};
+        //otherwise we get "update document must have first key starting with '$"
+        let update_doc = doc! {"$set": inner_update_doc};
+
+        let update_result = user_collcetion
+            .update_one(filter_doc, update_doc, None)
+            .await;
+        if update_result.is_err() {
+            let update_err = &update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!("Error updating email address: {}", update_err));
+        }
+        let unwrapped_result = update_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
+
+        return Ok(mail_validation_token);
+    }
+
+    async fn check_email_verfification_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<EmailVerificationStatus, String> {
+        // Get a handle to the deployment.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let filter = doc! {"user_name":&user_name};
+        let projection = doc! {"user_name":<i32>::from(1),
+        "mail_validated":<i32>::from(1),
+        "user_email":<i32>::from(1)};
+        let options = FindOptions::builder().projection(projection).build();
+
+        let data_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+        let query_execute_result = data_collcetion.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut doc_counter = 0;
+
+        while let Some(data_doc) = cursor.next().await {
+            doc_counter = doc_counter + 1;
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc: Document = data_doc.unwrap();
+            let stored_name = inner_doc.get_str("user_name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+
+            if stored_name.unwrap().eq(user_name) {
+                if inner_doc.contains_key("user_email") {
+                    let stored_email = inner_doc.get_str("user_email");
+                    if stored_email.is_err() {
+                        return Err(stored_email.unwrap_err().to_string());
+                    }
+                    if stored_email.unwrap().is_empty() {
+                        return Ok(EmailVerificationStatus::NotGiven);
+                    }
+                    let stored_verfication_flag = inner_doc.get_bool("mail_validated");
+                    if stored_verfication_flag.is_err() {
+                        return Err(stored_verfication_flag.unwrap_err().to_string());
+                    }
+                    if stored_verfication_flag.unwrap() {
+                        return Ok(EmailVerificationStatus::Verified);
+                    } else {
+                        return Ok(EmailVerificationStatus::NotVerified);
+                    };
+                } else {
+                    return Ok(EmailVerificationStatus::NotGiven);
+                }
+            }
+        }
+
+        if doc_counter > 0 {
+            return Err(format!("found {} entries", doc_counter));
+        }
+
+        return Result::Ok(EmailVerificationStatus::NotVerified);
+    }
+
+    async fn verify_email_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        email_validation_string: &Secret<String>,
+    ) -> Result<EmailVerificationStatus, String> {
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let filter = doc! {
+            "user_name":user_name
+        };
+        let projection = doc! {"user_name":<i32>::from(1),
+            "mail_validated":<i32>::from(1),
+            "mail_validation_token":<i32>::from(1),
+            "user_email":<i32>::from(1),
+            "user_id":<i32>::from(1),
+        };
+        let options = FindOptions::builder().projection(projection).build();
+
+        let data_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+        let query_execute_result = data_collcetion.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut doc_counter = 0;
+
+        while let Some(data_doc) = cursor.next().await {
+            doc_counter = doc_counter + 1;
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc: Document = data_doc.unwrap();
+            let stored_name = inner_doc.get_str("user_name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+
+            if stored_name.unwrap().eq(user_name) {
+                if inner_doc.contains_key("user_email") {
+                    let stored_email = inner_doc.get_str("user_email");
+                    if stored_email.is_err() {
+                        return Err(stored_email.unwrap_err().to_string());
+                    }
+                    let stored_verfication_flag = inner_doc.get_bool("mail_validated");
+                    if stored_verfication_flag.is_err() {
+                        return Err(stored_verfication_flag.unwrap_err().to_string());
+                    }
+                    let stored_verfication_token = inner_doc.get_str("mail_validation_token");
+                    if stored_verfication_token.is_err() {
+                        return Err(stored_verfication_token.unwrap_err().to_string());
+                    }
+                    let stored_user_id =
+                        ConvertTools::get_uuid_from_document(&inner_doc, "user_id");
+                    if stored_user_id.is_err() {
+                        return Err(stored_user_id.unwrap_err().to_string());
+                    }
+
+                    if stored_verfication_flag.unwrap() {
+                        return Ok(EmailVerificationStatus::Verified);
+                    } else {
+                        if stored_verfication_token
+                            .unwrap()
+                            .eq(email_validation_string.expose_secret())
+                        {
+                            //addtional check for later: include E-Mail in hashed token and compared it to ensure that the token "belongs" to the E-Mail address
+                            //currently only the time plus a salt is used to generate token
+                            let filter_doc = doc! {
+                            "user_id":stored_user_id.unwrap()};
+                            let inner_update_doc = doc! {
+                            "mail_validated": true,
+                            };
+                            //otherwise we get "update document must have first key starting with '$"
+                            let update_doc = doc! {"$set": inner_update_doc};
+
+                            let user_collcetion: Collection<Document> =
+                                db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+                            let update_result = user_collcetion
+                                .update_one(filter_doc, update_doc, None)
+                                .await;
+                            if update_result.is_err() {
+                                let update_err = &update_result.unwrap_err();
+                                warn!(target:"app::FinanceOverView","{}",update_err);
+                                return Err(format!(
+                                    "Error updating email validated token: {}",
+                                    update_err
+                                ));
+                            }
+                            let unwrapped_result = update_result.unwrap();
+
+                            debug!(target:"app::FinanceOverView","count of updated objects during user email verfication: {}",unwrapped_result.modified_count);
+
+                            if unwrapped_result.modified_count == 1 {
+                                return Ok(EmailVerificationStatus::Verified);
+                            } else {
+                                return Err("number of updated monrecord not 1".to_string());
+                            }
+                        } else {
+                            return Err(
+                                "provided E-Mail Validation token not matching stored token"
+                                    .to_string(),
+                            );
+                        }
+                    }
+                } else {
+                    return Err("no E-Mail given".to_string());
+                }
+            }
+        }
+
+        if doc_counter > 0 {
+            return Err(format!("found {} entries", doc_counter));
+        }
+
+        return Result::Ok(EmailVerificationStatus::NotVerified);
+    }
+
+    async fn get_user_general_data_by_user_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<GenerallUserData, String> {
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let filter = doc! {
+            "user_name":user_name
+        };
+        let projection = doc! {"user_name":<i32>::from(1),
+            "first_name":<i32>::from(1),
+            "last_name":<i32>::from(1),
+        };
+        let options = FindOptions::builder().projection(projection).build();
+
+        let data_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+        let query_execute_result = data_collcetion.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        let mut doc_counter = 0;
+
+        while let Some(data_doc) = cursor.next().await {
+            doc_counter = doc_counter + 1;
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc: Document = data_doc.unwrap();
+            let stored_name = inner_doc.get_str("user_name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+
+            if stored_name.unwrap().eq(user_name) {
+                let mut return_data: GenerallUserData = GenerallUserData {
+                    first_name: "".to_string(),
+                    last_name: "".to_string(),
+                };
+                let stored_first_name = inner_doc.get_str("first_name");
+                if stored_first_name.is_ok() {
+                    return_data.first_name = stored_first_name.unwrap().to_string();
+                }
+                let stored_last_name = inner_doc.get_str("last_name");
+                if stored_last_name.is_ok() {
+                    return_data.last_name = stored_last_name.unwrap().to_string();
+                }
+
+                return Ok(return_data);
+            }
+        }
+
+        return Err(format!("found {} entries", doc_counter));
+    }
+
+    async fn update_general_user_data_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        general_user_data: &GenerallUserData,
+    ) -> Result<String, String> {
+        //first validate if correct password is given
+        let query_username = self
+            .check_user_exsists_by_name(conncetion_settings, user_name)
+            .await;
+
+        if query_username.is_err() {
+            return Err(format!(
+                "Unable to verify username: {}",
+                query_username.unwrap_err()
+            ));
+        }
+
+        if !query_username.unwrap() {
+            return Err(format!("username does not exists: {}", user_name));
+        }
+
+        // Get a handle to the database.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+        let user_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+
+        let filter_doc = doc! {
+        "user_name":user_name};
+        let inner_update_doc = doc! {
+        "first_name": general_user_data.first_name.clone(),
+        "last_name": general_user_data.last_name.clone(),
+        };
+        //otherwise we get "update document must have first key starting with '$"
+        let update_doc = doc! {"$set": inner_update_doc};
+
+        let update_result = user_collcetion
+            .update_one(filter_doc, update_doc, None)
+            .await;
+        if update_result.is_err() {
+            let update_err = &update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!("Error updating email address: {}", update_err));
+        }
+        let unwrapped_result = update_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
+
+        return Ok("updated".to_string());
+    }
+
+    async fn update_user_reset_secret(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reset_secret_hash: &Secret<String>,
+    ) -> Result<bool, String> {
+        // Get a handle to the deployment.
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+        let user_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+
+        let filter_doc = doc! {
+        "user_id":user_id};
+        let inner_update_doc = doc! {
+        "reset_secret_hash": reset_secret_hash.expose_secret()};
+        //otherwise we get "update document must have first key starting with '$"
+        let update_doc = doc! {"$set": inner_update_doc};
+
+        let update_result = user_collcetion
+            .update_one(filter_doc, update_doc, None)
+            .await;
+        if update_result.is_err() {
+            let update_err = &update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err.to_string());
+        }
+        let unwrapped_result = update_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
+
+        return Ok(true);
+    }
+
+    async fn generate_passwort_reset_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_secret: &Secret<String>,
+        passwort_reset_time_limit_minutes: &u16,
+    ) -> Result<PasswordResetTokenRequestResult, String> {
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let filter = doc! {
+            "user_name":user_name
+        };
+        let projection = doc! {
+            "user_name":<i32>::from(1),
+            "user_email":<i32>::from(1),
+            "reset_secret_hash":<i32>::from(1),
+        };
+        let options = FindOptions::builder().projection(projection).build();
+
+        let data_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+        let query_execute_result = data_collcetion.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        if let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc: Document = data_doc.unwrap();
+            let stored_name = inner_doc.get_str("user_name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+            let stored_email = inner_doc.get_str("user_email");
+            if stored_email.is_err() {
+                return Err(stored_email.unwrap_err().to_string());
+            }
+            let stored_user_email = stored_email.unwrap();
+
+            if stored_name.unwrap().eq(user_name) {
+                let stored_reset_secret_raw = inner_doc.get_str("reset_secret_hash");
+                if stored_reset_secret_raw.is_ok() {
+                    let transformed_stored_serect = secrecy::Secret::<String>::new(
+                        stored_reset_secret_raw.unwrap().to_string(),
+                    );
+                    let verify_serect_result =
+                        verify_password_hash(&transformed_stored_serect, &reset_secret);
+                    if verify_serect_result.is_ok() {
+                        let email_validation_result =
+                            validate_email_format(&stored_user_email.to_string());
+                        if email_validation_result.is_err() {
+                            return Err(email_validation_result.unwrap_err().to_string());
+                        }
+                        if !email_validation_result.unwrap() {
+                            return Err("no valid e-mail address for operation".to_string());
+                        }
+                        let reset_token_value = Uuid::new().to_string();
+                        let reset_token_timestamp = async_session::chrono::Utc::now()
+                            + Duration::minutes(passwort_reset_time_limit_minutes.clone() as i64);
+
+                        let inner_update_doc = doc! {
+                        "password_reset_token_value": reset_token_value.clone(),
+                        "password_reset_token_timestamp": reset_token_timestamp.timestamp()
+                        };
+
+                        let filter2 = doc! {
+                            "user_name":user_name
+                        };
+                        //otherwise we get "update document must have first key starting with '$"
+                        let update_doc = doc! {"$set": inner_update_doc};
+
+                        let update_result =
+                            data_collcetion.update_one(filter2, update_doc, None).await;
+                        if update_result.is_err() {
+                            let update_err = &update_result.unwrap_err();
+                            warn!(target:"app::FinanceOverView","{}",update_err);
+                            return Err("Error validating reset token".to_string());
+                        }
+                        let unwrapped_result = update_result.unwrap();
+
+                        debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
+
+                        let return_value = PasswordResetTokenRequestResult {
+                            reset_token: reset_token_value,
+                            expires_at: reset_token_timestamp,
+                            user_email: stored_user_email.to_string(),
+                        };
+
+                        return Ok(return_value);
+                    }
+                    return Err("error in token generation".to_string());
+                }
+                return Err("error generating token".to_string());
+            }
+
+            return Err("can not generate token".to_string());
+        }
+        return Err("unable to generate token".to_string());
+    }
+
+    async fn change_password_with_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_token: &String,
+        new_password: &Secret<String>,
+    ) -> Result<bool, String> {
+        let client_create_result =
+            DbHandlerMongoDB::create_client_connection_async(conncetion_settings).await;
+        if client_create_result.is_err() {
+            let client_err = &client_create_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err.to_string());
+        }
+        let client = client_create_result.unwrap();
+
+        let db_instance = client.database(&conncetion_settings.instance);
+
+        let filter = doc! {
+            "user_name":user_name
+        };
+        let projection = doc! {"user_name":<i32>::from(1),
+            "password_reset_token_value":<i32>::from(1),
+            "password_reset_token_timestamp":<i32>::from(1),
+        };
+        let options = FindOptions::builder().projection(projection).build();
+
+        let data_collcetion: Collection<Document> =
+            db_instance.collection(DbHandlerMongoDB::COLLECTION_NAME_USER_LIST);
+        let query_execute_result = data_collcetion.find(filter, options).await;
+
+        if query_execute_result.is_err() {
+            return Result::Err(query_execute_result.unwrap_err().to_string());
+        }
+
+        let mut cursor = query_execute_result.unwrap();
+
+        if let Some(data_doc) = cursor.next().await {
+            if data_doc.is_err() {
+                return Err(data_doc.unwrap_err().to_string());
+            }
+
+            let inner_doc: Document = data_doc.unwrap();
+            let stored_name = inner_doc.get_str("user_name");
+            if stored_name.is_err() {
+                return Err(stored_name.unwrap_err().to_string());
+            }
+
+            if stored_name.unwrap().eq(user_name) {
+                let stored_reset_token_value = inner_doc.get_str("password_reset_token_value");
+                let stored_reset_token_timestamp =
+                    inner_doc.get_i64("password_reset_token_timestamp");
+
+                if stored_reset_token_timestamp.is_err() || stored_reset_token_value.is_err() {
+                    return Err("unable to retrive reset settings".to_string());
+                }
+                if stored_reset_token_value.unwrap().ne(reset_token) {
+                    return Err("token missmatch".to_string());
+                }
+                let timestamp_now = async_session::chrono::Utc::now();
+                if timestamp_now.timestamp() > stored_reset_token_timestamp.unwrap() {
+                    return Err("token expired".to_string());
+                }
+
+                let inner_update_doc = doc! {
+                "password_reset_token_value": "",
+                "password_reset_token_timestamp": "",
+                "password_hash":new_password.expose_secret()
+                };
+
+                let filter2 = doc! {
+                    "user_name":user_name
+                };
+                //otherwise we get "update document must have first key starting with '$"
+                let update_doc = doc! {"$set": inner_update_doc};
+
+                let update_result = data_collcetion.update_one(filter2, update_doc, None).await;
+                if update_result.is_err() {
+                    let update_err = &update_result.unwrap_err();
+                    warn!(target:"app::FinanceOverView","{}",update_err);
+                    return Err("Error resetting value".to_string());
+                }
+                let unwrapped_result = update_result.unwrap();
+
+                debug!(target:"app::FinanceOverView","count of updated objects: {}",unwrapped_result.modified_count);
+
+                return Ok(true);
+            }
+
+            return Err("can not  value".to_string());
+        }
+        return Err("unable to reset value".to_string());
+    }
+}
+
+#[async_trait]
+impl DBUserFunctions for DbHandlerSqlite {
+    async fn check_user_exsists_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<bool, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection.query_row(
+            "SELECT COUNT(*) FROM UserList WHERE user_name = ?1",
+            [user_name],
+            |row| row.get::<_, i64>(0),
+        );
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+
+        return Ok(query_result.unwrap() > 0);
+    }
+
+    async fn insert_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<Uuid, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let new_user_uuid = Uuid::new();
+        let insert_result = connection.execute(
+            "INSERT INTO UserList (user_id, user_name, password_hash) VALUES (?1, ?2, ?3)",
+            params![
+                new_user_uuid.to_string(),
+                &some_credentials.username,
+                some_credentials.password_hash.expose_secret()
+            ],
+        );
+        if insert_result.is_err() {
+            let insert_err = insert_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err.to_string());
+        }
+
+        return Ok(new_user_uuid);
+    }
+
+    async fn update_user_password(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<bool, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let update_result = connection.execute(
+            "UPDATE UserList SET password_hash = ?1 WHERE user_name = ?2",
+            params![
+                some_credentials.password_hash.expose_secret(),
+                &some_credentials.username
+            ],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok(true);
+    }
+
+    async fn get_stored_credentials_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<StoredCredentials, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection
+            .query_row(
+                "SELECT user_id, password_hash FROM UserList WHERE user_name = ?1",
+                [user_name],
+                |row| {
+                    Ok((
+                        DbHandlerSqlite::get_uuid_from_row(row, "user_id"),
+                        row.get::<_, String>("password_hash")?,
+                    ))
+                },
+            )
+            .optional();
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("found 0 entries".to_string());
+        }
+        let (some_uuid_parse_result, stored_password_hash) = stored_option.unwrap();
+        if some_uuid_parse_result.is_err() {
+            return Err("Could not parse UUID".to_string());
+        }
+
+        let some_cred = StoredCredentials {
+            user_id: some_uuid_parse_result.unwrap(),
+            password_hash: Secret::<String>::new(stored_password_hash),
+        };
+
+        return Ok(some_cred);
+    }
+
+    async fn update_user_email(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        new_email: &String,
+    ) -> Result<String, String> {
+        let query_username = self
+            .check_user_exsists_by_name(conncetion_settings, user_name)
+            .await;
+
+        if query_username.is_err() {
+            return Err(format!(
+                "Unable to verify username: {}",
+                query_username.unwrap_err()
+            ));
+        }
+
+        if !query_username.unwrap() {
+            return Err(format!("username does not exists: {}", user_name));
+        }
+
+        //generate a random hash for email validation
+        let salt = argon2::password_hash::SaltString::generate(&mut rand::thread_rng());
+        let current_time = Utc::now();
+
+        let mail_validation_token_result =
+            Argon2::default().hash_password(&current_time.to_string().as_bytes(), &salt);
+        if mail_validation_token_result.is_err() {
+            return Err(format!(
+                "Unable to generate email check token: {}",
+                mail_validation_token_result.unwrap_err()
+            ));
+        }
+        let mail_validation_token = mail_validation_token_result.unwrap().to_string();
+
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let update_result = connection.execute(
+            "UPDATE UserList SET user_email = ?1, mail_validated = 0, mail_validation_token = ?2
+            WHERE user_name = ?3",
+            params![new_email, &mail_validation_token, user_name],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!("Error updating email address: {}", update_err));
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok(mail_validation_token);
+    }
+
+    async fn check_email_verfification_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<EmailVerificationStatus, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection
+            .query_row(
+                "SELECT user_email, mail_validated FROM UserList WHERE user_name = ?1",
+                [user_name],
+                |row| Ok((row.get::<_, Option<String>>(0)?, row.get::<_, bool>(1)?)),
+            )
+            .optional();
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Ok(EmailVerificationStatus::NotVerified);
+        }
+
+        let (stored_email, stored_verfication_flag) = stored_option.unwrap();
+        if stored_email.unwrap_or_default().is_empty() {
+            return Ok(EmailVerificationStatus::NotGiven);
+        }
+        if stored_verfication_flag {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        return Ok(EmailVerificationStatus::NotVerified);
+    }
+
+    async fn verify_email_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        email_validation_string: &Secret<String>,
+    ) -> Result<EmailVerificationStatus, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection
+            .query_row(
+                "SELECT user_email, mail_validated, mail_validation_token FROM UserList
+                WHERE user_name = ?1",
+                [user_name],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, bool>(1)?,
+                        row.get::<_, Option<String>>(2)?,
+                    ))
+                },
+            )
+            .optional();
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Ok(EmailVerificationStatus::NotVerified);
+        }
+
+        let (stored_email, stored_verfication_flag, stored_verfication_token) =
+            stored_option.unwrap();
+        if stored_email.is_none() {
+            return Err("no E-Mail given".to_string());
+        }
+        if stored_verfication_flag {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        if !stored_verfication_token
+            .unwrap_or_default()
+            .eq(email_validation_string.expose_secret())
+        {
+            return Err("provided E-Mail Validation token not matching stored token".to_string());
+        }
+
+        let update_result = connection.execute(
+            "UPDATE UserList SET mail_validated = 1 WHERE user_name = ?1",
+            [user_name],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!(
+                "Error updating email validated token: {}",
+                update_err
+            ));
+        }
+        let updated_count = update_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","count of updated objects during user email verfication: {}",updated_count);
+
+        if updated_count == 1 {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        return Err("number of updated records not 1".to_string());
+    }
+
+    async fn get_user_general_data_by_user_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<GenerallUserData, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection
+            .query_row(
+                "SELECT first_name, last_name FROM UserList WHERE user_name = ?1",
+                [user_name],
+                |row| {
+                    Ok(GenerallUserData {
+                        first_name: row.get::<_, Option<String>>(0)?.unwrap_or_default(),
+                        last_name: row.get::<_, Option<String>>(1)?.unwrap_or_default(),
+                    })
+                },
+            )
+            .optional();
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("found 0 entries".to_string());
+        }
+
+        return Ok(stored_option.unwrap());
+    }
+
+    async fn update_general_user_data_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        general_user_data: &GenerallUserData,
+    ) -> Result<String, String> {
+        let query_username = self
+            .check_user_exsists_by_name(conncetion_settings, user_name)
+            .await;
+
+        if query_username.is_err() {
+            return Err(format!(
+                "Unable to verify username: {}",
+                query_username.unwrap_err()
+            ));
+        }
+
+        if !query_username.unwrap() {
+            return Err(format!("username does not exists: {}", user_name));
+        }
+
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let update_result = connection.execute(
+            "UPDATE UserList SET first_name = ?1, last_name = ?2 WHERE user_name = ?3",
+            params![
+                &general_user_data.first_name,
+                &general_user_data.last_name,
+                user_name
+            ],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!("Error updating user data: {}", update_err));
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok("updated".to_string());
+    }
+
+    async fn update_user_reset_secret(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reset_secret_hash: &Secret<String>,
+    ) -> Result<bool, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let update_result = connection.execute(
+            "UPDATE UserList SET reset_secret_hash = ?1 WHERE user_id = ?2",
+            params![reset_secret_hash.expose_secret(), user_id.to_string()],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err.to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok(true);
+    }
+
+    async fn generate_passwort_reset_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_secret: &Secret<String>,
+        passwort_reset_time_limit_minutes: &u16,
+    ) -> Result<PasswordResetTokenRequestResult, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let connection = connection_result.unwrap();
+
+        let query_result = connection
+            .query_row(
+                "SELECT user_email, reset_secret_hash FROM UserList WHERE user_name = ?1",
+                [user_name],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<String>>(1)?,
+                    ))
+                },
+            )
+            .optional();
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("unable to generate token".to_string());
+        }
+        let (stored_email, stored_reset_secret) = stored_option.unwrap();
+        if stored_reset_secret.is_none() {
+            return Err("error generating token".to_string());
+        }
+        let transformed_stored_serect = Secret::<String>::new(stored_reset_secret.unwrap());
+        let verify_serect_result = verify_password_hash(&transformed_stored_serect, &reset_secret);
+        if verify_serect_result.is_err() {
+            return Err("error in token generation".to_string());
+        }
+
+        let stored_user_email = stored_email.unwrap_or_default();
+        let email_validation_result = validate_email_format(&stored_user_email);
+        if email_validation_result.is_err() {
+            return Err(email_validation_result.unwrap_err().to_string());
+        }
+        if !email_validation_result.unwrap() {
+            return Err("no valid e-mail address for operation".to_string());
+        }
+
+        let reset_token_value = Uuid::new().to_string();
+        let reset_token_timestamp =
+            Utc::now() + Duration::minutes(passwort_reset_time_limit_minutes.clone() as i64);
+
+        let update_result = connection.execute(
+            "UPDATE UserList SET password_reset_token_value = ?1, password_reset_token_timestamp = ?2
+            WHERE user_name = ?3",
+            params![
+                &reset_token_value,
+                reset_token_timestamp.timestamp(),
+                user_name
+            ],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err("Error validating reset token".to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        let return_value = PasswordResetTokenRequestResult {
+            reset_token: reset_token_value,
+            expires_at: reset_token_timestamp,
+            user_email: stored_user_email,
+        };
+
+        return Ok(return_value);
+    }
+
+    async fn change_password_with_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_token: &String,
+        new_password: &Secret<String>,
+    ) -> Result<bool, String> {
+        let connection_result = DbHandlerSqlite::open_connection(conncetion_settings);
+        if connection_result.is_err() {
+            let connection_err = connection_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",connection_err);
+            return Err(connection_err);
+        }
+        let mut connection = connection_result.unwrap();
+
+        // reading and clearing the token in one transaction, so a token can only be used once
+        let transaction_result =
+            connection.transaction_with_behavior(TransactionBehavior::Immediate);
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                transaction_result.unwrap_err()
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let query_result = transaction
+            .query_row(
+                "SELECT password_reset_token_value, password_reset_token_timestamp FROM UserList
+                WHERE user_name = ?1",
+                [user_name],
+                |row| {
+                    Ok((
+                        row.get::<_, Option<String>>(0)?,
+                        row.get::<_, Option<i64>>(1)?,
+                    ))
+                },
+            )
+            .optional();
+        if query_result.is_err() {
+            return Err(query_result.unwrap_err().to_string());
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("unable to reset value".to_string());
+        }
+        let (stored_reset_token_value, stored_reset_token_timestamp) = stored_option.unwrap();
+        if stored_reset_token_value.is_none() || stored_reset_token_timestamp.is_none() {
+            return Err("unable to retrive reset settings".to_string());
+        }
+        if stored_reset_token_value.unwrap().ne(reset_token) {
+            return Err("token missmatch".to_string());
+        }
+        if Utc::now().timestamp() > stored_reset_token_timestamp.unwrap() {
+            return Err("token expired".to_string());
+        }
+
+        let update_result = transaction.execute(
+            "UPDATE UserList SET password_reset_token_value = NULL, password_reset_token_timestamp = NULL,
+            password_hash = ?1 WHERE user_name = ?2",
+            params![new_password.expose_secret(), user_name],
+        );
+        if update_result.is_err() {
+            let update_err = update_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err("Error resetting value".to_string());
+        }
+
+        let commit_result = transaction.commit();
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                commit_result.unwrap_err()
+            ));
+        }
+
+        return Ok(true);
+    }
+}
+
+#[async_trait]
+impl DBUserFunctions for DbHandlerPostgres {
+    async fn check_user_exsists_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<bool, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_one(
+                "SELECT COUNT(*) FROM UserList WHERE user_name = $1",
+                &[user_name],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+
+        return Ok(query_result.unwrap().get::<_, i64>(0) > 0);
+    }
+
+    async fn insert_user(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<Uuid, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let new_user_uuid = Uuid::new();
+        let insert_result = client
+            .execute(
+                "INSERT INTO UserList (user_id, user_name, password_hash) VALUES ($1, $2, $3)",
+                &[
+                    &new_user_uuid.to_uuid_1(),
+                    &some_credentials.username,
+                    some_credentials.password_hash.expose_secret(),
+                ],
+            )
+            .await;
+        if insert_result.is_err() {
+            let insert_err = DbHandlerPostgres::format_error(&insert_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",insert_err);
+            return Err(insert_err);
+        }
+
+        return Ok(new_user_uuid);
+    }
+
+    async fn update_user_password(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        some_credentials: &UserCredentialsHashed,
+    ) -> Result<bool, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let update_result = client
+            .execute(
+                "UPDATE UserList SET password_hash = $1 WHERE user_name = $2",
+                &[
+                    some_credentials.password_hash.expose_secret(),
+                    &some_credentials.username,
+                ],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err);
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok(true);
+    }
+
+    async fn get_stored_credentials_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<StoredCredentials, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_opt(
+                "SELECT user_id, password_hash FROM UserList WHERE user_name = $1",
+                &[user_name],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("found 0 entries".to_string());
+        }
+        let stored_row = stored_option.unwrap();
+        let some_uuid_parse_result = DbHandlerPostgres::get_uuid_from_row(&stored_row, "user_id");
+        if some_uuid_parse_result.is_err() {
+            return Err("Could not parse UUID".to_string());
+        }
+        let stored_password_hash = stored_row.try_get::<_, String>("password_hash");
+        if stored_password_hash.is_err() {
+            return Err(DbHandlerPostgres::format_error(
+                &stored_password_hash.unwrap_err(),
+            ));
+        }
+
+        let some_cred = StoredCredentials {
+            user_id: some_uuid_parse_result.unwrap(),
+            password_hash: Secret::<String>::new(stored_password_hash.unwrap()),
+        };
+
+        return Ok(some_cred);
+    }
+
+    async fn update_user_email(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        new_email: &String,
+    ) -> Result<String, String> {
+        let query_username = self
+            .check_user_exsists_by_name(conncetion_settings, user_name)
+            .await;
+
+        if query_username.is_err() {
+            return Err(format!(
+                "Unable to verify username: {}",
+                query_username.unwrap_err()
+            ));
+        }
+
+        if !query_username.unwrap() {
+            return Err(format!("username does not exists: {}", user_name));
+        }
+
+        //generate a random hash for email validation
+        let salt = argon2::password_hash::SaltString::generate(&mut rand::thread_rng());
+        let current_time = Utc::now();
+
+        let mail_validation_token_result =
+            Argon2::default().hash_password(&current_time.to_string().as_bytes(), &salt);
+        if mail_validation_token_result.is_err() {
+            return Err(format!(
+                "Unable to generate email check token: {}",
+                mail_validation_token_result.unwrap_err()
+            ));
+        }
+        let mail_validation_token = mail_validation_token_result.unwrap().to_string();
+
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let update_result = client
+            .execute(
+                "UPDATE UserList SET user_email = $1, mail_validated = FALSE, mail_validation_token = $2
+                WHERE user_name = $3",
+                &[new_email, &mail_validation_token, user_name],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!("Error updating email address: {}", update_err));
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok(mail_validation_token);
+    }
+
+    async fn check_email_verfification_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<EmailVerificationStatus, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_opt(
+                "SELECT user_email, mail_validated FROM UserList WHERE user_name = $1",
+                &[user_name],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Ok(EmailVerificationStatus::NotVerified);
+        }
+
+        let stored_row = stored_option.unwrap();
+        let stored_email = stored_row.get::<_, Option<String>>("user_email");
+        if stored_email.unwrap_or_default().is_empty() {
+            return Ok(EmailVerificationStatus::NotGiven);
+        }
+        if stored_row.get::<_, bool>("mail_validated") {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        return Ok(EmailVerificationStatus::NotVerified);
+    }
+
+    async fn verify_email_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        email_validation_string: &Secret<String>,
+    ) -> Result<EmailVerificationStatus, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_opt(
+                "SELECT user_email, mail_validated, mail_validation_token FROM UserList
+                WHERE user_name = $1",
+                &[user_name],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Ok(EmailVerificationStatus::NotVerified);
+        }
+
+        let stored_row = stored_option.unwrap();
+        if stored_row.get::<_, Option<String>>("user_email").is_none() {
+            return Err("no E-Mail given".to_string());
+        }
+        if stored_row.get::<_, bool>("mail_validated") {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        if !stored_row
+            .get::<_, Option<String>>("mail_validation_token")
+            .unwrap_or_default()
+            .eq(email_validation_string.expose_secret())
+        {
+            return Err("provided E-Mail Validation token not matching stored token".to_string());
+        }
+
+        let update_result = client
+            .execute(
+                "UPDATE UserList SET mail_validated = TRUE WHERE user_name = $1",
+                &[user_name],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!(
+                "Error updating email validated token: {}",
+                update_err
+            ));
+        }
+        let updated_count = update_result.unwrap();
+
+        debug!(target:"app::FinanceOverView","count of updated objects during user email verfication: {}",updated_count);
+
+        if updated_count == 1 {
+            return Ok(EmailVerificationStatus::Verified);
+        }
+        return Err("number of updated records not 1".to_string());
+    }
+
+    async fn get_user_general_data_by_user_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+    ) -> Result<GenerallUserData, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_opt(
+                "SELECT first_name, last_name FROM UserList WHERE user_name = $1",
+                &[user_name],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("found 0 entries".to_string());
+        }
+
+        let stored_row = stored_option.unwrap();
+        return Ok(GenerallUserData {
+            first_name: stored_row
+                .get::<_, Option<String>>("first_name")
+                .unwrap_or_default(),
+            last_name: stored_row
+                .get::<_, Option<String>>("last_name")
+                .unwrap_or_default(),
+        });
+    }
+
+    async fn update_general_user_data_by_name(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        general_user_data: &GenerallUserData,
+    ) -> Result<String, String> {
+        let query_username = self
+            .check_user_exsists_by_name(conncetion_settings, user_name)
+            .await;
+
+        if query_username.is_err() {
+            return Err(format!(
+                "Unable to verify username: {}",
+                query_username.unwrap_err()
+            ));
+        }
+
+        if !query_username.unwrap() {
+            return Err(format!("username does not exists: {}", user_name));
+        }
+
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let update_result = client
+            .execute(
+                "UPDATE UserList SET first_name = $1, last_name = $2 WHERE user_name = $3",
+                &[
+                    &general_user_data.first_name,
+                    &general_user_data.last_name,
+                    user_name,
+                ],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(format!("Error updating user data: {}", update_err));
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok("updated".to_string());
+    }
+
+    async fn update_user_reset_secret(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_id: &Uuid,
+        reset_secret_hash: &Secret<String>,
+    ) -> Result<bool, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let update_result = client
+            .execute(
+                "UPDATE UserList SET reset_secret_hash = $1 WHERE user_id = $2",
+                &[reset_secret_hash.expose_secret(), &user_id.to_uuid_1()],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err(update_err);
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        return Ok(true);
+    }
+
+    async fn generate_passwort_reset_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_secret: &Secret<String>,
+        passwort_reset_time_limit_minutes: &u16,
+    ) -> Result<PasswordResetTokenRequestResult, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let client = client_result.unwrap();
+
+        let query_result = client
+            .query_opt(
+                "SELECT user_email, reset_secret_hash FROM UserList WHERE user_name = $1",
+                &[user_name],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("unable to generate token".to_string());
+        }
+        let stored_row = stored_option.unwrap();
+        let stored_reset_secret = stored_row.get::<_, Option<String>>("reset_secret_hash");
+        if stored_reset_secret.is_none() {
+            return Err("error generating token".to_string());
+        }
+        let transformed_stored_serect = Secret::<String>::new(stored_reset_secret.unwrap());
+        let verify_serect_result = verify_password_hash(&transformed_stored_serect, &reset_secret);
+        if verify_serect_result.is_err() {
+            return Err("error in token generation".to_string());
+        }
+
+        let stored_user_email = stored_row
+            .get::<_, Option<String>>("user_email")
+            .unwrap_or_default();
+        let email_validation_result = validate_email_format(&stored_user_email);
+        if email_validation_result.is_err() {
+            return Err(email_validation_result.unwrap_err().to_string());
+        }
+        if !email_validation_result.unwrap() {
+            return Err("no valid e-mail address for operation".to_string());
+        }
+
+        let reset_token_value = Uuid::new().to_string();
+        let reset_token_timestamp =
+            Utc::now() + Duration::minutes(passwort_reset_time_limit_minutes.clone() as i64);
+
+        let update_result = client
+            .execute(
+                "UPDATE UserList SET password_reset_token_value = $1, password_reset_token_timestamp = $2
+                WHERE user_name = $3",
+                &[&reset_token_value, &reset_token_timestamp, user_name],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err("Error validating reset token".to_string());
+        }
+
+        debug!(target:"app::FinanceOverView","count of updated objects: {}",update_result.unwrap());
+
+        let return_value = PasswordResetTokenRequestResult {
+            reset_token: reset_token_value,
+            expires_at: reset_token_timestamp,
+            user_email: stored_user_email,
+        };
+
+        return Ok(return_value);
+    }
+
+    async fn change_password_with_token(
+        &self,
+        conncetion_settings: &DbConnectionSetting,
+        user_name: &String,
+        reset_token: &String,
+        new_password: &Secret<String>,
+    ) -> Result<bool, String> {
+        let client_result = DbHandlerPostgres::open_client(conncetion_settings).await;
+        if client_result.is_err() {
+            let client_err = client_result.unwrap_err();
+            warn!(target:"app::FinanceOverView","{}",client_err);
+            return Err(client_err);
+        }
+        let mut client = client_result.unwrap();
+
+        // reading and clearing the token in one transaction, so a token can only be used once
+        let transaction_result = client.transaction().await;
+        if transaction_result.is_err() {
+            return Err(format!(
+                "problem starting transaction: {}",
+                DbHandlerPostgres::format_error(&transaction_result.err().unwrap())
+            ));
+        }
+        let transaction = transaction_result.unwrap();
+
+        let query_result = transaction
+            .query_opt(
+                "SELECT password_reset_token_value, password_reset_token_timestamp FROM UserList
+                WHERE user_name = $1 FOR UPDATE",
+                &[user_name],
+            )
+            .await;
+        if query_result.is_err() {
+            return Err(DbHandlerPostgres::format_error(&query_result.unwrap_err()));
+        }
+        let stored_option = query_result.unwrap();
+        if stored_option.is_none() {
+            return Err("unable to reset value".to_string());
+        }
+        let stored_row = stored_option.unwrap();
+        let stored_reset_token_value =
+            stored_row.get::<_, Option<String>>("password_reset_token_value");
+        let stored_reset_token_timestamp =
+            stored_row.get::<_, Option<DateTime<Utc>>>("password_reset_token_timestamp");
+        if stored_reset_token_value.is_none() || stored_reset_token_timestamp.is_none() {
+            return Err("unable to retrive reset settings".to_string());
+        }
+        if stored_reset_token_value.unwrap().ne(reset_token) {
+            return Err("token missmatch".to_string());
+        }
+        if Utc::now() > stored_reset_token_timestamp.unwrap() {
+            return Err("token expired".to_string());
+        }
+
+        let update_result = transaction
+            .execute(
+                "UPDATE UserList SET password_reset_token_value = NULL, password_reset_token_timestamp = NULL,
+                password_hash = $1 WHERE user_name = $2",
+                &[new_password.expose_secret(), user_name],
+            )
+            .await;
+        if update_result.is_err() {
+            let update_err = DbHandlerPostgres::format_error(&update_result.unwrap_err());
+            warn!(target:"app::FinanceOverView","{}",update_err);
+            return Err("Error resetting value".to_string());
+        }
+
+        let commit_result = transaction.commit().await;
+        if commit_result.is_err() {
+            return Err(format!(
+                "Problem closing transaction: {}",
+                DbHandlerPostgres::format_error(&commit_result.unwrap_err())
+            ));
+        }
+
+        return Ok(true);
+    }
+}
@@ -3,11 +3,13 @@ use secrecy::Secret;
 
 use crate::{
     ajax_handle::SimpleAjaxRequestResult,
-    database_handler_mongodb::{DbConnectionSetting, DbHandlerMongoDB, EmailVerificationStatus},
+    database_handler::DbHandler,
+    database_handler_mongodb::{DbConnectionSetting, EmailVerificationStatus},
     datatypes::GenerallUserData,
     frontend_functions::save_general_userdata,
     session_data_handle::{SessionData, SessionDataResult},
     setting_struct::SettingStruct,
+    user_database::DBUserFunctions,
 };
 
 use async_session::{
@@ -17,11 +19,13 @@ use async_session::{
 
 pub(crate) async fn validate_user_email(
     db_connection: &DbConnectionSetting,
+    db_connector: &dyn DBUserFunctions,
     user_name: &String,
     email_secret: &Secret<String>,
 ) -> Result<EmailVerificationStatus, String> {
-    let validate_result =
-        DbHandlerMongoDB::verify_email_by_name(&db_connection, user_name, email_secret).await;
+    let validate_result = db_connector
+        .verify_email_by_name(&db_connection, user_name, email_secret)
+        .await;
     if validate_result.is_err() {
         return Err(format!(
             "Error during verfiy_email_by_name: {}",
@@ -96,7 +100,9 @@ pub async fn do_update_general_user_data(
         let ajax_return_result: String;
         let username: String = session.get("user_name").unwrap();
 
-        let update_result_async = save_general_userdata(&db_connection, &username, &input);
+        let db_handler = DbHandler::new(&db_connection);
+        let update_result_async =
+            save_general_userdata(&db_connection, &db_handler, &username, &input);
         let update_result = update_result_async.await;
 
         if update_result.is_err() {
@@ -124,4 +130,4 @@ pub async fn do_update_general_user_data(
 
         (headers, return_value)
     }
-}
\ No newline at end of file
+}